@@ -0,0 +1,132 @@
+//! Content-addressed cache of uploaded segments
+//!
+//! Maps `(provider index, segment sha256)` to the content identifier the
+//! provider returned. A segment whose hash is already cached for a provider
+//! is neither recompressed nor uploaded to it again. With
+//! `DecentralizedBackupConfig.cache_path` set the cache is persisted as JSON
+//! so it survives operator restarts.
+//!
+//! Entries are keyed by the provider's position in the config (`provider`
+//! first, then `additional_providers`); reordering providers invalidates the
+//! cache for the moved ones, which only costs a re-upload.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SegmentCache {
+    entries: HashMap<(usize, String), String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    provider: usize,
+    sha256: String,
+    cid: String,
+}
+
+impl SegmentCache {
+    /// Whether `provider` already holds the segment with this hash
+    pub fn contains(&self, provider: usize, sha256: &str) -> bool {
+        self.entries.contains_key(&(provider, sha256.to_string()))
+    }
+
+    /// Content identifier of a cached segment
+    pub fn cid(&self, provider: usize, sha256: &str) -> Option<&str> {
+        self.entries
+            .get(&(provider, sha256.to_string()))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, provider: usize, sha256: String, cid: String) {
+        self.entries.insert((provider, sha256), cid);
+    }
+
+    /// Forget a segment, returning its content identifier
+    pub fn remove(&mut self, provider: usize, sha256: &str) -> Option<String> {
+        self.entries.remove(&(provider, sha256.to_string()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Load a persisted cache; a missing file yields an empty cache.
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        let entries: Vec<CacheEntry> = serde_json::from_slice(&data)
+            .with_context(|| format!("Invalid segment cache {}", path.display()))?;
+
+        Ok(Self {
+            entries: entries
+                .into_iter()
+                .map(|e| ((e.provider, e.sha256), e.cid))
+                .collect(),
+        })
+    }
+
+    /// Persist the cache, replacing the file atomically.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut entries: Vec<CacheEntry> = self
+            .entries
+            .iter()
+            .map(|((provider, sha256), cid)| CacheEntry {
+                provider: *provider,
+                sha256: sha256.clone(),
+                cid: cid.clone(),
+            })
+            .collect();
+        entries.sort_by(|a, b| (a.provider, &a.sha256).cmp(&(b.provider, &b.sha256)));
+
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&entries)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("segments.json");
+
+        let mut cache = SegmentCache::default();
+        cache.insert(0, "abc".to_string(), "s3-key".to_string());
+        cache.insert(1, "abc".to_string(), "ar-tx".to_string());
+        cache.save(&path).unwrap();
+
+        let loaded = SegmentCache::load(&path).unwrap();
+        assert_eq!(loaded, cache);
+        assert_eq!(loaded.cid(1, "abc"), Some("ar-tx"));
+        assert!(!loaded.contains(2, "abc"));
+    }
+
+    #[test]
+    fn test_missing_file_is_empty_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SegmentCache::load(&dir.path().join("absent.json")).unwrap();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("segments.json");
+        std::fs::write(&path, b"not json").unwrap();
+        assert!(SegmentCache::load(&path).is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Provider construction with credentials from Kubernetes Secrets
+//!
+//! `StorageProvider` configs only name their credentials, e.g. the Arweave
+//! `wallet_secret` or a pinning service's `api_key_secret`. A reference is
+//! `<secret>` or `<secret>/<key>`; without a key the Secret must hold exactly
+//! one entry. Secrets are read from the namespace the backup runs in.
+
+use super::providers::arweave::ArweaveProvider;
+use super::providers::filecoin::FilecoinProvider;
+use super::providers::ipfs::{IPFSProvider, PinningConfig};
+use super::providers::s3::S3Provider;
+use super::providers::{RetryPolicy, StorageProviderTrait};
+use super::{DecentralizedBackupConfig, PinningService, PinningServiceType, StorageProvider};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use ed25519_dalek::SigningKey;
+use k8s_openapi::api::core::v1::Secret;
+use kube::{Api, Client};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Source of secret material, keyed by Secret name
+#[async_trait]
+pub trait SecretSource: Send + Sync {
+    /// Data of the named Secret, or `None` if it does not exist
+    async fn get(&self, name: &str) -> Result<Option<BTreeMap<String, Vec<u8>>>>;
+}
+
+/// Secrets of a single namespace
+pub struct KubeSecretSource {
+    api: Api<Secret>,
+}
+
+impl KubeSecretSource {
+    pub fn new(client: Client, namespace: &str) -> Self {
+        Self {
+            api: Api::namespaced(client, namespace),
+        }
+    }
+}
+
+#[async_trait]
+impl SecretSource for KubeSecretSource {
+    async fn get(&self, name: &str) -> Result<Option<BTreeMap<String, Vec<u8>>>> {
+        let secret = self
+            .api
+            .get_opt(name)
+            .await
+            .with_context(|| format!("Failed to read Secret {name}"))?;
+        Ok(secret.map(|s| {
+            s.data
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(key, value)| (key, value.0))
+                .collect()
+        }))
+    }
+}
+
+/// Resolve a `<secret>[/<key>]` reference to its value.
+pub async fn resolve_secret(source: &dyn SecretSource, reference: &str) -> Result<Vec<u8>> {
+    let (name, key) = match reference.split_once('/') {
+        Some((name, key)) => (name, Some(key)),
+        None => (reference, None),
+    };
+    let mut data = source
+        .get(name)
+        .await?
+        .ok_or_else(|| anyhow!("Secret {name} not found"))?;
+
+    match key {
+        Some(key) => data
+            .remove(key)
+            .ok_or_else(|| anyhow!("Secret {name} has no key {key}")),
+        None if data.len() == 1 => Ok(data.into_values().next().unwrap_or_default()),
+        None => Err(anyhow!(
+            "Secret {name} has {} keys ({}); reference one as {name}/<key>",
+            data.len(),
+            data.keys().cloned().collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Pin endpoint of a pinning service
+fn pinning_service_url(service_type: &PinningServiceType) -> &'static str {
+    match service_type {
+        PinningServiceType::Pinata => "https://api.pinata.cloud/psa/pins",
+        PinningServiceType::Web3Storage => "https://api.web3.storage/pins",
+        PinningServiceType::Infura => "https://ipfs.infura.io:5001/api/v0/pin/add",
+    }
+}
+
+/// Pinning service config with its API key loaded
+pub async fn resolve_pinning(
+    service: &PinningService,
+    source: &dyn SecretSource,
+) -> Result<PinningConfig> {
+    let api_key = resolve_secret(source, &service.api_key_secret).await?;
+    Ok(PinningConfig {
+        service_url: pinning_service_url(&service.service_type).to_string(),
+        api_key: String::from_utf8(api_key)
+            .context("Pinning service API key is not valid UTF-8")?
+            .trim()
+            .to_string(),
+    })
+}
+
+/// Arweave wallet JWK loaded from its Secret
+pub async fn resolve_arweave_wallet(
+    wallet_secret: &str,
+    source: &dyn SecretSource,
+) -> Result<serde_json::Value> {
+    let jwk = resolve_secret(source, wallet_secret).await?;
+    serde_json::from_slice(&jwk)
+        .with_context(|| format!("Arweave wallet in {wallet_secret} is not a JWK JSON document"))
+}
+
+/// Ed25519 key signing Arweave bundle items, from a 32-byte seed
+pub async fn resolve_bundle_key(reference: &str, source: &dyn SecretSource) -> Result<SigningKey> {
+    let seed: [u8; 32] = resolve_secret(source, reference)
+        .await?
+        .try_into()
+        .map_err(|seed: Vec<u8>| {
+            anyhow!(
+                "Bundle key in {reference} is {} bytes; expected a 32-byte Ed25519 seed",
+                seed.len()
+            )
+        })?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Build a provider retrying with `retry`, loading the credentials it
+/// references.
+pub async fn build_provider(
+    config: &StorageProvider,
+    retry: RetryPolicy,
+    source: &dyn SecretSource,
+) -> Result<Arc<dyn StorageProviderTrait>> {
+    Ok(match config {
+        StorageProvider::Arweave {
+            wallet_secret,
+            gateway,
+            bundle_key_secret,
+            ..
+        } => {
+            let wallet = resolve_arweave_wallet(wallet_secret, source).await?;
+            let mut provider = ArweaveProvider::new(gateway.clone(), wallet)
+                .await?
+                .with_retry(retry);
+            if let Some(reference) = bundle_key_secret {
+                provider =
+                    provider.with_bundle_signer(resolve_bundle_key(reference, source).await?);
+            }
+            Arc::new(provider)
+        }
+        StorageProvider::IPFS {
+            api_url,
+            pinning_service,
+            gateways,
+        } => {
+            let pinning = match pinning_service {
+                Some(service) => Some(resolve_pinning(service, source).await?),
+                None => None,
+            };
+            Arc::new(
+                IPFSProvider::new(api_url.clone(), pinning)
+                    .with_gateways(gateways.clone())
+                    .with_retry(retry),
+            )
+        }
+        StorageProvider::Filecoin {
+            lotus_api,
+            wallet_address,
+            deal_params,
+        } => Arc::new(
+            FilecoinProvider::new(lotus_api.clone(), wallet_address.clone())
+                .with_deal_params(deal_params.clone())
+                .with_retry(retry),
+        ),
+        StorageProvider::S3 {
+            bucket,
+            prefix,
+            region,
+            endpoint,
+            force_path_style,
+            sse,
+        } => Arc::new(
+            S3Provider::new(
+                bucket.clone(),
+                prefix.clone(),
+                region.clone(),
+                endpoint.clone(),
+                *force_path_style,
+                sse.clone(),
+            )
+            .await
+            .with_retry(retry),
+        ),
+    })
+}
+
+impl StorageProvider {
+    /// Instantiate the configured provider with the default retry policy,
+    /// reading its credentials from Secrets in `namespace`.
+    pub async fn build(
+        &self,
+        client: Client,
+        namespace: &str,
+    ) -> Result<Arc<dyn StorageProviderTrait>> {
+        build_provider(
+            self,
+            RetryPolicy::default(),
+            &KubeSecretSource::new(client, namespace),
+        )
+        .await
+    }
+}
+
+/// Build `provider` followed by `additional_providers`, in scheduler order.
+pub async fn build_providers(
+    config: &DecentralizedBackupConfig,
+    source: &dyn SecretSource,
+) -> Result<Vec<Arc<dyn StorageProviderTrait>>> {
+    let retry = RetryPolicy::from(&config.retry);
+    let mut providers = Vec::with_capacity(1 + config.additional_providers.len());
+    for provider in std::iter::once(&config.provider).chain(&config.additional_providers) {
+        providers.push(build_provider(provider, retry, source).await?);
+    }
+    Ok(providers)
+}
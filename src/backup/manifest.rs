@@ -0,0 +1,308 @@
+//! Backup manifests
+//!
+//! After each backup run the scheduler uploads a small JSON manifest next to
+//! the segments it pushed. A manifest records the ledger range, the segments
+//! with their sha256, when the run finished and whether segments were
+//! gzip-compressed, so a restore can find out what is available without
+//! walking the archive.
+//!
+//! Manifests are named `backup-<start>-<end>.manifest.json` and found through
+//! [`StorageProviderTrait::list`].
+
+use super::providers::{StorageProviderTrait, UploadMetadata};
+use super::scheduler::ArchiveSegment;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+/// Current manifest schema version
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Filename prefix of manifest objects
+pub const MANIFEST_PREFIX: &str = "backup-";
+
+/// Filename suffix identifying a manifest object
+pub const MANIFEST_SUFFIX: &str = ".manifest.json";
+
+/// Compression applied to the segments of a backup
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestCompression {
+    None,
+    Gzip,
+}
+
+/// One uploaded archive segment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestSegment {
+    pub filename: String,
+    pub sha256: String,
+    pub ledger: u64,
+    pub segment_type: String,
+}
+
+/// Index of what a single backup run uploaded
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Lowest ledger covered by the segments
+    pub ledger_start: u64,
+    /// Highest ledger covered by the segments
+    pub ledger_end: u64,
+    pub compression: ManifestCompression,
+    pub segments: Vec<ManifestSegment>,
+}
+
+impl BackupManifest {
+    /// Build the manifest of a run, or `None` if nothing was uploaded.
+    pub(crate) fn from_segments(
+        segments: &[ArchiveSegment],
+        compression_enabled: bool,
+        created_at: DateTime<Utc>,
+    ) -> Option<Self> {
+        let ledger_start = segments.iter().map(|s| s.ledger).min()?;
+        let ledger_end = segments.iter().map(|s| s.ledger).max()?;
+
+        Some(Self {
+            version: MANIFEST_VERSION,
+            created_at,
+            ledger_start,
+            ledger_end,
+            compression: if compression_enabled {
+                ManifestCompression::Gzip
+            } else {
+                ManifestCompression::None
+            },
+            segments: segments
+                .iter()
+                .map(|s| ManifestSegment {
+                    filename: s.filename.clone(),
+                    sha256: s.hash.clone(),
+                    ledger: s.ledger,
+                    segment_type: s.segment_type.clone(),
+                })
+                .collect(),
+        })
+    }
+
+    /// Object name, e.g. `backup-0000003f-00000bff.manifest.json`
+    pub fn filename(&self) -> String {
+        format!(
+            "{}{:08x}-{:08x}{}",
+            MANIFEST_PREFIX, self.ledger_start, self.ledger_end, MANIFEST_SUFFIX
+        )
+    }
+
+    /// Serialized manifest and the metadata to upload it with
+    pub fn to_upload(&self) -> Result<(Vec<u8>, UploadMetadata)> {
+        let data = serde_json::to_vec_pretty(self).context("Failed to serialize manifest")?;
+        let sha256 = format!("{:x}", Sha256::digest(&data));
+
+        let metadata = UploadMetadata {
+            filename: self.filename(),
+            content_type: "application/json".to_string(),
+            size: data.len(),
+            sha256,
+            tags: vec![
+                ("Type".to_string(), "manifest".to_string()),
+                ("LedgerStart".to_string(), self.ledger_start.to_string()),
+                ("LedgerEnd".to_string(), self.ledger_end.to_string()),
+            ],
+        };
+        Ok((data, metadata))
+    }
+}
+
+/// Whether a stored filename is a manifest
+pub fn is_manifest(filename: &str) -> bool {
+    filename.starts_with(MANIFEST_PREFIX) && filename.ends_with(MANIFEST_SUFFIX)
+}
+
+/// List the backups available on `provider`, oldest ledger range first.
+///
+/// Manifests that cannot be downloaded or parsed are skipped with a warning.
+pub async fn list_backups(provider: &dyn StorageProviderTrait) -> Result<Vec<BackupManifest>> {
+    let mut manifests = Vec::new();
+    for entry in provider
+        .list(MANIFEST_PREFIX)
+        .await?
+        .into_iter()
+        .filter(|e| is_manifest(&e.filename))
+    {
+        let parsed = match provider.download(&entry.cid).await {
+            Ok(data) => serde_json::from_slice::<BackupManifest>(&data)
+                .with_context(|| format!("Invalid manifest {}", entry.filename)),
+            Err(e) => Err(e),
+        };
+        match parsed {
+            Ok(manifest) => manifests.push(manifest),
+            Err(e) => warn!("Skipping backup manifest {}: {:#}", entry.cid, e),
+        }
+    }
+
+    manifests.sort_by_key(|m| (m.ledger_end, m.created_at));
+    Ok(manifests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::providers::BackupEntry;
+    use async_trait::async_trait;
+    use std::collections::BTreeMap;
+    use tokio::sync::RwLock;
+
+    /// Provider that stores objects in memory under their filename
+    #[derive(Default)]
+    struct MemoryProvider {
+        objects: RwLock<BTreeMap<String, Vec<u8>>>,
+    }
+
+    #[async_trait]
+    impl StorageProviderTrait for MemoryProvider {
+        async fn upload(&self, data: Vec<u8>, metadata: UploadMetadata) -> Result<String> {
+            self.objects
+                .write()
+                .await
+                .insert(metadata.filename.clone(), data);
+            Ok(metadata.filename)
+        }
+
+        async fn exists(&self, _content_hash: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn verify(&self, _cid: &str, _expected_hash: &str) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn list(&self, prefix: &str) -> Result<Vec<BackupEntry>> {
+            Ok(self
+                .objects
+                .read()
+                .await
+                .iter()
+                .filter(|(name, _)| name.starts_with(prefix))
+                .map(|(name, data)| BackupEntry {
+                    cid: name.clone(),
+                    filename: name.clone(),
+                    size: Some(data.len() as u64),
+                })
+                .collect())
+        }
+
+        async fn delete(&self, cid: &str) -> Result<()> {
+            self.objects.write().await.remove(cid);
+            Ok(())
+        }
+
+        async fn download(&self, cid: &str) -> Result<Vec<u8>> {
+            self.objects
+                .read()
+                .await
+                .get(cid)
+                .cloned()
+                .context("not found")
+        }
+    }
+
+    fn segment(ledger: u64) -> ArchiveSegment {
+        ArchiveSegment {
+            filename: format!("history-{ledger:08x}.xdr"),
+            path: String::new(),
+            hash: format!("hash-{ledger}"),
+            ledger,
+            segment_type: "history".to_string(),
+        }
+    }
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(ts)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_manifest_schema() {
+        let manifest = BackupManifest::from_segments(
+            &[segment(127), segment(63)],
+            true,
+            at("2026-01-01T00:00:00Z"),
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["createdAt"], "2026-01-01T00:00:00Z");
+        assert_eq!(json["ledgerStart"], 63);
+        assert_eq!(json["ledgerEnd"], 127);
+        assert_eq!(json["compression"], "gzip");
+        assert_eq!(json["segments"][0]["filename"], "history-0000007f.xdr");
+        assert_eq!(json["segments"][0]["sha256"], "hash-127");
+        assert_eq!(json["segments"][0]["segmentType"], "history");
+        assert_eq!(
+            manifest.filename(),
+            "backup-0000003f-0000007f.manifest.json"
+        );
+    }
+
+    #[test]
+    fn test_no_segments_no_manifest() {
+        assert!(BackupManifest::from_segments(&[], false, Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_upload_metadata_hashes_content() {
+        let manifest = BackupManifest::from_segments(&[segment(63)], false, Utc::now()).unwrap();
+        let (data, metadata) = manifest.to_upload().unwrap();
+        assert_eq!(metadata.sha256, format!("{:x}", Sha256::digest(&data)));
+        assert_eq!(metadata.size, data.len());
+        assert!(is_manifest(&metadata.filename));
+
+        let restored: BackupManifest = serde_json::from_slice(&data).unwrap();
+        assert_eq!(restored, manifest);
+    }
+
+    #[tokio::test]
+    async fn test_list_backups_returns_manifests_in_ledger_order() {
+        let provider = MemoryProvider::default();
+        for (ledgers, ts) in [
+            (vec![191, 255], "2026-01-02T00:00:00Z"),
+            (vec![63, 127], "2026-01-01T00:00:00Z"),
+        ] {
+            let segments: Vec<_> = ledgers.into_iter().map(segment).collect();
+            let manifest = BackupManifest::from_segments(&segments, true, at(ts)).unwrap();
+            let (data, metadata) = manifest.to_upload().unwrap();
+            provider.upload(data, metadata).await.unwrap();
+        }
+        provider
+            .upload(
+                b"segment".to_vec(),
+                UploadMetadata {
+                    filename: "history-0000003f.xdr".to_string(),
+                    content_type: "application/octet-stream".to_string(),
+                    size: 7,
+                    sha256: "hash-63".to_string(),
+                    tags: vec![],
+                },
+            )
+            .await
+            .unwrap();
+        provider.objects.write().await.insert(
+            "backup-corrupt.manifest.json".to_string(),
+            b"not json".to_vec(),
+        );
+
+        let backups = list_backups(&provider).await.unwrap();
+        let ranges: Vec<_> = backups
+            .iter()
+            .map(|m| (m.ledger_start, m.ledger_end))
+            .collect();
+        assert_eq!(ranges, vec![(63, 127), (191, 255)]);
+    }
+}
@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+pub mod cache;
+pub mod credentials;
+pub mod manifest;
 pub mod providers;
+pub mod restore_check;
+pub mod retention;
 pub mod scheduler;
 pub mod secret_rotation;
 pub mod verification;
@@ -11,6 +15,10 @@ pub mod verification;
 #[cfg(test)]
 mod scheduler_test;
 
+pub use cache::SegmentCache;
+pub use credentials::{KubeSecretSource, SecretSource};
+pub use manifest::{list_backups, BackupManifest};
+pub use restore_check::{verify_latest_backup, RestoreCheckStatus};
 pub use secret_rotation::{
     RotationEvent, RotationStatus, SecretRotationConfig, SecretRotationScheduler,
 };
@@ -26,7 +34,14 @@ pub struct DecentralizedBackupConfig {
     pub enabled: bool,
     /// Storage provider configuration
     pub provider: StorageProvider,
-    /// Backup schedule in cron format (default: every 6 hours)
+    /// Extra destinations that receive every segment uploaded to `provider`
+    ///
+    /// Each segment is read and compressed once, then pushed to all of them.
+    #[serde(default)]
+    pub additional_providers: Vec<StorageProvider>,
+    /// Backup schedule as a six- or seven-field cron expression with a leading
+    /// seconds field, e.g. `0 0 */6 * * *` (default: every 6 hours).
+    /// Five-field Unix cron is not accepted.
     #[serde(default = "default_schedule")]
     pub schedule: String,
     /// Maximum number of concurrent uploads
@@ -35,12 +50,61 @@ pub struct DecentralizedBackupConfig {
     /// Enable compression before upload
     #[serde(default = "default_compression")]
     pub compression_enabled: bool,
+    /// gzip level from 0 (store only) to 9 (smallest, slowest) (default: 6)
+    #[serde(default = "default_compression_level")]
+    pub compression_level: u32,
     /// Retention policy (optional)
     pub retention: Option<RetentionPolicy>,
+    /// Local file persisting the uploaded-segment cache across restarts
+    /// (default: in memory only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_path: Option<String>,
+    /// Schedule of the restorability check, which downloads the newest backup
+    /// and checks its segments against the manifest; same format as
+    /// `schedule` (default: disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify_schedule: Option<String>,
+    /// Retries of provider calls failing with a transport error, a 5xx or a
+    /// 429 (default: 3 attempts, backing off from 1s up to 30s)
+    #[serde(default)]
+    pub retry: RetryConfig,
+}
+
+impl DecentralizedBackupConfig {
+    /// Check that `schedule` and `verify_schedule` are seconds-first cron
+    /// expressions.
+    pub fn validate(&self) -> Result<(), String> {
+        validate_cron("schedule", &self.schedule)?;
+        if let Some(verify_schedule) = &self.verify_schedule {
+            validate_cron("verifySchedule", verify_schedule)?;
+        }
+        if self.retry.max_attempts == 0 {
+            return Err("retry.maxAttempts must be at least 1".to_string());
+        }
+        if self.compression_level > 9 {
+            return Err(format!(
+                "compressionLevel {} is out of range; expected 0-9",
+                self.compression_level
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn validate_cron(field: &str, expr: &str) -> Result<(), String> {
+    let fields = expr.split_whitespace().count();
+    if !(6..=7).contains(&fields) {
+        return Err(format!(
+            "{field} '{expr}' has {fields} field(s); expected 6 or 7 (second minute hour day-of-month month day-of-week [year]), e.g. \"0 0 */6 * * *\""
+        ));
+    }
+    <cron::Schedule as std::str::FromStr>::from_str(expr)
+        .map(|_| ())
+        .map_err(|e| format!("{field} '{expr}' is not a valid cron expression: {e}"))
 }
 
 fn default_schedule() -> String {
-    "0 */6 * * *".to_string() // Every 6 hours
+    "0 0 */6 * * *".to_string() // Every 6 hours
 }
 
 fn default_concurrency() -> usize {
@@ -51,6 +115,47 @@ fn default_compression() -> bool {
     true
 }
 
+fn default_compression_level() -> u32 {
+    6
+}
+
+/// Retries of storage provider calls
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryConfig {
+    /// Total attempts, including the first one
+    #[serde(default = "default_retry_attempts")]
+    pub max_attempts: u32,
+    /// Seconds before the first retry; doubled for every further retry
+    #[serde(default = "default_initial_backoff_secs")]
+    pub initial_backoff_secs: u64,
+    /// Upper bound in seconds for the delay between attempts
+    #[serde(default = "default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_attempts(),
+            initial_backoff_secs: default_initial_backoff_secs(),
+            max_backoff_secs: default_max_backoff_secs(),
+        }
+    }
+}
+
+fn default_retry_attempts() -> u32 {
+    3
+}
+
+fn default_initial_backoff_secs() -> u64 {
+    1
+}
+
+fn default_max_backoff_secs() -> u64 {
+    30
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum StorageProvider {
@@ -63,12 +168,20 @@ pub enum StorageProvider {
         /// Tags to add to transactions
         #[serde(default)]
         tags: Vec<(String, String)>,
+        /// Secret reference to a 32-byte Ed25519 seed that signs ANS-104
+        /// data items, enabling bundled uploads of many small files
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bundle_key_secret: Option<String>,
     },
     IPFS {
         /// IPFS API endpoint
         api_url: String,
         /// Pinning service (optional)
         pinning_service: Option<PinningService>,
+        /// HTTP gateways tried in order, e.g. `https://ipfs.io`, when content
+        /// cannot be read back from `api_url`
+        #[serde(default)]
+        gateways: Vec<String>,
     },
     Filecoin {
         /// Lotus API endpoint
@@ -78,6 +191,42 @@ pub enum StorageProvider {
         /// Storage deal parameters
         deal_params: FilecoinDealParams,
     },
+    S3 {
+        /// Bucket name
+        bucket: String,
+        /// Key prefix, e.g. "stellar/history/"
+        #[serde(default)]
+        prefix: String,
+        /// AWS region (default: from the environment)
+        region: Option<String>,
+        /// Endpoint of an S3-compatible store such as MinIO (default: AWS S3)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+        /// Address objects as `{endpoint}/{bucket}/{key}` instead of
+        /// `{bucket}.{endpoint}/{key}`. Required by MinIO and most
+        /// self-hosted S3-compatible stores.
+        #[serde(default)]
+        force_path_style: bool,
+        /// Server-side encryption applied to every uploaded object
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sse: Option<S3ServerSideEncryption>,
+    },
+}
+
+/// S3 server-side encryption mode
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "algorithm")]
+pub enum S3ServerSideEncryption {
+    /// S3-managed keys (SSE-S3)
+    #[serde(rename = "AES256")]
+    Aes256,
+    /// AWS KMS keys (SSE-KMS). Without `kms_key_id` the bucket's default
+    /// `aws/s3` key is used.
+    #[serde(rename = "aws:kms")]
+    AwsKms {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        kms_key_id: Option<String>,
+    },
 }
 
 fn default_arweave_gateway() -> String {
@@ -109,6 +258,14 @@ pub struct FilecoinDealParams {
     /// Verified deal
     #[serde(default)]
     pub verified: bool,
+    /// Start a new deal for content whose last deal ends within this many
+    /// epochs (default: 20160, about 7 days)
+    #[serde(default = "default_renew_before_epochs")]
+    pub renew_before_epochs: u64,
+}
+
+fn default_renew_before_epochs() -> u64 {
+    20_160
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
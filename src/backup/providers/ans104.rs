@@ -0,0 +1,431 @@
+//! ANS-104 bundled data items
+//!
+//! Packs many small files into a single Arweave transaction. Each file is a
+//! signed data item with its own id; gateways index bundled items so every
+//! file stays addressable as if it had been uploaded on its own.
+//!
+//! Items are signed with Ed25519 (signature type 2). The signed message is the
+//! ANS-104 deep hash of the item's fields; the item id is the SHA-256 of the
+//! signature.
+
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use base64::Engine;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use sha2::{Digest, Sha256, Sha384};
+
+/// ANS-104 signature type of Ed25519 data items
+pub const SIGNATURE_TYPE_ED25519: u16 = 2;
+
+const SIGNATURE_LEN: usize = 64;
+const OWNER_LEN: usize = 32;
+/// Width of the little-endian integers in the bundle header
+const HEADER_INT_LEN: usize = 32;
+
+/// A signed ANS-104 data item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataItem {
+    signature: [u8; SIGNATURE_LEN],
+    owner: [u8; OWNER_LEN],
+    target: Option<[u8; 32]>,
+    anchor: Option<[u8; 32]>,
+    tags: Vec<(String, String)>,
+    data: Vec<u8>,
+}
+
+impl DataItem {
+    /// Sign `data` with `tags` as a new item without target or anchor.
+    pub fn new_signed(key: &SigningKey, tags: Vec<(String, String)>, data: Vec<u8>) -> Self {
+        let owner = key.verifying_key().to_bytes();
+        let message = signature_message(&owner, None, None, &encode_tags(&tags), &data);
+        Self {
+            signature: key.sign(&message).to_bytes(),
+            owner,
+            target: None,
+            anchor: None,
+            tags,
+            data,
+        }
+    }
+
+    /// Raw item id, the SHA-256 of the signature
+    pub fn id(&self) -> [u8; 32] {
+        Sha256::digest(self.signature).into()
+    }
+
+    /// Item id as used by gateways (base64url, unpadded)
+    pub fn id_string(&self) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(self.id())
+    }
+
+    pub fn tags(&self) -> &[(String, String)] {
+        &self.tags
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Check the signature against the owner key.
+    pub fn verify(&self) -> Result<()> {
+        let key = VerifyingKey::from_bytes(&self.owner).context("Invalid data item owner")?;
+        let message = signature_message(
+            &self.owner,
+            self.target.as_ref(),
+            self.anchor.as_ref(),
+            &encode_tags(&self.tags),
+            &self.data,
+        );
+        key.verify(&message, &Signature::from_bytes(&self.signature))
+            .context("Data item signature does not match")
+    }
+
+    /// Binary data item layout
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let tags = encode_tags(&self.tags);
+        let mut out = Vec::with_capacity(2 + SIGNATURE_LEN + OWNER_LEN + 82 + tags.len());
+        out.extend_from_slice(&SIGNATURE_TYPE_ED25519.to_le_bytes());
+        out.extend_from_slice(&self.signature);
+        out.extend_from_slice(&self.owner);
+        for optional in [&self.target, &self.anchor] {
+            match optional {
+                Some(value) => {
+                    out.push(1);
+                    out.extend_from_slice(value);
+                }
+                None => out.push(0),
+            }
+        }
+        out.extend_from_slice(&(self.tags.len() as u64).to_le_bytes());
+        out.extend_from_slice(&(tags.len() as u64).to_le_bytes());
+        out.extend_from_slice(&tags);
+        out.extend_from_slice(&self.data);
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader(bytes);
+        let signature_type = u16::from_le_bytes(reader.array()?);
+        ensure!(
+            signature_type == SIGNATURE_TYPE_ED25519,
+            "Unsupported data item signature type {signature_type}"
+        );
+        let signature = reader.array()?;
+        let owner = reader.array()?;
+        let target = reader.optional()?;
+        let anchor = reader.optional()?;
+        let tag_count = u64::from_le_bytes(reader.array()?);
+        let tag_bytes = usize::try_from(u64::from_le_bytes(reader.array()?))?;
+        let tags = decode_tags(reader.take(tag_bytes)?)?;
+        ensure!(
+            tags.len() as u64 == tag_count,
+            "Data item declares {tag_count} tags but holds {}",
+            tags.len()
+        );
+
+        Ok(Self {
+            signature,
+            owner,
+            target,
+            anchor,
+            tags,
+            data: reader.0.to_vec(),
+        })
+    }
+}
+
+/// A set of data items sent as one transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bundle {
+    items: Vec<DataItem>,
+}
+
+impl Bundle {
+    pub fn new(items: Vec<DataItem>) -> Self {
+        Self { items }
+    }
+
+    pub fn items(&self) -> &[DataItem] {
+        &self.items
+    }
+
+    /// Item with the given gateway id
+    pub fn get(&self, id: &str) -> Option<&DataItem> {
+        self.items.iter().find(|item| item.id_string() == id)
+    }
+
+    /// Binary bundle: item count, then `(size, id)` per item, then the items
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let items: Vec<Vec<u8>> = self.items.iter().map(DataItem::to_bytes).collect();
+        let mut out = Vec::new();
+        out.extend_from_slice(&header_int(self.items.len() as u64));
+        for (item, bytes) in self.items.iter().zip(&items) {
+            out.extend_from_slice(&header_int(bytes.len() as u64));
+            out.extend_from_slice(&item.id());
+        }
+        for bytes in items {
+            out.extend_from_slice(&bytes);
+        }
+        out
+    }
+
+    /// Parse a bundle, checking every item's id and signature.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader(bytes);
+        let count = read_header_int(&mut reader)?;
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let size = usize::try_from(read_header_int(&mut reader)?)?;
+            let id: [u8; 32] = reader.array()?;
+            entries.push((size, id));
+        }
+
+        let mut items = Vec::with_capacity(entries.len());
+        for (size, id) in entries {
+            let item = DataItem::from_bytes(reader.take(size)?)?;
+            ensure!(item.id() == id, "Bundle header id does not match its item");
+            item.verify()?;
+            items.push(item);
+        }
+        ensure!(reader.0.is_empty(), "Trailing bytes after bundle items");
+        Ok(Self { items })
+    }
+}
+
+fn header_int(value: u64) -> [u8; HEADER_INT_LEN] {
+    let mut out = [0u8; HEADER_INT_LEN];
+    out[..8].copy_from_slice(&value.to_le_bytes());
+    out
+}
+
+fn read_header_int(reader: &mut Reader<'_>) -> Result<u64> {
+    let bytes: [u8; HEADER_INT_LEN] = reader.array()?;
+    ensure!(
+        bytes[8..].iter().all(|b| *b == 0),
+        "Bundle header value out of range"
+    );
+    Ok(u64::from_le_bytes(bytes[..8].try_into()?))
+}
+
+/// Message signed for a data item: the deep hash of its fields
+fn signature_message(
+    owner: &[u8],
+    target: Option<&[u8; 32]>,
+    anchor: Option<&[u8; 32]>,
+    tags: &[u8],
+    data: &[u8],
+) -> Vec<u8> {
+    let signature_type = SIGNATURE_TYPE_ED25519.to_string();
+    deep_hash(&[
+        b"dataitem".as_slice(),
+        b"1".as_slice(),
+        signature_type.as_bytes(),
+        owner,
+        target.map_or(&[][..], |t| &t[..]),
+        anchor.map_or(&[][..], |a| &a[..]),
+        tags,
+        data,
+    ])
+}
+
+/// Arweave deep hash of a list of blobs
+fn deep_hash(chunks: &[&[u8]]) -> Vec<u8> {
+    let mut acc = Sha384::digest(format!("list{}", chunks.len())).to_vec();
+    for chunk in chunks {
+        let mut tagged = Sha384::digest(format!("blob{}", chunk.len())).to_vec();
+        tagged.extend_from_slice(&Sha384::digest(chunk));
+        let chunk_hash = Sha384::digest(&tagged);
+
+        let mut next = acc;
+        next.extend_from_slice(&chunk_hash);
+        acc = Sha384::digest(&next).to_vec();
+    }
+    acc
+}
+
+/// Avro encoding of the tags (an array of `{name: bytes, value: bytes}`);
+/// no tags encode as no bytes at all.
+pub fn encode_tags(tags: &[(String, String)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    if tags.is_empty() {
+        return out;
+    }
+    write_long(&mut out, tags.len() as i64);
+    for (name, value) in tags {
+        for field in [name, value] {
+            write_long(&mut out, field.len() as i64);
+            out.extend_from_slice(field.as_bytes());
+        }
+    }
+    write_long(&mut out, 0);
+    out
+}
+
+fn decode_tags(bytes: &[u8]) -> Result<Vec<(String, String)>> {
+    let mut reader = Reader(bytes);
+    let mut tags = Vec::new();
+    while !reader.0.is_empty() {
+        let mut count = read_long(&mut reader)?;
+        if count == 0 {
+            break;
+        }
+        if count < 0 {
+            // Negative block counts are followed by the block's byte size
+            read_long(&mut reader)?;
+            count = -count;
+        }
+        for _ in 0..count {
+            let name = read_string(&mut reader)?;
+            let value = read_string(&mut reader)?;
+            tags.push((name, value));
+        }
+    }
+    Ok(tags)
+}
+
+fn write_long(out: &mut Vec<u8>, value: i64) {
+    let mut zigzag = ((value << 1) ^ (value >> 63)) as u64;
+    loop {
+        let byte = (zigzag & 0x7f) as u8;
+        zigzag >>= 7;
+        if zigzag == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_long(reader: &mut Reader<'_>) -> Result<i64> {
+    let mut zigzag = 0u64;
+    for shift in (0..64).step_by(7) {
+        let [byte] = reader.array()?;
+        zigzag |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((zigzag >> 1) as i64 ^ -((zigzag & 1) as i64));
+        }
+    }
+    bail!("Avro long is too long")
+}
+
+fn read_string(reader: &mut Reader<'_>) -> Result<String> {
+    let len = usize::try_from(read_long(reader)?).map_err(|_| anyhow!("Negative tag length"))?;
+    String::from_utf8(reader.take(len)?.to_vec()).context("Tag is not valid UTF-8")
+}
+
+/// Cursor over a byte slice
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        ensure!(self.0.len() >= len, "Unexpected end of data item");
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into()?)
+    }
+
+    fn optional(&mut self) -> Result<Option<[u8; 32]>> {
+        match self.array::<1>()? {
+            [0] => Ok(None),
+            [1] => Ok(Some(self.array()?)),
+            [flag] => bail!("Invalid presence flag {flag}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn item(name: &str, data: &[u8]) -> DataItem {
+        DataItem::new_signed(
+            &key(),
+            vec![("File-Name".to_string(), name.to_string())],
+            data.to_vec(),
+        )
+    }
+
+    #[test]
+    fn test_tag_encoding() {
+        assert_eq!(
+            encode_tags(&[("a".to_string(), "b".to_string())]),
+            vec![2, 2, b'a', 2, b'b', 0]
+        );
+        assert!(encode_tags(&[]).is_empty());
+
+        let tags = vec![
+            (
+                "Content-Type".to_string(),
+                "application/octet-stream".to_string(),
+            ),
+            ("File-Name".to_string(), "x".repeat(200)),
+        ];
+        assert_eq!(decode_tags(&encode_tags(&tags)).unwrap(), tags);
+    }
+
+    #[test]
+    fn test_data_item_roundtrip_and_signature() {
+        let item = item("history-0000003f.xdr", b"ledger 63");
+        item.verify().unwrap();
+
+        let parsed = DataItem::from_bytes(&item.to_bytes()).unwrap();
+        assert_eq!(parsed, item);
+        assert_eq!(parsed.id_string().len(), 43);
+
+        let mut tampered = item.clone();
+        tampered.data = b"ledger 64".to_vec();
+        assert!(tampered.verify().is_err());
+    }
+
+    #[test]
+    fn test_bundle_layout() {
+        let items = vec![item("a.xdr", b"first"), item("b.xdr", b"second")];
+        let bytes = Bundle::new(items.clone()).to_bytes();
+
+        assert_eq!(bytes[0], 2);
+        assert!(bytes[1..32].iter().all(|b| *b == 0));
+        let first_size = items[0].to_bytes().len();
+        assert_eq!(bytes[32..40], (first_size as u64).to_le_bytes());
+        assert_eq!(bytes[64..96], items[0].id());
+        // Header: count + two (size, id) pairs
+        assert_eq!(bytes[160..160 + first_size], items[0].to_bytes()[..]);
+    }
+
+    #[test]
+    fn test_items_are_retrievable_from_bundle() {
+        let items: Vec<DataItem> = (0..5)
+            .map(|i| {
+                item(
+                    &format!("history-{i:08x}.xdr"),
+                    format!("ledger {i}").as_bytes(),
+                )
+            })
+            .collect();
+        let bundle = Bundle::from_bytes(&Bundle::new(items.clone()).to_bytes()).unwrap();
+
+        assert_eq!(bundle.items().len(), 5);
+        for original in &items {
+            let found = bundle.get(&original.id_string()).unwrap();
+            assert_eq!(found.data(), original.data());
+            assert_eq!(found.tags(), original.tags());
+        }
+        assert!(bundle.get("missing").is_none());
+    }
+
+    #[test]
+    fn test_corrupt_bundle_is_rejected() {
+        let mut bytes = Bundle::new(vec![item("a.xdr", b"first")]).to_bytes();
+        *bytes.last_mut().unwrap() ^= 0xff;
+        assert!(Bundle::from_bytes(&bytes).is_err());
+
+        let bytes = Bundle::new(vec![item("a.xdr", b"first")]).to_bytes();
+        assert!(Bundle::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
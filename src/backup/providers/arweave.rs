@@ -1,16 +1,35 @@
-use super::{StorageProviderTrait, UploadMetadata};
+use super::ans104::{Bundle, DataItem};
+use super::{with_retry, BackupEntry, RetryPolicy, StorageProviderTrait, UploadMetadata};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use base64::Engine;
+use ed25519_dalek::SigningKey;
 use reqwest::Client;
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
+use tracing::info;
 
 pub struct ArweaveProvider {
     client: Client,
     gateway: String,
-    #[allow(dead_code)]
     wallet_jwk: Value,
+    bundle_signer: Option<SigningKey>,
+    retry: RetryPolicy,
+}
+
+/// Transactions requested per GraphQL page when listing
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Tags every Arweave upload of this operator carries
+fn file_tags(metadata: UploadMetadata) -> Vec<(String, String)> {
+    let mut tags = vec![
+        ("Content-Type".to_string(), metadata.content_type),
+        ("File-Name".to_string(), metadata.filename),
+        ("SHA-256".to_string(), metadata.sha256),
+        ("App-Name".to_string(), "Stellar-Archive-Backup".to_string()),
+    ];
+    tags.extend(metadata.tags);
+    tags
 }
 
 impl ArweaveProvider {
@@ -19,27 +38,53 @@ impl ArweaveProvider {
             client: Client::new(),
             gateway,
             wallet_jwk,
+            bundle_signer: None,
+            retry: RetryPolicy::default(),
         })
     }
-}
 
-#[async_trait]
-impl StorageProviderTrait for ArweaveProvider {
-    async fn upload(&self, data: Vec<u8>, metadata: UploadMetadata) -> Result<String> {
-        // Create Arweave transaction
-        let mut tags = vec![
-            ("Content-Type".to_string(), metadata.content_type.clone()),
-            ("File-Name".to_string(), metadata.filename.clone()),
-            ("SHA-256".to_string(), metadata.sha256.clone()),
+    /// Ed25519 key signing the data items of [`Self::upload_bundle`]
+    pub fn with_bundle_signer(mut self, key: SigningKey) -> Self {
+        self.bundle_signer = Some(key);
+        self
+    }
+
+    /// Upload many small files as one ANS-104 bundle transaction.
+    ///
+    /// Returns the data item id of each file, in order; gateways serve every
+    /// item under its id just like a standalone transaction.
+    pub async fn upload_bundle(
+        &self,
+        files: Vec<(Vec<u8>, UploadMetadata)>,
+    ) -> Result<Vec<String>> {
+        let key = self
+            .bundle_signer
+            .as_ref()
+            .context("Arweave bundling requires a bundle signing key")?;
+        let items: Vec<DataItem> = files
+            .into_iter()
+            .map(|(data, metadata)| DataItem::new_signed(key, file_tags(metadata), data))
+            .collect();
+        let ids = items.iter().map(DataItem::id_string).collect();
+
+        let tags = vec![
+            ("Bundle-Format".to_string(), "binary".to_string()),
+            ("Bundle-Version".to_string(), "2.0.0".to_string()),
             ("App-Name".to_string(), "Stellar-Archive-Backup".to_string()),
         ];
-        tags.extend(metadata.tags);
+        let tx_id = self
+            .submit_transaction(&Bundle::new(items).to_bytes(), &tags)
+            .await?;
+        info!("Submitted Arweave bundle transaction {}", tx_id);
+        Ok(ids)
+    }
 
+    async fn submit_transaction(&self, data: &[u8], tags: &[(String, String)]) -> Result<String> {
         // In production, use arweave-rs or bundlr for actual implementation
         // This is a simplified example
         let b64 = base64::engine::general_purpose::STANDARD;
         let tx_data = json!({
-            "data": b64.encode(&data),
+            "data": b64.encode(data),
             "tags": tags.iter().map(|(k, v)| json!({
                 "name": b64.encode(k),
                 "value": b64.encode(v)
@@ -47,17 +92,52 @@ impl StorageProviderTrait for ArweaveProvider {
         });
 
         // Sign and submit transaction (simplified)
-        let response = self
-            .client
-            .post(format!("{}/tx", self.gateway))
-            .json(&tx_data)
-            .send()
-            .await
-            .context("Failed to submit Arweave transaction")?;
+        let tx_data = &tx_data;
+        with_retry(&self.retry, "Arweave upload", || async move {
+            Ok(self
+                .client
+                .post(format!("{}/tx", self.gateway))
+                .json(tx_data)
+                .send()
+                .await
+                .context("Failed to submit Arweave transaction")?
+                .error_for_status()
+                .context("Arweave gateway rejected transaction")?
+                .text()
+                .await?)
+        })
+        .await
+    }
+
+    /// Addresses owning our uploads: the wallet, the base64url SHA-256 of the
+    /// JWK's RSA modulus, and the bundle signer that owns bundled items
+    fn owner_addresses(&self) -> Result<Vec<String>> {
+        let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let modulus = self.wallet_jwk["n"]
+            .as_str()
+            .context("Arweave wallet JWK has no modulus 'n'")?;
+        let modulus = b64
+            .decode(modulus.trim_end_matches('='))
+            .context("Arweave wallet JWK modulus is not base64url")?;
+        let mut owners = vec![b64.encode(Sha256::digest(modulus))];
+        if let Some(key) = &self.bundle_signer {
+            owners.push(b64.encode(Sha256::digest(key.verifying_key().to_bytes())));
+        }
+        Ok(owners)
+    }
 
-        let tx_id = response.text().await?;
+    /// Override the retry policy used for uploads
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+}
 
-        Ok(tx_id)
+#[async_trait]
+impl StorageProviderTrait for ArweaveProvider {
+    async fn upload(&self, data: Vec<u8>, metadata: UploadMetadata) -> Result<String> {
+        // Create Arweave transaction
+        self.submit_transaction(&data, &file_tags(metadata)).await
     }
 
     async fn exists(&self, content_hash: &str) -> Result<bool> {
@@ -84,6 +164,94 @@ impl StorageProviderTrait for ArweaveProvider {
             .unwrap_or(true))
     }
 
+    async fn list(&self, prefix: &str) -> Result<Vec<BackupEntry>> {
+        // Other wallets can tag their uploads the same way, so only our
+        // own transactions are listed. GraphQL cannot match tag prefixes, so
+        // File-Name is filtered here.
+        let owners = self
+            .owner_addresses()?
+            .iter()
+            .map(|owner| format!("\"{owner}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut entries = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let after = cursor
+                .as_deref()
+                .map(|c| format!(", after: \"{c}\""))
+                .unwrap_or_default();
+            let query = json!({
+                "query": format!(
+                    "{{ transactions(first: {LIST_PAGE_SIZE}{after}, owners: [{owners}], tags: [{{name: \"App-Name\", values: [\"Stellar-Archive-Backup\"]}}]) {{ pageInfo {{ hasNextPage }} edges {{ cursor node {{ id data {{ size }} tags {{ name value }} }} }} }} }}"
+                )
+            });
+
+            let response: Value = self
+                .client
+                .post(format!("{}/graphql", self.gateway))
+                .json(&query)
+                .send()
+                .await
+                .context("Failed to query Arweave transactions")?
+                .error_for_status()
+                .context("Arweave gateway rejected transaction query")?
+                .json()
+                .await?;
+
+            let transactions = &response["data"]["transactions"];
+            let edges = transactions["edges"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            entries.extend(edges.iter().filter_map(|edge| {
+                let node = &edge["node"];
+                let filename = node["tags"]
+                    .as_array()?
+                    .iter()
+                    .find(|tag| tag["name"] == "File-Name")?["value"]
+                    .as_str()?;
+                let cid = node["id"].as_str()?;
+                filename.starts_with(prefix).then(|| BackupEntry {
+                    cid: cid.to_string(),
+                    filename: filename.to_string(),
+                    size: node["data"]["size"].as_str().and_then(|s| s.parse().ok()),
+                })
+            }));
+
+            let has_next = transactions["pageInfo"]["hasNextPage"]
+                .as_bool()
+                .unwrap_or(false);
+            match edges.last().and_then(|edge| edge["cursor"].as_str()) {
+                Some(last) if has_next => cursor = Some(last.to_string()),
+                _ => return Ok(entries),
+            }
+        }
+    }
+
+    fn batches_uploads(&self) -> bool {
+        self.bundle_signer.is_some()
+    }
+
+    async fn upload_batch(&self, files: Vec<(Vec<u8>, UploadMetadata)>) -> Result<Vec<String>> {
+        if self.bundle_signer.is_some() {
+            return self.upload_bundle(files).await;
+        }
+        let mut ids = Vec::with_capacity(files.len());
+        for (data, metadata) in files {
+            ids.push(self.upload(data, metadata).await?);
+        }
+        Ok(ids)
+    }
+
+    fn supports_delete(&self) -> bool {
+        false
+    }
+
+    async fn delete(&self, cid: &str) -> Result<()> {
+        anyhow::bail!("Arweave storage is permanent, transaction {cid} cannot be deleted")
+    }
+
     async fn verify(&self, cid: &str, expected_hash: &str) -> Result<bool> {
         let data = self
             .client
@@ -100,3 +268,196 @@ impl StorageProviderTrait for ArweaveProvider {
         Ok(hash == expected_hash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn file(name: &str, data: &[u8]) -> (Vec<u8>, UploadMetadata) {
+        (
+            data.to_vec(),
+            UploadMetadata {
+                filename: name.to_string(),
+                content_type: "application/octet-stream".to_string(),
+                size: data.len(),
+                sha256: format!("{:x}", Sha256::digest(data)),
+                tags: vec![],
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_upload_bundle_sends_one_transaction() {
+        let gateway = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/tx"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("bundle-tx"))
+            .expect(1)
+            .mount(&gateway)
+            .await;
+
+        let provider = ArweaveProvider::new(gateway.uri(), json!({}))
+            .await
+            .unwrap()
+            .with_bundle_signer(SigningKey::from_bytes(&[1u8; 32]));
+        let ids = provider
+            .upload_bundle(vec![
+                file("history-0000003f.xdr", b"ledger 63"),
+                file("history-0000007f.xdr", b"ledger 127"),
+            ])
+            .await
+            .unwrap();
+
+        let requests = gateway.received_requests().await.unwrap();
+        let tx: Value = serde_json::from_slice(&requests[0].body).unwrap();
+        let b64 = base64::engine::general_purpose::STANDARD;
+        let bundle =
+            Bundle::from_bytes(&b64.decode(tx["data"].as_str().unwrap()).unwrap()).unwrap();
+        assert!(tx["tags"].as_array().unwrap().contains(
+            &json!({"name": b64.encode("Bundle-Format"), "value": b64.encode("binary")})
+        ));
+
+        assert_eq!(ids.len(), 2);
+        let item = bundle.get(&ids[1]).unwrap();
+        assert_eq!(item.data(), b"ledger 127");
+        assert!(item
+            .tags()
+            .contains(&("File-Name".to_string(), "history-0000007f.xdr".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_upload_bundle_requires_signer() {
+        let provider = ArweaveProvider::new("http://localhost:1984".to_string(), json!({}))
+            .await
+            .unwrap();
+        assert!(provider
+            .upload_bundle(vec![file("history-0000003f.xdr", b"ledger 63")])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_owner_and_follows_cursor() {
+        let gateway = MockServer::start().await;
+        let edge = |cursor: &str, id: &str, filename: &str| {
+            json!({
+                "cursor": cursor,
+                "node": {
+                    "id": id,
+                    "data": { "size": "9" },
+                    "tags": [{ "name": "File-Name", "value": filename }]
+                }
+            })
+        };
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .and(body_string_contains("page-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "transactions": {
+                    "pageInfo": { "hasNextPage": false },
+                    "edges": [edge("page-2", "tx-2", "history-0000007f.xdr")]
+                } }
+            })))
+            .with_priority(1)
+            .mount(&gateway)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "transactions": {
+                    "pageInfo": { "hasNextPage": true },
+                    "edges": [
+                        edge("page-0", "tx-0", "bucket-aa.xdr.gz"),
+                        edge("page-1", "tx-1", "history-0000003f.xdr"),
+                    ]
+                } }
+            })))
+            .mount(&gateway)
+            .await;
+
+        let modulus = b"wallet modulus";
+        let b64 = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let provider = ArweaveProvider::new(gateway.uri(), json!({ "n": b64.encode(modulus) }))
+            .await
+            .unwrap();
+        let entries = provider.list("history-").await.unwrap();
+
+        let cids: Vec<&str> = entries.iter().map(|e| e.cid.as_str()).collect();
+        assert_eq!(cids, ["tx-1", "tx-2"]);
+        assert_eq!(entries[0].size, Some(9));
+
+        let owner = b64.encode(Sha256::digest(modulus));
+        let requests = gateway.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        for request in &requests {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            assert!(body["query"]
+                .as_str()
+                .unwrap()
+                .contains(&format!("owners: [\"{owner}\"]")));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_requires_wallet_address() {
+        let provider = ArweaveProvider::new("http://localhost:1984".to_string(), json!({}))
+            .await
+            .unwrap();
+        assert!(provider.list("history-").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delete_is_unsupported() {
+        let provider = ArweaveProvider::new("http://localhost:1984".to_string(), json!({}))
+            .await
+            .unwrap();
+        assert!(!provider.supports_delete());
+        assert!(provider.delete("tx-1").await.is_err());
+    }
+
+    async fn upload_attempts(statuses: &[u16]) -> (Result<String>, usize) {
+        let gateway = MockServer::start().await;
+        for (i, status) in statuses.iter().enumerate() {
+            Mock::given(method("POST"))
+                .and(path("/tx"))
+                .respond_with(ResponseTemplate::new(*status))
+                .up_to_n_times(1)
+                .with_priority(i as u8 + 1)
+                .mount(&gateway)
+                .await;
+        }
+        Mock::given(method("POST"))
+            .and(path("/tx"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("tx-1"))
+            .mount(&gateway)
+            .await;
+
+        let provider = ArweaveProvider::new(gateway.uri(), json!({}))
+            .await
+            .unwrap()
+            .with_retry(RetryPolicy {
+                max_attempts: 3,
+                initial_backoff: std::time::Duration::ZERO,
+                max_backoff: std::time::Duration::ZERO,
+            });
+        let (data, metadata) = file("history-0000003f.xdr", b"ledger 63");
+        let result = provider.upload(data, metadata).await;
+        (result, gateway.received_requests().await.unwrap().len())
+    }
+
+    #[tokio::test]
+    async fn test_upload_retries_server_errors_and_rate_limits() {
+        let (result, attempts) = upload_attempts(&[503, 429]).await;
+        assert_eq!(result.unwrap(), "tx-1");
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_upload_does_not_retry_client_errors() {
+        let (result, attempts) = upload_attempts(&[400]).await;
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+}
@@ -1,13 +1,93 @@
-use super::{StorageProviderTrait, UploadMetadata};
+use super::{
+    with_retry, BackupEntry, DealStatus, RetryPolicy, StorageProviderTrait, UploadMetadata,
+};
+use crate::backup::FilecoinDealParams;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use tracing::{info, warn};
 
 pub struct FilecoinProvider {
     client: Client,
     lotus_api: String,
     wallet_address: String,
+    deal_params: Option<FilecoinDealParams>,
+    retry: RetryPolicy,
+}
+
+/// A storage deal as reported by Lotus
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DealInfo {
+    pub deal_id: u64,
+    /// Root CID of the stored content
+    pub root: String,
+    pub state: String,
+    pub start_epoch: i64,
+    pub duration: i64,
+}
+
+impl DealInfo {
+    /// Epoch the deal stops storing data
+    pub fn end_epoch(&self) -> i64 {
+        self.start_epoch + self.duration
+    }
+
+    /// Whether the deal is (or will be) storing data
+    pub fn is_active(&self) -> bool {
+        !matches!(
+            self.state.as_str(),
+            "StorageDealError"
+                | "StorageDealFailing"
+                | "StorageDealRejecting"
+                | "StorageDealExpired"
+                | "StorageDealSlashed"
+        )
+    }
+
+    fn from_json(deal: &Value) -> Option<Self> {
+        Some(Self {
+            deal_id: deal["DealID"].as_u64()?,
+            root: deal["Root"]["/"].as_str()?.to_string(),
+            state: deal["State"].as_str()?.to_string(),
+            start_epoch: deal["StartEpoch"].as_i64()?,
+            duration: deal["Duration"].as_i64()?,
+        })
+    }
+}
+
+/// Stored content that needs a new deal: every active deal for it ends
+/// within `renew_before` epochs of `head`, or it has no active deal at all
+/// (its deals lapsed, or starting one failed after the import).
+///
+/// Only `stored` roots are considered, so content removed by retention is
+/// left to expire.
+pub fn roots_to_renew(
+    deals: &[DealInfo],
+    stored: &BTreeSet<String>,
+    head: i64,
+    renew_before: u64,
+) -> Vec<String> {
+    let mut last_end: BTreeMap<&str, i64> = BTreeMap::new();
+    for deal in deals.iter().filter(|d| d.is_active()) {
+        let end = deal.end_epoch();
+        last_end
+            .entry(deal.root.as_str())
+            .and_modify(|last| *last = (*last).max(end))
+            .or_insert(end);
+    }
+
+    let window = i64::try_from(renew_before).unwrap_or(i64::MAX);
+    stored
+        .iter()
+        .filter(|root| {
+            last_end
+                .get(root.as_str())
+                .map_or(true, |end| end.saturating_sub(head) <= window)
+        })
+        .cloned()
+        .collect()
 }
 
 impl FilecoinProvider {
@@ -16,8 +96,79 @@ impl FilecoinProvider {
             client: Client::new(),
             lotus_api,
             wallet_address,
+            deal_params: None,
+            retry: RetryPolicy::default(),
         }
     }
+
+    /// Make storage deals for uploads and keep them renewed
+    pub fn with_deal_params(mut self, deal_params: FilecoinDealParams) -> Self {
+        self.deal_params = Some(deal_params);
+        self
+    }
+
+    async fn chain_head(&self) -> Result<i64> {
+        let head: Value = self
+            .client
+            .post(format!("{}/api/v0/chain/head", self.lotus_api))
+            .send()
+            .await
+            .context("Failed to query Filecoin chain head")?
+            .error_for_status()
+            .context("Lotus rejected chain head query")?
+            .json()
+            .await?;
+        head["Height"]
+            .as_i64()
+            .context("Missing height in chain head")
+    }
+
+    async fn list_deals(&self) -> Result<Vec<DealInfo>> {
+        let deals: Value = self
+            .client
+            .post(format!("{}/api/v0/client/list-deals", self.lotus_api))
+            .send()
+            .await
+            .context("Failed to list Filecoin deals")?
+            .error_for_status()
+            .context("Lotus rejected deal listing")?
+            .json()
+            .await?;
+        Ok(deals
+            .as_array()
+            .map(|deals| deals.iter().filter_map(DealInfo::from_json).collect())
+            .unwrap_or_default())
+    }
+
+    async fn start_deal(&self, root: &str, params: &FilecoinDealParams) -> Result<String> {
+        let response: Value = self
+            .client
+            .post(format!("{}/api/v0/client/start-deal", self.lotus_api))
+            .json(&serde_json::json!({
+                "root": root,
+                "wallet": self.wallet_address,
+                "price_per_epoch": params.price_per_epoch,
+                "duration": params.duration,
+                "verified": params.verified,
+            }))
+            .send()
+            .await
+            .context("Failed to start Filecoin deal")?
+            .error_for_status()
+            .context("Lotus rejected storage deal")?
+            .json()
+            .await?;
+        Ok(response["/"]
+            .as_str()
+            .context("Missing proposal CID in deal response")?
+            .to_string())
+    }
+
+    /// Override the retry policy used for uploads
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
 }
 
 #[async_trait]
@@ -30,24 +181,79 @@ impl StorageProviderTrait for FilecoinProvider {
             "filename": metadata.filename,
         });
 
-        let response: Value = self
-            .client
-            .post(format!("{}/api/v0/client/import", self.lotus_api))
-            .json(&import_data)
-            .send()
-            .await
-            .context("Failed to import data to Filecoin")?
-            .json()
-            .await?;
+        let import_data = &import_data;
+        let response: Value = with_retry(&self.retry, "Filecoin import", || async move {
+            Ok(self
+                .client
+                .post(format!("{}/api/v0/client/import", self.lotus_api))
+                .json(import_data)
+                .send()
+                .await
+                .context("Failed to import data to Filecoin")?
+                .error_for_status()
+                .context("Lotus rejected import")?
+                .json()
+                .await?)
+        })
+        .await?;
 
         let cid = response["Root"]["/"]
             .as_str()
             .context("Missing CID in Filecoin response")?
             .to_string();
 
+        // The import is kept if no deal can be made; content without a deal
+        // gets one at the next renewal check
+        if let Some(params) = &self.deal_params {
+            let deal = with_retry(&self.retry, "Filecoin deal", || {
+                self.start_deal(&cid, params)
+            })
+            .await;
+            match deal {
+                Ok(proposal) => info!("Started Filecoin deal {} for {}", proposal, cid),
+                Err(e) => warn!(
+                    "No Filecoin deal for {} yet, retrying at the next renewal check: {:#}",
+                    cid, e
+                ),
+            }
+        }
+
         Ok(cid)
     }
 
+    async fn renew_expiring(&self) -> Result<Option<DealStatus>> {
+        let Some(params) = &self.deal_params else {
+            return Ok(None);
+        };
+        let head = self.chain_head().await?;
+        let deals = self.list_deals().await?;
+        let stored: BTreeSet<String> = self.list("").await?.into_iter().map(|e| e.cid).collect();
+        let expiring = roots_to_renew(&deals, &stored, head, params.renew_before_epochs);
+
+        let mut renewed = Vec::new();
+        for root in &expiring {
+            match self.start_deal(root, params).await {
+                Ok(proposal) => {
+                    info!(
+                        "Renewed Filecoin storage of {} with deal {}",
+                        root, proposal
+                    );
+                    renewed.push(root.clone());
+                }
+                Err(e) => warn!("Failed to renew Filecoin deal for {}: {:#}", root, e),
+            }
+        }
+
+        let active: Vec<&DealInfo> = deals.iter().filter(|d| d.is_active()).collect();
+        Ok(Some(DealStatus {
+            head_epoch: head,
+            active_deals: active.len(),
+            expiring: expiring.len(),
+            renewed,
+            next_expiry_epoch: active.iter().map(|d| d.end_epoch()).min(),
+        }))
+    }
+
     async fn exists(&self, content_hash: &str) -> Result<bool> {
         let response: Value = self
             .client
@@ -61,6 +267,49 @@ impl StorageProviderTrait for FilecoinProvider {
         Ok(response["result"].as_bool().unwrap_or(false))
     }
 
+    async fn list(&self, prefix: &str) -> Result<Vec<BackupEntry>> {
+        let imports: Value = self
+            .client
+            .post(format!("{}/api/v0/client/list-imports", self.lotus_api))
+            .send()
+            .await
+            .context("Failed to list Filecoin imports")?
+            .json()
+            .await?;
+
+        Ok(imports
+            .as_array()
+            .map(|imports| {
+                imports
+                    .iter()
+                    .filter_map(|import| {
+                        let path = import["FilePath"].as_str()?;
+                        let filename = path.rsplit('/').next().unwrap_or(path);
+                        let cid = import["Root"]["/"].as_str()?;
+                        filename.starts_with(prefix).then(|| BackupEntry {
+                            cid: cid.to_string(),
+                            filename: filename.to_string(),
+                            size: None,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn delete(&self, cid: &str) -> Result<()> {
+        // Storage deals run until they expire; only the local import is dropped
+        self.client
+            .post(format!("{}/api/v0/client/remove-import", self.lotus_api))
+            .json(&serde_json::json!({ "cid": cid }))
+            .send()
+            .await
+            .context("Failed to remove Filecoin import")?
+            .error_for_status()
+            .context("Lotus rejected import removal")?;
+        Ok(())
+    }
+
     async fn verify(&self, cid: &str, expected_hash: &str) -> Result<bool> {
         let data = self
             .client
@@ -79,3 +328,208 @@ impl StorageProviderTrait for FilecoinProvider {
         Ok(hash == expected_hash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn deal(deal_id: u64, root: &str, state: &str, start_epoch: i64, duration: i64) -> DealInfo {
+        DealInfo {
+            deal_id,
+            root: root.to_string(),
+            state: state.to_string(),
+            start_epoch,
+            duration,
+        }
+    }
+
+    fn params(renew_before_epochs: u64) -> FilecoinDealParams {
+        FilecoinDealParams {
+            price_per_epoch: "500000000".to_string(),
+            duration: 518400,
+            verified: false,
+            renew_before_epochs,
+        }
+    }
+
+    #[test]
+    fn test_end_epoch_is_start_plus_duration() {
+        let deal = deal(1, "bafy-a", "StorageDealActive", 1_000, 518_400);
+        assert_eq!(deal.end_epoch(), 519_400);
+    }
+
+    fn stored(roots: &[&str]) -> BTreeSet<String> {
+        roots.iter().map(|root| root.to_string()).collect()
+    }
+
+    #[test]
+    fn test_renews_only_within_window() {
+        let deals = [
+            deal(1, "bafy-soon", "StorageDealActive", 0, 10_100),
+            deal(2, "bafy-later", "StorageDealActive", 0, 50_000),
+        ];
+        let roots = stored(&["bafy-soon", "bafy-later"]);
+        // bafy-soon ends 100 epochs after head, bafy-later 40000
+        assert_eq!(
+            roots_to_renew(&deals, &roots, 10_000, 100),
+            vec!["bafy-soon"]
+        );
+        assert!(roots_to_renew(&deals, &roots, 10_000, 99).is_empty());
+    }
+
+    #[test]
+    fn test_renewed_content_is_not_renewed_again() {
+        let deals = [
+            deal(1, "bafy-a", "StorageDealActive", 0, 10_100),
+            deal(2, "bafy-a", "StorageDealProposalAccepted", 10_050, 518_400),
+        ];
+        assert!(roots_to_renew(&deals, &stored(&["bafy-a"]), 10_000, 20_160).is_empty());
+    }
+
+    #[test]
+    fn test_content_without_active_deal_is_renewed() {
+        let deals = [
+            deal(1, "bafy-a", "StorageDealExpired", 0, 5_000),
+            deal(2, "bafy-b", "StorageDealSlashed", 0, 518_400),
+        ];
+        // bafy-c was imported but its deal could not be started
+        assert_eq!(
+            roots_to_renew(
+                &deals,
+                &stored(&["bafy-a", "bafy-b", "bafy-c"]),
+                10_000,
+                20_160
+            ),
+            vec!["bafy-a", "bafy-b", "bafy-c"]
+        );
+    }
+
+    #[test]
+    fn test_content_removed_by_retention_is_not_renewed() {
+        let deals = [deal(
+            1,
+            "bafy-expired-backup",
+            "StorageDealActive",
+            0,
+            10_100,
+        )];
+        assert!(roots_to_renew(&deals, &stored(&[]), 10_000, 20_160).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_renew_expiring_starts_deal_for_expiring_root_only() {
+        let lotus = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/chain/head"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "Height": 100_000
+            })))
+            .mount(&lotus)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/client/list-deals"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {
+                    "DealID": 1,
+                    "Root": {"/": "bafy-expiring"},
+                    "State": "StorageDealActive",
+                    "StartEpoch": 0,
+                    "Duration": 110_000
+                },
+                {
+                    "DealID": 2,
+                    "Root": {"/": "bafy-healthy"},
+                    "State": "StorageDealActive",
+                    "StartEpoch": 50_000,
+                    "Duration": 518_400
+                },
+                {
+                    "DealID": 3,
+                    "Root": {"/": "bafy-retired"},
+                    "State": "StorageDealActive",
+                    "StartEpoch": 0,
+                    "Duration": 105_000
+                }
+            ])))
+            .mount(&lotus)
+            .await;
+        // bafy-retired was removed by retention and is left to expire
+        Mock::given(method("POST"))
+            .and(path("/api/v0/client/list-imports"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"FilePath": "/imports/history-0000003f.xdr", "Root": {"/": "bafy-expiring"}},
+                {"FilePath": "/imports/history-0000007f.xdr", "Root": {"/": "bafy-healthy"}}
+            ])))
+            .mount(&lotus)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/client/start-deal"))
+            .and(body_partial_json(
+                serde_json::json!({"root": "bafy-expiring"}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({"/": "bafy-proposal"})),
+            )
+            .expect(1)
+            .mount(&lotus)
+            .await;
+
+        let provider = FilecoinProvider::new(lotus.uri(), "f1abc".to_string())
+            .with_deal_params(params(20_160));
+        let status = provider.renew_expiring().await.unwrap().unwrap();
+
+        assert_eq!(
+            status,
+            DealStatus {
+                head_epoch: 100_000,
+                active_deals: 3,
+                expiring: 1,
+                renewed: vec!["bafy-expiring".to_string()],
+                next_expiry_epoch: Some(105_000),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_deal_params_no_deal_status() {
+        let provider = FilecoinProvider::new("http://127.0.0.1:9".to_string(), "f1abc".to_string());
+        assert_eq!(provider.renew_expiring().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_upload_keeps_import_when_deal_fails() {
+        let lotus = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/client/import"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"Root": {"/": "bafy-new"}})),
+            )
+            .mount(&lotus)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/client/start-deal"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&lotus)
+            .await;
+
+        let provider = FilecoinProvider::new(lotus.uri(), "f1abc".to_string())
+            .with_deal_params(params(20_160));
+        let metadata = UploadMetadata {
+            filename: "history-0000003f.xdr".to_string(),
+            content_type: "application/octet-stream".to_string(),
+            size: 9,
+            sha256: "abc123".to_string(),
+            tags: vec![],
+        };
+        let cid = provider
+            .upload(b"ledger 63".to_vec(), metadata)
+            .await
+            .unwrap();
+
+        assert_eq!(cid, "bafy-new");
+    }
+}
@@ -1,13 +1,16 @@
-use super::{StorageProviderTrait, UploadMetadata};
+use super::{with_retry, BackupEntry, RetryPolicy, StorageProviderTrait, UploadMetadata};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::Value;
+use tracing::warn;
 
 pub struct IPFSProvider {
     client: Client,
     api_url: String,
     pinning_service: Option<PinningConfig>,
+    gateways: Vec<String>,
+    retry: RetryPolicy,
 }
 
 #[derive(Clone)]
@@ -22,28 +25,48 @@ impl IPFSProvider {
             client: Client::new(),
             api_url,
             pinning_service,
+            gateways: Vec::new(),
+            retry: RetryPolicy::default(),
         }
     }
+
+    /// Gateways to read content from when the API node cannot serve it
+    pub fn with_gateways(mut self, gateways: Vec<String>) -> Self {
+        self.gateways = gateways;
+        self
+    }
+
+    /// Override the retry policy used for uploads
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
 }
 
 #[async_trait]
 impl StorageProviderTrait for IPFSProvider {
     async fn upload(&self, data: Vec<u8>, metadata: UploadMetadata) -> Result<String> {
-        // Upload to IPFS
-        let form = reqwest::multipart::Form::new().part(
-            "file",
-            reqwest::multipart::Part::bytes(data).file_name(metadata.filename.clone()),
-        );
-
-        let response: Value = self
-            .client
-            .post(format!("{}/api/v0/add", self.api_url))
-            .multipart(form)
-            .send()
-            .await
-            .context("Failed to upload to IPFS")?
-            .json()
-            .await?;
+        // Upload to IPFS; the multipart form is consumed per request
+        let (data, metadata) = (&data, &metadata);
+        let response: Value = with_retry(&self.retry, "IPFS upload", || async move {
+            let form = reqwest::multipart::Form::new().part(
+                "file",
+                reqwest::multipart::Part::bytes(data.clone()).file_name(metadata.filename.clone()),
+            );
+            Ok(self
+                .client
+                .post(format!("{}/api/v0/add", self.api_url))
+                .query(&[("pin-name", metadata.filename.as_str())])
+                .multipart(form)
+                .send()
+                .await
+                .context("Failed to upload to IPFS")?
+                .error_for_status()
+                .context("IPFS rejected upload")?
+                .json()
+                .await?)
+        })
+        .await?;
 
         let cid = response["Hash"]
             .as_str()
@@ -52,7 +75,10 @@ impl StorageProviderTrait for IPFSProvider {
 
         // Pin if pinning service configured
         if let Some(ref pinning) = self.pinning_service {
-            self.pin_to_service(&cid, &metadata, pinning).await?;
+            with_retry(&self.retry, "IPFS pinning", || {
+                self.pin_to_service(&cid, metadata, pinning)
+            })
+            .await?;
         }
 
         Ok(cid)
@@ -73,16 +99,71 @@ impl StorageProviderTrait for IPFSProvider {
             .unwrap_or(false))
     }
 
-    async fn verify(&self, cid: &str, expected_hash: &str) -> Result<bool> {
-        // Cat the file and verify hash
-        let data = self
+    async fn list(&self, prefix: &str) -> Result<Vec<BackupEntry>> {
+        // Pins are named after the uploaded file (see `upload`)
+        let pins: Value = self
             .client
-            .post(format!("{}/api/v0/cat?arg={}", self.api_url, cid))
+            .post(format!("{}/api/v0/pin/ls", self.api_url))
+            .query(&[("type", "recursive"), ("names", "true")])
             .send()
-            .await?
-            .bytes()
+            .await
+            .context("Failed to list IPFS pins")?
+            .json()
             .await?;
 
+        Ok(pins["Keys"]
+            .as_object()
+            .map(|keys| {
+                keys.iter()
+                    .filter_map(|(cid, pin)| {
+                        let filename = pin["Name"].as_str()?;
+                        filename.starts_with(prefix).then(|| BackupEntry {
+                            cid: cid.clone(),
+                            filename: filename.to_string(),
+                            size: None,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn delete(&self, cid: &str) -> Result<()> {
+        // Unpin; the node's garbage collector frees the blocks
+        self.client
+            .post(format!("{}/api/v0/pin/rm", self.api_url))
+            .query(&[("arg", cid)])
+            .send()
+            .await
+            .context("Failed to unpin from IPFS")?
+            .error_for_status()
+            .context("IPFS rejected unpin")?;
+        Ok(())
+    }
+
+    async fn download(&self, cid: &str) -> Result<Vec<u8>> {
+        // The API node first, then each gateway in order
+        let mut last_error = match self.cat(cid).await {
+            Ok(data) => return Ok(data),
+            Err(e) => e,
+        };
+        for gateway in &self.gateways {
+            warn!(
+                "Retrieving {} via gateway {}: {:#}",
+                cid, gateway, last_error
+            );
+            match self.gateway_get(gateway, cid).await {
+                Ok(data) => return Ok(data),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error.context(format!("Failed to retrieve {cid} from IPFS")))
+    }
+
+    async fn verify(&self, cid: &str, expected_hash: &str) -> Result<bool> {
+        // Fetch the file and verify hash
+        let data = self.download(cid).await?;
+
         use sha2::Digest;
         let mut hasher = sha2::Sha256::new();
         hasher.update(&data);
@@ -93,6 +174,35 @@ impl StorageProviderTrait for IPFSProvider {
 }
 
 impl IPFSProvider {
+    async fn cat(&self, cid: &str) -> Result<Vec<u8>> {
+        let data = self
+            .client
+            .post(format!("{}/api/v0/cat", self.api_url))
+            .query(&[("arg", cid)])
+            .send()
+            .await
+            .context("Failed to reach IPFS API")?
+            .error_for_status()
+            .context("IPFS API could not serve content")?
+            .bytes()
+            .await?;
+        Ok(data.to_vec())
+    }
+
+    async fn gateway_get(&self, gateway: &str, cid: &str) -> Result<Vec<u8>> {
+        let data = self
+            .client
+            .get(format!("{}/ipfs/{}", gateway.trim_end_matches('/'), cid))
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach IPFS gateway {gateway}"))?
+            .error_for_status()
+            .with_context(|| format!("IPFS gateway {gateway} could not serve content"))?
+            .bytes()
+            .await?;
+        Ok(data.to_vec())
+    }
+
     async fn pin_to_service(
         &self,
         cid: &str,
@@ -119,3 +229,87 @@ impl IPFSProvider {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+    async fn gateway(status: u16, body: &'static [u8]) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/ipfs/{CID}")))
+            .respond_with(ResponseTemplate::new(status).set_body_bytes(body))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// API node that cannot serve any content
+    async fn failing_api() -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/cat"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn test_download_falls_through_failing_gateway() {
+        let api = failing_api().await;
+        let broken = gateway(504, b"").await;
+        let healthy = gateway(200, b"archive bytes").await;
+
+        let provider = IPFSProvider::new(api.uri(), None)
+            .with_gateways(vec![broken.uri(), format!("{}/", healthy.uri())]);
+
+        assert_eq!(provider.download(CID).await.unwrap(), b"archive bytes");
+        assert_eq!(broken.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_download_prefers_api_node() {
+        let api = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v0/cat"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"from node".as_slice()))
+            .mount(&api)
+            .await;
+        let unused = gateway(200, b"from gateway").await;
+
+        let provider = IPFSProvider::new(api.uri(), None).with_gateways(vec![unused.uri()]);
+
+        assert_eq!(provider.download(CID).await.unwrap(), b"from node");
+        assert!(unused.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_download_fails_when_every_gateway_fails() {
+        let api = failing_api().await;
+        let first = gateway(404, b"").await;
+        let second = gateway(502, b"").await;
+
+        let provider =
+            IPFSProvider::new(api.uri(), None).with_gateways(vec![first.uri(), second.uri()]);
+
+        assert!(provider.download(CID).await.is_err());
+        assert_eq!(second.received_requests().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_verify_uses_gateway_fallback() {
+        use sha2::Digest;
+
+        let api = failing_api().await;
+        let healthy = gateway(200, b"archive bytes").await;
+        let provider = IPFSProvider::new(api.uri(), None).with_gateways(vec![healthy.uri()]);
+
+        let hash = format!("{:x}", sha2::Sha256::digest(b"archive bytes"));
+        assert!(provider.verify(CID, &hash).await.unwrap());
+    }
+}
@@ -1,20 +1,134 @@
+pub mod ans104;
 pub mod arweave;
 pub mod filecoin;
 pub mod ipfs;
+pub mod s3;
 
+use super::RetryConfig;
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
 
 #[async_trait]
 pub trait StorageProviderTrait: Send + Sync {
+    /// Type name of the provider, for tests checking which one was built
+    #[cfg(test)]
+    fn name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     /// Upload data and return the content identifier
     async fn upload(&self, data: Vec<u8>, metadata: UploadMetadata) -> Result<String>;
 
+    /// Upload data, reporting progress through `progress`.
+    ///
+    /// Providers that upload in several requests (S3 multipart uploads)
+    /// override this to report intermediate byte counts; the default reports
+    /// the start and the end.
+    async fn upload_with_progress(
+        &self,
+        data: Vec<u8>,
+        metadata: UploadMetadata,
+        progress: Option<UploadProgress>,
+    ) -> Result<String> {
+        let total = data.len() as u64;
+        if let Some(progress) = &progress {
+            progress(0, total);
+        }
+        let cid = self.upload(data, metadata).await?;
+        if let Some(progress) = &progress {
+            progress(total, total);
+        }
+        Ok(cid)
+    }
+
+    /// Whether [`Self::upload_batch`] stores many files in one transaction,
+    /// making it worth batching small files for this provider
+    fn batches_uploads(&self) -> bool {
+        false
+    }
+
+    /// Upload several files, returning their content identifiers in order.
+    ///
+    /// Providers that can store many files in one transaction (Arweave
+    /// bundles) override this; the default uploads them one by one.
+    async fn upload_batch(&self, files: Vec<(Vec<u8>, UploadMetadata)>) -> Result<Vec<String>> {
+        let mut cids = Vec::with_capacity(files.len());
+        for (data, metadata) in files {
+            cids.push(self.upload(data, metadata).await?);
+        }
+        Ok(cids)
+    }
+
     /// Check if content exists (for deduplication)
     async fn exists(&self, content_hash: &str) -> Result<bool>;
 
     /// Verify uploaded content
     async fn verify(&self, cid: &str, expected_hash: &str) -> Result<bool>;
+
+    /// List stored content whose filename starts with `prefix`
+    async fn list(&self, prefix: &str) -> Result<Vec<BackupEntry>>;
+
+    /// Download previously uploaded content
+    async fn download(&self, cid: &str) -> Result<Vec<u8>> {
+        anyhow::bail!("Download of {cid} is not supported by this provider")
+    }
+
+    /// Renew storage deals that are about to expire.
+    ///
+    /// Returns the deal status for providers whose storage is deal-based
+    /// (Filecoin) and `None` for the others.
+    async fn renew_expiring(&self) -> Result<Option<DealStatus>> {
+        Ok(None)
+    }
+
+    /// Whether [`Self::delete`] can remove content. Permanent stores such as
+    /// Arweave return `false` and are skipped by retention.
+    fn supports_delete(&self) -> bool {
+        true
+    }
+
+    /// Remove previously uploaded content (delete, unpin, ...).
+    ///
+    /// Providers that cannot delete return an error.
+    async fn delete(&self, cid: &str) -> Result<()>;
+}
+
+/// Storage deal health of a deal-based provider
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DealStatus {
+    /// Chain height the status was computed at
+    pub head_epoch: i64,
+    /// Deals currently storing data
+    pub active_deals: usize,
+    /// Content whose last active deal ends within the renewal window
+    pub expiring: usize,
+    /// Content a new deal was started for during this check
+    pub renewed: Vec<String>,
+    /// Earliest end epoch among the active deals
+    pub next_expiry_epoch: Option<i64>,
+}
+
+/// Upload progress callback, called with `(bytes_sent, total_bytes)`.
+///
+/// `bytes_sent` never decreases within one upload; a retried request is not
+/// reported again from zero.
+pub type UploadProgress = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+/// Stored object reported by [`StorageProviderTrait::list`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupEntry {
+    /// Content identifier, as returned by `upload`
+    pub cid: String,
+    /// Filename the content was uploaded under
+    pub filename: String,
+    /// Size in bytes, if the provider reports it
+    pub size: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -25,3 +139,98 @@ pub struct UploadMetadata {
     pub sha256: String,
     pub tags: Vec<(String, String)>,
 }
+
+/// Retry policy for provider network calls, from [`RetryConfig`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for every further retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from(&RetryConfig::default())
+    }
+}
+
+impl RetryPolicy {
+    /// Delay after the given failed attempt (1-based)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+impl From<&RetryConfig> for RetryPolicy {
+    fn from(config: &RetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts,
+            initial_backoff: Duration::from_secs(config.initial_backoff_secs),
+            max_backoff: Duration::from_secs(config.max_backoff_secs),
+        }
+    }
+}
+
+/// Context marking a provider error as worth retrying, for clients whose
+/// errors are not `reqwest` errors (the S3 SDK)
+#[derive(Debug, Clone, Copy)]
+pub struct Transient;
+
+impl std::fmt::Display for Transient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("transient provider error")
+    }
+}
+
+/// Whether a response status is worth retrying: 5xx and 429
+pub fn retryable_status(status: u16) -> bool {
+    status >= 500 || status == 429
+}
+
+/// Whether a failed provider call may succeed when repeated: transport
+/// failures, 5xx and 429 responses. Other client errors and malformed
+/// responses fail immediately.
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<Transient>().is_some()
+        || error.chain().any(|cause| {
+            cause
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|e| match e.status() {
+                    Some(status) => retryable_status(status.as_u16()),
+                    None => e.is_connect() || e.is_timeout() || e.is_request() || e.is_body(),
+                })
+        })
+}
+
+/// Run `op` until it succeeds, fails with an error that is not
+/// [retryable](is_retryable), or `policy.max_attempts` is reached, sleeping
+/// with exponential backoff in between. The last error is returned.
+pub async fn with_retry<T, F, Fut>(policy: &RetryPolicy, what: &str, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < max_attempts && is_retryable(&e) => {
+                let delay = policy.backoff(attempt);
+                warn!(
+                    "{} failed (attempt {}/{}), retrying in {:?}: {:#}",
+                    what, attempt, max_attempts, delay, e
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
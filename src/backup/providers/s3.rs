@@ -0,0 +1,495 @@
+use super::{
+    retryable_status, with_retry, BackupEntry, RetryPolicy, StorageProviderTrait, Transient,
+    UploadMetadata, UploadProgress,
+};
+use crate::backup::S3ServerSideEncryption;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use aws_config::BehaviorVersion;
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart, ServerSideEncryption};
+use aws_sdk_s3::Client;
+use tracing::warn;
+
+/// Part size of multipart uploads; S3 requires at least 5 MiB for every part
+/// but the last
+const MULTIPART_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// S3-compatible object storage
+///
+/// Objects are keyed `{prefix}{sha256}/{filename}` so `exists` can check for
+/// a content hash without knowing the filename. Set `endpoint` and
+/// `force_path_style` to talk to MinIO and other S3-compatible stores.
+///
+/// Uploads with a progress callback that are larger than one part use a
+/// multipart upload and report progress after every part.
+pub struct S3Provider {
+    client: Client,
+    bucket: String,
+    prefix: String,
+    sse: Option<S3ServerSideEncryption>,
+    retry: RetryPolicy,
+    part_size: usize,
+}
+
+impl S3Provider {
+    pub async fn new(
+        bucket: String,
+        prefix: String,
+        region: Option<String>,
+        endpoint: Option<String>,
+        force_path_style: bool,
+        sse: Option<S3ServerSideEncryption>,
+    ) -> Self {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest());
+        if let Some(region) = region {
+            loader = loader.region(aws_config::Region::new(region));
+        }
+        let sdk_config = loader.load().await;
+
+        let mut config =
+            aws_sdk_s3::config::Builder::from(&sdk_config).force_path_style(force_path_style);
+        if let Some(endpoint) = endpoint {
+            config = config.endpoint_url(endpoint);
+        }
+
+        Self {
+            client: Client::from_conf(config.build()),
+            bucket,
+            prefix,
+            sse,
+            retry: RetryPolicy::default(),
+            part_size: MULTIPART_PART_SIZE,
+        }
+    }
+
+    /// Override the retry policy used for uploads
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn key(&self, content_hash: &str, filename: &str) -> String {
+        format!("{}{}/{}", self.prefix, content_hash, filename)
+    }
+
+    /// Upload `data` in parts of `part_size` bytes, reporting the bytes sent
+    /// after every part.
+    async fn upload_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+        data: &[u8],
+        progress: Option<&UploadProgress>,
+    ) -> Result<Vec<CompletedPart>> {
+        let total = data.len() as u64;
+        let mut sent = 0;
+        let mut parts = Vec::new();
+        for (i, chunk) in data.chunks(self.part_size).enumerate() {
+            let part_number = i as i32 + 1;
+            let request = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .part_number(part_number);
+            let output = with_retry(&self.retry, "S3 part upload", || {
+                let request = request.clone().body(chunk.to_vec().into());
+                async move {
+                    request
+                        .send()
+                        .await
+                        .map_err(|e| upload_error(e, "Failed to upload S3 part"))
+                }
+            })
+            .await?;
+            parts.push(
+                CompletedPart::builder()
+                    .part_number(part_number)
+                    .set_e_tag(output.e_tag().map(str::to_string))
+                    .build(),
+            );
+
+            sent += chunk.len() as u64;
+            if let Some(progress) = progress {
+                progress(sent, total);
+            }
+        }
+        Ok(parts)
+    }
+}
+
+/// `x-amz-server-side-encryption` value and KMS key id for `sse`.
+pub(crate) fn sse_params(sse: &S3ServerSideEncryption) -> (ServerSideEncryption, Option<String>) {
+    match sse {
+        S3ServerSideEncryption::Aes256 => (ServerSideEncryption::Aes256, None),
+        S3ServerSideEncryption::AwsKms { kms_key_id } => {
+            (ServerSideEncryption::AwsKms, kms_key_id.clone())
+        }
+    }
+}
+
+/// Upload error for `error`, marked [`Transient`] when worth retrying
+fn upload_error<E>(error: SdkError<E>, what: &'static str) -> anyhow::Error
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let transient = is_transient(&error);
+    let error = anyhow::Error::new(error).context(what);
+    if transient {
+        error.context(Transient)
+    } else {
+        error
+    }
+}
+
+/// Whether an SDK error is a transport failure or a 5xx/429 response
+fn is_transient<E>(error: &SdkError<E>) -> bool {
+    match error {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) | SdkError::ResponseError(_) => {
+            true
+        }
+        SdkError::ServiceError(_) => error
+            .raw_response()
+            .is_some_and(|response| retryable_status(response.status().as_u16())),
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl StorageProviderTrait for S3Provider {
+    async fn upload(&self, data: Vec<u8>, metadata: UploadMetadata) -> Result<String> {
+        let key = self.key(&metadata.sha256, &metadata.filename);
+
+        let mut request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .content_type(metadata.content_type)
+            .metadata("sha256", metadata.sha256);
+        for (name, value) in metadata.tags {
+            request = request.metadata(name.to_lowercase(), value);
+        }
+        if let Some(sse) = &self.sse {
+            let (algorithm, kms_key_id) = sse_params(sse);
+            request = request
+                .server_side_encryption(algorithm)
+                .set_ssekms_key_id(kms_key_id);
+        }
+
+        with_retry(&self.retry, "S3 upload", || {
+            let request = request.clone().body(data.clone().into());
+            async move {
+                request
+                    .send()
+                    .await
+                    .map_err(|e| upload_error(e, "Failed to upload to S3"))?;
+                Ok(())
+            }
+        })
+        .await?;
+
+        Ok(key)
+    }
+
+    async fn upload_with_progress(
+        &self,
+        data: Vec<u8>,
+        metadata: UploadMetadata,
+        progress: Option<UploadProgress>,
+    ) -> Result<String> {
+        let total = data.len() as u64;
+        if data.len() <= self.part_size {
+            if let Some(progress) = &progress {
+                progress(0, total);
+            }
+            let key = self.upload(data, metadata).await?;
+            if let Some(progress) = &progress {
+                progress(total, total);
+            }
+            return Ok(key);
+        }
+
+        let key = self.key(&metadata.sha256, &metadata.filename);
+        let mut create = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&key)
+            .content_type(metadata.content_type)
+            .metadata("sha256", metadata.sha256);
+        for (name, value) in metadata.tags {
+            create = create.metadata(name.to_lowercase(), value);
+        }
+        if let Some(sse) = &self.sse {
+            let (algorithm, kms_key_id) = sse_params(sse);
+            create = create
+                .server_side_encryption(algorithm)
+                .set_ssekms_key_id(kms_key_id);
+        }
+        let created = with_retry(&self.retry, "S3 multipart upload", || {
+            let create = create.clone();
+            async move {
+                create
+                    .send()
+                    .await
+                    .map_err(|e| upload_error(e, "Failed to start S3 multipart upload"))
+            }
+        })
+        .await?;
+        let upload_id = created
+            .upload_id()
+            .context("S3 returned no multipart upload id")?;
+
+        if let Some(progress) = &progress {
+            progress(0, total);
+        }
+        let parts = match self
+            .upload_parts(&key, upload_id, &data, progress.as_ref())
+            .await
+        {
+            Ok(parts) => parts,
+            Err(e) => {
+                // Uploaded parts are billed until the upload is aborted
+                if let Err(abort) = self
+                    .client
+                    .abort_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(&key)
+                    .upload_id(upload_id)
+                    .send()
+                    .await
+                {
+                    warn!("Failed to abort S3 multipart upload of {}: {}", key, abort);
+                }
+                return Err(e);
+            }
+        };
+
+        let complete = self
+            .client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&key)
+            .upload_id(upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            );
+        with_retry(&self.retry, "S3 multipart completion", || {
+            let complete = complete.clone();
+            async move {
+                complete
+                    .send()
+                    .await
+                    .map_err(|e| upload_error(e, "Failed to complete S3 multipart upload"))
+            }
+        })
+        .await?;
+
+        Ok(key)
+    }
+
+    async fn exists(&self, content_hash: &str) -> Result<bool> {
+        let response = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(format!("{}{}/", self.prefix, content_hash))
+            .max_keys(1)
+            .send()
+            .await
+            .context("Failed to list S3 objects")?;
+
+        Ok(response.key_count().unwrap_or(0) > 0)
+    }
+
+    async fn verify(&self, cid: &str, expected_hash: &str) -> Result<bool> {
+        let data = self.download(cid).await?;
+
+        use sha2::Digest;
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(&data);
+        let hash = format!("{:x}", hasher.finalize());
+
+        Ok(hash == expected_hash)
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<BackupEntry>> {
+        let mut entries = Vec::new();
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(&self.prefix)
+            .into_paginator()
+            .send();
+        while let Some(page) = pages.next().await {
+            let page = page.context("Failed to list S3 objects")?;
+            for object in page.contents() {
+                let Some(key) = object.key() else { continue };
+                // Keys are `{prefix}{sha256}/{filename}`
+                let filename = key.rsplit('/').next().unwrap_or(key);
+                if filename.starts_with(prefix) {
+                    entries.push(BackupEntry {
+                        cid: key.to_string(),
+                        filename: filename.to_string(),
+                        size: object.size().and_then(|s| u64::try_from(s).ok()),
+                    });
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn delete(&self, cid: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(cid)
+            .send()
+            .await
+            .context("Failed to delete from S3")?;
+        Ok(())
+    }
+
+    async fn download(&self, cid: &str) -> Result<Vec<u8>> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(cid)
+            .send()
+            .await
+            .context("Failed to download from S3")?;
+        Ok(object.body.collect().await?.into_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use wiremock::http::Method;
+    use wiremock::matchers::{method, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn provider(server: &MockServer, part_size: usize) -> S3Provider {
+        let config = aws_sdk_s3::config::Builder::new()
+            .behavior_version(BehaviorVersion::latest())
+            .region(aws_sdk_s3::config::Region::new("us-east-1"))
+            .credentials_provider(aws_sdk_s3::config::Credentials::new(
+                "access", "secret", None, None, "test",
+            ))
+            .endpoint_url(server.uri())
+            .force_path_style(true)
+            .build();
+        S3Provider {
+            client: Client::from_conf(config),
+            bucket: "backups".to_string(),
+            prefix: String::new(),
+            sse: None,
+            retry: RetryPolicy {
+                max_attempts: 1,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+            },
+            part_size,
+        }
+    }
+
+    fn metadata(data: &[u8]) -> UploadMetadata {
+        UploadMetadata {
+            filename: "history-0000003f.xdr.gz".to_string(),
+            content_type: "application/gzip".to_string(),
+            size: data.len(),
+            sha256: "abc123".to_string(),
+            tags: vec![],
+        }
+    }
+
+    async fn mock_multipart(server: &MockServer, part_status: u16) {
+        Mock::given(method("POST"))
+            .and(query_param("uploadId", "upload-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<CompleteMultipartUploadResult><Bucket>backups</Bucket><Key>abc123/history-0000003f.xdr.gz</Key></CompleteMultipartUploadResult>",
+            ))
+            .with_priority(1)
+            .mount(server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<InitiateMultipartUploadResult><Bucket>backups</Bucket><Key>abc123/history-0000003f.xdr.gz</Key><UploadId>upload-1</UploadId></InitiateMultipartUploadResult>",
+            ))
+            .mount(server)
+            .await;
+        Mock::given(method("PUT"))
+            .respond_with(ResponseTemplate::new(part_status).insert_header("ETag", "\"etag\""))
+            .mount(server)
+            .await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(server)
+            .await;
+    }
+
+    fn recording_progress() -> (UploadProgress, Arc<Mutex<Vec<(u64, u64)>>>) {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let progress: UploadProgress =
+            Arc::new(move |sent, total| recorded.lock().unwrap().push((sent, total)));
+        (progress, calls)
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload_reports_progress_per_part() {
+        let server = MockServer::start().await;
+        mock_multipart(&server, 200).await;
+        let (progress, calls) = recording_progress();
+
+        let data = b"0123456789".to_vec();
+        let key = provider(&server, 4)
+            .upload_with_progress(data.clone(), metadata(&data), Some(progress))
+            .await
+            .unwrap();
+
+        assert_eq!(key, "abc123/history-0000003f.xdr.gz");
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [(0, 10), (4, 10), (8, 10), (10, 10)]
+        );
+        let requests = server.received_requests().await.unwrap();
+        let parts: Vec<String> = requests
+            .iter()
+            .filter(|r| r.method == Method::PUT)
+            .filter_map(|r| {
+                r.url
+                    .query_pairs()
+                    .find(|(name, _)| name == "partNumber")
+                    .map(|(_, n)| n.into_owned())
+            })
+            .collect();
+        assert_eq!(parts, ["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_part_aborts_multipart_upload() {
+        let server = MockServer::start().await;
+        mock_multipart(&server, 400).await;
+        let (progress, calls) = recording_progress();
+
+        let data = b"0123456789".to_vec();
+        let result = provider(&server, 4)
+            .upload_with_progress(data.clone(), metadata(&data), Some(progress))
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(*calls.lock().unwrap(), [(0, 10)]);
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests.iter().any(|r| r.method == Method::DELETE));
+    }
+}
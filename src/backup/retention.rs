@@ -0,0 +1,139 @@
+//! Backup retention enforcement
+//!
+//! Applies [`RetentionPolicy`] to the backups listed by their manifests: the
+//! `min_backups` newest are always kept, and any other backup older than
+//! `days` is removed from the provider, segments first and manifest last, so
+//! an interrupted cleanup never leaves a manifest pointing at missing data.
+//! Segments are shared between runs (a run reuses what the segment cache
+//! says is already stored), so a segment any retained manifest still lists is
+//! kept. Providers that cannot delete (Arweave) are left untouched.
+
+use super::manifest::{list_backups, BackupManifest, ManifestSegment};
+use super::providers::StorageProviderTrait;
+use super::RetentionPolicy;
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use tracing::{info, warn};
+
+/// Backups the policy no longer keeps, given manifests in any order.
+pub fn expired_backups<'a>(
+    manifests: &'a [BackupManifest],
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<&'a BackupManifest> {
+    if policy.days == 0 {
+        return Vec::new();
+    }
+    let cutoff = now - Duration::days(i64::from(policy.days));
+
+    let mut newest_first: Vec<&BackupManifest> = manifests.iter().collect();
+    newest_first.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    newest_first
+        .into_iter()
+        .skip(policy.min_backups as usize)
+        .filter(|m| m.created_at < cutoff)
+        .collect()
+}
+
+/// What a retention pass removed from a provider
+#[derive(Debug, Default)]
+pub struct RetentionOutcome {
+    /// Manifests of the removed backups
+    pub removed: Vec<BackupManifest>,
+    /// Segments deleted with them; shared segments still listed by a retained
+    /// backup are not included
+    pub deleted_segments: Vec<ManifestSegment>,
+}
+
+/// Segments of `expired` that no manifest outside `expired` lists
+pub fn unreferenced_segments<'a>(
+    manifests: &[BackupManifest],
+    expired: &[&'a BackupManifest],
+) -> Vec<&'a ManifestSegment> {
+    let expired_names: HashSet<String> = expired.iter().map(|m| m.filename()).collect();
+    let retained: HashSet<&str> = manifests
+        .iter()
+        .filter(|m| !expired_names.contains(&m.filename()))
+        .flat_map(|m| m.segments.iter().map(|s| s.filename.as_str()))
+        .collect();
+    let mut seen = HashSet::new();
+    expired
+        .iter()
+        .copied()
+        .flat_map(|m| &m.segments)
+        .filter(|s| !retained.contains(s.filename.as_str()) && seen.insert(&s.filename))
+        .collect()
+}
+
+/// Delete expired backups from `provider`.
+///
+/// A backup whose content cannot be fully deleted keeps its manifest and is
+/// retried on the next run.
+pub async fn enforce_retention(
+    provider: &dyn StorageProviderTrait,
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Result<RetentionOutcome> {
+    let mut outcome = RetentionOutcome::default();
+    if !provider.supports_delete() {
+        return Ok(outcome);
+    }
+    let manifests = list_backups(provider).await?;
+    let expired = expired_backups(&manifests, policy, now);
+    if expired.is_empty() {
+        return Ok(outcome);
+    }
+    let deletable: HashSet<&str> = unreferenced_segments(&manifests, &expired)
+        .into_iter()
+        .map(|s| s.filename.as_str())
+        .collect();
+
+    let mut cids: HashMap<String, Vec<String>> = HashMap::new();
+    for entry in provider.list("").await? {
+        cids.entry(entry.filename).or_default().push(entry.cid);
+    }
+
+    let mut deleted: HashSet<&str> = HashSet::new();
+    for manifest in expired {
+        let mut complete = true;
+        for segment in &manifest.segments {
+            if !deletable.contains(segment.filename.as_str())
+                || deleted.contains(segment.filename.as_str())
+            {
+                continue;
+            }
+            for cid in cids.get(&segment.filename).into_iter().flatten() {
+                if let Err(e) = provider.delete(cid).await {
+                    warn!("Failed to delete expired backup object {}: {:#}", cid, e);
+                    complete = false;
+                    break;
+                }
+            }
+            if !complete {
+                break;
+            }
+            deleted.insert(&segment.filename);
+            outcome.deleted_segments.push(segment.clone());
+        }
+        if complete {
+            for cid in cids.get(&manifest.filename()).into_iter().flatten() {
+                if let Err(e) = provider.delete(cid).await {
+                    warn!("Failed to delete expired backup object {}: {:#}", cid, e);
+                    complete = false;
+                    break;
+                }
+            }
+        }
+        if complete {
+            info!(
+                "Removed expired backup {} (ledgers {}-{})",
+                manifest.filename(),
+                manifest.ledger_start,
+                manifest.ledger_end
+            );
+            outcome.removed.push(manifest.clone());
+        }
+    }
+    Ok(outcome)
+}
@@ -1,28 +1,132 @@
-use super::providers::{StorageProviderTrait, UploadMetadata};
+use super::credentials::{build_providers, SecretSource};
+use super::manifest::BackupManifest;
+use super::providers::{DealStatus, StorageProviderTrait, UploadMetadata, UploadProgress};
+use super::restore_check::{verify_latest_backup, RestoreCheckStatus};
+use super::retention::enforce_retention;
 use super::*;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
 use cron::Schedule;
+use std::collections::HashSet;
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::time::sleep;
-use tracing::{error, info, Instrument};
+use tracing::{error, info, warn, Instrument};
+
+/// Content ids of uploaded segments, keyed by provider index and hash
+pub(crate) type UploadedHashes = Arc<RwLock<SegmentCache>>;
+
+/// Total on-disk size of the segments of a run; unreadable files count as 0.
+pub(crate) async fn segments_size(segments: &[ArchiveSegment]) -> u64 {
+    let mut total = 0;
+    for segment in segments {
+        if let Ok(meta) = tokio::fs::metadata(&segment.path).await {
+            total += meta.len();
+        }
+    }
+    total
+}
+
+/// Segments up to this size on disk are batched for providers that batch
+/// uploads
+const BATCH_MAX_SEGMENT_SIZE: u64 = 256 * 1024;
+
+/// Segments per batch upload
+const BATCH_MAX_SEGMENTS: usize = 100;
+
+/// Percentage step between progress log lines of a single upload
+const PROGRESS_LOG_STEP: u64 = 25;
+
+/// Progress callback that logs every [`PROGRESS_LOG_STEP`] percent of an
+/// upload to `provider`.
+fn progress_logger(filename: String, provider: usize) -> UploadProgress {
+    let last_step = AtomicU64::new(0);
+    Arc::new(move |sent: u64, total: u64| {
+        if total == 0 {
+            return;
+        }
+        let step = sent.saturating_mul(100) / total / PROGRESS_LOG_STEP;
+        if step > last_step.fetch_max(step, Ordering::Relaxed) {
+            info!(
+                "Uploading {} to provider {}: {}/{} bytes ({}%)",
+                filename,
+                provider,
+                sent,
+                total,
+                sent.saturating_mul(100) / total
+            );
+        }
+    })
+}
 
 pub struct BackupScheduler {
     config: DecentralizedBackupConfig,
-    provider: Arc<dyn StorageProviderTrait>,
-    uploaded_hashes: Arc<RwLock<HashSet<String>>>,
+    providers: Vec<Arc<dyn StorageProviderTrait>>,
+    uploaded_hashes: UploadedHashes,
+    restore_check: Arc<RwLock<RestoreCheckStatus>>,
+    deal_status: Arc<RwLock<Vec<(usize, DealStatus)>>>,
 }
 
 impl BackupScheduler {
     pub fn new(config: DecentralizedBackupConfig, provider: Arc<dyn StorageProviderTrait>) -> Self {
+        Self::with_providers(config, vec![provider])
+    }
+
+    /// Create a scheduler that fans every segment out to several providers,
+    /// e.g. S3 plus Arweave for redundancy.
+    pub fn with_providers(
+        config: DecentralizedBackupConfig,
+        providers: Vec<Arc<dyn StorageProviderTrait>>,
+    ) -> Self {
+        let cache = match config.cache_path.as_deref() {
+            Some(path) => SegmentCache::load(Path::new(path)).unwrap_or_else(|e| {
+                warn!("Ignoring segment cache: {:#}", e);
+                SegmentCache::default()
+            }),
+            None => SegmentCache::default(),
+        };
+
         Self {
             config,
-            provider,
-            uploaded_hashes: Arc::new(RwLock::new(HashSet::new())),
+            providers,
+            uploaded_hashes: Arc::new(RwLock::new(cache)),
+            restore_check: Arc::new(RwLock::new(RestoreCheckStatus::default())),
+            deal_status: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
+    /// Create a scheduler for every provider in `config`, loading the
+    /// credentials they reference from `secrets`.
+    pub async fn from_config(
+        config: DecentralizedBackupConfig,
+        secrets: &dyn SecretSource,
+    ) -> Result<Self> {
+        let providers = build_providers(&config, secrets)
+            .await
+            .context("Failed to set up backup providers")?;
+        Ok(Self::with_providers(config, providers))
+    }
+
+    /// Providers every segment is uploaded to, primary first
+    pub fn providers(&self) -> &[Arc<dyn StorageProviderTrait>] {
+        &self.providers
+    }
+
+    /// Outcome of the latest restorability checks
+    pub async fn restore_check_status(&self) -> RestoreCheckStatus {
+        self.restore_check.read().await.clone()
+    }
+
+    /// Storage deal status of the deal-based providers, keyed by provider
+    /// index, as of the last backup run
+    pub async fn deal_status(&self) -> Vec<(usize, DealStatus)> {
+        self.deal_status.read().await.clone()
+    }
+
     pub async fn start(&self, history_archive_path: String) -> Result<()> {
+        self.config.validate().map_err(|e| anyhow!(e))?;
         let schedule =
             Schedule::from_str(&self.config.schedule).context("Invalid cron schedule")?;
 
@@ -31,6 +135,14 @@ impl BackupScheduler {
             self.config.schedule
         );
 
+        tokio::try_join!(
+            self.backup_loop(&schedule, &history_archive_path),
+            self.restore_check_loop()
+        )?;
+        Ok(())
+    }
+
+    async fn backup_loop(&self, schedule: &Schedule, history_archive_path: &str) -> Result<()> {
         loop {
             let now = chrono::Utc::now();
             let next = schedule
@@ -43,35 +155,189 @@ impl BackupScheduler {
             info!("Next backup scheduled in {:?}", duration);
             sleep(duration).await;
 
-            if let Err(e) = self.run_backup(&history_archive_path).await {
+            if let Err(e) = self.run_backup(history_archive_path).await {
                 error!("Backup failed: {}", e);
             }
         }
     }
 
+    /// Run the restorability check on `verify_schedule`; returns at once if
+    /// none is configured.
+    async fn restore_check_loop(&self) -> Result<()> {
+        let Some(verify_schedule) = &self.config.verify_schedule else {
+            return Ok(());
+        };
+        info!(
+            "Starting restorability checks with schedule: {}",
+            verify_schedule
+        );
+
+        loop {
+            let now = Utc::now();
+            let next = self
+                .next_restore_check(now)?
+                .context("No upcoming verify schedule")?;
+
+            let duration = (next - now).to_std().unwrap_or(Duration::from_secs(60));
+            info!("Next restorability check scheduled in {:?}", duration);
+            sleep(duration).await;
+
+            self.run_restore_check().await;
+        }
+    }
+
+    /// When the restorability check next runs after `after`, if scheduled.
+    pub(crate) fn next_restore_check(&self, after: DateTime<Utc>) -> Result<Option<DateTime<Utc>>> {
+        let Some(verify_schedule) = &self.config.verify_schedule else {
+            return Ok(None);
+        };
+        let schedule = Schedule::from_str(verify_schedule).context("Invalid verify schedule")?;
+        Ok(schedule.after(&after).next())
+    }
+
+    /// Check that the newest backup on the primary provider can be
+    /// downloaded and read back, and record the outcome.
+    pub(crate) async fn run_restore_check(&self) -> RestoreCheckStatus {
+        let result = match self.providers.first() {
+            Some(provider) => verify_latest_backup(provider.as_ref()).await,
+            None => Ok(None),
+        };
+        match &result {
+            Ok(Some(manifest)) => info!(
+                "Backup {} passed the restorability check",
+                manifest.filename()
+            ),
+            Ok(None) => info!("No backup to check for restorability yet"),
+            Err(e) => error!("Restorability check failed: {:#}", e),
+        }
+
+        let mut status = self.restore_check.write().await;
+        status.record(&result, Utc::now());
+        status.clone()
+    }
+
+    /// Renew expiring storage deals on every provider and record their
+    /// status. A provider whose check fails keeps its previous status.
+    pub(crate) async fn renew_deals(&self) -> Vec<(usize, DealStatus)> {
+        let mut current = self.deal_status.write().await;
+        for (index, provider) in self.providers.iter().enumerate() {
+            match provider.renew_expiring().await {
+                Ok(Some(status)) => {
+                    if status.expiring > status.renewed.len() {
+                        warn!(
+                            "Provider {}: renewed {} of {} expiring deals",
+                            index,
+                            status.renewed.len(),
+                            status.expiring
+                        );
+                    }
+                    current.retain(|(i, _)| *i != index);
+                    current.push((index, status));
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Provider {}: failed to check storage deals: {:#}", index, e),
+            }
+        }
+        current.sort_by_key(|(index, _)| *index);
+        current.clone()
+    }
+
     async fn run_backup(&self, archive_path: &str) -> Result<()> {
         info!("Starting backup of history archive: {}", archive_path);
+        let started = std::time::Instant::now();
 
         // Discover new archive segments
         let segments = self.discover_new_segments(archive_path).await?;
         info!("Found {} segments to backup", segments.len());
+        let size_bytes = segments_size(&segments).await;
 
-        // Upload with concurrency control
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(
+        let compression = self
+            .config
+            .compression_enabled
+            .then_some(self.config.compression_level);
+        let result = Self::upload_run(
+            segments,
+            &self.providers,
+            &self.uploaded_hashes,
+            compression,
             self.config.max_concurrent_uploads,
-        ));
+        )
+        .await;
+
+        if let (Ok(_), Some(policy)) = (&result, &self.config.retention) {
+            Self::apply_retention(&self.providers, &self.uploaded_hashes, policy).await;
+        }
+
+        self.renew_deals().await;
+
+        if let Some(path) = self.config.cache_path.as_deref() {
+            if let Err(e) = self.uploaded_hashes.read().await.save(Path::new(path)) {
+                warn!("Failed to persist segment cache: {:#}", e);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        {
+            let status = if result.is_ok() { "success" } else { "failed" };
+            crate::controller::metrics::observe_backup_duration(
+                archive_path,
+                status,
+                started.elapsed().as_secs_f64(),
+            );
+            if result.is_ok() {
+                crate::controller::metrics::set_backup_size_bytes(
+                    archive_path,
+                    i64::try_from(size_bytes).unwrap_or(i64::MAX),
+                );
+            }
+        }
+        info!(
+            "Backup of {} finished in {:?} ({} bytes)",
+            archive_path,
+            started.elapsed(),
+            size_bytes
+        );
+
+        result.map(|_| ())
+    }
+
+    /// Upload a run's segments in parallel, then their manifest.
+    ///
+    /// The manifest is written only once every segment is on every provider,
+    /// so a listed backup is always complete. If any segment or the manifest
+    /// fails, the uploads made by this run are deleted again and dropped from
+    /// the cache; segments stored by earlier runs are left alone.
+    pub(crate) async fn upload_run(
+        segments: Vec<ArchiveSegment>,
+        providers: &[Arc<dyn StorageProviderTrait>],
+        uploaded: &UploadedHashes,
+        compression: Option<u32>,
+        max_concurrent_uploads: usize,
+    ) -> Result<Option<BackupManifest>> {
+        let already_stored: HashSet<(usize, String)> = {
+            let cache = uploaded.read().await;
+            segments
+                .iter()
+                .flat_map(|s| (0..providers.len()).map(move |i| (i, s.hash.clone())))
+                .filter(|(i, hash)| cache.contains(*i, hash))
+                .collect()
+        };
+
+        Self::upload_batches(&segments, providers, uploaded, compression).await;
+
+        // Upload with concurrency control
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(max_concurrent_uploads));
 
         let mut tasks = vec![];
         let current_span = tracing::Span::current();
-        for segment in segments {
+        for segment in segments.iter().cloned() {
             let sem = semaphore.clone();
-            let provider = self.provider.clone();
-            let uploaded = self.uploaded_hashes.clone();
-            let compression = self.config.compression_enabled;
+            let providers = providers.to_vec();
+            let uploaded = uploaded.clone();
 
             let task = tokio::spawn(async move {
                 let _permit = sem.acquire().await.unwrap();
-                Self::upload_segment(segment, provider, uploaded, compression).await
+                Self::upload_segment(segment, providers, uploaded, compression).await
             })
             .instrument(current_span.clone());
 
@@ -79,14 +345,106 @@ impl BackupScheduler {
         }
 
         let results = futures::future::join_all(tasks).await;
-        let successful = results.iter().filter(|r| r.is_ok()).count();
+        let failed = results.iter().filter(|r| !matches!(r, Ok(Ok(())))).count();
+        if failed > 0 {
+            Self::remove_partial_uploads(&segments, providers, uploaded, &already_stored).await;
+            return Err(anyhow!(
+                "Backup failed: {}/{} segment(s) not uploaded, partial uploads removed",
+                failed,
+                results.len()
+            ));
+        }
+        info!("Backup completed: {} segment(s) uploaded", results.len());
+
+        let Some(manifest) =
+            BackupManifest::from_segments(&segments, compression.is_some(), chrono::Utc::now())
+        else {
+            return Ok(None);
+        };
+        if let Err(e) = Self::upload_manifest(&manifest, providers).await {
+            Self::remove_partial_uploads(&segments, providers, uploaded, &already_stored).await;
+            return Err(e.context("Backup manifest not written, partial uploads removed"));
+        }
+
+        Ok(Some(manifest))
+    }
+
+    /// Remove expired backups from every provider and forget the segments
+    /// deleted with them.
+    pub(crate) async fn apply_retention(
+        providers: &[Arc<dyn StorageProviderTrait>],
+        uploaded: &UploadedHashes,
+        policy: &RetentionPolicy,
+    ) {
+        for (i, provider) in providers.iter().enumerate() {
+            match enforce_retention(provider.as_ref(), policy, chrono::Utc::now()).await {
+                Ok(outcome) => {
+                    let mut cache = uploaded.write().await;
+                    for segment in &outcome.deleted_segments {
+                        cache.remove(i, &segment.sha256);
+                    }
+                }
+                Err(e) => warn!("Retention on provider {} failed: {:#}", i, e),
+            }
+        }
+    }
+
+    /// Delete segments uploaded by a failed run and forget them in the cache.
+    async fn remove_partial_uploads(
+        segments: &[ArchiveSegment],
+        providers: &[Arc<dyn StorageProviderTrait>],
+        uploaded: &UploadedHashes,
+        already_stored: &HashSet<(usize, String)>,
+    ) {
+        let mut cache = uploaded.write().await;
+        for segment in segments {
+            for (i, provider) in providers.iter().enumerate() {
+                if !provider.supports_delete()
+                    || already_stored.contains(&(i, segment.hash.clone()))
+                {
+                    continue;
+                }
+                let Some(cid) = cache.remove(i, &segment.hash) else {
+                    continue;
+                };
+                if let Err(e) = provider.delete(&cid).await {
+                    warn!(
+                        "Failed to remove partial upload {} from provider {}: {:#}",
+                        cid, i, e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Upload a run's manifest to every provider.
+    pub(crate) async fn upload_manifest(
+        manifest: &BackupManifest,
+        providers: &[Arc<dyn StorageProviderTrait>],
+    ) -> Result<()> {
+        let (data, metadata) = manifest.to_upload()?;
+        let uploads = providers
+            .iter()
+            .map(|provider| provider.upload(data.clone(), metadata.clone()));
+
+        let failures = futures::future::join_all(uploads)
+            .await
+            .into_iter()
+            .filter(|r| r.is_err())
+            .count();
+        if failures > 0 {
+            return Err(anyhow!(
+                "Upload of {} failed for {}/{} provider(s)",
+                metadata.filename,
+                failures,
+                providers.len()
+            ));
+        }
 
         info!(
-            "Backup completed: {}/{} successful",
-            successful,
-            results.len()
+            "Uploaded backup manifest {} (ledgers {}-{})",
+            metadata.filename, manifest.ledger_start, manifest.ledger_end
         );
-
         Ok(())
     }
 
@@ -97,60 +455,166 @@ impl BackupScheduler {
         Ok(vec![])
     }
 
+    /// Upload the small segments of a run in batches to the providers that
+    /// [batch uploads](StorageProviderTrait::batches_uploads), e.g. as one
+    /// Arweave bundle transaction.
+    ///
+    /// Segments of a failed batch stay uncached, so [`Self::upload_segment`]
+    /// uploads them one by one like for the other providers.
+    pub(crate) async fn upload_batches(
+        segments: &[ArchiveSegment],
+        providers: &[Arc<dyn StorageProviderTrait>],
+        uploaded: &UploadedHashes,
+        compression: Option<u32>,
+    ) {
+        for (i, provider) in providers.iter().enumerate() {
+            if !provider.batches_uploads() {
+                continue;
+            }
+            let mut small = Vec::new();
+            for segment in segments {
+                if uploaded.read().await.contains(i, &segment.hash) {
+                    continue;
+                }
+                if let Ok(meta) = tokio::fs::metadata(&segment.path).await {
+                    if meta.len() <= BATCH_MAX_SEGMENT_SIZE {
+                        small.push(segment);
+                    }
+                }
+            }
+
+            for batch in small.chunks(BATCH_MAX_SEGMENTS) {
+                let mut hashes = Vec::with_capacity(batch.len());
+                let mut files = Vec::with_capacity(batch.len());
+                for segment in batch {
+                    // Unreadable segments fail in `upload_segment`
+                    if let Ok(file) = read_segment(segment, compression).await {
+                        hashes.push(segment.hash.clone());
+                        files.push(file);
+                    }
+                }
+                let count = files.len();
+                match provider.upload_batch(files).await {
+                    Ok(cids) => {
+                        let mut cache = uploaded.write().await;
+                        for (hash, cid) in hashes.into_iter().zip(cids) {
+                            cache.insert(i, hash, cid);
+                        }
+                        info!("Uploaded {} segment(s) to provider {} in one batch", count, i);
+                    }
+                    Err(e) => warn!(
+                        "Batch upload of {} segment(s) to provider {} failed, uploading them one by one: {:#}",
+                        count, i, e
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Upload a segment to every provider that does not have it yet.
+    ///
+    /// The segment is read and compressed once and the same bytes are sent to
+    /// each provider. A failing provider does not stop the others; it is
+    /// retried on the next run. `compression` is the gzip level, `None` to
+    /// upload as-is.
     pub(crate) async fn upload_segment(
         segment: ArchiveSegment,
-        provider: Arc<dyn StorageProviderTrait>,
-        uploaded_hashes: Arc<RwLock<HashSet<String>>>,
-        compression_enabled: bool,
+        providers: Vec<Arc<dyn StorageProviderTrait>>,
+        uploaded_hashes: UploadedHashes,
+        compression: Option<u32>,
     ) -> Result<()> {
-        // Check if already uploaded (deduplication)
-        {
+        // Check which providers already have it (deduplication)
+        let pending: Vec<usize> = {
             let hashes = uploaded_hashes.read().await;
-            if hashes.contains(&segment.hash) {
-                info!("Segment {} already uploaded, skipping", segment.filename);
-                return Ok(());
-            }
+            (0..providers.len())
+                .filter(|i| !hashes.contains(*i, &segment.hash))
+                .collect()
+        };
+        if pending.is_empty() {
+            info!("Segment {} already uploaded, skipping", segment.filename);
+            return Ok(());
         }
 
-        // Read segment data
-        let mut data = tokio::fs::read(&segment.path)
-            .await
-            .context("Failed to read segment")?;
-
-        // Apply additional compression if enabled and not already compressed
-        if compression_enabled && !segment.filename.ends_with(".gz") {
-            data = compress_data(&data)?;
-        }
-
-        let metadata = UploadMetadata {
-            filename: segment.filename.clone(),
-            content_type: "application/octet-stream".to_string(),
-            size: data.len(),
-            sha256: segment.hash.clone(),
-            tags: vec![
-                ("Ledger".to_string(), segment.ledger.to_string()),
-                ("Type".to_string(), segment.segment_type.clone()),
-            ],
-        };
+        let (data, metadata) = read_segment(&segment, compression).await?;
 
-        // Upload
-        let cid = provider
-            .upload(data, metadata)
-            .await
-            .context("Upload failed")?;
+        // Upload to all pending providers concurrently
+        let uploads = pending.iter().map(|&i| {
+            let provider = providers[i].clone();
+            let data = data.clone();
+            let metadata = metadata.clone();
+            let progress = progress_logger(segment.filename.clone(), i);
+            async move {
+                let result = provider
+                    .upload_with_progress(data, metadata, Some(progress))
+                    .await;
+                (i, result)
+            }
+        });
 
-        info!("Uploaded {} -> {}", segment.filename, cid);
+        let mut failures = 0;
+        for (i, result) in futures::future::join_all(uploads).await {
+            match result {
+                Ok(cid) => {
+                    info!("Uploaded {} -> {} (provider {})", segment.filename, cid, i);
+                    // Mark as uploaded
+                    uploaded_hashes
+                        .write()
+                        .await
+                        .insert(i, segment.hash.clone(), cid);
+                }
+                Err(e) => {
+                    error!(
+                        "Upload of {} to provider {} failed: {:#}",
+                        segment.filename, i, e
+                    );
+                    failures += 1;
+                }
+            }
+        }
 
-        // Mark as uploaded
-        {
-            let mut hashes = uploaded_hashes.write().await;
-            hashes.insert(segment.hash.clone());
+        if failures > 0 {
+            return Err(anyhow!(
+                "Upload of {} failed for {}/{} provider(s)",
+                segment.filename,
+                failures,
+                pending.len()
+            ));
         }
 
         Ok(())
     }
 }
 
+/// Read a segment and compress it unless it already is, returning the bytes
+/// to upload and their metadata.
+async fn read_segment(
+    segment: &ArchiveSegment,
+    compression: Option<u32>,
+) -> Result<(Vec<u8>, UploadMetadata)> {
+    let mut data = tokio::fs::read(&segment.path)
+        .await
+        .context("Failed to read segment")?;
+
+    // Apply additional compression if enabled and not already compressed
+    if let Some(level) = compression {
+        if !segment.filename.ends_with(".gz") {
+            data = compress_data(&data, level)?;
+        }
+    }
+
+    let metadata = UploadMetadata {
+        filename: segment.filename.clone(),
+        content_type: "application/octet-stream".to_string(),
+        size: data.len(),
+        sha256: segment.hash.clone(),
+        tags: vec![
+            ("Ledger".to_string(), segment.ledger.to_string()),
+            ("Type".to_string(), segment.segment_type.clone()),
+        ],
+    };
+    Ok((data, metadata))
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct ArchiveSegment {
     pub(crate) filename: String,
@@ -160,12 +624,21 @@ pub(crate) struct ArchiveSegment {
     pub(crate) segment_type: String,
 }
 
-pub(crate) fn compress_data(data: &[u8]) -> Result<Vec<u8>> {
+pub(crate) fn compress_data(data: &[u8], level: u32) -> Result<Vec<u8>> {
     use flate2::write::GzEncoder;
     use flate2::Compression;
     use std::io::Write;
 
-    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level.min(9)));
     encoder.write_all(data)?;
     Ok(encoder.finish()?)
 }
+
+pub(crate) fn decompress_data(data: &[u8]) -> Result<Vec<u8>> {
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    let mut decoded = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
@@ -4,20 +4,34 @@
 //! rejection, BackupScheduler construction, DecentralizedBackupConfig
 //! serialisation round-trips for every provider variant, serde default
 //! values, RetentionPolicy serialisation, UploadMetadata construction,
-//! and gzip compression via `compress_data`.
+//! gzip compression via `compress_data`, fan-out of a single segment
+//! to several providers, and backup manifest upload.
 
 #[cfg(test)]
 mod tests {
-    use crate::backup::providers::{StorageProviderTrait, UploadMetadata};
-    use crate::backup::scheduler::{compress_data, BackupScheduler};
+    use crate::backup::credentials::{
+        build_provider, resolve_bundle_key, resolve_pinning, resolve_secret, SecretSource,
+    };
+    use crate::backup::providers::arweave::ArweaveProvider;
+    use crate::backup::providers::s3::sse_params;
+    use crate::backup::providers::{
+        with_retry, BackupEntry, DealStatus, RetryPolicy, StorageProviderTrait, Transient,
+        UploadMetadata, UploadProgress,
+    };
+    use crate::backup::retention::enforce_retention;
+    use crate::backup::scheduler::{compress_data, segments_size, ArchiveSegment, BackupScheduler};
     use crate::backup::*;
 
     use anyhow::Result;
     use async_trait::async_trait;
+    use aws_sdk_s3::types::ServerSideEncryption;
     use chrono::Utc;
     use cron::Schedule;
+    use std::collections::BTreeMap;
     use std::str::FromStr;
+    use std::sync::atomic::{AtomicU32, Ordering};
     use std::sync::Arc;
+    use std::time::Duration;
     use tokio::sync::RwLock;
 
     // ---------------------------------------------------------------------
@@ -26,14 +40,19 @@ mod tests {
 
     type UploadRecord = Vec<(Vec<u8>, UploadMetadata)>;
 
+    /// Bytes the mock "sends" between progress reports
+    const MOCK_CHUNK: usize = 4;
+
     struct MockProvider {
         uploads: Arc<RwLock<UploadRecord>>,
+        deleted: Arc<RwLock<Vec<String>>>,
     }
 
     impl MockProvider {
         fn new() -> Self {
             Self {
                 uploads: Arc::new(RwLock::new(Vec::new())),
+                deleted: Arc::new(RwLock::new(Vec::new())),
             }
         }
     }
@@ -41,8 +60,25 @@ mod tests {
     #[async_trait]
     impl StorageProviderTrait for MockProvider {
         async fn upload(&self, data: Vec<u8>, metadata: UploadMetadata) -> Result<String> {
+            let cid = format!("mock-cid-{}", metadata.sha256);
             self.uploads.write().await.push((data, metadata));
-            Ok("mock-cid-12345".to_string())
+            Ok(cid)
+        }
+
+        async fn upload_with_progress(
+            &self,
+            data: Vec<u8>,
+            metadata: UploadMetadata,
+            progress: Option<UploadProgress>,
+        ) -> Result<String> {
+            if let Some(progress) = &progress {
+                let total = data.len() as u64;
+                for end in (MOCK_CHUNK..data.len()).step_by(MOCK_CHUNK) {
+                    progress(end as u64, total);
+                }
+                progress(total, total);
+            }
+            self.upload(data, metadata).await
         }
 
         async fn exists(&self, _content_hash: &str) -> Result<bool> {
@@ -52,6 +88,113 @@ mod tests {
         async fn verify(&self, _cid: &str, _expected_hash: &str) -> Result<bool> {
             Ok(true)
         }
+
+        async fn list(&self, prefix: &str) -> Result<Vec<BackupEntry>> {
+            Ok(self
+                .uploads
+                .read()
+                .await
+                .iter()
+                .filter(|(_, m)| m.filename.starts_with(prefix))
+                .map(|(data, m)| BackupEntry {
+                    cid: format!("mock-cid-{}", m.sha256),
+                    filename: m.filename.clone(),
+                    size: Some(data.len() as u64),
+                })
+                .collect())
+        }
+
+        async fn download(&self, cid: &str) -> Result<Vec<u8>> {
+            self.uploads
+                .read()
+                .await
+                .iter()
+                .find(|(_, m)| format!("mock-cid-{}", m.sha256) == cid)
+                .map(|(data, _)| data.clone())
+                .ok_or_else(|| anyhow::anyhow!("{cid} not found"))
+        }
+
+        async fn delete(&self, cid: &str) -> Result<()> {
+            self.deleted.write().await.push(cid.to_string());
+            Ok(())
+        }
+    }
+
+    struct FailingProvider;
+
+    #[async_trait]
+    impl StorageProviderTrait for FailingProvider {
+        async fn upload(&self, _data: Vec<u8>, _metadata: UploadMetadata) -> Result<String> {
+            anyhow::bail!("provider unavailable")
+        }
+
+        async fn exists(&self, _content_hash: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn verify(&self, _cid: &str, _expected_hash: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn list(&self, _prefix: &str) -> Result<Vec<BackupEntry>> {
+            anyhow::bail!("provider unavailable")
+        }
+
+        async fn delete(&self, _cid: &str) -> Result<()> {
+            anyhow::bail!("provider unavailable")
+        }
+    }
+
+    /// Provider whose transport fails `failures` times before succeeding,
+    /// retrying the same way the real providers do.
+    struct FlakyProvider {
+        failures: u32,
+        attempts: AtomicU32,
+        retry: RetryPolicy,
+    }
+
+    impl FlakyProvider {
+        fn new(failures: u32, max_attempts: u32) -> Self {
+            Self {
+                failures,
+                attempts: AtomicU32::new(0),
+                retry: RetryPolicy {
+                    max_attempts,
+                    initial_backoff: Duration::ZERO,
+                    max_backoff: Duration::ZERO,
+                },
+            }
+        }
+    }
+
+    #[async_trait]
+    impl StorageProviderTrait for FlakyProvider {
+        async fn upload(&self, _data: Vec<u8>, metadata: UploadMetadata) -> Result<String> {
+            let metadata = &metadata;
+            with_retry(&self.retry, "flaky upload", || async move {
+                if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+                    return Err(anyhow::anyhow!("connection reset").context(Transient));
+                }
+                Ok(format!("flaky-cid-{}", metadata.sha256))
+            })
+            .await
+        }
+
+        async fn exists(&self, _content_hash: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn verify(&self, _cid: &str, _expected_hash: &str) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn list(&self, _prefix: &str) -> Result<Vec<BackupEntry>> {
+            Ok(Vec::new())
+        }
+
+        async fn delete(&self, _cid: &str) -> Result<()> {
+            Ok(())
+        }
     }
 
     // ---------------------------------------------------------------------
@@ -71,14 +214,20 @@ mod tests {
                 wallet_secret: "arweave-secret".to_string(),
                 gateway: "https://arweave.net".to_string(),
                 tags: vec![("App".to_string(), "StellarK8s".to_string())],
+                bundle_key_secret: None,
             },
+            additional_providers: vec![],
             schedule: EVERY_6H_CRON.to_string(),
             max_concurrent_uploads: 3,
             compression_enabled: true,
+            compression_level: 6,
             retention: Some(RetentionPolicy {
                 days: 30,
                 min_backups: 5,
             }),
+            cache_path: None,
+            verify_schedule: None,
+            retry: RetryConfig::default(),
         }
     }
 
@@ -91,11 +240,17 @@ mod tests {
                     service_type: PinningServiceType::Pinata,
                     api_key_secret: "pinata-key".to_string(),
                 }),
+                gateways: vec![],
             },
+            additional_providers: vec![],
             schedule: DAILY_MIDNIGHT_CRON.to_string(),
             max_concurrent_uploads: 5,
             compression_enabled: false,
+            compression_level: 6,
             retention: None,
+            cache_path: None,
+            verify_schedule: None,
+            retry: RetryConfig::default(),
         }
     }
 
@@ -109,15 +264,21 @@ mod tests {
                     price_per_epoch: "500000000".to_string(),
                     duration: 518400,
                     verified: true,
+                    renew_before_epochs: 20160,
                 },
             },
+            additional_providers: vec![],
             schedule: EVERY_2D_CRON.to_string(),
             max_concurrent_uploads: 1,
             compression_enabled: true,
+            compression_level: 6,
             retention: Some(RetentionPolicy {
                 days: 90,
                 min_backups: 10,
             }),
+            cache_path: None,
+            verify_schedule: None,
+            retry: RetryConfig::default(),
         }
     }
 
@@ -175,6 +336,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_validate_requires_seconds_field() {
+        let mut config = arweave_config();
+        config.schedule = EVERY_6H_CRON.to_string();
+        assert!(config.validate().is_ok());
+
+        config.schedule = "0 */6 * * *".to_string();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("expected 6 or 7"), "{err}");
+
+        config.schedule = "0 0 */6 * * * * *".to_string();
+        assert!(config.validate().is_err());
+
+        config.schedule = "0 0 25 * * *".to_string();
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("not a valid cron expression"), "{err}");
+    }
+
     // ---------------------------------------------------------------------
     // 2. Invalid cron schedule detection
     // ---------------------------------------------------------------------
@@ -306,7 +485,8 @@ mod tests {
         let config: DecentralizedBackupConfig =
             serde_json::from_str(json).expect("should deserialize with defaults");
 
-        assert_eq!(config.schedule, "0 */6 * * *");
+        assert_eq!(config.schedule, "0 0 */6 * * *");
+        assert!(config.validate().is_ok());
         assert_eq!(config.max_concurrent_uploads, 3);
         assert!(config.compression_enabled);
         assert_eq!(config.retention, None);
@@ -377,6 +557,7 @@ mod tests {
         match &config.provider {
             StorageProvider::Filecoin { deal_params, .. } => {
                 assert!(!deal_params.verified);
+                assert_eq!(deal_params.renew_before_epochs, 20_160);
             }
             other => panic!("Expected Filecoin, got {other:?}"),
         }
@@ -489,7 +670,7 @@ mod tests {
         use std::io::Read;
 
         let original = b"the quick brown fox jumps over the lazy dog";
-        let compressed = compress_data(original).expect("compression must succeed");
+        let compressed = compress_data(original, 6).expect("compression must succeed");
 
         assert_ne!(
             compressed,
@@ -508,7 +689,7 @@ mod tests {
 
     #[test]
     fn test_compress_data_gzip_magic_bytes() {
-        let compressed = compress_data(b"hello world").expect("compression must succeed");
+        let compressed = compress_data(b"hello world", 6).expect("compression must succeed");
         assert!(
             compressed.len() >= 2,
             "gzip output must have at least 2 bytes"
@@ -522,7 +703,7 @@ mod tests {
         use flate2::read::GzDecoder;
         use std::io::Read;
 
-        let compressed = compress_data(b"").expect("compressing empty input must succeed");
+        let compressed = compress_data(b"", 6).expect("compressing empty input must succeed");
         assert_eq!(compressed[0], 0x1f);
         assert_eq!(compressed[1], 0x8b);
 
@@ -538,7 +719,7 @@ mod tests {
         use std::io::Read;
 
         let original: Vec<u8> = (0..10_000).map(|i| (i % 256) as u8).collect();
-        let compressed = compress_data(&original).expect("compression must succeed");
+        let compressed = compress_data(&original, 6).expect("compression must succeed");
 
         let mut decoder = GzDecoder::new(&compressed[..]);
         let mut decompressed = Vec::new();
@@ -546,4 +727,1023 @@ mod tests {
 
         assert_eq!(decompressed, original);
     }
+
+    #[test]
+    fn test_compress_data_levels_produce_valid_gzip() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let original: Vec<u8> = (0..50_000u32)
+            .flat_map(|i| format!("ledger {} hash {:x}\n", i, i * 7919).into_bytes())
+            .collect();
+
+        let stored = compress_data(&original, 0).unwrap();
+        let fast = compress_data(&original, 1).unwrap();
+        let best = compress_data(&original, 9).unwrap();
+
+        for compressed in [&stored, &fast, &best] {
+            let mut decoder = GzDecoder::new(&compressed[..]);
+            let mut decompressed = Vec::new();
+            decoder.read_to_end(&mut decompressed).unwrap();
+            assert_eq!(decompressed, original);
+        }
+        assert!(stored.len() > original.len(), "level 0 only stores");
+        assert!(best.len() < fast.len(), "level 9 beats level 1");
+    }
+
+    #[test]
+    fn test_compression_level_default_and_range() {
+        let json =
+            r#"{"enabled":true,"provider":{"type":"ipfs","api_url":"http://localhost:5001"}}"#;
+        let mut config: DecentralizedBackupConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.compression_level, 6);
+
+        config.compression_level = 10;
+        assert!(config.validate().unwrap_err().contains("compressionLevel"));
+    }
+
+    // ---------------------------------------------------------------------
+    // 9. Fan-out to multiple providers
+    // ---------------------------------------------------------------------
+
+    fn segment_file(contents: &[u8]) -> (tempfile::NamedTempFile, ArchiveSegment) {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents).unwrap();
+        let segment = ArchiveSegment {
+            filename: "history-0000003f.xdr".to_string(),
+            path: file.path().to_string_lossy().to_string(),
+            hash: "abc123".to_string(),
+            ledger: 63,
+            segment_type: "history".to_string(),
+        };
+        (file, segment)
+    }
+
+    #[test]
+    fn test_s3_config_roundtrip() {
+        let mut config = arweave_config();
+        config.additional_providers = vec![StorageProvider::S3 {
+            bucket: "stellar-backups".to_string(),
+            prefix: "history/".to_string(),
+            region: Some("eu-west-1".to_string()),
+            endpoint: None,
+            force_path_style: false,
+            sse: None,
+        }];
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["additionalProviders"][0]["type"], "s3");
+        assert_eq!(json["additionalProviders"][0]["bucket"], "stellar-backups");
+
+        let restored: DecentralizedBackupConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config, restored);
+    }
+
+    #[test]
+    fn test_s3_path_style_config() {
+        let provider: StorageProvider = serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "bucket": "stellar-backups"
+        }))
+        .unwrap();
+        assert!(matches!(
+            provider,
+            StorageProvider::S3 {
+                force_path_style: false,
+                endpoint: None,
+                ..
+            }
+        ));
+
+        let provider: StorageProvider = serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "bucket": "stellar-backups",
+            "endpoint": "http://minio.storage.svc:9000",
+            "force_path_style": true
+        }))
+        .unwrap();
+        let StorageProvider::S3 {
+            endpoint,
+            force_path_style,
+            ..
+        } = provider
+        else {
+            panic!("expected S3 provider");
+        };
+        assert_eq!(endpoint.as_deref(), Some("http://minio.storage.svc:9000"));
+        assert!(force_path_style);
+    }
+
+    #[test]
+    fn test_s3_server_side_encryption_params() {
+        let provider: StorageProvider = serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "bucket": "stellar-backups",
+            "sse": { "algorithm": "aws:kms", "kms_key_id": "alias/stellar-backups" }
+        }))
+        .unwrap();
+        let StorageProvider::S3 { sse: Some(sse), .. } = provider else {
+            panic!("expected S3 provider with SSE");
+        };
+        assert_eq!(
+            sse_params(&sse),
+            (
+                ServerSideEncryption::AwsKms,
+                Some("alias/stellar-backups".to_string())
+            )
+        );
+
+        let aes: S3ServerSideEncryption =
+            serde_json::from_value(serde_json::json!({ "algorithm": "AES256" })).unwrap();
+        assert_eq!(sse_params(&aes), (ServerSideEncryption::Aes256, None));
+    }
+
+    #[test]
+    fn test_additional_providers_default_empty() {
+        let json =
+            r#"{"enabled":true,"provider":{"type":"ipfs","api_url":"http://localhost:5001"}}"#;
+        let config: DecentralizedBackupConfig = serde_json::from_str(json).unwrap();
+        assert!(config.additional_providers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_segment_fans_out_to_every_provider() {
+        let (_file, segment) = segment_file(b"ledger data");
+        let s3 = Arc::new(MockProvider::new());
+        let arweave = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![s3.clone(), arweave.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+
+        BackupScheduler::upload_segment(segment, providers, uploaded.clone(), Some(6))
+            .await
+            .unwrap();
+
+        let s3_uploads = s3.uploads.read().await;
+        let arweave_uploads = arweave.uploads.read().await;
+        assert_eq!(s3_uploads.len(), 1);
+        assert_eq!(arweave_uploads.len(), 1);
+        // Compressed once, identical bytes everywhere
+        assert_eq!(s3_uploads[0].0, arweave_uploads[0].0);
+        assert_eq!(&s3_uploads[0].0[..2], &[0x1f, 0x8b]);
+        assert_eq!(uploaded.read().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_second_run_of_identical_segment_hits_cache() {
+        let (file, segment) = segment_file(b"ledger data");
+        let provider = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+
+        BackupScheduler::upload_segment(
+            segment.clone(),
+            providers.clone(),
+            uploaded.clone(),
+            Some(6),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            uploaded.read().await.cid(0, &segment.hash),
+            Some("mock-cid-abc123")
+        );
+
+        // The cached segment is not read, compressed or uploaded again
+        drop(file);
+        BackupScheduler::upload_segment(segment, providers, uploaded, Some(6))
+            .await
+            .unwrap();
+        assert_eq!(provider.uploads.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_skips_providers_that_have_segment() {
+        let (_file, segment) = segment_file(b"ledger data");
+        let s3 = Arc::new(MockProvider::new());
+        let ipfs = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![s3.clone(), ipfs.clone()];
+        let mut cache = SegmentCache::default();
+        cache.insert(0, "abc123".to_string(), "mock-cid-abc123".to_string());
+        let uploaded = Arc::new(RwLock::new(cache));
+
+        BackupScheduler::upload_segment(segment.clone(), providers.clone(), uploaded.clone(), None)
+            .await
+            .unwrap();
+        assert!(s3.uploads.read().await.is_empty());
+        assert_eq!(ipfs.uploads.read().await.len(), 1);
+
+        // Second run has nothing left to do
+        BackupScheduler::upload_segment(segment, providers, uploaded, None)
+            .await
+            .unwrap();
+        assert_eq!(ipfs.uploads.read().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failing_provider_does_not_block_others() {
+        let (_file, segment) = segment_file(b"ledger data");
+        let s3 = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> =
+            vec![Arc::new(FailingProvider), s3.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+
+        let result = BackupScheduler::upload_segment(
+            segment.clone(),
+            providers.clone(),
+            uploaded.clone(),
+            None,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(s3.uploads.read().await.len(), 1);
+        // Only the failed provider is retried next time
+        assert!(!uploaded.read().await.contains(0, &segment.hash));
+        assert!(uploaded.read().await.contains(1, &segment.hash));
+    }
+
+    fn ledger_segment(ledger: u64) -> (tempfile::NamedTempFile, ArchiveSegment) {
+        let (file, mut segment) = segment_file(format!("ledger {ledger}").as_bytes());
+        segment.filename = format!("history-{ledger:08x}.xdr");
+        segment.hash = format!("hash-{ledger}");
+        segment.ledger = ledger;
+        (file, segment)
+    }
+
+    #[tokio::test]
+    async fn test_manifest_written_after_all_segments() {
+        let files: Vec<_> = [63, 127, 191].into_iter().map(ledger_segment).collect();
+        let segments: Vec<_> = files.iter().map(|(_, s)| s.clone()).collect();
+        let provider = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+
+        let manifest = BackupScheduler::upload_run(segments, &providers, &uploaded, Some(6), 2)
+            .await
+            .unwrap()
+            .expect("manifest for a non-empty run");
+
+        assert_eq!((manifest.ledger_start, manifest.ledger_end), (63, 191));
+        let uploads = provider.uploads.read().await;
+        assert_eq!(uploads.len(), 4);
+        assert!(uploads[..3]
+            .iter()
+            .all(|(_, m)| !crate::backup::manifest::is_manifest(&m.filename)));
+        assert_eq!(uploads[3].1.filename, manifest.filename());
+    }
+
+    #[tokio::test]
+    async fn test_small_segments_are_bundled_for_arweave() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let gateway = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/tx"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("tx-1"))
+            .mount(&gateway)
+            .await;
+        let arweave = ArweaveProvider::new(gateway.uri(), serde_json::json!({}))
+            .await
+            .unwrap()
+            .with_bundle_signer(ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]));
+        let files: Vec<_> = [63, 127, 191].into_iter().map(ledger_segment).collect();
+        let segments: Vec<_> = files.iter().map(|(_, s)| s.clone()).collect();
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![Arc::new(arweave)];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+
+        BackupScheduler::upload_run(segments, &providers, &uploaded, None, 2)
+            .await
+            .unwrap();
+
+        // One bundle for the segments, one transaction for the manifest
+        assert_eq!(gateway.received_requests().await.unwrap().len(), 2);
+        let cache = uploaded.read().await;
+        let ids: Vec<&str> = ["hash-63", "hash-127", "hash-191"]
+            .iter()
+            .filter_map(|hash| cache.cid(0, hash))
+            .collect();
+        assert_eq!(ids.len(), 3);
+        assert!(ids.iter().all(|id| *id != "tx-1"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_run_writes_no_manifest_and_removes_partial_uploads() {
+        let (_old_file, old) = ledger_segment(63);
+        let (_new_file, new) = ledger_segment(127);
+        let s3 = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> =
+            vec![s3.clone(), Arc::new(FailingProvider)];
+        let mut cache = SegmentCache::default();
+        cache.insert(0, old.hash.clone(), "earlier-run-cid".to_string());
+        let uploaded = Arc::new(RwLock::new(cache));
+
+        let result = BackupScheduler::upload_run(
+            vec![old.clone(), new.clone()],
+            &providers,
+            &uploaded,
+            None,
+            3,
+        )
+        .await;
+
+        assert!(result.is_err());
+        // Only this run's upload is removed, the earlier segment stays
+        assert_eq!(*s3.deleted.read().await, vec!["mock-cid-hash-127"]);
+        assert!(!uploaded.read().await.contains(0, &new.hash));
+        assert!(uploaded.read().await.contains(0, &old.hash));
+        assert!(s3
+            .uploads
+            .read()
+            .await
+            .iter()
+            .all(|(_, m)| !crate::backup::manifest::is_manifest(&m.filename)));
+    }
+
+    #[tokio::test]
+    async fn test_list_returns_recorded_uploads_by_prefix() {
+        let files: Vec<_> = [63, 127].into_iter().map(ledger_segment).collect();
+        let segments: Vec<_> = files.iter().map(|(_, s)| s.clone()).collect();
+        let provider = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+
+        let manifest = BackupScheduler::upload_run(segments, &providers, &uploaded, None, 2)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut history: Vec<_> = provider
+            .list("history-")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|e| e.filename)
+            .collect();
+        history.sort();
+        assert_eq!(
+            history,
+            vec!["history-0000003f.xdr", "history-0000007f.xdr"]
+        );
+
+        let manifests = provider.list("backup-").await.unwrap();
+        assert_eq!(manifests.len(), 1);
+        assert_eq!(manifests[0].filename, manifest.filename());
+        assert!(manifests[0].size.unwrap() > 0);
+
+        assert_eq!(provider.list("").await.unwrap().len(), 3);
+    }
+
+    /// Upload a one-segment backup taken `age_days` ago, returning its manifest
+    async fn stored_backup(provider: &MockProvider, ledger: u64, age_days: i64) -> BackupManifest {
+        let (_file, segment) = ledger_segment(ledger);
+        let created_at = Utc::now() - chrono::Duration::days(age_days);
+        let manifest =
+            BackupManifest::from_segments(&[segment.clone()], false, created_at).unwrap();
+
+        let metadata = UploadMetadata {
+            filename: segment.filename.clone(),
+            content_type: "application/octet-stream".to_string(),
+            size: 4,
+            sha256: segment.hash.clone(),
+            tags: vec![],
+        };
+        provider.upload(b"data".to_vec(), metadata).await.unwrap();
+        let (data, metadata) = manifest.to_upload().unwrap();
+        provider.upload(data, metadata).await.unwrap();
+        manifest
+    }
+
+    #[tokio::test]
+    async fn test_retention_deletes_expired_backup_cids() {
+        let provider = Arc::new(MockProvider::new());
+        let expired = stored_backup(&provider, 63, 30).await;
+        stored_backup(&provider, 127, 10).await;
+        stored_backup(&provider, 191, 1).await;
+        let (_, expired_manifest) = expired.to_upload().unwrap();
+
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let mut cache = SegmentCache::default();
+        cache.insert(0, "hash-63".to_string(), "mock-cid-hash-63".to_string());
+        cache.insert(0, "hash-127".to_string(), "mock-cid-hash-127".to_string());
+        let uploaded = Arc::new(RwLock::new(cache));
+        // Keep a week, but never fewer than the two newest backups
+        let policy = RetentionPolicy {
+            days: 7,
+            min_backups: 2,
+        };
+
+        BackupScheduler::apply_retention(&providers, &uploaded, &policy).await;
+
+        assert_eq!(
+            *provider.deleted.read().await,
+            vec![
+                "mock-cid-hash-63".to_string(),
+                format!("mock-cid-{}", expired_manifest.sha256),
+            ]
+        );
+        assert!(!uploaded.read().await.contains(0, "hash-63"));
+        assert!(uploaded.read().await.contains(0, "hash-127"));
+    }
+
+    /// Store the segments for `ledgers` and a manifest listing them
+    async fn stored_run(provider: &MockProvider, ledgers: &[u64], age_days: i64) -> BackupManifest {
+        let segments: Vec<_> = ledgers.iter().map(|l| ledger_segment(*l).1).collect();
+        let created_at = Utc::now() - chrono::Duration::days(age_days);
+        let manifest = BackupManifest::from_segments(&segments, false, created_at).unwrap();
+
+        for segment in &segments {
+            let stored = provider.list(&segment.filename).await.unwrap();
+            if !stored.is_empty() {
+                continue;
+            }
+            let metadata = UploadMetadata {
+                filename: segment.filename.clone(),
+                content_type: "application/octet-stream".to_string(),
+                size: 4,
+                sha256: segment.hash.clone(),
+                tags: vec![],
+            };
+            provider.upload(b"data".to_vec(), metadata).await.unwrap();
+        }
+        let (data, metadata) = manifest.to_upload().unwrap();
+        provider.upload(data, metadata).await.unwrap();
+        manifest
+    }
+
+    #[tokio::test]
+    async fn test_retention_keeps_segments_shared_with_retained_backups() {
+        let provider = Arc::new(MockProvider::new());
+        // The newer run reused ledger 127's segment from the cache
+        let expired = stored_run(&provider, &[63, 127], 30).await;
+        let kept = stored_run(&provider, &[127, 191], 1).await;
+        let (_, expired_manifest) = expired.to_upload().unwrap();
+
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let mut cache = SegmentCache::default();
+        for ledger in [63, 127, 191] {
+            cache.insert(
+                0,
+                format!("hash-{ledger}"),
+                format!("mock-cid-hash-{ledger}"),
+            );
+        }
+        let uploaded = Arc::new(RwLock::new(cache));
+        let policy = RetentionPolicy {
+            days: 7,
+            min_backups: 1,
+        };
+
+        BackupScheduler::apply_retention(&providers, &uploaded, &policy).await;
+
+        assert_eq!(
+            *provider.deleted.read().await,
+            vec![
+                "mock-cid-hash-63".to_string(),
+                format!("mock-cid-{}", expired_manifest.sha256),
+            ]
+        );
+        let cache = uploaded.read().await;
+        assert!(!cache.contains(0, "hash-63"));
+        assert!(cache.contains(0, "hash-127"));
+        assert!(cache.contains(0, "hash-191"));
+
+        // Nothing the retained backup lists was deleted
+        let deleted = provider.deleted.read().await;
+        for segment in &kept.segments {
+            assert!(!deleted.contains(&format!("mock-cid-{}", segment.sha256)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retention_with_zero_days_keeps_everything() {
+        let provider = Arc::new(MockProvider::new());
+        stored_backup(&provider, 63, 365).await;
+        let policy = RetentionPolicy {
+            days: 0,
+            min_backups: 0,
+        };
+
+        let removed = enforce_retention(provider.as_ref(), &policy, Utc::now())
+            .await
+            .unwrap();
+
+        assert!(removed.removed.is_empty());
+        assert!(provider.deleted.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retention_skips_providers_that_cannot_delete() {
+        // Unreachable gateway: skipping must not even list the provider
+        let arweave = ArweaveProvider::new("http://127.0.0.1:9".to_string(), serde_json::json!({}))
+            .await
+            .unwrap();
+        let policy = RetentionPolicy {
+            days: 1,
+            min_backups: 0,
+        };
+
+        let removed = enforce_retention(&arweave, &policy, Utc::now())
+            .await
+            .unwrap();
+
+        assert!(removed.removed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_manifest_uploaded_to_every_provider() {
+        let (_file, segment) = segment_file(b"ledger data");
+        let manifest = BackupManifest::from_segments(&[segment], true, Utc::now()).unwrap();
+        let s3 = Arc::new(MockProvider::new());
+        let arweave = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![s3.clone(), arweave.clone()];
+
+        BackupScheduler::upload_manifest(&manifest, &providers)
+            .await
+            .unwrap();
+
+        for provider in [s3, arweave] {
+            let uploads = provider.uploads.read().await;
+            assert_eq!(uploads.len(), 1);
+            assert_eq!(uploads[0].1.filename, manifest.filename());
+            assert_eq!(uploads[0].1.content_type, "application/json");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_manifest_upload_reports_failed_provider() {
+        let (_file, segment) = segment_file(b"ledger data");
+        let manifest = BackupManifest::from_segments(&[segment], true, Utc::now()).unwrap();
+        let providers: Vec<Arc<dyn StorageProviderTrait>> =
+            vec![Arc::new(MockProvider::new()), Arc::new(FailingProvider)];
+
+        assert!(BackupScheduler::upload_manifest(&manifest, &providers)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_backup_scheduler_with_multiple_providers() {
+        let mut config = arweave_config();
+        config.additional_providers = vec![StorageProvider::S3 {
+            bucket: "stellar-backups".to_string(),
+            prefix: String::new(),
+            region: None,
+            endpoint: None,
+            force_path_style: false,
+            sse: None,
+        }];
+        let arweave: Arc<dyn StorageProviderTrait> = Arc::new(MockProvider::new());
+        let s3: Arc<dyn StorageProviderTrait> = Arc::new(MockProvider::new());
+        let scheduler = BackupScheduler::with_providers(config, vec![arweave.clone(), s3.clone()]);
+
+        let scheduled = scheduler.providers();
+        assert_eq!(scheduled.len(), 2);
+        assert!(Arc::ptr_eq(&scheduled[0], &arweave));
+        assert!(Arc::ptr_eq(&scheduled[1], &s3));
+    }
+
+    #[tokio::test]
+    async fn test_upload_retries_transient_failures() {
+        let (_file, segment) = segment_file(b"ledger data");
+        let provider = Arc::new(FlakyProvider::new(2, 3));
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+
+        BackupScheduler::upload_segment(segment, providers, uploaded.clone(), None)
+            .await
+            .unwrap();
+
+        assert_eq!(provider.attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            uploaded.read().await.cid(0, "abc123"),
+            Some("flaky-cid-abc123")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_fails_after_retries_are_exhausted() {
+        let (_file, segment) = segment_file(b"ledger data");
+        let provider = Arc::new(FlakyProvider::new(3, 3));
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+
+        assert!(
+            BackupScheduler::upload_segment(segment, providers, uploaded.clone(), None)
+                .await
+                .is_err()
+        );
+        assert_eq!(provider.attempts.load(Ordering::SeqCst), 3);
+        assert!(uploaded.read().await.is_empty());
+    }
+
+    #[test]
+    fn test_retry_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(4), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failures_are_not_retried() {
+        let attempts = AtomicU32::new(0);
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        };
+        let result: Result<()> = with_retry(&policy, "upload", || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            anyhow::bail!("invalid request")
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_retry_policy_from_config() {
+        let config: DecentralizedBackupConfig = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "provider": { "type": "ipfs", "api_url": "http://localhost:5001" },
+            "retry": { "maxAttempts": 5, "initialBackoffSecs": 2 }
+        }))
+        .unwrap();
+        assert_eq!(
+            RetryPolicy::from(&config.retry),
+            RetryPolicy {
+                max_attempts: 5,
+                initial_backoff: Duration::from_secs(2),
+                max_backoff: Duration::from_secs(30),
+            }
+        );
+
+        let mut config = ipfs_config();
+        config.retry.max_attempts = 0;
+        assert!(config.validate().is_err());
+    }
+
+    fn recording_progress() -> (UploadProgress, Arc<std::sync::Mutex<Vec<(u64, u64)>>>) {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let progress: UploadProgress = Arc::new(move |sent, total| {
+            recorded.lock().unwrap().push((sent, total));
+        });
+        (progress, calls)
+    }
+
+    fn progress_metadata(size: usize) -> UploadMetadata {
+        UploadMetadata {
+            filename: "history-0000003f.xdr".to_string(),
+            content_type: "application/octet-stream".to_string(),
+            size,
+            sha256: "abc123".to_string(),
+            tags: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_progress_is_monotonic() {
+        let provider = MockProvider::new();
+        let (progress, calls) = recording_progress();
+
+        provider
+            .upload_with_progress(vec![0u8; 10], progress_metadata(10), Some(progress))
+            .await
+            .unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(*calls, vec![(4, 10), (8, 10), (10, 10)]);
+        assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[tokio::test]
+    async fn test_default_upload_progress_reports_start_and_end() {
+        let provider = FlakyProvider::new(0, 1);
+        let (progress, calls) = recording_progress();
+
+        provider
+            .upload_with_progress(vec![0u8; 10], progress_metadata(10), Some(progress))
+            .await
+            .unwrap();
+
+        assert_eq!(*calls.lock().unwrap(), vec![(0, 10), (10, 10)]);
+    }
+
+    #[tokio::test]
+    async fn test_segments_size_sums_readable_segments() {
+        let (_a, first) = segment_file(b"12345");
+        let (_b, second) = segment_file(b"123");
+        let mut missing = first.clone();
+        missing.path = "/nonexistent/history-0000007f.xdr".to_string();
+
+        assert_eq!(segments_size(&[first, second, missing]).await, 8);
+    }
+
+    /// Segment whose recorded hash is the real sha256 of its contents
+    fn hashed_segment(contents: &[u8]) -> (tempfile::NamedTempFile, ArchiveSegment) {
+        use sha2::{Digest, Sha256};
+
+        let (file, mut segment) = segment_file(contents);
+        segment.hash = format!("{:x}", Sha256::digest(contents));
+        (file, segment)
+    }
+
+    fn restore_check_config() -> DecentralizedBackupConfig {
+        let mut config = arweave_config();
+        config.verify_schedule = Some("0 30 3 * * *".to_string());
+        config
+    }
+
+    #[test]
+    fn test_restore_check_scheduling() {
+        let at = |ts: &str| {
+            chrono::DateTime::parse_from_rfc3339(ts)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let scheduler = BackupScheduler::new(restore_check_config(), Arc::new(MockProvider::new()));
+        assert_eq!(
+            scheduler
+                .next_restore_check(at("2026-01-01T04:00:00Z"))
+                .unwrap(),
+            Some(at("2026-01-02T03:30:00Z"))
+        );
+
+        let unscheduled = BackupScheduler::new(arweave_config(), Arc::new(MockProvider::new()));
+        assert_eq!(
+            unscheduled
+                .next_restore_check(at("2026-01-01T04:00:00Z"))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_invalid_verify_schedule_is_rejected() {
+        let mut config = restore_check_config();
+        config.verify_schedule = Some("30 3 * * *".to_string());
+        let err = config.validate().unwrap_err();
+        assert!(err.contains("verifySchedule"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_restore_check_records_last_verified() {
+        let (_file, segment) = hashed_segment(b"ledger data");
+        let provider = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+        let manifest =
+            BackupScheduler::upload_run(vec![segment], &providers, &uploaded, Some(6), 1)
+                .await
+                .unwrap()
+                .unwrap();
+
+        let scheduler = BackupScheduler::with_providers(restore_check_config(), providers);
+        assert_eq!(scheduler.restore_check_status().await.last_verified, None);
+
+        let status = scheduler.run_restore_check().await;
+        assert!(status.last_verified.is_some());
+        assert_eq!(status.last_verified_backup, Some(manifest.filename()));
+        assert_eq!(status.last_error, None);
+        assert_eq!(scheduler.restore_check_status().await, status);
+    }
+
+    #[tokio::test]
+    async fn test_restore_check_detects_corrupt_segment() {
+        let (_file, segment) = hashed_segment(b"ledger data");
+        let provider = Arc::new(MockProvider::new());
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![provider.clone()];
+        let uploaded = Arc::new(RwLock::new(SegmentCache::default()));
+        BackupScheduler::upload_run(vec![segment], &providers, &uploaded, None, 1)
+            .await
+            .unwrap();
+        provider.uploads.write().await[0].0 = b"bit rot".to_vec();
+
+        let scheduler = BackupScheduler::with_providers(restore_check_config(), providers);
+        let status = scheduler.run_restore_check().await;
+        assert_eq!(status.last_verified, None);
+        assert!(status.last_attempt.is_some());
+        assert!(status
+            .last_error
+            .as_deref()
+            .unwrap()
+            .contains("does not match its checksum"));
+    }
+
+    #[tokio::test]
+    async fn test_restore_check_without_backups_is_not_an_error() {
+        let scheduler = BackupScheduler::new(restore_check_config(), Arc::new(MockProvider::new()));
+        let status = scheduler.run_restore_check().await;
+        assert!(status.last_attempt.is_some());
+        assert_eq!(status.last_verified, None);
+        assert_eq!(status.last_error, None);
+    }
+
+    /// In-memory stand-in for a namespace's Secrets
+    #[derive(Default)]
+    struct FakeSecrets(BTreeMap<String, BTreeMap<String, Vec<u8>>>);
+
+    impl FakeSecrets {
+        fn with(mut self, name: &str, key: &str, value: &[u8]) -> Self {
+            self.0
+                .entry(name.to_string())
+                .or_default()
+                .insert(key.to_string(), value.to_vec());
+            self
+        }
+    }
+
+    #[async_trait]
+    impl SecretSource for FakeSecrets {
+        async fn get(&self, name: &str) -> Result<Option<BTreeMap<String, Vec<u8>>>> {
+            Ok(self.0.get(name).cloned())
+        }
+    }
+
+    const WALLET_JWK: &[u8] = br#"{"kty":"RSA","n":"abc","e":"AQAB"}"#;
+
+    #[tokio::test]
+    async fn test_resolve_secret_by_key_and_single_entry() {
+        let secrets = FakeSecrets::default()
+            .with("arweave-secret", "wallet.json", WALLET_JWK)
+            .with("pinata", "api-key", b"key")
+            .with("pinata", "api-secret", b"secret");
+
+        assert_eq!(
+            resolve_secret(&secrets, "arweave-secret").await.unwrap(),
+            WALLET_JWK
+        );
+        assert_eq!(
+            resolve_secret(&secrets, "pinata/api-secret").await.unwrap(),
+            b"secret"
+        );
+
+        let ambiguous = resolve_secret(&secrets, "pinata").await.unwrap_err();
+        assert!(ambiguous.to_string().contains("api-key, api-secret"));
+        assert!(resolve_secret(&secrets, "pinata/missing").await.is_err());
+        assert!(resolve_secret(&secrets, "absent").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pinning_api_key_is_loaded_from_secret() {
+        let secrets = FakeSecrets::default().with("pinata", "api-key", b"pinata-key\n");
+        let service = PinningService {
+            service_type: PinningServiceType::Pinata,
+            api_key_secret: "pinata/api-key".to_string(),
+        };
+
+        let pinning = resolve_pinning(&service, &secrets).await.unwrap();
+        assert_eq!(pinning.api_key, "pinata-key");
+        assert_eq!(pinning.service_url, "https://api.pinata.cloud/psa/pins");
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_from_config_loads_provider_secrets() {
+        let secrets = FakeSecrets::default().with("arweave-secret", "wallet.json", WALLET_JWK);
+        let mut config = arweave_config();
+        config.additional_providers = vec![StorageProvider::IPFS {
+            api_url: "http://ipfs:5001".to_string(),
+            pinning_service: None,
+            gateways: vec!["https://ipfs.io".to_string()],
+        }];
+
+        assert!(BackupScheduler::from_config(config, &secrets).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_from_config_fails_without_secret() {
+        let secrets = FakeSecrets::default().with("arweave-secret", "wallet.json", b"not json");
+        assert!(BackupScheduler::from_config(arweave_config(), &secrets)
+            .await
+            .is_err());
+        assert!(
+            BackupScheduler::from_config(arweave_config(), &FakeSecrets::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_factory_builds_each_provider_variant() {
+        let secrets = FakeSecrets::default()
+            .with("arweave-secret", "wallet.json", WALLET_JWK)
+            .with("pinata-key", "api-key", b"pinata-key");
+        let cases = [
+            (arweave_config().provider, "ArweaveProvider"),
+            (ipfs_config().provider, "IPFSProvider"),
+            (filecoin_config().provider, "FilecoinProvider"),
+            (
+                StorageProvider::S3 {
+                    bucket: "stellar-backups".to_string(),
+                    prefix: String::new(),
+                    region: Some("us-east-1".to_string()),
+                    endpoint: Some("http://minio:9000".to_string()),
+                    force_path_style: true,
+                    sse: None,
+                },
+                "S3Provider",
+            ),
+        ];
+
+        for (config, expected) in cases {
+            let provider = build_provider(&config, RetryPolicy::default(), &secrets)
+                .await
+                .unwrap();
+            assert!(
+                provider.name().ends_with(expected),
+                "{} should be {expected}",
+                provider.name()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bundle_key_must_be_an_ed25519_seed() {
+        let secrets = FakeSecrets::default()
+            .with("bundle", "seed", &[9u8; 32])
+            .with("bundle", "short", &[9u8; 16]);
+
+        let key = resolve_bundle_key("bundle/seed", &secrets).await.unwrap();
+        assert_eq!(key.to_bytes(), [9u8; 32]);
+        let err = resolve_bundle_key("bundle/short", &secrets)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("16 bytes"));
+    }
+
+    /// Deal-based provider whose deal check returns a fixed outcome
+    struct DealProvider {
+        status: Option<DealStatus>,
+    }
+
+    #[async_trait]
+    impl StorageProviderTrait for DealProvider {
+        async fn upload(&self, _data: Vec<u8>, metadata: UploadMetadata) -> Result<String> {
+            Ok(metadata.filename)
+        }
+
+        async fn exists(&self, _content_hash: &str) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn verify(&self, _cid: &str, _expected_hash: &str) -> Result<bool> {
+            Ok(true)
+        }
+
+        async fn list(&self, _prefix: &str) -> Result<Vec<BackupEntry>> {
+            Ok(vec![])
+        }
+
+        async fn delete(&self, _cid: &str) -> Result<()> {
+            Ok(())
+        }
+
+        async fn renew_expiring(&self) -> Result<Option<DealStatus>> {
+            self.status
+                .clone()
+                .map(Some)
+                .ok_or_else(|| anyhow::anyhow!("lotus unreachable"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deal_status_recorded_for_deal_based_providers() {
+        let status = DealStatus {
+            head_epoch: 100_000,
+            active_deals: 3,
+            expiring: 1,
+            renewed: vec!["bafy-expiring".to_string()],
+            next_expiry_epoch: Some(110_000),
+        };
+        let providers: Vec<Arc<dyn StorageProviderTrait>> = vec![
+            Arc::new(MockProvider::new()),
+            Arc::new(DealProvider {
+                status: Some(status.clone()),
+            }),
+        ];
+        let scheduler = BackupScheduler::with_providers(filecoin_config(), providers);
+        assert!(scheduler.deal_status().await.is_empty());
+
+        assert_eq!(scheduler.renew_deals().await, vec![(1, status.clone())]);
+        assert_eq!(scheduler.deal_status().await, vec![(1, status)]);
+
+        let json = serde_json::to_value(&scheduler.deal_status().await[0].1).unwrap();
+        assert_eq!(json["nextExpiryEpoch"], 110_000);
+    }
+
+    #[tokio::test]
+    async fn test_failed_deal_check_records_no_status() {
+        let scheduler =
+            BackupScheduler::new(filecoin_config(), Arc::new(DealProvider { status: None }));
+        assert!(scheduler.renew_deals().await.is_empty());
+    }
 }
@@ -1,6 +1,17 @@
 use kube::CustomResourceExt;
-use stellar_k8s::crd::StellarNode;
+use stellar_k8s::crd::{StellarNetworkConfig, StellarNode, StellarNodeTemplate};
 
 fn main() {
-    print!("{}", serde_yaml::to_string(&StellarNode::crd()).unwrap());
+    let crd = match std::env::args().nth(1).as_deref() {
+        None | Some("stellarnode") => StellarNode::crd(),
+        Some("stellarnodetemplate") => StellarNodeTemplate::crd(),
+        Some("stellarnetworkconfig") => StellarNetworkConfig::crd(),
+        Some(other) => {
+            eprintln!(
+                "unknown CRD '{other}', expected stellarnode, stellarnodetemplate or stellarnetworkconfig"
+            );
+            std::process::exit(2);
+        }
+    };
+    print!("{}", serde_yaml::to_string(&crd).unwrap());
 }
@@ -159,6 +159,16 @@ pub struct RunArgs {
     #[arg(long, env = "OPERATOR_NAMESPACE", default_value = "default")]
     pub namespace: String,
 
+    /// Path to a kubeconfig file used to connect to the cluster.
+    ///
+    /// When unset, the configuration is inferred from KUBECONFIG, ~/.kube/config or the
+    /// in-cluster service account. Useful for local development outside a cluster.
+    /// Env: KUBECONFIG_PATH
+    ///
+    /// Example: --kubeconfig ~/.kube/kind-stellar.yaml
+    #[arg(long, env = "KUBECONFIG_PATH")]
+    pub kubeconfig: Option<std::path::PathBuf>,
+
     /// Restrict the operator to only watch and manage StellarNode resources in a specific namespace.
     ///
     /// When unset (default), the operator watches all namespaces and requires cluster-wide RBAC.
@@ -181,6 +191,19 @@ pub struct RunArgs {
     #[arg(long, env = "DRY_RUN")]
     pub dry_run: bool,
 
+    /// Do not add the StellarNode finalizer to managed resources.
+    ///
+    /// For restricted environments where finalizers would leave deletions stuck
+    /// while the operator is down. Cleanup becomes best-effort: owned resources are
+    /// garbage collected by Kubernetes, but retained PVCs and external resources are
+    /// not removed. Existing finalizers are stripped on the next reconcile.
+    /// Individual nodes can opt out with the `stellar.org/disable-finalizer: "true"` annotation.
+    /// Env: DISABLE_FINALIZERS
+    ///
+    /// Example: --disable-finalizers
+    #[arg(long, env = "DISABLE_FINALIZERS")]
+    pub disable_finalizers: bool,
+
     /// Run the latency-aware scheduler instead of the standard operator reconciler.
     ///
     /// The scheduler assigns pending pods to nodes based on measured network latency
@@ -488,6 +511,13 @@ mod cli_tests {
         assert!(args.dry_run);
     }
 
+    #[test]
+    fn run_disable_finalizers_flag() {
+        assert!(!parse_run(&[]).unwrap().disable_finalizers);
+        let args = parse_run(&["--disable-finalizers"]).unwrap();
+        assert!(args.disable_finalizers);
+    }
+
     #[test]
     fn run_scheduler_flag() {
         let args = parse_run(&["--scheduler"]).unwrap();
@@ -506,6 +536,15 @@ mod cli_tests {
         assert!(args.enable_mtls);
     }
 
+    #[test]
+    fn run_kubeconfig_flag() {
+        let args = parse_run(&["--kubeconfig", "/tmp/kind.yaml"]).unwrap();
+        assert_eq!(
+            args.kubeconfig,
+            Some(std::path::PathBuf::from("/tmp/kind.yaml"))
+        );
+    }
+
     #[test]
     fn run_dump_config_flag() {
         let args = parse_run(&["--dump-config"]).unwrap();
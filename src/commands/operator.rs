@@ -2,6 +2,8 @@ use chrono::Utc;
 use k8s_openapi::api::coordination::v1::Lease;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::MicroTime;
 use kube::api::{Api, ObjectMeta, Patch, PatchParams, PostParams};
+use kube::config::{KubeConfigOptions, Kubeconfig};
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tracing::{info, info_span, warn, Instrument, Level};
@@ -19,6 +21,56 @@ const LEASE_DURATION_SECS: i32 = 15;
 const RENEW_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
 const RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
 
+/// Guidance appended when no cluster configuration can be inferred
+const NO_CLUSTER_CONFIG_HINT: &str = "no in-cluster service account or local kubeconfig was found; \
+     when running outside a cluster, pass --kubeconfig <path> (env KUBECONFIG_PATH) or set KUBECONFIG";
+
+/// Where the Kubernetes client configuration is loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ClientConfigSource {
+    /// Explicit kubeconfig file from --kubeconfig / KUBECONFIG_PATH
+    Kubeconfig(PathBuf),
+    /// kube's default inference: KUBECONFIG, ~/.kube/config, then in-cluster
+    Inferred,
+}
+
+/// Pick the client configuration source; an empty path counts as unset.
+fn client_config_source(kubeconfig: Option<&Path>) -> ClientConfigSource {
+    match kubeconfig {
+        Some(path) if !path.as_os_str().is_empty() => {
+            ClientConfigSource::Kubeconfig(path.to_path_buf())
+        }
+        _ => ClientConfigSource::Inferred,
+    }
+}
+
+/// Build a Kubernetes client, turning configuration failures into actionable errors.
+async fn create_client(source: &ClientConfigSource) -> Result<kube::Client, Error> {
+    let config = match source {
+        ClientConfigSource::Kubeconfig(path) => {
+            let kubeconfig = Kubeconfig::read_from(path).map_err(|e| {
+                Error::config_step(
+                    "load kubeconfig",
+                    format!("cannot read {}: {e}", path.display()),
+                )
+            })?;
+            kube::Config::from_custom_kubeconfig(kubeconfig, &KubeConfigOptions::default())
+                .await
+                .map_err(|e| {
+                    Error::config_step("load kubeconfig", format!("{}: {e}", path.display()))
+                })?
+        }
+        ClientConfigSource::Inferred => kube::Config::infer().await.map_err(|e| {
+            Error::config_step(
+                "connect to kubernetes",
+                format!("{e}; {NO_CLUSTER_CONFIG_HINT}"),
+            )
+        })?,
+    };
+
+    kube::Client::try_from(config).map_err(Error::KubeError)
+}
+
 pub async fn run_operator(args: RunArgs) -> Result<(), Error> {
     // Handle --dump-config: print resolved configuration and exit.
     if args.dump_config {
@@ -26,9 +78,11 @@ pub async fn run_operator(args: RunArgs) -> Result<(), Error> {
         let resolved = serde_json::json!({
             "cli": {
                 "namespace": args.namespace,
+                "kubeconfig": args.kubeconfig,
                 "watch_namespace": args.watch_namespace,
                 "enable_mtls": args.enable_mtls,
                 "dry_run": args.dry_run,
+                "disable_finalizers": args.disable_finalizers,
                 "scheduler": args.scheduler,
                 "scheduler_name": args.scheduler_name,
                 "retry_budget_retriable_secs": args.retry_budget_retriable_secs,
@@ -118,9 +172,7 @@ pub async fn run_operator(args: RunArgs) -> Result<(), Error> {
     }
 
     // Initialize Kubernetes client
-    let client = kube::Client::try_default()
-        .await
-        .map_err(Error::KubeError)?;
+    let client = create_client(&client_config_source(args.kubeconfig.as_deref())).await?;
 
     info!("Connected to Kubernetes cluster");
 
@@ -204,7 +256,7 @@ pub async fn run_operator(args: RunArgs) -> Result<(), Error> {
         );
     }
 
-    // Update leader-status and uptime metrics every 10 s
+    // Update leader-status, uptime and process usage metrics every 10 s
     #[cfg(feature = "metrics")]
     {
         let is_leader_metrics = Arc::clone(&is_leader);
@@ -215,12 +267,25 @@ pub async fn run_operator(args: RunArgs) -> Result<(), Error> {
                 let leader = is_leader_metrics.load(Ordering::Relaxed);
                 controller::metrics::set_leader_status(leader);
                 controller::metrics::inc_uptime_seconds(10);
+                controller::metrics::update_process_metrics();
             }
         });
     }
 
     // Create shared controller state
     let operator_config = controller::OperatorConfig::load();
+    if operator_config.grafana_dashboard.enabled {
+        let dashboard_ns = operator_config
+            .grafana_dashboard
+            .namespace
+            .clone()
+            .unwrap_or_else(|| namespace.clone());
+        if let Err(e) =
+            controller::grafana_dashboard::ensure_dashboard(&client, &dashboard_ns).await
+        {
+            warn!("Failed to publish Grafana dashboard: {:?}", e);
+        }
+    }
     #[cfg(feature = "rest-api")]
     let oidc_config = operator_config.oidc.clone();
     let audit_log = Arc::new(controller::AuditLog::new());
@@ -254,6 +319,7 @@ pub async fn run_operator(args: RunArgs) -> Result<(), Error> {
         watch_namespace: args.watch_namespace.clone(),
         mtls_config: mtls_config.clone(),
         dry_run: args.dry_run,
+        disable_finalizers: args.disable_finalizers,
         retry_budget_retriable_secs: args.retry_budget_retriable_secs,
         retry_budget_nonretriable_secs: args.retry_budget_nonretriable_secs,
         retry_budget_max_attempts: args.retry_budget_max_attempts,
@@ -282,7 +348,10 @@ pub async fn run_operator(args: RunArgs) -> Result<(), Error> {
 
     // Start the peer discovery manager
     let peer_discovery_client = client.clone();
-    let peer_discovery_config = controller::PeerDiscoveryConfig::default();
+    let peer_discovery_config = controller::PeerDiscoveryConfig {
+        max_peers: state.operator_config.max_known_peers,
+        ..Default::default()
+    };
     tokio::spawn(
         async move {
             let manager =
@@ -446,6 +515,25 @@ pub async fn run_operator(args: RunArgs) -> Result<(), Error> {
         info!("Auto-snapshot worker spawned");
     }
 
+    {
+        let gc_client = client.clone();
+        let gc_is_leader = Arc::clone(&is_leader);
+        let gc_namespace = args.watch_namespace.clone();
+        let gc_dry_run = args.dry_run;
+        tokio::spawn(
+            async move {
+                controller::orphan_gc::run_orphan_gc_worker(
+                    gc_client,
+                    gc_is_leader,
+                    gc_namespace,
+                    gc_dry_run,
+                )
+                .await;
+            }
+            .instrument(root_span.clone()),
+        );
+    }
+
     // Start the snapshot integrity checker background worker
     {
         use stellar_k8s::controller::snapshot_integrity::{
@@ -645,3 +733,40 @@ async fn try_acquire_or_renew(
         Err(e) => Err(e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_kubeconfig_path_infers_config() {
+        assert_eq!(client_config_source(None), ClientConfigSource::Inferred);
+    }
+
+    #[test]
+    fn test_empty_kubeconfig_path_infers_config() {
+        assert_eq!(
+            client_config_source(Some(Path::new(""))),
+            ClientConfigSource::Inferred
+        );
+    }
+
+    #[test]
+    fn test_explicit_kubeconfig_path_is_used() {
+        assert_eq!(
+            client_config_source(Some(Path::new("/home/dev/.kube/kind.yaml"))),
+            ClientConfigSource::Kubeconfig(PathBuf::from("/home/dev/.kube/kind.yaml"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_kubeconfig_file_names_the_path() {
+        let source = ClientConfigSource::Kubeconfig(PathBuf::from("/nonexistent/kubeconfig"));
+        let err = create_client(&source).await.unwrap_err();
+
+        assert!(matches!(err, Error::ConfigError(_)));
+        let msg = err.to_string();
+        assert!(msg.contains("[load kubeconfig]"));
+        assert!(msg.contains("/nonexistent/kubeconfig"));
+    }
+}
@@ -172,6 +172,18 @@ pub async fn check_history_archive_health(
 /// Ledger lag threshold above which an archive is considered significantly behind
 pub const ARCHIVE_LAG_THRESHOLD: u64 = 20;
 
+/// Effective archive lag threshold for a node.
+///
+/// Uses `spec.validatorConfig.archiveLagThreshold` when set, falling back to
+/// [`ARCHIVE_LAG_THRESHOLD`].
+pub fn archive_lag_threshold(node: &crate::crd::StellarNode) -> u64 {
+    node.spec
+        .validator_config
+        .as_ref()
+        .and_then(|vc| vc.archive_lag_threshold)
+        .unwrap_or(ARCHIVE_LAG_THRESHOLD)
+}
+
 /// Relevant subset of stellar-history.json needed for integrity checks
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -191,22 +203,22 @@ pub struct ArchiveIntegrityResult {
     pub node_ledger: u64,
     /// Number of ledgers the archive is behind the node (`None` if archive is unavailable)
     pub lag: Option<u64>,
+    /// Maximum tolerated lag before the archive is considered behind
+    pub lag_threshold: u64,
     /// Error message if the check failed
     pub error: Option<String>,
 }
 
 impl ArchiveIntegrityResult {
-    /// Returns `true` when the archive is reachable and its lag is below the threshold
+    /// Returns `true` when the archive is reachable and its lag is within the threshold
     pub fn is_healthy(&self) -> bool {
-        self.lag
-            .map(|l| l <= ARCHIVE_LAG_THRESHOLD)
-            .unwrap_or(false)
+        self.lag.map(|l| l <= self.lag_threshold).unwrap_or(false)
     }
 
     /// Human-readable summary for status conditions
     pub fn summary(&self) -> String {
         match (self.archive_ledger, self.lag) {
-            (Some(al), Some(lag)) if lag <= ARCHIVE_LAG_THRESHOLD => {
+            (Some(al), Some(lag)) if lag <= self.lag_threshold => {
                 format!(
                     "archive {} is healthy (archive_ledger={}, node_ledger={}, lag={})",
                     self.url, al, self.node_ledger, lag
@@ -263,6 +275,7 @@ async fn fetch_archive_ledger(client: &Client, url: &str, timeout: Duration) ->
 /// # Arguments
 /// * `urls` - Archive URLs to check (all are checked in parallel)
 /// * `node_ledger` - The current ledger sequence of the validator node
+/// * `lag_threshold` - Maximum tolerated lag in ledgers (see [`archive_lag_threshold`])
 /// * `timeout` - Per-URL HTTP timeout (default: 10 s)
 ///
 /// # Returns
@@ -270,6 +283,7 @@ async fn fetch_archive_ledger(client: &Client, url: &str, timeout: Duration) ->
 pub async fn check_archive_integrity(
     urls: &[String],
     node_ledger: u64,
+    lag_threshold: u64,
     timeout: Option<Duration>,
 ) -> Vec<ArchiveIntegrityResult> {
     if urls.is_empty() {
@@ -296,6 +310,7 @@ pub async fn check_archive_integrity(
                     archive_ledger: None,
                     node_ledger,
                     lag: None,
+                    lag_threshold,
                     error: Some(format!("client build error: {e}")),
                 })
                 .collect();
@@ -314,6 +329,7 @@ pub async fn check_archive_integrity(
                         archive_ledger: Some(archive_ledger),
                         node_ledger,
                         lag: Some(lag),
+                        lag_threshold,
                         error: None,
                     }
                 }
@@ -324,6 +340,7 @@ pub async fn check_archive_integrity(
                         archive_ledger: None,
                         node_ledger,
                         lag: None,
+                        lag_threshold,
                         error: Some(e.to_string()),
                     }
                 }
@@ -483,6 +500,7 @@ mod tests {
             archive_ledger,
             node_ledger,
             lag,
+            lag_threshold: ARCHIVE_LAG_THRESHOLD,
             error,
         }
     }
@@ -517,6 +535,45 @@ mod tests {
         assert!(result.is_healthy());
     }
 
+    #[test]
+    fn test_integrity_threshold_override_relaxes_decision() {
+        // 30 ledgers behind is degraded under the default but healthy with a 50-ledger override
+        let mut result = make_integrity_result(Some(970), 1000);
+        assert!(!result.is_healthy());
+
+        result.lag_threshold = 50;
+        assert!(result.is_healthy());
+        assert!(result.summary().contains("is healthy"));
+    }
+
+    #[test]
+    fn test_integrity_threshold_override_tightens_decision() {
+        // 10 ledgers behind is healthy under the default but degraded with a 5-ledger override
+        let mut result = make_integrity_result(Some(990), 1000);
+        assert!(result.is_healthy());
+
+        result.lag_threshold = 5;
+        assert!(!result.is_healthy());
+        assert!(result.summary().contains("is lagging by 10 ledgers"));
+    }
+
+    #[test]
+    fn test_archive_lag_threshold_defaults_and_override() {
+        use crate::crd::{StellarNode, StellarNodeSpec, ValidatorConfig};
+
+        let mut node = StellarNode::new("validator-1", StellarNodeSpec::default());
+        assert_eq!(archive_lag_threshold(&node), ARCHIVE_LAG_THRESHOLD);
+
+        node.spec.validator_config = Some(ValidatorConfig::default());
+        assert_eq!(archive_lag_threshold(&node), ARCHIVE_LAG_THRESHOLD);
+
+        node.spec.validator_config = Some(ValidatorConfig {
+            archive_lag_threshold: Some(100),
+            ..Default::default()
+        });
+        assert_eq!(archive_lag_threshold(&node), 100);
+    }
+
     #[test]
     fn test_integrity_unreachable_archive_is_unhealthy() {
         let result = make_integrity_result(None, 1000);
@@ -527,7 +584,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_check_archive_integrity_empty_urls() {
-        let results = check_archive_integrity(&[], 1000, None).await;
+        let results = check_archive_integrity(&[], 1000, ARCHIVE_LAG_THRESHOLD, None).await;
         assert!(results.is_empty());
     }
 
@@ -538,6 +595,7 @@ mod tests {
         let results = check_archive_integrity(
             &urls,
             1000,
+            ARCHIVE_LAG_THRESHOLD,
             Some(Duration::from_millis(200)), // short timeout to keep the test fast
         )
         .await;
@@ -558,7 +616,7 @@ mod tests {
             .await;
 
         let urls = vec![mock_server.uri()];
-        let results = check_archive_integrity(&urls, 1000, None).await;
+        let results = check_archive_integrity(&urls, 1000, ARCHIVE_LAG_THRESHOLD, None).await;
         assert_eq!(results.len(), 1);
         assert!(!results[0].is_healthy());
         assert!(results[0].archive_ledger.is_none());
@@ -582,7 +640,8 @@ mod tests {
 
         let urls = vec![mock_server.uri()];
         let node_ledger = 1000;
-        let results = check_archive_integrity(&urls, node_ledger, None).await;
+        let results =
+            check_archive_integrity(&urls, node_ledger, ARCHIVE_LAG_THRESHOLD, None).await;
         assert_eq!(results.len(), 1);
         assert!(results[0].is_healthy());
         assert_eq!(results[0].archive_ledger, Some(990));
@@ -603,7 +662,8 @@ mod tests {
 
         let urls = vec![mock_server.uri()];
         let node_ledger = 1000;
-        let results = check_archive_integrity(&urls, node_ledger, None).await;
+        let results =
+            check_archive_integrity(&urls, node_ledger, ARCHIVE_LAG_THRESHOLD, None).await;
         assert_eq!(results.len(), 1);
         assert!(!results[0].is_healthy());
         assert_eq!(results[0].archive_ledger, Some(970));
@@ -624,7 +684,8 @@ mod tests {
 
         let urls = vec![mock_server.uri()];
         let node_ledger = 1000;
-        let results = check_archive_integrity(&urls, node_ledger, None).await;
+        let results =
+            check_archive_integrity(&urls, node_ledger, ARCHIVE_LAG_THRESHOLD, None).await;
         assert_eq!(results.len(), 1);
         assert!(results[0].is_healthy());
         assert_eq!(results[0].archive_ledger, Some(1010));
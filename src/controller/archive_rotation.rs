@@ -0,0 +1,202 @@
+//! Automatic history archive rotation
+//!
+//! The first URL in `spec.validatorConfig.historyArchiveUrls` is the
+//! validator's primary archive until the operator says otherwise. Each periodic
+//! integrity check records, per URL, how many checks in a row found the archive
+//! lagging or unreachable. Once the primary has been behind for
+//! `archiveRotation.consecutiveChecks` checks, the healthiest other archive is
+//! recorded as `status.primaryHistoryArchive`.
+//!
+//! The spec is never rewritten, so GitOps tools see no drift. The recorded
+//! primary is rendered first into `stellar-core.cfg` and into the
+//! [`PRIMARY_ARCHIVE_ANNOTATION`] on the pod template, which rolls the pods.
+
+use super::archive_health::ArchiveIntegrityResult;
+use crate::crd::types::ArchiveLagStrike;
+
+/// Pod template annotation recording the primary history archive
+pub const PRIMARY_ARCHIVE_ANNOTATION: &str = "stellar.org/primary-history-archive";
+
+/// A decision to replace the primary history archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveRotation {
+    /// Primary archive that stayed behind
+    pub from: String,
+    /// Healthier archive promoted to primary
+    pub to: String,
+}
+
+/// Configured archive URLs with the recorded `primary` moved to the front.
+///
+/// A primary that is no longer configured is ignored, falling back to the
+/// spec order.
+pub fn archive_order(urls: &[String], primary: Option<&str>) -> Vec<String> {
+    let mut ordered = urls.to_vec();
+    if let Some(idx) = primary.and_then(|p| ordered.iter().position(|u| u == p)) {
+        let primary = ordered.remove(idx);
+        ordered.insert(0, primary);
+    }
+    ordered
+}
+
+/// Count consecutive lagging checks per URL from the latest integrity results.
+///
+/// Healthy archives are dropped from the list; lagging or unreachable ones have
+/// their previous count incremented.
+pub fn update_lag_strikes(
+    previous: &[ArchiveLagStrike],
+    results: &[ArchiveIntegrityResult],
+) -> Vec<ArchiveLagStrike> {
+    results
+        .iter()
+        .filter(|r| !r.is_healthy())
+        .map(|r| {
+            let before = previous
+                .iter()
+                .find(|s| s.url == r.url)
+                .map(|s| s.consecutive_lagging_checks)
+                .unwrap_or(0);
+            ArchiveLagStrike {
+                url: r.url.clone(),
+                consecutive_lagging_checks: before + 1,
+            }
+        })
+        .collect()
+}
+
+/// Decide whether the primary archive should be rotated.
+///
+/// `urls` is the current order, primary first (see [`archive_order`]). Returns
+/// `None` unless the primary has at least `required_checks` strikes and
+/// another configured archive is currently healthy. The replacement is the
+/// healthy archive with the smallest lag, ties broken by configuration order.
+pub fn rotation_decision(
+    urls: &[String],
+    strikes: &[ArchiveLagStrike],
+    results: &[ArchiveIntegrityResult],
+    required_checks: u32,
+) -> Option<ArchiveRotation> {
+    let primary = urls.first()?;
+    let primary_strikes = strikes
+        .iter()
+        .find(|s| &s.url == primary)
+        .map(|s| s.consecutive_lagging_checks)
+        .unwrap_or(0);
+    if primary_strikes < required_checks.max(1) {
+        return None;
+    }
+
+    let replacement = urls[1..]
+        .iter()
+        .filter_map(|url| {
+            results
+                .iter()
+                .find(|r| &r.url == url && r.is_healthy())
+                .map(|r| (url, r.lag.unwrap_or(0)))
+        })
+        .min_by_key(|(_, lag)| *lag)
+        .map(|(url, _)| url.clone())?;
+
+    Some(ArchiveRotation {
+        from: primary.clone(),
+        to: replacement,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::archive_health::ARCHIVE_LAG_THRESHOLD;
+
+    fn result(url: &str, lag: Option<u64>) -> ArchiveIntegrityResult {
+        ArchiveIntegrityResult {
+            url: url.to_string(),
+            archive_ledger: lag.map(|l| 1000 - l),
+            node_ledger: 1000,
+            lag,
+            lag_threshold: ARCHIVE_LAG_THRESHOLD,
+            error: if lag.is_none() {
+                Some("connection refused".to_string())
+            } else {
+                None
+            },
+        }
+    }
+
+    fn strike(url: &str, n: u32) -> ArchiveLagStrike {
+        ArchiveLagStrike {
+            url: url.to_string(),
+            consecutive_lagging_checks: n,
+        }
+    }
+
+    fn urls() -> Vec<String> {
+        vec!["https://a".into(), "https://b".into(), "https://c".into()]
+    }
+
+    #[test]
+    fn test_strikes_increment_and_reset() {
+        let previous = vec![strike("https://a", 2), strike("https://b", 1)];
+        let results = vec![
+            result("https://a", Some(100)),
+            result("https://b", Some(0)),
+            result("https://c", None),
+        ];
+
+        let strikes = update_lag_strikes(&previous, &results);
+        assert_eq!(
+            strikes,
+            vec![strike("https://a", 3), strike("https://c", 1)]
+        );
+    }
+
+    #[test]
+    fn test_no_rotation_before_required_checks() {
+        let results = vec![result("https://a", Some(100)), result("https://b", Some(0))];
+        let strikes = vec![strike("https://a", 2)];
+        assert!(rotation_decision(&urls(), &strikes, &results, 3).is_none());
+    }
+
+    #[test]
+    fn test_rotates_to_least_lagging_healthy_archive() {
+        let results = vec![
+            result("https://a", Some(100)),
+            result("https://b", Some(10)),
+            result("https://c", Some(2)),
+        ];
+        let strikes = vec![strike("https://a", 3)];
+
+        let rotation = rotation_decision(&urls(), &strikes, &results, 3).unwrap();
+        assert_eq!(rotation.from, "https://a");
+        assert_eq!(rotation.to, "https://c");
+    }
+
+    #[test]
+    fn test_no_rotation_when_no_healthy_alternative() {
+        let results = vec![
+            result("https://a", Some(100)),
+            result("https://b", None),
+            result("https://c", Some(50)),
+        ];
+        let strikes = vec![strike("https://a", 5)];
+        assert!(rotation_decision(&urls(), &strikes, &results, 3).is_none());
+    }
+
+    #[test]
+    fn test_lagging_secondary_does_not_trigger_rotation() {
+        let results = vec![result("https://a", Some(0)), result("https://b", Some(100))];
+        let strikes = vec![strike("https://b", 10)];
+        assert!(rotation_decision(&urls(), &strikes, &results, 3).is_none());
+    }
+
+    #[test]
+    fn test_archive_order_puts_recorded_primary_first() {
+        assert_eq!(archive_order(&urls(), None), urls());
+        assert_eq!(
+            archive_order(&urls(), Some("https://c")),
+            vec!["https://c", "https://a", "https://b"]
+        );
+        // A primary dropped from the spec no longer applies
+        assert_eq!(archive_order(&urls(), Some("https://gone")), urls());
+    }
+}
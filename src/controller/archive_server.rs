@@ -0,0 +1,504 @@
+//! Shared history archive served over HTTP
+//!
+//! With `spec.validatorConfig.archiveServer` set, a validator publishes its
+//! history to a dedicated `<name>-archive` PVC through a `[HISTORY.local]`
+//! archive in its stellar-core.cfg, and an nginx sidecar serves the volume
+//! read-only behind a `<name>-archive` Service. Other nodes can then list
+//! `http://<name>-archive.<namespace>.svc.cluster.local` among their
+//! `historyArchiveUrls`.
+//!
+//! The sidecar shares the validator's pod, so the volume only needs
+//! `ReadWriteOnce`. An `archive-init` init container runs
+//! `stellar-core new-hist local` on an empty volume, so the archive is
+//! initialised before the first checkpoint is published.
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{
+    Capabilities, Container, ContainerPort, PersistentVolumeClaim, PersistentVolumeClaimSpec,
+    PersistentVolumeClaimVolumeSource, SeccompProfile, SecurityContext, Service, ServicePort,
+    ServiceSpec, Volume, VolumeMount, VolumeResourceRequirements,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::api::{Api, Patch, PatchParams};
+use kube::{Client, ResourceExt};
+use tracing::info;
+
+use super::pss::build_container_security_context;
+use super::resources::{delete_if_owned, owner_reference, resource_name, standard_labels};
+use crate::crd::{ArchiveServerConfig, NodeType, StellarNode};
+use crate::error::Result;
+
+/// Name of the archive volume in the validator pod
+pub const ARCHIVE_VOLUME: &str = "history-archive";
+
+/// Where stellar-core writes the archive
+pub const ARCHIVE_MOUNT_PATH: &str = "/history-archive";
+
+/// Port the nginx sidecar listens on
+pub const ARCHIVE_SERVER_PORT: i32 = 8080;
+
+/// Document root of the unprivileged nginx image
+const NGINX_ROOT: &str = "/usr/share/nginx/html";
+
+/// History archive state file `new-hist` writes; present once initialised
+const ARCHIVE_STATE_FILE: &str = ".well-known/stellar-history.json";
+
+/// Archive server settings of a validator that has them
+pub fn archive_server_config(node: &StellarNode) -> Option<&ArchiveServerConfig> {
+    if node.spec.node_type != NodeType::Validator {
+        return None;
+    }
+    node.spec.validator_config.as_ref()?.archive_server.as_ref()
+}
+
+/// Name of the archive PVC and Service
+pub fn archive_resource_name(node: &StellarNode) -> String {
+    resource_name(node, "archive")
+}
+
+/// In-cluster URL other nodes fetch the archive from
+pub fn archive_url(node: &StellarNode) -> String {
+    format!(
+        "http://{}.{}.svc.cluster.local",
+        archive_resource_name(node),
+        node.namespace().unwrap_or_else(|| "default".to_string())
+    )
+}
+
+/// stellar-core.cfg section publishing to the archive volume
+pub fn archive_history_config() -> String {
+    format!(
+        "\n[HISTORY.local]\n\
+         get=\"cp {ARCHIVE_MOUNT_PATH}/{{0}} {{1}}\"\n\
+         put=\"cp {{0}} {ARCHIVE_MOUNT_PATH}/{{1}}\"\n\
+         mkdir=\"mkdir -p {ARCHIVE_MOUNT_PATH}/{{0}}\"\n"
+    )
+}
+
+pub(crate) fn build_archive_pvc(
+    node: &StellarNode,
+    config: &ArchiveServerConfig,
+) -> PersistentVolumeClaim {
+    PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: Some(archive_resource_name(node)),
+            namespace: node.namespace(),
+            labels: Some(standard_labels(node)),
+            owner_references: Some(vec![owner_reference(node)]),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+            storage_class_name: config.storage_class.clone(),
+            resources: Some(VolumeResourceRequirements {
+                requests: Some(BTreeMap::from([(
+                    "storage".to_string(),
+                    Quantity(config.size.clone()),
+                )])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        status: None,
+    }
+}
+
+pub(crate) fn build_archive_service(node: &StellarNode) -> Service {
+    let labels = standard_labels(node);
+    Service {
+        metadata: ObjectMeta {
+            name: Some(archive_resource_name(node)),
+            namespace: node.namespace(),
+            labels: Some(labels.clone()),
+            owner_references: Some(vec![owner_reference(node)]),
+            ..Default::default()
+        },
+        spec: Some(ServiceSpec {
+            selector: Some(labels),
+            ports: Some(vec![ServicePort {
+                name: Some("http".to_string()),
+                port: 80,
+                target_port: Some(IntOrString::Int(ARCHIVE_SERVER_PORT)),
+                protocol: Some("TCP".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }),
+        status: None,
+    }
+}
+
+/// Pod volume backed by the archive PVC
+pub fn archive_volume(node: &StellarNode) -> Volume {
+    Volume {
+        name: ARCHIVE_VOLUME.to_string(),
+        persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
+            claim_name: archive_resource_name(node),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Mount of the archive volume in the stellar-core container
+pub fn archive_volume_mount() -> VolumeMount {
+    VolumeMount {
+        name: ARCHIVE_VOLUME.to_string(),
+        mount_path: ARCHIVE_MOUNT_PATH.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Init container creating the archive with `stellar-core new-hist local`,
+/// skipped once the volume holds an archive
+pub fn build_archive_init_container(node: &StellarNode) -> Container {
+    let script = format!(
+        r#"set -e
+# Skip once the volume holds an archive (idempotent)
+if [ -f "{ARCHIVE_MOUNT_PATH}/{ARCHIVE_STATE_FILE}" ]; then
+  echo "History archive already initialised."
+  exit 0
+fi
+stellar-core new-hist local --conf /config/stellar-core.cfg
+"#
+    );
+    Container {
+        name: "archive-init".to_string(),
+        image: Some(node.spec.container_image()),
+        command: Some(vec!["/bin/sh".to_string(), "-c".to_string(), script]),
+        volume_mounts: Some(vec![
+            VolumeMount {
+                name: "config".to_string(),
+                mount_path: "/config".to_string(),
+                read_only: Some(true),
+                ..Default::default()
+            },
+            archive_volume_mount(),
+        ]),
+        security_context: Some(build_container_security_context(
+            node.spec.security_context.as_ref(),
+        )),
+        ..Default::default()
+    }
+}
+
+/// nginx sidecar serving the archive volume read-only
+pub fn build_archive_server_sidecar(config: &ArchiveServerConfig) -> Container {
+    Container {
+        name: "archive-server".to_string(),
+        image: Some(config.image.clone()),
+        ports: Some(vec![ContainerPort {
+            name: Some("archive".to_string()),
+            container_port: ARCHIVE_SERVER_PORT,
+            protocol: Some("TCP".to_string()),
+            ..Default::default()
+        }]),
+        volume_mounts: Some(vec![VolumeMount {
+            name: ARCHIVE_VOLUME.to_string(),
+            mount_path: NGINX_ROOT.to_string(),
+            read_only: Some(true),
+            ..Default::default()
+        }]),
+        security_context: Some(SecurityContext {
+            allow_privilege_escalation: Some(false),
+            capabilities: Some(Capabilities {
+                drop: Some(vec!["ALL".to_string()]),
+                add: None,
+            }),
+            run_as_non_root: Some(true),
+            privileged: Some(false),
+            seccomp_profile: Some(SeccompProfile {
+                type_: "RuntimeDefault".to_string(),
+                localhost_profile: None,
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Create or update the archive PVC and Service of a validator that serves
+/// its archive, and remove the Service once it no longer does. The PVC is
+/// kept so published history survives turning the server off.
+pub async fn ensure_archive_server(
+    client: &Client,
+    node: &StellarNode,
+    dry_run: bool,
+) -> Result<()> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = archive_resource_name(node);
+    let svc_api: Api<Service> = Api::namespaced(client.clone(), &namespace);
+
+    let Some(config) = archive_server_config(node) else {
+        delete_if_owned(&svc_api, "archive Service", &name, node, dry_run).await?;
+        return Ok(());
+    };
+
+    let mut params = PatchParams::apply("stellar-operator").force();
+    params.dry_run = dry_run;
+    let pvc_api: Api<PersistentVolumeClaim> = Api::namespaced(client.clone(), &namespace);
+    if pvc_api.get_opt(&name).await?.is_none() {
+        pvc_api
+            .patch(
+                &name,
+                &params,
+                &Patch::Apply(&build_archive_pvc(node, config)),
+            )
+            .await?;
+        info!("Created archive PVC {}/{}", namespace, name);
+    }
+    svc_api
+        .patch(&name, &params, &Patch::Apply(&build_archive_service(node)))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::{StellarNodeSpec, ValidatorConfig, DEFAULT_ARCHIVE_SERVER_IMAGE};
+
+    fn validator(archive_server: Option<ArchiveServerConfig>) -> StellarNode {
+        let mut node = StellarNode::new(
+            "validator-1",
+            StellarNodeSpec {
+                node_type: NodeType::Validator,
+                validator_config: Some(ValidatorConfig {
+                    archive_server,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.uid = Some("uid-1".to_string());
+        node
+    }
+
+    fn archive_config() -> ArchiveServerConfig {
+        ArchiveServerConfig {
+            size: "250Gi".to_string(),
+            storage_class: Some("premium-rwo".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_archive_pvc_and_service() {
+        let node = validator(Some(archive_config()));
+
+        let pvc = build_archive_pvc(&node, &archive_config());
+        assert_eq!(pvc.metadata.name.as_deref(), Some("validator-1-archive"));
+        let spec = pvc.spec.unwrap();
+        assert_eq!(spec.storage_class_name.as_deref(), Some("premium-rwo"));
+        assert_eq!(
+            spec.resources.unwrap().requests.unwrap()["storage"],
+            Quantity("250Gi".to_string())
+        );
+
+        let svc = build_archive_service(&node);
+        assert_eq!(svc.metadata.name.as_deref(), Some("validator-1-archive"));
+        let spec = svc.spec.unwrap();
+        assert_eq!(spec.selector, Some(standard_labels(&node)));
+        let port = &spec.ports.unwrap()[0];
+        assert_eq!(port.port, 80);
+        assert_eq!(
+            port.target_port,
+            Some(IntOrString::Int(ARCHIVE_SERVER_PORT))
+        );
+
+        assert_eq!(
+            archive_url(&node),
+            "http://validator-1-archive.stellar.svc.cluster.local"
+        );
+    }
+
+    #[test]
+    fn test_sidecar_serves_archive_read_only() {
+        let sidecar = build_archive_server_sidecar(&archive_config());
+        assert_eq!(sidecar.image.as_deref(), Some(DEFAULT_ARCHIVE_SERVER_IMAGE));
+        let mount = &sidecar.volume_mounts.unwrap()[0];
+        assert_eq!(mount.name, ARCHIVE_VOLUME);
+        assert_eq!(mount.mount_path, NGINX_ROOT);
+        assert_eq!(mount.read_only, Some(true));
+        assert_eq!(
+            sidecar.ports.unwrap()[0].container_port,
+            ARCHIVE_SERVER_PORT
+        );
+    }
+
+    #[test]
+    fn test_history_config_publishes_to_volume() {
+        let cfg: toml::Value = archive_history_config().parse().unwrap();
+        let local = &cfg["HISTORY"]["local"];
+        assert_eq!(local["put"].as_str(), Some("cp {0} /history-archive/{1}"));
+        assert_eq!(
+            local["mkdir"].as_str(),
+            Some("mkdir -p /history-archive/{0}")
+        );
+    }
+
+    #[test]
+    fn test_only_validators_serve_archives() {
+        assert!(archive_server_config(&validator(Some(archive_config()))).is_some());
+        assert!(archive_server_config(&validator(None)).is_none());
+
+        let mut horizon = validator(Some(archive_config()));
+        horizon.spec.node_type = NodeType::Horizon;
+        assert!(archive_server_config(&horizon).is_none());
+    }
+
+    #[test]
+    fn test_validator_pod_and_config_publish_to_archive() {
+        use crate::controller::resources::{build_config_map_for_test, build_statefulset_for_test};
+
+        let node = validator(Some(archive_config()));
+        let pod = build_statefulset_for_test(&node)
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert!(pod.containers.iter().any(|c| c.name == "archive-server"));
+        assert!(pod.containers[0]
+            .volume_mounts
+            .iter()
+            .flatten()
+            .any(|m| m.name == ARCHIVE_VOLUME && m.mount_path == ARCHIVE_MOUNT_PATH));
+        assert!(pod
+            .volumes
+            .iter()
+            .flatten()
+            .any(|v| v.name == ARCHIVE_VOLUME));
+        let cfg = build_config_map_for_test(&node).data.unwrap()["stellar-core.cfg"].clone();
+        assert!(cfg.contains("[HISTORY.local]"), "{cfg}");
+
+        let pod = build_statefulset_for_test(&validator(None))
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert!(!pod.containers.iter().any(|c| c.name == "archive-server"));
+        assert!(!pod
+            .init_containers
+            .iter()
+            .flatten()
+            .any(|c| c.name == "archive-init"));
+    }
+
+    #[test]
+    fn test_init_container_creates_archive_once() {
+        use crate::controller::resources::build_statefulset_for_test;
+
+        let node = validator(Some(archive_config()));
+        let pod = build_statefulset_for_test(&node)
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        let init = pod
+            .init_containers
+            .iter()
+            .flatten()
+            .find(|c| c.name == "archive-init")
+            .unwrap();
+        assert_eq!(init.image, Some(node.spec.container_image()));
+        let script = &init.command.as_ref().unwrap()[2];
+        assert!(script.contains("stellar-core new-hist local --conf /config/stellar-core.cfg"));
+        assert!(script.contains(&format!(
+            "if [ -f \"{ARCHIVE_MOUNT_PATH}/{ARCHIVE_STATE_FILE}\" ]; then"
+        )));
+        let mounts = init.volume_mounts.as_ref().unwrap();
+        assert!(mounts
+            .iter()
+            .any(|m| m.name == ARCHIVE_VOLUME && m.mount_path == ARCHIVE_MOUNT_PATH));
+        assert!(mounts
+            .iter()
+            .any(|m| m.name == "config" && m.mount_path == "/config"));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_creates_pvc_once_and_applies_service() {
+        let node = validator(Some(archive_config()));
+        let (client, server) = fake_client();
+        let requests = server.serve();
+
+        ensure_archive_server(&client, &node, false).await.unwrap();
+        ensure_archive_server(&client, &node, false).await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let pvc_writes = requests
+            .iter()
+            .filter(|r| {
+                r.method == http::Method::PATCH && r.path.contains("persistentvolumeclaims")
+            })
+            .count();
+        let svc_writes = requests
+            .iter()
+            .filter(|r| r.method == http::Method::PATCH && r.path.contains("services"))
+            .count();
+        assert_eq!(pvc_writes, 1);
+        assert_eq!(svc_writes, 2);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_server_removes_owned_service() {
+        let node = validator(None);
+        let service = build_archive_service(&validator(Some(archive_config())));
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            let body = serde_json::to_value(&service).unwrap();
+            let get = server.respond_with(&body).await;
+            let delete = server.respond_with(&body).await;
+            (get, delete)
+        });
+
+        ensure_archive_server(&client, &node, false).await.unwrap();
+        let (get, delete) = server.await.unwrap();
+        assert_eq!(get.method, http::Method::GET);
+        assert_eq!(delete.method, http::Method::DELETE);
+        assert_eq!(
+            delete.path,
+            "/api/v1/namespaces/stellar/services/validator-1-archive"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_disabled_server_without_service_deletes_nothing() {
+        let node = validator(None);
+        let (client, server) = fake_client();
+        let requests = server.serve();
+
+        ensure_archive_server(&client, &node, false).await.unwrap();
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, http::Method::GET);
+    }
+
+    #[tokio::test]
+    async fn test_former_validator_removes_service() {
+        let mut node = validator(Some(archive_config()));
+        let service = build_archive_service(&node);
+        node.spec.node_type = NodeType::Horizon;
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            let body = serde_json::to_value(&service).unwrap();
+            server.respond_with(&body).await;
+            server.respond_with(&body).await
+        });
+
+        ensure_archive_server(&client, &node, false).await.unwrap();
+        let request = server.await.unwrap();
+        assert_eq!(request.method, http::Method::DELETE);
+        assert_eq!(
+            request.path,
+            "/api/v1/namespaces/stellar/services/validator-1-archive"
+        );
+    }
+}
@@ -41,6 +41,8 @@ impl AuditWorker {
         let wc = watcher::Config::default();
 
         let mut stream = watcher(api, wc).default_backoff().boxed();
+        #[cfg(feature = "metrics")]
+        let _watch_stream = crate::controller::metrics::track_watch_streams(1);
 
         while let Some(event) = stream
             .try_next()
@@ -215,6 +215,8 @@ mod tests {
                 },
                 storage: crate::crd::StorageConfig {
                     storage_class: "standard".to_string(),
+                    tier: None,
+                    access_modes: vec![],
                     size: "100Gi".to_string(),
                     ..Default::default()
                 },
@@ -9,6 +9,10 @@ pub const CONDITION_TYPE_READY: &str = "Ready";
 pub const CONDITION_TYPE_PROGRESSING: &str = "Progressing";
 pub const CONDITION_TYPE_DEGRADED: &str = "Degraded";
 pub const CONDITION_TYPE_AVAILABLE: &str = "Available";
+/// Set while the spec would lose data on deletion (see `retention_backup_conflict`)
+pub const CONDITION_TYPE_DATA_RETENTION_RISK: &str = "DataRetentionRisk";
+/// Set while `spec.suspended` holds the workload at 0 replicas
+pub const CONDITION_TYPE_SUSPENDED: &str = "Suspended";
 
 /// Standard condition statuses
 pub const CONDITION_STATUS_TRUE: &str = "True";
@@ -69,6 +73,35 @@ pub fn remove_condition(conditions: &mut Vec<Condition>, type_: &str) {
     conditions.retain(|c| c.type_ != type_);
 }
 
+/// Set `DataRetentionRisk=True` with the given warning, or clear it when `None`
+pub fn set_data_retention_risk(conditions: &mut Vec<Condition>, warning: Option<&str>) {
+    match warning {
+        Some(message) => set_condition(
+            conditions,
+            CONDITION_TYPE_DATA_RETENTION_RISK,
+            CONDITION_STATUS_TRUE,
+            "RetentionDeleteWithSnapshots",
+            message,
+        ),
+        None => remove_condition(conditions, CONDITION_TYPE_DATA_RETENTION_RISK),
+    }
+}
+
+/// Set `Suspended=True` while the node is suspended, or clear it
+pub fn set_suspended(conditions: &mut Vec<Condition>, suspended: bool) {
+    if suspended {
+        set_condition(
+            conditions,
+            CONDITION_TYPE_SUSPENDED,
+            CONDITION_STATUS_TRUE,
+            "SuspendedBySpec",
+            "Replicas are scaled to 0 because spec.suspended is true",
+        );
+    } else {
+        remove_condition(conditions, CONDITION_TYPE_SUSPENDED);
+    }
+}
+
 /// Create a Ready=True condition
 pub fn ready_condition(reason: &str, message: &str) -> Condition {
     Condition {
@@ -752,4 +785,34 @@ mod tests {
         assert_eq!(conditions[1].type_, CONDITION_TYPE_READY);
         assert_eq!(conditions[2].type_, CONDITION_TYPE_PROGRESSING);
     }
+
+    // ── set_data_retention_risk ───────────────────────────────────────────────
+
+    #[test]
+    fn test_data_retention_risk_set_and_cleared() {
+        let mut conditions = Vec::new();
+
+        set_data_retention_risk(&mut conditions, Some("PVC is deleted with the node"));
+        let risk = find_condition(&conditions, CONDITION_TYPE_DATA_RETENTION_RISK).unwrap();
+        assert_eq!(risk.status, CONDITION_STATUS_TRUE);
+        assert_eq!(risk.reason, "RetentionDeleteWithSnapshots");
+
+        set_data_retention_risk(&mut conditions, None);
+        assert!(find_condition(&conditions, CONDITION_TYPE_DATA_RETENTION_RISK).is_none());
+    }
+
+    // ── set_suspended ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_suspended_set_and_cleared() {
+        let mut conditions = Vec::new();
+
+        set_suspended(&mut conditions, true);
+        let suspended = find_condition(&conditions, CONDITION_TYPE_SUSPENDED).unwrap();
+        assert_eq!(suspended.status, CONDITION_STATUS_TRUE);
+        assert_eq!(suspended.reason, "SuspendedBySpec");
+
+        set_suspended(&mut conditions, false);
+        assert!(find_condition(&conditions, CONDITION_TYPE_SUSPENDED).is_none());
+    }
 }
@@ -0,0 +1,249 @@
+//! stellar-core log level from the `stellar.org/log-level` annotation
+//!
+//! stellar-core does not read its log level from the environment: it takes
+//! the `--ll` run argument or, at runtime, the `ll` admin command. On every
+//! reconcile of an annotated validator the operator sends `ll?level=<level>`
+//! to each running pod, so pods that restarted since pick the level up again
+//! without a rollout, and records the level on the StatefulSet
+//! ([`APPLIED_LOG_LEVEL_ANNOTATION`]). Once the node annotation is removed the
+//! pods are set back to [`DEFAULT_CORE_LOG_LEVEL`] and the record is cleared.
+
+use std::time::Duration;
+
+use k8s_openapi::api::apps::v1::StatefulSet;
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::{Api, ListParams, Patch, PatchParams};
+use kube::{Client, ResourceExt};
+use serde_json::json;
+use tracing::{debug, info, warn};
+
+use super::resources::requested_log_level;
+use crate::crd::{NodeType, StellarNode};
+use crate::error::{Error, Result};
+
+/// StatefulSet annotation recording the level last sent to the pods
+pub const APPLIED_LOG_LEVEL_ANNOTATION: &str = "stellar.org/applied-log-level";
+
+/// stellar-core's level when started without `--ll`
+pub const DEFAULT_CORE_LOG_LEVEL: &str = "info";
+
+/// stellar-core admin URL setting the log level of the pod at `pod_ip`
+pub fn core_log_level_url(pod_ip: &str, level: &str) -> String {
+    format!("http://{pod_ip}:11626/ll?level={level}")
+}
+
+/// Level to send to a validator's pods: the requested one, the default when
+/// a level applied earlier is no longer requested, or `None` when neither
+pub fn level_to_apply(
+    requested: Option<&'static str>,
+    applied: Option<&str>,
+) -> Option<&'static str> {
+    match (requested, applied) {
+        (Some(level), _) => Some(level),
+        (None, Some(_)) => Some(DEFAULT_CORE_LOG_LEVEL),
+        (None, None) => None,
+    }
+}
+
+/// Apply the validator's `stellar.org/log-level` annotation to its running
+/// stellar-core pods through the `ll` admin command.
+pub async fn apply_core_log_level(
+    client: &Client,
+    node: &StellarNode,
+    dry_run: bool,
+) -> Result<()> {
+    if node.spec.node_type != NodeType::Validator {
+        return Ok(());
+    }
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+    let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), &namespace);
+    let Some(statefulset) = statefulsets.get_opt(&name).await? else {
+        return Ok(());
+    };
+
+    let requested = requested_log_level(node);
+    let applied = statefulset
+        .annotations()
+        .get(APPLIED_LOG_LEVEL_ANNOTATION)
+        .cloned();
+    let Some(level) = level_to_apply(requested, applied.as_deref()) else {
+        return Ok(());
+    };
+    if dry_run {
+        info!(
+            "[dry-run] Would set the stellar-core log level of {}/{} to {}",
+            namespace, name, level
+        );
+        return Ok(());
+    }
+
+    let failed = set_pods_log_level(client, &namespace, &name, level).await?;
+    if failed > 0 {
+        return Err(Error::ConfigError(format!(
+            "Failed to set the log level of {failed} pod(s) of {namespace}/{name}"
+        )));
+    }
+
+    if applied.as_deref() != requested {
+        let patch = json!({
+            "metadata": { "annotations": { APPLIED_LOG_LEVEL_ANNOTATION: requested } }
+        });
+        statefulsets
+            .patch(&name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await?;
+        info!(
+            "Set the stellar-core log level of {}/{} to {}",
+            namespace, name, level
+        );
+    }
+    Ok(())
+}
+
+/// Send `level` to every pod of the validator that has an IP, returning how
+/// many pods did not take it
+async fn set_pods_log_level(
+    client: &Client,
+    namespace: &str,
+    name: &str,
+    level: &str,
+) -> Result<usize> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let label_selector =
+        format!("app.kubernetes.io/instance={name},app.kubernetes.io/name=stellar-node");
+    let pod_list = pods
+        .list(&ListParams::default().labels(&label_selector))
+        .await?;
+
+    let http = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|e| Error::ConfigError(format!("Failed to build HTTP client: {e}")))?;
+
+    let mut failed = 0;
+    for pod in pod_list.items {
+        let Some(pod_ip) = pod.status.as_ref().and_then(|s| s.pod_ip.as_ref()) else {
+            continue;
+        };
+        let url = core_log_level_url(pod_ip, level);
+        debug!("Setting log level via {}", url);
+        match http.get(&url).send().await {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => {
+                warn!(
+                    "Failed to set log level of pod at {}: HTTP {}",
+                    pod_ip,
+                    response.status()
+                );
+                failed += 1;
+            }
+            Err(e) => {
+                warn!("Failed to set log level of pod at {}: {}", pod_ip, e);
+                failed += 1;
+            }
+        }
+    }
+    Ok(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::resources::LOG_LEVEL_ANNOTATION;
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::StellarNodeSpec;
+    use kube::api::PostParams;
+    use std::collections::BTreeMap;
+
+    fn validator(log_level: Option<&str>) -> StellarNode {
+        let mut node = StellarNode::new(
+            "validator-1",
+            StellarNodeSpec {
+                node_type: NodeType::Validator,
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.annotations = log_level
+            .map(|level| BTreeMap::from([(LOG_LEVEL_ANNOTATION.to_string(), level.to_string())]));
+        node
+    }
+
+    fn statefulset(applied: Option<&str>) -> StatefulSet {
+        let mut statefulset: StatefulSet = serde_json::from_value(json!({
+            "metadata": { "name": "validator-1", "namespace": "stellar" },
+            "spec": { "selector": {}, "serviceName": "validator-1", "template": {} }
+        }))
+        .unwrap();
+        statefulset.metadata.annotations = applied.map(|level| {
+            BTreeMap::from([(APPLIED_LOG_LEVEL_ANNOTATION.to_string(), level.to_string())])
+        });
+        statefulset
+    }
+
+    async fn applied_level(applied: Option<&str>, node: &StellarNode) -> Option<String> {
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let api: Api<StatefulSet> = Api::namespaced(client.clone(), "stellar");
+        api.create(&PostParams::default(), &statefulset(applied))
+            .await
+            .unwrap();
+
+        apply_core_log_level(&client, node, false).await.unwrap();
+        api.get("validator-1")
+            .await
+            .unwrap()
+            .annotations()
+            .get(APPLIED_LOG_LEVEL_ANNOTATION)
+            .cloned()
+    }
+
+    #[test]
+    fn test_core_log_level_url() {
+        assert_eq!(
+            core_log_level_url("10.0.0.5", "debug"),
+            "http://10.0.0.5:11626/ll?level=debug"
+        );
+    }
+
+    #[test]
+    fn test_level_to_apply() {
+        assert_eq!(level_to_apply(Some("debug"), None), Some("debug"));
+        assert_eq!(level_to_apply(Some("debug"), Some("trace")), Some("debug"));
+        assert_eq!(level_to_apply(None, Some("debug")), Some("info"));
+        assert_eq!(level_to_apply(None, None), None);
+    }
+
+    #[tokio::test]
+    async fn test_requested_level_is_recorded() {
+        assert_eq!(
+            applied_level(None, &validator(Some("debug")))
+                .await
+                .as_deref(),
+            Some("debug")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_removed_annotation_clears_record() {
+        assert_eq!(applied_level(Some("debug"), &validator(None)).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_unannotated_validator_is_left_alone() {
+        let (client, server) = fake_client();
+        let requests = server.serve();
+        let api: Api<StatefulSet> = Api::namespaced(client.clone(), "stellar");
+        api.create(&PostParams::default(), &statefulset(None))
+            .await
+            .unwrap();
+
+        apply_core_log_level(&client, &validator(None), false)
+            .await
+            .unwrap();
+        let requests = requests.lock().unwrap();
+        assert!(!requests
+            .iter()
+            .any(|r| r.method == http::Method::PATCH || r.path.ends_with("/pods")));
+    }
+}
@@ -252,6 +252,10 @@ mod tests {
                     ingest_workers: 1,
                     enable_experimental_ingestion: false,
                     auto_migration: true,
+                    migration_max_attempts: None,
+                    migration_backoff_seconds: None,
+                    admin_port: None,
+                    per_hour_rate_limit: None,
                 }),
                 validator_config: None,
                 soroban_config: None,
@@ -368,6 +372,10 @@ mod tests {
                 ingest_workers: 1,
                 enable_experimental_ingestion: false,
                 auto_migration: true,
+                migration_max_attempts: None,
+                migration_backoff_seconds: None,
+                admin_port: None,
+                per_hour_rate_limit: None,
             }),
             validator_config: None,
             soroban_config: None,
@@ -11,6 +11,7 @@
 //! - Cross-cluster health checks
 //! - Automatic peer discovery
 
+use chrono::{DateTime, Utc};
 use k8s_openapi::api::core::v1::Service;
 use kube::{
     api::{Api, Patch, PatchParams},
@@ -18,7 +19,7 @@ use kube::{
 };
 use tracing::{debug, info, instrument, warn};
 
-use crate::crd::{CrossClusterConfig, CrossClusterMode, StellarNode};
+use crate::crd::{CrossClusterConfig, CrossClusterMode, PeerEndpointResolution, StellarNode};
 use crate::error::{Error, Result};
 
 /// Ensure cross-cluster services are configured
@@ -139,6 +140,149 @@ async fn create_submariner_service_export(
     }
 }
 
+/// DNS domain Submariner Lighthouse serves imported services under
+const CLUSTERSET_DOMAIN: &str = "svc.clusterset.local";
+
+/// Discover peer endpoints from Submariner ServiceImport resources.
+///
+/// Only runs when `autoDiscovery` is enabled with the Submariner mesh. Every
+/// ServiceImport in the node's namespace that exposes a `peer` port yields one
+/// peer per source cluster, addressed through the per-cluster clusterset DNS
+/// name. Source clusters matching `crossCluster.clusterId` are the node's own
+/// cluster and are skipped; without a cluster ID nothing is discovered.
+#[instrument(skip(client, node), fields(name = %node.name_any()))]
+pub async fn discover_service_import_peers(
+    client: &Client,
+    node: &StellarNode,
+) -> Result<Vec<crate::crd::PeerClusterConfig>> {
+    use kube::api::{DynamicObject, ListParams};
+    use kube::discovery::ApiResource;
+
+    let Some(cc) = node.spec.cross_cluster.as_ref() else {
+        return Ok(Vec::new());
+    };
+    let uses_submariner = cc.enabled
+        && cc.auto_discovery
+        && cc
+            .service_mesh
+            .as_ref()
+            .is_some_and(|m| m.mesh_type == crate::crd::CrossClusterMeshType::Submariner);
+    if !uses_submariner {
+        return Ok(Vec::new());
+    }
+    let Some(local_cluster_id) = cc.cluster_id.as_deref().filter(|id| !id.is_empty()) else {
+        warn!(
+            "Skipping ServiceImport discovery for {}: crossCluster.clusterId is not set",
+            node.name_any()
+        );
+        return Ok(Vec::new());
+    };
+
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+
+    let api_resource = ApiResource {
+        group: "multicluster.x-k8s.io".to_string(),
+        version: "v1alpha1".to_string(),
+        api_version: "multicluster.x-k8s.io/v1alpha1".to_string(),
+        kind: "ServiceImport".to_string(),
+        plural: "serviceimports".to_string(),
+    };
+    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), &namespace, &api_resource);
+
+    let imports = match api.list(&ListParams::default()).await {
+        Ok(list) => list.items,
+        Err(e) => {
+            warn!(
+                "Failed to list ServiceImports (Submariner may not be installed): {}",
+                e
+            );
+            return Ok(Vec::new());
+        }
+    };
+
+    let peers = service_import_peers(&imports, local_cluster_id);
+
+    debug!(
+        "Discovered {} peer endpoints from Submariner ServiceImports",
+        peers.len()
+    );
+    Ok(peers)
+}
+
+/// Peers of all ServiceImports, leaving out those in the local cluster.
+pub(crate) fn service_import_peers(
+    imports: &[kube::api::DynamicObject],
+    local_cluster_id: &str,
+) -> Vec<crate::crd::PeerClusterConfig> {
+    imports
+        .iter()
+        .flat_map(peers_from_service_import)
+        .filter(|peer| peer.cluster_id != local_cluster_id)
+        .collect()
+}
+
+/// Convert a ServiceImport into one peer per source cluster.
+///
+/// Returns nothing for imports without a `peer` port or whose source clusters
+/// have not been reported by Lighthouse yet.
+pub(crate) fn peers_from_service_import(
+    import: &kube::api::DynamicObject,
+) -> Vec<crate::crd::PeerClusterConfig> {
+    let name = import.name_any();
+    let namespace = import.namespace().unwrap_or_else(|| "default".to_string());
+
+    let port = import.data["spec"]["ports"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|p| p["name"] == "peer")
+        .and_then(|p| p["port"].as_u64())
+        .and_then(|p| u16::try_from(p).ok());
+    let Some(port) = port else {
+        return Vec::new();
+    };
+
+    import.data["status"]["clusters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c["cluster"].as_str())
+        .filter(|cluster| !cluster.is_empty())
+        .map(|cluster| crate::crd::PeerClusterConfig {
+            cluster_id: cluster.to_string(),
+            endpoint: format!("{cluster}.{name}.{namespace}.{CLUSTERSET_DOMAIN}"),
+            latency_threshold_ms: None,
+            region: None,
+            priority: 100,
+            port: Some(port),
+            enabled: true,
+            kubeconfig_secret_ref: None,
+            kubeconfig_secret_key: "kubeconfig".to_string(),
+            target_namespace: None,
+        })
+        .collect()
+}
+
+/// Append discovered peers to the configured ones.
+///
+/// Explicitly configured peers take precedence: a discovered peer is dropped
+/// when its cluster ID or endpoint is already present.
+pub(crate) fn merge_discovered_peers(
+    configured: &[crate::crd::PeerClusterConfig],
+    discovered: Vec<crate::crd::PeerClusterConfig>,
+) -> Vec<crate::crd::PeerClusterConfig> {
+    let mut peers = configured.to_vec();
+    for peer in discovered {
+        let known = peers
+            .iter()
+            .any(|p| p.cluster_id == peer.cluster_id || p.endpoint == peer.endpoint);
+        if !known {
+            peers.push(peer);
+        }
+    }
+    peers
+}
+
 /// Create Istio ServiceEntry for multi-cluster
 async fn create_istio_service_export(
     client: &Client,
@@ -223,12 +367,20 @@ async fn create_istio_service_export(
 }
 
 /// Create ExternalName services for peer clusters
+///
+/// Peer endpoints are re-resolved once their cached resolution in
+/// `status.peerEndpoints` is older than `externalName.ttl`, and the resolved
+/// addresses are reported back on the node status. With `pinToIp`, peers are
+/// served by a selectorless headless Service and Endpoints on the pinned IPs
+/// instead of an ExternalName record.
 #[instrument(skip(client, node, config), fields(name = %node.name_any()))]
 async fn ensure_external_name_services(
     client: &Client,
     node: &StellarNode,
     config: &CrossClusterConfig,
 ) -> Result<()> {
+    use k8s_openapi::api::core::v1::Endpoints;
+
     let external_name_config = config
         .external_name
         .as_ref()
@@ -240,6 +392,14 @@ async fn ensure_external_name_services(
 
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let api: Api<Service> = Api::namespaced(client.clone(), &namespace);
+    let endpoints_api: Api<Endpoints> = Api::namespaced(client.clone(), &namespace);
+
+    let previous = node
+        .status
+        .as_ref()
+        .map(|s| s.peer_endpoints.clone())
+        .unwrap_or_default();
+    let resolutions = refresh_peer_resolutions(config, external_name_config, &previous).await;
 
     // Create ExternalName service for each peer cluster
     for peer in &config.peer_clusters {
@@ -249,31 +409,261 @@ async fn ensure_external_name_services(
 
         let service_name = format!("{}-peer-{}", node.name_any(), peer.cluster_id);
 
-        let external_service = build_external_name_service(node, peer, &service_name);
+        let pinned_ips = resolutions
+            .iter()
+            .find(|r| r.cluster_id == peer.cluster_id && r.pinned && !r.resolved_ips.is_empty())
+            .map(|r| r.resolved_ips.as_slice());
+
+        let service = match pinned_ips {
+            Some(_) => build_pinned_peer_service(node, peer, &service_name),
+            None => build_external_name_service(node, peer, &service_name),
+        };
 
         api.patch(
             &service_name,
             &PatchParams::apply("stellar-operator").force(),
-            &Patch::Apply(&external_service),
+            &Patch::Apply(&service),
         )
         .await?;
 
-        info!(
-            "ExternalName service {} created for peer cluster {}",
-            service_name, peer.cluster_id
-        );
+        if let Some(ips) = pinned_ips {
+            let endpoints = build_peer_endpoints(node, peer, &service_name, ips);
+            endpoints_api
+                .patch(
+                    &service_name,
+                    &PatchParams::apply("stellar-operator").force(),
+                    &Patch::Apply(&endpoints),
+                )
+                .await?;
+            info!(
+                "Peer service {} pinned to {:?} for peer cluster {}",
+                service_name, ips, peer.cluster_id
+            );
+        } else {
+            // The pin was dropped: the Endpoints written for it are stale
+            super::resources::delete_if_owned(
+                &endpoints_api,
+                "Endpoints",
+                &service_name,
+                node,
+                false,
+            )
+            .await?;
+            info!(
+                "ExternalName service {} created for peer cluster {}",
+                service_name, peer.cluster_id
+            );
+        }
+    }
+
+    if resolutions != previous {
+        let nodes: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
+        let patch = serde_json::json!({ "status": { "peerEndpoints": resolutions } });
+        nodes
+            .patch_status(
+                &node.name_any(),
+                &PatchParams::apply("stellar-operator"),
+                &Patch::Merge(&patch),
+            )
+            .await?;
     }
 
     Ok(())
 }
 
-/// Build an ExternalName service for a peer cluster
-fn build_external_name_service(
+/// Drop the node's cached peer endpoint resolutions, pinned ones included, so
+/// every peer endpoint is resolved again on the next pass, e.g. after the
+/// node resumes from suspension.
+pub async fn expire_peer_resolutions(
+    client: &Client,
+    node: &StellarNode,
+    dry_run: bool,
+) -> Result<()> {
+    let cached = node
+        .status
+        .as_ref()
+        .is_some_and(|s| !s.peer_endpoints.is_empty());
+    if !cached {
+        return Ok(());
+    }
+
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let nodes: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
+    let patch = serde_json::json!({ "status": { "peerEndpoints": null } });
+    let mut params = PatchParams::default();
+    params.dry_run = dry_run;
+    nodes
+        .patch_status(&node.name_any(), &params, &Patch::Merge(&patch))
+        .await?;
+    debug!("Expired cached peer endpoints of {}", node.name_any());
+    Ok(())
+}
+
+/// Re-resolve enabled peer endpoints whose cached resolution has expired.
+async fn refresh_peer_resolutions(
+    config: &CrossClusterConfig,
+    external_name: &crate::crd::ExternalNameConfig,
+    previous: &[PeerEndpointResolution],
+) -> Vec<PeerEndpointResolution> {
+    let now = Utc::now();
+    let mut resolutions = Vec::new();
+
+    for peer in config.peer_clusters.iter().filter(|p| p.enabled) {
+        let cached = previous.iter().find(|r| r.cluster_id == peer.cluster_id);
+
+        match cached {
+            Some(c)
+                if !needs_resolution(
+                    Some(c),
+                    peer,
+                    external_name.ttl,
+                    external_name.pin_to_ip,
+                    now,
+                ) =>
+            {
+                resolutions.push(c.clone());
+            }
+            _ => {
+                let result = resolve_endpoint(&peer.endpoint, peer.port.unwrap_or(11625)).await;
+                resolutions.push(apply_resolution(
+                    cached,
+                    peer,
+                    external_name.pin_to_ip,
+                    result,
+                    now,
+                ));
+            }
+        }
+    }
+
+    resolutions
+}
+
+/// Whether a peer endpoint must be resolved again.
+///
+/// A cached resolution is reused until it is `ttl_secs` old. Pinned
+/// resolutions never expire; they are only refreshed when the configured
+/// endpoint or the pinning mode changes, or when no address was ever resolved.
+pub(crate) fn needs_resolution(
+    cached: Option<&PeerEndpointResolution>,
+    peer: &crate::crd::PeerClusterConfig,
+    ttl_secs: u32,
+    pin_to_ip: bool,
+    now: DateTime<Utc>,
+) -> bool {
+    let Some(cached) = cached else {
+        return true;
+    };
+    if cached.endpoint != peer.endpoint
+        || cached.pinned != pin_to_ip
+        || cached.resolved_ips.is_empty()
+    {
+        return true;
+    }
+    if pin_to_ip {
+        return false;
+    }
+
+    match cached
+        .resolved_at
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+    {
+        Some(at) => now.signed_duration_since(at) >= chrono::Duration::seconds(i64::from(ttl_secs)),
+        None => true,
+    }
+}
+
+/// Fold a resolution attempt into the cached state for a peer.
+///
+/// A failed lookup keeps the previously resolved addresses for the same
+/// endpoint so a transient DNS outage does not unpin or blank the peer.
+pub(crate) fn apply_resolution(
+    cached: Option<&PeerEndpointResolution>,
+    peer: &crate::crd::PeerClusterConfig,
+    pin_to_ip: bool,
+    result: Result<Vec<String>>,
+    now: DateTime<Utc>,
+) -> PeerEndpointResolution {
+    let cached = cached.filter(|c| c.endpoint == peer.endpoint);
+
+    match result {
+        Ok(ips) if !ips.is_empty() => {
+            if let Some(c) = cached.filter(|c| c.resolved_ips != ips) {
+                info!(
+                    "Peer cluster {} endpoint {} now resolves to {:?} (was {:?})",
+                    peer.cluster_id, peer.endpoint, ips, c.resolved_ips
+                );
+            }
+            PeerEndpointResolution {
+                cluster_id: peer.cluster_id.clone(),
+                endpoint: peer.endpoint.clone(),
+                resolved_ips: ips,
+                resolved_at: Some(now.to_rfc3339()),
+                pinned: pin_to_ip,
+                last_error: None,
+            }
+        }
+        other => {
+            let error = match other {
+                Err(e) => e.to_string(),
+                Ok(_) => format!("{} resolved to no addresses", peer.endpoint),
+            };
+            warn!(
+                "Failed to resolve peer cluster {} endpoint: {}",
+                peer.cluster_id, error
+            );
+            PeerEndpointResolution {
+                cluster_id: peer.cluster_id.clone(),
+                endpoint: peer.endpoint.clone(),
+                resolved_ips: cached.map(|c| c.resolved_ips.clone()).unwrap_or_default(),
+                resolved_at: cached.and_then(|c| c.resolved_at.clone()),
+                pinned: pin_to_ip,
+                last_error: Some(error),
+            }
+        }
+    }
+}
+
+/// Resolve a peer endpoint to its sorted, de-duplicated IP addresses
+async fn resolve_endpoint(endpoint: &str, port: u16) -> Result<Vec<String>> {
+    use tokio::time::{timeout, Duration};
+
+    if let Ok(ip) = endpoint.parse::<std::net::IpAddr>() {
+        return Ok(vec![ip.to_string()]);
+    }
+
+    let addrs = match timeout(
+        Duration::from_secs(5),
+        tokio::net::lookup_host((endpoint, port)),
+    )
+    .await
+    {
+        Ok(Ok(addrs)) => addrs,
+        Ok(Err(e)) => {
+            return Err(Error::NetworkError(format!(
+                "DNS resolution of {endpoint} failed: {e}"
+            )))
+        }
+        Err(_) => {
+            return Err(Error::NetworkError(format!(
+                "DNS resolution of {endpoint} timed out"
+            )))
+        }
+    };
+
+    let mut ips: Vec<String> = addrs.map(|a| a.ip().to_string()).collect();
+    ips.sort();
+    ips.dedup();
+    Ok(ips)
+}
+
+/// Labels and annotations shared by the objects created for a peer cluster
+fn peer_service_metadata(
     node: &StellarNode,
     peer: &crate::crd::PeerClusterConfig,
     service_name: &str,
-) -> Service {
-    use k8s_openapi::api::core::v1::{ServicePort, ServiceSpec};
+) -> k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta {
     use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
     use std::collections::BTreeMap;
 
@@ -292,20 +682,32 @@ fn build_external_name_service(
     let mut annotations = BTreeMap::new();
     super::resources::merge_service_annotations(&mut annotations, node);
 
+    ObjectMeta {
+        name: Some(service_name.to_string()),
+        namespace: node.namespace(),
+        labels: Some(labels),
+        owner_references: Some(vec![super::resources::owner_reference(node)]),
+        annotations: if annotations.is_empty() {
+            None
+        } else {
+            Some(annotations)
+        },
+        ..Default::default()
+    }
+}
+
+/// Build an ExternalName service for a peer cluster
+fn build_external_name_service(
+    node: &StellarNode,
+    peer: &crate::crd::PeerClusterConfig,
+    service_name: &str,
+) -> Service {
+    use k8s_openapi::api::core::v1::{ServicePort, ServiceSpec};
+
     let port = peer.port.unwrap_or(11625);
 
     Service {
-        metadata: ObjectMeta {
-            name: Some(service_name.to_string()),
-            namespace: node.namespace(),
-            labels: Some(labels),
-            annotations: if annotations.is_empty() {
-                None
-            } else {
-                Some(annotations)
-            },
-            ..Default::default()
-        },
+        metadata: peer_service_metadata(node, peer, service_name),
         spec: Some(ServiceSpec {
             type_: Some("ExternalName".to_string()),
             external_name: Some(peer.endpoint.clone()),
@@ -321,6 +723,64 @@ fn build_external_name_service(
     }
 }
 
+/// Build a selectorless headless service for a peer pinned to resolved IPs
+fn build_pinned_peer_service(
+    node: &StellarNode,
+    peer: &crate::crd::PeerClusterConfig,
+    service_name: &str,
+) -> Service {
+    use k8s_openapi::api::core::v1::{ServicePort, ServiceSpec};
+
+    let port = peer.port.unwrap_or(11625);
+
+    Service {
+        metadata: peer_service_metadata(node, peer, service_name),
+        spec: Some(ServiceSpec {
+            type_: Some("ClusterIP".to_string()),
+            cluster_ip: Some("None".to_string()),
+            ports: Some(vec![ServicePort {
+                name: Some("peer".to_string()),
+                port: port as i32,
+                protocol: Some("TCP".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }),
+        status: None,
+    }
+}
+
+/// Build the Endpoints backing a pinned peer service
+fn build_peer_endpoints(
+    node: &StellarNode,
+    peer: &crate::crd::PeerClusterConfig,
+    service_name: &str,
+    ips: &[String],
+) -> k8s_openapi::api::core::v1::Endpoints {
+    use k8s_openapi::api::core::v1::{EndpointAddress, EndpointPort, EndpointSubset, Endpoints};
+
+    Endpoints {
+        metadata: peer_service_metadata(node, peer, service_name),
+        subsets: Some(vec![EndpointSubset {
+            addresses: Some(
+                ips.iter()
+                    .map(|ip| EndpointAddress {
+                        ip: ip.clone(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+            ports: Some(vec![EndpointPort {
+                name: Some("peer".to_string()),
+                port: i32::from(peer.port.unwrap_or(11625)),
+                protocol: Some("TCP".to_string()),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }]),
+    }
+}
+
 /// Check latency to peer clusters and update status
 #[instrument(skip(client, node), fields(name = %node.name_any()))]
 pub async fn check_peer_latency(
@@ -342,9 +802,12 @@ pub async fn check_peer_latency(
         _ => return Ok(Vec::new()),
     };
 
+    let discovered = discover_service_import_peers(client, node).await?;
+    let peers = merge_discovered_peers(&cross_cluster.peer_clusters, discovered);
+
     let mut results = Vec::new();
 
-    for peer in &cross_cluster.peer_clusters {
+    for peer in &peers {
         if !peer.enabled {
             continue;
         }
@@ -374,6 +837,40 @@ pub async fn check_peer_latency(
     Ok(results)
 }
 
+/// Delays applied before each retry of a latency probe that failed with a
+/// retriable error; attempts past the last tier reuse the final delay.
+const PROBE_RETRY_BACKOFF_MS: [u64; 3] = [200, 1000, 5000];
+
+/// Backoff before the given retry attempt (0-indexed)
+fn probe_retry_backoff(attempt: u32) -> std::time::Duration {
+    let tier = (attempt as usize).min(PROBE_RETRY_BACKOFF_MS.len() - 1);
+    std::time::Duration::from_millis(PROBE_RETRY_BACKOFF_MS[tier])
+}
+
+/// Run a latency probe, retrying retriable failures with tiered backoff.
+async fn probe_with_retry<F, Fut>(
+    max_retries: u32,
+    backoff: impl Fn(u32) -> std::time::Duration,
+    mut probe: F,
+) -> Result<u32>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<u32>>,
+{
+    let mut attempt = 0;
+    loop {
+        match probe().await {
+            Err(e) if e.is_retriable() && attempt < max_retries => {
+                let delay = backoff(attempt);
+                debug!("Latency probe failed ({}), retrying in {:?}", e, delay);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Measure latency to a peer cluster
 async fn measure_peer_latency(
     _client: &Client,
@@ -382,38 +879,105 @@ async fn measure_peer_latency(
 ) -> Result<u32> {
     use crate::crd::LatencyMeasurementMethod;
 
+    let probe_timeout = std::time::Duration::from_millis(config.timeout_ms);
+
     // Collect multiple samples
     let mut samples = Vec::new();
 
     for _ in 0..config.sample_count {
-        let latency = match config.method {
-            LatencyMeasurementMethod::Ping => {
-                // ICMP ping (requires elevated privileges)
-                measure_ping_latency(&peer.endpoint).await?
-            }
-            LatencyMeasurementMethod::TCP => {
-                // TCP connection time
-                let port = peer.port.unwrap_or(11625);
-                measure_tcp_latency(&peer.endpoint, port).await?
-            }
-            LatencyMeasurementMethod::HTTP => {
-                // HTTP request time
-                measure_http_latency(&peer.endpoint).await?
-            }
-            LatencyMeasurementMethod::GRPC => {
-                // gRPC health check
-                measure_grpc_latency(&peer.endpoint).await?
+        let latency = probe_with_retry(config.max_retries, probe_retry_backoff, || async {
+            match config.method {
+                LatencyMeasurementMethod::Ping => {
+                    // ICMP ping (requires elevated privileges)
+                    measure_ping_latency(&peer.endpoint).await
+                }
+                LatencyMeasurementMethod::TCP => {
+                    // TCP connection time
+                    let port = peer.port.unwrap_or(11625);
+                    measure_tcp_latency(&peer.endpoint, port, probe_timeout).await
+                }
+                LatencyMeasurementMethod::HTTP => {
+                    // HTTP request time
+                    measure_http_latency(&peer.endpoint, probe_timeout).await
+                }
+                LatencyMeasurementMethod::GRPC => {
+                    // gRPC health check
+                    measure_grpc_latency(&peer.endpoint).await
+                }
             }
-        };
+        })
+        .await?;
         samples.push(latency);
     }
 
-    // Calculate percentile
-    samples.sort_unstable();
-    let index = ((config.percentile as f64 / 100.0) * samples.len() as f64).ceil() as usize - 1;
-    let index = index.min(samples.len() - 1);
+    let samples = reject_outliers(&samples, config);
+    nearest_rank_percentile(&samples, config.percentile).ok_or_else(|| {
+        Error::ConfigError(format!(
+            "latency measurement for peer {} collected no samples (sampleCount is 0)",
+            peer.cluster_id
+        ))
+    })
+}
 
-    Ok(samples[index])
+/// Drop transient latency spikes according to the configured rejection method.
+///
+/// MAD rejection keeps samples within `mad_threshold` median absolute
+/// deviations of the median; the deviation is floored at 1ms so a run of
+/// identical samples does not reject every sample that differs by a
+/// millisecond. Trimming drops `trim_percent` of the samples from each end and
+/// is skipped when it would leave nothing.
+pub(crate) fn reject_outliers(
+    samples: &[u32],
+    config: &crate::crd::LatencyMeasurementConfig,
+) -> Vec<u32> {
+    use crate::crd::LatencyOutlierRejection;
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+
+    match config.outlier_rejection {
+        LatencyOutlierRejection::None => sorted,
+        LatencyOutlierRejection::Mad => {
+            let Some(median) = nearest_rank_percentile(&sorted, 50) else {
+                return sorted;
+            };
+            let deviations: Vec<u32> = sorted.iter().map(|s| s.abs_diff(median)).collect();
+            let mad = nearest_rank_percentile(&deviations, 50).unwrap_or(0).max(1);
+            let limit = config.mad_threshold * f64::from(mad);
+
+            sorted
+                .into_iter()
+                .filter(|s| f64::from(s.abs_diff(median)) <= limit)
+                .collect()
+        }
+        LatencyOutlierRejection::Trim => {
+            let trim = sorted.len() * usize::from(config.trim_percent.min(100)) / 100;
+            if trim * 2 >= sorted.len() {
+                return sorted;
+            }
+            sorted[trim..sorted.len() - trim].to_vec()
+        }
+    }
+}
+
+/// Nearest-rank percentile of a set of latency samples.
+///
+/// The rank is `ceil(p / 100 * n)` computed in integer arithmetic and clamped
+/// to `1..=n`, so `p = 0` yields the minimum and `p >= 100` the maximum.
+/// Returns `None` when there are no samples.
+pub(crate) fn nearest_rank_percentile(samples: &[u32], percentile: u8) -> Option<u32> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+
+    let n = sorted.len();
+    let p = usize::from(percentile.min(100));
+    let rank = (p * n).div_ceil(100).clamp(1, n);
+
+    Some(sorted[rank - 1])
 }
 
 /// Measure ICMP ping latency
@@ -428,28 +992,35 @@ async fn measure_ping_latency(endpoint: &str) -> Result<u32> {
 }
 
 /// Measure TCP connection latency
-async fn measure_tcp_latency(endpoint: &str, port: u16) -> Result<u32> {
+async fn measure_tcp_latency(
+    endpoint: &str,
+    port: u16,
+    probe_timeout: std::time::Duration,
+) -> Result<u32> {
     use std::time::Instant;
     use tokio::net::TcpStream;
-    use tokio::time::{timeout, Duration};
+    use tokio::time::timeout;
 
     let start = Instant::now();
     let addr = format!("{endpoint}:{port}");
 
-    match timeout(Duration::from_secs(5), TcpStream::connect(&addr)).await {
+    match timeout(probe_timeout, TcpStream::connect(&addr)).await {
         Ok(Ok(_)) => {
             let latency = start.elapsed().as_millis() as u32;
             Ok(latency)
         }
         Ok(Err(e)) => Err(Error::NetworkError(format!("TCP connect failed: {e}"))),
-        Err(_) => Err(Error::NetworkError("TCP connect timeout".to_string())),
+        Err(_) => Err(Error::NetworkError(format!(
+            "TCP connect timeout after {}ms",
+            probe_timeout.as_millis()
+        ))),
     }
 }
 
 /// Measure HTTP request latency
-async fn measure_http_latency(endpoint: &str) -> Result<u32> {
+async fn measure_http_latency(endpoint: &str, probe_timeout: std::time::Duration) -> Result<u32> {
     use std::time::Instant;
-    use tokio::time::{timeout, Duration};
+    use tokio::time::timeout;
 
     let start = Instant::now();
     let url = if endpoint.starts_with("http") {
@@ -459,17 +1030,20 @@ async fn measure_http_latency(endpoint: &str) -> Result<u32> {
     };
 
     let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
+        .timeout(probe_timeout)
         .build()
         .map_err(|e| Error::NetworkError(format!("HTTP client error: {e}")))?;
 
-    match timeout(Duration::from_secs(5), client.get(&url).send()).await {
+    match timeout(probe_timeout, client.get(&url).send()).await {
         Ok(Ok(_)) => {
             let latency = start.elapsed().as_millis() as u32;
             Ok(latency)
         }
         Ok(Err(e)) => Err(Error::NetworkError(format!("HTTP request failed: {e}"))),
-        Err(_) => Err(Error::NetworkError("HTTP request timeout".to_string())),
+        Err(_) => Err(Error::NetworkError(format!(
+            "HTTP request timeout after {}ms",
+            probe_timeout.as_millis()
+        ))),
     }
 }
 
@@ -578,12 +1152,14 @@ async fn sync_secret_to_cluster(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::controller::test_harness::fake_client;
     use crate::crd::{
         types::{NodeType, StellarNetwork},
-        CrossClusterConfig, PeerClusterConfig, ResourceRequirements, ResourceSpec, StellarNode,
-        StellarNodeSpec, StorageConfig,
+        CrossClusterConfig, ExternalNameConfig, PeerClusterConfig, ResourceRequirements,
+        ResourceSpec, StellarNode, StellarNodeSpec, StorageConfig,
     };
     use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use kube::api::DynamicObject;
 
     // -----------------------------------------------------------------------
     // Helpers
@@ -770,6 +1346,377 @@ mod tests {
         );
     }
 
+    // -----------------------------------------------------------------------
+    // Peer endpoint DNS re-resolution
+    // -----------------------------------------------------------------------
+
+    fn resolution(
+        endpoint: &str,
+        ips: &[&str],
+        resolved_at: DateTime<Utc>,
+    ) -> PeerEndpointResolution {
+        PeerEndpointResolution {
+            cluster_id: "cluster-b".to_string(),
+            endpoint: endpoint.to_string(),
+            resolved_ips: ips.iter().map(|ip| ip.to_string()).collect(),
+            resolved_at: Some(resolved_at.to_rfc3339()),
+            pinned: false,
+            last_error: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expired_resolutions_are_dropped_from_status() {
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let nodes: Api<StellarNode> = Api::namespaced(client.clone(), "stellar");
+
+        let mut node = external_name_node(true);
+        let mut pinned = resolution("203.0.113.20", &["203.0.113.20"], Utc::now());
+        pinned.pinned = true;
+        node.status = Some(crate::crd::StellarNodeStatus {
+            peer_endpoints: vec![pinned],
+            ..Default::default()
+        });
+        let node = nodes
+            .create(&kube::api::PostParams::default(), &node)
+            .await
+            .unwrap();
+
+        expire_peer_resolutions(&client, &node, false)
+            .await
+            .unwrap();
+        let node = nodes.get("validator-a").await.unwrap();
+        assert!(node.status.unwrap().peer_endpoints.is_empty());
+    }
+
+    #[test]
+    fn test_unresolved_peer_needs_resolution() {
+        let peer = make_peer("cluster-b", "core.eu.example.com");
+        assert!(needs_resolution(None, &peer, 300, false, Utc::now()));
+    }
+
+    #[test]
+    fn test_fresh_resolution_is_reused_until_ttl() {
+        let now = Utc::now();
+        let peer = make_peer("cluster-b", "core.eu.example.com");
+        let cached = resolution(
+            "core.eu.example.com",
+            &["203.0.113.5"],
+            now - chrono::Duration::seconds(299),
+        );
+        assert!(!needs_resolution(Some(&cached), &peer, 300, false, now));
+
+        let expired = resolution(
+            "core.eu.example.com",
+            &["203.0.113.5"],
+            now - chrono::Duration::seconds(300),
+        );
+        assert!(needs_resolution(Some(&expired), &peer, 300, false, now));
+    }
+
+    #[test]
+    fn test_changed_endpoint_is_resolved_immediately() {
+        let now = Utc::now();
+        let peer = make_peer("cluster-b", "core.us.example.com");
+        let cached = resolution("core.eu.example.com", &["203.0.113.5"], now);
+        assert!(needs_resolution(Some(&cached), &peer, 300, false, now));
+    }
+
+    #[test]
+    fn test_pinned_resolution_never_expires() {
+        let now = Utc::now();
+        let peer = make_peer("cluster-b", "core.eu.example.com");
+        let mut cached = resolution(
+            "core.eu.example.com",
+            &["203.0.113.5"],
+            now - chrono::Duration::days(7),
+        );
+        cached.pinned = true;
+        assert!(!needs_resolution(Some(&cached), &peer, 300, true, now));
+        assert!(
+            needs_resolution(Some(&cached), &peer, 300, false, now),
+            "unpinning must trigger a fresh lookup"
+        );
+    }
+
+    #[test]
+    fn test_re_resolution_updates_ips_and_timestamp() {
+        let now = Utc::now();
+        let peer = make_peer("cluster-b", "core.eu.example.com");
+        let mut cached = resolution(
+            "core.eu.example.com",
+            &["203.0.113.5"],
+            now - chrono::Duration::seconds(600),
+        );
+        cached.last_error = Some("timed out".to_string());
+
+        let updated = apply_resolution(
+            Some(&cached),
+            &peer,
+            false,
+            Ok(vec!["203.0.113.9".to_string()]),
+            now,
+        );
+        assert_eq!(updated.resolved_ips, vec!["203.0.113.9"]);
+        assert_eq!(updated.resolved_at, Some(now.to_rfc3339()));
+        assert_eq!(updated.last_error, None);
+    }
+
+    #[test]
+    fn test_failed_re_resolution_keeps_previous_ips() {
+        let now = Utc::now();
+        let then = now - chrono::Duration::seconds(600);
+        let peer = make_peer("cluster-b", "core.eu.example.com");
+        let cached = resolution("core.eu.example.com", &["203.0.113.5"], then);
+
+        let updated = apply_resolution(
+            Some(&cached),
+            &peer,
+            false,
+            Err(Error::NetworkError("DNS resolution failed".to_string())),
+            now,
+        );
+        assert_eq!(updated.resolved_ips, vec!["203.0.113.5"]);
+        assert_eq!(updated.resolved_at, Some(then.to_rfc3339()));
+        assert!(updated
+            .last_error
+            .unwrap()
+            .contains("DNS resolution failed"));
+    }
+
+    #[test]
+    fn test_failed_resolution_of_new_endpoint_drops_stale_ips() {
+        let now = Utc::now();
+        let peer = make_peer("cluster-b", "core.us.example.com");
+        let cached = resolution("core.eu.example.com", &["203.0.113.5"], now);
+
+        let updated = apply_resolution(Some(&cached), &peer, false, Ok(Vec::new()), now);
+        assert!(updated.resolved_ips.is_empty());
+        assert_eq!(updated.resolved_at, None);
+        assert!(updated.last_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_ip_literal_endpoint_resolves_to_itself() {
+        let ips = resolve_endpoint("203.0.113.20", 11625).await.unwrap();
+        assert_eq!(ips, vec!["203.0.113.20"]);
+    }
+
+    #[test]
+    fn test_pinned_peer_service_is_headless_without_external_name() {
+        let node = make_node("validator-a", "default");
+        let peer = make_peer("cluster-b", "core.eu.example.com");
+
+        let svc = build_pinned_peer_service(&node, &peer, "svc-name");
+        let spec = svc.spec.unwrap();
+        assert_eq!(spec.type_.as_deref(), Some("ClusterIP"));
+        assert_eq!(spec.cluster_ip.as_deref(), Some("None"));
+        assert_eq!(spec.external_name, None);
+        assert_eq!(spec.selector, None);
+    }
+
+    #[test]
+    fn test_pinned_peer_endpoints_list_resolved_ips() {
+        let node = make_node("validator-a", "default");
+        let peer = PeerClusterConfig {
+            port: Some(11700),
+            ..make_peer("cluster-b", "core.eu.example.com")
+        };
+        let ips = vec!["203.0.113.5".to_string(), "203.0.113.6".to_string()];
+
+        let endpoints = build_peer_endpoints(&node, &peer, "svc-name", &ips);
+        assert_eq!(endpoints.metadata.name.as_deref(), Some("svc-name"));
+        let subset = &endpoints.subsets.unwrap()[0];
+        let addresses: Vec<_> = subset
+            .addresses
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|a| a.ip.as_str())
+            .collect();
+        assert_eq!(addresses, vec!["203.0.113.5", "203.0.113.6"]);
+        assert_eq!(subset.ports.as_ref().unwrap()[0].port, 11700);
+    }
+
+    fn external_name_node(pin_to_ip: bool) -> StellarNode {
+        let mut node = make_node("validator-a", "stellar");
+        node.metadata.uid = Some("uid-a".to_string());
+        node.spec.cross_cluster = Some(CrossClusterConfig {
+            enabled: true,
+            mode: CrossClusterMode::ExternalName,
+            external_name: Some(ExternalNameConfig {
+                external_dns_name: "validator-a.example.com".to_string(),
+                dns_provider: None,
+                ttl: 300,
+                create_external_name_services: true,
+                pin_to_ip,
+            }),
+            peer_clusters: vec![make_peer("cluster-b", "203.0.113.20")],
+            ..Default::default()
+        });
+        node
+    }
+
+    #[tokio::test]
+    async fn test_unpinned_peer_drops_its_endpoints() {
+        use k8s_openapi::api::core::v1::Endpoints;
+
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let endpoints: Api<Endpoints> = Api::namespaced(client.clone(), "stellar");
+
+        let pinned = external_name_node(true);
+        let config = pinned.spec.cross_cluster.clone().unwrap();
+        ensure_external_name_services(&client, &pinned, &config)
+            .await
+            .unwrap();
+        assert!(endpoints
+            .get_opt("validator-a-peer-cluster-b")
+            .await
+            .unwrap()
+            .is_some());
+
+        let unpinned = external_name_node(false);
+        let config = unpinned.spec.cross_cluster.clone().unwrap();
+        ensure_external_name_services(&client, &unpinned, &config)
+            .await
+            .unwrap();
+        assert!(endpoints
+            .get_opt("validator-a-peer-cluster-b")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unpinned_peer_keeps_foreign_endpoints() {
+        use k8s_openapi::api::core::v1::Endpoints;
+
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let endpoints: Api<Endpoints> = Api::namespaced(client.clone(), "stellar");
+        let foreign: Endpoints = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "validator-a-peer-cluster-b", "namespace": "stellar" }
+        }))
+        .unwrap();
+        endpoints
+            .create(&kube::api::PostParams::default(), &foreign)
+            .await
+            .unwrap();
+
+        let node = external_name_node(false);
+        let config = node.spec.cross_cluster.clone().unwrap();
+        ensure_external_name_services(&client, &node, &config)
+            .await
+            .unwrap();
+        assert!(endpoints
+            .get_opt("validator-a-peer-cluster-b")
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    // -----------------------------------------------------------------------
+    // Submariner ServiceImport discovery
+    // -----------------------------------------------------------------------
+
+    fn service_import(name: &str, ports: serde_json::Value, clusters: &[&str]) -> DynamicObject {
+        serde_json::from_value(serde_json::json!({
+            "apiVersion": "multicluster.x-k8s.io/v1alpha1",
+            "kind": "ServiceImport",
+            "metadata": { "name": name, "namespace": "stellar" },
+            "spec": { "type": "ClusterSetIP", "ips": ["243.0.0.5"], "ports": ports },
+            "status": {
+                "clusters": clusters
+                    .iter()
+                    .map(|c| serde_json::json!({ "cluster": c }))
+                    .collect::<Vec<_>>()
+            }
+        }))
+        .unwrap()
+    }
+
+    fn stellar_ports() -> serde_json::Value {
+        serde_json::json!([
+            { "name": "peer", "port": 11625, "protocol": "TCP" },
+            { "name": "http", "port": 11626, "protocol": "TCP" }
+        ])
+    }
+
+    #[test]
+    fn test_service_import_yields_peer_per_source_cluster() {
+        let import = service_import(
+            "validator-b-service",
+            stellar_ports(),
+            &["cluster-eu", "cluster-us"],
+        );
+        let peers = peers_from_service_import(&import);
+
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].cluster_id, "cluster-eu");
+        assert_eq!(
+            peers[0].endpoint,
+            "cluster-eu.validator-b-service.stellar.svc.clusterset.local"
+        );
+        assert_eq!(peers[0].port, Some(11625));
+        assert!(peers[0].enabled);
+        assert_eq!(peers[1].cluster_id, "cluster-us");
+    }
+
+    #[test]
+    fn test_service_import_without_peer_port_is_ignored() {
+        let import = service_import(
+            "postgres",
+            serde_json::json!([{ "name": "pg", "port": 5432, "protocol": "TCP" }]),
+            &["cluster-eu"],
+        );
+        assert!(peers_from_service_import(&import).is_empty());
+    }
+
+    #[test]
+    fn test_service_import_without_clusters_yields_no_peers() {
+        let import = service_import("validator-b-service", stellar_ports(), &[]);
+        assert!(peers_from_service_import(&import).is_empty());
+    }
+
+    #[test]
+    fn test_service_imports_skip_the_local_cluster_only() {
+        // The node's own export is imported under its own name, and the
+        // same-named nodes of other clusters are exported under it as well.
+        let imports = vec![
+            service_import(
+                "validator-a-service",
+                stellar_ports(),
+                &["cluster-eu", "cluster-us"],
+            ),
+            service_import("validator-b-service", stellar_ports(), &["cluster-eu"]),
+        ];
+
+        let peers = service_import_peers(&imports, "cluster-eu");
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].cluster_id, "cluster-us");
+        assert_eq!(
+            peers[0].endpoint,
+            "cluster-us.validator-a-service.stellar.svc.clusterset.local"
+        );
+    }
+
+    #[test]
+    fn test_configured_peers_take_precedence_over_discovered() {
+        let configured = vec![make_peer("cluster-eu", "10.0.0.1")];
+        let discovered = peers_from_service_import(&service_import(
+            "validator-b-service",
+            stellar_ports(),
+            &["cluster-eu", "cluster-us"],
+        ));
+
+        let peers = merge_discovered_peers(&configured, discovered);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].endpoint, "10.0.0.1");
+        assert_eq!(peers[1].cluster_id, "cluster-us");
+    }
+
     // -----------------------------------------------------------------------
     // PeerLatencyStatus — detecting unreachable clusters and status propagation
     // -----------------------------------------------------------------------
@@ -836,41 +1783,138 @@ mod tests {
     // Latency percentile calculation (retry/sampling logic)
     // -----------------------------------------------------------------------
 
-    /// Replicate the percentile index calculation from `measure_peer_latency`.
-    fn percentile_index(sample_count: usize, percentile: u8) -> usize {
-        let index = ((percentile as f64 / 100.0) * sample_count as f64).ceil() as usize - 1;
-        index.min(sample_count - 1)
-    }
-
     #[test]
     fn test_p95_of_ten_samples_returns_tenth_sample() {
-        // 10 samples, p95 → ceil(0.95 * 10) - 1 = ceil(9.5) - 1 = 10 - 1 = 9 (last)
-        let mut samples: Vec<u32> = (10..20).collect(); // [10, 11, ..., 19]
-        samples.sort_unstable();
-        let idx = percentile_index(samples.len(), 95);
+        // rank = ceil(0.95 * 10) = 10 → last sample
+        let samples: Vec<u32> = (10..20).collect(); // [10, 11, ..., 19]
         assert_eq!(
-            samples[idx], 19,
+            nearest_rank_percentile(&samples, 95),
+            Some(19),
             "p95 of 10 samples should be the last (highest) value"
         );
     }
 
     #[test]
     fn test_p50_of_ten_samples_returns_median() {
-        // 10 samples, p50 → ceil(0.50 * 10) - 1 = ceil(5.0) - 1 = 5 - 1 = 4
-        let mut samples: Vec<u32> = vec![10, 20, 30, 40, 50, 60, 70, 80, 90, 100];
-        samples.sort_unstable();
-        let idx = percentile_index(samples.len(), 50);
-        assert_eq!(samples[idx], 50);
+        // rank = ceil(0.50 * 10) = 5
+        let samples: Vec<u32> = vec![100, 20, 30, 40, 50, 60, 70, 80, 90, 10];
+        assert_eq!(nearest_rank_percentile(&samples, 50), Some(50));
+    }
+
+    #[test]
+    fn test_rank_is_exact_where_float_math_rounds_up() {
+        // (7 / 100.0) * 100.0 evaluates to 7.000000000000001 in f64, which a
+        // float ceil turns into rank 8; the nearest rank is 7.
+        let samples: Vec<u32> = (1..=100).collect();
+        assert_eq!(nearest_rank_percentile(&samples, 7), Some(7));
+    }
+
+    #[test]
+    fn test_p99_of_hundred_samples() {
+        let samples: Vec<u32> = (1..=100).rev().collect();
+        assert_eq!(nearest_rank_percentile(&samples, 99), Some(99));
+        assert_eq!(nearest_rank_percentile(&samples, 100), Some(100));
+    }
+
+    #[test]
+    fn test_p0_returns_minimum() {
+        let samples: Vec<u32> = vec![30, 10, 20];
+        assert_eq!(nearest_rank_percentile(&samples, 0), Some(10));
+    }
+
+    #[test]
+    fn test_percentile_above_hundred_is_clamped() {
+        let samples: Vec<u32> = vec![30, 10, 20];
+        assert_eq!(nearest_rank_percentile(&samples, 250), Some(30));
     }
 
     #[test]
     fn test_single_sample_always_returned_at_any_percentile() {
-        let samples: Vec<u32> = vec![42];
-        let idx = percentile_index(samples.len(), 95);
+        for p in [0, 50, 95, 99, 100] {
+            assert_eq!(
+                nearest_rank_percentile(&[42], p),
+                Some(42),
+                "single sample must always be returned regardless of percentile"
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_samples_have_no_percentile() {
+        assert_eq!(nearest_rank_percentile(&[], 95), None);
+    }
+
+    // -----------------------------------------------------------------------
+    // Outlier rejection
+    // -----------------------------------------------------------------------
+
+    fn latency_config(
+        outlier_rejection: crate::crd::LatencyOutlierRejection,
+    ) -> crate::crd::LatencyMeasurementConfig {
+        serde_json::from_value(serde_json::json!({
+            "outlierRejection": outlier_rejection,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_latency_config_defaults_disable_outlier_rejection() {
+        let config: crate::crd::LatencyMeasurementConfig =
+            serde_json::from_value(serde_json::json!({})).unwrap();
         assert_eq!(
-            samples[idx], 42,
-            "single sample must always be returned regardless of percentile"
+            config.outlier_rejection,
+            crate::crd::LatencyOutlierRejection::None
         );
+        assert_eq!(config.mad_threshold, 3.0);
+        assert_eq!(config.trim_percent, 10);
+    }
+
+    #[test]
+    fn test_without_rejection_spike_trips_p95() {
+        let config = latency_config(crate::crd::LatencyOutlierRejection::None);
+        let samples = [40, 42, 41, 43, 40, 44, 42, 41, 43, 900];
+        let kept = reject_outliers(&samples, &config);
+        assert_eq!(nearest_rank_percentile(&kept, 95), Some(900));
+    }
+
+    #[test]
+    fn test_mad_rejects_injected_spike() {
+        let config = latency_config(crate::crd::LatencyOutlierRejection::Mad);
+        let samples = [40, 42, 41, 43, 40, 44, 42, 41, 43, 900];
+        let kept = reject_outliers(&samples, &config);
+        assert_eq!(kept.len(), 9);
+        assert!(!kept.contains(&900), "GC-pause spike must be rejected");
+        assert_eq!(nearest_rank_percentile(&kept, 95), Some(44));
+    }
+
+    #[test]
+    fn test_mad_keeps_small_jitter_around_identical_samples() {
+        let config = latency_config(crate::crd::LatencyOutlierRejection::Mad);
+        let samples = [50, 50, 50, 50, 51, 50, 49, 50, 50, 50];
+        assert_eq!(reject_outliers(&samples, &config).len(), samples.len());
+    }
+
+    #[test]
+    fn test_mad_keeps_consistently_high_latency() {
+        // A peer that is slow on every sample is not an outlier.
+        let config = latency_config(crate::crd::LatencyOutlierRejection::Mad);
+        let samples = [300, 310, 305, 298, 302];
+        assert_eq!(reject_outliers(&samples, &config).len(), samples.len());
+    }
+
+    #[test]
+    fn test_trim_drops_both_ends() {
+        let config = latency_config(crate::crd::LatencyOutlierRejection::Trim);
+        let samples = [40, 42, 41, 43, 1, 44, 42, 41, 43, 900];
+        let kept = reject_outliers(&samples, &config);
+        assert_eq!(kept, vec![40, 41, 41, 42, 42, 43, 43, 44]);
+    }
+
+    #[test]
+    fn test_trim_never_drops_every_sample() {
+        let mut config = latency_config(crate::crd::LatencyOutlierRejection::Trim);
+        config.trim_percent = 50;
+        assert_eq!(reject_outliers(&[10, 20], &config), vec![10, 20]);
     }
 
     #[test]
@@ -910,4 +1954,103 @@ mod tests {
         assert_eq!(active.len(), 1, "only enabled peers must be processed");
         assert_eq!(active[0].cluster_id, "cluster-enabled");
     }
+
+    // -----------------------------------------------------------------------
+    // Probe timeouts and retry of NetworkError
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn test_probe_retry_backoff_is_tiered() {
+        use std::time::Duration;
+        assert_eq!(probe_retry_backoff(0), Duration::from_millis(200));
+        assert_eq!(probe_retry_backoff(1), Duration::from_millis(1000));
+        assert_eq!(probe_retry_backoff(2), Duration::from_millis(5000));
+        assert_eq!(probe_retry_backoff(10), Duration::from_millis(5000));
+    }
+
+    #[tokio::test]
+    async fn test_network_error_triggers_retry() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let result = probe_with_retry(
+            2,
+            |_| std::time::Duration::ZERO,
+            || async {
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    Err(Error::NetworkError("connection reset".to_string()))
+                } else {
+                    Ok(42)
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retries_stop_after_max_retries() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let result = probe_with_retry(
+            2,
+            |_| std::time::Duration::ZERO,
+            || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Error::NetworkError("unreachable".to_string()))
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::NetworkError(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_non_retriable_error_is_not_retried() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let calls = AtomicU32::new(0);
+        let result = probe_with_retry(
+            2,
+            |_| std::time::Duration::ZERO,
+            || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(Error::ConfigError("bad endpoint".to_string()))
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::ConfigError(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_http_probe_respects_configured_timeout() {
+        use std::time::{Duration, Instant};
+
+        // Accept connections but never answer, so only the timeout can end the probe.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+
+        let start = Instant::now();
+        let result =
+            measure_http_latency(&format!("http://{addr}/info"), Duration::from_millis(100)).await;
+
+        assert!(matches!(result, Err(Error::NetworkError(_))));
+        assert!(
+            start.elapsed() < Duration::from_secs(2),
+            "probe must give up after the configured timeout, took {:?}",
+            start.elapsed()
+        );
+    }
 }
@@ -9,7 +9,8 @@ use reqwest::Client as HttpClient;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 
-use crate::crd::{NodeType, StellarNode};
+use super::version_rollback::WorkloadRollout;
+use crate::crd::{CanaryMetricCheck, NodeType, StellarNode};
 use crate::error::{Error, Result};
 
 // Annotation keys for CVE tracking
@@ -22,6 +23,8 @@ pub const CANARY_TEST_STATUS_ANNOTATION: &str = "stellar.org/canary-test-status"
 pub const CVE_ROLLOUT_STATUS_ANNOTATION: &str = "stellar.org/cve-rollout-status";
 pub const CVE_ROLLBACK_REASON_ANNOTATION: &str = "stellar.org/cve-rollback-reason";
 pub const CVE_AUTO_PATCH_ANNOTATION: &str = "stellar.org/cve-auto-patch";
+pub const CVE_ROLLOUT_FINISHED_ANNOTATION: &str = "stellar.org/cve-rollout-finished-at";
+pub const CANARY_STARTED_AT_ANNOTATION: &str = "stellar.org/canary-started-at";
 
 /// Result of a CVE scan from registry scanner
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -211,6 +214,51 @@ impl CVERolloutStatus {
             CVERolloutStatus::Failed => "Failed",
         }
     }
+
+    /// Parse the value of the rollout status annotation
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Idle" => Some(CVERolloutStatus::Idle),
+            "CanaryTesting" => Some(CVERolloutStatus::CanaryTesting),
+            "Rolling" => Some(CVERolloutStatus::Rolling),
+            "Complete" => Some(CVERolloutStatus::Complete),
+            "RollingBack" => Some(CVERolloutStatus::RollingBack),
+            "RolledBack" => Some(CVERolloutStatus::RolledBack),
+            "Failed" => Some(CVERolloutStatus::Failed),
+            _ => None,
+        }
+    }
+
+    /// Whether the rollout is over and its canary no longer needed
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            CVERolloutStatus::Complete | CVERolloutStatus::RolledBack
+        )
+    }
+}
+
+/// Whether the canary of a rollout that finished at `finished_at` (Unix
+/// seconds) has outlived its grace period at `now`
+pub fn canary_cleanup_due(
+    status: CVERolloutStatus,
+    finished_at: Option<i64>,
+    now: i64,
+    grace_secs: u64,
+) -> bool {
+    status.is_terminal()
+        && finished_at.is_some_and(|finished| now.saturating_sub(finished) >= grace_secs as i64)
+}
+
+/// Whether a canary started at `started_at` (Unix seconds) has run past
+/// `timeout_secs` at `now`; canaries without a recorded start never time out
+pub fn canary_timed_out(started_at: Option<i64>, now: i64, timeout_secs: u64) -> bool {
+    started_at.is_some_and(|started| now.saturating_sub(started) >= timeout_secs as i64)
+}
+
+/// Whether the workload finished rolling out the patched `version`
+pub fn patched_rollout_finished(rollout: &WorkloadRollout, version: &str) -> bool {
+    rollout.complete && rollout.version == version
 }
 
 /// Client for scanning container images for CVEs
@@ -493,6 +541,72 @@ impl CanaryTestRunner {
     }
 }
 
+/// Query of a canary metric check with its placeholders filled in
+pub fn render_canary_query(query: &str, namespace: &str, canary: &str) -> String {
+    query
+        .replace("{namespace}", namespace)
+        .replace("{canary}", canary)
+}
+
+/// Whether `value` lies within the bounds of `check`
+pub fn canary_metric_within_bounds(check: &CanaryMetricCheck, value: f64) -> bool {
+    check.max.is_none_or(|max| value <= max) && check.min.is_none_or(|min| value >= min)
+}
+
+/// Names of the checks whose sampled value is out of bounds; `values` holds
+/// one sample per check, `None` where Prometheus had no data
+pub fn failed_canary_checks<'a>(
+    checks: &'a [CanaryMetricCheck],
+    values: &[Option<f64>],
+) -> Vec<&'a str> {
+    checks
+        .iter()
+        .zip(values)
+        .filter(|(check, value)| value.is_some_and(|v| !canary_metric_within_bounds(check, v)))
+        .map(|(check, _)| check.name.as_str())
+        .collect()
+}
+
+/// Decide the canary analysis from one sample per check.
+///
+/// Any value out of bounds fails the canary; otherwise a check still without
+/// data keeps the analysis running, and the canary passes once every check
+/// has a value within bounds.
+pub fn evaluate_canary_analysis(
+    checks: &[CanaryMetricCheck],
+    values: &[Option<f64>],
+) -> CanaryTestStatus {
+    if !failed_canary_checks(checks, values).is_empty() {
+        CanaryTestStatus::Failed
+    } else if values.len() < checks.len() || values.iter().any(Option::is_none) {
+        CanaryTestStatus::Running
+    } else {
+        CanaryTestStatus::Passed
+    }
+}
+
+/// Sample every canary analysis check from Prometheus, in order
+pub async fn sample_canary_metrics(
+    prometheus_url: &str,
+    checks: &[CanaryMetricCheck],
+    namespace: &str,
+    canary: &str,
+) -> Vec<Option<f64>> {
+    let mut values = Vec::with_capacity(checks.len());
+    for check in checks {
+        let query = render_canary_query(&check.query, namespace, canary);
+        values.push(
+            crate::controller::predictive_scaling::scrape_prometheus_metric(
+                prometheus_url,
+                &query,
+                "",
+            )
+            .await,
+        );
+    }
+    values
+}
+
 /// Monitor consensus health during patched version rollout
 pub struct ConsensusHealthMonitor;
 
@@ -8,16 +8,19 @@ use kube::{
 use tracing::{debug, info, warn};
 
 use crate::crd::StellarNode;
-use crate::error::Result;
+use crate::error::{Error, Result};
 
 use super::cve::{
-    create_canary_deployment, delete_canary_deployment, rollback_version, trigger_rolling_update,
-    CVERolloutStatus, CanaryTestRunner, CanaryTestStatus, ConsensusHealthMonitor,
-    RegistryScannerClient, CANARY_DEPLOYMENT_ANNOTATION, CANARY_TEST_STATUS_ANNOTATION,
-    CVE_AUTO_PATCH_ANNOTATION, CVE_DETECTED_ANNOTATION, CVE_PATCHED_VERSION_ANNOTATION,
-    CVE_ROLLBACK_REASON_ANNOTATION, CVE_ROLLOUT_STATUS_ANNOTATION, CVE_SCAN_TIME_ANNOTATION,
+    canary_cleanup_due, canary_timed_out, create_canary_deployment, delete_canary_deployment,
+    evaluate_canary_analysis, failed_canary_checks, patched_rollout_finished, rollback_version,
+    sample_canary_metrics, trigger_rolling_update, CVERolloutStatus, CanaryTestRunner,
+    CanaryTestStatus, ConsensusHealthMonitor, RegistryScannerClient, CANARY_DEPLOYMENT_ANNOTATION,
+    CANARY_STARTED_AT_ANNOTATION, CANARY_TEST_STATUS_ANNOTATION, CVE_AUTO_PATCH_ANNOTATION,
+    CVE_DETECTED_ANNOTATION, CVE_PATCHED_VERSION_ANNOTATION, CVE_ROLLBACK_REASON_ANNOTATION,
+    CVE_ROLLOUT_FINISHED_ANNOTATION, CVE_ROLLOUT_STATUS_ANNOTATION, CVE_SCAN_TIME_ANNOTATION,
     CVE_VULNERABLE_IMAGE_ANNOTATION,
 };
+use super::version_rollback::fetch_workload_rollout;
 use crate::crd::CVEHandlingConfig;
 
 /// Check if auto-patch is enabled via annotation (safety gate)
@@ -68,11 +71,16 @@ pub async fn reconcile_cve_patches(
     // Check status of ongoing CVE patch operations
     check_cve_patch_status(client, node, config).await?;
 
-    // Monitor consensus health if rolling out patched version
-    if is_rolling_out_patch(node) {
-        monitor_consensus_during_rollout(client, node, config).await?;
+    // Monitor consensus health if rolling out patched version, and mark the
+    // rollout complete once every replica runs it
+    if is_rolling_out_patch(node) && !monitor_consensus_during_rollout(client, node, config).await?
+    {
+        complete_finished_rollout(client, node).await?;
     }
 
+    // Remove the canary once the rollout is over
+    cleanup_finished_canary(client, node, config).await?;
+
     Ok(())
 }
 
@@ -223,6 +231,10 @@ async fn initiate_canary_deployment(
         CANARY_TEST_STATUS_ANNOTATION.to_string(),
         CanaryTestStatus::Running.as_str().to_string(),
     );
+    annotations.insert(
+        CANARY_STARTED_AT_ANNOTATION.to_string(),
+        Utc::now().timestamp().to_string(),
+    );
 
     update_node_annotations(client, node, annotations).await?;
 
@@ -272,34 +284,38 @@ async fn check_cve_patch_status(
                     namespace, name, canary_name
                 );
 
-                let result = run_canary_health_checks(client, node).await?;
+                let result = run_canary_health_checks(client, node, config).await?;
+                let started_at = annotations
+                    .get(CANARY_STARTED_AT_ANNOTATION)
+                    .and_then(|ts| ts.parse::<i64>().ok());
 
                 if result == CanaryTestStatus::Passed {
                     // Tests passed, initiate rolling update
                     on_canary_test_passed(client, node, config).await?;
                 } else if result == CanaryTestStatus::Failed {
                     // Tests failed, mark as failed
-                    on_canary_test_failed(client, node, config).await?;
+                    on_canary_test_failed(client, node, CanaryTestStatus::Failed).await?;
+                } else if canary_timed_out(
+                    started_at,
+                    Utc::now().timestamp(),
+                    config.canary_test_timeout_secs,
+                ) {
+                    // No verdict, e.g. the metrics never arrived, within the timeout
+                    warn!(
+                        "Canary {}/{}/{} reached no verdict within {}s",
+                        namespace, name, canary_name, config.canary_test_timeout_secs
+                    );
+                    on_canary_test_failed(client, node, CanaryTestStatus::Timeout).await?;
                 }
             }
         }
 
         CanaryTestStatus::Passed => {
-            // Canary tests passed, check if rolling update is complete
-            let rollout_status_str = annotations
-                .get(CVE_ROLLOUT_STATUS_ANNOTATION)
-                .map(|s| s.as_str())
-                .unwrap_or("Idle");
-
-            if rollout_status_str == "Complete" {
-                // Rollout complete, clean up canary deployment
-                if let Some(canary_name) = annotations.get(CANARY_DEPLOYMENT_ANNOTATION) {
-                    delete_canary_deployment(client, node, canary_name).await?;
-                }
-            }
+            // Canary tests passed; the canary is removed by
+            // cleanup_finished_canary once the rollout completes
         }
 
-        CanaryTestStatus::Failed => {
+        CanaryTestStatus::Failed | CanaryTestStatus::Timeout => {
             // Canary tests failed, do not proceed with rollout
             warn!(
                 "Canary tests failed for {}/{}, will not proceed with patched version rollout",
@@ -313,8 +329,12 @@ async fn check_cve_patch_status(
     Ok(())
 }
 
-/// Run health checks on canary pod
-async fn run_canary_health_checks(client: &Client, node: &StellarNode) -> Result<CanaryTestStatus> {
+/// Run health checks on canary pod, then the metric analysis once they pass
+async fn run_canary_health_checks(
+    client: &Client,
+    node: &StellarNode,
+    config: &CVEHandlingConfig,
+) -> Result<CanaryTestStatus> {
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
 
     // Get canary pod
@@ -326,12 +346,57 @@ async fn run_canary_health_checks(client: &Client, node: &StellarNode) -> Result
 
     if let Some(canary_pod) = pods.items.first() {
         let test_status = CanaryTestRunner::run_tests(client, node, canary_pod).await?;
-        return Ok(test_status);
+        if test_status != CanaryTestStatus::Passed || config.canary_analysis.is_empty() {
+            return Ok(test_status);
+        }
+        return Ok(run_canary_analysis(node, &namespace, config).await);
     }
 
     Ok(CanaryTestStatus::Running)
 }
 
+/// Evaluate the configured canary metric checks against Prometheus
+async fn run_canary_analysis(
+    node: &StellarNode,
+    namespace: &str,
+    config: &CVEHandlingConfig,
+) -> CanaryTestStatus {
+    let Some(prometheus_url) = config.prometheus_url.as_deref() else {
+        warn!(
+            "canaryAnalysis is set for {}/{} but prometheusUrl is not, failing the canary",
+            namespace,
+            node.name_any()
+        );
+        return CanaryTestStatus::Failed;
+    };
+
+    let canary = node
+        .annotations()
+        .get(CANARY_DEPLOYMENT_ANNOTATION)
+        .cloned()
+        .unwrap_or_else(|| format!("{}-cve-canary", node.name_any()));
+    let values =
+        sample_canary_metrics(prometheus_url, &config.canary_analysis, namespace, &canary).await;
+
+    let failed = failed_canary_checks(&config.canary_analysis, &values);
+    if !failed.is_empty() {
+        warn!(
+            "Canary {}/{} failed metric analysis: {}",
+            namespace,
+            canary,
+            failed.join(", ")
+        );
+    }
+    let status = evaluate_canary_analysis(&config.canary_analysis, &values);
+    debug!(
+        "Canary analysis for {}/{}: {}",
+        namespace,
+        canary,
+        status.as_str()
+    );
+    status
+}
+
 /// Handle successful canary test
 async fn on_canary_test_passed(
     client: &Client,
@@ -372,11 +437,11 @@ async fn on_canary_test_passed(
     Ok(())
 }
 
-/// Handle failed canary test
+/// Handle a canary that failed its tests or timed out, recording `status`
 async fn on_canary_test_failed(
     client: &Client,
     node: &StellarNode,
-    _config: &CVEHandlingConfig,
+    status: CanaryTestStatus,
 ) -> Result<()> {
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let name = node.name_any();
@@ -400,7 +465,7 @@ async fn on_canary_test_failed(
     let mut new_annotations = annotations.clone();
     new_annotations.insert(
         CANARY_TEST_STATUS_ANNOTATION.to_string(),
-        CanaryTestStatus::Failed.as_str().to_string(),
+        status.as_str().to_string(),
     );
     new_annotations.insert(
         CVE_ROLLOUT_STATUS_ANNOTATION.to_string(),
@@ -422,14 +487,15 @@ fn is_rolling_out_patch(node: &StellarNode) -> bool {
         .unwrap_or(false)
 }
 
-/// Monitor consensus health during rollout and rollback if needed
+/// Monitor consensus health during rollout and rollback if needed; returns
+/// whether the rollout was rolled back
 async fn monitor_consensus_during_rollout(
     client: &Client,
     node: &StellarNode,
     config: &CVEHandlingConfig,
-) -> Result<()> {
+) -> Result<bool> {
     if !config.enable_auto_rollback {
-        return Ok(());
+        return Ok(false);
     }
 
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
@@ -486,6 +552,10 @@ async fn monitor_consensus_during_rollout(
             CVE_ROLLOUT_STATUS_ANNOTATION.to_string(),
             CVERolloutStatus::RolledBack.as_str().to_string(),
         );
+        new_annotations.insert(
+            CVE_ROLLOUT_FINISHED_ANNOTATION.to_string(),
+            Utc::now().timestamp().to_string(),
+        );
         new_annotations.insert(
             CVE_ROLLBACK_REASON_ANNOTATION.to_string(),
             format!(
@@ -498,7 +568,95 @@ async fn monitor_consensus_during_rollout(
         update_node_annotations(client, node, new_annotations).await?;
     }
 
-    Ok(())
+    Ok(degraded)
+}
+
+/// Mark a rolling patched version `Complete` once the workload runs it on
+/// every replica, stamping when it finished for the canary cleanup
+async fn complete_finished_rollout(client: &Client, node: &StellarNode) -> Result<()> {
+    let Some(rollout) = fetch_workload_rollout(client, node).await? else {
+        return Ok(());
+    };
+    if !patched_rollout_finished(&rollout, &node.spec.version) {
+        return Ok(());
+    }
+
+    info!(
+        "CVE patch rollout of {} completed for {}/{}",
+        rollout.version,
+        node.namespace().unwrap_or_else(|| "default".to_string()),
+        node.name_any()
+    );
+    let mut new_annotations = node.annotations().clone();
+    new_annotations.insert(
+        CVE_ROLLOUT_STATUS_ANNOTATION.to_string(),
+        CVERolloutStatus::Complete.as_str().to_string(),
+    );
+    new_annotations.insert(
+        CVE_ROLLOUT_FINISHED_ANNOTATION.to_string(),
+        Utc::now().timestamp().to_string(),
+    );
+    update_node_annotations(client, node, new_annotations).await
+}
+
+/// Delete the canary of a rollout that completed or was rolled back, once the
+/// grace period has passed; returns whether it was deleted.
+///
+/// The first pass that sees the terminal state stamps
+/// `CVE_ROLLOUT_FINISHED_ANNOTATION` if the transition did not, so the grace
+/// period always runs from a recorded time.
+pub(crate) async fn cleanup_finished_canary(
+    client: &Client,
+    node: &StellarNode,
+    config: &CVEHandlingConfig,
+) -> Result<bool> {
+    let annotations = node.annotations();
+    let Some(status) = annotations
+        .get(CVE_ROLLOUT_STATUS_ANNOTATION)
+        .and_then(|s| CVERolloutStatus::parse(s))
+        .filter(CVERolloutStatus::is_terminal)
+    else {
+        return Ok(false);
+    };
+    let Some(canary_name) = annotations.get(CANARY_DEPLOYMENT_ANNOTATION) else {
+        return Ok(false);
+    };
+
+    let now = Utc::now().timestamp();
+    let finished_at = annotations
+        .get(CVE_ROLLOUT_FINISHED_ANNOTATION)
+        .and_then(|ts| ts.parse::<i64>().ok());
+    let Some(finished_at) = finished_at else {
+        let mut new_annotations = annotations.clone();
+        new_annotations.insert(CVE_ROLLOUT_FINISHED_ANNOTATION.to_string(), now.to_string());
+        update_node_annotations(client, node, new_annotations).await?;
+        return Ok(false);
+    };
+
+    if !canary_cleanup_due(
+        status,
+        Some(finished_at),
+        now,
+        config.canary_cleanup_grace_secs,
+    ) {
+        return Ok(false);
+    }
+
+    info!(
+        "CVE rollout for {}/{} is {}, removing canary {}",
+        node.namespace().unwrap_or_else(|| "default".to_string()),
+        node.name_any(),
+        status.as_str(),
+        canary_name
+    );
+    match delete_canary_deployment(client, node, canary_name).await {
+        Ok(()) => {}
+        Err(Error::KubeError(kube::Error::Api(e))) if e.code == 404 => {}
+        Err(e) => return Err(e),
+    }
+    remove_node_annotation(client, node, CANARY_DEPLOYMENT_ANNOTATION).await?;
+
+    Ok(true)
 }
 
 /// Get the image being used by a StellarNode
@@ -567,3 +725,25 @@ async fn update_node_annotations(
 
     Ok(())
 }
+
+/// Remove one annotation from the node
+async fn remove_node_annotation(client: &Client, node: &StellarNode, key: &str) -> Result<()> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let nodes_api: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
+
+    let patch = serde_json::json!({
+        "metadata": {
+            "annotations": { key: null }
+        }
+    });
+
+    nodes_api
+        .patch(
+            &node.name_any(),
+            &PatchParams::apply("cve-handler"),
+            &Patch::Merge(patch),
+        )
+        .await?;
+
+    Ok(())
+}
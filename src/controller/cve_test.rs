@@ -3,11 +3,18 @@
 #[cfg(test)]
 mod tests {
     use crate::controller::cve::{
-        CVECount, CVEDetectionResult, CVERolloutStatus, CanaryTestStatus, Vulnerability,
-        VulnerabilitySeverity,
+        canary_cleanup_due, canary_metric_within_bounds, canary_timed_out,
+        evaluate_canary_analysis, failed_canary_checks, patched_rollout_finished,
+        render_canary_query, CVECount, CVEDetectionResult, CVERolloutStatus, CanaryTestStatus,
+        Vulnerability, VulnerabilitySeverity, CANARY_DEPLOYMENT_ANNOTATION,
+        CVE_ROLLOUT_FINISHED_ANNOTATION, CVE_ROLLOUT_STATUS_ANNOTATION,
     };
-    use crate::crd::CVEHandlingConfig;
+    use crate::controller::cve_reconciler::cleanup_finished_canary;
+    use crate::controller::test_harness::fake_client;
+    use crate::controller::version_rollback::WorkloadRollout;
+    use crate::crd::{CVEHandlingConfig, CanaryMetricCheck, StellarNode, StellarNodeSpec};
     use chrono::Utc;
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_cve_handling_config_defaults() {
@@ -96,6 +103,7 @@ mod tests {
             canary_pass_rate_threshold: 100.0,
             enable_auto_rollback: true,
             consensus_health_threshold: 0.95,
+            ..Default::default()
         };
 
         assert!(config.critical_only);
@@ -130,6 +138,7 @@ mod tests {
             canary_pass_rate_threshold: 100.0,
             enable_auto_rollback: true,
             consensus_health_threshold: 0.90, // Less strict
+            ..Default::default()
         };
 
         assert!(!config.critical_only);
@@ -148,6 +157,7 @@ mod tests {
             canary_pass_rate_threshold: 100.0,
             enable_auto_rollback: false, // Disable auto-rollback
             consensus_health_threshold: 0.95,
+            ..Default::default()
         };
 
         assert!(!config.enable_auto_rollback);
@@ -485,6 +495,7 @@ mod tests {
             canary_pass_rate_threshold: 100.0,
             enable_auto_rollback: true,
             consensus_health_threshold: 0.95,
+            ..Default::default()
         };
 
         assert!(!config.enabled, "Disabled config should skip CVE handling");
@@ -522,4 +533,242 @@ mod tests {
             assert!(default_enabled, "Default behavior should enable auto-patch");
         }
     }
+
+    fn metric_check(name: &str, min: Option<f64>, max: Option<f64>) -> CanaryMetricCheck {
+        CanaryMetricCheck {
+            name: name.to_string(),
+            query: format!("{name}{{pod=~\"{{canary}}-.*\"}}"),
+            max,
+            min,
+        }
+    }
+
+    #[test]
+    fn test_render_canary_query() {
+        let query = render_canary_query(
+            r#"rate(errors{namespace="{namespace}",pod=~"{canary}-.*"}[5m])"#,
+            "stellar",
+            "horizon-cve-canary",
+        );
+        assert_eq!(
+            query,
+            r#"rate(errors{namespace="stellar",pod=~"horizon-cve-canary-.*"}[5m])"#
+        );
+    }
+
+    #[test]
+    fn test_canary_metric_bounds() {
+        let error_rate = metric_check("error_rate", None, Some(0.01));
+        assert!(canary_metric_within_bounds(&error_rate, 0.0));
+        assert!(canary_metric_within_bounds(&error_rate, 0.01));
+        assert!(!canary_metric_within_bounds(&error_rate, 0.05));
+
+        let throughput = metric_check("throughput", Some(10.0), Some(100.0));
+        assert!(!canary_metric_within_bounds(&throughput, 5.0));
+        assert!(canary_metric_within_bounds(&throughput, 50.0));
+        assert!(!canary_metric_within_bounds(&throughput, 150.0));
+    }
+
+    #[test]
+    fn test_canary_analysis_decision() {
+        let checks = vec![
+            metric_check("error_rate", None, Some(0.01)),
+            metric_check("p99_latency", None, Some(0.5)),
+        ];
+
+        assert_eq!(
+            evaluate_canary_analysis(&checks, &[Some(0.001), Some(0.2)]),
+            CanaryTestStatus::Passed
+        );
+        assert_eq!(
+            evaluate_canary_analysis(&checks, &[Some(0.001), Some(1.5)]),
+            CanaryTestStatus::Failed
+        );
+        assert_eq!(
+            failed_canary_checks(&checks, &[Some(0.001), Some(1.5)]),
+            vec!["p99_latency"]
+        );
+
+        // Missing data keeps the analysis running, unless another check failed
+        assert_eq!(
+            evaluate_canary_analysis(&checks, &[Some(0.001), None]),
+            CanaryTestStatus::Running
+        );
+        assert_eq!(
+            evaluate_canary_analysis(&checks, &[Some(0.2), None]),
+            CanaryTestStatus::Failed
+        );
+        assert_eq!(evaluate_canary_analysis(&[], &[]), CanaryTestStatus::Passed);
+    }
+
+    #[test]
+    fn test_canary_cleanup_only_after_terminal_state_and_grace() {
+        let now = 10_000;
+        assert!(CVERolloutStatus::Complete.is_terminal());
+        assert!(CVERolloutStatus::RolledBack.is_terminal());
+        assert!(!CVERolloutStatus::Rolling.is_terminal());
+        assert!(!CVERolloutStatus::CanaryTesting.is_terminal());
+
+        assert!(canary_cleanup_due(
+            CVERolloutStatus::Complete,
+            Some(now - 300),
+            now,
+            300
+        ));
+        assert!(canary_cleanup_due(
+            CVERolloutStatus::RolledBack,
+            Some(now - 600),
+            now,
+            300
+        ));
+        // Still within the grace period
+        assert!(!canary_cleanup_due(
+            CVERolloutStatus::Complete,
+            Some(now - 60),
+            now,
+            300
+        ));
+        // Finish time not recorded yet
+        assert!(!canary_cleanup_due(
+            CVERolloutStatus::Complete,
+            None,
+            now,
+            300
+        ));
+        assert!(!canary_cleanup_due(
+            CVERolloutStatus::Rolling,
+            Some(now - 600),
+            now,
+            300
+        ));
+    }
+
+    #[test]
+    fn test_canary_without_verdict_times_out() {
+        let now = 10_000;
+        assert!(!canary_timed_out(Some(now - 60), now, 300));
+        assert!(canary_timed_out(Some(now - 300), now, 300));
+        // Canaries started before the start time was recorded
+        assert!(!canary_timed_out(None, now, 300));
+    }
+
+    #[test]
+    fn test_patched_rollout_finishes_once_every_replica_runs_it() {
+        let rollout = |version: &str, complete| WorkloadRollout {
+            version: version.to_string(),
+            complete,
+        };
+        assert!(patched_rollout_finished(
+            &rollout("v21.0.1", true),
+            "v21.0.1"
+        ));
+        assert!(!patched_rollout_finished(
+            &rollout("v21.0.1", false),
+            "v21.0.1"
+        ));
+        // Still running the vulnerable version
+        assert!(!patched_rollout_finished(
+            &rollout("v21.0.0", true),
+            "v21.0.1"
+        ));
+    }
+
+    #[test]
+    fn test_rollout_status_parse_round_trips() {
+        for status in [
+            CVERolloutStatus::Idle,
+            CVERolloutStatus::CanaryTesting,
+            CVERolloutStatus::Rolling,
+            CVERolloutStatus::Complete,
+            CVERolloutStatus::RollingBack,
+            CVERolloutStatus::RolledBack,
+            CVERolloutStatus::Failed,
+        ] {
+            assert_eq!(CVERolloutStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(CVERolloutStatus::parse("Unknown"), None);
+    }
+
+    fn finished_rollout_node(status: CVERolloutStatus, finished_at: Option<i64>) -> StellarNode {
+        let mut node = StellarNode::new("validator-1", StellarNodeSpec::default());
+        node.metadata.namespace = Some("stellar".to_string());
+        let mut annotations = BTreeMap::from([
+            (
+                CVE_ROLLOUT_STATUS_ANNOTATION.to_string(),
+                status.as_str().to_string(),
+            ),
+            (
+                CANARY_DEPLOYMENT_ANNOTATION.to_string(),
+                "validator-1-cve-canary".to_string(),
+            ),
+        ]);
+        if let Some(finished_at) = finished_at {
+            annotations.insert(
+                CVE_ROLLOUT_FINISHED_ANNOTATION.to_string(),
+                finished_at.to_string(),
+            );
+        }
+        node.metadata.annotations = Some(annotations);
+        node
+    }
+
+    #[tokio::test]
+    async fn test_terminal_rollout_deletes_canary() {
+        let finished_at = Utc::now().timestamp() - 600;
+        for status in [CVERolloutStatus::Complete, CVERolloutStatus::RolledBack] {
+            let node = finished_rollout_node(status, Some(finished_at));
+            let (client, server) = fake_client();
+            let requests = server.serve();
+
+            let cleaned = cleanup_finished_canary(&client, &node, &CVEHandlingConfig::default())
+                .await
+                .unwrap();
+
+            assert!(cleaned, "{status:?}");
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests[0].method, http::Method::DELETE);
+            assert_eq!(
+                requests[0].path,
+                "/apis/apps/v1/namespaces/stellar/deployments/validator-1-cve-canary"
+            );
+            assert_eq!(requests[1].method, http::Method::PATCH);
+            assert_eq!(
+                requests[1].body["metadata"]["annotations"].get(CANARY_DEPLOYMENT_ANNOTATION),
+                Some(&serde_json::Value::Null)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_terminal_rollout_waits_for_grace_period() {
+        let config = CVEHandlingConfig::default();
+
+        // First pass only records when the rollout finished
+        let node = finished_rollout_node(CVERolloutStatus::RolledBack, None);
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move { server.respond_echo().await });
+        assert!(!cleanup_finished_canary(&client, &node, &config)
+            .await
+            .unwrap());
+        let patch = server.await.unwrap();
+        assert_eq!(patch.method, http::Method::PATCH);
+        assert!(patch.body["metadata"]["annotations"][CVE_ROLLOUT_FINISHED_ANNOTATION].is_string());
+
+        // Within the grace period nothing is sent
+        let node = finished_rollout_node(CVERolloutStatus::Complete, Some(Utc::now().timestamp()));
+        let (client, server) = fake_client();
+        let requests = server.serve();
+        assert!(!cleanup_finished_canary(&client, &node, &config)
+            .await
+            .unwrap());
+        assert!(requests.lock().unwrap().is_empty());
+
+        // A rollout still in progress keeps its canary
+        let node = finished_rollout_node(CVERolloutStatus::Rolling, Some(0));
+        assert!(!cleanup_finished_canary(&client, &node, &config)
+            .await
+            .unwrap());
+        assert!(requests.lock().unwrap().is_empty());
+    }
 }
@@ -123,6 +123,8 @@ pub async fn watch_feature_flags(
         watcher::Config::default().fields(&format!("metadata.name={FEATURE_FLAGS_CONFIGMAP}"));
 
     let mut stream = watcher::watcher(api, watcher_config).boxed();
+    #[cfg(feature = "metrics")]
+    let _watch_stream = crate::controller::metrics::track_watch_streams(1);
 
     info!(
         namespace = %namespace,
@@ -21,6 +21,12 @@ use crate::error::Result;
 /// the resource from being deleted until cleanup is complete.
 pub const STELLAR_NODE_FINALIZER: &str = "stellarnode.stellar.org/finalizer";
 
+/// Annotation that opts a single StellarNode out of finalizer management
+///
+/// Set to `"true"` to skip the finalizer for this node even when the operator
+/// has finalizers enabled.
+pub const DISABLE_FINALIZER_ANNOTATION: &str = "stellar.org/disable-finalizer";
+
 /// Add finalizer to a StellarNode if not present
 ///
 /// Called during the Apply phase to ensure the finalizer is set.
@@ -98,6 +104,44 @@ pub fn has_finalizer(node: &StellarNode) -> bool {
         .any(|f| f == STELLAR_NODE_FINALIZER)
 }
 
+/// Check whether the operator should keep its finalizer on this node
+///
+/// Finalizers are skipped when the operator runs with `--disable-finalizers`
+/// or the node carries [`DISABLE_FINALIZER_ANNOTATION`]. Without a finalizer,
+/// deletion never waits on the operator and cleanup is best-effort: owned
+/// resources are garbage collected through their owner references.
+pub fn finalizer_enabled(node: &StellarNode, operator_disabled: bool) -> bool {
+    if operator_disabled {
+        return false;
+    }
+    !node
+        .annotations()
+        .get(DISABLE_FINALIZER_ANNOTATION)
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// Finalizer list to patch onto a live StellarNode, or `None` if unchanged
+///
+/// Adds our finalizer when enabled and strips it when disabled, so toggling
+/// the setting also releases nodes that were created with the finalizer.
+pub fn desired_finalizers(node: &StellarNode, enabled: bool) -> Option<Vec<String>> {
+    match (enabled, has_finalizer(node)) {
+        (true, false) => {
+            let mut finalizers = node.finalizers().to_vec();
+            finalizers.push(STELLAR_NODE_FINALIZER.to_string());
+            Some(finalizers)
+        }
+        (false, true) => Some(
+            node.finalizers()
+                .iter()
+                .filter(|f| f.as_str() != STELLAR_NODE_FINALIZER)
+                .cloned()
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +170,8 @@ mod tests {
             },
             storage: StorageConfig {
                 storage_class: "standard".to_string(),
+                tier: None,
+                access_modes: vec![],
                 size: "100Gi".to_string(),
                 retention_policy: Default::default(),
                 annotations: None,
@@ -257,6 +303,63 @@ mod tests {
         );
     }
 
+    fn node_with(finalizers: &[&str], annotation: Option<&str>) -> StellarNode {
+        StellarNode {
+            metadata: ObjectMeta {
+                name: Some("test-node".to_string()),
+                namespace: Some("default".to_string()),
+                finalizers: Some(finalizers.iter().map(|f| f.to_string()).collect()),
+                annotations: annotation.map(|v| {
+                    [(DISABLE_FINALIZER_ANNOTATION.to_string(), v.to_string())]
+                        .into_iter()
+                        .collect()
+                }),
+                ..Default::default()
+            },
+            spec: create_test_spec(),
+            status: None,
+        }
+    }
+
+    #[test]
+    fn test_finalizer_added_when_enabled() {
+        let node = node_with(&["other.finalizer/test"], None);
+        assert!(finalizer_enabled(&node, false));
+        assert_eq!(
+            desired_finalizers(&node, true),
+            Some(vec![
+                "other.finalizer/test".to_string(),
+                STELLAR_NODE_FINALIZER.to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_finalizer_not_added_when_operator_disabled() {
+        let node = node_with(&[], None);
+        assert!(!finalizer_enabled(&node, true));
+        assert_eq!(desired_finalizers(&node, false), None);
+    }
+
+    #[test]
+    fn test_finalizer_not_added_when_annotation_set() {
+        let node = node_with(&[], Some("True"));
+        assert!(!finalizer_enabled(&node, false));
+
+        let node = node_with(&[], Some("false"));
+        assert!(finalizer_enabled(&node, false));
+    }
+
+    #[test]
+    fn test_existing_finalizer_removed_when_disabled() {
+        let node = node_with(&[STELLAR_NODE_FINALIZER, "other.finalizer/test"], None);
+        assert_eq!(
+            desired_finalizers(&node, false),
+            Some(vec!["other.finalizer/test".to_string()])
+        );
+        assert_eq!(desired_finalizers(&node, true), None);
+    }
+
     // -----------------------------------------------------------------------
     // PVC retention policy tests
     // -----------------------------------------------------------------------
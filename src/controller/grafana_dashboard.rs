@@ -0,0 +1,205 @@
+//! Grafana dashboard for the operator's node metrics
+//!
+//! When `grafanaDashboard.enabled` is set in the operator config, the operator
+//! publishes a dashboard ConfigMap labelled `grafana_dashboard: "1"` so the
+//! Grafana sidecar (kube-prometheus-stack and the Grafana Helm chart) picks it
+//! up without any manual import. Panels cover the per-node gauges exported by
+//! [`super::metrics`]: ledger sequence, ingestion lag, Horizon TPS, peer
+//! connections and history archive lag.
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::ConfigMap;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::api::{Patch, PatchParams};
+use kube::{Api, Client};
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::error::{Error, Result};
+
+/// Label the Grafana sidecar watches for dashboard ConfigMaps
+pub const DASHBOARD_LABEL: &str = "grafana_dashboard";
+
+/// Name of the dashboard ConfigMap
+pub const DASHBOARD_CONFIG_MAP: &str = "stellar-operator-dashboard";
+
+/// ConfigMap key holding the dashboard JSON
+pub const DASHBOARD_KEY: &str = "stellar-nodes.json";
+
+/// Stable dashboard uid, so re-imports replace rather than duplicate it
+pub const DASHBOARD_UID: &str = "stellar-k8s-nodes";
+
+/// Node selector shared by every panel query
+const NODE_SELECTOR: &str = r#"namespace=~"$namespace", name=~"$name""#;
+
+/// Time series panel for one gauge, laid out on a two-column grid
+fn panel(id: u32, title: &str, metric: &str, unit: &str) -> Value {
+    let index = id - 1;
+    json!({
+        "id": id,
+        "type": "timeseries",
+        "title": title,
+        "datasource": { "type": "prometheus", "uid": "${datasource}" },
+        "gridPos": { "h": 8, "w": 12, "x": (index % 2) * 12, "y": (index / 2) * 8 },
+        "fieldConfig": { "defaults": { "unit": unit }, "overrides": [] },
+        "targets": [{
+            "refId": "A",
+            "expr": format!("{metric}{{{NODE_SELECTOR}}}"),
+            "legendFormat": "{{namespace}}/{{name}}"
+        }]
+    })
+}
+
+/// Query variable listing the values of a node label
+fn label_variable(label: &str, filter: &str) -> Value {
+    json!({
+        "name": label,
+        "type": "query",
+        "datasource": { "type": "prometheus", "uid": "${datasource}" },
+        "query": format!("label_values(stellar_node_ledger_sequence{filter}, {label})"),
+        "refresh": 2,
+        "includeAll": true,
+        "multi": true,
+        "current": { "text": "All", "value": "$__all" }
+    })
+}
+
+/// Dashboard model in Grafana's JSON format
+pub fn build_dashboard() -> Value {
+    json!({
+        "uid": DASHBOARD_UID,
+        "title": "Stellar Nodes",
+        "tags": ["stellar", "stellar-k8s"],
+        "timezone": "browser",
+        "schemaVersion": 39,
+        "refresh": "30s",
+        "time": { "from": "now-6h", "to": "now" },
+        "templating": {
+            "list": [
+                {
+                    "name": "datasource",
+                    "type": "datasource",
+                    "query": "prometheus",
+                    "current": { "text": "Prometheus", "value": "prometheus" }
+                },
+                label_variable("namespace", ""),
+                label_variable("name", r#"{namespace=~"$namespace"}"#)
+            ]
+        },
+        "panels": [
+            panel(1, "Ledger Sequence", "stellar_node_ledger_sequence", "none"),
+            panel(2, "Ingestion Lag", "stellar_node_ingestion_lag", "none"),
+            panel(3, "Horizon TPS", "stellar_horizon_tps", "reqps"),
+            panel(4, "Peer Connections", "stellar_node_active_connections", "none"),
+            panel(5, "History Archive Lag", "stellar_archive_ledger_lag", "none")
+        ]
+    })
+}
+
+/// Dashboard ConfigMap for the Grafana sidecar
+pub fn build_dashboard_config_map(namespace: &str) -> ConfigMap {
+    let labels = BTreeMap::from([
+        (DASHBOARD_LABEL.to_string(), "1".to_string()),
+        (
+            "app.kubernetes.io/managed-by".to_string(),
+            "stellar-operator".to_string(),
+        ),
+    ]);
+
+    ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(DASHBOARD_CONFIG_MAP.to_string()),
+            namespace: Some(namespace.to_string()),
+            labels: Some(labels),
+            ..Default::default()
+        },
+        data: Some(BTreeMap::from([(
+            DASHBOARD_KEY.to_string(),
+            serde_json::to_string_pretty(&build_dashboard()).unwrap_or_default(),
+        )])),
+        ..Default::default()
+    }
+}
+
+/// Create or update the dashboard ConfigMap in `namespace`.
+pub async fn ensure_dashboard(client: &Client, namespace: &str) -> Result<()> {
+    let api: Api<ConfigMap> = Api::namespaced(client.clone(), namespace);
+    let config_map = build_dashboard_config_map(namespace);
+
+    api.patch(
+        DASHBOARD_CONFIG_MAP,
+        &PatchParams::apply("stellar-operator").force(),
+        &Patch::Apply(&config_map),
+    )
+    .await
+    .map_err(Error::KubeError)?;
+
+    info!(
+        "Ensured Grafana dashboard ConfigMap {}/{}",
+        namespace, DASHBOARD_CONFIG_MAP
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_map_has_sidecar_label() {
+        let cm = build_dashboard_config_map("monitoring");
+        assert_eq!(cm.metadata.namespace.as_deref(), Some("monitoring"));
+        assert_eq!(
+            cm.metadata.labels.unwrap().get(DASHBOARD_LABEL),
+            Some(&"1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_config_map_holds_valid_dashboard_json() {
+        let cm = build_dashboard_config_map("monitoring");
+        let raw = cm.data.unwrap().remove(DASHBOARD_KEY).unwrap();
+        let dashboard: Value = serde_json::from_str(&raw).expect("dashboard must be valid JSON");
+
+        assert_eq!(dashboard["uid"], DASHBOARD_UID);
+        let panels = dashboard["panels"].as_array().unwrap();
+        let exprs: Vec<&str> = panels
+            .iter()
+            .map(|p| p["targets"][0]["expr"].as_str().unwrap())
+            .collect();
+        for metric in [
+            "stellar_node_ledger_sequence",
+            "stellar_node_ingestion_lag",
+            "stellar_horizon_tps",
+            "stellar_node_active_connections",
+            "stellar_archive_ledger_lag",
+        ] {
+            assert!(
+                exprs.iter().any(|e| e.starts_with(&format!("{metric}{{"))),
+                "no panel for {metric}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_panels_have_unique_ids_and_do_not_overlap() {
+        let dashboard = build_dashboard();
+        let panels = dashboard["panels"].as_array().unwrap();
+        let mut positions = std::collections::HashSet::new();
+        let mut ids = std::collections::HashSet::new();
+        for p in panels {
+            assert!(ids.insert(p["id"].as_u64().unwrap()));
+            assert!(positions.insert((p["gridPos"]["x"].clone(), p["gridPos"]["y"].clone())));
+        }
+    }
+
+    #[test]
+    fn test_panel_queries_filter_by_node() {
+        let dashboard = build_dashboard();
+        assert_eq!(
+            dashboard["panels"][0]["targets"][0]["expr"],
+            r#"stellar_node_ledger_sequence{namespace=~"$namespace", name=~"$name"}"#
+        );
+    }
+}
@@ -166,6 +166,11 @@ pub async fn check_node_health(
     let pod = match ready_pod {
         Some(p) => p,
         None => {
+            if node.spec.node_type == NodeType::Horizon {
+                if let Some(message) = pods.items.iter().find_map(horizon_migration_failure) {
+                    return Ok(HealthCheckResult::unhealthy(message));
+                }
+            }
             return Ok(HealthCheckResult::pending(
                 "Waiting for pod to be ready".to_string(),
             ));
@@ -216,6 +221,51 @@ fn is_pod_ready(pod: &Pod) -> bool {
     false
 }
 
+/// Detect a failed Horizon database migration or schema verification init
+/// container on a pod.
+///
+/// Returns a human-readable failure message when either container has exited
+/// with a non-zero code, in its current or its last recorded state.
+pub fn horizon_migration_failure(pod: &Pod) -> Option<String> {
+    use crate::controller::resources::{
+        HORIZON_MIGRATION_CONTAINER_NAME, HORIZON_MIGRATION_VERIFY_CONTAINER_NAME,
+    };
+
+    let statuses = pod.status.as_ref()?.init_container_statuses.as_ref()?;
+    statuses.iter().find_map(|status| {
+        let step = match status.name.as_str() {
+            HORIZON_MIGRATION_CONTAINER_NAME => "migration",
+            HORIZON_MIGRATION_VERIFY_CONTAINER_NAME => "schema verification",
+            _ => return None,
+        };
+
+        let terminated = status
+            .state
+            .as_ref()
+            .and_then(|s| s.terminated.as_ref())
+            .filter(|t| t.exit_code != 0)
+            .or_else(|| {
+                status
+                    .last_state
+                    .as_ref()
+                    .and_then(|s| s.terminated.as_ref())
+                    .filter(|t| t.exit_code != 0)
+            })?;
+
+        Some(format!(
+            "Horizon database {step} failed on pod {} (exit code {}, restarts {}){}",
+            pod.name_any(),
+            terminated.exit_code,
+            status.restart_count,
+            terminated
+                .message
+                .as_ref()
+                .map(|m| format!(": {m}"))
+                .unwrap_or_default()
+        ))
+    })
+}
+
 /// Check Horizon node health
 async fn check_horizon_health(
     pod_ip: &str,
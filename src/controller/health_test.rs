@@ -35,4 +35,51 @@ mod tests {
         assert!(!result.synced);
         assert_eq!(result.ledger_sequence, None);
     }
+
+    fn pod_with_migration_state(
+        state: Option<k8s_openapi::api::core::v1::ContainerState>,
+        last_state: Option<k8s_openapi::api::core::v1::ContainerState>,
+    ) -> k8s_openapi::api::core::v1::Pod {
+        use k8s_openapi::api::core::v1::{ContainerStatus, Pod, PodStatus};
+
+        let mut pod = Pod::default();
+        pod.metadata.name = Some("horizon-0".to_string());
+        pod.status = Some(PodStatus {
+            init_container_statuses: Some(vec![ContainerStatus {
+                name: "horizon-db-migration".to_string(),
+                restart_count: 3,
+                state,
+                last_state,
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        pod
+    }
+
+    fn terminated(exit_code: i32) -> k8s_openapi::api::core::v1::ContainerState {
+        use k8s_openapi::api::core::v1::{ContainerState, ContainerStateTerminated};
+
+        ContainerState {
+            terminated: Some(ContainerStateTerminated {
+                exit_code,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_horizon_migration_failure_detected_from_last_state() {
+        let pod = pod_with_migration_state(None, Some(terminated(1)));
+        let message = horizon_migration_failure(&pod).expect("failure should be detected");
+        assert!(message.contains("horizon-0"));
+        assert!(message.contains("exit code 1"));
+    }
+
+    #[test]
+    fn test_horizon_migration_success_not_reported() {
+        let pod = pod_with_migration_state(Some(terminated(0)), None);
+        assert!(horizon_migration_failure(&pod).is_none());
+    }
 }
@@ -0,0 +1,426 @@
+//! Short-lived cache of the latest network ledger
+//!
+//! Ingestion lag compares a node's ledger with the latest ledger reported by
+//! the network's Horizon, or by a stellar-core `/info` endpoint for networks
+//! without one (see [`parse_latest_ledger`]). Without caching every reconcile of every node makes
+//! its own Horizon request, which public Horizons rate-limit quickly. The
+//! cache keeps one value per Horizon URL for [`LATEST_LEDGER_TTL`], and
+//! concurrent misses for the same URL share a single request.
+//!
+//! Each URL also has a circuit breaker. After
+//! [`LATEST_LEDGER_FAILURE_THRESHOLD`] failed requests in a row the Horizon
+//! is left alone for [`LATEST_LEDGER_OPEN_WINDOW`]; meanwhile lookups return
+//! the last value fetched, with a staleness warning, instead of waiting for
+//! another timeout.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::warn;
+
+use super::traffic::{CircuitBreaker, CircuitBreakerConfig};
+use crate::error::{Error, Result};
+
+/// How long a fetched latest ledger is reused (about two ledger closes)
+pub const LATEST_LEDGER_TTL: Duration = Duration::from_secs(10);
+
+/// Consecutive failed requests that open a Horizon's circuit
+pub const LATEST_LEDGER_FAILURE_THRESHOLD: u32 = 3;
+
+/// How long an open circuit skips requests before trying Horizon again
+pub const LATEST_LEDGER_OPEN_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+struct SlotState {
+    /// Last successfully fetched ledger and when, in ms since the epoch
+    last: Option<(u64, u64)>,
+    breaker: CircuitBreaker,
+}
+
+type Slot = Arc<tokio::sync::Mutex<SlotState>>;
+
+/// Latest ledger per Horizon URL
+pub struct LatestLedgerCache {
+    ttl: Duration,
+    breaker: CircuitBreakerConfig,
+    slots: Mutex<HashMap<String, Slot>>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+impl LatestLedgerCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_breaker(
+            ttl,
+            CircuitBreakerConfig {
+                failure_threshold: LATEST_LEDGER_FAILURE_THRESHOLD,
+                success_threshold: 1,
+                open_window_ms: LATEST_LEDGER_OPEN_WINDOW.as_millis() as u64,
+            },
+        )
+    }
+
+    pub fn with_breaker(ttl: Duration, breaker: CircuitBreakerConfig) -> Self {
+        Self {
+            ttl,
+            breaker,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn slot(&self, horizon_url: &str) -> Slot {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        slots
+            .entry(horizon_url.to_string())
+            .or_insert_with(|| {
+                Arc::new(tokio::sync::Mutex::new(SlotState {
+                    last: None,
+                    breaker: CircuitBreaker::new(self.breaker.clone()),
+                }))
+            })
+            .clone()
+    }
+
+    /// Latest ledger of `horizon_url`, calling `fetch` only when no value
+    /// younger than the TTL is cached. Failed fetches are not cached.
+    pub async fn get_or_fetch<F, Fut>(&self, horizon_url: &str, fetch: F) -> Result<u64>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<u64>>,
+    {
+        self.get_or_fetch_at(horizon_url, now_ms(), fetch).await
+    }
+
+    async fn get_or_fetch_at<F, Fut>(&self, horizon_url: &str, now_ms: u64, fetch: F) -> Result<u64>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<u64>>,
+    {
+        let slot = self.slot(horizon_url);
+        let mut state = slot.lock().await;
+        if let Some((fetched_at, ledger)) = state.last {
+            if u128::from(now_ms.saturating_sub(fetched_at)) < self.ttl.as_millis() {
+                return Ok(ledger);
+            }
+        }
+
+        if !state.breaker.allow(now_ms) {
+            return match state.last {
+                Some((fetched_at, ledger)) => {
+                    warn!(
+                        "Horizon {} circuit open; using latest ledger {} from {}s ago",
+                        horizon_url,
+                        ledger,
+                        now_ms.saturating_sub(fetched_at) / 1000
+                    );
+                    Ok(ledger)
+                }
+                None => Err(Error::ConfigError(format!(
+                    "Horizon {horizon_url} circuit open and no latest ledger known"
+                ))),
+            };
+        }
+
+        let result = fetch().await;
+        state.breaker.on_result(result.is_ok(), now_ms);
+        let ledger = result?;
+        state.last = Some((now_ms, ledger));
+        Ok(ledger)
+    }
+}
+
+/// Latest ledger in a Horizon root (`history_latest_ledger`) or stellar-core
+/// `/info` (`info.ledger.num`) response
+pub fn parse_latest_ledger(json: &serde_json::Value) -> Option<u64> {
+    json["history_latest_ledger"]
+        .as_u64()
+        .or_else(|| json["info"]["ledger"]["num"].as_u64())
+}
+
+static LATEST_LEDGER_CACHE: OnceLock<LatestLedgerCache> = OnceLock::new();
+
+/// Cache shared by all reconciles
+pub fn latest_ledger_cache() -> &'static LatestLedgerCache {
+    LATEST_LEDGER_CACHE.get_or_init(|| LatestLedgerCache::new(LATEST_LEDGER_TTL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    async fn fetch_counting(calls: &AtomicU32, ledger: u64) -> Result<u64> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok(ledger)
+    }
+
+    #[tokio::test]
+    async fn test_hit_within_ttl_skips_fetch() {
+        let cache = LatestLedgerCache::new(Duration::from_secs(60));
+        let calls = AtomicU32::new(0);
+
+        let first = cache
+            .get_or_fetch("https://horizon", || fetch_counting(&calls, 100))
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_fetch("https://horizon", || fetch_counting(&calls, 200))
+            .await
+            .unwrap();
+
+        assert_eq!((first, second), (100, 100));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entry_is_refetched() {
+        let cache = LatestLedgerCache::new(Duration::ZERO);
+        let calls = AtomicU32::new(0);
+
+        cache
+            .get_or_fetch("https://horizon", || fetch_counting(&calls, 100))
+            .await
+            .unwrap();
+        let ledger = cache
+            .get_or_fetch("https://horizon", || fetch_counting(&calls, 200))
+            .await
+            .unwrap();
+
+        assert_eq!(ledger, 200);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_networks_are_cached_separately() {
+        let cache = LatestLedgerCache::new(Duration::from_secs(60));
+        let calls = AtomicU32::new(0);
+
+        let mainnet = cache
+            .get_or_fetch("https://horizon.stellar.org", || {
+                fetch_counting(&calls, 100)
+            })
+            .await
+            .unwrap();
+        let testnet = cache
+            .get_or_fetch("https://horizon-testnet.stellar.org", || {
+                fetch_counting(&calls, 5)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!((mainnet, testnet), (100, 5));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_not_cached() {
+        let cache = LatestLedgerCache::new(Duration::from_secs(60));
+        let failed = cache
+            .get_or_fetch("https://horizon", || async {
+                Err(Error::ConfigError("rate limited".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+
+        let ledger = cache
+            .get_or_fetch("https://horizon", || async { Ok(42) })
+            .await
+            .unwrap();
+        assert_eq!(ledger, 42);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_misses_share_one_fetch() {
+        let cache = Arc::new(LatestLedgerCache::new(Duration::from_secs(60)));
+        let calls = Arc::new(AtomicU32::new(0));
+
+        let lookups = (0..8).map(|_| {
+            let cache = cache.clone();
+            let calls = calls.clone();
+            tokio::spawn(async move {
+                cache
+                    .get_or_fetch("https://horizon", || async {
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        fetch_counting(&calls, 7).await
+                    })
+                    .await
+                    .unwrap()
+            })
+        });
+        for lookup in futures::future::join_all(lookups).await {
+            assert_eq!(lookup.unwrap(), 7);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    async fn fetch_failing(calls: &AtomicU32) -> Result<u64> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Err(Error::ConfigError("horizon timed out".to_string()))
+    }
+
+    /// No TTL, so every lookup consults the breaker; opens after 2 failures
+    fn breaker_cache() -> LatestLedgerCache {
+        LatestLedgerCache::with_breaker(
+            Duration::ZERO,
+            CircuitBreakerConfig {
+                failure_threshold: 2,
+                success_threshold: 1,
+                open_window_ms: 60_000,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_returns_last_known_without_calling_horizon() {
+        let cache = breaker_cache();
+        let calls = AtomicU32::new(0);
+        let url = "https://horizon";
+
+        let ledger = cache
+            .get_or_fetch_at(url, 0, || fetch_counting(&calls, 100))
+            .await
+            .unwrap();
+        assert_eq!(ledger, 100);
+        for t in [1_000, 2_000] {
+            assert!(cache
+                .get_or_fetch_at(url, t, || fetch_failing(&calls))
+                .await
+                .is_err());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Circuit is open: stale value, no request
+        let stale = cache
+            .get_or_fetch_at(url, 3_000, || fetch_failing(&calls))
+            .await
+            .unwrap();
+        assert_eq!(stale, 100);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_without_known_ledger_fails_fast() {
+        let cache = breaker_cache();
+        let calls = AtomicU32::new(0);
+        for t in [0, 1_000] {
+            let _ = cache
+                .get_or_fetch_at("https://horizon", t, || fetch_failing(&calls))
+                .await;
+        }
+
+        let err = cache
+            .get_or_fetch_at("https://horizon", 2_000, || fetch_failing(&calls))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("circuit open"), "{err}");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_recovers_after_open_window() {
+        let cache = breaker_cache();
+        let calls = AtomicU32::new(0);
+        let url = "https://horizon";
+        cache
+            .get_or_fetch_at(url, 0, || fetch_counting(&calls, 100))
+            .await
+            .unwrap();
+        for t in [1_000, 2_000] {
+            let _ = cache
+                .get_or_fetch_at(url, t, || fetch_failing(&calls))
+                .await;
+        }
+
+        // Half-open after the window: one trial request, which succeeds
+        let fresh = cache
+            .get_or_fetch_at(url, 62_000, || fetch_counting(&calls, 150))
+            .await
+            .unwrap();
+        assert_eq!(fresh, 150);
+
+        // Closed again: the next lookup goes to Horizon
+        let next = cache
+            .get_or_fetch_at(url, 63_000, || fetch_counting(&calls, 151))
+            .await
+            .unwrap();
+        assert_eq!(next, 151);
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_failed_trial_reopens_circuit() {
+        let cache = breaker_cache();
+        let calls = AtomicU32::new(0);
+        let url = "https://horizon";
+        cache
+            .get_or_fetch_at(url, 0, || fetch_counting(&calls, 100))
+            .await
+            .unwrap();
+        for t in [1_000, 2_000, 62_000] {
+            let _ = cache
+                .get_or_fetch_at(url, t, || fetch_failing(&calls))
+                .await;
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+
+        let stale = cache
+            .get_or_fetch_at(url, 63_000, || fetch_failing(&calls))
+            .await
+            .unwrap();
+        assert_eq!(stale, 100);
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_parse_horizon_root() {
+        let json = serde_json::json!({
+            "horizon_version": "2.30.0",
+            "core_latest_ledger": 51234570,
+            "history_latest_ledger": 51234567,
+            "network_passphrase": "Public Global Stellar Network ; September 2015"
+        });
+        assert_eq!(parse_latest_ledger(&json), Some(51234567));
+    }
+
+    #[test]
+    fn test_parse_core_info() {
+        let json: serde_json::Value = serde_json::from_str(
+            r#"{
+                "info": {
+                    "build": "stellar-core 21.0.0",
+                    "ledger": {
+                        "age": 3,
+                        "baseFee": 100,
+                        "closeTime": 1760000000,
+                        "hash": "9f1c0d8e7b6a5f4e3d2c1b0a9f8e7d6c5b4a3f2e1d0c9b8a7f6e5d4c3b2a1f0e",
+                        "num": 1204567,
+                        "version": 21
+                    },
+                    "network": "Private Network ; January 2026",
+                    "state": "Synced!"
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(parse_latest_ledger(&json), Some(1204567));
+    }
+
+    #[test]
+    fn test_parse_unknown_response() {
+        assert_eq!(
+            parse_latest_ledger(&serde_json::json!({"status": "ok"})),
+            None
+        );
+        assert_eq!(
+            parse_latest_ledger(&serde_json::json!({"info": {"state": "Booting"}})),
+            None
+        );
+    }
+}
@@ -8,13 +8,22 @@
 //! - `stellar_operator_reconcile_errors_total` (counter): operator reconcile errors labeled by controller and kind.
 //! - `stellar_node_ledger_sequence` (gauge): ledger sequence labeled by namespace/name/node_type/network/hardware_generation.
 //! - `stellar_node_ingestion_lag` (gauge): ingestion lag labeled by namespace/name/node_type/network/hardware_generation.
+//! - `stellar_node_ingestion_lag_seconds` (gauge): ingestion lag in seconds behind the network, from the ledger lag times the measured ledger close time.
 //! - `stellar_node_sync_status` (gauge): node sync status (0=Pending, 1=Creating, 2=Running, 3=Syncing, 4=Ready, 5=Failed, 6=Degraded, 7=Suspended).
 //! - `stellar_node_up` (gauge): binary indicator if node is up based on pod readiness (1=up, 0=down).
+//! - `stellar_node_info` (gauge): always 1, labeled by namespace/name/node_type/network/version; join on namespace/name to split other node metrics by version during rollouts.
 //! - `stellar_horizon_tps` (gauge): Horizon TPS labeled by namespace/name/node_type/network/hardware_generation.
 //! - `stellar_horizon_queue_length` (gauge): pending Horizon request queue length labeled by namespace/name/node_type/network/hardware_generation.
 //! - `stellar_node_active_connections` (gauge): active peer connections labeled by namespace/name/node_type/network/hardware_generation.
+//! - `stellar_backup_duration_seconds` (histogram): decentralized backup run duration labeled by archive and status.
+//! - `stellar_backup_size_bytes` (gauge): archive data covered by the last successful decentralized backup run, labeled by archive.
+//! - `stellar_operator_active_reconciles`, `stellar_operator_open_watch_streams`,
+//!   `stellar_operator_tokio_alive_tasks`, `stellar_operator_resident_memory_bytes` (gauges): operator self-usage.
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicI64, AtomicU64};
+use std::sync::Mutex;
+use std::time::Instant;
 
 use once_cell::sync::Lazy;
 use prometheus_client::encoding::EncodeLabelSet;
@@ -60,6 +69,19 @@ pub static LEDGER_SEQUENCE: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
 pub static INGESTION_LAG: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
 
+/// Gauge tracking ledger ingestion lag per node, in seconds behind the network
+pub static INGESTION_LAG_SECONDS: Lazy<Family<NodeLabels, Gauge<f64, AtomicU64>>> =
+    Lazy::new(Family::default);
+
+/// Ledger close time assumed until one has been measured; the network's
+/// target close time
+pub const DEFAULT_LEDGER_CLOSE_SECONDS: f64 = 5.0;
+
+/// Last network ledger seen per node, with when it was seen and the close
+/// time measured so far
+static NETWORK_LEDGER_OBSERVATIONS: Lazy<Mutex<HashMap<(String, String), (u64, Instant, f64)>>> =
+    Lazy::new(Default::default);
+
 /// Gauge tracking requests per second for Horizon nodes
 pub static HORIZON_TPS: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
@@ -72,6 +94,10 @@ pub static HORIZON_QUEUE_LENGTH: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>>
 pub static ACTIVE_CONNECTIONS: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
 
+/// Gauge tracking whether a validator has lost sync or quorum (1 = desynced, 0 = in consensus)
+pub static VALIDATOR_DESYNCED: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
+    Lazy::new(Family::default);
+
 /// Gauge tracking archive integrity status (1 = healthy, 0 = corrupted)
 pub static ARCHIVE_INTEGRITY_STATUS: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
@@ -81,6 +107,10 @@ pub static ARCHIVE_INTEGRITY_STATUS: Lazy<Family<NodeLabels, Gauge<i64, AtomicI6
 pub static ARCHIVE_LEDGER_LAG: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
 
+/// Gauge tracking the archive lag threshold (in ledgers) configured for each node
+pub static ARCHIVE_LAG_THRESHOLD_LEDGERS: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
+    Lazy::new(Family::default);
+
 /// Gauge tracking whether the ZK manifest signature is valid (1 = valid, 0 = invalid or absent).
 pub static ZK_ARCHIVE_SIGNATURE_VALID: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
@@ -98,6 +128,25 @@ pub static NODE_SYNC_STATUS: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
 /// Gauge tracking node up status (0=down, 1=up) based on pod readiness
 pub static NODE_UP: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> = Lazy::new(Family::default);
 
+/// Labels for the node info gauge
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct NodeInfoLabels {
+    pub namespace: String,
+    pub name: String,
+    pub node_type: String,
+    pub network: String,
+    /// Stellar software version the node runs (`spec.version`)
+    pub version: String,
+}
+
+/// Gauge that is always `1` and carries the version of each node
+pub static NODE_INFO: Lazy<Family<NodeInfoLabels, Gauge<i64, AtomicI64>>> =
+    Lazy::new(Family::default);
+
+/// Labels last reported per node, so an upgrade replaces the old series
+static NODE_INFO_CURRENT: Lazy<Mutex<HashMap<(String, String), NodeInfoLabels>>> =
+    Lazy::new(Default::default);
+
 /// Gauge tracking number of critical nodes in the quorum
 pub static QUORUM_CRITICAL_NODES: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
@@ -122,6 +171,10 @@ pub static PVC_SIZE_BYTES: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
 pub static PVC_EXPANSION_COUNT: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
 
+/// Gauge tracking the Unix time of the last successful backup
+pub static LAST_BACKUP_TIMESTAMP: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
+    Lazy::new(Family::default);
+
 /// Gauge tracking snapshot integrity check status (1 = pass, 0 = fail)
 pub static SNAPSHOT_INTEGRITY_STATUS: Lazy<Family<NodeLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
@@ -309,6 +362,32 @@ pub static DR_DRILL_EXECUTIONS_TOTAL: Lazy<Family<DRDrillLabels, Counter<u64, At
 pub static DR_DRILL_TIME_TO_RECOVERY_MS: Lazy<Family<DRDrillLabels, Gauge<i64, AtomicI64>>> =
     Lazy::new(Family::default);
 
+/// Labels for decentralized backup run metrics
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct BackupLabels {
+    pub archive: String,
+    pub status: String, // "success", "failed"
+}
+
+/// Labels for decentralized backup size metrics
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct BackupArchiveLabels {
+    pub archive: String,
+}
+
+/// Histogram tracking decentralized backup run duration in seconds
+pub static BACKUP_DURATION_SECONDS: Lazy<Family<BackupLabels, Histogram>> = Lazy::new(|| {
+    fn backup_histogram() -> Histogram {
+        // 1s .. ~9h across 16 buckets
+        Histogram::new(exponential_buckets(1.0, 2.0, 16))
+    }
+    Family::new_with_constructor(backup_histogram)
+});
+
+/// Gauge tracking the archive data covered by the last successful backup run
+pub static BACKUP_SIZE_BYTES: Lazy<Family<BackupArchiveLabels, Gauge<i64, AtomicI64>>> =
+    Lazy::new(Family::default);
+
 /// Labels for traffic shaping metrics.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
 pub struct TrafficRequestLabels {
@@ -378,6 +457,11 @@ pub static REGISTRY: Lazy<Registry> = Lazy::new(|| {
         "Lag between latest network ledger and node ledger",
         INGESTION_LAG.clone(),
     );
+    registry.register(
+        "stellar_node_ingestion_lag_seconds",
+        "Seconds the node is behind the network, from ledger lag and measured ledger close time",
+        INGESTION_LAG_SECONDS.clone(),
+    );
     registry.register(
         "stellar_horizon_tps",
         "Transactions per second for Horizon API nodes",
@@ -398,6 +482,11 @@ pub static REGISTRY: Lazy<Registry> = Lazy::new(|| {
         "Ledgers the history archive is behind the validator node (0 = in-sync)",
         ARCHIVE_LEDGER_LAG.clone(),
     );
+    registry.register(
+        "stellar_archive_lag_threshold",
+        "Ledgers a history archive may lag behind the node before it is considered degraded",
+        ARCHIVE_LAG_THRESHOLD_LEDGERS.clone(),
+    );
     registry.register(
         "stellar_node_sync_status",
         "Current sync status of the Stellar node (0=Pending, 1=Creating, 2=Running, 3=Syncing, 4=Ready, 5=Failed, 6=Degraded, 7=Suspended)",
@@ -408,6 +497,17 @@ pub static REGISTRY: Lazy<Registry> = Lazy::new(|| {
         "Binary indicator if node is up based on pod readiness (1=up, 0=down)",
         NODE_UP.clone(),
     );
+    registry.register(
+        "stellar_node_info",
+        "Node information (node_type, network, version); always 1",
+        NODE_INFO.clone(),
+    );
+
+    registry.register(
+        "stellar_validator_desynced",
+        "Whether the validator has lost sync or quorum (1 = desynced, 0 = in consensus)",
+        VALIDATOR_DESYNCED.clone(),
+    );
 
     registry.register(
         "stellar_archive_integrity_status",
@@ -530,6 +630,18 @@ pub static REGISTRY: Lazy<Registry> = Lazy::new(|| {
         DR_DRILL_TIME_TO_RECOVERY_MS.clone(),
     );
 
+    // Register decentralized backup metrics
+    registry.register(
+        "stellar_backup_duration_seconds",
+        "Duration of decentralized backup runs in seconds",
+        BACKUP_DURATION_SECONDS.clone(),
+    );
+    registry.register(
+        "stellar_backup_size_bytes",
+        "Archive data covered by the last successful decentralized backup run in bytes",
+        BACKUP_SIZE_BYTES.clone(),
+    );
+
     // Register PVC disk scaling metrics
     registry.register(
         "stellar_pvc_disk_usage_percent",
@@ -551,6 +663,11 @@ pub static REGISTRY: Lazy<Registry> = Lazy::new(|| {
         "Number of expansions performed on this PVC",
         PVC_EXPANSION_COUNT.clone(),
     );
+    registry.register(
+        "stellar_node_last_backup_timestamp",
+        "Unix time of the last successful backup of the node",
+        LAST_BACKUP_TIMESTAMP.clone(),
+    );
 
     // Register snapshot integrity metrics
     registry.register(
@@ -607,6 +724,26 @@ pub static REGISTRY: Lazy<Registry> = Lazy::new(|| {
         "1 if the operator is ready (K8s watch healthy and first reconcile complete), 0 otherwise",
         OPERATOR_READY_STATUS.clone(),
     );
+    registry.register(
+        "stellar_operator_active_reconciles",
+        "Number of reconcile loops currently in progress",
+        OPERATOR_ACTIVE_RECONCILES.clone(),
+    );
+    registry.register(
+        "stellar_operator_open_watch_streams",
+        "Number of Kubernetes watch streams held open by the operator",
+        OPERATOR_OPEN_WATCH_STREAMS.clone(),
+    );
+    registry.register(
+        "stellar_operator_tokio_alive_tasks",
+        "Number of tokio tasks alive on the operator runtime",
+        OPERATOR_TOKIO_ALIVE_TASKS.clone(),
+    );
+    registry.register(
+        "stellar_operator_resident_memory_bytes",
+        "Resident set size of the operator process in bytes",
+        OPERATOR_RESIDENT_MEMORY_BYTES.clone(),
+    );
 
     // ── Observability Pipeline metrics ────────────────────────────────────
     registry.register(
@@ -797,6 +934,69 @@ pub fn set_ingestion_lag(
     INGESTION_LAG.get_or_create(&labels).set(lag);
 }
 
+/// Seconds behind the network of a node `lag_ledgers` behind, when ledgers
+/// close every `close_time_seconds`
+pub fn lag_seconds(lag_ledgers: i64, close_time_seconds: f64) -> f64 {
+    lag_ledgers.max(0) as f64 * close_time_seconds
+}
+
+/// Average close time between two observations of the network ledger, or
+/// `None` when no ledger closed in between
+pub fn measure_close_time(previous: (u64, Instant), current: (u64, Instant)) -> Option<f64> {
+    let closed = current.0.checked_sub(previous.0).filter(|&n| n > 0)?;
+    let elapsed = current.1.checked_duration_since(previous.1)?;
+    Some(elapsed.as_secs_f64() / closed as f64)
+}
+
+/// Record the latest network ledger seen for a node and return the ledger
+/// close time measured so far, [`DEFAULT_LEDGER_CLOSE_SECONDS`] until two
+/// observations a ledger apart exist
+pub fn observe_network_ledger(namespace: &str, name: &str, ledger: u64, at: Instant) -> f64 {
+    let mut observations = NETWORK_LEDGER_OBSERVATIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let key = (namespace.to_string(), name.to_string());
+    let close_time = match observations.get(&key) {
+        Some(&(previous, seen, close_time)) => {
+            match measure_close_time((previous, seen), (ledger, at)) {
+                Some(measured) => measured,
+                // Keep the earlier observation until the network moves on
+                None => return close_time,
+            }
+        }
+        None => DEFAULT_LEDGER_CLOSE_SECONDS,
+    };
+    observations.insert(key, (ledger, at, close_time));
+    close_time
+}
+
+/// Drop the network ledger observations of a deleted node
+pub fn forget_network_ledger(namespace: &str, name: &str) {
+    NETWORK_LEDGER_OBSERVATIONS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&(namespace.to_string(), name.to_string()));
+}
+
+/// Update the time-based ingestion lag metric for a node
+pub fn set_ingestion_lag_seconds(
+    namespace: &str,
+    name: &str,
+    node_type: &str,
+    network: &str,
+    hardware_generation: &str,
+    seconds: f64,
+) {
+    let labels = NodeLabels {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        node_type: node_type.to_string(),
+        network: network.to_string(),
+        hardware_generation: hardware_generation.to_string(),
+    };
+    INGESTION_LAG_SECONDS.get_or_create(&labels).set(seconds);
+}
+
 /// Update the ingestion lag metric for a node with Differential Privacy
 pub fn set_ingestion_lag_with_dp(
     namespace: &str,
@@ -975,11 +1175,36 @@ pub fn set_node_up(
     NODE_UP.get_or_create(&labels).set(if up { 1 } else { 0 });
 }
 
+/// Report the version a node runs.
+///
+/// Only the latest version of each node is exported: when `version` changes
+/// the series with the previous one is removed.
+pub fn set_node_info(namespace: &str, name: &str, node_type: &str, network: &str, version: &str) {
+    let labels = NodeInfoLabels {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        node_type: node_type.to_string(),
+        network: network.to_string(),
+        version: version.to_string(),
+    };
+    let mut current = NODE_INFO_CURRENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(previous) =
+        current.insert((namespace.to_string(), name.to_string()), labels.clone())
+    {
+        if previous != labels {
+            NODE_INFO.remove(&previous);
+        }
+    }
+    NODE_INFO.get_or_create(&labels).set(1);
+}
+
 /// Set the archive ledger lag metric for a node.
 ///
 /// `lag` is the number of ledgers the history archive is behind the validator node.
-/// A value above [`crate::controller::archive_health::ARCHIVE_LAG_THRESHOLD`] indicates
-/// the archive is significantly stale and a Prometheus alert should fire.
+/// A value above the node's archive lag threshold (see [`set_archive_lag_threshold`])
+/// indicates the archive is significantly stale and a Prometheus alert should fire.
 pub fn set_archive_ledger_lag(
     namespace: &str,
     name: &str,
@@ -998,6 +1223,51 @@ pub fn set_archive_ledger_lag(
     ARCHIVE_LEDGER_LAG.get_or_create(&labels).set(lag);
 }
 
+/// Set the archive lag threshold metric for a node.
+///
+/// Alert rules compare `stellar_archive_ledger_lag` against this gauge so per-node
+/// overrides of the threshold are honoured.
+pub fn set_archive_lag_threshold(
+    namespace: &str,
+    name: &str,
+    node_type: &str,
+    network: &str,
+    hardware_generation: &str,
+    threshold: i64,
+) {
+    let labels = NodeLabels {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        node_type: node_type.to_string(),
+        network: network.to_string(),
+        hardware_generation: hardware_generation.to_string(),
+    };
+    ARCHIVE_LAG_THRESHOLD_LEDGERS
+        .get_or_create(&labels)
+        .set(threshold);
+}
+
+/// Set whether a validator has lost sync or quorum.
+pub fn set_validator_desynced(
+    namespace: &str,
+    name: &str,
+    node_type: &str,
+    network: &str,
+    hardware_generation: &str,
+    desynced: bool,
+) {
+    let labels = NodeLabels {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        node_type: node_type.to_string(),
+        network: network.to_string(),
+        hardware_generation: hardware_generation.to_string(),
+    };
+    VALIDATOR_DESYNCED
+        .get_or_create(&labels)
+        .set(if desynced { 1 } else { 0 });
+}
+
 /// Set the archive integrity status metric for a node.
 ///
 /// `status` is 1 for healthy (integrity verified) and 0 for corrupted.
@@ -1358,6 +1628,25 @@ pub fn set_dr_drill_time_to_recovery(namespace: &str, name: &str, status: &str,
         .set(ttr_ms);
 }
 
+/// Record a decentralized backup run
+pub fn observe_backup_duration(archive: &str, status: &str, duration_seconds: f64) {
+    let labels = BackupLabels {
+        archive: archive.to_string(),
+        status: status.to_string(),
+    };
+    BACKUP_DURATION_SECONDS
+        .get_or_create(&labels)
+        .observe(duration_seconds);
+}
+
+/// Set the size of the last successful decentralized backup run
+pub fn set_backup_size_bytes(archive: &str, size_bytes: i64) {
+    let labels = BackupArchiveLabels {
+        archive: archive.to_string(),
+    };
+    BACKUP_SIZE_BYTES.get_or_create(&labels).set(size_bytes);
+}
+
 // ============================================================================
 // Operator build-info and leader metrics (Issue #301)
 // ============================================================================
@@ -1387,6 +1676,20 @@ pub static OPERATOR_UPTIME_SECONDS: Lazy<Counter<u64, AtomicU64>> = Lazy::new(Co
 /// Gauge tracking whether the operator is ready (1 = ready, 0 = not ready).
 pub static OPERATOR_READY_STATUS: Lazy<Gauge<i64, AtomicI64>> = Lazy::new(Gauge::default);
 
+// ── Operator self-usage metrics ───────────────────────────────────────────
+
+/// Gauge tracking reconcile loops currently in progress.
+pub static OPERATOR_ACTIVE_RECONCILES: Lazy<Gauge<i64, AtomicI64>> = Lazy::new(Gauge::default);
+
+/// Gauge tracking Kubernetes watch streams currently held open by the operator.
+pub static OPERATOR_OPEN_WATCH_STREAMS: Lazy<Gauge<i64, AtomicI64>> = Lazy::new(Gauge::default);
+
+/// Gauge tracking tokio tasks alive on the operator runtime.
+pub static OPERATOR_TOKIO_ALIVE_TASKS: Lazy<Gauge<i64, AtomicI64>> = Lazy::new(Gauge::default);
+
+/// Gauge tracking the operator's resident set size in bytes.
+pub static OPERATOR_RESIDENT_MEMORY_BYTES: Lazy<Gauge<i64, AtomicI64>> = Lazy::new(Gauge::default);
+
 // ── Observability Pipeline Metrics ────────────────────────────────────────
 
 /// Labels for observability pipeline event source metrics
@@ -1583,6 +1886,64 @@ pub fn set_ready_status(ready: bool) {
     OPERATOR_READY_STATUS.set(if ready { 1 } else { 0 });
 }
 
+/// Raises a gauge while alive and lowers it again when dropped.
+#[must_use = "the gauge is decremented as soon as the guard is dropped"]
+pub struct GaugeGuard {
+    gauge: &'static Gauge<i64, AtomicI64>,
+    amount: i64,
+}
+
+impl GaugeGuard {
+    fn new(gauge: &'static Gauge<i64, AtomicI64>, amount: i64) -> Self {
+        gauge.inc_by(amount);
+        Self { gauge, amount }
+    }
+}
+
+impl Drop for GaugeGuard {
+    fn drop(&mut self) {
+        self.gauge.dec_by(self.amount);
+    }
+}
+
+/// Count a reconcile as active until the returned guard is dropped.
+pub fn track_active_reconcile() -> GaugeGuard {
+    GaugeGuard::new(&OPERATOR_ACTIVE_RECONCILES, 1)
+}
+
+/// Count `streams` watch streams as open until the returned guard is dropped.
+pub fn track_watch_streams(streams: i64) -> GaugeGuard {
+    GaugeGuard::new(&OPERATOR_OPEN_WATCH_STREAMS, streams)
+}
+
+/// Extract the `VmRSS` value from `/proc/self/status` contents, in bytes.
+fn parse_vm_rss_bytes(status: &str) -> Option<u64> {
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let mut fields = line["VmRSS:".len()..].split_whitespace();
+    let value: u64 = fields.next()?.parse().ok()?;
+    match fields.next() {
+        Some("kB") | None => Some(value * 1024),
+        Some(_) => None,
+    }
+}
+
+/// Read the operator's resident set size. Returns `None` off Linux.
+pub fn read_resident_memory_bytes() -> Option<u64> {
+    std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| parse_vm_rss_bytes(&status))
+}
+
+/// Refresh the resident memory and tokio task gauges. Call from a periodic task.
+pub fn update_process_metrics() {
+    if let Some(rss) = read_resident_memory_bytes() {
+        OPERATOR_RESIDENT_MEMORY_BYTES.set(rss as i64);
+    }
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        OPERATOR_TOKIO_ALIVE_TASKS.set(handle.metrics().num_alive_tasks() as i64);
+    }
+}
+
 /// Set the ZK archive signature validity gauge for a node (1 = valid, 0 = invalid/missing).
 pub fn set_zk_archive_signature_valid(
     namespace: &str,
@@ -1702,6 +2063,25 @@ pub fn set_pvc_expansion_count(
     PVC_EXPANSION_COUNT.get_or_create(&labels).set(count);
 }
 
+/// Set the last successful backup timestamp metric
+pub fn set_last_backup_timestamp(
+    namespace: &str,
+    name: &str,
+    node_type: &str,
+    network: &str,
+    hardware_generation: &str,
+    timestamp: i64,
+) {
+    let labels = NodeLabels {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        node_type: node_type.to_string(),
+        network: network.to_string(),
+        hardware_generation: hardware_generation.to_string(),
+    };
+    LAST_BACKUP_TIMESTAMP.get_or_create(&labels).set(timestamp);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1749,6 +2129,67 @@ mod tests {
         // Function should not panic
     }
 
+    #[test]
+    fn test_lag_seconds_conversion() {
+        assert_eq!(lag_seconds(0, 5.0), 0.0);
+        assert_eq!(lag_seconds(12, 5.0), 60.0);
+        assert_eq!(lag_seconds(10, 5.5), 55.0);
+        // A node ahead of the reference is not behind
+        assert_eq!(lag_seconds(-3, 5.0), 0.0);
+    }
+
+    #[test]
+    fn test_measure_close_time() {
+        let start = Instant::now();
+        let later = start + std::time::Duration::from_secs(60);
+        assert_eq!(measure_close_time((100, start), (112, later)), Some(5.0));
+        assert_eq!(measure_close_time((100, start), (100, later)), None);
+        assert_eq!(measure_close_time((100, start), (90, later)), None);
+        assert_eq!(measure_close_time((100, later), (112, start)), None);
+    }
+
+    #[test]
+    fn test_observe_network_ledger_measures_close_time() {
+        let start = Instant::now();
+        let secs = std::time::Duration::from_secs;
+        let observe = |ledger, at| observe_network_ledger("default", "close-time-node", ledger, at);
+
+        assert_eq!(observe(1000, start), DEFAULT_LEDGER_CLOSE_SECONDS);
+        assert_eq!(observe(1010, start + secs(60)), 6.0);
+        // No new ledger: keep the last measurement and the older observation
+        assert_eq!(observe(1010, start + secs(90)), 6.0);
+        assert_eq!(observe(1020, start + secs(110)), 5.0);
+    }
+
+    #[test]
+    fn test_forgotten_node_measures_close_time_afresh() {
+        let start = Instant::now();
+        let secs = std::time::Duration::from_secs;
+        let observe = |ledger, at| observe_network_ledger("default", "deleted-node", ledger, at);
+
+        observe(1000, start);
+        assert_eq!(observe(1010, start + secs(60)), 6.0);
+
+        forget_network_ledger("default", "deleted-node");
+        assert_eq!(
+            observe(1020, start + secs(100)),
+            DEFAULT_LEDGER_CLOSE_SECONDS
+        );
+    }
+
+    #[test]
+    fn test_set_ingestion_lag_seconds() {
+        set_ingestion_lag_seconds(
+            "default",
+            "test-node",
+            "core",
+            "testnet",
+            "Intel Icelake",
+            lag_seconds(5, DEFAULT_LEDGER_CLOSE_SECONDS),
+        );
+        // Function should not panic
+    }
+
     #[test]
     fn test_set_horizon_tps() {
         set_horizon_tps(
@@ -1792,6 +2233,29 @@ mod tests {
         assert_eq!(labels.hardware_generation, "Intel Icelake");
     }
 
+    #[test]
+    fn test_set_last_backup_timestamp() {
+        set_last_backup_timestamp(
+            "default",
+            "backup-node",
+            "validator",
+            "testnet",
+            "unknown",
+            1_767_225_600,
+        );
+        let labels = NodeLabels {
+            namespace: "default".to_string(),
+            name: "backup-node".to_string(),
+            node_type: "validator".to_string(),
+            network: "testnet".to_string(),
+            hardware_generation: "unknown".to_string(),
+        };
+        assert_eq!(
+            LAST_BACKUP_TIMESTAMP.get_or_create(&labels).get(),
+            1_767_225_600
+        );
+    }
+
     #[test]
     fn test_registry_registration() {
         // Access the registry to ensure metrics are registered
@@ -1799,6 +2263,24 @@ mod tests {
         // If this doesn't panic, metrics are properly registered
     }
 
+    #[test]
+    fn test_node_info_registered_with_version() {
+        use prometheus_client::encoding::text::encode;
+
+        set_node_info("default", "info-node", "validator", "testnet", "v21.0.0");
+        set_node_info("default", "info-node", "validator", "testnet", "v21.1.0");
+
+        let mut buffer = String::new();
+        encode(&mut buffer, &REGISTRY).unwrap();
+        let series: Vec<&str> = buffer
+            .lines()
+            .filter(|l| l.starts_with("stellar_node_info{") && l.contains(r#"name="info-node""#))
+            .collect();
+        assert_eq!(series.len(), 1, "{series:?}");
+        assert!(series[0].contains(r#"version="v21.1.0""#), "{}", series[0]);
+        assert!(series[0].ends_with(" 1"), "{}", series[0]);
+    }
+
     #[test]
     fn test_soroban_wasm_execution_duration() {
         observe_wasm_execution_duration("default", "soroban-1", "testnet", "contract123", 1500.0);
@@ -1912,4 +2394,86 @@ mod tests {
         inc_operator_reconcile_error("stellarnode", "unknown");
         // Function should not panic with various error kinds
     }
+
+    #[test]
+    fn test_self_usage_metrics_are_registered() {
+        use prometheus_client::encoding::text::encode;
+
+        let mut buffer = String::new();
+        encode(&mut buffer, &REGISTRY).unwrap();
+        for name in [
+            "stellar_operator_active_reconciles",
+            "stellar_operator_open_watch_streams",
+            "stellar_operator_tokio_alive_tasks",
+            "stellar_operator_resident_memory_bytes",
+        ] {
+            assert!(
+                buffer.contains(&format!("# TYPE {name} gauge")),
+                "{name} must be registered"
+            );
+        }
+    }
+
+    #[test]
+    fn test_backup_metrics_are_registered() {
+        use prometheus_client::encoding::text::encode;
+
+        observe_backup_duration("/var/stellar/history", "success", 12.5);
+        set_backup_size_bytes("/var/stellar/history", 4096);
+
+        let mut buffer = String::new();
+        encode(&mut buffer, &REGISTRY).unwrap();
+        assert!(buffer.contains("# TYPE stellar_backup_duration_seconds histogram"));
+        assert!(buffer.contains("# TYPE stellar_backup_size_bytes gauge"));
+    }
+
+    #[test]
+    fn test_backup_metrics_update() {
+        observe_backup_duration("/archives/metrics-test", "failed", 3.0);
+        observe_backup_duration("/archives/metrics-test", "failed", 5.0);
+        set_backup_size_bytes("/archives/metrics-test", 1024);
+        set_backup_size_bytes("/archives/metrics-test", 2048);
+
+        let labels = BackupArchiveLabels {
+            archive: "/archives/metrics-test".to_string(),
+        };
+        assert_eq!(BACKUP_SIZE_BYTES.get_or_create(&labels).get(), 2048);
+
+        use prometheus_client::encoding::text::encode;
+        let mut buffer = String::new();
+        encode(&mut buffer, &REGISTRY).unwrap();
+        assert!(buffer.contains(
+            "stellar_backup_duration_seconds_count{archive=\"/archives/metrics-test\",status=\"failed\"} 2"
+        ));
+    }
+
+    #[test]
+    fn test_gauge_guard_restores_value_on_drop() {
+        static GAUGE: Lazy<Gauge<i64, AtomicI64>> = Lazy::new(Gauge::default);
+
+        let guard = GaugeGuard::new(&GAUGE, 7);
+        assert_eq!(GAUGE.get(), 7);
+        let nested = GaugeGuard::new(&GAUGE, 1);
+        assert_eq!(GAUGE.get(), 8);
+        drop(nested);
+        drop(guard);
+        assert_eq!(GAUGE.get(), 0);
+    }
+
+    #[test]
+    fn test_parse_vm_rss_bytes() {
+        let status =
+            "Name:\tstellar-operator\nVmPeak:\t  204800 kB\nVmRSS:\t   51200 kB\nThreads:\t8\n";
+        assert_eq!(parse_vm_rss_bytes(status), Some(51200 * 1024));
+        assert_eq!(parse_vm_rss_bytes("Name:\tstellar-operator\n"), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_process_metrics_reads_runtime() {
+        update_process_metrics();
+        assert!(OPERATOR_TOKIO_ALIVE_TASKS.get() >= 0);
+        if cfg!(target_os = "linux") {
+            assert!(OPERATOR_RESIDENT_MEMORY_BYTES.get() > 0);
+        }
+    }
 }
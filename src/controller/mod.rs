@@ -73,6 +73,8 @@ pub mod snapshot_integrity;
 pub mod anomaly_detection;
 pub(crate) mod archive_health;
 pub mod archive_prune;
+pub(crate) mod archive_rotation;
+pub mod archive_server;
 pub mod audit;
 pub mod audit_log;
 pub mod audit_recorder;
@@ -83,6 +85,7 @@ pub mod captive_core;
 pub mod chaos_engineering;
 pub mod compliance_export;
 pub mod conditions;
+pub mod core_log_level;
 pub mod cost;
 pub mod cross_cluster;
 pub mod cross_region_sync;
@@ -102,23 +105,32 @@ pub mod dr_drill;
 mod dr_test;
 pub(crate) mod finalizers;
 pub(crate) mod forensic_snapshot;
+pub mod grafana_dashboard;
 pub(crate) mod health;
 #[cfg(test)]
 mod health_test;
 pub mod kms_secret;
+pub mod ledger_cache;
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod mtls;
 pub mod mtls_rotation;
+pub mod network_config;
+pub mod node_template;
 pub mod oci_snapshot;
 pub mod operator_config;
+pub mod orphan_gc;
+pub mod otel_collector;
 pub mod peer_discovery;
 #[cfg(test)]
 mod peer_discovery_test;
 pub mod performance;
+pub mod pitr;
+pub mod pod_restart;
 pub mod pruning_reconciler;
 pub mod pruning_worker;
 pub mod quorum;
+pub mod quorum_guard;
 pub mod read_pool;
 pub(crate) mod reconciler;
 #[cfg(test)]
@@ -138,12 +150,17 @@ pub mod soroban_cache;
 pub mod spot_drain;
 pub mod state_sync;
 pub mod storage_migration;
+pub mod storage_tier;
 pub(crate) mod sync_scale;
 pub(crate) mod sync_state_monitor;
+#[cfg(any(test, feature = "in-memory-kube"))]
+pub mod test_harness;
 pub mod topology;
 pub mod traffic;
 #[cfg(test)]
 mod traffic_test;
+pub mod version_guard;
+pub mod version_rollback;
 pub mod volume_resizer;
 pub mod vpa;
 pub(crate) mod vsl;
@@ -152,8 +169,9 @@ pub mod zk_archive_verifier;
 
 pub use anomaly_detection::{run_anomaly_detection, AnomalyDetector, AnomalyEvent};
 pub use archive_health::{
-    calculate_backoff, check_archive_integrity, check_history_archive_health, ArchiveHealthResult,
-    ArchiveIntegrityResult, ARCHIVE_LAG_THRESHOLD,
+    archive_lag_threshold, calculate_backoff, check_archive_integrity,
+    check_history_archive_health, ArchiveHealthResult, ArchiveIntegrityResult,
+    ARCHIVE_LAG_THRESHOLD,
 };
 pub use audit_log::{AdminAction, AuditEntry, AuditLog};
 pub use audit_recorder::AuditRecorder;
@@ -164,7 +182,10 @@ pub use blue_green::{
     switch_traffic_to_green, wait_for_green_ready, BlueGreenConfig, BlueGreenStatus,
 };
 pub use cross_cloud_failover::reconcile_cross_cloud_failover;
-pub use cross_cluster::{check_peer_latency, ensure_cross_cluster_services, PeerLatencyStatus};
+pub use cross_cluster::{
+    check_peer_latency, discover_service_import_peers, ensure_cross_cluster_services,
+    PeerLatencyStatus,
+};
 pub use cve_reconciler::reconcile_cve_patches;
 pub use cve_scanner::{
     list_vulnerable_pods, register_cve_metrics, spawn_background_scanner, CveScannerConfig,
@@ -198,10 +219,12 @@ pub use network_isolation::{
     check_network_safety, network_label_value, same_network_namespace_selector,
     NetworkSafetyViolation, NAMESPACE_NETWORK_LABEL, NODE_NETWORK_LABEL,
 };
-pub use operator_config::{hardcoded_defaults, OperatorConfig};
+pub use operator_config::{
+    hardcoded_defaults, LedgerSource, MetricsEndpointConfig, MetricsTlsConfig, OperatorConfig,
+};
 pub use peer_discovery::{
     get_peers_from_config_map, trigger_peer_config_reload, PeerDiscoveryConfig,
-    PeerDiscoveryManager, PeerInfo,
+    PeerDiscoveryManager, PeerDiscoveryResult, PeerInfo,
 };
 pub use pruning_reconciler::{reconcile_pruning, update_pruning_status};
 pub use pss::{
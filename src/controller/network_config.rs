@@ -0,0 +1,669 @@
+//! StellarNetworkConfig resolution
+//!
+//! A StellarNode with `spec.networkConfigRef` takes its network settings from
+//! the referenced cluster-scoped [`StellarNetworkConfig`]:
+//! - `spec.network` must name the same network as the config
+//! - the config's passphrase fills `customNetworkPassphrase`; a passphrase set
+//!   on the node must agree with it
+//! - the config's archives are used by validators with history archives
+//!   enabled and by Captive Core when the node lists none of its own
+//! - the config's `horizonUrl` replaces the Horizon used for ingestion lag,
+//!   taking precedence over the operator's `horizonUrls` and the public one,
+//!   unless the operator's `ledgerSources` reads the network from elsewhere
+//! - the config's `quorumSet` is published in a shared `<config>-quorum-set`
+//!   ConfigMap in the namespace of each validator that sets neither its own
+//!   `quorumSet` nor a `vlSource`, and those validators render their
+//!   `stellar-core.cfg` from it, so a quorum change is made once for every
+//!   validator of the network
+//!
+//! The shared ConfigMap carries a non-controller owner reference to every
+//! validator rendering from it, each applied under the validator's own field
+//! manager, so it is garbage collected once the last of them is deleted.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use k8s_openapi::api::core::v1::ConfigMap;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{ObjectMeta, OwnerReference};
+use kube::api::{Api, Patch, PatchParams};
+use kube::runtime::reflector::ObjectRef;
+use kube::{Client, ResourceExt};
+use tracing::debug;
+
+use super::operator_config::{LedgerSource, OperatorConfig};
+use super::resources::owner_reference;
+use crate::crd::{
+    NodeType, StellarNetwork, StellarNetworkConfig, StellarNetworkConfigSpec, StellarNode,
+    StellarNodeSpec,
+};
+use crate::error::{Error, Result};
+
+/// Key of the quorum set in the shared quorum ConfigMap
+pub const QUORUM_SET_KEY: &str = "quorum-set.toml";
+
+/// Label naming the StellarNetworkConfig a shared resource comes from
+pub const NETWORK_CONFIG_LABEL: &str = "stellar.org/network-config";
+
+/// Public Horizon of a well-known network
+pub fn public_horizon_url(network: &StellarNetwork) -> Option<&'static str> {
+    match network {
+        StellarNetwork::Mainnet => Some("https://horizon.stellar.org"),
+        StellarNetwork::Testnet => Some("https://horizon-testnet.stellar.org"),
+        StellarNetwork::Futurenet => Some("https://horizon-futurenet.stellar.org"),
+        StellarNetwork::Custom(_) => None,
+    }
+}
+
+/// Key of a network in the operator's `horizonUrls`
+pub fn network_key(network: &StellarNetwork) -> &str {
+    match network {
+        StellarNetwork::Mainnet => "mainnet",
+        StellarNetwork::Testnet => "testnet",
+        StellarNetwork::Futurenet => "futurenet",
+        StellarNetwork::Custom(name) => name,
+    }
+}
+
+/// Horizon used as the reference ledger for ingestion lag: the network
+/// config's, then the operator override, then the public Horizon
+pub fn lag_horizon_url(
+    network: &StellarNetwork,
+    config: Option<&StellarNetworkConfigSpec>,
+    overrides: &BTreeMap<String, String>,
+) -> Option<String> {
+    config
+        .and_then(|c| c.horizon_url.clone())
+        .or_else(|| overrides.get(network_key(network)).cloned())
+        .or_else(|| public_horizon_url(network).map(str::to_string))
+}
+
+/// URL queried for the latest ledger of a network: stellar-core's `/info` or
+/// a custom URL when the operator's `ledgerSources` says so, the Horizon from
+/// [`lag_horizon_url`] otherwise
+pub fn latest_ledger_url(
+    network: &StellarNetwork,
+    config: Option<&StellarNetworkConfigSpec>,
+    operator: &OperatorConfig,
+) -> Option<String> {
+    match operator.ledger_sources.get(network_key(network)) {
+        Some(LedgerSource::Core(base)) => Some(format!("{}/info", base.trim_end_matches('/'))),
+        Some(LedgerSource::Custom(url)) => Some(url.clone()),
+        Some(LedgerSource::Horizon) | None => {
+            lag_horizon_url(network, config, &operator.horizon_urls)
+        }
+    }
+}
+
+/// Apply the network settings of `config` (named `name`) to a node spec.
+pub fn resolve_network(
+    spec: &StellarNodeSpec,
+    name: &str,
+    config: &StellarNetworkConfigSpec,
+) -> Result<StellarNodeSpec> {
+    config.validate().map_err(|e| {
+        Error::ValidationError(format!("StellarNetworkConfig '{name}' is invalid: {e}"))
+    })?;
+    if spec.network != config.network {
+        return Err(Error::ValidationError(format!(
+            "spec.network {:?} does not match StellarNetworkConfig '{name}' ({:?})",
+            spec.network, config.network
+        )));
+    }
+
+    let mut resolved = spec.clone();
+    if let Some(passphrase) = &config.passphrase {
+        match &spec.custom_network_passphrase {
+            Some(own) if own != passphrase => {
+                return Err(Error::ValidationError(format!(
+                    "customNetworkPassphrase differs from the passphrase of StellarNetworkConfig '{name}'"
+                )))
+            }
+            _ => resolved.custom_network_passphrase = Some(passphrase.clone()),
+        }
+    }
+
+    if !config.history_archive_urls.is_empty() {
+        if let Some(validator) = resolved.validator_config.as_mut() {
+            if validator.enable_history_archive && validator.history_archive_urls.is_empty() {
+                validator.history_archive_urls = config.history_archive_urls.clone();
+            }
+        }
+        if let Some(captive_core) = resolved
+            .soroban_config
+            .as_mut()
+            .and_then(|s| s.captive_core_structured_config.as_mut())
+        {
+            if captive_core.history_archive_urls.is_empty() {
+                captive_core.history_archive_urls = config.history_archive_urls.clone();
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Fetch the StellarNetworkConfig referenced by `spec.networkConfigRef`.
+///
+/// Returns `Ok(None)` when the node does not reference one.
+pub async fn get_network_config(
+    client: &Client,
+    node: &StellarNode,
+) -> Result<Option<StellarNetworkConfig>> {
+    let Some(name) = node.spec.network_config_ref.as_deref() else {
+        return Ok(None);
+    };
+    let configs: Api<StellarNetworkConfig> = Api::all(client.clone());
+    let config = configs.get_opt(name).await?.ok_or_else(|| {
+        Error::ValidationError(format!(
+            "StellarNetworkConfig '{name}' referenced by spec.networkConfigRef not found"
+        ))
+    })?;
+    Ok(Some(config))
+}
+
+/// Resolve `spec.networkConfigRef`, returning the node with its effective spec.
+///
+/// Returns `Ok(None)` when the node does not reference a network config.
+pub async fn apply_network_config(
+    client: &Client,
+    node: &StellarNode,
+) -> Result<Option<StellarNode>> {
+    let Some(config) = get_network_config(client, node).await? else {
+        return Ok(None);
+    };
+    let name = config.name_any();
+
+    debug!(
+        "Applying StellarNetworkConfig {} to StellarNode {}/{}",
+        name,
+        node.namespace().unwrap_or_default(),
+        node.name_any()
+    );
+
+    let mut resolved = node.clone();
+    resolved.spec = resolve_network(&node.spec, &name, &config.spec)?;
+    Ok(Some(resolved))
+}
+
+/// Whether the node is a validator that takes its quorum set from its
+/// StellarNetworkConfig, having neither its own `quorumSet` nor a `vlSource`
+pub fn uses_shared_quorum_set(spec: &StellarNodeSpec) -> bool {
+    spec.node_type == NodeType::Validator
+        && spec
+            .validator_config
+            .as_ref()
+            .is_some_and(|v| v.quorum_set.is_none() && v.vl_source.is_none())
+}
+
+/// Name of the ConfigMap holding the quorum set of the config named `name`
+pub fn shared_quorum_config_map_name(name: &str) -> String {
+    format!("{name}-quorum-set")
+}
+
+/// Field manager applying `node`'s share of the shared quorum ConfigMap, so
+/// the owner references of the validators using it add up
+fn shared_quorum_field_manager(node: &StellarNode) -> String {
+    format!("stellar-operator-{}", node.name_any())
+}
+
+/// ConfigMap publishing the quorum set of `config` to the validator `node`,
+/// if the config has one
+pub fn build_shared_quorum_config_map(
+    config: &StellarNetworkConfig,
+    node: &StellarNode,
+) -> Option<ConfigMap> {
+    let quorum_set = config.spec.quorum_set.as_ref()?;
+    let name = config.name_any();
+    Some(ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(shared_quorum_config_map_name(&name)),
+            namespace: node.namespace(),
+            labels: Some(BTreeMap::from([
+                (
+                    "app.kubernetes.io/managed-by".to_string(),
+                    "stellar-operator".to_string(),
+                ),
+                (NETWORK_CONFIG_LABEL.to_string(), name),
+            ])),
+            owner_references: Some(vec![OwnerReference {
+                controller: Some(false),
+                ..owner_reference(node)
+            }]),
+            ..Default::default()
+        },
+        data: Some(BTreeMap::from([(
+            QUORUM_SET_KEY.to_string(),
+            quorum_set.clone(),
+        )])),
+        ..Default::default()
+    })
+}
+
+/// Render the validator's quorum set from the shared quorum ConfigMap.
+pub fn use_shared_quorum_set(spec: &mut StellarNodeSpec, cm: &ConfigMap) {
+    let quorum_set = cm
+        .data
+        .as_ref()
+        .and_then(|data| data.get(QUORUM_SET_KEY))
+        .cloned();
+    if let Some(validator) = spec.validator_config.as_mut() {
+        validator.quorum_set = quorum_set;
+    }
+}
+
+/// Publish the quorum set of the validator's StellarNetworkConfig in the
+/// shared ConfigMap of its namespace, returning the node with the quorum set
+/// read back from that ConfigMap.
+///
+/// Returns `Ok(None)` when the node does not take its quorum set from a
+/// network config.
+pub async fn apply_shared_quorum_set(
+    client: &Client,
+    node: &StellarNode,
+    dry_run: bool,
+) -> Result<Option<StellarNode>> {
+    if !uses_shared_quorum_set(&node.spec) {
+        return Ok(None);
+    }
+    let Some(config) = get_network_config(client, node).await? else {
+        return Ok(None);
+    };
+    let Some(cm) = build_shared_quorum_config_map(&config, node) else {
+        return Ok(None);
+    };
+
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<ConfigMap> = Api::namespaced(client.clone(), &namespace);
+    let mut params = PatchParams::apply(&shared_quorum_field_manager(node)).force();
+    params.dry_run = dry_run;
+    let applied = api
+        .patch(
+            &shared_quorum_config_map_name(&config.name_any()),
+            &params,
+            &Patch::Apply(&cm),
+        )
+        .await?;
+
+    let mut resolved = node.clone();
+    use_shared_quorum_set(&mut resolved.spec, &applied);
+    Ok(Some(resolved))
+}
+
+/// StellarNodes in any namespace that reference `config`, so edits to it are
+/// applied without waiting for the next periodic requeue.
+pub fn nodes_using_network_config(
+    nodes: &[Arc<StellarNode>],
+    config: &StellarNetworkConfig,
+) -> Vec<ObjectRef<StellarNode>> {
+    let name = config.name_any();
+    nodes
+        .iter()
+        .filter(|node| node.spec.network_config_ref.as_deref() == Some(name.as_str()))
+        .map(|node| ObjectRef::from_obj(node.as_ref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::types::{CaptiveCoreConfig, SorobanConfig, ValidatorConfig};
+
+    const PASSPHRASE: &str = "Private Network ; January 2026";
+
+    fn private_net() -> StellarNetworkConfigSpec {
+        StellarNetworkConfigSpec {
+            network: StellarNetwork::Custom("private-net".to_string()),
+            passphrase: Some(PASSPHRASE.to_string()),
+            history_archive_urls: vec!["https://archive.private-net.example.com".to_string()],
+            horizon_url: Some("https://horizon.private-net.example.com".to_string()),
+            quorum_set: None,
+        }
+    }
+
+    fn validator(archives: &[&str]) -> StellarNodeSpec {
+        StellarNodeSpec {
+            network: StellarNetwork::Custom("private-net".to_string()),
+            network_config_ref: Some("private-net".to_string()),
+            validator_config: Some(ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                enable_history_archive: true,
+                history_archive_urls: archives.iter().map(|a| a.to_string()).collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_config_fills_passphrase_and_archives() {
+        let spec = resolve_network(&validator(&[]), "private-net", &private_net()).unwrap();
+        assert_eq!(spec.network_passphrase(), PASSPHRASE);
+        assert_eq!(
+            spec.validator_config.unwrap().history_archive_urls,
+            vec!["https://archive.private-net.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_node_archives_take_precedence() {
+        let spec = resolve_network(
+            &validator(&["https://own-archive"]),
+            "private-net",
+            &private_net(),
+        )
+        .unwrap();
+        assert_eq!(
+            spec.validator_config.unwrap().history_archive_urls,
+            vec!["https://own-archive"]
+        );
+    }
+
+    #[test]
+    fn test_captive_core_archives_filled() {
+        let spec = StellarNodeSpec {
+            soroban_config: Some(SorobanConfig {
+                captive_core_structured_config: Some(CaptiveCoreConfig::default()),
+                ..Default::default()
+            }),
+            validator_config: None,
+            ..validator(&[])
+        };
+        let resolved = resolve_network(&spec, "private-net", &private_net()).unwrap();
+        let captive_core = resolved
+            .soroban_config
+            .unwrap()
+            .captive_core_structured_config
+            .unwrap();
+        assert_eq!(captive_core.history_archive_urls.len(), 1);
+    }
+
+    #[test]
+    fn test_network_mismatch_is_rejected() {
+        let spec = StellarNodeSpec {
+            network: StellarNetwork::Testnet,
+            ..validator(&[])
+        };
+        let err = resolve_network(&spec, "private-net", &private_net()).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{err}");
+    }
+
+    #[test]
+    fn test_conflicting_passphrase_is_rejected() {
+        let spec = StellarNodeSpec {
+            custom_network_passphrase: Some("Another Network".to_string()),
+            ..validator(&[])
+        };
+        assert!(resolve_network(&spec, "private-net", &private_net()).is_err());
+
+        let spec = StellarNodeSpec {
+            custom_network_passphrase: Some(PASSPHRASE.to_string()),
+            ..validator(&[])
+        };
+        assert!(resolve_network(&spec, "private-net", &private_net()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let config = StellarNetworkConfigSpec {
+            passphrase: None,
+            ..private_net()
+        };
+        let err = resolve_network(&validator(&[]), "private-net", &config).unwrap_err();
+        assert!(err.to_string().contains("passphrase is required"), "{err}");
+
+        let config = StellarNetworkConfigSpec {
+            network: StellarNetwork::Mainnet,
+            passphrase: Some("Test SDF Network ; September 2015".to_string()),
+            ..private_net()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    const QUORUM_SET: &str =
+        "[QUORUM_SET]\nTHRESHOLD_PERCENT=67\nVALIDATORS=[\"GA v1\", \"GB v2\", \"GC v3\"]\n";
+
+    fn quorum_config() -> StellarNetworkConfig {
+        StellarNetworkConfig::new(
+            "private-net",
+            StellarNetworkConfigSpec {
+                quorum_set: Some(QUORUM_SET.to_string()),
+                ..private_net()
+            },
+        )
+    }
+
+    fn validator_node(name: &str, spec: StellarNodeSpec) -> StellarNode {
+        let mut node = StellarNode::new(
+            name,
+            StellarNodeSpec {
+                node_type: NodeType::Validator,
+                ..spec
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.uid = Some(format!("uid-{name}"));
+        node
+    }
+
+    #[test]
+    fn test_shared_quorum_set_is_used_without_own_quorum_set_or_vsl() {
+        assert!(uses_shared_quorum_set(
+            &validator_node("validator-1", validator(&[])).spec
+        ));
+
+        let mut own = validator(&[]);
+        own.validator_config.as_mut().unwrap().quorum_set = Some("[QUORUM_SET]".to_string());
+        assert!(!uses_shared_quorum_set(
+            &validator_node("validator-1", own).spec
+        ));
+
+        let mut vsl = validator(&[]);
+        vsl.validator_config.as_mut().unwrap().vl_source =
+            Some("https://vsl.example.com".to_string());
+        assert!(!uses_shared_quorum_set(
+            &validator_node("validator-1", vsl).spec
+        ));
+    }
+
+    #[test]
+    fn test_shared_quorum_config_map_rendering() {
+        let node = validator_node("validator-1", validator(&[]));
+        let mut config = quorum_config();
+
+        let cm = build_shared_quorum_config_map(&config, &node).unwrap();
+        assert_eq!(cm.metadata.name.as_deref(), Some("private-net-quorum-set"));
+        assert_eq!(cm.metadata.namespace.as_deref(), Some("stellar"));
+        assert_eq!(
+            cm.metadata.labels.as_ref().unwrap()[NETWORK_CONFIG_LABEL],
+            "private-net"
+        );
+        assert_eq!(cm.data.as_ref().unwrap()[QUORUM_SET_KEY], QUORUM_SET);
+        let owner = &cm.metadata.owner_references.as_ref().unwrap()[0];
+        assert_eq!(owner.kind, "StellarNode");
+        assert_eq!(owner.name, "validator-1");
+        assert_eq!(owner.uid, "uid-validator-1");
+        assert_eq!(owner.controller, Some(false));
+
+        config.spec.quorum_set = None;
+        assert!(build_shared_quorum_config_map(&config, &node).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_validators_render_quorum_set_from_shared_config_map() {
+        use crate::controller::resources::build_config_map_for_test;
+        use crate::controller::test_harness::fake_client;
+
+        let (client, server) = fake_client();
+        let requests = server.serve();
+        let configs: Api<StellarNetworkConfig> = Api::all(client.clone());
+        configs
+            .create(&Default::default(), &quorum_config())
+            .await
+            .unwrap();
+
+        for name in ["validator-1", "validator-2"] {
+            let spec = resolve_network(&validator(&[]), "private-net", &private_net()).unwrap();
+            let node = validator_node(name, spec);
+            let resolved = apply_shared_quorum_set(&client, &node, false)
+                .await
+                .unwrap()
+                .unwrap();
+            let cm = build_config_map_for_test(&resolved);
+            assert!(cm.data.unwrap()["stellar-core.cfg"].contains(QUORUM_SET));
+        }
+
+        let applies: Vec<_> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.path.ends_with("/configmaps/private-net-quorum-set"))
+            .map(|r| r.query.clone())
+            .collect();
+        assert_eq!(applies.len(), 2);
+        assert!(applies[0].contains("fieldManager=stellar-operator-validator-1"));
+        assert!(applies[1].contains("fieldManager=stellar-operator-validator-2"));
+    }
+
+    #[test]
+    fn test_invalid_quorum_set_is_rejected() {
+        let config = StellarNetworkConfigSpec {
+            quorum_set: Some("THRESHOLD_PERCENT=67".to_string()),
+            ..private_net()
+        };
+        assert!(config.validate().unwrap_err().contains("[QUORUM_SET]"));
+
+        let config = StellarNetworkConfigSpec {
+            quorum_set: Some("[QUORUM_SET".to_string()),
+            ..private_net()
+        };
+        assert!(config.validate().is_err());
+
+        let config = StellarNetworkConfigSpec {
+            quorum_set: Some(QUORUM_SET.to_string()),
+            ..private_net()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_crd_is_cluster_scoped() {
+        use kube::CustomResourceExt;
+        assert_eq!(StellarNetworkConfig::crd().spec.scope, "Cluster");
+    }
+
+    #[test]
+    fn test_lag_horizon_prefers_config() {
+        let config = private_net();
+        let none = BTreeMap::new();
+        assert_eq!(
+            lag_horizon_url(&config.network, Some(&config), &none).as_deref(),
+            Some("https://horizon.private-net.example.com")
+        );
+        assert_eq!(lag_horizon_url(&config.network, None, &none), None);
+        assert_eq!(
+            lag_horizon_url(&StellarNetwork::Mainnet, None, &none).as_deref(),
+            Some("https://horizon.stellar.org")
+        );
+    }
+
+    #[test]
+    fn test_operator_override_replaces_public_horizon() {
+        let overrides = BTreeMap::from([
+            (
+                "mainnet".to_string(),
+                "http://horizon-proxy.infra:8000".to_string(),
+            ),
+            (
+                "private-net".to_string(),
+                "http://horizon.private".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            lag_horizon_url(&StellarNetwork::Mainnet, None, &overrides).as_deref(),
+            Some("http://horizon-proxy.infra:8000")
+        );
+        assert_eq!(
+            lag_horizon_url(&StellarNetwork::Testnet, None, &overrides).as_deref(),
+            Some("https://horizon-testnet.stellar.org")
+        );
+        let custom = StellarNetwork::Custom("private-net".to_string());
+        assert_eq!(
+            lag_horizon_url(&custom, None, &overrides).as_deref(),
+            Some("http://horizon.private")
+        );
+    }
+
+    #[test]
+    fn test_network_config_beats_operator_override() {
+        let config = private_net();
+        let overrides = BTreeMap::from([(
+            "private-net".to_string(),
+            "http://horizon.private".to_string(),
+        )]);
+        assert_eq!(
+            lag_horizon_url(&config.network, Some(&config), &overrides).as_deref(),
+            Some("https://horizon.private-net.example.com")
+        );
+    }
+
+    #[test]
+    fn test_ledger_source_selects_url() {
+        let custom = StellarNetwork::Custom("private-net".to_string());
+        let mut operator = OperatorConfig::default();
+        assert_eq!(latest_ledger_url(&custom, None, &operator), None);
+        assert_eq!(
+            latest_ledger_url(&StellarNetwork::Testnet, None, &operator).as_deref(),
+            Some("https://horizon-testnet.stellar.org")
+        );
+
+        operator.ledger_sources.insert(
+            "private-net".to_string(),
+            LedgerSource::Core("http://core.private-net:11626/".to_string()),
+        );
+        operator.ledger_sources.insert(
+            "testnet".to_string(),
+            LedgerSource::Custom("https://ledger.example.com/testnet".to_string()),
+        );
+        let config = private_net();
+        assert_eq!(
+            latest_ledger_url(&custom, Some(&config), &operator).as_deref(),
+            Some("http://core.private-net:11626/info")
+        );
+        assert_eq!(
+            latest_ledger_url(&StellarNetwork::Testnet, None, &operator).as_deref(),
+            Some("https://ledger.example.com/testnet")
+        );
+    }
+
+    #[test]
+    fn test_config_maps_to_referencing_nodes_in_every_namespace() {
+        let node = |name: &str, namespace: &str, config_ref: Option<&str>| {
+            let mut node = StellarNode::new(
+                name,
+                StellarNodeSpec {
+                    network_config_ref: config_ref.map(str::to_string),
+                    ..Default::default()
+                },
+            );
+            node.metadata.namespace = Some(namespace.to_string());
+            Arc::new(node)
+        };
+        let nodes = vec![
+            node("validator", "stellar", Some("private-net")),
+            node("horizon", "staging", Some("private-net")),
+            node("other-network", "stellar", Some("testnet-config")),
+            node("no-config", "stellar", None),
+        ];
+        let config = StellarNetworkConfig::new("private-net", private_net());
+
+        let refs = nodes_using_network_config(&nodes, &config);
+        let names: Vec<(&str, Option<&str>)> = refs
+            .iter()
+            .map(|r| (r.name.as_str(), r.namespace.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            [("validator", Some("stellar")), ("horizon", Some("staging"))]
+        );
+    }
+}
@@ -0,0 +1,347 @@
+//! StellarNodeTemplate resolution
+//!
+//! A StellarNode with `spec.templateRef` inherits the referenced
+//! [`StellarNodeTemplate`]'s spec. The merge works on the node's spec exactly
+//! as stored in the API server, not on the typed spec: serde defaults would
+//! otherwise make every omitted field look like an explicit override.
+//!
+//! Precedence follows JSON merge patch (RFC 7386) with the node as the patch:
+//! - fields set on the node win over the template
+//! - nested objects are merged key by key
+//! - lists and scalars from the node replace the template's value
+//! - an explicit `null` on the node clears the template's value
+//!
+//! Top-level fields the CRD schema requires (`nodeType`, `network`,
+//! `version`, ...) or defaults (`replicas`, `resources`, `storage`, ...) are
+//! always present on the stored node, so a template value for them could
+//! never take effect. Templates setting them are rejected instead of being
+//! silently overridden.
+
+use std::sync::Arc;
+
+use kube::api::{Api, ApiResource, DynamicObject};
+use kube::runtime::reflector::ObjectRef;
+use kube::{Client, CustomResourceExt, ResourceExt};
+use serde_json::{Map, Value};
+use tracing::debug;
+
+use crate::crd::{StellarNode, StellarNodeSpec, StellarNodeTemplate};
+use crate::error::{Error, Result};
+
+/// Merge `overrides` on top of `base`, returning the combined value.
+pub fn merge_spec(base: &Value, overrides: &Value) -> Value {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            let mut merged = base.clone();
+            for (key, value) in overrides {
+                if value.is_null() {
+                    merged.remove(key);
+                    continue;
+                }
+                let combined = match merged.get(key) {
+                    Some(existing) => merge_spec(existing, value),
+                    None => value.clone(),
+                };
+                merged.insert(key.clone(), combined);
+            }
+            Value::Object(merged)
+        }
+        (_, overrides) => overrides.clone(),
+    }
+}
+
+/// Top-level StellarNode spec fields the CRD schema requires or defaults,
+/// which every stored node therefore carries.
+pub fn always_set_fields() -> Vec<String> {
+    let crd = StellarNode::crd();
+    let Some(spec) = crd
+        .spec
+        .versions
+        .first()
+        .and_then(|version| version.schema.as_ref())
+        .and_then(|schema| schema.open_api_v3_schema.as_ref())
+        .and_then(|schema| schema.properties.as_ref())
+        .and_then(|properties| properties.get("spec"))
+    else {
+        return Vec::new();
+    };
+
+    let mut fields = spec.required.clone().unwrap_or_default();
+    if let Some(properties) = &spec.properties {
+        fields.extend(
+            properties
+                .iter()
+                .filter(|(_, property)| property.default.is_some())
+                .map(|(name, _)| name.clone()),
+        );
+    }
+    fields.sort();
+    fields.dedup();
+    fields
+}
+
+/// Build the effective spec of a node from its template and raw spec.
+pub fn resolve_spec(template: &Value, raw_spec: &Value) -> Result<StellarNodeSpec> {
+    let base = match template {
+        Value::Object(fields) => {
+            let mut ignored: Vec<&str> = always_set_fields()
+                .iter()
+                .filter_map(|field| fields.get_key_value(field.as_str()))
+                .map(|(field, _)| field.as_str())
+                .collect();
+            if !ignored.is_empty() {
+                ignored.sort_unstable();
+                return Err(Error::ValidationError(format!(
+                    "StellarNodeTemplate spec.template sets {}, which every StellarNode sets itself; remove them from the template",
+                    ignored.join(", ")
+                )));
+            }
+            template.clone()
+        }
+        Value::Null => Value::Object(Map::new()),
+        _ => {
+            return Err(Error::ValidationError(
+                "StellarNodeTemplate spec.template must be an object".to_string(),
+            ))
+        }
+    };
+    let merged = merge_spec(&base, raw_spec);
+    serde_json::from_value(merged).map_err(|e| {
+        Error::ValidationError(format!(
+            "Spec merged from StellarNodeTemplate is invalid: {e}"
+        ))
+    })
+}
+
+/// Resolve `spec.templateRef`, returning the node with its effective spec.
+///
+/// Returns `Ok(None)` when the node does not reference a template.
+pub async fn apply_template(client: &Client, node: &StellarNode) -> Result<Option<StellarNode>> {
+    let Some(template_name) = node.spec.template_ref.as_deref() else {
+        return Ok(None);
+    };
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+
+    let templates: Api<StellarNodeTemplate> = Api::namespaced(client.clone(), &namespace);
+    let template = templates.get_opt(template_name).await?.ok_or_else(|| {
+        Error::ValidationError(format!(
+            "StellarNodeTemplate '{template_name}' referenced by spec.templateRef not found in namespace '{namespace}'"
+        ))
+    })?;
+
+    let raw_nodes: Api<DynamicObject> = Api::namespaced_with(
+        client.clone(),
+        &namespace,
+        &ApiResource::erase::<StellarNode>(&()),
+    );
+    let raw = raw_nodes.get(&node.name_any()).await?;
+    let raw_spec = raw.data.get("spec").cloned().unwrap_or(Value::Null);
+
+    debug!(
+        "Applying StellarNodeTemplate {}/{} to StellarNode {}",
+        namespace,
+        template_name,
+        node.name_any()
+    );
+
+    let mut resolved = node.clone();
+    resolved.spec = resolve_spec(&template.spec.template, &raw_spec)?;
+    Ok(Some(resolved))
+}
+
+/// StellarNodes that inherit from `template`, so edits to it are applied
+/// without waiting for the next periodic requeue.
+pub fn nodes_using_template(
+    nodes: &[Arc<StellarNode>],
+    template: &StellarNodeTemplate,
+) -> Vec<ObjectRef<StellarNode>> {
+    let name = template.name_any();
+    nodes
+        .iter()
+        .filter(|node| {
+            node.namespace() == template.namespace()
+                && node.spec.template_ref.as_deref() == Some(name.as_str())
+        })
+        .map(|node| ObjectRef::from_obj(node.as_ref()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::{NodeType, StellarNetwork, StellarNodeTemplateSpec};
+    use serde_json::json;
+
+    fn template() -> Value {
+        json!({
+            "priorityClassName": "stellar-validator-critical",
+            "validatorConfig": {
+                "seedSecretRef": "template-seed",
+                "enableHistoryArchive": true,
+                "historyArchiveUrls": ["https://a", "https://b"]
+            }
+        })
+    }
+
+    #[test]
+    fn test_node_fields_override_template() {
+        let spec = resolve_spec(
+            &template(),
+            &json!({
+                "nodeType": "Validator",
+                "network": "mainnet",
+                "version": "v22.0.0",
+                "templateRef": "mainnet-validator"
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(spec.version, "v22.0.0");
+        assert_eq!(spec.network, StellarNetwork::Mainnet);
+        assert_eq!(
+            spec.priority_class_name.as_deref(),
+            Some("stellar-validator-critical")
+        );
+        assert_eq!(spec.template_ref.as_deref(), Some("mainnet-validator"));
+    }
+
+    #[test]
+    fn test_nested_objects_merge_key_by_key() {
+        let spec = resolve_spec(
+            &template(),
+            &json!({
+                "nodeType": "Validator",
+                "network": "mainnet",
+                "version": "v21.0.0",
+                "validatorConfig": { "seedSecretRef": "node-seed" }
+            }),
+        )
+        .unwrap();
+
+        let validator = spec.validator_config.unwrap();
+        assert_eq!(validator.seed_secret_ref, "node-seed");
+        assert!(validator.enable_history_archive);
+        assert_eq!(
+            validator.history_archive_urls,
+            vec!["https://a", "https://b"]
+        );
+    }
+
+    #[test]
+    fn test_lists_are_replaced_not_appended() {
+        let merged = merge_spec(
+            &template(),
+            &json!({ "validatorConfig": { "historyArchiveUrls": ["https://c"] } }),
+        );
+        assert_eq!(
+            merged["validatorConfig"]["historyArchiveUrls"],
+            json!(["https://c"])
+        );
+    }
+
+    #[test]
+    fn test_null_clears_template_value() {
+        let merged = merge_spec(&template(), &json!({ "priorityClassName": null }));
+        assert!(merged.get("priorityClassName").is_none());
+        assert_eq!(merged["validatorConfig"]["seedSecretRef"], "template-seed");
+    }
+
+    #[test]
+    fn test_template_fills_fields_omitted_on_node() {
+        let spec = resolve_spec(
+            &template(),
+            &json!({
+                "nodeType": "Validator",
+                "network": "mainnet",
+                "version": "v21.0.0",
+                "replicas": 1
+            }),
+        )
+        .unwrap();
+        assert_eq!(spec.node_type, NodeType::Validator);
+        assert_eq!(
+            spec.priority_class_name.as_deref(),
+            Some("stellar-validator-critical")
+        );
+        assert_eq!(
+            spec.validator_config.unwrap().seed_secret_ref,
+            "template-seed"
+        );
+    }
+
+    #[test]
+    fn test_always_set_fields_cover_required_and_defaulted() {
+        let fields = always_set_fields();
+        for field in [
+            "nodeType",
+            "network",
+            "version",
+            "replicas",
+            "resources",
+            "storage",
+        ] {
+            assert!(fields.iter().any(|f| f == field), "{field} missing");
+        }
+        assert!(!fields.iter().any(|f| f == "priorityClassName"));
+    }
+
+    #[test]
+    fn test_template_setting_always_set_fields_is_rejected() {
+        let mut template = template();
+        template["version"] = json!("v21.0.0");
+        template["replicas"] = json!(3);
+        let err = resolve_spec(
+            &template,
+            &json!({ "nodeType": "Validator", "network": "mainnet", "version": "v22.0.0" }),
+        )
+        .unwrap_err();
+        let Error::ValidationError(message) = err else {
+            panic!("expected a validation error");
+        };
+        assert!(message.contains("replicas, version"), "{message}");
+    }
+
+    #[test]
+    fn test_non_object_template_is_rejected() {
+        assert!(resolve_spec(&json!(["not", "an", "object"]), &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_incomplete_merged_spec_is_rejected() {
+        let err = resolve_spec(&json!({}), &json!({ "version": "v21.0.0" })).unwrap_err();
+        assert!(matches!(err, Error::ValidationError(_)));
+    }
+
+    #[test]
+    fn test_template_maps_to_referencing_nodes_in_namespace() {
+        let node = |name: &str, namespace: &str, template_ref: Option<&str>| {
+            let mut node = StellarNode::new(
+                name,
+                StellarNodeSpec {
+                    template_ref: template_ref.map(str::to_string),
+                    ..Default::default()
+                },
+            );
+            node.metadata.namespace = Some(namespace.to_string());
+            Arc::new(node)
+        };
+        let nodes = vec![
+            node("uses-it", "stellar", Some("mainnet-validator")),
+            node("other-template", "stellar", Some("testnet-validator")),
+            node("no-template", "stellar", None),
+            node("other-namespace", "staging", Some("mainnet-validator")),
+        ];
+        let mut template = StellarNodeTemplate::new(
+            "mainnet-validator",
+            StellarNodeTemplateSpec {
+                template: template(),
+            },
+        );
+        template.metadata.namespace = Some("stellar".to_string());
+
+        let refs = nodes_using_template(&nodes, &template);
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].name, "uses-it");
+        assert_eq!(refs[0].namespace.as_deref(), Some("stellar"));
+    }
+}
@@ -16,14 +16,24 @@
 //! 1. The operator creates a Job (`<node>-snapshot-pull`) before the node pod starts.
 //! 2. The Job pulls the OCI image and extracts the layer tarball onto the node PVC.
 //! 3. Once the Job succeeds the operator proceeds with normal node reconciliation.
+//!
+//! ## Backup status
+//! Push Jobs are the node's backups. The most recently finished one is recorded
+//! in `status.lastBackupTime` / `status.lastBackupStatus`.
+//!
+//! Only one push Job runs at a time; a new one is not created while another is
+//! still active. Finished push Jobs beyond `successfulJobsHistoryLimit` and
+//! `failedJobsHistoryLimit` are deleted, newest kept.
 
+use chrono::{DateTime, Utc};
 use k8s_openapi::api::batch::v1::{Job, JobSpec};
 use k8s_openapi::api::core::v1::{
-    Container, EnvVar, PodSpec, PodTemplateSpec, ProjectedVolumeSource, SecretProjection, Volume,
-    VolumeMount, VolumeProjection,
+    Container, EnvVar, PodSpec, PodTemplateSpec, ProjectedVolumeSource, ResourceRequirements,
+    SecretProjection, Volume, VolumeMount, VolumeProjection,
 };
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
-use kube::api::{Api, PostParams};
+use kube::api::{Api, DeleteParams, ListParams, PostParams};
 use kube::{Client, ResourceExt};
 use tracing::{debug, info};
 
@@ -43,6 +53,18 @@ const SCRATCH_MOUNT_PATH: &str = "/scratch";
 // Where the registry credential secret is projected.
 const DOCKER_CONFIG_PATH: &str = "/root/.docker";
 
+/// Label distinguishing snapshot push and pull Jobs
+pub const JOB_TYPE_LABEL: &str = "stellar.org/job-type";
+
+/// [`JOB_TYPE_LABEL`] value of snapshot push (backup) Jobs
+pub const PUSH_JOB_TYPE: &str = "snapshot-push";
+
+/// `status.lastBackupStatus` of a backup whose Job completed
+pub const BACKUP_SUCCEEDED: &str = "Succeeded";
+
+/// `status.lastBackupStatus` of a backup whose Job failed
+pub const BACKUP_FAILED: &str = "Failed";
+
 // ─── Tag helpers ─────────────────────────────────────────────────────────────
 
 /// Resolve the OCI image tag according to the configured [`TagStrategy`].
@@ -143,6 +165,23 @@ fn pvc_volume_readonly(pvc_name: &str) -> Volume {
     }
 }
 
+/// Container resources for push/pull Jobs from `cfg.resources`.
+fn job_resources(cfg: &OciSnapshotConfig) -> ResourceRequirements {
+    let quantities = |spec: &crate::crd::ResourceSpec| {
+        [
+            ("cpu".to_string(), Quantity(spec.cpu.clone())),
+            ("memory".to_string(), Quantity(spec.memory.clone())),
+        ]
+        .into_iter()
+        .collect()
+    };
+    ResourceRequirements {
+        requests: Some(quantities(&cfg.resources.requests)),
+        limits: Some(quantities(&cfg.resources.limits)),
+        claims: None,
+    }
+}
+
 /// Build an `emptyDir` scratch volume for intermediate tarballs.
 fn scratch_volume() -> Volume {
     Volume {
@@ -177,10 +216,7 @@ pub fn build_snapshot_push_job(
     let pvc_name = format!("{}-data", node.name_any());
 
     let mut labels = standard_labels(node);
-    labels.insert(
-        "stellar.org/job-type".to_string(),
-        "snapshot-push".to_string(),
-    );
+    labels.insert(JOB_TYPE_LABEL.to_string(), PUSH_JOB_TYPE.to_string());
 
     let container = Container {
         name: "snapshot-push".to_string(),
@@ -218,6 +254,7 @@ pub fn build_snapshot_push_job(
                 ..Default::default()
             },
         ]),
+        resources: Some(job_resources(cfg)),
         ..Default::default()
     };
 
@@ -231,6 +268,7 @@ pub fn build_snapshot_push_job(
         },
         spec: Some(JobSpec {
             backoff_limit: Some(3),
+            active_deadline_seconds: cfg.active_deadline_seconds,
             template: PodTemplateSpec {
                 metadata: Some(ObjectMeta {
                     labels: Some(standard_labels(node)),
@@ -314,6 +352,7 @@ pub fn build_snapshot_pull_job(
                 ..Default::default()
             },
         ]),
+        resources: Some(job_resources(cfg)),
         ..Default::default()
     };
 
@@ -327,6 +366,7 @@ pub fn build_snapshot_pull_job(
         },
         spec: Some(JobSpec {
             backoff_limit: Some(3),
+            active_deadline_seconds: cfg.active_deadline_seconds,
             template: PodTemplateSpec {
                 metadata: Some(ObjectMeta {
                     labels: Some(standard_labels(node)),
@@ -354,13 +394,14 @@ pub fn build_snapshot_pull_job(
 /// Idempotently create the snapshot push Job.
 ///
 /// If a Job with the same name already exists it is left unchanged (skip).
-/// Returns the Job name on success.
+/// While another push Job of the node is still running no new Job is created
+/// and `None` is returned. Returns the Job name on success.
 pub async fn ensure_snapshot_push_job(
     client: &Client,
     node: &StellarNode,
     cfg: &OciSnapshotConfig,
     ledger_seq: u64,
-) -> Result<String> {
+) -> Result<Option<String>> {
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let api: Api<Job> = Api::namespaced(client.clone(), &namespace);
     let job = build_snapshot_push_job(node, cfg, ledger_seq);
@@ -369,14 +410,22 @@ pub async fn ensure_snapshot_push_job(
     match api.get(&job_name).await {
         Ok(_) => {
             debug!("Snapshot push Job {} already exists, skipping", job_name);
-            Ok(job_name)
+            Ok(Some(job_name))
         }
         Err(kube::Error::Api(e)) if e.code == 404 => {
+            let jobs = list_push_jobs(client, node).await?;
+            if let Some(active) = active_job(&jobs) {
+                debug!(
+                    "Snapshot push Job {} still running, not starting {}",
+                    active, job_name
+                );
+                return Ok(None);
+            }
             info!("Creating snapshot push Job {}", job_name);
             api.create(&PostParams::default(), &job)
                 .await
                 .map_err(Error::KubeError)?;
-            Ok(job_name)
+            Ok(Some(job_name))
         }
         Err(e) => Err(Error::KubeError(e)),
     }
@@ -433,14 +482,157 @@ pub async fn is_snapshot_job_done(
     }
 }
 
+// ─── Backup status ────────────────────────────────────────────────────────────
+
+/// Outcome of a finished snapshot push Job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupResult {
+    pub job_name: String,
+    pub finished_at: DateTime<Utc>,
+    pub succeeded: bool,
+}
+
+impl BackupResult {
+    /// Value for `status.lastBackupStatus`
+    pub fn status(&self) -> &'static str {
+        if self.succeeded {
+            BACKUP_SUCCEEDED
+        } else {
+            BACKUP_FAILED
+        }
+    }
+
+    /// Status patch recording this result, or `None` if it is already recorded.
+    pub fn status_patch(&self, node: &StellarNode) -> Option<serde_json::Value> {
+        let time = self.finished_at.to_rfc3339();
+        let status = node.status.as_ref();
+        let recorded = status.and_then(|s| s.last_backup_time.as_deref()) == Some(time.as_str())
+            && status.and_then(|s| s.last_backup_status.as_deref()) == Some(self.status());
+        if recorded {
+            return None;
+        }
+        Some(serde_json::json!({
+            "status": {
+                "lastBackupTime": time,
+                "lastBackupStatus": self.status(),
+            }
+        }))
+    }
+}
+
+/// Outcome of a single Job, or `None` while it is still running.
+fn job_result(job: &Job) -> Option<BackupResult> {
+    let status = job.status.as_ref()?;
+    let finished = status
+        .conditions
+        .iter()
+        .flatten()
+        .find(|c| (c.type_ == "Complete" || c.type_ == "Failed") && c.status == "True")?;
+    let succeeded = finished.type_ == "Complete";
+    let completion_time = status.completion_time.as_ref().filter(|_| succeeded);
+    let finished_at = completion_time
+        .or(finished.last_transition_time.as_ref())?
+        .0;
+
+    Some(BackupResult {
+        job_name: job.name_any(),
+        finished_at,
+        succeeded,
+    })
+}
+
+/// Most recently finished Job among `jobs`.
+pub fn latest_backup_result(jobs: &[Job]) -> Option<BackupResult> {
+    jobs.iter()
+        .filter_map(job_result)
+        .max_by_key(|r| r.finished_at)
+}
+
+/// Look up the most recently finished snapshot push Job of a node.
+pub async fn latest_push_job_result(
+    client: &Client,
+    node: &StellarNode,
+) -> Result<Option<BackupResult>> {
+    let jobs = list_push_jobs(client, node).await?;
+    Ok(latest_backup_result(&jobs))
+}
+
+/// Name of a push Job that has not finished yet, if any.
+pub fn active_job(jobs: &[Job]) -> Option<String> {
+    jobs.iter()
+        .filter(|job| job.metadata.deletion_timestamp.is_none())
+        .find(|job| job_result(job).is_none())
+        .map(|job| job.name_any())
+}
+
+/// Names of finished Jobs beyond the configured history limits.
+///
+/// The newest `successful_limit` succeeded and `failed_limit` failed Jobs are
+/// kept; running Jobs are never returned.
+pub fn jobs_to_prune(jobs: &[Job], successful_limit: u32, failed_limit: u32) -> Vec<String> {
+    let mut finished: Vec<BackupResult> = jobs.iter().filter_map(job_result).collect();
+    finished.sort_by(|a, b| b.finished_at.cmp(&a.finished_at));
+
+    let (succeeded, failed): (Vec<_>, Vec<_>) = finished.into_iter().partition(|r| r.succeeded);
+    succeeded
+        .into_iter()
+        .skip(successful_limit as usize)
+        .chain(failed.into_iter().skip(failed_limit as usize))
+        .map(|r| r.job_name)
+        .collect()
+}
+
+/// Delete finished push Jobs beyond the history limits of `cfg`.
+pub async fn prune_push_jobs(
+    client: &Client,
+    node: &StellarNode,
+    cfg: &OciSnapshotConfig,
+) -> Result<usize> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<Job> = Api::namespaced(client.clone(), &namespace);
+    let jobs = list_push_jobs(client, node).await?;
+    let prune = jobs_to_prune(
+        &jobs,
+        cfg.successful_jobs_history_limit,
+        cfg.failed_jobs_history_limit,
+    );
+
+    for name in &prune {
+        debug!("Deleting old snapshot push Job {}", name);
+        match api.delete(name, &DeleteParams::background()).await {
+            Ok(_) => {}
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => return Err(Error::KubeError(e)),
+        }
+    }
+    Ok(prune.len())
+}
+
+async fn list_push_jobs(client: &Client, node: &StellarNode) -> Result<Vec<Job>> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<Job> = Api::namespaced(client.clone(), &namespace);
+    let selector = format!(
+        "app.kubernetes.io/instance={},{}={}",
+        node.name_any(),
+        JOB_TYPE_LABEL,
+        PUSH_JOB_TYPE
+    );
+    let jobs = api
+        .list(&ListParams::default().labels(&selector))
+        .await
+        .map_err(Error::KubeError)?;
+    Ok(jobs.items)
+}
+
 // ─── Tests ────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::crd::{
-        HistoryMode, NodeType, OciSnapshotConfig, ResourceRequirements, RolloutStrategy,
-        StellarNetwork, StellarNode, StellarNodeSpec, StorageConfig, TagStrategy, ValidatorConfig,
+        HistoryMode, NodeType, OciSnapshotConfig, ResourceRequirements, ResourceSpec,
+        RolloutStrategy, StellarNetwork, StellarNode, StellarNodeSpec, StorageConfig, TagStrategy,
+        ValidatorConfig,
     };
 
     fn test_cfg(tag_strategy: TagStrategy, fixed_tag: Option<&str>) -> OciSnapshotConfig {
@@ -454,6 +646,19 @@ mod tests {
             push: true,
             pull: false,
             pull_image_ref: None,
+            successful_jobs_history_limit: 3,
+            failed_jobs_history_limit: 1,
+            active_deadline_seconds: None,
+            resources: ResourceRequirements {
+                requests: ResourceSpec {
+                    cpu: "250m".to_string(),
+                    memory: "512Mi".to_string(),
+                },
+                limits: ResourceSpec {
+                    cpu: "1".to_string(),
+                    memory: "2Gi".to_string(),
+                },
+            },
         }
     }
 
@@ -741,4 +946,202 @@ mod tests {
         let restart = job.spec.unwrap().template.spec.unwrap().restart_policy;
         assert_eq!(restart.as_deref(), Some("OnFailure"));
     }
+
+    // ─── Backup status ───────────────────────────────────────────────────────
+
+    fn finished_job(name: &str, condition: &str, at: &str) -> Job {
+        use k8s_openapi::api::batch::v1::{JobCondition, JobStatus};
+        use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+        let time = Time(at.parse().unwrap());
+        Job {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            spec: None,
+            status: Some(JobStatus {
+                completion_time: (condition == "Complete").then(|| time.clone()),
+                conditions: Some(vec![JobCondition {
+                    type_: condition.to_string(),
+                    status: "True".to_string(),
+                    last_transition_time: Some(time),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_push_job_labelled_as_backup() {
+        let node = make_node("my-node");
+        let cfg = test_cfg(TagStrategy::LatestLedger, None);
+        let job = build_snapshot_push_job(&node, &cfg, 1);
+        let labels = job.metadata.labels.unwrap();
+        assert_eq!(
+            labels.get(JOB_TYPE_LABEL).map(String::as_str),
+            Some(PUSH_JOB_TYPE)
+        );
+    }
+
+    #[test]
+    fn test_latest_backup_result_picks_most_recent_finished_job() {
+        let mut running = finished_job("push-3", "Complete", "2026-01-03T00:00:00Z");
+        running.status = None;
+        let jobs = vec![
+            finished_job("push-1", "Complete", "2026-01-01T00:00:00Z"),
+            finished_job("push-2", "Failed", "2026-01-02T00:00:00Z"),
+            running,
+        ];
+
+        let result = latest_backup_result(&jobs).unwrap();
+        assert_eq!(result.job_name, "push-2");
+        assert!(!result.succeeded);
+        assert_eq!(result.status(), BACKUP_FAILED);
+    }
+
+    #[test]
+    fn test_completed_job_updates_backup_status() {
+        let node = make_node("my-node");
+        let result =
+            latest_backup_result(&[finished_job("push-1", "Complete", "2026-01-01T06:00:00Z")])
+                .unwrap();
+
+        let patch = result.status_patch(&node).unwrap();
+        assert_eq!(patch["status"]["lastBackupStatus"], BACKUP_SUCCEEDED);
+        assert_eq!(
+            patch["status"]["lastBackupTime"],
+            "2026-01-01T06:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_failed_job_updates_backup_status() {
+        let mut node = make_node("my-node");
+        node.status = Some(crate::crd::StellarNodeStatus {
+            last_backup_time: Some("2026-01-01T06:00:00+00:00".to_string()),
+            last_backup_status: Some(BACKUP_SUCCEEDED.to_string()),
+            ..Default::default()
+        });
+        let result =
+            latest_backup_result(&[finished_job("push-2", "Failed", "2026-01-02T06:00:00Z")])
+                .unwrap();
+
+        let patch = result.status_patch(&node).unwrap();
+        assert_eq!(patch["status"]["lastBackupStatus"], BACKUP_FAILED);
+    }
+
+    #[test]
+    fn test_recorded_backup_result_needs_no_patch() {
+        let mut node = make_node("my-node");
+        node.status = Some(crate::crd::StellarNodeStatus {
+            last_backup_time: Some("2026-01-01T06:00:00+00:00".to_string()),
+            last_backup_status: Some(BACKUP_SUCCEEDED.to_string()),
+            ..Default::default()
+        });
+        let result =
+            latest_backup_result(&[finished_job("push-1", "Complete", "2026-01-01T06:00:00Z")])
+                .unwrap();
+
+        assert!(result.status_patch(&node).is_none());
+    }
+
+    // ─── History limits and concurrency ──────────────────────────────────────
+
+    fn running_job(name: &str) -> Job {
+        let mut job = finished_job(name, "Complete", "2026-01-01T00:00:00Z");
+        job.status = None;
+        job
+    }
+
+    #[test]
+    fn test_history_limit_defaults() {
+        let cfg: OciSnapshotConfig = serde_json::from_value(serde_json::json!({
+            "registry": "ghcr.io",
+            "image": "myorg/stellar-snapshot",
+            "credentialSecretName": "registry-creds"
+        }))
+        .unwrap();
+        assert_eq!(cfg.successful_jobs_history_limit, 3);
+        assert_eq!(cfg.failed_jobs_history_limit, 1);
+        assert_eq!(cfg.active_deadline_seconds, None);
+    }
+
+    #[test]
+    fn test_jobs_beyond_history_limits_are_pruned() {
+        let jobs = vec![
+            finished_job("push-1", "Complete", "2026-01-01T00:00:00Z"),
+            finished_job("push-2", "Failed", "2026-01-02T00:00:00Z"),
+            finished_job("push-3", "Complete", "2026-01-03T00:00:00Z"),
+            finished_job("push-4", "Failed", "2026-01-04T00:00:00Z"),
+            finished_job("push-5", "Complete", "2026-01-05T00:00:00Z"),
+            running_job("push-6"),
+        ];
+
+        let mut pruned = jobs_to_prune(&jobs, 2, 1);
+        pruned.sort();
+        assert_eq!(pruned, vec!["push-1", "push-2"]);
+    }
+
+    #[test]
+    fn test_zero_history_limit_prunes_all_finished_jobs() {
+        let jobs = vec![
+            finished_job("push-1", "Complete", "2026-01-01T00:00:00Z"),
+            running_job("push-2"),
+        ];
+        assert_eq!(jobs_to_prune(&jobs, 0, 0), vec!["push-1"]);
+    }
+
+    #[test]
+    fn test_active_job_blocks_new_push() {
+        let finished = vec![finished_job("push-1", "Complete", "2026-01-01T00:00:00Z")];
+        assert_eq!(active_job(&finished), None);
+
+        let mut jobs = finished;
+        jobs.push(running_job("push-2"));
+        assert_eq!(active_job(&jobs).as_deref(), Some("push-2"));
+    }
+
+    #[test]
+    fn test_active_deadline_applied_to_jobs() {
+        let node = make_node("my-node");
+        let mut cfg = test_cfg(TagStrategy::LatestLedger, None);
+        cfg.active_deadline_seconds = Some(3600);
+
+        let push = build_snapshot_push_job(&node, &cfg, 1);
+        let pull = build_snapshot_pull_job(&node, &cfg, 1);
+        assert_eq!(push.spec.unwrap().active_deadline_seconds, Some(3600));
+        assert_eq!(pull.spec.unwrap().active_deadline_seconds, Some(3600));
+    }
+
+    #[test]
+    fn test_job_resources_default_and_override() {
+        let cfg: OciSnapshotConfig = serde_json::from_value(serde_json::json!({
+            "registry": "ghcr.io",
+            "image": "myorg/stellar-snapshot",
+            "credentialSecretName": "registry-creds"
+        }))
+        .unwrap();
+        assert_eq!(cfg.resources.requests.memory, "512Mi");
+        assert_eq!(cfg.resources.limits.memory, "2Gi");
+
+        let node = make_node("my-node");
+        let mut cfg = test_cfg(TagStrategy::LatestLedger, None);
+        cfg.resources.limits.memory = "8Gi".to_string();
+
+        for job in [
+            build_snapshot_push_job(&node, &cfg, 1),
+            build_snapshot_pull_job(&node, &cfg, 1),
+        ] {
+            let pod = job.spec.unwrap().template.spec.unwrap();
+            let resources = pod.containers[0].resources.clone().unwrap();
+            let requests = resources.requests.unwrap();
+            let limits = resources.limits.unwrap();
+            assert_eq!(requests["cpu"].0, "250m");
+            assert_eq!(requests["memory"].0, "512Mi");
+            assert_eq!(limits["cpu"].0, "1");
+            assert_eq!(limits["memory"].0, "8Gi");
+        }
+    }
 }
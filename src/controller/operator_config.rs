@@ -8,6 +8,7 @@
 
 use crate::crd::{NodeType, ResourceRequirements, ResourceSpec};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Duration;
 use tracing::warn;
 
@@ -60,6 +61,136 @@ pub struct OperatorConfig {
     /// Disk scaling configuration
     #[serde(default)]
     pub disk_scaling: DiskScalingConfig,
+    /// Grafana dashboard publishing
+    #[serde(default)]
+    pub grafana_dashboard: GrafanaDashboardConfig,
+    /// Horizon queried for the latest ledger of a network, keyed by
+    /// `mainnet`, `testnet`, `futurenet` or a custom network's name.
+    /// Replaces the public Horizon, e.g. to go through a proxy.
+    #[serde(default)]
+    pub horizon_urls: BTreeMap<String, String>,
+    /// Where the latest ledger of a network is read from, keyed like
+    /// `horizonUrls`; networks not listed use Horizon
+    #[serde(default)]
+    pub ledger_sources: BTreeMap<String, LedgerSource>,
+    /// Most validators published as known peers by peer discovery; quorum
+    /// members and Ready validators are kept first. Unlimited when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_known_peers: Option<usize>,
+    /// Where Prometheus scrapes node metrics
+    #[serde(default)]
+    pub metrics_endpoint: MetricsEndpointConfig,
+    /// Prometheus Operator resource generated to scrape node metrics
+    #[serde(default)]
+    pub metrics_monitor: MetricsMonitorKind,
+    /// TLS used by Prometheus when scraping node metrics
+    #[serde(default)]
+    pub metrics_tls: MetricsTlsConfig,
+}
+
+/// Publishing of the Grafana dashboard ConfigMap
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GrafanaDashboardConfig {
+    /// Create the dashboard ConfigMap on startup
+    #[serde(default)]
+    pub enabled: bool,
+    /// Namespace the Grafana sidecar watches; defaults to the operator's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+/// Source of a network's latest ledger for ingestion lag.
+///
+/// ```yaml
+/// ledgerSources:
+///   mainnet: horizon
+///   private-net:
+///     core: http://core.private-net:11626
+///   lab-net:
+///     custom: https://ledger.lab-net.example.com/latest
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LedgerSource {
+    /// Horizon root (`history_latest_ledger`), see `horizonUrls`
+    #[default]
+    Horizon,
+    /// Base URL of a stellar-core HTTP endpoint, queried at `/info`
+    Core(String),
+    /// URL answering with a Horizon root or a stellar-core `/info` document
+    Custom(String),
+}
+
+/// Prometheus Operator resource that scrapes Horizon and Soroban RPC nodes
+///
+/// ```yaml
+/// metricsMonitor: podMonitor
+/// ```
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MetricsMonitorKind {
+    /// A ServiceMonitor scraping the node's Service
+    #[default]
+    ServiceMonitor,
+    /// A PodMonitor scraping the node's pods directly
+    PodMonitor,
+}
+
+/// Metrics endpoint scraped by the generated ServiceMonitors or PodMonitors
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsEndpointConfig {
+    /// HTTP path of the metrics endpoint
+    #[serde(default = "default_metrics_path")]
+    pub path: String,
+    /// Named port to scrape, a Service port for a ServiceMonitor and a
+    /// container port for a PodMonitor; takes precedence over `targetPort`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    /// Container port to scrape when no named port is set
+    #[serde(default = "default_metrics_target_port")]
+    pub target_port: u16,
+}
+
+fn default_metrics_path() -> String {
+    "/metrics".to_string()
+}
+
+fn default_metrics_target_port() -> u16 {
+    8000
+}
+
+impl Default for MetricsEndpointConfig {
+    fn default() -> Self {
+        Self {
+            path: default_metrics_path(),
+            port: None,
+            target_port: default_metrics_target_port(),
+        }
+    }
+}
+
+/// How Prometheus reaches the metrics endpoints of managed nodes.
+///
+/// Independent of peer mTLS (`--enable-mtls`): nodes can serve metrics over
+/// TLS without mTLS between peers, and the other way round.
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsTlsConfig {
+    /// Scrape over https instead of http
+    #[serde(default)]
+    pub enabled: bool,
+    /// Secret in the node's namespace whose `ca.crt` signs the metrics
+    /// certificate; the system trust store is used when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_secret: Option<String>,
+    /// Name to verify the serving certificate against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+    /// Skip certificate verification
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -158,6 +289,11 @@ pub struct ReconcilerConfig {
     /// Enable jitter for backoff calculations
     #[serde(default = "default_enable_jitter")]
     pub enable_jitter: bool,
+
+    /// Most StellarNodes reconciled at once, read-only pools included; `0`
+    /// removes the bound
+    #[serde(default = "default_concurrency")]
+    pub concurrency: u16,
 }
 
 fn default_requeue_interval() -> u64 {
@@ -176,6 +312,10 @@ fn default_enable_jitter() -> bool {
     true
 }
 
+fn default_concurrency() -> u16 {
+    16
+}
+
 impl Default for ReconcilerConfig {
     fn default() -> Self {
         Self {
@@ -183,11 +323,17 @@ impl Default for ReconcilerConfig {
             error_backoff_base: default_error_backoff_base(),
             max_backoff: default_max_backoff(),
             enable_jitter: default_enable_jitter(),
+            concurrency: default_concurrency(),
         }
     }
 }
 
 impl ReconcilerConfig {
+    /// Controller settings bounding how many reconciles run concurrently
+    pub fn controller_config(&self) -> kube::runtime::controller::Config {
+        kube::runtime::controller::Config::default().concurrency(self.concurrency)
+    }
+
     /// Calculate exponential backoff with optional jitter
     ///
     /// # Arguments
@@ -407,6 +553,25 @@ defaultResources:
         assert_eq!(config.error_backoff_base, 15);
         assert_eq!(config.max_backoff, 300);
         assert!(config.enable_jitter);
+        assert_eq!(config.concurrency, 16);
+    }
+
+    #[test]
+    fn test_reconciler_concurrency_config() {
+        let cfg: OperatorConfig = serde_yaml::from_str("reconciler:\n  concurrency: 4\n").unwrap();
+        assert_eq!(cfg.reconciler.concurrency, 4);
+        // Other reconciler settings keep their defaults
+        assert_eq!(cfg.reconciler.requeue_interval, 60);
+
+        let unbounded: OperatorConfig =
+            serde_yaml::from_str("reconciler:\n  concurrency: 0\n").unwrap();
+        assert_eq!(unbounded.reconciler.concurrency, 0);
+
+        let defaults: OperatorConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(defaults.reconciler.concurrency, 16);
+
+        // Builds the controller config without panicking
+        let _ = cfg.reconciler.controller_config();
     }
 
     #[test]
@@ -416,6 +581,7 @@ defaultResources:
             error_backoff_base: 10,
             max_backoff: 300,
             enable_jitter: false,
+            ..Default::default()
         };
 
         // Test exponential growth: base * 2^retry_count
@@ -433,6 +599,7 @@ defaultResources:
             error_backoff_base: 10,
             max_backoff: 100,
             enable_jitter: false,
+            ..Default::default()
         };
 
         // Should cap at max_backoff
@@ -447,6 +614,7 @@ defaultResources:
             error_backoff_base: 10,
             max_backoff: 300,
             enable_jitter: true,
+            ..Default::default()
         };
 
         // With jitter, result should be between 0.5x and 1.5x of base calculation
@@ -464,6 +632,7 @@ defaultResources:
             error_backoff_base: u64::MAX / 2,
             max_backoff: 300,
             enable_jitter: false,
+            ..Default::default()
         };
 
         // Should handle overflow gracefully and cap at max_backoff
@@ -598,4 +767,101 @@ reconciler:
             );
         }
     }
+
+    #[test]
+    fn test_metrics_tls_parsed_independently() {
+        let yaml = r#"
+metricsTls:
+  enabled: true
+  caSecret: metrics-ca
+"#;
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(yaml.as_bytes()).unwrap();
+        let cfg = OperatorConfig::load_from_file(f.path().to_str().unwrap());
+        assert!(cfg.metrics_tls.enabled);
+        assert_eq!(cfg.metrics_tls.ca_secret.as_deref(), Some("metrics-ca"));
+        assert!(!cfg.metrics_tls.insecure_skip_verify);
+        assert!(!OperatorConfig::default().metrics_tls.enabled);
+    }
+
+    #[test]
+    fn test_metrics_monitor_kind() {
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(b"metricsMonitor: podMonitor\n").unwrap();
+        let cfg = OperatorConfig::load_from_file(f.path().to_str().unwrap());
+        assert_eq!(cfg.metrics_monitor, MetricsMonitorKind::PodMonitor);
+        assert_eq!(
+            OperatorConfig::default().metrics_monitor,
+            MetricsMonitorKind::ServiceMonitor
+        );
+    }
+
+    #[test]
+    fn test_metrics_endpoint_defaults() {
+        let yaml = r#"
+metricsEndpoint:
+  port: exporter
+"#;
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(yaml.as_bytes()).unwrap();
+        let cfg = OperatorConfig::load_from_file(f.path().to_str().unwrap());
+        assert_eq!(cfg.metrics_endpoint.port.as_deref(), Some("exporter"));
+        assert_eq!(cfg.metrics_endpoint.path, "/metrics");
+        assert_eq!(cfg.metrics_endpoint.target_port, 8000);
+        assert_eq!(
+            OperatorConfig::default().metrics_endpoint,
+            MetricsEndpointConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_horizon_url_overrides() {
+        let yaml = r#"
+horizonUrls:
+  mainnet: http://horizon-proxy.infra:8000
+  private-net: http://horizon.private
+"#;
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(yaml.as_bytes()).unwrap();
+        let cfg = OperatorConfig::load_from_file(f.path().to_str().unwrap());
+        assert_eq!(
+            cfg.horizon_urls.get("mainnet").map(String::as_str),
+            Some("http://horizon-proxy.infra:8000")
+        );
+        assert_eq!(cfg.horizon_urls.len(), 2);
+        assert!(OperatorConfig::default().horizon_urls.is_empty());
+    }
+
+    #[test]
+    fn test_ledger_sources() {
+        let yaml = r#"
+ledgerSources:
+  mainnet: horizon
+  private-net:
+    core: http://core.private-net:11626
+  lab-net:
+    custom: https://ledger.lab-net.example.com/latest
+"#;
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(yaml.as_bytes()).unwrap();
+        let cfg = OperatorConfig::load_from_file(f.path().to_str().unwrap());
+        assert_eq!(cfg.ledger_sources["mainnet"], LedgerSource::Horizon);
+        assert_eq!(
+            cfg.ledger_sources["private-net"],
+            LedgerSource::Core("http://core.private-net:11626".to_string())
+        );
+        assert_eq!(
+            cfg.ledger_sources["lab-net"],
+            LedgerSource::Custom("https://ledger.lab-net.example.com/latest".to_string())
+        );
+    }
+
+    #[test]
+    fn test_max_known_peers() {
+        let mut f = tempfile::NamedTempFile::new().unwrap();
+        f.write_all(b"maxKnownPeers: 24\n").unwrap();
+        let cfg = OperatorConfig::load_from_file(f.path().to_str().unwrap());
+        assert_eq!(cfg.max_known_peers, Some(24));
+        assert_eq!(OperatorConfig::default().max_known_peers, None);
+    }
 }
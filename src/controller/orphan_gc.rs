@@ -0,0 +1,280 @@
+//! Orphaned resource garbage collection
+//!
+//! Child resources normally carry an owner reference to their StellarNode and
+//! are removed by the Kubernetes garbage collector. If a StellarNode is
+//! force-deleted (finalizer stripped) and some children were created without a
+//! proper owner reference, they are left behind.
+//!
+//! This worker periodically lists resources labelled
+//! `app.kubernetes.io/managed-by=stellar-operator` and deletes the ones whose
+//! `app.kubernetes.io/instance` no longer matches a StellarNode in the same
+//! namespace. Resources that still reference a StellarNode owner are left to
+//! the Kubernetes garbage collector. PersistentVolumeClaims are never swept so
+//! that ledger data survives until an operator deletes it explicitly.
+
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::core::v1::{ConfigMap, Service};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::NamespaceResourceScope;
+use kube::api::{Api, DeleteParams, ListParams};
+use kube::{Client, Resource, ResourceExt};
+use serde::de::DeserializeOwned;
+use tracing::{debug, info, warn};
+
+use crate::crd::StellarNode;
+use crate::error::Result;
+
+/// How often the sweep runs
+const SWEEP_INTERVAL_SECS: u64 = 600;
+
+/// Selector for resources created for a StellarNode by this operator
+pub const MANAGED_RESOURCE_SELECTOR: &str =
+    "app.kubernetes.io/managed-by=stellar-operator,app.kubernetes.io/name=stellar-node";
+
+/// Label naming the StellarNode a managed resource belongs to
+const INSTANCE_LABEL: &str = "app.kubernetes.io/instance";
+
+/// A managed resource whose StellarNode no longer exists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanedResource {
+    pub kind: String,
+    pub namespace: String,
+    pub name: String,
+    /// Name of the missing StellarNode
+    pub instance: String,
+}
+
+/// Find managed resources whose owning StellarNode is missing.
+///
+/// `live_nodes` holds `(namespace, name)` of every existing StellarNode.
+/// Resources already being deleted, without an instance label, or with a
+/// StellarNode owner reference are skipped.
+pub fn find_orphans(
+    kind: &str,
+    resources: &[ObjectMeta],
+    live_nodes: &BTreeSet<(String, String)>,
+) -> Vec<OrphanedResource> {
+    resources
+        .iter()
+        .filter(|meta| meta.deletion_timestamp.is_none())
+        .filter(|meta| {
+            !meta
+                .owner_references
+                .iter()
+                .flatten()
+                .any(|r| r.kind == StellarNode::kind(&()))
+        })
+        .filter_map(|meta| {
+            let namespace = meta.namespace.clone()?;
+            let instance = meta.labels.as_ref()?.get(INSTANCE_LABEL)?.clone();
+            if live_nodes.contains(&(namespace.clone(), instance.clone())) {
+                return None;
+            }
+            Some(OrphanedResource {
+                kind: kind.to_string(),
+                namespace,
+                name: meta.name.clone()?,
+                instance,
+            })
+        })
+        .collect()
+}
+
+/// Run the orphan sweep loop.
+///
+/// Only the leader sweeps. With `dry_run` set, orphans are logged but kept.
+pub async fn run_orphan_gc_worker(
+    client: Client,
+    is_leader: Arc<AtomicBool>,
+    watch_namespace: Option<String>,
+    dry_run: bool,
+) {
+    info!(
+        "Orphan resource sweep started (interval: {}s)",
+        SWEEP_INTERVAL_SECS
+    );
+
+    let mut interval = tokio::time::interval(Duration::from_secs(SWEEP_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if !is_leader.load(Ordering::Relaxed) {
+            continue;
+        }
+        match sweep(&client, watch_namespace.as_deref(), dry_run).await {
+            Ok(0) => debug!("Orphan sweep found nothing to delete"),
+            Ok(n) => info!("Orphan sweep removed {} resource(s)", n),
+            Err(e) => warn!("Orphan sweep failed: {}", e),
+        }
+    }
+}
+
+/// One pass over all swept kinds, returning the number of orphans handled.
+async fn sweep(client: &Client, namespace: Option<&str>, dry_run: bool) -> Result<usize> {
+    // Snapshot resources before nodes: anything created after the node list
+    // was taken cannot be mistaken for an orphan.
+    let deployments = list_managed::<Deployment>(client, namespace).await?;
+    let statefulsets = list_managed::<StatefulSet>(client, namespace).await?;
+    let services = list_managed::<Service>(client, namespace).await?;
+    let configmaps = list_managed::<ConfigMap>(client, namespace).await?;
+
+    let nodes: Api<StellarNode> = match namespace {
+        Some(ns) => Api::namespaced(client.clone(), ns),
+        None => Api::all(client.clone()),
+    };
+    let live_nodes: BTreeSet<(String, String)> = nodes
+        .list(&ListParams::default())
+        .await?
+        .into_iter()
+        .map(|n| (n.namespace().unwrap_or_default(), n.name_any()))
+        .collect();
+
+    let mut handled = 0;
+    handled += delete_orphans::<Deployment>(client, &deployments, &live_nodes, dry_run).await;
+    handled += delete_orphans::<StatefulSet>(client, &statefulsets, &live_nodes, dry_run).await;
+    handled += delete_orphans::<Service>(client, &services, &live_nodes, dry_run).await;
+    handled += delete_orphans::<ConfigMap>(client, &configmaps, &live_nodes, dry_run).await;
+    Ok(handled)
+}
+
+async fn list_managed<K>(client: &Client, namespace: Option<&str>) -> Result<Vec<ObjectMeta>>
+where
+    K: Resource<Scope = NamespaceResourceScope, DynamicType = ()>
+        + Clone
+        + DeserializeOwned
+        + Debug,
+{
+    let api: Api<K> = match namespace {
+        Some(ns) => Api::namespaced(client.clone(), ns),
+        None => Api::all(client.clone()),
+    };
+    let list = api
+        .list_metadata(&ListParams::default().labels(MANAGED_RESOURCE_SELECTOR))
+        .await?;
+    Ok(list.items.into_iter().map(|m| m.metadata).collect())
+}
+
+async fn delete_orphans<K>(
+    client: &Client,
+    resources: &[ObjectMeta],
+    live_nodes: &BTreeSet<(String, String)>,
+    dry_run: bool,
+) -> usize
+where
+    K: Resource<Scope = NamespaceResourceScope, DynamicType = ()>
+        + Clone
+        + DeserializeOwned
+        + Debug,
+{
+    let orphans = find_orphans(&K::kind(&()), resources, live_nodes);
+    for orphan in &orphans {
+        if dry_run {
+            info!(
+                "[dry-run] Would delete orphaned {} {}/{} (StellarNode {} not found)",
+                orphan.kind, orphan.namespace, orphan.name, orphan.instance
+            );
+            continue;
+        }
+        let api: Api<K> = Api::namespaced(client.clone(), &orphan.namespace);
+        match api.delete(&orphan.name, &DeleteParams::background()).await {
+            Ok(_) => info!(
+                "Deleted orphaned {} {}/{} (StellarNode {} not found)",
+                orphan.kind, orphan.namespace, orphan.name, orphan.instance
+            ),
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => warn!(
+                "Failed to delete orphaned {} {}/{}: {}",
+                orphan.kind, orphan.namespace, orphan.name, e
+            ),
+        }
+    }
+    orphans.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{OwnerReference, Time};
+
+    fn managed(namespace: &str, name: &str, instance: Option<&str>) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some(namespace.to_string()),
+            labels: Some(
+                [(
+                    "app.kubernetes.io/managed-by".to_string(),
+                    "stellar-operator".to_string(),
+                )]
+                .into_iter()
+                .chain(instance.map(|i| (INSTANCE_LABEL.to_string(), i.to_string())))
+                .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn live(nodes: &[(&str, &str)]) -> BTreeSet<(String, String)> {
+        nodes
+            .iter()
+            .map(|(ns, n)| (ns.to_string(), n.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resource_of_missing_node_is_orphaned() {
+        let resources = vec![
+            managed("stellar", "validator-1", Some("validator-1")),
+            managed("stellar", "validator-2", Some("validator-2")),
+        ];
+        let orphans = find_orphans(
+            "Deployment",
+            &resources,
+            &live(&[("stellar", "validator-1")]),
+        );
+
+        assert_eq!(
+            orphans,
+            vec![OrphanedResource {
+                kind: "Deployment".to_string(),
+                namespace: "stellar".to_string(),
+                name: "validator-2".to_string(),
+                instance: "validator-2".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_same_name_in_other_namespace_is_orphaned() {
+        let resources = vec![managed("other", "validator-1", Some("validator-1"))];
+        let orphans = find_orphans("Service", &resources, &live(&[("stellar", "validator-1")]));
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].namespace, "other");
+    }
+
+    #[test]
+    fn test_resource_with_stellarnode_owner_is_left_to_gc() {
+        let mut meta = managed("stellar", "validator-2", Some("validator-2"));
+        meta.owner_references = Some(vec![OwnerReference {
+            api_version: "stellar.org/v1alpha1".to_string(),
+            kind: "StellarNode".to_string(),
+            name: "validator-2".to_string(),
+            uid: "abc".to_string(),
+            ..Default::default()
+        }]);
+        assert!(find_orphans("ConfigMap", &[meta], &live(&[])).is_empty());
+    }
+
+    #[test]
+    fn test_unlabelled_and_terminating_resources_are_skipped() {
+        let mut terminating = managed("stellar", "validator-3", Some("validator-3"));
+        terminating.deletion_timestamp = Some(Time(chrono::Utc::now()));
+        let resources = vec![managed("stellar", "shared", None), terminating];
+
+        assert!(find_orphans("StatefulSet", &resources, &live(&[])).is_empty());
+    }
+}
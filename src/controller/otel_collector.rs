@@ -0,0 +1,391 @@
+//! OpenTelemetry Collector sidecar of a node's pods
+//!
+//! With `spec.otelCollector.enabled`, every pod of the node gets an
+//! `otel-collector` sidecar receiving OTLP on localhost, and the main
+//! container is pointed at it through `OTEL_EXPORTER_OTLP_ENDPOINT`. The
+//! collector forwards traces to `spec.otelCollector.endpoint`.
+//!
+//! The collector configuration lives in a `<name>-otel-collector` ConfigMap.
+//! Before anything leaves the pod it redacts the span and resource
+//! attributes the operator scrubs from its own traces
+//! ([`SCRUBBED_SPAN_ATTRIBUTES`]). The endpoint reaches the collector as an
+//! environment variable, so changing it rolls the pods.
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{
+    Capabilities, ConfigMap, ConfigMapVolumeSource, Container, EnvVar, ResourceRequirements,
+    SeccompProfile, SecurityContext, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::api::{Api, Patch, PatchParams};
+use kube::{Client, ResourceExt};
+
+use super::resources::{delete_if_owned, owner_reference, resource_name, standard_labels};
+use crate::crd::{OtelCollectorConfig, StellarNode};
+use crate::error::Result;
+use crate::telemetry::SCRUBBED_SPAN_ATTRIBUTES;
+
+/// Name of the collector configuration volume in the pod
+pub const OTEL_CONFIG_VOLUME: &str = "otel-collector-config";
+
+/// Where the collector configuration is mounted
+pub const OTEL_CONFIG_MOUNT_PATH: &str = "/etc/otelcol";
+
+/// Key of the collector configuration in the ConfigMap
+pub const OTEL_CONFIG_KEY: &str = "config.yaml";
+
+/// OTLP endpoint the main container exports to
+pub const LOCAL_OTLP_ENDPOINT: &str = "http://localhost:4317";
+
+/// Collector settings of a node that has the sidecar enabled
+pub fn otel_collector_config(node: &StellarNode) -> Option<&OtelCollectorConfig> {
+    node.spec
+        .otel_collector
+        .as_ref()
+        .filter(|otel| otel.enabled)
+}
+
+/// Name of the collector ConfigMap
+pub fn otel_config_map_name(node: &StellarNode) -> String {
+    resource_name(node, "otel-collector")
+}
+
+/// Collector configuration: OTLP in on localhost, scrubbed, OTLP out to the
+/// endpoint in `OTLP_ENDPOINT`
+pub fn collector_config() -> String {
+    let scrub: String = SCRUBBED_SPAN_ATTRIBUTES
+        .iter()
+        .map(|key| {
+            format!("      - key: {key}\n        action: update\n        value: \"[REDACTED]\"\n")
+        })
+        .collect();
+    format!(
+        r#"receivers:
+  otlp:
+    protocols:
+      grpc:
+        endpoint: 127.0.0.1:4317
+      http:
+        endpoint: 127.0.0.1:4318
+processors:
+  memory_limiter:
+    check_interval: 1s
+    limit_mib: 100
+  resource/scrub:
+    attributes:
+{scrub}  attributes/scrub:
+    actions:
+{scrub}  batch: {{}}
+exporters:
+  otlp:
+    endpoint: ${{env:OTLP_ENDPOINT}}
+    tls:
+      insecure: ${{env:OTLP_INSECURE}}
+service:
+  telemetry:
+    metrics:
+      level: none
+  pipelines:
+    traces:
+      receivers: [otlp]
+      processors: [memory_limiter, resource/scrub, attributes/scrub, batch]
+      exporters: [otlp]
+"#
+    )
+}
+
+fn build_config_map(node: &StellarNode) -> ConfigMap {
+    ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(otel_config_map_name(node)),
+            namespace: node.namespace(),
+            labels: Some(standard_labels(node)),
+            owner_references: Some(vec![owner_reference(node)]),
+            ..Default::default()
+        },
+        data: Some(BTreeMap::from([(
+            OTEL_CONFIG_KEY.to_string(),
+            collector_config(),
+        )])),
+        ..Default::default()
+    }
+}
+
+/// Pod volume holding the collector configuration
+pub fn otel_config_volume(node: &StellarNode) -> Volume {
+    Volume {
+        name: OTEL_CONFIG_VOLUME.to_string(),
+        config_map: Some(ConfigMapVolumeSource {
+            name: Some(otel_config_map_name(node)),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` of the main container, pointing at the sidecar
+pub fn local_otlp_env() -> EnvVar {
+    EnvVar {
+        name: "OTEL_EXPORTER_OTLP_ENDPOINT".to_string(),
+        value: Some(LOCAL_OTLP_ENDPOINT.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Collector sidecar forwarding to the configured endpoint
+pub fn build_otel_collector_sidecar(config: &OtelCollectorConfig) -> Container {
+    let env = |name: &str, value: String| EnvVar {
+        name: name.to_string(),
+        value: Some(value),
+        ..Default::default()
+    };
+    let quantities = |cpu: &str, memory: &str| {
+        BTreeMap::from([
+            ("cpu".to_string(), Quantity(cpu.to_string())),
+            ("memory".to_string(), Quantity(memory.to_string())),
+        ])
+    };
+
+    Container {
+        name: "otel-collector".to_string(),
+        image: Some(config.image.clone()),
+        args: Some(vec![format!(
+            "--config={OTEL_CONFIG_MOUNT_PATH}/{OTEL_CONFIG_KEY}"
+        )]),
+        env: Some(vec![
+            env("OTLP_ENDPOINT", config.endpoint.clone()),
+            env("OTLP_INSECURE", config.insecure.to_string()),
+        ]),
+        volume_mounts: Some(vec![VolumeMount {
+            name: OTEL_CONFIG_VOLUME.to_string(),
+            mount_path: OTEL_CONFIG_MOUNT_PATH.to_string(),
+            read_only: Some(true),
+            ..Default::default()
+        }]),
+        resources: Some(ResourceRequirements {
+            requests: Some(quantities("50m", "64Mi")),
+            limits: Some(quantities("200m", "128Mi")),
+            ..Default::default()
+        }),
+        security_context: Some(SecurityContext {
+            allow_privilege_escalation: Some(false),
+            capabilities: Some(Capabilities {
+                drop: Some(vec!["ALL".to_string()]),
+                add: None,
+            }),
+            run_as_non_root: Some(true),
+            read_only_root_filesystem: Some(true),
+            privileged: Some(false),
+            seccomp_profile: Some(SeccompProfile {
+                type_: "RuntimeDefault".to_string(),
+                localhost_profile: None,
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Create or update the collector ConfigMap of a node with the sidecar
+/// enabled, and remove it once the sidecar is turned off.
+pub async fn ensure_otel_collector(
+    client: &Client,
+    node: &StellarNode,
+    dry_run: bool,
+) -> Result<()> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = otel_config_map_name(node);
+    let api: Api<ConfigMap> = Api::namespaced(client.clone(), &namespace);
+
+    if otel_collector_config(node).is_none() {
+        delete_if_owned(&api, "OTel Collector ConfigMap", &name, node, dry_run).await?;
+        return Ok(());
+    }
+
+    let mut params = PatchParams::apply("stellar-operator").force();
+    params.dry_run = dry_run;
+    api.patch(&name, &params, &Patch::Apply(&build_config_map(node)))
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::{NodeType, StellarNodeSpec, DEFAULT_OTEL_COLLECTOR_IMAGE};
+
+    fn otel_config() -> OtelCollectorConfig {
+        OtelCollectorConfig {
+            enabled: true,
+            endpoint: "otel-gateway.observability.svc:4317".to_string(),
+            insecure: true,
+            image: DEFAULT_OTEL_COLLECTOR_IMAGE.to_string(),
+        }
+    }
+
+    fn node(node_type: NodeType, otel_collector: Option<OtelCollectorConfig>) -> StellarNode {
+        let mut node = StellarNode::new(
+            "node-1",
+            StellarNodeSpec {
+                node_type,
+                otel_collector,
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.uid = Some("uid-1".to_string());
+        node
+    }
+
+    #[test]
+    fn test_sidecar_forwards_to_endpoint() {
+        let sidecar = build_otel_collector_sidecar(&otel_config());
+        assert_eq!(sidecar.name, "otel-collector");
+        assert_eq!(sidecar.image.as_deref(), Some(DEFAULT_OTEL_COLLECTOR_IMAGE));
+        assert_eq!(
+            sidecar.args,
+            Some(vec!["--config=/etc/otelcol/config.yaml".to_string()])
+        );
+        let env: BTreeMap<_, _> = sidecar
+            .env
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.name, e.value.unwrap_or_default()))
+            .collect();
+        assert_eq!(env["OTLP_ENDPOINT"], "otel-gateway.observability.svc:4317");
+        assert_eq!(env["OTLP_INSECURE"], "true");
+
+        let mount = &sidecar.volume_mounts.unwrap()[0];
+        assert_eq!(mount.name, OTEL_CONFIG_VOLUME);
+        assert_eq!(mount.mount_path, OTEL_CONFIG_MOUNT_PATH);
+        assert_eq!(mount.read_only, Some(true));
+        let security = sidecar.security_context.unwrap();
+        assert_eq!(security.run_as_non_root, Some(true));
+        assert_eq!(security.read_only_root_filesystem, Some(true));
+    }
+
+    #[test]
+    fn test_config_volume_and_map() {
+        let node = node(NodeType::Horizon, Some(otel_config()));
+        let volume = otel_config_volume(&node);
+        assert_eq!(volume.name, OTEL_CONFIG_VOLUME);
+        assert_eq!(
+            volume.config_map.unwrap().name.as_deref(),
+            Some("node-1-otel-collector")
+        );
+
+        let cm = build_config_map(&node);
+        assert_eq!(cm.metadata.name.as_deref(), Some("node-1-otel-collector"));
+        assert_eq!(cm.metadata.owner_references.unwrap().len(), 1);
+        let config: serde_yaml::Value =
+            serde_yaml::from_str(&cm.data.unwrap()[OTEL_CONFIG_KEY]).unwrap();
+        assert_eq!(
+            config["receivers"]["otlp"]["protocols"]["grpc"]["endpoint"],
+            "127.0.0.1:4317"
+        );
+        assert_eq!(
+            config["exporters"]["otlp"]["endpoint"],
+            "${env:OTLP_ENDPOINT}"
+        );
+    }
+
+    #[test]
+    fn test_config_scrubs_operator_attributes() {
+        let config: serde_yaml::Value = serde_yaml::from_str(&collector_config()).unwrap();
+        for processor in ["attributes/scrub", "resource/scrub"] {
+            let list = if processor == "attributes/scrub" {
+                "actions"
+            } else {
+                "attributes"
+            };
+            let keys: Vec<&str> = config["processors"][processor][list]
+                .as_sequence()
+                .unwrap()
+                .iter()
+                .inspect(|action| {
+                    assert_eq!(action["action"], "update");
+                    assert_eq!(action["value"], "[REDACTED]");
+                })
+                .filter_map(|action| action["key"].as_str())
+                .collect();
+            assert_eq!(keys, SCRUBBED_SPAN_ATTRIBUTES, "{processor}");
+        }
+
+        let processors = &config["service"]["pipelines"]["traces"]["processors"];
+        let processors: Vec<&str> = processors
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter_map(|p| p.as_str())
+            .collect();
+        assert!(processors.contains(&"attributes/scrub"));
+        assert!(processors.contains(&"resource/scrub"));
+        assert_eq!(processors.last(), Some(&"batch"));
+    }
+
+    #[test]
+    fn test_only_enabled_collectors_are_injected() {
+        assert!(otel_collector_config(&node(NodeType::Validator, Some(otel_config()))).is_some());
+        assert!(otel_collector_config(&node(NodeType::Validator, None)).is_none());
+        let disabled = OtelCollectorConfig {
+            enabled: false,
+            ..otel_config()
+        };
+        assert!(otel_collector_config(&node(NodeType::Horizon, Some(disabled))).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_applies_then_removes_config_map() {
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let api: Api<ConfigMap> = Api::namespaced(client.clone(), "stellar");
+
+        let enabled = node(NodeType::SorobanRpc, Some(otel_config()));
+        ensure_otel_collector(&client, &enabled, false)
+            .await
+            .unwrap();
+        let cm = api.get("node-1-otel-collector").await.unwrap();
+        assert!(cm.data.unwrap()[OTEL_CONFIG_KEY].contains("attributes/scrub"));
+
+        ensure_otel_collector(&client, &node(NodeType::SorobanRpc, None), false)
+            .await
+            .unwrap();
+        assert!(api
+            .get_opt("node-1-otel-collector")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_collector_keeps_foreign_config_map() {
+        let (client, server) = fake_client();
+        let requests = server.serve();
+        let api: Api<ConfigMap> = Api::namespaced(client.clone(), "stellar");
+        let foreign = ConfigMap {
+            metadata: ObjectMeta {
+                name: Some("node-1-otel-collector".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        api.create(&Default::default(), &foreign).await.unwrap();
+
+        ensure_otel_collector(&client, &node(NodeType::SorobanRpc, None), false)
+            .await
+            .unwrap();
+        assert!(api
+            .get_opt("node-1-otel-collector")
+            .await
+            .unwrap()
+            .is_some());
+        assert!(!requests
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|r| r.method == http::Method::DELETE));
+    }
+}
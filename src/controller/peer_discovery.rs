@@ -1,8 +1,8 @@
 //! Dynamic peer discovery for Stellar nodes
 //!
 //! Watches all StellarNode resources in the cluster and maintains a shared ConfigMap
-//! with the latest peer IPs and ports. Automatically triggers configuration reload
-//! when peers change.
+//! with the latest peer IPs and ports. Automatically connects validators to the
+//! new peers when they change.
 //!
 //! ## Implementation
 //!
@@ -10,8 +10,20 @@
 //! - Polls all StellarNode resources every 30 seconds
 //! - Filters for Validator nodes only
 //! - Extracts peer information (IP, port, namespace, name)
-//! - Updates shared ConfigMap when peer list changes
-//! - Triggers config reload on healthy validators
+//! - Keeps the best `max_peers` validators when a cap is configured
+//! - Updates shared ConfigMap when peer list changes, listing quorum members
+//!   as `PREFERRED_PEERS` and the other validators as `KNOWN_PEERS`
+//! - Connects healthy validators to the updated peers
+//!
+//! ## Applying peer changes
+//!
+//! Each validator's StatefulSet records the hash of the peer list it last
+//! picked up in the `stellar.org/peers-hash` annotation. When the shared list
+//! changes, the operator asks every pod to connect to each peer through the
+//! stellar-core `connect` admin command, so peer updates don't restart the
+//! node; the mounted peer list takes effect at the next restart. Nodes
+//! annotated `stellar.org/config-reload: "false"`, and nodes where a pod
+//! rejects the command, fall back to a rolling restart of the StatefulSet.
 
 use std::collections::{BTreeMap, HashSet};
 use std::net::IpAddr;
@@ -93,6 +105,7 @@ impl DnsResolver for TokioDnsResolver {
     }
 }
 
+use k8s_openapi::api::apps::v1::StatefulSet;
 use k8s_openapi::api::core::v1::{ConfigMap, Service};
 use kube::{
     api::{Api, ListParams, Patch, PatchParams},
@@ -100,8 +113,10 @@ use kube::{
     ResourceExt,
 };
 use serde_json::json;
+use sha2::{Digest, Sha256};
 use tracing::{debug, error, info, instrument, warn};
 
+use super::pod_restart::restart_patch;
 use crate::crd::{NodeType, StellarNode};
 use crate::error::{Error, Result};
 
@@ -134,6 +149,156 @@ impl PeerInfo {
     }
 }
 
+/// What makes a discovered peer worth keeping when the peer list is capped
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PeerRank {
+    /// The validator takes part in consensus (has a quorum set)
+    pub in_quorum: bool,
+    /// The validator is Ready, i.e. in sync with the network
+    pub ready: bool,
+}
+
+impl PeerRank {
+    /// Rank of a validator
+    pub fn for_node(node: &StellarNode) -> Self {
+        Self {
+            in_quorum: node
+                .spec
+                .validator_config
+                .as_ref()
+                .is_some_and(|v| v.quorum_set.is_some()),
+            ready: node.status.as_ref().is_some_and(|s| s.is_ready()),
+        }
+    }
+}
+
+/// Order peers best first (quorum members, then ready ones, ties broken by
+/// namespace and name so the order does not change between polls) and keep
+/// the `max` best.
+fn rank_peers(
+    mut candidates: Vec<(PeerInfo, PeerRank)>,
+    max: Option<usize>,
+) -> Vec<(PeerInfo, PeerRank)> {
+    candidates.sort_by(|(a, a_rank), (b, b_rank)| {
+        b_rank
+            .in_quorum
+            .cmp(&a_rank.in_quorum)
+            .then(b_rank.ready.cmp(&a_rank.ready))
+            .then_with(|| a.namespace.cmp(&b.namespace))
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.ip.cmp(&b.ip))
+    });
+    candidates.dedup_by(|(a, _), (b, _)| a == b);
+    candidates.truncate(max.unwrap_or(candidates.len()));
+    candidates
+}
+
+/// Keep the `max` best peers: quorum members first, then ready ones.
+pub fn select_peers(
+    candidates: Vec<(PeerInfo, PeerRank)>,
+    max: Option<usize>,
+) -> HashSet<PeerInfo> {
+    rank_peers(candidates, max)
+        .into_iter()
+        .map(|(peer, _)| peer)
+        .collect()
+}
+
+/// Peers published by discovery, split the way stellar-core configures them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PeerDiscoveryResult {
+    /// Quorum members, rendered as `PREFERRED_PEERS`
+    pub preferred: Vec<PeerInfo>,
+    /// Other validators, rendered as `KNOWN_PEERS`
+    pub known: Vec<PeerInfo>,
+}
+
+impl PeerDiscoveryResult {
+    /// Keep the `max` best candidates and split them into preferred and known
+    /// peers, best first.
+    pub fn from_candidates(candidates: Vec<(PeerInfo, PeerRank)>, max: Option<usize>) -> Self {
+        let mut result = Self::default();
+        for (peer, rank) in rank_peers(candidates, max) {
+            if rank.in_quorum {
+                result.preferred.push(peer);
+            } else {
+                result.known.push(peer);
+            }
+        }
+        result
+    }
+
+    /// Number of peers in both lists
+    pub fn len(&self) -> usize {
+        self.preferred.len() + self.known.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// stellar-core config fragment with both peer lists
+    pub fn to_toml(&self) -> String {
+        let list = |peers: &[PeerInfo]| {
+            peers
+                .iter()
+                .map(|p| format!("\"{}\"", p.to_peer_string()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!(
+            "PREFERRED_PEERS = [{}]\nKNOWN_PEERS = [{}]\n",
+            list(&self.preferred),
+            list(&self.known)
+        )
+    }
+
+    /// Data of the shared peers ConfigMap
+    pub fn config_map_data(&self) -> BTreeMap<String, String> {
+        let mut data = BTreeMap::new();
+
+        // Add peers as JSON array, flagging the preferred ones
+        let peers_json: Vec<serde_json::Value> = self
+            .preferred
+            .iter()
+            .map(|p| (p, true))
+            .chain(self.known.iter().map(|p| (p, false)))
+            .map(|(p, preferred)| {
+                let mut json = p.to_json();
+                json["preferred"] = json!(preferred);
+                json
+            })
+            .collect();
+        data.insert(
+            "peers.json".to_string(),
+            serde_json::to_string_pretty(&peers_json).unwrap_or_else(|_| "[]".to_string()),
+        );
+
+        // Add peers as simple lists (ip:port format)
+        let peer_strings =
+            |peers: &[PeerInfo]| peers.iter().map(|p| p.to_peer_string()).collect::<Vec<_>>();
+        let mut all = peer_strings(&self.preferred);
+        all.extend(peer_strings(&self.known));
+        data.insert("peers.txt".to_string(), all.join("\n"));
+        data.insert(
+            "preferred_peers.txt".to_string(),
+            peer_strings(&self.preferred).join("\n"),
+        );
+        data.insert(
+            "known_peers.txt".to_string(),
+            peer_strings(&self.known).join("\n"),
+        );
+
+        // Add both lists as a stellar-core config fragment
+        data.insert("peers.toml".to_string(), self.to_toml());
+
+        // Add peer count
+        data.insert("peer_count".to_string(), self.len().to_string());
+
+        data
+    }
+}
+
 /// Configuration for peer discovery
 #[derive(Clone, Debug)]
 pub struct PeerDiscoveryConfig {
@@ -143,6 +308,8 @@ pub struct PeerDiscoveryConfig {
     pub config_map_name: String,
     /// Port used by Stellar Core for peer connections
     pub peer_port: u16,
+    /// Most peers published in the shared ConfigMap; all validators when unset
+    pub max_peers: Option<usize>,
 }
 
 impl Default for PeerDiscoveryConfig {
@@ -151,6 +318,7 @@ impl Default for PeerDiscoveryConfig {
             config_namespace: "stellar-system".to_string(),
             config_map_name: "stellar-peers".to_string(),
             peer_port: 11625,
+            max_peers: None,
         }
     }
 }
@@ -175,20 +343,31 @@ impl PeerDiscoveryManager {
         );
 
         let stellar_nodes: Api<StellarNode> = Api::all(self.client.clone());
-        let mut last_peers: HashSet<PeerInfo> = HashSet::new();
+        let mut last_peers = PeerDiscoveryResult::default();
 
         loop {
             // Poll for nodes
             match stellar_nodes.list(&Default::default()).await {
                 Ok(nodes) => {
-                    let mut current_peers = HashSet::new();
+                    let mut candidates = Vec::new();
 
                     for node in nodes.items {
-                        if let Err(e) = self.process_node_event(&node, &mut current_peers).await {
+                        if let Err(e) = self.process_node_event(&node, &mut candidates).await {
                             debug!("Error processing node {}: {}", node.name_any(), e);
                         }
                     }
 
+                    let discovered = candidates.len();
+                    let current_peers =
+                        PeerDiscoveryResult::from_candidates(candidates, self.config.max_peers);
+                    if current_peers.len() < discovered {
+                        debug!(
+                            "Capped peer list at {} of {} discovered validators",
+                            current_peers.len(),
+                            discovered
+                        );
+                    }
+
                     // Check if peers changed
                     if current_peers != last_peers {
                         info!(
@@ -216,21 +395,15 @@ impl PeerDiscoveryManager {
     async fn process_node_event(
         &self,
         node: &StellarNode,
-        current_peers: &mut HashSet<PeerInfo>,
+        candidates: &mut Vec<(PeerInfo, PeerRank)>,
     ) -> Result<()> {
-        // Only include validators in peer discovery
-        if node.spec.node_type != NodeType::Validator {
-            return Ok(());
-        }
-
-        // Skip suspended nodes
-        if node.spec.suspended {
+        if !is_peer_candidate(node) {
             return Ok(());
         }
 
         // Extract peer information
         if let Some(peer) = self.extract_peer_info(node).await? {
-            current_peers.insert(peer);
+            candidates.push((peer, PeerRank::for_node(node)));
         }
 
         Ok(())
@@ -297,25 +470,11 @@ impl PeerDiscoveryManager {
 
     /// Update the shared peers ConfigMap with current peer list
     #[instrument(skip(self, peers))]
-    async fn update_peers_config_map(&self, peers: &HashSet<PeerInfo>) -> Result<()> {
+    async fn update_peers_config_map(&self, peers: &PeerDiscoveryResult) -> Result<()> {
         let api: Api<ConfigMap> =
             Api::namespaced(self.client.clone(), &self.config.config_namespace);
 
-        let mut data = BTreeMap::new();
-
-        // Add peers as JSON array
-        let peers_json: Vec<serde_json::Value> = peers.iter().map(|p| p.to_json()).collect();
-        data.insert(
-            "peers.json".to_string(),
-            serde_json::to_string_pretty(&peers_json).unwrap_or_else(|_| "[]".to_string()),
-        );
-
-        // Add peers as simple list (ip:port format)
-        let peers_list: Vec<String> = peers.iter().map(|p| p.to_peer_string()).collect();
-        data.insert("peers.txt".to_string(), peers_list.join("\n"));
-
-        // Add peer count
-        data.insert("peer_count".to_string(), peers.len().to_string());
+        let data = peers.config_map_data();
 
         let cm = ConfigMap {
             metadata: kube::api::ObjectMeta {
@@ -395,58 +554,286 @@ pub async fn get_peers_from_config_map(
     }
 }
 
-/// Trigger configuration reload for a specific node
-pub async fn trigger_peer_config_reload(client: &Client, node: &StellarNode) -> Result<()> {
-    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
-    let name = node.name_any();
+/// Node annotation that withdraws a validator from the shared peer list while
+/// it keeps running, so it can leave the network gracefully
+pub const DRAIN_ANNOTATION: &str = "stellar.org/drain";
 
-    // Get the pod to find its IP
-    let pods: Api<k8s_openapi::api::core::v1::Pod> = Api::namespaced(client.clone(), &namespace);
+/// Whether the node is drained for maintenance
+pub fn is_drained(node: &StellarNode) -> bool {
+    node.annotations()
+        .get(DRAIN_ANNOTATION)
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// Whether other validators should learn about the node: running validators
+/// that are neither suspended nor drained
+pub fn is_peer_candidate(node: &StellarNode) -> bool {
+    node.spec.node_type == NodeType::Validator && !node.spec.suspended && !is_drained(node)
+}
+
+/// StatefulSet annotation holding the hash of the peer list the node last picked up
+pub const PEERS_HASH_ANNOTATION: &str = "stellar.org/peers-hash";
+
+/// Peers hash recorded on a node whose peer list must be re-applied, e.g.
+/// after it resumes from suspension
+pub const STALE_PEERS_HASH: &str = "stale";
+
+/// Node annotation that disables config reload, forcing restarts on peer changes
+pub const CONFIG_RELOAD_ANNOTATION: &str = "stellar.org/config-reload";
+
+/// How a validator picks up a change of the shared peer list
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeerUpdateAction {
+    /// The node already runs with the current peers
+    Skip,
+    /// First time the node is seen: remember the hash without touching the pods
+    Record,
+    /// Ask stellar-core to connect to the peers in place
+    Reload,
+    /// Roll the pods so they start with the new peers
+    RollingRestart,
+}
+
+/// Stable hash of a peer list, independent of its order
+pub fn peers_hash(peers: &[PeerInfo]) -> String {
+    let mut entries: Vec<String> = peers.iter().map(|p| p.to_peer_string()).collect();
+    entries.sort();
+    entries.dedup();
+    let digest = Sha256::digest(entries.join("\n").as_bytes());
+    digest.iter().take(8).map(|b| format!("{b:02x}")).collect()
+}
+
+/// Whether the node accepts in-place config reloads
+pub fn config_reload_supported(node: &StellarNode) -> bool {
+    !node
+        .annotations()
+        .get(CONFIG_RELOAD_ANNOTATION)
+        .is_some_and(|v| v.eq_ignore_ascii_case("false"))
+}
+
+/// Decide how a node picks up the peer list hashed as `current`, given the
+/// hash it last `applied`.
+pub fn decide_peer_update(
+    applied: Option<&str>,
+    current: &str,
+    reload_supported: bool,
+) -> PeerUpdateAction {
+    match applied {
+        None => PeerUpdateAction::Record,
+        Some(applied) if applied == current => PeerUpdateAction::Skip,
+        Some(_) if reload_supported => PeerUpdateAction::Reload,
+        Some(_) => PeerUpdateAction::RollingRestart,
+    }
+}
+
+/// Action to take once the reload has been attempted on every pod: record the
+/// new hash when all pods reloaded, restart otherwise
+pub fn after_reload(failed_pods: usize) -> PeerUpdateAction {
+    if failed_pods == 0 {
+        PeerUpdateAction::Record
+    } else {
+        PeerUpdateAction::RollingRestart
+    }
+}
+
+/// Merge patch recording `hash` on the StatefulSet, rolling the pods too when
+/// `restarted_at` is set
+fn peers_hash_patch(hash: &str, restarted_at: Option<&str>) -> serde_json::Value {
+    let mut patch = json!({
+        "metadata": {
+            "annotations": { PEERS_HASH_ANNOTATION: hash }
+        }
+    });
+    if let Some(restarted_at) = restarted_at {
+        patch["spec"] = restart_patch(restarted_at)["spec"].take();
+    }
+    patch
+}
+
+/// Ask every pod of the node to connect to `peers`, returning how many pods
+/// did not
+async fn connect_pods_to_peers(
+    client: &Client,
+    namespace: &str,
+    name: &str,
+    peers: &[PeerInfo],
+) -> usize {
+    let pods: Api<k8s_openapi::api::core::v1::Pod> = Api::namespaced(client.clone(), namespace);
 
     let label_selector = format!("app={name}");
     let params = ListParams::default().labels(&label_selector);
 
-    match pods.list(&params).await {
-        Ok(pod_list) => {
-            for pod in pod_list.items {
-                if let Some(status) = &pod.status {
-                    if let Some(pod_ip) = &status.pod_ip {
-                        debug!("Triggering config reload for pod at {}", pod_ip);
-                        if let Err(e) = trigger_config_reload_http(pod_ip).await {
-                            warn!("Failed to trigger config reload: {}", e);
-                        }
-                    }
-                }
-            }
-        }
+    let pod_list = match pods.list(&params).await {
+        Ok(pod_list) => pod_list,
         Err(e) => {
             warn!("Failed to list pods for {}/{}: {}", namespace, name, e);
+            return 1;
         }
+    };
+
+    let mut failed = 0;
+    for pod in pod_list.items {
+        let Some(pod_ip) = pod.status.as_ref().and_then(|s| s.pod_ip.as_ref()) else {
+            continue;
+        };
+        debug!("Connecting pod at {} to {} peer(s)", pod_ip, peers.len());
+        if let Err(e) = connect_peers_http(pod_ip, peers).await {
+            warn!("Failed to connect pod at {} to its peers: {}", pod_ip, e);
+            failed += 1;
+        }
+    }
+    failed
+}
+
+/// Peers a validator connects to: the shared list without the node itself,
+/// keeping the `max` best (the list is stored best first)
+pub fn peers_for_node(
+    peers: Vec<PeerInfo>,
+    node: &StellarNode,
+    max: Option<usize>,
+) -> Vec<PeerInfo> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+    let mut peers: Vec<PeerInfo> = peers
+        .into_iter()
+        .filter(|p| p.name != name || p.namespace != namespace)
+        .collect();
+    peers.truncate(max.unwrap_or(peers.len()));
+    peers
+}
+
+/// Read the shared peer list as the node applies it, see [`peers_for_node`]
+async fn get_node_peers(
+    client: &Client,
+    node: &StellarNode,
+    max_peers: Option<usize>,
+) -> Result<Vec<PeerInfo>> {
+    let peers = get_peers_from_config_map(client, &PeerDiscoveryConfig::default()).await?;
+    Ok(peers_for_node(peers, node, max_peers))
+}
+
+/// Make the next peer update of a validator re-apply the shared peer list,
+/// whether or not it changed while the node was paused: the pods come back
+/// with a fresh process that has to be connected to its peers again.
+pub async fn mark_peers_stale(client: &Client, node: &StellarNode, dry_run: bool) -> Result<()> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+    let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), &namespace);
+
+    if statefulsets.get_opt(&name).await?.is_none() {
+        return Ok(());
     }
 
+    let mut params = PatchParams::default();
+    params.dry_run = dry_run;
+    statefulsets
+        .patch(
+            &name,
+            &params,
+            &Patch::Merge(&peers_hash_patch(STALE_PEERS_HASH, None)),
+        )
+        .await?;
+    debug!("Marked peers of {}/{} for refresh", namespace, name);
     Ok(())
 }
 
-/// Trigger config reload via HTTP command
-async fn trigger_config_reload_http(pod_ip: &str) -> Result<()> {
-    let url = format!("http://{pod_ip}:11626/http-command?admin=true&command=config-reload");
+/// Apply a change of the shared peer list to a validator, connecting it to
+/// the peers in place or falling back to a rolling restart. The node itself
+/// is left out and at most `max_peers` are used, as in the published list.
+pub async fn trigger_peer_config_reload(
+    client: &Client,
+    node: &StellarNode,
+    max_peers: Option<usize>,
+    dry_run: bool,
+) -> Result<()> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
 
-    debug!("Triggering config-reload via {}", url);
+    let peers = get_node_peers(client, node, max_peers).await?;
+    let current = peers_hash(&peers);
+
+    let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), &namespace);
+    let Some(statefulset) = statefulsets.get_opt(&name).await? else {
+        return Ok(());
+    };
+    let applied = statefulset
+        .annotations()
+        .get(PEERS_HASH_ANNOTATION)
+        .cloned();
+
+    let restart = match decide_peer_update(
+        applied.as_deref(),
+        &current,
+        config_reload_supported(node),
+    ) {
+        PeerUpdateAction::Skip => return Ok(()),
+        PeerUpdateAction::Record => false,
+        PeerUpdateAction::Reload if dry_run => {
+            info!(
+                "Dry run: would connect {}/{} to the updated peers",
+                namespace, name
+            );
+            false
+        }
+        PeerUpdateAction::Reload => {
+            let failed = connect_pods_to_peers(client, &namespace, &name, &peers).await;
+            let restart = after_reload(failed) == PeerUpdateAction::RollingRestart;
+            if restart {
+                warn!(
+                    "Connecting to peers failed on {} pod(s) of {}/{}, falling back to a rolling restart",
+                    failed, namespace, name
+                );
+            }
+            restart
+        }
+        PeerUpdateAction::RollingRestart => true,
+    };
 
+    let restarted_at = restart.then(|| chrono::Utc::now().to_rfc3339());
+    if restart {
+        info!(
+            "Restarting {}/{} to pick up the updated peer list",
+            namespace, name
+        );
+    }
+    let patch = peers_hash_patch(&current, restarted_at.as_deref());
+    let mut params = PatchParams::default();
+    params.dry_run = dry_run;
+    statefulsets
+        .patch(&name, &params, &Patch::Merge(&patch))
+        .await?;
+    Ok(())
+}
+
+/// stellar-core admin URL asking the pod at `pod_ip` to connect to `peer`
+pub fn peer_connect_url(pod_ip: &str, peer: &PeerInfo) -> String {
+    format!(
+        "http://{pod_ip}:11626/connect?peer={}&port={}",
+        peer.ip, peer.port
+    )
+}
+
+/// Connect the pod at `pod_ip` to every peer through the `connect` admin command
+async fn connect_peers_http(pod_ip: &str, peers: &[PeerInfo]) -> Result<()> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(5))
         .build()
         .map_err(|e| Error::ConfigError(format!("Failed to build HTTP client: {e}")))?;
 
-    let response = client.get(&url).send().await.map_err(Error::HttpError)?;
-
-    if !response.status().is_success() {
-        return Err(Error::ConfigError(format!(
-            "Failed to trigger config-reload: status {}",
-            response.status()
-        )));
+    for peer in peers {
+        let url = peer_connect_url(pod_ip, peer);
+        debug!("Connecting to peer via {}", url);
+
+        let response = client.get(&url).send().await.map_err(Error::HttpError)?;
+        if !response.status().is_success() {
+            return Err(Error::ConfigError(format!(
+                "Failed to connect to peer {}: status {}",
+                peer.to_peer_string(),
+                response.status()
+            )));
+        }
     }
 
-    info!("Successfully triggered config-reload for pod at {}", pod_ip);
+    info!("Connected pod at {} to {} peer(s)", pod_ip, peers.len());
     Ok(())
 }
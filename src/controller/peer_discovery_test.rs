@@ -1,13 +1,19 @@
 //! Unit tests for peer discovery logic
 //!
 //! Covers: peer list building from StellarNode CRDs, DNS lookup mocking,
-//! peer scoring/selection, and edge cases (empty list, all-unreachable).
+//! peer scoring/selection, edge cases (empty list, all-unreachable), and the
+//! reload-vs-restart decision on peer changes.
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
-    use crate::controller::peer_discovery::{PeerDiscoveryConfig, PeerInfo};
+    use crate::controller::peer_discovery::{
+        after_reload, config_reload_supported, decide_peer_update, is_drained, is_peer_candidate,
+        mark_peers_stale, peer_connect_url, peers_for_node, peers_hash, select_peers,
+        PeerDiscoveryConfig, PeerDiscoveryResult, PeerInfo, PeerRank, PeerUpdateAction,
+        CONFIG_RELOAD_ANNOTATION, DRAIN_ANNOTATION, PEERS_HASH_ANNOTATION, STALE_PEERS_HASH,
+    };
     use crate::crd::NodeType;
 
     // -------------------------------------------------------------------------
@@ -374,6 +380,466 @@ mod tests {
         let peer_count = peers.len().to_string();
         assert_eq!(peer_count, "3");
     }
+
+    // -------------------------------------------------------------------------
+    // Reload vs restart on peer changes
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_peers_hash_ignores_order() {
+        let a = make_peer("validator-a", "stellar", "10.0.0.1", 11625);
+        let b = make_peer("validator-b", "stellar", "10.0.0.2", 11625);
+
+        assert_eq!(
+            peers_hash(&[a.clone(), b.clone()]),
+            peers_hash(&[b.clone(), a.clone()])
+        );
+        assert_ne!(peers_hash(&[a.clone()]), peers_hash(&[a, b]));
+    }
+
+    #[test]
+    fn test_unchanged_peers_are_skipped() {
+        assert_eq!(
+            decide_peer_update(Some("abc"), "abc", true),
+            PeerUpdateAction::Skip
+        );
+        assert_eq!(
+            decide_peer_update(Some("abc"), "abc", false),
+            PeerUpdateAction::Skip
+        );
+    }
+
+    #[test]
+    fn test_first_observation_only_records_hash() {
+        assert_eq!(
+            decide_peer_update(None, "abc", false),
+            PeerUpdateAction::Record
+        );
+    }
+
+    #[test]
+    fn test_changed_peers_reload_when_supported() {
+        assert_eq!(
+            decide_peer_update(Some("old"), "new", true),
+            PeerUpdateAction::Reload
+        );
+    }
+
+    #[test]
+    fn test_changed_peers_restart_when_reload_unsupported() {
+        assert_eq!(
+            decide_peer_update(Some("old"), "new", false),
+            PeerUpdateAction::RollingRestart
+        );
+    }
+
+    #[test]
+    fn test_failed_reload_falls_back_to_restart() {
+        assert_eq!(after_reload(0), PeerUpdateAction::Record);
+        assert_eq!(after_reload(1), PeerUpdateAction::RollingRestart);
+    }
+
+    #[test]
+    fn test_peer_connect_url_uses_the_connect_admin_command() {
+        let peer = make_peer("validator-2", "stellar", "10.0.0.2", 11625);
+        assert_eq!(
+            peer_connect_url("10.0.0.1", &peer),
+            "http://10.0.0.1:11626/connect?peer=10.0.0.2&port=11625"
+        );
+    }
+
+    #[test]
+    fn test_config_reload_annotation() {
+        use crate::crd::{StellarNode, StellarNodeSpec};
+        use std::collections::BTreeMap;
+
+        let mut node = StellarNode::new("validator-1", StellarNodeSpec::default());
+        assert!(config_reload_supported(&node));
+
+        node.metadata.annotations = Some(BTreeMap::from([(
+            CONFIG_RELOAD_ANNOTATION.to_string(),
+            "false".to_string(),
+        )]));
+        assert!(!config_reload_supported(&node));
+    }
+
+    // -------------------------------------------------------------------------
+    // Drained nodes
+    // -------------------------------------------------------------------------
+
+    fn validator_node(name: &str, drain: Option<&str>) -> crate::crd::StellarNode {
+        use crate::crd::{StellarNode, StellarNodeSpec};
+        use std::collections::BTreeMap;
+
+        let mut node = StellarNode::new(name, StellarNodeSpec::default());
+        node.metadata.namespace = Some("stellar-system".to_string());
+        if let Some(drain) = drain {
+            node.metadata.annotations = Some(BTreeMap::from([(
+                DRAIN_ANNOTATION.to_string(),
+                drain.to_string(),
+            )]));
+        }
+        node
+    }
+
+    #[test]
+    fn test_drain_annotation() {
+        assert!(!is_drained(&validator_node("validator-0", None)));
+        assert!(!is_drained(&validator_node("validator-0", Some("false"))));
+        assert!(is_drained(&validator_node("validator-0", Some("true"))));
+        assert!(is_drained(&validator_node("validator-0", Some("True"))));
+    }
+
+    #[test]
+    fn test_drained_node_excluded_from_discovered_peers() {
+        let nodes = [
+            validator_node("validator-0", None),
+            validator_node("validator-1", Some("true")),
+            validator_node("validator-2", Some("false")),
+        ];
+
+        let candidates = nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| is_peer_candidate(node))
+            .map(|(i, node)| {
+                (
+                    make_peer(
+                        node.metadata.name.as_deref().unwrap(),
+                        "stellar-system",
+                        &format!("10.0.0.{}", i + 1),
+                        11625,
+                    ),
+                    PeerRank::for_node(node),
+                )
+            })
+            .collect();
+        let result = PeerDiscoveryResult::from_candidates(candidates, None);
+
+        let names: HashSet<&str> = result
+            .preferred
+            .iter()
+            .chain(&result.known)
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, HashSet::from(["validator-0", "validator-2"]));
+    }
+
+    #[test]
+    fn test_drained_node_is_not_suspended() {
+        let node = validator_node("validator-1", Some("true"));
+        assert!(!node.spec.suspended);
+        assert!(!is_peer_candidate(&node));
+
+        let mut suspended = validator_node("validator-2", None);
+        suspended.spec.suspended = true;
+        assert!(!is_peer_candidate(&suspended));
+    }
+
+    // -------------------------------------------------------------------------
+    // Known-peers cap
+    // -------------------------------------------------------------------------
+
+    fn rank(in_quorum: bool, ready: bool) -> PeerRank {
+        PeerRank { in_quorum, ready }
+    }
+
+    #[test]
+    fn test_select_peers_without_cap_keeps_all() {
+        let candidates: Vec<_> = (1..=5)
+            .map(|i| {
+                (
+                    make_peer(&format!("v{i}"), "stellar", &format!("10.0.0.{i}"), 11625),
+                    PeerRank::default(),
+                )
+            })
+            .collect();
+        assert_eq!(select_peers(candidates, None).len(), 5);
+    }
+
+    #[test]
+    fn test_select_peers_respects_cap() {
+        let candidates: Vec<_> = (1..=10)
+            .map(|i| {
+                (
+                    make_peer(
+                        &format!("v{i:02}"),
+                        "stellar",
+                        &format!("10.0.0.{i}"),
+                        11625,
+                    ),
+                    rank(i % 2 == 0, true),
+                )
+            })
+            .collect();
+        let selected = select_peers(candidates, Some(3));
+        assert_eq!(selected.len(), 3);
+        assert!(selected
+            .iter()
+            .all(|p| ["v02", "v04", "v06"].contains(&p.name.as_str())));
+    }
+
+    #[test]
+    fn test_select_peers_prefers_quorum_members_then_ready() {
+        let candidates = vec![
+            (
+                make_peer("a", "stellar", "10.0.0.1", 11625),
+                rank(false, true),
+            ),
+            (
+                make_peer("b", "stellar", "10.0.0.2", 11625),
+                rank(true, false),
+            ),
+            (
+                make_peer("c", "stellar", "10.0.0.3", 11625),
+                rank(false, false),
+            ),
+            (
+                make_peer("d", "stellar", "10.0.0.4", 11625),
+                rank(true, true),
+            ),
+        ];
+        let names = |n| {
+            let mut names: Vec<String> = select_peers(candidates.clone(), Some(n))
+                .into_iter()
+                .map(|p| p.name)
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names(1), vec!["d"]);
+        assert_eq!(names(2), vec!["b", "d"]);
+        assert_eq!(names(3), vec!["a", "b", "d"]);
+    }
+
+    #[test]
+    fn test_select_peers_is_deterministic() {
+        let candidates: Vec<_> = (1..=8)
+            .map(|i| {
+                (
+                    make_peer(&format!("v{i}"), "stellar", &format!("10.0.0.{i}"), 11625),
+                    PeerRank::default(),
+                )
+            })
+            .collect();
+        let mut reversed = candidates.clone();
+        reversed.reverse();
+
+        let first = select_peers(candidates, Some(4));
+        assert_eq!(first, select_peers(reversed, Some(4)));
+        let mut names: Vec<_> = first.into_iter().map(|p| p.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["v1", "v2", "v3", "v4"]);
+    }
+
+    #[test]
+    fn test_select_peers_drops_duplicates() {
+        let peer = make_peer("v1", "stellar", "10.0.0.1", 11625);
+        let candidates = vec![
+            (peer.clone(), PeerRank::default()),
+            (peer, PeerRank::default()),
+            (
+                make_peer("v2", "stellar", "10.0.0.2", 11625),
+                PeerRank::default(),
+            ),
+        ];
+        assert_eq!(select_peers(candidates, Some(2)).len(), 2);
+    }
+
+    // -------------------------------------------------------------------------
+    // PREFERRED_PEERS vs KNOWN_PEERS
+    // -------------------------------------------------------------------------
+
+    fn partition_candidates() -> Vec<(PeerInfo, PeerRank)> {
+        vec![
+            (
+                make_peer("core-b", "stellar", "10.0.0.2", 11625),
+                rank(true, true),
+            ),
+            (
+                make_peer("watcher", "stellar", "10.0.0.3", 11625),
+                rank(false, true),
+            ),
+            (
+                make_peer("core-a", "stellar", "10.0.0.1", 11625),
+                rank(true, false),
+            ),
+            (
+                make_peer("archiver", "stellar", "10.0.0.4", 11625),
+                rank(false, false),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_quorum_members_are_preferred() {
+        let result = PeerDiscoveryResult::from_candidates(partition_candidates(), None);
+        let names = |peers: &[PeerInfo]| peers.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+
+        assert_eq!(names(&result.preferred), vec!["core-b", "core-a"]);
+        assert_eq!(names(&result.known), vec!["watcher", "archiver"]);
+        assert_eq!(result.len(), 4);
+    }
+
+    #[test]
+    fn test_partition_applies_cap_to_both_lists() {
+        let result = PeerDiscoveryResult::from_candidates(partition_candidates(), Some(3));
+        assert_eq!(result.preferred.len(), 2);
+        assert_eq!(result.known.len(), 1);
+        assert_eq!(result.known[0].name, "watcher");
+    }
+
+    #[test]
+    fn test_peer_lists_render_separately() {
+        let result = PeerDiscoveryResult::from_candidates(partition_candidates(), None);
+        assert_eq!(
+            result.to_toml(),
+            "PREFERRED_PEERS = [\"10.0.0.2:11625\", \"10.0.0.1:11625\"]\n\
+             KNOWN_PEERS = [\"10.0.0.3:11625\", \"10.0.0.4:11625\"]\n"
+        );
+        assert!(result.to_toml().parse::<toml::Value>().is_ok());
+
+        let data = result.config_map_data();
+        assert_eq!(
+            data["preferred_peers.txt"],
+            "10.0.0.2:11625\n10.0.0.1:11625"
+        );
+        assert_eq!(data["known_peers.txt"], "10.0.0.3:11625\n10.0.0.4:11625");
+        assert_eq!(data["peer_count"], "4");
+
+        let json: Vec<serde_json::Value> = serde_json::from_str(&data["peers.json"]).unwrap();
+        let preferred: Vec<bool> = json.iter().map(|p| p["preferred"] == true).collect();
+        assert_eq!(preferred, vec![true, true, false, false]);
+    }
+
+    #[test]
+    fn test_empty_result_renders_empty_lists() {
+        let result = PeerDiscoveryResult::default();
+        assert!(result.is_empty());
+        assert_eq!(result.to_toml(), "PREFERRED_PEERS = []\nKNOWN_PEERS = []\n");
+    }
+
+    #[test]
+    fn test_node_is_left_out_of_its_own_peers() {
+        let node = validator_node("core-a", None);
+        let peers = vec![
+            make_peer("core-a", "stellar-system", "10.0.0.1", 11625),
+            make_peer("core-a", "other", "10.0.1.1", 11625),
+            make_peer("core-b", "stellar-system", "10.0.0.2", 11625),
+        ];
+
+        let names: Vec<_> = peers_for_node(peers, &node, None)
+            .into_iter()
+            .map(|p| format!("{}/{}", p.namespace, p.name))
+            .collect();
+        assert_eq!(names, vec!["other/core-a", "stellar-system/core-b"]);
+    }
+
+    #[test]
+    fn test_node_peers_keep_the_best_within_the_cap() {
+        let node = validator_node("core-a", None);
+        let peers = vec![
+            make_peer("core-b", "stellar-system", "10.0.0.2", 11625),
+            make_peer("core-a", "stellar-system", "10.0.0.1", 11625),
+            make_peer("core-c", "stellar-system", "10.0.0.3", 11625),
+            make_peer("core-d", "stellar-system", "10.0.0.4", 11625),
+        ];
+
+        let names: Vec<_> = peers_for_node(peers, &node, Some(2))
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        assert_eq!(names, vec!["core-b", "core-c"]);
+    }
+
+    // -------------------------------------------------------------------------
+    // Peer refresh on resume
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn test_stale_hash_forces_peer_update() {
+        let current = peers_hash(&[make_peer("validator-a", "stellar", "10.0.0.1", 11625)]);
+        assert_ne!(current, STALE_PEERS_HASH);
+        assert_eq!(
+            decide_peer_update(Some(STALE_PEERS_HASH), &current, true),
+            PeerUpdateAction::Reload
+        );
+        assert_eq!(
+            decide_peer_update(Some(STALE_PEERS_HASH), &current, false),
+            PeerUpdateAction::RollingRestart
+        );
+    }
+
+    fn paused_statefulset(peers_hash: &str) -> serde_json::Value {
+        serde_json::json!({
+            "apiVersion": "apps/v1",
+            "kind": "StatefulSet",
+            "metadata": {
+                "name": "validator-1",
+                "namespace": "stellar",
+                "annotations": { PEERS_HASH_ANNOTATION: peers_hash }
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_resume_marks_peers_stale() {
+        use crate::controller::test_harness::fake_client;
+        use crate::crd::{StellarNode, StellarNodeSpec};
+
+        let mut node = StellarNode::new("validator-1", StellarNodeSpec::default());
+        node.metadata.namespace = Some("stellar".to_string());
+
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            server.respond_with(&paused_statefulset("old")).await;
+            server.respond_echo().await
+        });
+        mark_peers_stale(&client, &node, false).await.unwrap();
+        let patch = server.await.unwrap();
+
+        assert_eq!(patch.method, http::Method::PATCH);
+        assert_eq!(
+            patch.path,
+            "/apis/apps/v1/namespaces/stellar/statefulsets/validator-1"
+        );
+        assert_eq!(
+            patch.body["metadata"]["annotations"][PEERS_HASH_ANNOTATION],
+            STALE_PEERS_HASH
+        );
+        // Only the recorded hash changes; the pods are not rolled
+        assert!(patch.body.get("spec").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resume_refreshes_peers_unchanged_while_paused() {
+        use k8s_openapi::api::apps::v1::StatefulSet;
+        use kube::api::{Api, PostParams};
+        use kube::ResourceExt;
+
+        use crate::controller::test_harness::in_memory_client;
+        use crate::crd::{StellarNode, StellarNodeSpec};
+
+        let mut node = StellarNode::new("validator-1", StellarNodeSpec::default());
+        node.metadata.namespace = Some("stellar".to_string());
+
+        // No shared peers ConfigMap: the list is empty, as it was before the pause
+        let (client, _requests) = in_memory_client();
+        let statefulsets = Api::<StatefulSet>::namespaced(client.clone(), "stellar");
+        let statefulset: StatefulSet =
+            serde_json::from_value(paused_statefulset(&peers_hash(&[]))).unwrap();
+        statefulsets
+            .create(&PostParams::default(), &statefulset)
+            .await
+            .unwrap();
+        mark_peers_stale(&client, &node, false).await.unwrap();
+
+        let statefulset = statefulsets.get("validator-1").await.unwrap();
+        assert_eq!(
+            statefulset.annotations()[PEERS_HASH_ANNOTATION],
+            STALE_PEERS_HASH
+        );
+    }
 }
 
 // =============================================================================
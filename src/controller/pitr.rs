@@ -0,0 +1,666 @@
+//! Point-in-time restore (PITR) for Horizon
+//!
+//! Restores a Horizon node to a moment in the past by combining a
+//! VolumeSnapshot of its data PVC with the CloudNativePG WAL archive of its
+//! managed database. A restore is requested with annotations on the StellarNode:
+//!
+//! - `stellar.org/pitr-target-time`: RFC3339 time to replay WAL up to
+//! - `stellar.org/pitr-snapshot`: VolumeSnapshot of the data PVC taken at or
+//!   before the target time
+//!
+//! The operator records progress in `stellar.org/pitr-phase` and walks the
+//! steps strictly in order, starting a step only once the previous one is
+//! observed to be finished:
+//!
+//! 1. `ScalingDown` - Horizon is scaled to zero so nothing writes during the restore
+//! 2. `RestoringVolume` - the data PVC is recreated from the snapshot
+//! 3. `RestoringDatabase` - the CNPG Cluster is recreated from the base backup,
+//!    replaying WAL up to the target time
+//! 4. `WaitingForDatabase` - the recovered cluster becomes ready
+//! 5. `Complete` - normal reconciliation resumes and scales Horizon back up
+//!
+//! The request annotations stay on the node after completion. They describe
+//! where the current PVC and database came from, and the PVC and Cluster
+//! builders keep emitting the matching `dataSource` and recovery bootstrap so
+//! later applies never try to change those immutable fields. To run another
+//! restore, update the annotations and remove `stellar.org/pitr-phase`.
+//!
+//! The recovered cluster archives its WAL under a new server name so it never
+//! overwrites the original archive; restores always replay the original
+//! cluster's lineage.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::PersistentVolumeClaim;
+use kube::api::{Api, DeleteParams, DynamicObject, Patch, PatchParams};
+use kube::{Client, ResourceExt};
+use tracing::info;
+
+use super::resources::{self, resource_name};
+use super::snapshot::volume_snapshot_api_resource;
+use crate::crd::{Cluster, NodeType, StellarNode};
+use crate::error::{Error, Result};
+
+/// RFC3339 time the database is restored to
+pub const PITR_TARGET_TIME_ANNOTATION: &str = "stellar.org/pitr-target-time";
+
+/// VolumeSnapshot the data PVC is restored from
+pub const PITR_SNAPSHOT_ANNOTATION: &str = "stellar.org/pitr-snapshot";
+
+/// Operator-managed progress of the restore
+pub const PITR_PHASE_ANNOTATION: &str = "stellar.org/pitr-phase";
+
+/// How often a running restore is re-checked
+pub const RESTORE_POLL_SECS: u64 = 10;
+
+/// Steps of a point-in-time restore, in execution order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestorePhase {
+    Pending,
+    ScalingDown,
+    RestoringVolume,
+    RestoringDatabase,
+    WaitingForDatabase,
+    Complete,
+}
+
+impl RestorePhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RestorePhase::Pending => "Pending",
+            RestorePhase::ScalingDown => "ScalingDown",
+            RestorePhase::RestoringVolume => "RestoringVolume",
+            RestorePhase::RestoringDatabase => "RestoringDatabase",
+            RestorePhase::WaitingForDatabase => "WaitingForDatabase",
+            RestorePhase::Complete => "Complete",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        [
+            RestorePhase::Pending,
+            RestorePhase::ScalingDown,
+            RestorePhase::RestoringVolume,
+            RestorePhase::RestoringDatabase,
+            RestorePhase::WaitingForDatabase,
+            RestorePhase::Complete,
+        ]
+        .into_iter()
+        .find(|p| p.as_str() == value)
+    }
+}
+
+impl std::fmt::Display for RestorePhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What the operator saw of the resources involved in a restore
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RestoreObservation {
+    /// The requested VolumeSnapshot is ready to use
+    pub snapshot_ready: bool,
+    /// No Horizon pods are running
+    pub workload_stopped: bool,
+    /// The data PVC exists and was provisioned from the snapshot
+    pub volume_restored: bool,
+    /// The CNPG Cluster exists and recovers to the target time
+    pub database_restored: bool,
+    /// The recovered CNPG Cluster has all instances ready
+    pub database_ready: bool,
+}
+
+/// Decide the phase after `current`, given what was observed.
+///
+/// Advances at most one step per call so each step's action runs before the
+/// next step starts.
+pub fn next_phase(current: RestorePhase, observed: &RestoreObservation) -> RestorePhase {
+    match current {
+        RestorePhase::Pending if observed.snapshot_ready => RestorePhase::ScalingDown,
+        RestorePhase::ScalingDown if observed.workload_stopped => RestorePhase::RestoringVolume,
+        RestorePhase::RestoringVolume if observed.volume_restored => {
+            RestorePhase::RestoringDatabase
+        }
+        RestorePhase::RestoringDatabase if observed.database_restored => {
+            RestorePhase::WaitingForDatabase
+        }
+        RestorePhase::WaitingForDatabase if observed.database_ready => RestorePhase::Complete,
+        phase => phase,
+    }
+}
+
+/// Phase recorded on the node; a missing or unknown value means `Pending`.
+pub fn current_phase(node: &StellarNode) -> RestorePhase {
+    node.annotations()
+        .get(PITR_PHASE_ANNOTATION)
+        .and_then(|v| RestorePhase::parse(v))
+        .unwrap_or(RestorePhase::Pending)
+}
+
+/// Whether the node has a restore that has not completed yet
+pub fn restore_in_progress(node: &StellarNode) -> bool {
+    node.annotations().contains_key(PITR_TARGET_TIME_ANNOTATION)
+        && current_phase(node) != RestorePhase::Complete
+}
+
+/// Target time of the node's current or completed restore
+pub fn restore_target_time(node: &StellarNode) -> Option<DateTime<Utc>> {
+    node.annotations()
+        .get(PITR_TARGET_TIME_ANNOTATION)
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// VolumeSnapshot the data PVC of a restored node is provisioned from
+pub fn restore_snapshot(node: &StellarNode) -> Option<&str> {
+    restore_target_time(node)?;
+    node.annotations()
+        .get(PITR_SNAPSHOT_ANNOTATION)
+        .map(String::as_str)
+        .filter(|s| !s.is_empty())
+}
+
+/// Server name the recovered cluster archives its WAL under
+pub fn recovered_server_name(node: &StellarNode, target: DateTime<Utc>) -> String {
+    format!("{}-pitr-{}", node.name_any(), target.format("%Y%m%d%H%M%S"))
+}
+
+/// A validated restore request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreRequest {
+    pub target_time: DateTime<Utc>,
+    pub snapshot: String,
+}
+
+impl RestoreRequest {
+    /// Read and validate the restore annotations of a node.
+    ///
+    /// Returns `Ok(None)` when no restore is requested.
+    pub fn from_node(node: &StellarNode, now: DateTime<Utc>) -> Result<Option<Self>> {
+        let Some(raw_target) = node.annotations().get(PITR_TARGET_TIME_ANNOTATION) else {
+            return Ok(None);
+        };
+        if node.spec.node_type != NodeType::Horizon {
+            return Err(Error::ValidationError(
+                "Point-in-time restore is only supported for Horizon nodes".to_string(),
+            ));
+        }
+        let backup_enabled = node
+            .spec
+            .managed_database
+            .as_ref()
+            .and_then(|db| db.backup.as_ref())
+            .is_some_and(|b| b.enabled);
+        if !backup_enabled {
+            return Err(Error::ValidationError(
+                "Point-in-time restore requires spec.managedDatabase.backup for WAL archiving"
+                    .to_string(),
+            ));
+        }
+        let target_time = restore_target_time(node).ok_or_else(|| {
+            Error::ValidationError(format!(
+                "{PITR_TARGET_TIME_ANNOTATION} must be an RFC3339 timestamp, got '{raw_target}'"
+            ))
+        })?;
+        if target_time > now {
+            return Err(Error::ValidationError(format!(
+                "{PITR_TARGET_TIME_ANNOTATION} {raw_target} is in the future"
+            )));
+        }
+        let snapshot = restore_snapshot(node).ok_or_else(|| {
+            Error::ValidationError(format!(
+                "{PITR_SNAPSHOT_ANNOTATION} must name the VolumeSnapshot to restore the data PVC from"
+            ))
+        })?;
+
+        Ok(Some(Self {
+            target_time,
+            snapshot: snapshot.to_string(),
+        }))
+    }
+}
+
+/// Whether a VolumeSnapshot can be used for a restore to `target_time`.
+///
+/// Errors if the snapshot was taken after the target: WAL replay only moves
+/// the database forward, so the volume would be newer than the database.
+pub fn snapshot_usable(snapshot: &DynamicObject, target_time: DateTime<Utc>) -> Result<bool> {
+    if let Some(created) = &snapshot.metadata.creation_timestamp {
+        if created.0 > target_time {
+            return Err(Error::ValidationError(format!(
+                "VolumeSnapshot {} was taken at {}, after the restore target {}",
+                snapshot.name_any(),
+                created.0.to_rfc3339(),
+                target_time.to_rfc3339()
+            )));
+        }
+    }
+    Ok(snapshot
+        .data
+        .pointer("/status/readyToUse")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false))
+}
+
+/// Whether a CNPG Cluster bootstraps by recovering to `target_time`.
+pub fn cluster_recovers_to(cluster: &Cluster, target_time: DateTime<Utc>) -> bool {
+    let recovery_target = cluster
+        .spec
+        .bootstrap
+        .as_ref()
+        .and_then(|b| b.recovery.as_ref())
+        .and_then(|r| r.recovery_target.as_ref())
+        .and_then(|t| t.target_time.as_deref())
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok());
+    cluster.metadata.deletion_timestamp.is_none()
+        && recovery_target.is_some_and(|t| t == target_time)
+}
+
+/// Run one pass of the restore, returning the phase it is in afterwards.
+pub async fn reconcile_restore(
+    client: &Client,
+    node: &StellarNode,
+    request: &RestoreRequest,
+) -> Result<RestorePhase> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+    let phase = current_phase(node);
+    let observed = observe(client, node, request).await?;
+
+    let next = next_phase(phase, &observed);
+    if next != phase {
+        info!(
+            "Point-in-time restore of {}/{}: {} -> {}",
+            namespace, name, phase, next
+        );
+        set_phase(client, node, next).await?;
+        return Ok(next);
+    }
+
+    match phase {
+        RestorePhase::ScalingDown => {
+            let api: Api<Deployment> = Api::namespaced(client.clone(), &namespace);
+            if api.get_opt(&name).await?.is_some() {
+                let patch = serde_json::json!({ "spec": { "replicas": 0 } });
+                api.patch(&name, &PatchParams::default(), &Patch::Merge(&patch))
+                    .await?;
+            }
+        }
+        RestorePhase::RestoringVolume => {
+            let api: Api<PersistentVolumeClaim> = Api::namespaced(client.clone(), &namespace);
+            let pvc_name = resource_name(node, "data");
+            match api.get_opt(&pvc_name).await? {
+                Some(pvc) if pvc.metadata.deletion_timestamp.is_none() => {
+                    info!(
+                        "Deleting PVC {}/{} to restore it from {}",
+                        namespace, pvc_name, request.snapshot
+                    );
+                    api.delete(&pvc_name, &DeleteParams::default()).await?;
+                }
+                Some(_) => {}
+                None => {
+                    resources::ensure_pvc(client, node, &BTreeMap::new(), false).await?;
+                }
+            }
+        }
+        RestorePhase::RestoringDatabase => {
+            let api: Api<Cluster> = Api::namespaced(client.clone(), &namespace);
+            match api.get_opt(&name).await? {
+                Some(cluster) if cluster.metadata.deletion_timestamp.is_none() => {
+                    info!(
+                        "Deleting CNPG Cluster {}/{} to recover it to {}",
+                        namespace,
+                        name,
+                        request.target_time.to_rfc3339()
+                    );
+                    api.delete(&name, &DeleteParams::default()).await?;
+                }
+                Some(_) => {}
+                None => resources::ensure_cnpg_cluster(client, node, false).await?,
+            }
+        }
+        RestorePhase::Pending | RestorePhase::WaitingForDatabase | RestorePhase::Complete => {}
+    }
+
+    Ok(phase)
+}
+
+async fn observe(
+    client: &Client,
+    node: &StellarNode,
+    request: &RestoreRequest,
+) -> Result<RestoreObservation> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+
+    let snapshots: Api<DynamicObject> =
+        Api::namespaced_with(client.clone(), &namespace, &volume_snapshot_api_resource());
+    let snapshot = snapshots.get_opt(&request.snapshot).await?.ok_or_else(|| {
+        Error::ValidationError(format!(
+            "VolumeSnapshot {}/{} not found",
+            namespace, request.snapshot
+        ))
+    })?;
+    let snapshot_ready = snapshot_usable(&snapshot, request.target_time)?;
+
+    let deployments: Api<Deployment> = Api::namespaced(client.clone(), &namespace);
+    let workload_stopped = match deployments.get_opt(&name).await? {
+        Some(d) => {
+            let desired = d.spec.as_ref().and_then(|s| s.replicas).unwrap_or(1);
+            let running = d.status.as_ref().and_then(|s| s.replicas).unwrap_or(0);
+            desired == 0 && running == 0
+        }
+        None => true,
+    };
+
+    let pvcs: Api<PersistentVolumeClaim> = Api::namespaced(client.clone(), &namespace);
+    let volume_restored = pvcs
+        .get_opt(&resource_name(node, "data"))
+        .await?
+        .is_some_and(|pvc| {
+            pvc.metadata.deletion_timestamp.is_none()
+                && pvc
+                    .spec
+                    .as_ref()
+                    .and_then(|s| s.data_source.as_ref())
+                    .is_some_and(|ds| ds.name == request.snapshot)
+        });
+
+    let clusters: Api<Cluster> = Api::namespaced(client.clone(), &namespace);
+    let cluster = clusters.get_opt(&name).await?;
+    let database_restored = cluster
+        .as_ref()
+        .is_some_and(|c| cluster_recovers_to(c, request.target_time));
+    let database_ready = database_restored
+        && cluster.as_ref().is_some_and(|c| {
+            let ready = c
+                .status
+                .as_ref()
+                .and_then(|s| s.ready_instances)
+                .unwrap_or(0);
+            ready >= c.spec.instances
+        });
+
+    Ok(RestoreObservation {
+        snapshot_ready,
+        workload_stopped,
+        volume_restored,
+        database_restored,
+        database_ready,
+    })
+}
+
+async fn set_phase(client: &Client, node: &StellarNode, phase: RestorePhase) -> Result<()> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
+    let patch = serde_json::json!({
+        "metadata": { "annotations": { PITR_PHASE_ANNOTATION: phase.as_str() } }
+    });
+    api.patch(
+        &node.name_any(),
+        &PatchParams::default(),
+        &Patch::Merge(&patch),
+    )
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::resources::{build_cnpg_cluster_for_test, build_pvc_for_test};
+    use crate::crd::{
+        ManagedDatabaseBackupConfig, ManagedDatabaseConfig, StellarNodeSpec, StorageConfig,
+    };
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+    const TARGET: &str = "2026-03-01T12:00:00Z";
+
+    fn horizon(annotations: &[(&str, &str)]) -> StellarNode {
+        let mut node = StellarNode::new(
+            "horizon-1",
+            StellarNodeSpec {
+                node_type: NodeType::Horizon,
+                managed_database: Some(ManagedDatabaseConfig {
+                    instances: 2,
+                    storage: StorageConfig::default(),
+                    backup: Some(ManagedDatabaseBackupConfig {
+                        enabled: true,
+                        destination_path: "s3://stellar-wal/horizon".to_string(),
+                        credentials_secret_ref: "wal-creds".to_string(),
+                        retention_policy: "30d".to_string(),
+                        endpoint_url: None,
+                    }),
+                    pooling: None,
+                    postgres_version: "16".to_string(),
+                    database_name: None,
+                    username: None,
+                }),
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.annotations = Some(
+            annotations
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        );
+        node
+    }
+
+    fn requested() -> StellarNode {
+        horizon(&[
+            (PITR_TARGET_TIME_ANNOTATION, TARGET),
+            (PITR_SNAPSHOT_ANNOTATION, "horizon-1-data-20260301"),
+        ])
+    }
+
+    fn now() -> DateTime<Utc> {
+        "2026-03-02T00:00:00Z".parse().unwrap()
+    }
+
+    fn all_done() -> RestoreObservation {
+        RestoreObservation {
+            snapshot_ready: true,
+            workload_stopped: true,
+            volume_restored: true,
+            database_restored: true,
+            database_ready: true,
+        }
+    }
+
+    #[test]
+    fn test_steps_run_in_order_one_per_pass() {
+        let mut phase = RestorePhase::Pending;
+        let mut seen = vec![phase];
+        while phase != RestorePhase::Complete {
+            phase = next_phase(phase, &all_done());
+            seen.push(phase);
+        }
+        assert_eq!(
+            seen,
+            vec![
+                RestorePhase::Pending,
+                RestorePhase::ScalingDown,
+                RestorePhase::RestoringVolume,
+                RestorePhase::RestoringDatabase,
+                RestorePhase::WaitingForDatabase,
+                RestorePhase::Complete,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_each_step_waits_for_its_own_outcome() {
+        let waits = [
+            (
+                RestorePhase::Pending,
+                RestoreObservation {
+                    snapshot_ready: false,
+                    ..all_done()
+                },
+            ),
+            (
+                RestorePhase::ScalingDown,
+                RestoreObservation {
+                    workload_stopped: false,
+                    ..all_done()
+                },
+            ),
+            (
+                RestorePhase::RestoringVolume,
+                RestoreObservation {
+                    volume_restored: false,
+                    ..all_done()
+                },
+            ),
+            (
+                RestorePhase::RestoringDatabase,
+                RestoreObservation {
+                    database_restored: false,
+                    ..all_done()
+                },
+            ),
+            (
+                RestorePhase::WaitingForDatabase,
+                RestoreObservation {
+                    database_ready: false,
+                    ..all_done()
+                },
+            ),
+        ];
+        for (phase, observed) in waits {
+            assert_eq!(
+                next_phase(phase, &observed),
+                phase,
+                "{phase} advanced early"
+            );
+        }
+    }
+
+    #[test]
+    fn test_database_is_not_restored_before_workload_stops() {
+        // A cluster already recovering must not let the restore skip scale-down
+        let observed = RestoreObservation {
+            workload_stopped: false,
+            ..all_done()
+        };
+        assert_eq!(
+            next_phase(RestorePhase::ScalingDown, &observed),
+            RestorePhase::ScalingDown
+        );
+    }
+
+    #[test]
+    fn test_phase_annotation_round_trip() {
+        let mut node = requested();
+        assert_eq!(current_phase(&node), RestorePhase::Pending);
+        assert!(restore_in_progress(&node));
+
+        node.annotations_mut().insert(
+            PITR_PHASE_ANNOTATION.to_string(),
+            RestorePhase::Complete.to_string(),
+        );
+        assert_eq!(current_phase(&node), RestorePhase::Complete);
+        assert!(!restore_in_progress(&node));
+    }
+
+    #[test]
+    fn test_request_parsed_from_annotations() {
+        let request = RestoreRequest::from_node(&requested(), now())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            request.target_time,
+            TARGET.parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(request.snapshot, "horizon-1-data-20260301");
+        assert!(RestoreRequest::from_node(&horizon(&[]), now())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalid_requests_are_rejected() {
+        let mut validator = requested();
+        validator.spec.node_type = NodeType::Validator;
+        assert!(RestoreRequest::from_node(&validator, now()).is_err());
+
+        let mut no_wal = requested();
+        no_wal.spec.managed_database = None;
+        assert!(RestoreRequest::from_node(&no_wal, now()).is_err());
+
+        let future = "2026-03-01T00:00:00Z".parse().unwrap();
+        assert!(RestoreRequest::from_node(&requested(), future).is_err());
+
+        let no_snapshot = horizon(&[(PITR_TARGET_TIME_ANNOTATION, TARGET)]);
+        assert!(RestoreRequest::from_node(&no_snapshot, now()).is_err());
+
+        let bad_time = horizon(&[
+            (PITR_TARGET_TIME_ANNOTATION, "yesterday"),
+            (PITR_SNAPSHOT_ANNOTATION, "snap"),
+        ]);
+        assert!(RestoreRequest::from_node(&bad_time, now()).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_newer_than_target_is_rejected() {
+        let target: DateTime<Utc> = TARGET.parse().unwrap();
+        let mut snapshot = DynamicObject::new("snap", &volume_snapshot_api_resource())
+            .data(serde_json::json!({ "status": { "readyToUse": true } }));
+
+        snapshot.metadata.creation_timestamp = Some(Time("2026-03-01T06:00:00Z".parse().unwrap()));
+        assert!(snapshot_usable(&snapshot, target).unwrap());
+
+        snapshot.metadata.creation_timestamp = Some(Time("2026-03-01T18:00:00Z".parse().unwrap()));
+        assert!(snapshot_usable(&snapshot, target).is_err());
+    }
+
+    #[test]
+    fn test_restored_pvc_is_provisioned_from_snapshot() {
+        let pvc = build_pvc_for_test(&requested(), String::new());
+        let data_source = pvc.spec.unwrap().data_source.unwrap();
+        assert_eq!(data_source.kind, "VolumeSnapshot");
+        assert_eq!(data_source.name, "horizon-1-data-20260301");
+    }
+
+    #[test]
+    fn test_restored_cluster_recovers_from_wal_archive_to_target() {
+        let node = requested();
+        let cluster = build_cnpg_cluster_for_test(&node);
+        let target: DateTime<Utc> = TARGET.parse().unwrap();
+        assert!(cluster_recovers_to(&cluster, target));
+
+        let bootstrap = cluster.spec.bootstrap.as_ref().unwrap();
+        assert!(bootstrap.initdb.is_none());
+        let origin = &cluster.spec.external_clusters.as_ref().unwrap()[0];
+        assert_eq!(origin.name, bootstrap.recovery.as_ref().unwrap().source);
+        let store = origin.barman_object_store.as_ref().unwrap();
+        assert_eq!(store.destination_path, "s3://stellar-wal/horizon");
+        assert_eq!(store.server_name.as_deref(), Some("horizon-1"));
+
+        // New WAL goes to a fresh folder instead of the archive being replayed
+        let backup_store = cluster
+            .spec
+            .backup
+            .as_ref()
+            .and_then(|b| b.barman_object_store.as_ref())
+            .unwrap();
+        assert_eq!(
+            backup_store.server_name.as_deref(),
+            Some("horizon-1-pitr-20260301120000")
+        );
+    }
+
+    #[test]
+    fn test_cluster_without_restore_uses_initdb() {
+        let cluster = build_cnpg_cluster_for_test(&horizon(&[]));
+        let bootstrap = cluster.spec.bootstrap.unwrap();
+        assert!(bootstrap.initdb.is_some());
+        assert!(bootstrap.recovery.is_none());
+    }
+}
@@ -0,0 +1,123 @@
+//! Pod template annotation that rolls a node's pods
+//!
+//! Restarts requested through the REST API, and peer list changes a validator
+//! cannot reload in place, roll the pods by stamping the current time on the
+//! workload's pod template. Both use the single [`RESTARTED_AT_ANNOTATION`],
+//! and every restart also clears the key `kubectl rollout restart` stamps
+//! ([`SUPERSEDED_RESTART_ANNOTATIONS`]), so the template keeps one current
+//! restart time however many restarts it has seen.
+
+use serde_json::{json, Map, Value};
+
+/// Pod template annotation bumped to trigger a rolling restart of a node's workload
+pub const RESTARTED_AT_ANNOTATION: &str = "stellar.org/restarted-at";
+
+/// Restart annotations replaced by [`RESTARTED_AT_ANNOTATION`]
+pub const SUPERSEDED_RESTART_ANNOTATIONS: &[&str] = &["kubectl.kubernetes.io/restartedAt"];
+
+/// Pod template annotations of a restart at `restarted_at`, as a merge patch
+/// that also removes every superseded restart annotation
+pub fn restart_annotations(restarted_at: &str) -> Value {
+    let mut annotations: Map<String, Value> = SUPERSEDED_RESTART_ANNOTATIONS
+        .iter()
+        .map(|key| (key.to_string(), Value::Null))
+        .collect();
+    annotations.insert(RESTARTED_AT_ANNOTATION.to_string(), json!(restarted_at));
+    Value::Object(annotations)
+}
+
+/// Merge patch that rolls a StatefulSet's or Deployment's pods
+pub fn restart_patch(restarted_at: &str) -> Value {
+    json!({
+        "spec": {
+            "template": {
+                "metadata": {
+                    "annotations": restart_annotations(restarted_at)
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::test_harness::fake_client;
+    use k8s_openapi::api::apps::v1::StatefulSet;
+    use kube::api::{Api, Patch, PatchParams, PostParams};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_restart_patch_replaces_superseded_annotations() {
+        let annotations = &restart_patch("2026-01-01T00:00:00+00:00")["spec"]["template"]
+            ["metadata"]["annotations"];
+        assert_eq!(
+            annotations[RESTARTED_AT_ANNOTATION],
+            "2026-01-01T00:00:00+00:00"
+        );
+        for key in SUPERSEDED_RESTART_ANNOTATIONS {
+            assert_eq!(annotations.get(*key), Some(&Value::Null), "{key}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_restarts_keep_one_annotation() {
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let api: Api<StatefulSet> = Api::namespaced(client, "stellar");
+
+        // Template as left by `kubectl rollout restart`
+        let statefulset: StatefulSet = serde_json::from_value(json!({
+            "metadata": { "name": "validator-1" },
+            "spec": {
+                "selector": {},
+                "serviceName": "validator-1",
+                "template": {
+                    "metadata": {
+                        "annotations": {
+                            "kubectl.kubernetes.io/restartedAt": "2025-12-01T00:00:00Z",
+                            "prometheus.io/scrape": "true"
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        api.create(&PostParams::default(), &statefulset)
+            .await
+            .unwrap();
+
+        for day in 1..=5 {
+            let restarted_at = format!("2026-01-0{day}T00:00:00+00:00");
+            api.patch(
+                "validator-1",
+                &PatchParams::default(),
+                &Patch::Merge(restart_patch(&restarted_at)),
+            )
+            .await
+            .unwrap();
+        }
+
+        let annotations = api
+            .get("validator-1")
+            .await
+            .unwrap()
+            .spec
+            .unwrap()
+            .template
+            .metadata
+            .unwrap()
+            .annotations
+            .unwrap();
+        assert_eq!(
+            annotations,
+            BTreeMap::from([
+                (
+                    RESTARTED_AT_ANNOTATION.to_string(),
+                    "2026-01-05T00:00:00+00:00".to_string()
+                ),
+                ("prometheus.io/scrape".to_string(), "true".to_string()),
+            ])
+        );
+    }
+}
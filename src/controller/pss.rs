@@ -104,6 +104,13 @@ pub async fn ensure_namespace_pss_labels(client: &Client, namespace: &str) -> Re
 
 // ── Security context builders ────────────────────────────────────────────────
 
+/// UID of the stellar user the workloads run as
+pub const STELLAR_UID: i64 = 10000;
+
+/// GID of the stellar user; the default `fsGroup`, so mounted volumes are
+/// writable by stellar-core
+pub const STELLAR_GID: i64 = 10000;
+
 /// Build a PSS-compliant pod-level security context.
 ///
 /// Sets:
@@ -115,9 +122,9 @@ pub async fn ensure_namespace_pss_labels(client: &Client, namespace: &str) -> Re
 pub fn restricted_pod_security_context() -> PodSecurityContext {
     PodSecurityContext {
         run_as_non_root: Some(true),
-        run_as_user: Some(10000),
-        run_as_group: Some(10000),
-        fs_group: Some(10000),
+        run_as_user: Some(STELLAR_UID),
+        run_as_group: Some(STELLAR_GID),
+        fs_group: Some(STELLAR_GID),
         seccomp_profile: Some(SeccompProfile {
             type_: "RuntimeDefault".to_string(),
             localhost_profile: None,
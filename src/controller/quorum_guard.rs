@@ -0,0 +1,309 @@
+//! Guard against quorum set changes that could split the network
+//!
+//! A validator whose new quorum slices need not share a member with its old
+//! ones can, while the change rolls out, agree on a different ledger than the
+//! rest of its organisation. Before a new `validatorConfig.quorumSet` reaches
+//! the ConfigMap, the reconciler compares it with the `[QUORUM_SET]` currently
+//! deployed and refuses the change unless every slice of the old set
+//! intersects every slice of the new one, or the node carries
+//! `stellar.org/force-quorum-change: "true"`.
+//!
+//! The check is deliberately basic: each inner set is treated as a single
+//! member identified by its validators, and only the top level is compared.
+//! Quorum sets that do not parse are never rejected, and nodes fed by a VSL
+//! (`vlSource`) are skipped because their quorum set is not taken from the
+//! spec.
+
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::api::Api;
+use kube::{Client, ResourceExt};
+use tracing::warn;
+
+use super::resources::resource_name;
+use crate::crd::{NodeType, StellarNode};
+use crate::error::{Error, Result};
+
+/// Annotation that lets a validator apply a quorum set change the guard rejects
+pub const FORCE_QUORUM_CHANGE_ANNOTATION: &str = "stellar.org/force-quorum-change";
+
+/// stellar-core's default `THRESHOLD_PERCENT`
+const DEFAULT_THRESHOLD_PERCENT: u32 = 67;
+
+/// Top level of a stellar-core `[QUORUM_SET]`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuorumSlices {
+    /// Percentage of members a slice must contain
+    pub threshold_percent: u32,
+    /// Validators and inner sets, each counted as one member
+    pub members: Vec<String>,
+}
+
+impl QuorumSlices {
+    /// Smallest number of members that make up a slice
+    pub fn slice_size(&self) -> usize {
+        let needed = (self.members.len() * self.threshold_percent as usize).div_ceil(100);
+        needed.clamp(1, self.members.len().max(1))
+    }
+}
+
+/// Public key (or `$name`) of a `VALIDATORS` entry, which may carry a
+/// trailing comment
+fn validator_id(entry: &str) -> String {
+    entry
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn parse_table(table: &toml::value::Table) -> QuorumSlices {
+    let threshold_percent = table
+        .get("THRESHOLD_PERCENT")
+        .and_then(toml::Value::as_integer)
+        .and_then(|t| u32::try_from(t).ok())
+        .unwrap_or(DEFAULT_THRESHOLD_PERCENT);
+    let mut members: Vec<String> = table
+        .get("VALIDATORS")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+        .map(validator_id)
+        .collect();
+    for inner in table.values().filter_map(toml::Value::as_table) {
+        let mut validators = parse_table(inner).members;
+        validators.sort();
+        members.push(format!("inner[{}]", validators.join(",")));
+    }
+    members.sort();
+    members.dedup();
+    QuorumSlices {
+        threshold_percent,
+        members,
+    }
+}
+
+/// The `[QUORUM_SET]` of a stellar-core configuration, if it has one
+pub fn parse_quorum_set(cfg: &str) -> Option<QuorumSlices> {
+    let value = cfg.parse::<toml::Value>().ok()?;
+    let table = value.get("QUORUM_SET")?.as_table()?;
+    let slices = parse_table(table);
+    (!slices.members.is_empty()).then_some(slices)
+}
+
+/// Why moving from the `old` quorum set to the `new` one is unsafe, if it is
+///
+/// A slice of each set must contain at least `slice_size - unshared` of the
+/// members both share; the two slices are only guaranteed to meet when those
+/// minimums add up to more than the shared members.
+pub fn unsafe_quorum_change(old: &QuorumSlices, new: &QuorumSlices) -> Option<String> {
+    if !(51..=100).contains(&new.threshold_percent) {
+        return Some(format!(
+            "THRESHOLD_PERCENT={} must be between 51 and 100, or two slices of the \
+             same quorum set can be disjoint",
+            new.threshold_percent
+        ));
+    }
+
+    let shared = old
+        .members
+        .iter()
+        .filter(|member| new.members.contains(member))
+        .count();
+    let forced = |slices: &QuorumSlices| {
+        slices
+            .slice_size()
+            .saturating_sub(slices.members.len() - shared)
+    };
+    if forced(old) + forced(new) > shared {
+        return None;
+    }
+
+    Some(format!(
+        "the new quorum set shares {shared} of its {} members with the old one, so a \
+         slice of {} new members need not intersect a slice of {} old members",
+        new.members.len(),
+        new.slice_size(),
+        old.slice_size()
+    ))
+}
+
+/// Whether the node opted into unchecked quorum set changes
+pub fn quorum_change_forced(node: &StellarNode) -> bool {
+    node.annotations()
+        .get(FORCE_QUORUM_CHANGE_ANNOTATION)
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// Reject an unsafe move from the `deployed` stellar-core configuration to
+/// the quorum set in the spec unless the node forces it.
+pub fn check_quorum_change(node: &StellarNode, deployed: Option<&str>) -> Result<()> {
+    let Some(config) = node.spec.validator_config.as_ref() else {
+        return Ok(());
+    };
+    if config.vl_source.is_some() {
+        return Ok(());
+    }
+    let old = deployed.and_then(parse_quorum_set);
+    let new = config.quorum_set.as_deref().and_then(parse_quorum_set);
+    let (Some(old), Some(new)) = (old, new) else {
+        return Ok(());
+    };
+    let Some(reason) = unsafe_quorum_change(&old, &new) else {
+        return Ok(());
+    };
+
+    if quorum_change_forced(node) {
+        warn!(
+            "Applying unsafe quorum set change to {}/{} ({} is set): {}",
+            node.namespace().unwrap_or_default(),
+            node.name_any(),
+            FORCE_QUORUM_CHANGE_ANNOTATION,
+            reason
+        );
+        return Ok(());
+    }
+
+    Err(Error::ValidationError(format!(
+        "quorum set change could split the network: {reason}. Set the \
+         {FORCE_QUORUM_CHANGE_ANNOTATION}: \"true\" annotation to proceed anyway"
+    )))
+}
+
+/// Fail when a validator's quorum set change is unsafe and not forced.
+pub async fn check_quorum_set_change(client: &Client, node: &StellarNode) -> Result<()> {
+    if node.spec.node_type != NodeType::Validator {
+        return Ok(());
+    }
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<ConfigMap> = Api::namespaced(client.clone(), &namespace);
+    let deployed = api
+        .get_opt(&resource_name(node, "config"))
+        .await?
+        .and_then(|cm| cm.data)
+        .and_then(|mut data| data.remove("stellar-core.cfg"));
+    check_quorum_change(node, deployed.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::{StellarNodeSpec, ValidatorConfig};
+    use std::collections::BTreeMap;
+
+    fn qset(threshold: u32, validators: &[&str]) -> String {
+        let validators: Vec<String> = validators.iter().map(|v| format!("\"{v}\"")).collect();
+        format!(
+            "[QUORUM_SET]\nTHRESHOLD_PERCENT={threshold}\nVALIDATORS=[{}]\n",
+            validators.join(", ")
+        )
+    }
+
+    fn slices(threshold: u32, validators: &[&str]) -> QuorumSlices {
+        parse_quorum_set(&qset(threshold, validators)).unwrap()
+    }
+
+    fn validator(quorum_set: String, force: Option<&str>) -> StellarNode {
+        let mut node = StellarNode::new(
+            "validator-1",
+            StellarNodeSpec {
+                node_type: NodeType::Validator,
+                validator_config: Some(ValidatorConfig {
+                    quorum_set: Some(quorum_set),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        if let Some(force) = force {
+            node.metadata.annotations = Some(BTreeMap::from([(
+                FORCE_QUORUM_CHANGE_ANNOTATION.to_string(),
+                force.to_string(),
+            )]));
+        }
+        node
+    }
+
+    #[test]
+    fn test_parse_quorum_set() {
+        let cfg = r#"
+NETWORK_PASSPHRASE="Test SDF Network ; September 2015"
+
+[QUORUM_SET]
+THRESHOLD_PERCENT=67
+VALIDATORS=["GA sdf1", "GB sdf2", "GC sdf3"]
+
+[QUORUM_SET.org]
+THRESHOLD_PERCENT=51
+VALIDATORS=["GE", "GD"]
+"#;
+        let slices = parse_quorum_set(cfg).unwrap();
+        assert_eq!(slices.threshold_percent, 67);
+        assert_eq!(slices.members, vec!["GA", "GB", "GC", "inner[GD,GE]"]);
+        assert_eq!(slices.slice_size(), 3);
+
+        assert_eq!(parse_quorum_set("[VALIDATORS]\nTHRESHOLD_PERCENT=66"), None);
+        assert_eq!(parse_quorum_set("INVALID TOML ["), None);
+        assert_eq!(
+            parse_quorum_set("[QUORUM_SET]\nVALIDATORS=[\"GA\"]")
+                .unwrap()
+                .threshold_percent,
+            DEFAULT_THRESHOLD_PERCENT
+        );
+    }
+
+    #[test]
+    fn test_overlapping_change_is_safe() {
+        let old = slices(67, &["GA", "GB", "GC", "GD"]);
+        // One validator swapped out
+        assert_eq!(
+            unsafe_quorum_change(&old, &slices(67, &["GA", "GB", "GC", "GE"])),
+            None
+        );
+        // One validator added
+        assert_eq!(
+            unsafe_quorum_change(&old, &slices(67, &["GA", "GB", "GC", "GD", "GE"])),
+            None
+        );
+        assert_eq!(unsafe_quorum_change(&old, &old), None);
+    }
+
+    #[test]
+    fn test_disjoint_or_thin_overlap_is_unsafe() {
+        let old = slices(67, &["GA", "GB", "GC", "GD"]);
+        assert!(unsafe_quorum_change(&old, &slices(67, &["GE", "GF", "GG", "GH"])).is_some());
+        // Sharing one of four members is not enough at 67%
+        assert!(unsafe_quorum_change(&old, &slices(67, &["GA", "GF", "GG", "GH"])).is_some());
+        // Too low a threshold lets slices of the new set itself be disjoint
+        assert!(unsafe_quorum_change(&old, &slices(50, &["GA", "GB", "GC"])).is_some());
+    }
+
+    #[test]
+    fn test_unsafe_change_is_blocked_unless_forced() {
+        let deployed = qset(67, &["GA", "GB", "GC"]);
+        let new = qset(67, &["GD", "GE", "GF"]);
+
+        let err = check_quorum_change(&validator(new.clone(), None), Some(&deployed)).unwrap_err();
+        assert!(
+            err.to_string().contains(FORCE_QUORUM_CHANGE_ANNOTATION),
+            "{err}"
+        );
+        assert!(
+            check_quorum_change(&validator(new.clone(), Some("false")), Some(&deployed)).is_err()
+        );
+        assert!(
+            check_quorum_change(&validator(new.clone(), Some("true")), Some(&deployed)).is_ok()
+        );
+        // Nothing deployed yet
+        assert!(check_quorum_change(&validator(new, None), None).is_ok());
+    }
+
+    #[test]
+    fn test_vsl_managed_quorum_set_is_not_checked() {
+        let mut node = validator(qset(67, &["GD", "GE", "GF"]), None);
+        node.spec.validator_config.as_mut().unwrap().vl_source =
+            Some("https://stellar.example.com/vsl.toml".to_string());
+        assert!(check_quorum_change(&node, Some(&qset(67, &["GA", "GB", "GC"]))).is_ok());
+    }
+}
@@ -4,10 +4,21 @@
 //! - `StatefulSet` — the pool of read-only stellar-core replicas
 //! - `Service` (ClusterIP) — stable DNS endpoint for clients
 //! - `HorizontalPodAutoscaler` (v2) — CPU/memory-based autoscaling
-//! - `ConfigMap` — startup script with archive sharding logic
+//! - `ConfigMap` — startup script with archive sharding logic, and the
+//!   archive fetch script that verifies bucket hashes and fails over to the
+//!   next shard on a bad download
 //!
 //! All resources are created when `spec.readReplicaConfig` is set and
 //! cleaned up when it is removed.
+//!
+//! Each replica keeps its buckets and ledger database under `/data`, on an
+//! emptyDir that is lost with the pod so the replica catches up again or,
+//! with `dataVolume: Persistent`, on a PVC from the StatefulSet's claim
+//! template. Claim templates cannot be patched, so switching between the two
+//! deletes the StatefulSet, leaving its pods running, and recreates it. The
+//! claim template uses `spec.storage.storageClass`; when that is empty and
+//! `spec.storage.tier` is set, a class of the tier is selected when the
+//! template is first created and kept afterwards.
 
 use k8s_openapi::api::apps::v1::{StatefulSet, StatefulSetSpec};
 use k8s_openapi::api::autoscaling::v2::{
@@ -15,8 +26,9 @@ use k8s_openapi::api::autoscaling::v2::{
     MetricTarget,
 };
 use k8s_openapi::api::core::v1::{
-    ConfigMap, Container, ContainerPort, PodSpec, PodTemplateSpec, Service, ServicePort,
-    ServiceSpec, Volume, VolumeMount,
+    ConfigMap, Container, ContainerPort, EmptyDirVolumeSource, PersistentVolumeClaim,
+    PersistentVolumeClaimSpec, PodSpec, PodTemplateSpec, Service, ServicePort, ServiceSpec, Volume,
+    VolumeMount, VolumeResourceRequirements,
 };
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
@@ -29,7 +41,8 @@ use std::collections::BTreeMap;
 use tracing::{info, instrument, warn};
 
 use super::resources::{merge_service_annotations, merge_service_metadata_labels};
-use crate::crd::{ReadReplicaConfig, StellarNode};
+use super::storage_tier;
+use crate::crd::{ReadReplicaConfig, ReadReplicaVolume, StellarNode};
 use crate::error::Result;
 
 // ---------------------------------------------------------------------------
@@ -46,6 +59,12 @@ const FIELD_MANAGER: &str = "stellar-operator";
 const DEFAULT_CPU_TARGET: i32 = 70;
 /// Default memory utilization target (%) for HPA
 const DEFAULT_MEMORY_TARGET: i32 = 80;
+/// ConfigMap key of the history archive fetch script
+const ARCHIVE_GET_SCRIPT: &str = "archive-get.sh";
+/// Name of the replicas' data volume (and of its claim template)
+const DATA_VOLUME: &str = "data";
+/// Where the data volume is mounted
+const DATA_MOUNT_PATH: &str = "/data";
 
 // ---------------------------------------------------------------------------
 // Name helpers
@@ -140,7 +159,38 @@ async fn ensure_read_statefulset(
     let api: Api<StatefulSet> = Api::namespaced(client.clone(), &namespace);
     let name = statefulset_name(node);
 
-    let ss = build_read_statefulset(node, config, enable_mtls);
+    let mut ss = build_read_statefulset(node, config, enable_mtls);
+    let existing = api.get_opt(&name).await?;
+    if config.data_volume == ReadReplicaVolume::Persistent
+        && node.spec.storage.storage_class.is_empty()
+    {
+        let class = match existing.as_ref().and_then(data_claim_storage_class) {
+            Some(class) => Some(class),
+            None => storage_tier::select_tiered_storage_class(client, node).await?,
+        };
+        if let Some(class) = class {
+            set_data_claim_storage_class(&mut ss, class);
+        }
+    }
+    if let Some(existing) = existing {
+        if existing.metadata.deletion_timestamp.is_some() {
+            // Recreated once the deletion below completes, which triggers
+            // another reconcile through the owner reference
+            info!(
+                "Waiting for read StatefulSet {}/{} to be deleted",
+                namespace, name
+            );
+            return Ok(());
+        }
+        if claim_templates_changed(&existing, &ss) {
+            info!(
+                "Data volume of read StatefulSet {}/{} changed, recreating it",
+                namespace, name
+            );
+            api.delete(&name, &DeleteParams::orphan()).await?;
+            return Ok(());
+        }
+    }
     api.patch(
         &name,
         &PatchParams::apply(FIELD_MANAGER).force(),
@@ -191,12 +241,136 @@ fn build_read_statefulset(
             // Headless service name for stable pod DNS (pod-0.name.ns.svc…)
             service_name: name.clone(),
             template: build_read_pod_template(node, config, &labels, enable_mtls),
+            volume_claim_templates: build_data_claim_template(node, config).map(|pvc| vec![pvc]),
+            ..Default::default()
+        }),
+        status: None,
+    }
+}
+
+/// Claim template of the replicas' data PVCs; `None` for emptyDir data
+fn build_data_claim_template(
+    node: &StellarNode,
+    config: &ReadReplicaConfig,
+) -> Option<PersistentVolumeClaim> {
+    if config.data_volume != ReadReplicaVolume::Persistent {
+        return None;
+    }
+    let size = config
+        .data_volume_size
+        .clone()
+        .unwrap_or_else(|| node.spec.storage.size.clone());
+    let storage_class = &node.spec.storage.storage_class;
+    Some(PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: Some(DATA_VOLUME.to_string()),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+            storage_class_name: (!storage_class.is_empty()).then(|| storage_class.clone()),
+            resources: Some(VolumeResourceRequirements {
+                requests: Some(BTreeMap::from([("storage".to_string(), Quantity(size))])),
+                ..Default::default()
+            }),
             ..Default::default()
         }),
         status: None,
+    })
+}
+
+/// Storage class of the data claim template of `ss`
+fn data_claim_storage_class(ss: &StatefulSet) -> Option<String> {
+    ss.spec
+        .as_ref()?
+        .volume_claim_templates
+        .as_ref()?
+        .iter()
+        .find(|pvc| pvc.metadata.name.as_deref() == Some(DATA_VOLUME))?
+        .spec
+        .as_ref()?
+        .storage_class_name
+        .clone()
+}
+
+/// Point the data claim template of `ss` at the StorageClass `class`
+fn set_data_claim_storage_class(ss: &mut StatefulSet, class: String) {
+    let claim = ss
+        .spec
+        .as_mut()
+        .and_then(|spec| spec.volume_claim_templates.as_mut())
+        .and_then(|claims| {
+            claims
+                .iter_mut()
+                .find(|pvc| pvc.metadata.name.as_deref() == Some(DATA_VOLUME))
+        });
+    if let Some(spec) = claim.and_then(|pvc| pvc.spec.as_mut()) {
+        spec.storage_class_name = Some(class);
     }
 }
 
+/// Name, storage class, access modes and size of a claim template
+type ClaimTemplateSummary = (
+    Option<String>,
+    Option<String>,
+    Option<Vec<String>>,
+    Option<Quantity>,
+);
+
+/// Summary of each claim template of `ss`
+fn claim_template_summary(ss: &StatefulSet) -> Vec<ClaimTemplateSummary> {
+    ss.spec
+        .as_ref()
+        .and_then(|spec| spec.volume_claim_templates.as_ref())
+        .into_iter()
+        .flatten()
+        .map(|pvc| {
+            let spec = pvc.spec.clone().unwrap_or_default();
+            let size = spec
+                .resources
+                .and_then(|r| r.requests)
+                .and_then(|requests| requests.get("storage").cloned());
+            (
+                pvc.metadata.name.clone(),
+                spec.storage_class_name,
+                spec.access_modes,
+                size,
+            )
+        })
+        .collect()
+}
+
+/// Whether the claim templates of `desired` differ from the immutable ones of
+/// `existing`
+fn claim_templates_changed(existing: &StatefulSet, desired: &StatefulSet) -> bool {
+    claim_template_summary(existing) != claim_template_summary(desired)
+}
+
+/// Pod volumes of a replica: the startup ConfigMap, plus the data emptyDir
+/// when data is not on a PVC
+fn build_pod_volumes(config: &ReadReplicaConfig, cm_name: String) -> Vec<Volume> {
+    let mut volumes = vec![Volume {
+        name: "config".to_string(),
+        config_map: Some(k8s_openapi::api::core::v1::ConfigMapVolumeSource {
+            name: Some(cm_name),
+            default_mode: Some(0o755),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }];
+    if config.data_volume == ReadReplicaVolume::EmptyDir {
+        volumes.push(Volume {
+            name: DATA_VOLUME.to_string(),
+            empty_dir: Some(EmptyDirVolumeSource {
+                size_limit: config.data_volume_size.clone().map(Quantity),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    }
+    volumes
+}
+
 // ---------------------------------------------------------------------------
 // Service (ClusterIP)
 // ---------------------------------------------------------------------------
@@ -395,18 +569,25 @@ fn build_read_config_map(node: &StellarNode) -> ConfigMap {
             script.push_str("ARCHIVE_COUNT=${#ARCHIVES[@]}\n");
             script.push_str("INDEX=$((ORDINAL % ARCHIVE_COUNT))\n");
             script.push_str("SELECTED_ARCHIVE=${ARCHIVES[$INDEX]}\n");
+            script.push_str("export ARCHIVE_SHARD=$INDEX\n");
             script.push_str("echo \"Selected archive shard: $SELECTED_ARCHIVE\"\n\n");
 
             script.push_str("cat > /etc/stellar/stellar-core.cfg <<EOF\n");
             script.push_str("HTTP_PORT=11626\n");
             script.push_str("PUBLIC_HTTP_PORT=true\n");
             script.push_str("RUN_STANDALONE=false\n");
+            script.push_str(&format!(
+                "DATABASE=\"sqlite3://{DATA_MOUNT_PATH}/stellar.db\"\n"
+            ));
+            script.push_str(&format!("BUCKET_DIR_PATH=\"{DATA_MOUNT_PATH}/buckets\"\n"));
             script.push_str(&format!(
                 "NETWORK_PASSPHRASE=\"{}\"\n",
                 node.spec.network_passphrase()
             ));
             script.push_str("[HISTORY.h1]\n");
-            script.push_str("get=\"curl -sf $SELECTED_ARCHIVE/{0} -o {1}\"\n\n");
+            script.push_str(&format!(
+                "get=\"/config/{ARCHIVE_GET_SCRIPT} {{0}} {{1}}\"\n\n"
+            ));
 
             let validator_svc = format!(
                 "{}.{}.svc.cluster.local",
@@ -416,6 +597,11 @@ fn build_read_config_map(node: &StellarNode) -> ConfigMap {
             script.push_str("[PREFERRED_PEERS]\n");
             script.push_str(&format!("\"{validator_svc}\"\n"));
             script.push_str("EOF\n");
+
+            data.insert(
+                ARCHIVE_GET_SCRIPT.to_string(),
+                build_archive_get_script(&vc.history_archive_urls),
+            );
         }
     }
 
@@ -435,6 +621,59 @@ fn build_read_config_map(node: &StellarNode) -> ConfigMap {
     }
 }
 
+/// Script stellar-core runs to fetch one file (`$1`) from the history
+/// archives into `$2`.
+///
+/// It starts at the replica's own shard (`ARCHIVE_SHARD`) and moves on to the
+/// next one when a download fails. Buckets are named after the SHA256 of their
+/// uncompressed contents, the hash listed in the archive's HAS file, so a
+/// bucket whose contents do not match its name is discarded and fetched from
+/// the next shard. If no shard serves a good copy the script fails, which
+/// fails the catchup step.
+fn build_archive_get_script(archives: &[String]) -> String {
+    let mut script = String::new();
+    script.push_str("#!/bin/bash\n");
+    script.push_str("REMOTE=\"$1\"\n");
+    script.push_str("LOCAL=\"$2\"\n");
+    script.push_str("ARCHIVES=(\n");
+    for url in archives {
+        script.push_str(&format!("  \"{url}\"\n"));
+    }
+    script.push_str(")\n");
+    script.push_str("ARCHIVE_COUNT=${#ARCHIVES[@]}\n");
+    script.push_str("SHARD=${ARCHIVE_SHARD:-0}\n\n");
+
+    script.push_str("EXPECTED=\"\"\n");
+    script.push_str("case \"$REMOTE\" in\n");
+    script.push_str("  */bucket-*.xdr.gz)\n");
+    script.push_str("    EXPECTED=$(basename \"$REMOTE\" .xdr.gz)\n");
+    script.push_str("    EXPECTED=${EXPECTED#bucket-}\n");
+    script.push_str("    ;;\n");
+    script.push_str("esac\n\n");
+
+    script.push_str("for ((i = 0; i < ARCHIVE_COUNT; i++)); do\n");
+    script.push_str("  ARCHIVE=${ARCHIVES[$(((SHARD + i) % ARCHIVE_COUNT))]}\n");
+    script.push_str("  if ! curl -sf \"$ARCHIVE/$REMOTE\" -o \"$LOCAL\"; then\n");
+    script.push_str("    echo \"Download of $REMOTE from $ARCHIVE failed\" >&2\n");
+    script.push_str("    continue\n");
+    script.push_str("  fi\n");
+    script.push_str("  if [ -n \"$EXPECTED\" ]; then\n");
+    script.push_str("    ACTUAL=$(gunzip -c \"$LOCAL\" 2>/dev/null | sha256sum | cut -d' ' -f1)\n");
+    script.push_str("    if [ \"$ACTUAL\" != \"$EXPECTED\" ]; then\n");
+    script
+        .push_str("      echo \"Checksum mismatch for $REMOTE from $ARCHIVE: got $ACTUAL\" >&2\n");
+    script.push_str("      rm -f \"$LOCAL\"\n");
+    script.push_str("      continue\n");
+    script.push_str("    fi\n");
+    script.push_str("  fi\n");
+    script.push_str("  exit 0\n");
+    script.push_str("done\n\n");
+
+    script.push_str("echo \"No archive shard served a valid $REMOTE\" >&2\n");
+    script.push_str("exit 1\n");
+    script
+}
+
 // ---------------------------------------------------------------------------
 // Pod template
 // ---------------------------------------------------------------------------
@@ -498,22 +737,21 @@ fn build_read_pod_template(
                         ..Default::default()
                     },
                 ]),
-                volume_mounts: Some(vec![VolumeMount {
-                    name: "config".to_string(),
-                    mount_path: "/config".to_string(),
-                    ..Default::default()
-                }]),
+                volume_mounts: Some(vec![
+                    VolumeMount {
+                        name: "config".to_string(),
+                        mount_path: "/config".to_string(),
+                        ..Default::default()
+                    },
+                    VolumeMount {
+                        name: DATA_VOLUME.to_string(),
+                        mount_path: DATA_MOUNT_PATH.to_string(),
+                        ..Default::default()
+                    },
+                ]),
                 ..Default::default()
             }],
-            volumes: Some(vec![Volume {
-                name: "config".to_string(),
-                config_map: Some(k8s_openapi::api::core::v1::ConfigMapVolumeSource {
-                    name: Some(cm_name),
-                    default_mode: Some(0o755),
-                    ..Default::default()
-                }),
-                ..Default::default()
-            }]),
+            volumes: Some(build_pod_volumes(config, cm_name)),
             affinity: super::resources::merge_workload_affinity(node),
             topology_spread_constraints: Some(super::resources::build_topology_spread_constraints(
                 &node.spec,
@@ -578,3 +816,307 @@ pub async fn delete_read_pool(client: &Client, node: &StellarNode) -> Result<()>
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use sha2::{Digest, Sha256};
+    use std::io::Write;
+    use std::path::Path;
+    use std::process::Command;
+
+    const BUCKET_CONTENTS: &[u8] = b"bucket entries";
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn bucket_path() -> String {
+        let hash = format!("{:x}", Sha256::digest(BUCKET_CONTENTS));
+        format!(
+            "bucket/{}/{}/{}/bucket-{hash}.xdr.gz",
+            &hash[0..2],
+            &hash[2..4],
+            &hash[4..6]
+        )
+    }
+
+    /// Archive shard serving `contents` under `remote`
+    fn shard(remote: &str, contents: &[u8]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(remote);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+        dir
+    }
+
+    /// Run the fetch script for `remote` against `shards`, starting at shard 0
+    fn fetch(shards: &[&Path], remote: &str, local: &Path) -> bool {
+        let archives: Vec<String> = shards
+            .iter()
+            .map(|p| format!("file://{}", p.display()))
+            .collect();
+        Command::new("bash")
+            .arg("-c")
+            .arg(build_archive_get_script(&archives))
+            .arg(ARCHIVE_GET_SCRIPT)
+            .arg(remote)
+            .arg(local)
+            .env("ARCHIVE_SHARD", "0")
+            .status()
+            .unwrap()
+            .success()
+    }
+
+    #[test]
+    fn test_valid_bucket_is_fetched_from_own_shard() {
+        let remote = bucket_path();
+        let good = shard(&remote, &gzip(BUCKET_CONTENTS));
+        let out = tempfile::tempdir().unwrap();
+        let local = out.path().join("bucket.xdr.gz");
+
+        assert!(fetch(&[good.path()], &remote, &local));
+        assert_eq!(std::fs::read(&local).unwrap(), gzip(BUCKET_CONTENTS));
+    }
+
+    #[test]
+    fn test_corrupt_bucket_fails_over_to_next_shard() {
+        let remote = bucket_path();
+        let corrupt = shard(&remote, &gzip(b"tampered entries"));
+        let good = shard(&remote, &gzip(BUCKET_CONTENTS));
+        let out = tempfile::tempdir().unwrap();
+        let local = out.path().join("bucket.xdr.gz");
+
+        assert!(fetch(&[corrupt.path(), good.path()], &remote, &local));
+        assert_eq!(std::fs::read(&local).unwrap(), gzip(BUCKET_CONTENTS));
+    }
+
+    #[test]
+    fn test_missing_file_fails_over_to_next_shard() {
+        let remote = bucket_path();
+        let empty = tempfile::tempdir().unwrap();
+        let good = shard(&remote, &gzip(BUCKET_CONTENTS));
+        let out = tempfile::tempdir().unwrap();
+        let local = out.path().join("bucket.xdr.gz");
+
+        assert!(fetch(&[empty.path(), good.path()], &remote, &local));
+    }
+
+    #[test]
+    fn test_sync_fails_when_no_shard_has_a_valid_bucket() {
+        let remote = bucket_path();
+        let corrupt = shard(&remote, &gzip(b"tampered entries"));
+        let not_gzip = shard(&remote, b"not gzip");
+        let out = tempfile::tempdir().unwrap();
+        let local = out.path().join("bucket.xdr.gz");
+
+        assert!(!fetch(&[corrupt.path(), not_gzip.path()], &remote, &local));
+        assert!(!local.exists());
+    }
+
+    #[test]
+    fn test_non_bucket_files_are_not_hash_checked() {
+        let remote = "history/00/00/00/history-0000003f.json";
+        let archive = shard(remote, b"{\"version\": 1}");
+        let out = tempfile::tempdir().unwrap();
+        let local = out.path().join("has.json");
+
+        assert!(fetch(&[archive.path()], remote, &local));
+        assert_eq!(std::fs::read(&local).unwrap(), b"{\"version\": 1}");
+    }
+
+    fn pool_node(data_volume: ReadReplicaVolume, size: Option<&str>) -> StellarNode {
+        let mut spec = crate::crd::StellarNodeSpec {
+            read_replica_config: Some(ReadReplicaConfig {
+                replicas: 2,
+                data_volume,
+                data_volume_size: size.map(str::to_string),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        spec.storage.storage_class = "premium-rwo".to_string();
+        spec.storage.size = "200Gi".to_string();
+        let mut node = StellarNode::new("validator-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    fn pool_statefulset(node: &StellarNode) -> StatefulSet {
+        build_read_statefulset(node, node.spec.read_replica_config.as_ref().unwrap(), false)
+    }
+
+    fn data_mount(ss: &StatefulSet) -> Option<String> {
+        ss.spec
+            .as_ref()
+            .unwrap()
+            .template
+            .spec
+            .as_ref()
+            .unwrap()
+            .containers[0]
+            .volume_mounts
+            .iter()
+            .flatten()
+            .find(|m| m.name == DATA_VOLUME)
+            .map(|m| m.mount_path.clone())
+    }
+
+    fn data_volume(ss: &StatefulSet) -> Option<Volume> {
+        ss.spec
+            .as_ref()
+            .unwrap()
+            .template
+            .spec
+            .as_ref()
+            .unwrap()
+            .volumes
+            .iter()
+            .flatten()
+            .find(|v| v.name == DATA_VOLUME)
+            .cloned()
+    }
+
+    #[test]
+    fn test_persistent_pool_uses_claim_template() {
+        let ss = pool_statefulset(&pool_node(ReadReplicaVolume::Persistent, None));
+
+        let templates = ss.spec.as_ref().unwrap().volume_claim_templates.clone();
+        let pvc_spec = templates.unwrap()[0].spec.clone().unwrap();
+        assert_eq!(pvc_spec.storage_class_name.as_deref(), Some("premium-rwo"));
+        assert_eq!(
+            pvc_spec.resources.unwrap().requests.unwrap()["storage"],
+            Quantity("200Gi".to_string())
+        );
+        assert!(data_volume(&ss).is_none());
+        assert_eq!(data_mount(&ss).as_deref(), Some(DATA_MOUNT_PATH));
+
+        let ss = pool_statefulset(&pool_node(ReadReplicaVolume::Persistent, Some("50Gi")));
+        let templates = ss.spec.unwrap().volume_claim_templates.unwrap();
+        assert_eq!(
+            templates[0]
+                .spec
+                .clone()
+                .unwrap()
+                .resources
+                .unwrap()
+                .requests
+                .unwrap()["storage"],
+            Quantity("50Gi".to_string())
+        );
+    }
+
+    #[test]
+    fn test_empty_dir_pool_has_no_claim_template() {
+        let ss = pool_statefulset(&pool_node(ReadReplicaVolume::EmptyDir, Some("20Gi")));
+
+        assert!(ss.spec.as_ref().unwrap().volume_claim_templates.is_none());
+        let empty_dir = data_volume(&ss).unwrap().empty_dir.unwrap();
+        assert_eq!(empty_dir.size_limit, Some(Quantity("20Gi".to_string())));
+        assert_eq!(data_mount(&ss).as_deref(), Some(DATA_MOUNT_PATH));
+
+        let ss = pool_statefulset(&pool_node(ReadReplicaVolume::EmptyDir, None));
+        assert_eq!(
+            data_volume(&ss).unwrap().empty_dir.unwrap().size_limit,
+            None
+        );
+    }
+
+    #[test]
+    fn test_data_volume_defaults_to_empty_dir() {
+        assert_eq!(
+            ReadReplicaConfig::default().data_volume,
+            ReadReplicaVolume::EmptyDir
+        );
+    }
+
+    #[test]
+    fn test_claim_templates_changed() {
+        let empty_dir = pool_statefulset(&pool_node(ReadReplicaVolume::EmptyDir, None));
+        let persistent = pool_statefulset(&pool_node(ReadReplicaVolume::Persistent, None));
+        let resized = pool_statefulset(&pool_node(ReadReplicaVolume::Persistent, Some("50Gi")));
+
+        assert!(!claim_templates_changed(&empty_dir, &empty_dir));
+        assert!(!claim_templates_changed(&persistent, &persistent));
+        assert!(claim_templates_changed(&empty_dir, &persistent));
+        assert!(claim_templates_changed(&persistent, &empty_dir));
+        assert!(claim_templates_changed(&persistent, &resized));
+
+        // An emptyDir size limit lives in the pod template, which is patched
+        let limited = pool_statefulset(&pool_node(ReadReplicaVolume::EmptyDir, Some("20Gi")));
+        assert!(!claim_templates_changed(&empty_dir, &limited));
+    }
+
+    #[tokio::test]
+    async fn test_changed_data_volume_recreates_statefulset() {
+        use crate::controller::test_harness::fake_client;
+
+        let node = pool_node(ReadReplicaVolume::EmptyDir, None);
+        let existing = pool_statefulset(&pool_node(ReadReplicaVolume::Persistent, None));
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            let get = server
+                .respond_with(&serde_json::to_value(&existing).unwrap())
+                .await;
+            let delete = server
+                .respond_with(&serde_json::to_value(&existing).unwrap())
+                .await;
+            (get, delete)
+        });
+
+        let config = node.spec.read_replica_config.as_ref().unwrap();
+        ensure_read_statefulset(&client, &node, config, false)
+            .await
+            .unwrap();
+        let (get, delete) = server.await.unwrap();
+        assert_eq!(get.method, http::Method::GET);
+        assert_eq!(delete.method, http::Method::DELETE);
+        assert_eq!(
+            delete.path,
+            "/apis/apps/v1/namespaces/stellar/statefulsets/validator-1-read"
+        );
+        assert_eq!(delete.body["propagationPolicy"], "Orphan");
+    }
+    #[tokio::test]
+    async fn test_tier_class_is_selected_once_for_claim_template() {
+        use crate::controller::test_harness::fake_client;
+        use crate::crd::types::StorageTier;
+        use k8s_openapi::api::storage::v1::StorageClass;
+
+        let mut node = pool_node(ReadReplicaVolume::Persistent, None);
+        node.spec.storage.storage_class.clear();
+        node.spec.storage.tier = Some(StorageTier::Ssd);
+        let (client, server) = fake_client();
+        let requests = server.serve();
+        let classes: Api<StorageClass> = Api::all(client.clone());
+        let mut class = StorageClass {
+            provisioner: "pd.csi.storage.gke.io".to_string(),
+            parameters: Some(BTreeMap::from([("type".to_string(), "pd-ssd".to_string())])),
+            ..Default::default()
+        };
+        class.metadata.name = Some("fast".to_string());
+        classes.create(&Default::default(), &class).await.unwrap();
+
+        let config = node.spec.read_replica_config.as_ref().unwrap();
+        for _ in 0..2 {
+            ensure_read_statefulset(&client, &node, config, false)
+                .await
+                .unwrap();
+        }
+
+        let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), "stellar");
+        let ss = statefulsets.get("validator-1-read").await.unwrap();
+        assert_eq!(data_claim_storage_class(&ss).as_deref(), Some("fast"));
+        let lists = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.method == http::Method::GET && r.path.ends_with("/storageclasses"))
+            .count();
+        assert_eq!(lists, 1);
+    }
+}
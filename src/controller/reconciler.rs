@@ -28,7 +28,8 @@ use k8s_openapi::api::policy::v1::PodDisruptionBudget;
 
 use futures::StreamExt;
 use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
-use k8s_openapi::api::core::v1::{PersistentVolumeClaim, Service};
+use k8s_openapi::api::batch::v1::Job;
+use k8s_openapi::api::core::v1::{ConfigMap, PersistentVolumeClaim, Service};
 use kube::{
     api::{Api, Patch, PatchParams},
     client::Client,
@@ -43,8 +44,8 @@ use tracing::{debug, error, info, info_span, instrument, warn};
 use tracing_subscriber::{reload::Handle, EnvFilter, Registry};
 
 use crate::crd::{
-    Condition, DisasterRecoveryStatus, NodeType, SpecValidationError, StellarNode,
-    StellarNodeStatus,
+    Condition, DisasterRecoveryStatus, NodeType, SpecValidationError, StellarNetworkConfig,
+    StellarNode, StellarNodeStatus, StellarNodeTemplate,
 };
 use crate::error::{Error, Result};
 #[cfg(feature = "metrics")]
@@ -52,33 +53,43 @@ use crate::infra;
 use crate::plugin_sdk::{HookResult, ReconcileContext};
 
 use super::archive_health::{
-    calculate_backoff, check_archive_integrity, check_archive_integrity_random,
-    check_history_archive_health, ArchiveHealthResult, ArchiveIntegrityCheckResult,
-    ARCHIVE_LAG_THRESHOLD,
+    archive_lag_threshold, calculate_backoff, check_archive_integrity,
+    check_archive_integrity_random, check_history_archive_health, ArchiveHealthResult,
+    ArchiveIntegrityCheckResult,
 };
+use super::archive_rotation;
+use super::archive_server;
 use super::audit_worker::AuditWorker;
 use super::conditions;
+use super::core_log_level;
 use super::cross_cloud_failover;
+use super::cross_cluster;
 use super::cve_reconciler;
 use super::disk_scaler;
 use super::dr;
 use super::dr_drill;
-use super::finalizers::STELLAR_NODE_FINALIZER;
+use super::finalizers::{self, STELLAR_NODE_FINALIZER};
 use super::health;
 use super::kms_secret;
 use super::label_propagation::LabelPropagator;
+use super::ledger_cache;
 use super::maintenance;
 #[cfg(feature = "metrics")]
 use super::metrics;
 use super::mtls;
+use super::network_config;
+use super::node_template;
 use super::oci_snapshot;
 use super::operator_config::{hardcoded_defaults, OperatorConfig};
+use super::otel_collector;
 use super::peer_discovery;
+use super::pitr;
 use super::pss;
 use super::remediation;
 use super::resources;
 use super::secret_watcher;
 use super::service_mesh;
+use super::snapshot::{self, FinalSnapshotState};
 use super::spot_drain;
 use super::sync_scale;
 use super::sync_state_monitor;
@@ -282,6 +293,8 @@ pub struct ControllerState {
     pub watch_namespace: Option<String>,
     pub mtls_config: Option<crate::MtlsConfig>,
     pub dry_run: bool,
+    /// Skip adding the StellarNode finalizer so deletions never block on the operator.
+    pub disable_finalizers: bool,
     /// Requeue interval in seconds for retriable reconciliation errors.
     pub retry_budget_retriable_secs: u64,
     /// Requeue interval in seconds for non-retriable reconciliation errors.
@@ -375,6 +388,7 @@ impl ControllerState {
 ///         watch_namespace: None,
 ///         mtls_config: None,
 ///         dry_run: false,
+///         disable_finalizers: false,
 ///         retry_budget_retriable_secs: 15,
 ///         retry_budget_nonretriable_secs: 60,
 ///         retry_budget_max_attempts: 3,
@@ -509,7 +523,16 @@ pub async fn run_controller(state: Arc<ControllerState>) -> Result<()> {
         });
     }
 
-    Controller::new(stellar_nodes, Config::default())
+    // StellarNode plus the ten owned/watched resource types registered below
+    #[cfg(feature = "metrics")]
+    let _watch_streams = metrics::track_watch_streams(11);
+
+    let controller = Controller::new(stellar_nodes, Config::default());
+    let node_store = controller.store();
+    let network_config_nodes = node_store.clone();
+
+    controller
+        .with_config(state.operator_config.reconciler.controller_config())
         // Watch owned resources for changes
         .owns::<Deployment>(
             if let Some(ns) = &state.watch_namespace {
@@ -551,6 +574,25 @@ pub async fn run_controller(state: Arc<ControllerState>) -> Result<()> {
             },
             Config::default(),
         )
+        // Edits to the rendered node config are reverted on the next reconcile;
+        // only the operator's ConfigMaps are cached, not every one in the cluster
+        .owns::<ConfigMap>(
+            if let Some(ns) = &state.watch_namespace {
+                Api::namespaced(client.clone(), ns)
+            } else {
+                Api::all(client.clone())
+            },
+            Config::default().labels("app.kubernetes.io/managed-by=stellar-operator"),
+        )
+        // Snapshot push Job completion updates the node's backup status
+        .owns::<Job>(
+            if let Some(ns) = &state.watch_namespace {
+                Api::namespaced(client.clone(), ns)
+            } else {
+                Api::all(client.clone())
+            },
+            Config::default(),
+        )
         .watches::<k8s_openapi::api::core::v1::Secret, _>(
             if let Some(ns) = &state.watch_namespace {
                 Api::namespaced(client.clone(), ns)
@@ -564,6 +606,24 @@ pub async fn run_controller(state: Arc<ControllerState>) -> Result<()> {
                 vec![]
             },
         )
+        // Template edits are applied to the nodes inheriting from it
+        .watches::<StellarNodeTemplate, _>(
+            if let Some(ns) = &state.watch_namespace {
+                Api::namespaced(client.clone(), ns)
+            } else {
+                Api::all(client.clone())
+            },
+            Config::default(),
+            move |template| node_template::nodes_using_template(&node_store.state(), &template),
+        )
+        // StellarNetworkConfig is cluster-scoped and referenced from any namespace
+        .watches::<StellarNetworkConfig, _>(
+            Api::all(client.clone()),
+            Config::default(),
+            move |config| {
+                network_config::nodes_using_network_config(&network_config_nodes.state(), &config)
+            },
+        )
         .shutdown_on_signal()
         .run(|obj, ctx| reconcile(obj, ctx), error_policy, state.clone())
         .fold(BatchSummaryReport::new(50), {
@@ -797,6 +857,8 @@ fn reconcile(
 
         #[cfg(feature = "metrics")]
         let reconcile_start = std::time::Instant::now();
+        #[cfg(feature = "metrics")]
+        let _active_reconcile = metrics::track_active_reconcile();
 
         if !ctx.is_leader.load(std::sync::atomic::Ordering::Relaxed) {
             debug!("Not the leader, skipping reconciliation");
@@ -812,6 +874,35 @@ fn reconcile(
                 namespace, node_name, obj.spec.node_type
             );
 
+            // Resolve spec.templateRef so validation and apply see the effective spec
+            let obj = match obj.metadata.deletion_timestamp {
+                Some(_) => obj.clone(),
+                None => match node_template::apply_template(&client, &obj).await? {
+                    Some(resolved) => Arc::new(resolved),
+                    None => obj.clone(),
+                },
+            };
+
+            // Resolve spec.networkConfigRef on top of the template
+            let obj = match obj.metadata.deletion_timestamp {
+                Some(_) => obj.clone(),
+                None => match network_config::apply_network_config(&client, &obj).await? {
+                    Some(resolved) => Arc::new(resolved),
+                    None => obj.clone(),
+                },
+            };
+
+            // Publish the network config's quorum set and render validators from it
+            let obj = match obj.metadata.deletion_timestamp {
+                Some(_) => obj.clone(),
+                None => match network_config::apply_shared_quorum_set(&client, &obj, ctx.dry_run)
+                    .await?
+                {
+                    Some(resolved) => Arc::new(resolved),
+                    None => obj.clone(),
+                },
+            };
+
             // 1. Advanced Configuration Validation
             let validation_errors = crate::config_mgmt::validation::Validator::validate(&obj.spec);
             if !validation_errors.is_empty() {
@@ -837,7 +928,12 @@ fn reconcile(
             // Manual finalizer logic to avoid HRTB Send issues with the helper closure
             if obj.metadata.deletion_timestamp.is_some() {
                 if obj.finalizers().iter().any(|f| f == STELLAR_NODE_FINALIZER) {
-                    cleanup_stellar_node(client.clone(), obj.clone(), ctx.clone()).await?;
+                    let action = cleanup_stellar_node(client.clone(), obj.clone(), ctx.clone()).await?;
+                    // Cleanup asks to be requeued while it waits (e.g. for the final snapshot);
+                    // keep the finalizer until it reports completion.
+                    if action != Action::await_change() {
+                        return Ok(action);
+                    }
 
                     let patch = serde_json::json!({
                         "metadata": {
@@ -848,9 +944,8 @@ fn reconcile(
                 }
                 Ok(Action::await_change())
             } else {
-                if !obj.finalizers().iter().any(|f| f == STELLAR_NODE_FINALIZER) {
-                    let mut finalizers = obj.finalizers().to_vec();
-                    finalizers.push(STELLAR_NODE_FINALIZER.to_string());
+                let enabled = finalizers::finalizer_enabled(&obj, ctx.disable_finalizers);
+                if let Some(finalizers) = finalizers::desired_finalizers(&obj, enabled) {
                     let patch = serde_json::json!({
                         "metadata": {
                             "finalizers": finalizers
@@ -945,6 +1040,10 @@ pub(crate) fn apply_stellar_node(
             return Err(Error::ValidationError(message));
         }
 
+        if let Some(warning) = node.spec.host_network_warning() {
+            warn!("{}/{}: {}", namespace, name, warning);
+        }
+
         // Network safety check — must run before any resources are created.
         // Ensures no Mainnet node shares a namespace with a Testnet node (or vice versa).
         if let Err(e) = super::network_isolation::check_network_safety(&client, &node).await {
@@ -967,6 +1066,69 @@ pub(crate) fn apply_stellar_node(
             return Err(e);
         }
 
+        // Rollback request — revert spec.version and let the patch trigger the
+        // next reconcile
+        match super::version_rollback::apply_rollback(&client, &node).await {
+            Ok(Some(target)) => {
+                emit_event!(
+                    &client,
+                    &ctx.event_reporter,
+                    &node,
+                    kube::runtime::events::EventType::Normal,
+                    "RollbackRequested",
+                    "VersionRollback",
+                    &format!(
+                        "Reverting spec.version from {} to last good version {}",
+                        node.spec.version, target
+                    ),
+                )
+                .await?;
+                return Ok(Action::await_change());
+            }
+            Ok(None) => {}
+            Err(e) => warn!(
+                "Failed to apply rollback request for {}/{}: {}",
+                namespace, name, e
+            ),
+        }
+
+        // Downgrade guard — an older release on upgraded state can corrupt the ledger
+        if let Err(e) = super::version_guard::check_version_downgrade(&client, &node).await {
+            let msg = e.to_string();
+            warn!("Version guard failed for {}/{}: {}", namespace, name, msg);
+            emit_event!(
+                &client,
+                &ctx.event_reporter,
+                &node,
+                kube::runtime::events::EventType::Warning,
+                "VersionDowngradeBlocked",
+                "VersionGuard",
+                &msg,
+            )
+            .await?;
+            update_status(&client, &node, "Failed", Some(msg.clone()), 0, true).await?;
+            return Err(e);
+        }
+
+        // Quorum guard — a quorum set sharing too little with the deployed one
+        // can split the validator off from the network
+        if let Err(e) = super::quorum_guard::check_quorum_set_change(&client, &node).await {
+            let msg = e.to_string();
+            warn!("Quorum guard failed for {}/{}: {}", namespace, name, msg);
+            emit_event!(
+                &client,
+                &ctx.event_reporter,
+                &node,
+                kube::runtime::events::EventType::Warning,
+                "QuorumChangeBlocked",
+                "QuorumGuard",
+                &msg,
+            )
+            .await?;
+            update_status(&client, &node, "Failed", Some(msg.clone()), 0, true).await?;
+            return Err(e);
+        }
+
         let propagated_labels = Arc::new(LabelPropagator::new(&node).compute());
 
         // ── Plugin SDK: pre_reconcile hooks ───────────────────────────────────
@@ -990,6 +1152,53 @@ pub(crate) fn apply_stellar_node(
             );
         }
 
+        // 0. Point-in-time restore owns the node's workload, PVC and database
+        // until it completes
+        if pitr::restore_in_progress(&node) && !ctx.dry_run {
+            match pitr::RestoreRequest::from_node(&node, Utc::now()) {
+                Ok(Some(request)) => {
+                    let before = pitr::current_phase(&node);
+                    let phase = pitr::reconcile_restore(&client, &node, &request).await?;
+                    let message = format!(
+                        "Point-in-time restore to {} from {}: {}",
+                        request.target_time.to_rfc3339(),
+                        request.snapshot,
+                        phase
+                    );
+                    if phase != before {
+                        publish_stellar_event!(
+                            &client,
+                            &ctx.event_reporter,
+                            &node,
+                            EventType::Normal,
+                            "PointInTimeRestore",
+                            "Restore",
+                            &message,
+                        )
+                        .await
+                        .ok();
+                    }
+                    if phase != pitr::RestorePhase::Complete {
+                        update_status(&client, &node, "Restoring", Some(message), 0, true)
+                            .await?;
+                    }
+                    return Ok(Action::requeue(Duration::from_secs(
+                        pitr::RESTORE_POLL_SECS,
+                    )));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    warn!(
+                        "Invalid point-in-time restore request for {}/{}: {}",
+                        namespace, name, e
+                    );
+                    update_status(&client, &node, "Failed", Some(e.to_string()), 0, true)
+                        .await?;
+                    return Err(e);
+                }
+            }
+        }
+
         // 1. Core infrastructure (PVC and ConfigMap) always managed by operator
         apply_or_emit!(
             &ctx,
@@ -1012,6 +1221,42 @@ pub(crate) fn apply_stellar_node(
         })
         .await?;
 
+        // 1b. A changed node type leaves the workload of the other kind behind
+        apply_or_emit!(
+            &ctx,
+            &node,
+            ActionType::Delete,
+            "Stale workload",
+            move |client: Client, ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
+                if resources::delete_stale_workload(&client, &node, ctx.dry_run).await? {
+                    let message = format!(
+                        "Node type changed to {:?}; replaced the old workload with a {}",
+                        node.spec.node_type,
+                        resources::workload_kind(&node.spec.node_type)
+                    );
+                    info!(
+                        "{}/{}: {}",
+                        node.namespace().unwrap_or_default(),
+                        node.name_any(),
+                        message
+                    );
+                    publish_stellar_event!(
+                        &client,
+                        &ctx.event_reporter,
+                        &node,
+                        EventType::Normal,
+                        "NodeTypeChanged",
+                        "Migrate",
+                        &message,
+                    )
+                    .await
+                    .ok();
+                }
+                Ok(())
+            }
+        )
+        .await?;
+
         // 2. Handle suspension
         if node.spec.suspended {
             apply_or_emit!(&ctx, &node, ActionType::Update, "Suspended state resources", clones: [propagated_labels], move |client: Client, ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
@@ -1380,6 +1625,46 @@ pub(crate) fn apply_stellar_node(
         .await?;
         info!("ConfigMap ensured for {}/{}", namespace, name);
 
+        // 3. (cont.) History archive volume and server of a publishing validator;
+        // run for every node type so a former validator's server is removed
+        apply_or_emit!(
+            &ctx,
+            &node,
+            ActionType::Update,
+            "History archive server",
+            move |client: Client, ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
+                archive_server::ensure_archive_server(&client, &node, ctx.dry_run).await?;
+                Ok(())
+            }
+        )
+        .await?;
+
+        // 3. (cont.) Configuration of the OpenTelemetry Collector sidecar
+        apply_or_emit!(
+            &ctx,
+            &node,
+            ActionType::Update,
+            "OpenTelemetry Collector config",
+            move |client: Client, ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
+                otel_collector::ensure_otel_collector(&client, &node, ctx.dry_run).await?;
+                Ok(())
+            }
+        )
+        .await?;
+
+        // 3a. ServiceAccount (and its scoped Role) must exist before the pods referencing it
+        apply_or_emit!(
+            &ctx,
+            &node,
+            ActionType::Update,
+            "ServiceAccount",
+            move |client: Client, ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
+                resources::ensure_service_account(&client, &node, ctx.dry_run).await?;
+                Ok(())
+            }
+        )
+        .await?;
+
         // 3. Handle suspension or Maintenance
         if node.spec.maintenance_mode {
             update_status(
@@ -1405,6 +1690,45 @@ pub(crate) fn apply_stellar_node(
             // Continue to ensure resources exist but with 0 replicas
         }
 
+        // Coming back from suspension: the workload scales up from 0 with the
+        // peers and addresses it knew before, so resolve the peer endpoints
+        // again, have the next healthy reconcile re-apply the shared peer
+        // list, and re-check sync instead of trusting the status reported
+        // before the node went down.
+        let resuming = is_resuming(&node);
+        if resuming {
+            info!("Node {}/{} is resuming from suspension", namespace, name);
+            if let Err(e) =
+                cross_cluster::expire_peer_resolutions(&client, &node, ctx.dry_run).await
+            {
+                warn!(
+                    "Failed to expire peer endpoints of {}/{}: {}",
+                    namespace, name, e
+                );
+            }
+            if node.spec.node_type == NodeType::Validator {
+                if let Err(e) =
+                    peer_discovery::mark_peers_stale(&client, &node, ctx.dry_run).await
+                {
+                    warn!(
+                        "Failed to schedule peer refresh for {}/{}: {}",
+                        namespace, name, e
+                    );
+                }
+            }
+            publish_stellar_event!(
+                &client,
+                &ctx.event_reporter,
+                &node,
+                EventType::Normal,
+                "NodeResumed",
+                "Resume",
+                "Node resumed from suspension; refreshing peers and re-checking sync",
+            )
+            .await
+            .ok();
+        }
+
         // 4. Ensure mTLS certificates
         apply_or_emit!(
             &ctx,
@@ -1950,7 +2274,14 @@ pub(crate) fn apply_stellar_node(
             ActionType::Update,
             "Monitoring and Scaling resources",
             move |client: Client, ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
-                resources::ensure_service_monitor(&client, &node).await?;
+                resources::ensure_metrics_monitor(
+                    &client,
+                    &node,
+                    ctx.operator_config.metrics_monitor,
+                    &ctx.operator_config.metrics_endpoint,
+                    &ctx.operator_config.metrics_tls,
+                )
+                .await?;
 
                 if node.spec.autoscaling.is_some() {
                     resources::ensure_hpa(&client, &node, ctx.dry_run).await?;
@@ -1992,7 +2323,7 @@ pub(crate) fn apply_stellar_node(
         if node.spec.node_type == NodeType::Validator {
             if let Some(ref snapshot_config) = node.spec.snapshot_schedule {
                 if let Err(e) =
-                    super::snapshot::reconcile_snapshot(&client, &node, snapshot_config).await
+                    snapshot::reconcile_snapshot(&client, &node, snapshot_config).await
                 {
                     warn!(
                         "Snapshot reconciliation failed for {}/{}: {}",
@@ -2008,7 +2339,7 @@ pub(crate) fn apply_stellar_node(
         // If the DB trigger updated the status very recently (e.g. < 15 seconds ago), we can skip the health check API poll
         let mut skipped_poll = false;
         let mut recent_health = None;
-        if let Some(ref status) = node.status {
+        if let Some(status) = node.status.as_ref().filter(|_| !resuming) {
             if let Some(updated_at_str) = &status.ledger_updated_at {
                 if let Ok(updated_at) = chrono::DateTime::parse_from_rfc3339(updated_at_str) {
                     let age = chrono::Utc::now()
@@ -2037,6 +2368,69 @@ pub(crate) fn apply_stellar_node(
             namespace, name, health_result.healthy, health_result.synced, health_result.message
         );
 
+        // Consensus state from stellar-core /info (Validator only), compared
+        // with the previous sample to catch a validator falling out of consensus
+        let previous_consensus = node.status.as_ref().and_then(|s| s.consensus.clone());
+        let sampled_at = Utc::now();
+        let consensus = sync_state_monitor::resolve_node_consensus_health(&client, &node)
+            .await
+            .map(|c| {
+                sync_state_monitor::track_ledger_progress(previous_consensus.as_ref(), c, sampled_at)
+            });
+        let was_desynced = sync_state_monitor::was_desynced(&node);
+        let desync = consensus.as_ref().and_then(|c| {
+            sync_state_monitor::detect_desync(
+                previous_consensus.as_ref(),
+                c,
+                was_desynced,
+                sampled_at,
+            )
+        });
+        if let Some(desync) = desync.as_ref().filter(|_| !was_desynced) {
+            warn!("Validator {}/{} desynchronized: {}", namespace, name, desync.message);
+            let recorder = recorder_for(&client, &ctx.event_reporter, &node);
+            if let Err(e) = publish_object_event(
+                &recorder,
+                EventType::Warning,
+                "ValidatorDesynced",
+                "ConsensusMonitor",
+                &desync.message,
+            )
+            .await
+            {
+                warn!("Failed to publish ValidatorDesynced event: {e}");
+            }
+        }
+        #[cfg(feature = "metrics")]
+        if consensus.is_some() {
+            let hardware_generation = hardware_generation_for_metrics(&client, &node).await;
+            metrics::set_validator_desynced(
+                &namespace,
+                &name,
+                &node.spec.node_type.to_string(),
+                node.spec.network_passphrase(),
+                &hardware_generation,
+                desync.is_some(),
+            );
+        }
+        if let Some(consensus) = &consensus {
+            let api: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
+            let patch = sync_state_monitor::consensus_status_patch(consensus);
+            if let Err(e) = api
+                .patch_status(
+                    &name,
+                    &PatchParams::apply("stellar-operator"),
+                    &Patch::Merge(&patch),
+                )
+                .await
+            {
+                warn!(
+                    "Failed to patch consensus status for {}/{}: {}",
+                    namespace, name, e
+                );
+            }
+        }
+
         // 7a. Sync-state-driven resource scaling (Validator only)
         //
         // Queries the stellar-core /info endpoint to determine whether the node is
@@ -2125,9 +2519,16 @@ pub(crate) fn apply_stellar_node(
             }
         }
 
-        // 6. Trigger peer configuration reload for validators if healthy
+        // 6. Apply peer list changes to healthy validators (reload, or restart as a fallback)
         if node.spec.node_type == NodeType::Validator && health_result.healthy {
-            if let Err(e) = peer_discovery::trigger_peer_config_reload(&client, &node).await {
+            if let Err(e) = peer_discovery::trigger_peer_config_reload(
+                &client,
+                &node,
+                ctx.operator_config.max_known_peers,
+                ctx.dry_run,
+            )
+            .await
+            {
                 warn!(
                     "Failed to trigger peer config reload for {}/{}: {}",
                     namespace, name, e
@@ -2135,6 +2536,14 @@ pub(crate) fn apply_stellar_node(
             }
         }
 
+        // 6.1. Apply the stellar.org/log-level annotation to running stellar-core pods
+        if let Err(e) = core_log_level::apply_core_log_level(&client, &node, ctx.dry_run).await {
+            warn!(
+                "Failed to apply the log level of {}/{}: {}",
+                namespace, name, e
+            );
+        }
+
         // 6.5. Quorum analysis for validators
         if node.spec.node_type == NodeType::Validator && health_result.healthy {
             if let Err(e) = perform_quorum_analysis(&client, &node, ctx.retry_budget_max_attempts).await
@@ -2377,21 +2786,54 @@ pub(crate) fn apply_stellar_node(
         }
 
         // 10. Final Status Update
+        let deployment = if node.spec.node_type != NodeType::Validator && !node.spec.suspended {
+            get_deployment(&client, &node).await
+        } else {
+            None
+        };
+        let stuck = deployment.as_ref().and_then(stuck_rollout);
+        // A multi-replica Horizon is only Ready once enough replicas are ready
+        let horizon_replicas = (node.spec.node_type == NodeType::Horizon
+            && !node.spec.suspended
+            && health_result.healthy
+            && health_result.synced)
+            .then(|| deployment_replicas(&node, deployment.as_ref()));
         let (phase, message) = if node.spec.suspended {
             ("Suspended", "Node is suspended".to_string())
+        } else if let Some((reason, detail)) = &stuck {
+            ("Degraded", format!("Rollout is stuck ({reason}): {detail}"))
+        } else if let Some(desync) = &desync {
+            ("Degraded", desync.message.clone())
         } else if !health_result.healthy {
             ("Creating", health_result.message.clone())
         } else if !health_result.synced {
             ("Syncing", health_result.message.clone())
+        } else if let Some((desired, ready)) = horizon_replicas
+            .filter(|&(desired, ready)| !horizon_replicas_ready(&node, desired, ready))
+        {
+            (
+                "Creating",
+                format!(
+                    "{ready}/{} Horizon replicas ready",
+                    required_horizon_replicas(&node, desired)
+                ),
+            )
         } else {
             ("Ready", "Node is healthy and synced".to_string())
         };
 
-        apply_or_emit!(&ctx, &node, ActionType::Update, "Status (Final)", clones: [health_result, message], move |client: Client, _ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
+        apply_or_emit!(&ctx, &node, ActionType::Update, "Status (Final)", clones: [health_result, message, stuck, desync], move |client: Client, _ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
             update_status_with_health(&client, &node, phase, Some(message.clone()), health_result.clone()).await?;
 
             let ready_replicas = get_ready_replicas(&client, &node).await.unwrap_or(0);
-            update_status(&client, &node, phase, Some(message), ready_replicas, true).await?;
+            update_status_with_conditions(&client, &node, phase, Some(message), ready_replicas, true, |conditions| {
+                if let Some((reason, detail)) = &stuck {
+                    set_degraded(conditions, reason, detail);
+                } else if let Some(desync) = &desync {
+                    set_degraded(conditions, desync.reason, &desync.message);
+                }
+            })
+            .await?;
             Ok(())
         })
         .await?;
@@ -2427,7 +2869,9 @@ pub(crate) fn apply_stellar_node(
                 // Calculate ingestion lag if we can get the latest network ledger
                 // For now we assume we have a way to track the "latest" known ledger across the cluster
                 // or fetch it from a public horizon.
-                if let Ok(network_latest) = get_latest_network_ledger(&node.spec.network).await {
+                if let Ok(network_latest) =
+                    get_latest_network_ledger(&client, &node, &ctx.operator_config).await
+                {
                     let lag = (network_latest as i64) - (seq as i64);
                     metrics::set_ingestion_lag(
                         &namespace,
@@ -2437,6 +2881,20 @@ pub(crate) fn apply_stellar_node(
                         &hardware_generation,
                         lag.max(0),
                     );
+                    let close_time = metrics::observe_network_ledger(
+                        &namespace,
+                        &name,
+                        network_latest,
+                        std::time::Instant::now(),
+                    );
+                    metrics::set_ingestion_lag_seconds(
+                        &namespace,
+                        &name,
+                        &node.spec.node_type.to_string(),
+                        node.spec.network_passphrase(),
+                        &hardware_generation,
+                        metrics::lag_seconds(lag, close_time),
+                    );
                 }
             }
         }
@@ -2463,6 +2921,15 @@ pub(crate) fn apply_stellar_node(
                 &hardware_generation,
                 health_result.healthy,
             );
+
+            // Publish the running version to correlate metrics with rollouts
+            metrics::set_node_info(
+                &namespace,
+                &name,
+                &node.spec.node_type.to_string(),
+                node.spec.network_passphrase(),
+                &node.spec.version,
+            );
         }
 
         // 10d. Proactive disk scaling check
@@ -2629,6 +3096,35 @@ pub(crate) fn apply_stellar_node(
                     }
                 }
 
+                // Record the latest finished push Job as the node's backup status
+                if oci_cfg.push {
+                    match oci_snapshot::latest_push_job_result(&client, &node).await {
+                        Ok(Some(result)) => {
+                            if let Err(e) =
+                                update_backup_status(&client, &ctx, &node, &result).await
+                            {
+                                warn!(
+                                    "Failed to update backup status for {}/{}: {}",
+                                    namespace, name, e
+                                );
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => warn!(
+                            "Failed to list OCI snapshot push Jobs for {}/{}: {}",
+                            namespace, name, e
+                        ),
+                    }
+
+                    // Enforce the push Job history limits once the outcome is recorded
+                    if let Err(e) = oci_snapshot::prune_push_jobs(&client, &node, oci_cfg).await {
+                        warn!(
+                            "Failed to prune OCI snapshot push Jobs for {}/{}: {}",
+                            namespace, name, e
+                        );
+                    }
+                }
+
                 // Pull: trigger on bootstrap when the node has never synced (ledger_seq == 0).
                 // This extracts a prior snapshot so the node doesn't need a full catchup.
                 if oci_cfg.pull && ledger_seq == 0 {
@@ -2801,16 +3297,37 @@ pub(crate) fn cleanup_stellar_node(
         info!("Cleaning up StellarNode: {}/{}", namespace, name);
 
         let recorder = recorder_for(&client, &ctx.event_reporter, &node);
-        if let Err(e) = publish_object_event(
-            &recorder,
-            EventType::Normal,
-            "FinalizerCleanupStarted",
-            "Finalize",
-            "Finalizer cleanup started; removing managed Kubernetes resources for this StellarNode.",
-        )
-        .await
-        {
-            warn!("Failed to publish FinalizerCleanupStarted event: {e}");
+        let final_snapshot = match (
+            node.spec.should_delete_pvc(),
+            ctx.dry_run,
+            node.spec.snapshot_schedule.as_ref(),
+        ) {
+            (true, false, Some(config)) => Some(config),
+            _ => None,
+        };
+        // Cleanup is re-run every poll while the final snapshot is pending;
+        // only the first pass, before the snapshot exists, announces it
+        let polling_final_snapshot = match final_snapshot {
+            Some(_) => snapshot::final_snapshot_requested(&client, &node)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Failed to look up final VolumeSnapshot: {:?}", e);
+                    false
+                }),
+            None => false,
+        };
+        if !polling_final_snapshot {
+            if let Err(e) = publish_object_event(
+                &recorder,
+                EventType::Normal,
+                "FinalizerCleanupStarted",
+                "Finalize",
+                "Finalizer cleanup started; removing managed Kubernetes resources for this StellarNode.",
+            )
+            .await
+            {
+                warn!("Failed to publish FinalizerCleanupStarted event: {e}");
+            }
         }
 
         // Delete resources in reverse order of creation
@@ -2860,6 +3377,15 @@ pub(crate) fn cleanup_stellar_node(
         })
         .await?;
 
+        // 2a. Delete PodMonitor (when configured instead of a ServiceMonitor)
+        apply_or_emit!(&ctx, &node, ActionType::Delete, "PodMonitor", move |client: Client, _ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
+            if let Err(e) = resources::delete_pod_monitor(&client, &node).await {
+                warn!("Failed to delete PodMonitor: {:?}", e);
+            }
+            Ok(())
+        })
+        .await?;
+
         // 3. Delete Ingress
         apply_or_emit!(&ctx, &node, ActionType::Delete, "Ingress", move |client: Client, ctx: Arc<ControllerState>, node: Arc<StellarNode>| async move {
             if let Err(e) = resources::delete_ingress(&client, &node, ctx.dry_run).await {
@@ -2941,6 +3467,58 @@ pub(crate) fn cleanup_stellar_node(
         })
         .await?;
 
+        // 6a. Take a final snapshot of the data PVC before it is deleted
+        if let Some(config) = final_snapshot {
+            let snapshot_name = snapshot::final_snapshot_name(&node);
+            match snapshot::ensure_final_snapshot(&client, &node, config).await {
+                Err(e) => {
+                    let message = format!(
+                        "Failed to take final VolumeSnapshot {snapshot_name}: {e}; deleting the data PVC without it."
+                    );
+                    warn!("{}/{}: {}", namespace, name, message);
+                    if let Err(e) = publish_object_event(
+                        &recorder,
+                        EventType::Warning,
+                        "FinalSnapshotFailed",
+                        "Finalize",
+                        &message,
+                    )
+                    .await
+                    {
+                        warn!("Failed to publish FinalSnapshotFailed event: {e}");
+                    }
+                }
+                Ok(FinalSnapshotState::Ready) => {
+                    info!("Final VolumeSnapshot {}/{} is ready", namespace, snapshot_name);
+                }
+                Ok(FinalSnapshotState::Pending) => {
+                    info!(
+                        "Waiting for final VolumeSnapshot {}/{} before deleting PVC",
+                        namespace, snapshot_name
+                    );
+                    return Ok(Action::requeue(Duration::from_secs(snapshot::FINAL_SNAPSHOT_POLL_SECS)));
+                }
+                Ok(FinalSnapshotState::TimedOut) => {
+                    let message = format!(
+                        "Final VolumeSnapshot {snapshot_name} was not ready after {}s; deleting the data PVC without it.",
+                        snapshot::FINAL_SNAPSHOT_TIMEOUT_SECS
+                    );
+                    warn!("{}/{}: {}", namespace, name, message);
+                    if let Err(e) = publish_object_event(
+                        &recorder,
+                        EventType::Warning,
+                        "FinalSnapshotTimedOut",
+                        "Finalize",
+                        &message,
+                    )
+                    .await
+                    {
+                        warn!("Failed to publish FinalSnapshotTimedOut event: {e}");
+                    }
+                }
+            }
+        }
+
         // 7. Delete PVC based on retention policy
         if node.spec.should_delete_pvc() {
             info!(
@@ -2961,6 +3539,10 @@ pub(crate) fn cleanup_stellar_node(
             );
         }
 
+        // Forget the network ledger observations, so a node recreated under
+        // the same name does not measure its close time from stale ones
+        metrics::forget_network_ledger(&namespace, &name);
+
         info!("Cleanup complete for StellarNode: {}/{}", namespace, name);
 
         // Return await_change to signal finalizer completion
@@ -3015,6 +3597,85 @@ async fn get_ready_replicas(client: &Client, node: &StellarNode) -> Result<i32>
     }
 }
 
+/// The node's Deployment, if it can be read
+async fn get_deployment(client: &Client, node: &StellarNode) -> Option<Deployment> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+    let api: Api<Deployment> = Api::namespaced(client.clone(), &namespace);
+    match api.get(&name).await {
+        Ok(deployment) => Some(deployment),
+        Err(e) => {
+            warn!("Failed to get Deployment {}/{}: {:?}", namespace, name, e);
+            None
+        }
+    }
+}
+
+/// Desired and ready replicas of a node's Deployment
+///
+/// The desired count comes from the Deployment, since an HPA may have scaled
+/// it away from `spec.replicas`; that is used when the Deployment is missing.
+fn deployment_replicas(node: &StellarNode, deployment: Option<&Deployment>) -> (i32, i32) {
+    let desired = deployment
+        .and_then(|d| d.spec.as_ref())
+        .and_then(|s| s.replicas)
+        .unwrap_or(node.spec.replicas);
+    let ready = deployment
+        .and_then(|d| d.status.as_ref())
+        .and_then(|s| s.ready_replicas)
+        .unwrap_or(0);
+    (desired, ready)
+}
+
+/// Reason and message of a rollout the Deployment controller stopped
+/// waiting for, from a `Progressing` condition that turned False (usually
+/// `ProgressDeadlineExceeded` once `progressDeadlineSeconds` passed)
+pub(crate) fn stuck_rollout(deployment: &Deployment) -> Option<(String, String)> {
+    deployment
+        .status
+        .as_ref()?
+        .conditions
+        .as_ref()?
+        .iter()
+        .find(|c| c.type_ == "Progressing" && c.status == "False")
+        .map(|c| {
+            (
+                c.reason
+                    .clone()
+                    .unwrap_or_else(|| "ProgressDeadlineExceeded".to_string()),
+                c.message.clone().unwrap_or_default(),
+            )
+        })
+}
+
+/// Mark the node Degraded with `reason`: the one the Deployment gave for its
+/// stuck rollout, or why the validator fell out of consensus
+pub(crate) fn set_degraded(conditions: &mut Vec<Condition>, reason: &str, message: &str) {
+    conditions::set_condition(
+        conditions,
+        conditions::CONDITION_TYPE_DEGRADED,
+        conditions::CONDITION_STATUS_TRUE,
+        reason,
+        message,
+    );
+}
+
+/// Replicas a Horizon node needs ready: the Deployment's `desired` count,
+/// and no fewer than `autoscaling.minReplicas`
+pub(crate) fn required_horizon_replicas(node: &StellarNode, desired: i32) -> i32 {
+    let min_replicas = node
+        .spec
+        .autoscaling
+        .as_ref()
+        .map_or(0, |autoscaling| autoscaling.min_replicas);
+    desired.max(min_replicas)
+}
+
+/// Whether `ready` replicas are enough for a Horizon node to be Ready
+pub(crate) fn horizon_replicas_ready(node: &StellarNode, desired: i32, ready: i32) -> bool {
+    ready >= required_horizon_replicas(node, desired)
+}
+
 /// Get the current version of the stable deployment
 #[instrument(skip(client, node), fields(name = %node.name_any(), namespace = node.namespace()))]
 async fn get_current_deployment_version(
@@ -3158,6 +3819,21 @@ async fn measure_canary_error_rate(
     Ok(errors as f64 / sample_count as f64)
 }
 
+/// Reasons of the Ready condition while a node is suspended, as set by
+/// [`update_suspended_status`] and [`apply_phase_conditions`]
+const SUSPENDED_READY_REASONS: &[&str] = &["NodeSuspended", "Suspended"];
+
+/// Whether a suspended node has just been un-suspended: the spec is active
+/// again while the status still reports the suspension
+pub(crate) fn is_resuming(node: &StellarNode) -> bool {
+    let Some(status) = node.status.as_ref().filter(|_| !node.spec.suspended) else {
+        return false;
+    };
+    status.is_suspended()
+        || conditions::find_condition(&status.conditions, conditions::CONDITION_TYPE_READY)
+            .is_some_and(|c| SUSPENDED_READY_REASONS.contains(&c.reason.as_str()))
+}
+
 /// Update status for suspended nodes
 #[allow(deprecated)]
 #[instrument(skip(client, node), fields(name = %node.name_any(), namespace = node.namespace()))]
@@ -3188,6 +3864,7 @@ async fn update_suspended_status(client: &Client, node: &StellarNode) -> Result<
     );
     conditions::remove_condition(&mut conditions, conditions::CONDITION_TYPE_PROGRESSING);
     conditions::remove_condition(&mut conditions, conditions::CONDITION_TYPE_DEGRADED);
+    conditions::set_suspended(&mut conditions, true);
 
     // Set observed generation on conditions
     if let Some(gen) = node.metadata.generation {
@@ -3197,6 +3874,7 @@ async fn update_suspended_status(client: &Client, node: &StellarNode) -> Result<
     }
 
     let status = StellarNodeStatus {
+        phase: "Suspended".to_string(),
         message: Some("Node suspended - scaled to 0 replicas".to_string()),
         observed_generation: node.metadata.generation,
         replicas: 0,
@@ -3405,6 +4083,30 @@ pub(crate) fn apply_phase_conditions(
                 "Node is under remediation and not currently available",
             );
         }
+        "Restoring" => {
+            conditions::set_condition(
+                conditions,
+                conditions::CONDITION_TYPE_READY,
+                conditions::CONDITION_STATUS_FALSE,
+                "Restoring",
+                message.unwrap_or("Point-in-time restore in progress"),
+            );
+            conditions::set_condition(
+                conditions,
+                conditions::CONDITION_TYPE_PROGRESSING,
+                conditions::CONDITION_STATUS_TRUE,
+                "Restoring",
+                message.unwrap_or("Point-in-time restore in progress"),
+            );
+            conditions::remove_condition(conditions, conditions::CONDITION_TYPE_DEGRADED);
+            conditions::set_condition(
+                conditions,
+                conditions::CONDITION_TYPE_AVAILABLE,
+                conditions::CONDITION_STATUS_FALSE,
+                "Restoring",
+                "Node is being restored and is not available",
+            );
+        }
         "Suspended" => {
             conditions::set_condition(
                 conditions,
@@ -3460,8 +4162,6 @@ pub(crate) fn apply_phase_conditions(
     }
 }
 
-#[allow(deprecated)]
-#[instrument(skip(client, node, message), fields(name = %node.name_any(), namespace = node.namespace(), phase))]
 async fn update_status(
     client: &Client,
     node: &StellarNode,
@@ -3469,6 +4169,30 @@ async fn update_status(
     message: Option<String>,
     ready_replicas: i32,
     update_obs_gen: bool,
+) -> Result<()> {
+    update_status_with_conditions(
+        client,
+        node,
+        phase,
+        message,
+        ready_replicas,
+        update_obs_gen,
+        |_| {},
+    )
+    .await
+}
+
+/// Like [`update_status`], letting `adjust` amend the phase conditions
+#[allow(deprecated)]
+#[instrument(skip(client, node, message, adjust), fields(name = %node.name_any(), namespace = node.namespace(), phase))]
+async fn update_status_with_conditions(
+    client: &Client,
+    node: &StellarNode,
+    phase: &str,
+    message: Option<String>,
+    ready_replicas: i32,
+    update_obs_gen: bool,
+    adjust: impl FnOnce(&mut Vec<Condition>),
 ) -> Result<()> {
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let api: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
@@ -3489,6 +4213,12 @@ async fn update_status(
         .unwrap_or_default();
 
     apply_phase_conditions(&mut conditions, phase, message.as_deref());
+    adjust(&mut conditions);
+    conditions::set_suspended(&mut conditions, node.spec.suspended);
+    conditions::set_data_retention_risk(
+        &mut conditions,
+        node.spec.retention_backup_conflict().as_deref(),
+    );
 
     // Set observed generation on all conditions
     if let Some(gen) = observed_generation {
@@ -3515,6 +4245,31 @@ async fn update_status(
     if let Some(msg) = message {
         status_patch["message"] = serde_json::Value::String(msg.to_string());
     }
+    let rollout = if phase == "Ready" {
+        super::version_rollback::fetch_workload_rollout(client, node)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(
+                    "Failed to read the workload rollout of {}: {:?}",
+                    node.name_any(),
+                    e
+                );
+                None
+            })
+    } else {
+        None
+    };
+    if let Some(version) = super::version_rollback::last_good_version(rollout.as_ref(), phase) {
+        status_patch["lastGoodVersion"] = serde_json::Value::String(version.to_string());
+    }
+    if let Some(history) = super::version_rollback::next_version_history(
+        node,
+        rollout.as_ref(),
+        phase,
+        &Utc::now().to_rfc3339(),
+    ) {
+        status_patch["versionHistory"] = serde_json::json!(history);
+    }
 
     let patch = serde_json::json!({ "status": status_patch });
     api.patch_status(
@@ -3534,7 +4289,8 @@ async fn update_status(
 /// Fetches `stellar-history.json` from each configured archive, compares the reported
 /// ledger sequence to the node's current ledger, and:
 /// - Sets / clears the `ArchiveIntegrityDegraded` condition on the node's status.
-/// - Updates the `stellar_archive_ledger_lag` Prometheus gauge so alert rules can fire.
+/// - Updates the `stellar_archive_ledger_lag` and `stellar_archive_lag_threshold` Prometheus
+///   gauges so alert rules can compare the lag against the node's own threshold.
 ///
 /// The function is intentionally fire-and-forget on individual per-URL errors so that a
 /// single unreachable archive does not block the rest of reconciliation.
@@ -3569,7 +4325,8 @@ async fn run_archive_integrity_check(
         namespace, name, node_ledger
     );
 
-    let results = check_archive_integrity(archive_urls, node_ledger, None).await;
+    let lag_threshold = archive_lag_threshold(node);
+    let results = check_archive_integrity(archive_urls, node_ledger, lag_threshold, None).await;
 
     // Determine the overall worst-case lag across all archives.
     let degraded_archives: Vec<_> = results.iter().filter(|r| !r.is_healthy()).collect();
@@ -3588,6 +4345,23 @@ async fn run_archive_integrity_check(
         &hardware_generation,
         max_lag as i64,
     );
+    #[cfg(feature = "metrics")]
+    metrics::set_archive_lag_threshold(
+        &namespace,
+        &name,
+        &node.spec.node_type.to_string(),
+        node.spec.network_passphrase(),
+        &hardware_generation,
+        lag_threshold as i64,
+    );
+
+    // Track how many checks in a row each archive has been behind.
+    let previous_strikes = node
+        .status
+        .as_ref()
+        .map(|s| s.archive_lag_strikes.clone())
+        .unwrap_or_default();
+    let strikes = archive_rotation::update_lag_strikes(&previous_strikes, &results);
 
     // Patch the Degraded condition on the node status.
     let api: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
@@ -3620,7 +4394,7 @@ async fn run_archive_integrity_check(
             conditions::CONDITION_STATUS_TRUE,
             "ArchiveLagging",
             &format!(
-                "Archive lag exceeds threshold of {ARCHIVE_LAG_THRESHOLD} ledgers. Max lag={max_lag}. {message}"
+                "Archive lag exceeds threshold of {lag_threshold} ledgers. Max lag={max_lag}. {message}"
             ),
         );
     } else {
@@ -3633,20 +4407,65 @@ async fn run_archive_integrity_check(
             &format!(
                 "All {} archive(s) are within {} ledgers of the node",
                 results.len(),
-                ARCHIVE_LAG_THRESHOLD
+                lag_threshold
             ),
         );
     }
 
-    let patch = serde_json::json!({ "status": { "conditions": conds } });
+    // Rotate a persistently stale primary archive. The new primary is recorded
+    // in status, which reorders stellar-core.cfg and the primary-archive pod
+    // annotation on the next reconcile, rolling the pods. The spec is left alone.
+    let rotation_cfg = node
+        .spec
+        .validator_config
+        .as_ref()
+        .and_then(|vc| vc.archive_rotation.as_ref())
+        .filter(|cfg| cfg.enabled);
+    let rotation = rotation_cfg.and_then(|rotation_cfg| {
+        let primary = node
+            .status
+            .as_ref()
+            .and_then(|s| s.primary_history_archive.as_deref());
+        archive_rotation::rotation_decision(
+            &archive_rotation::archive_order(archive_urls, primary),
+            &strikes,
+            &results,
+            rotation_cfg.consecutive_checks,
+        )
+    });
+
+    let mut status = serde_json::json!({ "conditions": conds, "archiveLagStrikes": strikes });
+    if let Some(rotation) = &rotation {
+        status["primaryHistoryArchive"] = serde_json::json!(rotation.to);
+    }
     api.patch_status(
         &name,
         &PatchParams::apply("stellar-operator"),
-        &Patch::Merge(&patch),
+        &Patch::Merge(&serde_json::json!({ "status": status })),
     )
     .await
     .map_err(Error::KubeError)?;
 
+    if let (Some(rotation_cfg), Some(rotation)) = (rotation_cfg, rotation) {
+        info!(
+            "Rotating primary history archive for {}/{} from {} to {}",
+            namespace, name, rotation.from, rotation.to
+        );
+        publish_stellar_event!(
+            client,
+            reporter,
+            node,
+            EventType::Normal,
+            "ArchiveRotated",
+            "ArchiveRotation",
+            &format!(
+                "Primary history archive {} lagged for {} consecutive checks; rotated to {}",
+                rotation.from, rotation_cfg.consecutive_checks, rotation.to
+            ),
+        )
+        .await?;
+    }
+
     Ok(())
 }
 
@@ -3808,6 +4627,7 @@ async fn update_status_with_health(
         );
         conditions::remove_condition(&mut conditions, conditions::CONDITION_TYPE_PROGRESSING);
     }
+    conditions::set_suspended(&mut conditions, node.spec.suspended);
 
     // Set observed generation on all conditions
     if let Some(gen) = node.metadata.generation {
@@ -4021,30 +4841,44 @@ fn parse_duration(s: &str) -> Result<Duration> {
     }
 }
 
-/// Helper to get the latest ledger from the Stellar network
-async fn get_latest_network_ledger(network: &crate::crd::StellarNetwork) -> Result<u64> {
-    let url = match network {
-        crate::crd::StellarNetwork::Mainnet => "https://horizon.stellar.org",
-        crate::crd::StellarNetwork::Testnet => "https://horizon-testnet.stellar.org",
-        crate::crd::StellarNetwork::Futurenet => "https://horizon-futurenet.stellar.org",
-        crate::crd::StellarNetwork::Custom(_) => {
-            return Err(Error::ConfigError(
-                "Custom network not supported for lag calculation yet".to_string(),
-            ))
-        }
-    };
+/// Helper to get the latest ledger from the Stellar network, asking the
+/// Horizon or stellar-core picked by [`network_config::latest_ledger_url`].
+/// Results are shared across reconciles for a few seconds, see
+/// [`ledger_cache`].
+async fn get_latest_network_ledger(
+    client: &Client,
+    node: &StellarNode,
+    operator_config: &OperatorConfig,
+) -> Result<u64> {
+    let config = network_config::get_network_config(client, node).await?;
+    let url = network_config::latest_ledger_url(
+        &node.spec.network,
+        config.as_ref().map(|c| &c.spec),
+        operator_config,
+    )
+    .ok_or_else(|| {
+        Error::ConfigError(
+            "Custom network needs a horizonUrl or ledgerSources entry for lag calculation"
+                .to_string(),
+        )
+    })?;
+
+    ledger_cache::latest_ledger_cache()
+        .get_or_fetch(&url, || fetch_latest_ledger(&url))
+        .await
+}
 
-    let client = reqwest::Client::new();
-    let resp = client.get(url).send().await.map_err(Error::HttpError)?;
+/// Ask a Horizon or stellar-core for its latest ledger
+async fn fetch_latest_ledger(url: &str) -> Result<u64> {
+    let http = reqwest::Client::new();
+    let resp = http.get(url).send().await.map_err(Error::HttpError)?;
     let json: serde_json::Value = resp
         .json()
         .await
         .map_err(|e| Error::ConfigError(e.to_string()))?;
 
-    let ledger = json["history_latest_ledger"].as_u64().ok_or_else(|| {
-        Error::ConfigError("Failed to get latest ledger from horizon".to_string())
-    })?;
-    Ok(ledger)
+    ledger_cache::parse_latest_ledger(&json)
+        .ok_or_else(|| Error::ConfigError(format!("Failed to get latest ledger from {url}")))
 }
 /// Update the status with DR results
 #[instrument(skip(client, node, dr_status), fields(name = %node.name_any(), namespace = node.namespace()))]
@@ -4073,6 +4907,57 @@ async fn update_dr_status(
     Ok(())
 }
 
+/// Record a finished backup Job in the node status and metrics
+async fn update_backup_status(
+    client: &Client,
+    ctx: &ControllerState,
+    node: &StellarNode,
+    result: &oci_snapshot::BackupResult,
+) -> Result<()> {
+    #[cfg(feature = "metrics")]
+    if result.succeeded {
+        let hardware_generation = hardware_generation_for_metrics(client, node).await;
+        metrics::set_last_backup_timestamp(
+            &node.namespace().unwrap_or_else(|| "default".to_string()),
+            &node.name_any(),
+            &node.spec.node_type.to_string(),
+            node.spec.network_passphrase(),
+            &hardware_generation,
+            result.finished_at.timestamp(),
+        );
+    }
+
+    let Some(patch) = result.status_patch(node) else {
+        return Ok(());
+    };
+
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
+    api.patch_status(
+        &node.name_any(),
+        &PatchParams::apply("stellar-operator"),
+        &Patch::Merge(&patch),
+    )
+    .await
+    .map_err(Error::KubeError)?;
+
+    if !result.succeeded {
+        publish_stellar_event!(
+            client,
+            &ctx.event_reporter,
+            node,
+            EventType::Warning,
+            "BackupFailed",
+            "Snapshot",
+            &format!("Snapshot push Job {} failed", result.job_name),
+        )
+        .await
+        .ok();
+    }
+
+    Ok(())
+}
+
 async fn update_cross_cloud_failover_status(
     client: &Client,
     node: &StellarNode,
@@ -4099,7 +4984,8 @@ async fn update_cross_cloud_failover_status(
 }
 
 /// Public entry point for state-machine fuzzing. Calls the same reconcile logic as the controller.
-/// Only compiled when the `reconciler-fuzz` feature is enabled.
+/// Only compiled when the `reconciler-fuzz` feature is enabled; with `in-memory-kube` it can be
+/// driven without a cluster through `controller::test_harness::in_memory_client`.
 #[cfg(feature = "reconciler-fuzz")]
 pub async fn reconcile_for_fuzz(
     obj: Arc<StellarNode>,
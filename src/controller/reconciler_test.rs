@@ -12,8 +12,8 @@ mod tests {
     use super::super::reconciler::*;
     use crate::controller::{AnomalyDetector, AuditLog, AuditRecorder, JobRegistry};
     use crate::crd::{
-        CaptiveCoreConfig, Condition, HorizonConfig, ManagedDatabaseConfig, NodeType,
-        ResourceRequirements, ResourceSpec, SorobanConfig, StellarNetwork, StellarNode,
+        AutoscalingConfig, CaptiveCoreConfig, Condition, HorizonConfig, ManagedDatabaseConfig,
+        NodeType, ResourceRequirements, ResourceSpec, SorobanConfig, StellarNetwork, StellarNode,
         StellarNodeSpec, StorageConfig, ValidatorConfig,
     };
     use crate::error::Error;
@@ -65,6 +65,8 @@ mod tests {
                 },
                 storage: StorageConfig {
                     storage_class: "standard".to_string(),
+                    tier: None,
+                    access_modes: vec![],
                     size: "100Gi".to_string(),
                     retention_policy: Default::default(),
                     annotations: None,
@@ -83,6 +85,10 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
                     history_archive_urls: vec![
                         "https://history.stellar.org/prd/core-testnet/core_testnet_001".to_string(),
                     ],
+                    archive_lag_threshold: None,
+                    archive_rotation: None,
+                    bucket_list_db: None,
+                    archive_server: None,
                     catchup_complete: false,
                     key_source: Default::default(),
                     kms_config: None,
@@ -168,6 +174,8 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
                 },
                 storage: StorageConfig {
                     storage_class: "standard".to_string(),
+                    tier: None,
+                    access_modes: vec![],
                     size: "50Gi".to_string(),
                     retention_policy: Default::default(),
                     annotations: None,
@@ -181,6 +189,10 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
                     ingest_workers: 2,
                     enable_experimental_ingestion: false,
                     auto_migration: true,
+                    migration_max_attempts: None,
+                    migration_backoff_seconds: None,
+                    admin_port: None,
+                    per_hour_rate_limit: None,
                 }),
                 soroban_config: None,
                 replicas: 2,
@@ -193,6 +205,8 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
                     instances: 2,
                     storage: StorageConfig {
                         storage_class: "standard".to_string(),
+                        tier: None,
+                        access_modes: vec![],
                         size: "20Gi".to_string(),
                         retention_policy: Default::default(),
                         annotations: None,
@@ -268,6 +282,8 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
                 },
                 storage: StorageConfig {
                     storage_class: "fast".to_string(),
+                    tier: None,
+                    access_modes: vec![],
                     size: "200Gi".to_string(),
                     retention_policy: Default::default(),
                     annotations: None,
@@ -366,6 +382,7 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
             watch_namespace: None,
             mtls_config: None,
             dry_run: true,
+            disable_finalizers: false,
             retry_budget_retriable_secs: 15,
             retry_budget_nonretriable_secs: 60,
             retry_budget_max_attempts: 3,
@@ -422,6 +439,7 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
             watch_namespace: None,
             mtls_config: None,
             dry_run: true,
+            disable_finalizers: false,
             retry_budget_retriable_secs: 15,
             retry_budget_nonretriable_secs: 60,
             retry_budget_max_attempts: 3,
@@ -477,6 +495,7 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
             watch_namespace: None,
             mtls_config: None,
             dry_run: true,
+            disable_finalizers: false,
             retry_budget_retriable_secs: 15,
             retry_budget_nonretriable_secs: 60,
             retry_budget_max_attempts: 3,
@@ -593,6 +612,187 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
         );
     }
 
+    fn with_ready_reason(mut node: StellarNode, reason: &str) -> StellarNode {
+        node.status = Some(crate::crd::StellarNodeStatus {
+            conditions: vec![crate::controller::conditions::not_ready_condition(
+                reason,
+                "Node is suspended",
+            )],
+            ..Default::default()
+        });
+        node
+    }
+
+    /// Test that un-suspending a node is detected from the status it left behind
+    #[test]
+    fn test_resuming_after_suspension() {
+        let node = create_test_validator_node("test-resume", "default");
+        assert!(!is_resuming(&node), "a fresh node is not resuming");
+
+        for reason in ["NodeSuspended", "Suspended"] {
+            let mut node = with_ready_reason(node.clone(), reason);
+            assert!(
+                is_resuming(&node),
+                "un-suspended with Ready reason {reason}"
+            );
+
+            node.spec.suspended = true;
+            assert!(!is_resuming(&node), "still suspended");
+        }
+
+        let syncing = with_ready_reason(node, "NodeSyncing");
+        assert!(!is_resuming(&syncing));
+    }
+
+    /// Test that a multi-replica Horizon needs every desired replica ready
+    #[test]
+    fn test_horizon_multi_replica_readiness() {
+        let mut node = create_test_horizon_node("test-horizon", "default");
+        assert_eq!(node.spec.replicas, 2);
+
+        assert!(horizon_replicas_ready(&node, 2, 2));
+        assert!(horizon_replicas_ready(&node, 2, 3));
+        assert!(!horizon_replicas_ready(&node, 2, 1));
+        assert!(!horizon_replicas_ready(&node, 2, 0));
+
+        // An HPA that scaled the Deployment up raises the bar
+        assert!(!horizon_replicas_ready(&node, 4, 3));
+        assert!(horizon_replicas_ready(&node, 4, 4));
+
+        node.spec.autoscaling = Some(AutoscalingConfig {
+            min_replicas: 3,
+            max_replicas: 6,
+            ..Default::default()
+        });
+        assert_eq!(required_horizon_replicas(&node, 2), 3);
+        assert!(!horizon_replicas_ready(&node, 2, 2));
+        assert!(horizon_replicas_ready(&node, 2, 3));
+        assert_eq!(required_horizon_replicas(&node, 5), 5);
+        assert!(!horizon_replicas_ready(&node, 5, 4));
+    }
+
+    /// Test that a change to a ConfigMap or PDB the operator rendered
+    /// enqueues a reconcile of the node that owns it
+    #[tokio::test]
+    async fn test_owned_configmap_and_pdb_changes_enqueue_owner() {
+        use crate::controller::resources::{build_config_map_for_test, build_pdb_for_test};
+        use futures::{stream, StreamExt};
+        use kube::runtime::controller::trigger_owners;
+        use kube::runtime::reflector::ObjectRef;
+
+        let node = create_test_validator_node("test-owned", "stellar");
+        let expected = ObjectRef::from_obj(&node);
+
+        let mut config_map = build_config_map_for_test(&node);
+        config_map
+            .data
+            .get_or_insert_with(Default::default)
+            .insert("edited".to_string(), "by hand".to_string());
+        let requests: Vec<_> = trigger_owners::<StellarNode, _>(
+            stream::iter([Ok::<_, std::convert::Infallible>(config_map)]),
+            (),
+            (),
+        )
+        .collect()
+        .await;
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].as_ref().unwrap().obj_ref, expected);
+
+        let pdb = build_pdb_for_test(&node).expect("validators get a PDB");
+        let requests: Vec<_> = trigger_owners::<StellarNode, _>(
+            stream::iter([Ok::<_, std::convert::Infallible>(pdb)]),
+            (),
+            (),
+        )
+        .collect()
+        .await;
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].as_ref().unwrap().obj_ref, expected);
+    }
+
+    fn deployment_with_progressing(
+        status: &str,
+        reason: &str,
+        message: &str,
+    ) -> k8s_openapi::api::apps::v1::Deployment {
+        use k8s_openapi::api::apps::v1::{Deployment, DeploymentCondition, DeploymentStatus};
+
+        Deployment {
+            status: Some(DeploymentStatus {
+                conditions: Some(vec![
+                    DeploymentCondition {
+                        type_: "Available".to_string(),
+                        status: "True".to_string(),
+                        reason: Some("MinimumReplicasAvailable".to_string()),
+                        ..Default::default()
+                    },
+                    DeploymentCondition {
+                        type_: "Progressing".to_string(),
+                        status: status.to_string(),
+                        reason: Some(reason.to_string()),
+                        message: Some(message.to_string()),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Test that a Deployment past its progress deadline is reported stuck
+    #[test]
+    fn test_stuck_rollout_detection() {
+        let stuck = deployment_with_progressing(
+            "False",
+            "ProgressDeadlineExceeded",
+            "ReplicaSet \"horizon-7d9f\" has timed out progressing.",
+        );
+        assert_eq!(
+            stuck_rollout(&stuck),
+            Some((
+                "ProgressDeadlineExceeded".to_string(),
+                "ReplicaSet \"horizon-7d9f\" has timed out progressing.".to_string()
+            ))
+        );
+
+        let progressing = deployment_with_progressing("True", "ReplicaSetUpdated", "rolling out");
+        assert_eq!(stuck_rollout(&progressing), None);
+
+        let complete =
+            deployment_with_progressing("True", "NewReplicaSetAvailable", "rollout complete");
+        assert_eq!(stuck_rollout(&complete), None);
+
+        assert_eq!(
+            stuck_rollout(&k8s_openapi::api::apps::v1::Deployment::default()),
+            None
+        );
+    }
+
+    /// Test that a stuck rollout surfaces as Degraded with the Deployment's reason
+    #[test]
+    fn test_stuck_rollout_sets_degraded_reason() {
+        use crate::controller::conditions::{
+            find_condition, CONDITION_STATUS_FALSE, CONDITION_STATUS_TRUE, CONDITION_TYPE_DEGRADED,
+            CONDITION_TYPE_READY,
+        };
+
+        let mut conditions = Vec::new();
+        apply_phase_conditions(&mut conditions, "Degraded", Some("Rollout is stuck"));
+        set_degraded(
+            &mut conditions,
+            "ProgressDeadlineExceeded",
+            "timed out progressing",
+        );
+
+        let degraded = find_condition(&conditions, CONDITION_TYPE_DEGRADED).unwrap();
+        assert_eq!(degraded.status, CONDITION_STATUS_TRUE);
+        assert_eq!(degraded.reason, "ProgressDeadlineExceeded");
+        assert_eq!(degraded.message, "timed out progressing");
+        let ready = find_condition(&conditions, CONDITION_TYPE_READY).unwrap();
+        assert_eq!(ready.status, CONDITION_STATUS_FALSE);
+    }
+
     /// Test node metadata structure for different node types
     #[test]
     fn test_node_metadata_structure() {
@@ -724,6 +924,7 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
             watch_namespace: None,
             mtls_config: None,
             dry_run: false,
+            disable_finalizers: false,
             retry_budget_retriable_secs: 15,
             retry_budget_nonretriable_secs: 60,
             retry_budget_max_attempts: 3,
@@ -775,6 +976,7 @@ VALIDATORS=["VALIDATOR1", "VALIDATOR2"]"#
             watch_namespace: None,
             mtls_config: None,
             dry_run: true,
+            disable_finalizers: false,
             retry_budget_retriable_secs: 15,
             retry_budget_nonretriable_secs: 60,
             retry_budget_max_attempts: 3,
@@ -5,13 +5,22 @@
 
 use crate::controller::resource_meta::merge_resource_meta;
 
+use super::archive_rotation::{archive_order, PRIMARY_ARCHIVE_ANNOTATION};
+use super::archive_server;
 // *** NEW: import kms_secret so we can accept SeedInjectionSpec ***
 use super::kms_secret;
 use super::label_propagation::LabelPropagator;
+use super::operator_config::{MetricsEndpointConfig, MetricsMonitorKind, MetricsTlsConfig};
+use super::otel_collector;
+use super::pss;
+use super::storage_tier;
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use k8s_openapi::api::apps::v1::{Deployment, DeploymentSpec, StatefulSet, StatefulSetSpec};
+use k8s_openapi::api::apps::v1::{
+    Deployment, DeploymentSpec, DeploymentStrategy, RollingUpdateDeployment, StatefulSet,
+    StatefulSetSpec,
+};
 use k8s_openapi::api::autoscaling::v2::{
     CrossVersionObjectReference, HPAScalingPolicy, HPAScalingRules, HorizontalPodAutoscaler,
     HorizontalPodAutoscalerBehavior, HorizontalPodAutoscalerSpec, MetricIdentifier, MetricSpec,
@@ -19,10 +28,10 @@ use k8s_openapi::api::autoscaling::v2::{
 };
 use k8s_openapi::api::core::v1::{
     Affinity, Capabilities, ConfigMap, Container, ContainerPort, EnvVar, EnvVarSource,
-    PersistentVolumeClaim, PersistentVolumeClaimSpec, PodAffinityTerm, PodAntiAffinity,
-    PodSecurityContext, PodSpec, PodTemplateSpec, ResourceRequirements as K8sResources,
-    SeccompProfile, SecretKeySelector, SecurityContext, Service, ServicePort, ServiceSpec,
-    Toleration, TypedLocalObjectReference, Volume, VolumeMount, VolumeResourceRequirements,
+    PersistentVolumeClaim, PersistentVolumeClaimSpec, PodAffinityTerm, PodAntiAffinity, PodSpec,
+    PodTemplateSpec, ResourceRequirements as K8sResources, SeccompProfile, SecretKeySelector,
+    SecurityContext, Service, ServiceAccount, ServicePort, ServiceSpec, Toleration,
+    TypedLocalObjectReference, Volume, VolumeMount, VolumeResourceRequirements,
     WeightedPodAffinityTerm,
 };
 use k8s_openapi::api::networking::v1::{
@@ -31,22 +40,26 @@ use k8s_openapi::api::networking::v1::{
     NetworkPolicyPeer, NetworkPolicyPort, NetworkPolicySpec, ServiceBackendPort,
 };
 use k8s_openapi::api::policy::v1::{PodDisruptionBudget, PodDisruptionBudgetSpec};
+use k8s_openapi::api::rbac::v1::{Role, RoleBinding, RoleRef, Subject};
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta, OwnerReference};
 use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
 use kube::api::{
-    Api, ApiResource, DeleteParams, DynamicObject, GroupVersionKind, Patch, PatchParams, PostParams,
+    Api, ApiResource, DeleteParams, DynamicObject, GroupVersionKind, ListParams, Patch,
+    PatchParams, PostParams,
 };
 use kube::{Client, Resource, ResourceExt};
 use tracing::{info, instrument, warn};
 
-use crate::crd::types::{PodAntiAffinityStrength, ReplicationRole, RolloutStrategyType};
+use crate::crd::types::{
+    PodAntiAffinityStrength, ReplicationRole, RolloutStrategyType, ServiceAccountConfig,
+};
 use crate::crd::{
     BackupConfiguration, BarmanObjectStore, BootstrapConfiguration, Cluster, ClusterSpec,
     ExternalCluster, HistoryMode, HsmProvider, IngressConfig, InitDbConfiguration, KeySource,
     ManagedDatabaseConfig, MonitoringConfiguration, NetworkPolicyConfig, NodeType, PgBouncerSpec,
     Pooler, PoolerCluster, PoolerSpec, PostgresConfiguration, RecoveryConfiguration,
-    ReplicaConfiguration, ResourceRequirements, S3Credentials,
+    RecoveryTarget, ReplicaConfiguration, ResourceRequirements, S3Credentials,
     SecretKeySelector as CnpgSecretKeySelector, StellarNode, StellarNodeSpec, StorageConfiguration,
     WalBackupConfiguration,
 };
@@ -138,6 +151,25 @@ pub(crate) fn resource_name(node: &StellarNode, suffix: &str) -> String {
     format!("{}-{}", node.name_any(), suffix)
 }
 
+/// History archive URLs a validator fetches from, primary first.
+///
+/// The primary is `status.primaryHistoryArchive` when the operator rotated it,
+/// otherwise the first configured URL. Empty unless the node is a validator
+/// with `enableHistoryArchive` set.
+fn primary_ordered_archives(node: &StellarNode) -> Vec<String> {
+    match &node.spec.validator_config {
+        Some(vc) if node.spec.node_type == NodeType::Validator && vc.enable_history_archive => {
+            archive_order(
+                &vc.history_archive_urls,
+                node.status
+                    .as_ref()
+                    .and_then(|s| s.primary_history_archive.as_deref()),
+            )
+        }
+        _ => Vec::new(),
+    }
+}
+
 /// Apply a [`ProbeOverride`] on top of an optional base [`k8s_openapi::api::core::v1::Probe`].
 /// Apply a [`ProbeOverride`] on top of an optional base [`k8s_openapi::api::core::v1::Probe`].
 ///
@@ -354,18 +386,37 @@ pub async fn ensure_pvc(
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let api: Api<PersistentVolumeClaim> = Api::namespaced(client.clone(), &namespace);
     let name = resource_name(node, "data");
+    let existing = api.get_opt(&name).await?;
 
-    // Dynamic resolution of storage class for local mode.
-    let mut has_local_path = false;
-    let mut has_local_storage = false;
-    if node.spec.storage.mode == crate::crd::types::StorageMode::Local
-        && node.spec.storage.storage_class.is_empty()
-    {
-        let sc_api: Api<k8s_openapi::api::storage::v1::StorageClass> = Api::all(client.clone());
-        has_local_path = sc_api.get("local-path").await.is_ok();
-        has_local_storage = sc_api.get("local-storage").await.is_ok();
-    }
-    let resolved_storage_class = resolve_pvc_storage_class(node, has_local_path, has_local_storage);
+    // storageClassName cannot change once the PVC exists: keep it and only
+    // check it against the tier, selecting a class on create alone
+    let existing_storage_class = existing
+        .as_ref()
+        .and_then(|pvc| pvc.spec.as_ref())
+        .and_then(|spec| spec.storage_class_name.clone())
+        .filter(|class| !class.is_empty());
+    let resolved_storage_class = match existing_storage_class {
+        Some(class) => {
+            storage_tier::check_named_storage_class(client, node, &class).await?;
+            class
+        }
+        None => {
+            // Dynamic resolution of storage class for local mode.
+            let mut has_local_path = false;
+            let mut has_local_storage = false;
+            if node.spec.storage.mode == crate::crd::types::StorageMode::Local
+                && node.spec.storage.storage_class.is_empty()
+            {
+                let sc_api: Api<k8s_openapi::api::storage::v1::StorageClass> =
+                    Api::all(client.clone());
+                has_local_path = sc_api.get("local-path").await.is_ok();
+                has_local_storage = sc_api.get("local-storage").await.is_ok();
+            }
+            let resolved_storage_class =
+                resolve_pvc_storage_class(node, has_local_path, has_local_storage);
+            storage_tier::resolve_tiered_storage_class(client, node, resolved_storage_class).await?
+        }
+    };
     if node.spec.storage.mode == crate::crd::types::StorageMode::Local
         && resolved_storage_class.is_empty()
     {
@@ -374,12 +425,11 @@ pub async fn ensure_pvc(
         );
     }
 
-    // Fetch existing resource labels for stale-label removal
-    let existing_labels = match api.get(&name).await {
-        Ok(existing) => existing.metadata.labels.clone().unwrap_or_default(),
-        Err(kube::Error::Api(e)) if e.code == 404 => BTreeMap::new(),
-        Err(e) => return Err(Error::KubeError(e)),
-    };
+    // Existing resource labels for stale-label removal
+    let existing_labels = existing
+        .as_ref()
+        .and_then(|pvc| pvc.metadata.labels.clone())
+        .unwrap_or_default();
 
     let mut pvc = build_pvc(node, resolved_storage_class);
 
@@ -390,8 +440,8 @@ pub async fn ensure_pvc(
         LabelPropagator::remove_stale_labels(&merged, propagated_labels, &existing_labels);
     pvc.metadata.labels = Some(final_labels);
 
-    match api.get(&name).await {
-        Ok(existing) => {
+    match existing {
+        Some(existing) => {
             if pvc_needs_update(&existing, &pvc) {
                 info!("Updating PVC {}", name);
                 api.patch(&name, &patch_params(dry_run), &Patch::Apply(&pvc))
@@ -400,11 +450,10 @@ pub async fn ensure_pvc(
                 info!("PVC {} already exists and is up-to-date", name);
             }
         }
-        Err(kube::Error::Api(e)) if e.code == 404 => {
+        None => {
             info!("Creating PVC {}", name);
             api.create(&post_params(dry_run), &pvc).await?;
         }
-        Err(e) => return Err(Error::KubeError(e)),
     }
 
     Ok(())
@@ -459,13 +508,16 @@ pub(crate) fn build_pvc(node: &StellarNode, storage_class_name: String) -> Persi
     let annotations = node.spec.storage.annotations.clone().unwrap_or_default();
 
     // When restoring from a VolumeSnapshot, set dataSource so the PVC is populated from the snapshot.
-    // Priority: spec.storage.snapshotRef.volumeSnapshotName > spec.restoreFromSnapshot.volumeSnapshotName
-    let data_source = node
-        .spec
-        .storage
-        .snapshot_ref
-        .as_ref()
-        .and_then(|r| r.volume_snapshot_name.as_deref())
+    // Priority: point-in-time restore > spec.storage.snapshotRef.volumeSnapshotName
+    // > spec.restoreFromSnapshot.volumeSnapshotName
+    let data_source = super::pitr::restore_snapshot(node)
+        .or_else(|| {
+            node.spec
+                .storage
+                .snapshot_ref
+                .as_ref()
+                .and_then(|r| r.volume_snapshot_name.as_deref())
+        })
         .or_else(|| {
             node.spec
                 .restore_from_snapshot
@@ -492,10 +544,10 @@ pub(crate) fn build_pvc(node: &StellarNode, storage_class_name: String) -> Persi
                 owner_references: Some(vec![owner_reference(node)]),
                 ..Default::default()
             },
-            &None,
+            &node.spec.pvc_meta,
         ),
         spec: Some(PersistentVolumeClaimSpec {
-            access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+            access_modes: Some(node.spec.storage.pvc_access_modes()),
             storage_class_name: if storage_class_name.is_empty() {
                 None
             } else {
@@ -578,6 +630,10 @@ pub(crate) fn build_config_map(
         NodeType::Validator => {
             let mut core_cfg = String::new();
             if let Some(config) = &node.spec.validator_config {
+                // Top-level keys must come before the [QUORUM_SET] table
+                if let Some(db) = &config.bucket_list_db {
+                    core_cfg.push_str(&db.to_stellar_core_toml());
+                }
                 if let Some(qs) = quorum_override {
                     core_cfg.push_str(&qs.to_stellar_core_toml());
                 } else if let Some(q) = &config.quorum_set {
@@ -604,6 +660,15 @@ pub(crate) fn build_config_map(
                 }
             }
 
+            // History archives, primary first (see archive_rotation)
+            for (idx, url) in primary_ordered_archives(node).iter().enumerate() {
+                core_cfg.push_str(&format!("\n[HISTORY.archive{}]\n", idx + 1));
+                core_cfg.push_str(&format!("get=\"curl -sf {url}/{{0}} -o {{1}}\"\n"));
+            }
+            if archive_server::archive_server_config(node).is_some() {
+                core_cfg.push_str(&archive_server::archive_history_config());
+            }
+
             if !core_cfg.is_empty() {
                 data.insert("stellar-core.cfg".to_string(), core_cfg);
             }
@@ -742,6 +807,29 @@ pub async fn delete_config_map(client: &Client, node: &StellarNode, dry_run: boo
     Ok(())
 }
 
+/// Kind of workload a node type runs as: a StatefulSet for validators, a
+/// Deployment for Horizon and Soroban RPC
+pub fn workload_kind(node_type: &NodeType) -> &'static str {
+    match node_type {
+        NodeType::Validator => "StatefulSet",
+        NodeType::Horizon | NodeType::SorobanRpc => "Deployment",
+    }
+}
+
+/// Refuse to build a `kind` workload for a node that runs as the other kind
+fn check_workload_kind(node: &StellarNode, kind: &str) -> Result<()> {
+    let expected = workload_kind(&node.spec.node_type);
+    if expected == kind {
+        return Ok(());
+    }
+    Err(Error::ValidationError(format!(
+        "{}/{} is a {:?} node and runs as a {expected}, not a {kind}",
+        node.namespace().unwrap_or_default(),
+        node.name_any(),
+        node.spec.node_type
+    )))
+}
+
 // ============================================================================
 // Deployment (for Horizon and Soroban RPC)
 // ============================================================================
@@ -755,6 +843,7 @@ pub async fn ensure_deployment(
     propagated_labels: &BTreeMap<String, String>,
     dry_run: bool,
 ) -> Result<()> {
+    check_workload_kind(node, "Deployment")?;
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let api: Api<Deployment> = Api::namespaced(client.clone(), &namespace);
     let name = node.name_any();
@@ -866,12 +955,31 @@ fn build_deployment(node: &StellarNode, enable_mtls: bool) -> Deployment {
             },
             // Deployments (Horizon/SorobanRpc) never need seed injection → pass None
             template: build_pod_template(node, &labels, enable_mtls, None),
+            strategy: rolling_update_strategy(node),
+            min_ready_seconds: node.spec.strategy.min_ready_seconds,
+            progress_deadline_seconds: node.spec.strategy.progress_deadline_seconds,
             ..Default::default()
         }),
         status: None,
     }
 }
 
+/// RollingUpdate strategy carrying `spec.strategy.maxSurge`/`maxUnavailable`;
+/// `None` leaves the Kubernetes defaults in place
+fn rolling_update_strategy(node: &StellarNode) -> Option<DeploymentStrategy> {
+    let strategy = &node.spec.strategy;
+    if strategy.max_surge.is_none() && strategy.max_unavailable.is_none() {
+        return None;
+    }
+    Some(DeploymentStrategy {
+        type_: Some("RollingUpdate".to_string()),
+        rolling_update: Some(RollingUpdateDeployment {
+            max_surge: strategy.max_surge.clone(),
+            max_unavailable: strategy.max_unavailable.clone(),
+        }),
+    })
+}
+
 // ============================================================================
 // StatefulSet (for Validators)
 // ============================================================================
@@ -880,7 +988,7 @@ fn build_deployment(node: &StellarNode, enable_mtls: bool) -> Deployment {
 ///
 /// `seed_injection` describes how the validator seed should be mounted into
 /// the pod — either as an env var from a Secret/ExternalSecret, or as a CSI
-/// volume mount. Pass `None` when called for non-validator nodes.
+/// volume mount. Fails for non-validator nodes, which run as a Deployment.
 #[instrument(skip(client, node, propagated_labels), fields(name = %node.name_any(), namespace = node.namespace()))]
 pub async fn ensure_statefulset(
     client: &Client,
@@ -890,6 +998,7 @@ pub async fn ensure_statefulset(
     propagated_labels: &BTreeMap<String, String>,
     dry_run: bool,
 ) -> Result<()> {
+    check_workload_kind(node, "StatefulSet")?;
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let api: Api<StatefulSet> = Api::namespaced(client.clone(), &namespace);
     let name = node.name_any();
@@ -1001,6 +1110,34 @@ pub async fn delete_workload(client: &Client, node: &StellarNode, dry_run: bool)
     Ok(())
 }
 
+/// Delete the workload of the kind the node's type does not run as, left
+/// over when `spec.nodeType` changed. Only a workload the node owns is
+/// removed; returns whether one was, i.e. whether the node just changed
+/// workload kind.
+///
+/// The data PVC is kept and relabelled by `ensure_pvc`, and the Service
+/// ports follow the new node type on its next apply. A new pod may wait for
+/// the old one to release a `ReadWriteOnce` data volume.
+pub async fn delete_stale_workload(
+    client: &Client,
+    node: &StellarNode,
+    dry_run: bool,
+) -> Result<bool> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+
+    match node.spec.node_type {
+        NodeType::Validator => {
+            let api: Api<Deployment> = Api::namespaced(client.clone(), &namespace);
+            delete_if_owned(&api, "Deployment", &name, node, dry_run).await
+        }
+        NodeType::Horizon | NodeType::SorobanRpc => {
+            let api: Api<StatefulSet> = Api::namespaced(client.clone(), &namespace);
+            delete_if_owned(&api, "StatefulSet", &name, node, dry_run).await
+        }
+    }
+}
+
 // ============================================================================
 // Service
 // ============================================================================
@@ -1037,6 +1174,17 @@ pub async fn ensure_service(
     let patch = Patch::Apply(&service);
     api.patch(&name, &patch_params(dry_run), &patch).await?;
 
+    let admin_name = resource_name(node, "admin");
+    match build_admin_service(node) {
+        Some(admin) => {
+            api.patch(&admin_name, &patch_params(dry_run), &Patch::Apply(&admin))
+                .await?;
+        }
+        None => {
+            delete_if_owned(&api, "Service", &admin_name, node, dry_run).await?;
+        }
+    }
+
     Ok(())
 }
 
@@ -1078,6 +1226,59 @@ pub async fn ensure_canary_service(
     Ok(())
 }
 
+/// Annotation that sets a node's log level without editing its spec
+pub const LOG_LEVEL_ANNOTATION: &str = "stellar.org/log-level";
+
+/// Level requested by the node's `stellar.org/log-level` annotation, spelled
+/// the way stellar-core takes it. `None` without the annotation or when it is
+/// not one of `trace`, `debug`, `info`, `warn`/`warning`, `error` or `fatal`.
+pub(crate) fn requested_log_level(node: &StellarNode) -> Option<&'static str> {
+    let requested = node.annotations().get(LOG_LEVEL_ANNOTATION)?;
+    let level = match requested.trim().to_ascii_lowercase().as_str() {
+        "trace" => "trace",
+        "debug" => "debug",
+        "info" => "info",
+        "warn" | "warning" => "warning",
+        "error" => "error",
+        "fatal" => "fatal",
+        _ => {
+            warn!(
+                "Ignoring {}={:?} on {}/{}: not a log level",
+                LOG_LEVEL_ANNOTATION,
+                requested,
+                node.namespace().unwrap_or_default(),
+                node.name_any()
+            );
+            return None;
+        }
+    };
+    Some(level)
+}
+
+/// `LOG_LEVEL` env var applying the node's `stellar.org/log-level` annotation
+/// to Horizon and Soroban RPC. stellar-core ignores its environment and gets
+/// the level through its `ll` admin command instead (see
+/// [`super::core_log_level`]).
+pub(crate) fn log_level_env(node: &StellarNode) -> Option<(&'static str, &'static str)> {
+    if node.spec.node_type == NodeType::Validator {
+        return None;
+    }
+    // Horizon and Soroban RPC spell it the logrus way
+    let level = match requested_log_level(node)? {
+        "warning" => "warn",
+        level => level,
+    };
+    Some(("LOG_LEVEL", level))
+}
+
+/// Admin port of a Horizon node, when its config exposes one
+fn horizon_admin_port(node: &StellarNode) -> Option<u16> {
+    match node.spec.node_type {
+        NodeType::Horizon => node.spec.horizon_config.as_ref()?.admin_port,
+        _ => None,
+    }
+}
+
 fn build_service(node: &StellarNode, enable_mtls: bool) -> Service {
     let mut labels = standard_labels(node);
     merge_service_metadata_labels(&mut labels, node);
@@ -1172,7 +1373,7 @@ fn build_service(node: &StellarNode, enable_mtls: bool) -> Service {
                 owner_references: Some(vec![owner_reference(node)]),
                 ..Default::default()
             },
-            &None,
+            &node.spec.service_meta,
         ),
         spec: Some(ServiceSpec {
             selector: Some(labels),
@@ -1183,6 +1384,34 @@ fn build_service(node: &StellarNode, enable_mtls: bool) -> Service {
     }
 }
 
+/// Cluster-internal Service for Horizon's admin port (pprof and metrics), kept
+/// off the main Service so it is never exposed through a LoadBalancer or the
+/// Ingress
+pub(crate) fn build_admin_service(node: &StellarNode) -> Option<Service> {
+    let admin_port = horizon_admin_port(node)?;
+    let labels = standard_labels(node);
+    Some(Service {
+        metadata: ObjectMeta {
+            name: Some(resource_name(node, "admin")),
+            namespace: node.namespace(),
+            labels: Some(labels.clone()),
+            owner_references: Some(vec![owner_reference(node)]),
+            ..Default::default()
+        },
+        spec: Some(ServiceSpec {
+            type_: Some("ClusterIP".to_string()),
+            selector: Some(labels),
+            ports: Some(vec![ServicePort {
+                name: Some("admin".to_string()),
+                port: i32::from(admin_port),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }),
+        status: None,
+    })
+}
+
 // ============================================================================
 // LoadBalancer Service (MetalLB Integration) — stubs, wiring in progress
 // ============================================================================
@@ -1248,6 +1477,7 @@ fn build_cnpg_cluster(node: &StellarNode, config: &ManagedDatabaseConfig) -> Clu
     let name = node.name_any();
 
     let mut cluster = Cluster {
+        status: None,
         metadata: ObjectMeta {
             name: Some(name.clone()),
             namespace: node.namespace(),
@@ -1273,25 +1503,7 @@ fn build_cnpg_cluster(node: &StellarNode, config: &ManagedDatabaseConfig) -> Clu
                 storage_class: Some(config.storage.storage_class.clone()),
             },
             backup: config.backup.as_ref().map(|b| BackupConfiguration {
-                barman_object_store: Some(BarmanObjectStore {
-                    destination_path: b.destination_path.clone(),
-                    endpoint_u_r_l: None,
-                    s3_credentials: Some(S3Credentials {
-                        access_key_id: CnpgSecretKeySelector {
-                            name: b.credentials_secret_ref.clone(),
-                            key: "AWS_ACCESS_KEY_ID".to_string(),
-                        },
-                        secret_access_key: CnpgSecretKeySelector {
-                            name: b.credentials_secret_ref.clone(),
-                            key: "AWS_SECRET_ACCESS_KEY".to_string(),
-                        },
-                    }),
-                    azure_credentials: None,
-                    google_credentials: None,
-                    wal: Some(WalBackupConfiguration {
-                        compression: Some("gzip".to_string()),
-                    }),
-                }),
+                barman_object_store: Some(cnpg_object_store(b, None)),
                 retention_policy: Some(b.retention_policy.clone()),
             }),
             bootstrap: Some(BootstrapConfiguration {
@@ -1340,10 +1552,11 @@ fn build_cnpg_cluster(node: &StellarNode, config: &ManagedDatabaseConfig) -> Clu
                     p.insert("sslmode".to_string(), "require".to_string());
                     p
                 },
-                password: CnpgSecretKeySelector {
+                password: Some(CnpgSecretKeySelector {
                     name: format!("{}-app", node.name_any()),
                     key: "password".to_string(),
-                },
+                }),
+                barman_object_store: None,
             };
 
             cluster.spec.external_clusters = Some(vec![external_cluster]);
@@ -1353,6 +1566,7 @@ fn build_cnpg_cluster(node: &StellarNode, config: &ManagedDatabaseConfig) -> Clu
                 bootstrap.initdb = None; // Cannot use initdb with recovery
                 bootstrap.recovery = Some(RecoveryConfiguration {
                     source: remote_name.clone(),
+                    recovery_target: None,
                 });
             }
 
@@ -1364,9 +1578,64 @@ fn build_cnpg_cluster(node: &StellarNode, config: &ManagedDatabaseConfig) -> Clu
         }
     }
 
+    // Point-in-time restore: recover from the base backup and WAL archive up to
+    // the target time, and archive new WAL under a fresh server name.
+    if let (Some(target), Some(backup)) = (super::pitr::restore_target_time(node), &config.backup) {
+        let origin = format!("{name}-origin");
+        cluster.spec.external_clusters = Some(vec![ExternalCluster {
+            name: origin.clone(),
+            connection_parameters: BTreeMap::new(),
+            password: None,
+            barman_object_store: Some(cnpg_object_store(backup, Some(name.clone()))),
+        }]);
+        cluster.spec.bootstrap = Some(BootstrapConfiguration {
+            initdb: None,
+            recovery: Some(RecoveryConfiguration {
+                source: origin,
+                recovery_target: Some(RecoveryTarget {
+                    target_time: Some(target.to_rfc3339()),
+                }),
+            }),
+        });
+        if let Some(store) = cluster
+            .spec
+            .backup
+            .as_mut()
+            .and_then(|b| b.barman_object_store.as_mut())
+        {
+            store.server_name = Some(super::pitr::recovered_server_name(node, target));
+        }
+    }
+
     cluster
 }
 
+fn cnpg_object_store(
+    backup: &crate::crd::types::ManagedDatabaseBackupConfig,
+    server_name: Option<String>,
+) -> BarmanObjectStore {
+    BarmanObjectStore {
+        destination_path: backup.destination_path.clone(),
+        server_name,
+        endpoint_u_r_l: backup.endpoint_url.clone(),
+        s3_credentials: Some(S3Credentials {
+            access_key_id: CnpgSecretKeySelector {
+                name: backup.credentials_secret_ref.clone(),
+                key: "AWS_ACCESS_KEY_ID".to_string(),
+            },
+            secret_access_key: CnpgSecretKeySelector {
+                name: backup.credentials_secret_ref.clone(),
+                key: "AWS_SECRET_ACCESS_KEY".to_string(),
+            },
+        }),
+        azure_credentials: None,
+        google_credentials: None,
+        wal: Some(WalBackupConfiguration {
+            compression: Some("gzip".to_string()),
+        }),
+    }
+}
+
 #[instrument(skip(client, node), fields(name = %node.name_any(), namespace = node.namespace()))]
 pub async fn ensure_cnpg_pooler(client: &Client, node: &StellarNode, dry_run: bool) -> Result<()> {
     let managed_db = match &node.spec.managed_database {
@@ -1911,21 +2180,30 @@ fn build_pod_template(
         )),
         affinity: merge_workload_affinity(node),
         tolerations: build_workload_tolerations(node),
-        security_context: Some(PodSecurityContext {
-            run_as_non_root: Some(true),
-            run_as_user: Some(10000),
-            run_as_group: Some(10000),
-            fs_group: Some(10000),
-            seccomp_profile: Some(SeccompProfile {
-                localhost_profile: None,
-                type_: "RuntimeDefault".to_string(),
-            }),
-            ..Default::default()
-        }),
+        // fsGroup makes the data volume writable by the non-root stellar user
+        security_context: Some(pss::build_pod_security_context(
+            node.spec.security_context.as_ref(),
+        )),
         priority_class_name: node.spec.priority_class_name.clone(),
+        service_account_name: service_account_name(node),
+        automount_service_account_token: node
+            .spec
+            .service_account
+            .as_ref()
+            .and_then(|sa| sa.automount_token),
+        dns_policy: node.spec.dns_policy.clone(),
+        dns_config: node.spec.dns_config.clone(),
         ..Default::default()
     };
 
+    if node.spec.host_network {
+        pod_spec.host_network = Some(true);
+        // Keep resolving cluster Services from the host network namespace
+        pod_spec
+            .dns_policy
+            .get_or_insert_with(|| "ClusterFirstWithHostNet".to_string());
+    }
+
     if let Some(custom_volumes) = &node.spec.volumes {
         let volumes = pod_spec.volumes.get_or_insert_with(Vec::new);
         volumes.extend(custom_volumes.clone());
@@ -1947,6 +2225,7 @@ fn build_pod_template(
             if horizon_config.auto_migration && !blue_green_migration {
                 let init_containers = pod_spec.init_containers.get_or_insert_with(Vec::new);
                 init_containers.push(build_horizon_migration_container(node));
+                init_containers.push(build_horizon_migration_verify_container(node));
             }
         }
     }
@@ -2044,6 +2323,44 @@ fn build_pod_template(
         }
     }
 
+    // Serve the published history archive (Validator nodes only)
+    if let Some(archive_config) = archive_server::archive_server_config(node) {
+        if let Some(container) = pod_spec.containers.first_mut() {
+            container
+                .volume_mounts
+                .get_or_insert_with(Vec::new)
+                .push(archive_server::archive_volume_mount());
+        }
+        pod_spec
+            .volumes
+            .get_or_insert_with(Vec::new)
+            .push(archive_server::archive_volume(node));
+        pod_spec
+            .containers
+            .push(archive_server::build_archive_server_sidecar(archive_config));
+        pod_spec
+            .init_containers
+            .get_or_insert_with(Vec::new)
+            .push(archive_server::build_archive_init_container(node));
+    }
+
+    // Forward the node's traces through an OpenTelemetry Collector sidecar
+    if let Some(otel_config) = otel_collector::otel_collector_config(node) {
+        if let Some(container) = pod_spec.containers.first_mut() {
+            let env = container.env.get_or_insert_with(Vec::new);
+            if !env.iter().any(|e| e.name == "OTEL_EXPORTER_OTLP_ENDPOINT") {
+                env.push(otel_collector::local_otlp_env());
+            }
+        }
+        pod_spec
+            .volumes
+            .get_or_insert_with(Vec::new)
+            .push(otel_collector::otel_config_volume(node));
+        pod_spec
+            .containers
+            .push(otel_collector::build_otel_collector_sidecar(otel_config));
+    }
+
     // Add state-sync sidecar if enabled
     if let Some(dr_config) = &node.spec.dr_config {
         if dr_config.enabled
@@ -2640,6 +2957,13 @@ fn build_pod_template(
             pod_object_meta.annotations = Some(merged);
         }
     }
+    // Changing the primary archive must roll the pods so they pick up the new config
+    if let Some(primary) = primary_ordered_archives(node).first() {
+        pod_object_meta
+            .annotations
+            .get_or_insert_with(BTreeMap::new)
+            .insert(PRIMARY_ARCHIVE_ANNOTATION.to_string(), primary.clone());
+    }
 
     // ── Soroban RPC multi-layer cache ─────────────────────────────────────────
     // When cache_config is set, provision an emptyDir volume backed by the
@@ -2689,6 +3013,19 @@ fn build_pod_template(
         }
     }
 
+    if node.spec.host_network {
+        // Ports are bound on the host; declaring them as host ports lets the
+        // scheduler avoid nodes where they are already taken
+        for port in pod_spec
+            .containers
+            .iter_mut()
+            .filter_map(|c| c.ports.as_mut())
+            .flatten()
+        {
+            port.host_port = Some(port.container_port);
+        }
+    }
+
     PodTemplateSpec {
         metadata: Some(merge_resource_meta(
             pod_object_meta,
@@ -2982,6 +3319,25 @@ fn build_container(node: &StellarNode, enable_mtls: bool) -> Container {
                 value: Some(ingest_workers.to_string()),
                 ..Default::default()
             });
+            if let Some(admin_port) = horizon_admin_port(node) {
+                env_vars.push(EnvVar {
+                    name: "ADMIN_PORT".to_string(),
+                    value: Some(admin_port.to_string()),
+                    ..Default::default()
+                });
+            }
+            if let Some(limit) = node
+                .spec
+                .horizon_config
+                .as_ref()
+                .and_then(|cfg| cfg.per_hour_rate_limit)
+            {
+                env_vars.push(EnvVar {
+                    name: "PER_HOUR_RATE_LIMIT".to_string(),
+                    value: Some(limit.to_string()),
+                    ..Default::default()
+                });
+            }
         }
         NodeType::SorobanRpc => {
             env_vars.push(EnvVar {
@@ -2997,6 +3353,15 @@ fn build_container(node: &StellarNode, enable_mtls: bool) -> Container {
         }
     }
 
+    // A changed level changes the pod template, which rolls the pods
+    if let Some((name, level)) = log_level_env(node) {
+        env_vars.push(EnvVar {
+            name: name.to_string(),
+            value: Some(level.to_string()),
+            ..Default::default()
+        });
+    }
+
     // Source validator seed from Secret or shared RAM volume (KMS)
     if let NodeType::Validator = node.spec.node_type {
         if let Some(validator_config) = &node.spec.validator_config {
@@ -3217,10 +3582,20 @@ fn build_container(node: &StellarNode, enable_mtls: bool) -> Container {
     Container {
         name: "stellar-node".to_string(),
         image: Some(node.spec.container_image()),
-        ports: Some(vec![ContainerPort {
-            container_port,
-            ..Default::default()
-        }]),
+        command: node.spec.command.clone(),
+        args: node.spec.args.clone(),
+        ports: Some(
+            std::iter::once(ContainerPort {
+                container_port,
+                ..Default::default()
+            })
+            .chain(horizon_admin_port(node).map(|port| ContainerPort {
+                name: Some("admin".to_string()),
+                container_port: i32::from(port),
+                ..Default::default()
+            }))
+            .collect(),
+        ),
         env: Some(env_vars),
         resources: Some(K8sResources {
             requests: Some(requests),
@@ -3360,15 +3735,57 @@ fn build_workload_tolerations(node: &StellarNode) -> Option<Vec<Toleration>> {
     }
 }
 
+/// Name of the init container that runs Horizon database migrations.
+pub(crate) const HORIZON_MIGRATION_CONTAINER_NAME: &str = "horizon-db-migration";
+
+const DEFAULT_MIGRATION_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_MIGRATION_BACKOFF_SECONDS: u32 = 5;
+const MAX_MIGRATION_BACKOFF_SECONDS: u32 = 300;
+
+/// Build the shell script run by the Horizon migration init container.
+///
+/// The migration is retried with exponential backoff so that transient database
+/// outages (e.g. Postgres still starting) do not immediately crash-loop the pod.
+pub(crate) fn build_horizon_migration_script(node: &StellarNode) -> String {
+    let horizon_config = node.spec.horizon_config.as_ref();
+    let max_attempts = horizon_config
+        .and_then(|c| c.migration_max_attempts)
+        .unwrap_or(DEFAULT_MIGRATION_MAX_ATTEMPTS)
+        .max(1);
+    let backoff = horizon_config
+        .and_then(|c| c.migration_backoff_seconds)
+        .unwrap_or(DEFAULT_MIGRATION_BACKOFF_SECONDS)
+        .clamp(1, MAX_MIGRATION_BACKOFF_SECONDS);
+
+    format!(
+        r#"max_attempts={max_attempts}
+backoff={backoff}
+attempt=1
+until horizon db upgrade || horizon db init; do
+  if [ "$attempt" -ge "$max_attempts" ]; then
+    echo "Horizon database migration failed after $attempt attempts" >&2
+    exit 1
+  fi
+  echo "Horizon database migration attempt $attempt/$max_attempts failed, retrying in ${{backoff}}s" >&2
+  sleep "$backoff"
+  attempt=$((attempt + 1))
+  backoff=$((backoff * 2))
+  if [ "$backoff" -gt {max_backoff} ]; then
+    backoff={max_backoff}
+  fi
+done
+echo "Horizon database migration succeeded on attempt $attempt"
+"#,
+        max_backoff = MAX_MIGRATION_BACKOFF_SECONDS,
+    )
+}
+
 /// Build the migration container for Horizon
 pub(crate) fn build_horizon_migration_container(node: &StellarNode) -> Container {
     let mut container = build_container(node, false);
-    container.name = "horizon-db-migration".to_string();
+    container.name = HORIZON_MIGRATION_CONTAINER_NAME.to_string();
     container.command = Some(vec!["/bin/sh".to_string()]);
-    container.args = Some(vec![
-        "-c".to_string(),
-        "horizon db upgrade || horizon db init".to_string(),
-    ]);
+    container.args = Some(vec!["-c".to_string(), build_horizon_migration_script(node)]);
     container.ports = None;
     container.liveness_probe = None;
     container.readiness_probe = None;
@@ -3377,6 +3794,78 @@ pub(crate) fn build_horizon_migration_container(node: &StellarNode) -> Container
     container
 }
 
+/// Name of the init container that verifies the Horizon schema after migration.
+pub(crate) const HORIZON_MIGRATION_VERIFY_CONTAINER_NAME: &str = "horizon-db-verify";
+
+/// `MAJOR.MINOR.PATCH` of a `spec.version` tag, without the `v` prefix, suffix
+/// or digest. `None` for a bare `sha256:` digest.
+fn release_number(version: &str) -> Option<&str> {
+    if version.starts_with("sha256:") {
+        return None;
+    }
+    let tag = version.split('@').next().unwrap_or(version);
+    let tag = tag.strip_prefix('v').unwrap_or(tag);
+    Some(tag.split('-').next().unwrap_or(tag))
+}
+
+/// Build the shell script that verifies the Horizon schema is fully migrated.
+///
+/// The Horizon binary must first report the release named by `spec.version`,
+/// so a mismatched image fails before the schema is checked (a digest-only
+/// version has no release to compare). `horizon db migrate status` then lists
+/// every migration known to the binary together with whether it has been
+/// applied. Any pending migration means the database schema does not match the
+/// version the image expects, so the script fails and the serving container
+/// never starts against a half-migrated database.
+pub(crate) fn build_horizon_migration_verify_script(node: &StellarNode) -> String {
+    let version_check = release_number(&node.spec.version)
+        .map(|release| {
+            format!(
+                r#"if ! deployed_version=$(horizon version 2>&1); then
+  echo "$deployed_version" >&2
+  echo "Unable to read the Horizon version, expected $expected_version" >&2
+  exit 1
+fi
+case "$deployed_version" in
+  *"{release}"*) ;;
+  *)
+    echo "Horizon reports version '$deployed_version' but the image is $expected_version" >&2
+    exit 1
+    ;;
+esac
+"#
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        r#"expected_version="{version}"
+{version_check}if ! status=$(horizon db migrate status 2>&1); then
+  echo "$status" >&2
+  echo "Unable to read Horizon schema status for image version $expected_version" >&2
+  exit 1
+fi
+echo "$status"
+if echo "$status" | grep -qiE 'pending|not applied'; then
+  echo "Horizon schema does not match the version expected by image $expected_version: pending migrations remain" >&2
+  exit 1
+fi
+echo "Horizon schema verified for image version $expected_version"
+"#,
+        version = node.spec.version,
+    )
+}
+
+/// Build the init container that verifies the Horizon schema after migration.
+pub(crate) fn build_horizon_migration_verify_container(node: &StellarNode) -> Container {
+    let mut container = build_horizon_migration_container(node);
+    container.name = HORIZON_MIGRATION_VERIFY_CONTAINER_NAME.to_string();
+    container.args = Some(vec![
+        "-c".to_string(),
+        build_horizon_migration_verify_script(node),
+    ]);
+    container
+}
+
 /// Build the snapshot-restore init container for compressed DB backup bootstrapping.
 ///
 /// This container runs before Stellar Core and:
@@ -3827,20 +4316,53 @@ fn service_monitor_api_resource() -> ApiResource {
     })
 }
 
-pub async fn ensure_service_monitor(client: &Client, node: &StellarNode) -> Result<()> {
-    if !matches!(
-        node.spec.node_type,
-        NodeType::Horizon | NodeType::SorobanRpc
-    ) {
-        return Ok(());
+/// Scrape endpoint of a ServiceMonitor or PodMonitor. The scheme and
+/// `tlsConfig` come from `tls` alone, never from peer mTLS.
+pub fn service_monitor_endpoint(
+    metrics: &MetricsEndpointConfig,
+    tls: &MetricsTlsConfig,
+) -> serde_json::Value {
+    let mut endpoint = serde_json::json!({
+        "path": metrics.path,
+        "interval": "30s",
+        "scheme": if tls.enabled { "https" } else { "http" }
+    });
+    match &metrics.port {
+        Some(port) => endpoint["port"] = port.clone().into(),
+        None => endpoint["targetPort"] = metrics.target_port.into(),
     }
 
+    if tls.enabled {
+        let mut tls_config = serde_json::Map::new();
+        if let Some(secret) = &tls.ca_secret {
+            tls_config.insert(
+                "ca".to_string(),
+                serde_json::json!({ "secret": { "name": secret, "key": "ca.crt" } }),
+            );
+        }
+        if let Some(server_name) = &tls.server_name {
+            tls_config.insert("serverName".to_string(), server_name.clone().into());
+        }
+        if tls.insecure_skip_verify {
+            tls_config.insert("insecureSkipVerify".to_string(), true.into());
+        }
+        endpoint["tlsConfig"] = tls_config.into();
+    }
+
+    endpoint
+}
+
+/// Build the ServiceMonitor for a Horizon or Soroban RPC node
+pub fn build_service_monitor(
+    node: &StellarNode,
+    metrics: &MetricsEndpointConfig,
+    tls: &MetricsTlsConfig,
+) -> DynamicObject {
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let name = resource_name(node, "service-monitor");
-    let api_resource = service_monitor_api_resource();
-    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), &namespace, &api_resource);
 
-    let mut service_monitor = DynamicObject::new(&name, &api_resource).within(&namespace);
+    let mut service_monitor =
+        DynamicObject::new(&name, &service_monitor_api_resource()).within(&namespace);
     service_monitor.metadata.labels = Some(standard_labels(node));
     service_monitor.metadata.owner_references = Some(vec![owner_reference(node)]);
     service_monitor.data = serde_json::to_value(serde_json::json!({
@@ -3855,17 +4377,31 @@ pub async fn ensure_service_monitor(client: &Client, node: &StellarNode) -> Resu
                     "app.kubernetes.io/instance": node.name_any()
                 }
             },
-            "endpoints": [
-                {
-                    "targetPort": 8000,
-                    "path": "/metrics",
-                    "interval": "30s",
-                    "scheme": "http"
-                }
-            ]
+            "endpoints": [service_monitor_endpoint(metrics, tls)]
         }
     }))
     .unwrap_or_default();
+    service_monitor
+}
+
+pub async fn ensure_service_monitor(
+    client: &Client,
+    node: &StellarNode,
+    metrics: &MetricsEndpointConfig,
+    tls: &MetricsTlsConfig,
+) -> Result<()> {
+    if !matches!(
+        node.spec.node_type,
+        NodeType::Horizon | NodeType::SorobanRpc
+    ) {
+        return Ok(());
+    }
+
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = resource_name(node, "service-monitor");
+    let api_resource = service_monitor_api_resource();
+    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), &namespace, &api_resource);
+    let service_monitor = build_service_monitor(node, metrics, tls);
 
     api.patch(
         &name,
@@ -3910,6 +4446,127 @@ pub async fn delete_service_monitor(client: &Client, node: &StellarNode) -> Resu
     Ok(())
 }
 
+// ============================================================================
+// PodMonitor
+// ============================================================================
+
+fn pod_monitor_api_resource() -> ApiResource {
+    ApiResource::from_gvk(&GroupVersionKind {
+        group: "monitoring.coreos.com".to_string(),
+        version: "v1".to_string(),
+        kind: "PodMonitor".to_string(),
+    })
+}
+
+/// Build the PodMonitor for a Horizon or Soroban RPC node, scraping its pods
+/// with the same endpoint a ServiceMonitor would use
+pub fn build_pod_monitor(
+    node: &StellarNode,
+    metrics: &MetricsEndpointConfig,
+    tls: &MetricsTlsConfig,
+) -> DynamicObject {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = resource_name(node, "pod-monitor");
+
+    let mut pod_monitor = DynamicObject::new(&name, &pod_monitor_api_resource()).within(&namespace);
+    pod_monitor.metadata.labels = Some(standard_labels(node));
+    pod_monitor.metadata.owner_references = Some(vec![owner_reference(node)]);
+    pod_monitor.data = serde_json::json!({
+        "spec": {
+            "jobLabel": "app.kubernetes.io/instance",
+            "namespaceSelector": {
+                "matchNames": [namespace]
+            },
+            "selector": {
+                "matchLabels": {
+                    "app.kubernetes.io/name": "stellar-node",
+                    "app.kubernetes.io/instance": node.name_any()
+                }
+            },
+            "podMetricsEndpoints": [service_monitor_endpoint(metrics, tls)]
+        }
+    });
+    pod_monitor
+}
+
+pub async fn ensure_pod_monitor(
+    client: &Client,
+    node: &StellarNode,
+    metrics: &MetricsEndpointConfig,
+    tls: &MetricsTlsConfig,
+) -> Result<()> {
+    if !matches!(
+        node.spec.node_type,
+        NodeType::Horizon | NodeType::SorobanRpc
+    ) {
+        return Ok(());
+    }
+
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = resource_name(node, "pod-monitor");
+    let api_resource = pod_monitor_api_resource();
+    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), &namespace, &api_resource);
+    let pod_monitor = build_pod_monitor(node, metrics, tls);
+
+    api.patch(
+        &name,
+        &PatchParams::apply("stellar-operator").force(),
+        &Patch::Apply(&pod_monitor),
+    )
+    .await
+    .map_err(Error::KubeError)?;
+
+    info!(
+        "Ensured PodMonitor {}/{} for Prometheus Operator scraping",
+        namespace, name
+    );
+
+    Ok(())
+}
+
+pub async fn delete_pod_monitor(client: &Client, node: &StellarNode) -> Result<()> {
+    if !matches!(
+        node.spec.node_type,
+        NodeType::Horizon | NodeType::SorobanRpc
+    ) {
+        return Ok(());
+    }
+
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = resource_name(node, "pod-monitor");
+    let api_resource = pod_monitor_api_resource();
+    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), &namespace, &api_resource);
+
+    match api.delete(&name, &DeleteParams::default()).await {
+        Ok(_) => info!("Deleted PodMonitor {}/{}", namespace, name),
+        Err(kube::Error::Api(api_err)) if api_err.code == 404 => {}
+        Err(e) => return Err(Error::KubeError(e)),
+    }
+
+    Ok(())
+}
+
+/// Apply the monitor of the configured kind and remove one of the other
+/// kind left from an earlier configuration
+pub async fn ensure_metrics_monitor(
+    client: &Client,
+    node: &StellarNode,
+    kind: MetricsMonitorKind,
+    metrics: &MetricsEndpointConfig,
+    tls: &MetricsTlsConfig,
+) -> Result<()> {
+    match kind {
+        MetricsMonitorKind::ServiceMonitor => {
+            ensure_service_monitor(client, node, metrics, tls).await?;
+            delete_pod_monitor(client, node).await
+        }
+        MetricsMonitorKind::PodMonitor => {
+            ensure_pod_monitor(client, node, metrics, tls).await?;
+            delete_service_monitor(client, node).await
+        }
+    }
+}
+
 pub async fn delete_alerting(client: &Client, node: &StellarNode, dry_run: bool) -> Result<()> {
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let name = resource_name(node, "alerts");
@@ -4042,7 +4699,7 @@ pub(crate) fn build_network_policy(
     let mut egress_rules: Vec<k8s_openapi::api::networking::v1::NetworkPolicyEgressRule> =
         Vec::new();
 
-    let app_ports = match node.spec.node_type {
+    let mut app_ports = match node.spec.node_type {
         NodeType::Validator => vec![
             NetworkPolicyPort {
                 port: Some(k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(11625)),
@@ -4062,6 +4719,18 @@ pub(crate) fn build_network_policy(
         }],
     };
 
+    if archive_server::archive_server_config(node).is_some() {
+        app_ports.push(NetworkPolicyPort {
+            port: Some(
+                k8s_openapi::apimachinery::pkg::util::intstr::IntOrString::Int(
+                    archive_server::ARCHIVE_SERVER_PORT,
+                ),
+            ),
+            protocol: Some("TCP".to_string()),
+            ..Default::default()
+        });
+    }
+
     if !config.allow_namespaces.is_empty() {
         let peers: Vec<NetworkPolicyPeer> = config
             .allow_namespaces
@@ -4561,6 +5230,175 @@ pub async fn delete_pdb(client: &Client, node: &StellarNode, dry_run: bool) -> R
     Ok(())
 }
 
+// ============================================================================
+// ServiceAccount
+// ============================================================================
+
+/// ServiceAccount the node's pods run as, if the node configures one
+pub fn service_account_name(node: &StellarNode) -> Option<String> {
+    node.spec
+        .service_account
+        .as_ref()
+        .map(|sa| sa.name.clone().unwrap_or_else(|| node.name_any()))
+}
+
+/// ServiceAccount created for the node, when `serviceAccount.create` is set
+pub(crate) fn build_service_account(node: &StellarNode) -> Option<ServiceAccount> {
+    let config = node.spec.service_account.as_ref().filter(|sa| sa.create)?;
+    Some(ServiceAccount {
+        metadata: ObjectMeta {
+            name: service_account_name(node),
+            namespace: node.namespace(),
+            labels: Some(standard_labels(node)),
+            annotations: (!config.annotations.is_empty()).then(|| config.annotations.clone()),
+            owner_references: Some(vec![owner_reference(node)]),
+            ..Default::default()
+        },
+        automount_service_account_token: config.automount_token,
+        ..Default::default()
+    })
+}
+
+/// Role and RoleBinding granting `serviceAccount.rules` to the node's
+/// ServiceAccount only
+pub(crate) fn build_service_account_role(node: &StellarNode) -> Option<(Role, RoleBinding)> {
+    let config = node.spec.service_account.as_ref().filter(|sa| sa.create)?;
+    let rules: Vec<_> = config
+        .rules
+        .iter()
+        .filter(|rule| ServiceAccountConfig::rule_is_allowed(rule))
+        .cloned()
+        .collect();
+    if rules.is_empty() {
+        return None;
+    }
+    let name = resource_name(node, "workload");
+    let metadata = ObjectMeta {
+        name: Some(name.clone()),
+        namespace: node.namespace(),
+        labels: Some(standard_labels(node)),
+        owner_references: Some(vec![owner_reference(node)]),
+        ..Default::default()
+    };
+
+    let role = Role {
+        metadata: metadata.clone(),
+        rules: Some(rules),
+    };
+    let binding = RoleBinding {
+        metadata,
+        role_ref: RoleRef {
+            api_group: "rbac.authorization.k8s.io".to_string(),
+            kind: "Role".to_string(),
+            name,
+        },
+        subjects: Some(vec![Subject {
+            kind: "ServiceAccount".to_string(),
+            name: service_account_name(node)?,
+            namespace: node.namespace(),
+            ..Default::default()
+        }]),
+    };
+    Some((role, binding))
+}
+
+/// Whether `owner_references` point at `node`
+fn owned_by(owner_references: Option<&Vec<OwnerReference>>, node: &StellarNode) -> bool {
+    let uid = node.uid().unwrap_or_default();
+    owner_references.is_some_and(|refs| refs.iter().any(|r| r.uid == uid))
+}
+
+/// Delete `name` if it exists and belongs to the node, leaving objects the
+/// user created alone. Returns whether the object was deleted.
+pub(crate) async fn delete_if_owned<K>(
+    api: &Api<K>,
+    kind: &str,
+    name: &str,
+    node: &StellarNode,
+    dry_run: bool,
+) -> Result<bool>
+where
+    K: Resource + Clone + serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let Some(existing) = api.get_opt(name).await.map_err(Error::KubeError)? else {
+        return Ok(false);
+    };
+    if !owned_by(existing.meta().owner_references.as_ref(), node) {
+        return Ok(false);
+    }
+    match api.delete(name, &delete_params(dry_run)).await {
+        Ok(_) => info!("Deleted {} {}", kind, name),
+        Err(kube::Error::Api(e)) if e.code == 404 => return Ok(false),
+        Err(e) => return Err(Error::KubeError(e)),
+    }
+    Ok(true)
+}
+
+/// Create or update the node's ServiceAccount and its Role, removing the ones
+/// the operator created earlier once they are no longer configured.
+pub async fn ensure_service_account(
+    client: &Client,
+    node: &StellarNode,
+    dry_run: bool,
+) -> Result<()> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let params = patch_params(dry_run);
+    let accounts: Api<ServiceAccount> = Api::namespaced(client.clone(), &namespace);
+    let roles: Api<Role> = Api::namespaced(client.clone(), &namespace);
+    let bindings: Api<RoleBinding> = Api::namespaced(client.clone(), &namespace);
+
+    let account_name = match build_service_account(node) {
+        Some(account) => {
+            let name = account.metadata.name.clone().unwrap_or_default();
+            info!("Reconciling ServiceAccount {}/{}", namespace, name);
+            accounts
+                .patch(&name, &params, &Patch::Apply(&account))
+                .await
+                .map_err(Error::KubeError)?;
+            Some(name)
+        }
+        None => None,
+    };
+
+    // Accounts created under another name, before `serviceAccount` was
+    // renamed or removed, still carry the node's labels
+    let selector = format!(
+        "app.kubernetes.io/instance={},app.kubernetes.io/managed-by=stellar-operator",
+        node.name_any()
+    );
+    let created = accounts
+        .list(&ListParams::default().labels(&selector))
+        .await
+        .map_err(Error::KubeError)?;
+    for account in created.items {
+        let name = account.name_any();
+        if account_name.as_ref() != Some(&name) {
+            delete_if_owned(&accounts, "ServiceAccount", &name, node, dry_run).await?;
+        }
+    }
+
+    let role_name = resource_name(node, "workload");
+    match build_service_account_role(node) {
+        Some((role, binding)) => {
+            info!("Reconciling Role/RoleBinding {}/{}", namespace, role_name);
+            roles
+                .patch(&role_name, &params, &Patch::Apply(&role))
+                .await
+                .map_err(Error::KubeError)?;
+            bindings
+                .patch(&role_name, &params, &Patch::Apply(&binding))
+                .await
+                .map_err(Error::KubeError)?;
+        }
+        None => {
+            delete_if_owned(&bindings, "RoleBinding", &role_name, node, dry_run).await?;
+            delete_if_owned(&roles, "Role", &role_name, node, dry_run).await?;
+        }
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Test helpers — thin wrappers that expose private builders for unit tests
 // (Issue #298)
@@ -4581,6 +5419,11 @@ pub(crate) fn build_pvc_for_test(
     build_pvc(node, storage_class)
 }
 
+#[cfg(test)]
+pub(crate) fn build_cnpg_cluster_for_test(node: &StellarNode) -> Cluster {
+    build_cnpg_cluster(node, node.spec.managed_database.as_ref().unwrap())
+}
+
 #[cfg(test)]
 pub(crate) fn build_config_map_for_test(node: &StellarNode) -> ConfigMap {
     build_config_map(node, None, false)
@@ -4755,6 +5598,26 @@ mod ensure_pvc_tests {
         assert!(!pvc_needs_update(&existing, &desired));
     }
 
+    #[test]
+    fn build_pvc_uses_configured_access_modes() {
+        use crate::crd::types::PvcAccessMode;
+        let access_modes = |node: &StellarNode| {
+            build_pvc(node, "standard".to_string())
+                .spec
+                .unwrap()
+                .access_modes
+                .unwrap()
+        };
+
+        let mut node = test_node();
+        assert_eq!(access_modes(&node), vec!["ReadWriteOnce"]);
+
+        node.spec.node_type = NodeType::Horizon;
+        node.spec.storage.access_modes =
+            vec![PvcAccessMode::ReadWriteMany, PvcAccessMode::ReadOnlyMany];
+        assert_eq!(access_modes(&node), vec!["ReadWriteMany", "ReadOnlyMany"]);
+    }
+
     // -----------------------------------------------------------------------
     // Retention policy — Delete scenario
     // -----------------------------------------------------------------------
@@ -4860,4 +5723,34 @@ mod ensure_pvc_tests {
         assert!(metric_names.contains(&"stellar_horizon_tps".to_string()));
         assert!(metric_names.contains(&"stellar_horizon_queue_length".to_string()));
     }
+
+    #[tokio::test]
+    async fn existing_pvc_keeps_its_storage_class() {
+        use crate::controller::test_harness::fake_client;
+        use crate::crd::types::StorageTier;
+        use k8s_openapi::api::core::v1::PersistentVolumeClaim;
+        use kube::api::Api;
+        use std::collections::BTreeMap;
+
+        let (client, server) = fake_client();
+        let requests = server.serve();
+        let mut node = test_node();
+        node.spec.storage.tier = Some(StorageTier::Ssd);
+        let api: Api<PersistentVolumeClaim> = Api::namespaced(client.clone(), "stellar-system");
+        api.create(&Default::default(), &build_pvc(&node, "gp3".to_string()))
+            .await
+            .unwrap();
+
+        super::ensure_pvc(&client, &node, &BTreeMap::new(), false)
+            .await
+            .unwrap();
+
+        let pvc = api.get("test-node-data").await.unwrap();
+        assert_eq!(pvc.spec.unwrap().storage_class_name.as_deref(), Some("gp3"));
+        assert!(!requests
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|r| r.path.ends_with("/storageclasses")));
+    }
 }
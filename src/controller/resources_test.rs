@@ -556,6 +556,10 @@ peer-2 = "G..."
             ingest_workers: 1,
             enable_experimental_ingestion: false,
             auto_migration: true,
+            migration_max_attempts: None,
+            migration_backoff_seconds: None,
+            admin_port: None,
+            per_hour_rate_limit: None,
         });
 
         let deploy = build_deployment_for_test(&node);
@@ -600,6 +604,30 @@ peer-2 = "G..."
         assert_owner_reference(&sts.metadata, &node);
     }
 
+    #[test]
+    fn test_pod_security_context_sets_fs_group() {
+        let fs_group = |node: &StellarNode| {
+            build_statefulset_for_test(node)
+                .spec
+                .unwrap()
+                .template
+                .spec
+                .unwrap()
+                .security_context
+                .unwrap()
+                .fs_group
+        };
+
+        let mut node = make_node(NodeType::Validator);
+        assert_eq!(fs_group(&node), Some(crate::controller::pss::STELLAR_GID));
+
+        node.spec.security_context = Some(crate::crd::types::StellarSecurityContext {
+            fs_group: Some(2000),
+            ..Default::default()
+        });
+        assert_eq!(fs_group(&node), Some(2000));
+    }
+
     #[test]
     fn test_service_has_standard_labels_and_owner_ref() {
         let node = make_node(NodeType::Horizon);
@@ -642,6 +670,57 @@ peer-2 = "G..."
         );
     }
 
+    #[test]
+    fn test_service_meta_annotations_land_only_on_service() {
+        let mut node = make_node(NodeType::Validator);
+        node.spec.service_meta = Some(ObjectMeta {
+            annotations: Some(BTreeMap::from([(
+                "service.beta.kubernetes.io/aws-load-balancer-type".to_string(),
+                "nlb".to_string(),
+            )])),
+            ..Default::default()
+        });
+        let has_lb_annotation = |meta: &ObjectMeta| {
+            meta.annotations.as_ref().is_some_and(|a| {
+                a.contains_key("service.beta.kubernetes.io/aws-load-balancer-type")
+            })
+        };
+
+        let svc = build_service_for_test(&node);
+        assert!(has_lb_annotation(&svc.metadata));
+
+        let pvc = build_pvc_for_test(&node, "standard".to_string());
+        assert!(!has_lb_annotation(&pvc.metadata));
+
+        let sts = build_statefulset_for_test(&node);
+        assert!(!has_lb_annotation(&sts.metadata));
+        let pod_meta = sts.spec.unwrap().template.metadata.unwrap_or_default();
+        assert!(!has_lb_annotation(&pod_meta));
+    }
+
+    #[test]
+    fn test_pvc_meta_labels_land_only_on_pvc() {
+        let mut node = make_node(NodeType::Validator);
+        node.spec.pvc_meta = Some(ObjectMeta {
+            labels: Some(BTreeMap::from([(
+                "backup-tier".to_string(),
+                "gold".to_string(),
+            )])),
+            ..Default::default()
+        });
+
+        let pvc = build_pvc_for_test(&node, "standard".to_string());
+        let labels = pvc.metadata.labels.expect("labels must exist");
+        assert_eq!(labels.get("backup-tier"), Some(&"gold".to_string()));
+
+        let svc = build_service_for_test(&node);
+        assert!(!svc
+            .metadata
+            .labels
+            .expect("labels must exist")
+            .contains_key("backup-tier"));
+    }
+
     #[test]
     fn test_custom_volumes_and_volume_mounts_are_injected_into_pod_spec() {
         let mut node = make_node(NodeType::Horizon);
@@ -1400,6 +1479,109 @@ mod init_containers_tests {
             "operator-managed init containers must come before user-defined ones"
         );
     }
+
+    #[test]
+    fn test_horizon_migration_script_retries_with_backoff() {
+        use crate::controller::resources::build_horizon_migration_container;
+        use crate::crd::types::HorizonConfig;
+
+        let mut node = make_node(NodeType::Horizon, None);
+        node.spec.horizon_config = Some(HorizonConfig {
+            database_secret_ref: "db-secret".to_string(),
+            auto_migration: true,
+            migration_max_attempts: Some(7),
+            migration_backoff_seconds: Some(10),
+            ..Default::default()
+        });
+
+        let container = build_horizon_migration_container(&node);
+        let args = container.args.expect("migration container must have args");
+        let script = &args[1];
+
+        assert!(script.contains("max_attempts=7"));
+        assert!(script.contains("backoff=10"));
+        assert!(script.contains("until horizon db upgrade || horizon db init; do"));
+        assert!(script.contains("sleep \"$backoff\""));
+        assert!(script.contains("backoff=$((backoff * 2))"));
+        assert!(script.contains("exit 1"));
+    }
+
+    #[test]
+    fn test_horizon_migration_verified_before_serving() {
+        use crate::crd::types::HorizonConfig;
+
+        let mut node = make_node(NodeType::Horizon, None);
+        node.spec.horizon_config = Some(HorizonConfig {
+            database_secret_ref: "db-secret".to_string(),
+            auto_migration: true,
+            ..Default::default()
+        });
+
+        let dep = build_deployment_for_test(&node);
+        let init_containers = dep
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap()
+            .init_containers
+            .unwrap_or_default();
+
+        let pos_migration = init_containers
+            .iter()
+            .position(|c| c.name == "horizon-db-migration")
+            .expect("migration init container must be present");
+        let pos_verify = init_containers
+            .iter()
+            .position(|c| c.name == "horizon-db-verify")
+            .expect("verification init container must be present");
+        assert_eq!(
+            pos_verify,
+            pos_migration + 1,
+            "verification must run immediately after migration"
+        );
+
+        let script = &init_containers[pos_verify].args.as_ref().unwrap()[1];
+        assert!(script.contains("horizon db migrate status"));
+        assert!(script.contains("expected_version=\"v21.0.0\""));
+        assert!(script.contains("exit 1"));
+    }
+
+    #[test]
+    fn test_horizon_verify_compares_deployed_version() {
+        use crate::controller::resources::build_horizon_migration_verify_script;
+
+        let mut node = make_node(NodeType::Horizon, None);
+        node.spec.version = format!("v2.31.0-rc1@sha256:{}", "a".repeat(64));
+        let script = build_horizon_migration_verify_script(&node);
+        assert!(script.contains("deployed_version=$(horizon version 2>&1)"));
+        assert!(script.contains("*\"2.31.0\"*) ;;"));
+        let version_check = script.find("horizon version").unwrap();
+        let schema_check = script.find("horizon db migrate status").unwrap();
+        assert!(version_check < schema_check);
+
+        // A bare digest names no release to compare against
+        node.spec.version = format!("sha256:{}", "a".repeat(64));
+        let script = build_horizon_migration_verify_script(&node);
+        assert!(!script.contains("horizon version"));
+        assert!(script.contains("horizon db migrate status"));
+    }
+
+    #[test]
+    fn test_horizon_migration_script_uses_defaults() {
+        use crate::controller::resources::build_horizon_migration_script;
+        use crate::crd::types::HorizonConfig;
+
+        let mut node = make_node(NodeType::Horizon, None);
+        node.spec.horizon_config = Some(HorizonConfig {
+            database_secret_ref: "db-secret".to_string(),
+            ..Default::default()
+        });
+
+        let script = build_horizon_migration_script(&node);
+        assert!(script.contains("max_attempts=5"));
+        assert!(script.contains("backoff=5"));
+    }
 }
 
 // -----------------------------------------------------------------------
@@ -1935,3 +2117,1596 @@ fn test_spec_and_jurisdiction_tolerations_are_applied() {
         "jurisdiction tolerations must be merged"
     );
 }
+
+// -----------------------------------------------------------------------
+// history archive rotation config tests
+// -----------------------------------------------------------------------
+
+#[cfg(test)]
+mod archive_rotation_config_tests {
+    use crate::controller::archive_health::{ArchiveIntegrityResult, ARCHIVE_LAG_THRESHOLD};
+    use crate::controller::archive_rotation::{
+        rotation_decision, update_lag_strikes, PRIMARY_ARCHIVE_ANNOTATION,
+    };
+    use crate::controller::resources::{build_config_map_for_test, build_statefulset_for_test};
+    use crate::crd::types::ValidatorConfig;
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec, StellarNodeStatus};
+
+    fn validator_with_archives(urls: &[&str]) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: NodeType::Validator,
+            validator_config: Some(ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                enable_history_archive: true,
+                history_archive_urls: urls.iter().map(|u| u.to_string()).collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("validator", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    fn lag_result(url: &str, lag: u64) -> ArchiveIntegrityResult {
+        ArchiveIntegrityResult {
+            url: url.to_string(),
+            archive_ledger: Some(1000 - lag),
+            node_ledger: 1000,
+            lag: Some(lag),
+            lag_threshold: ARCHIVE_LAG_THRESHOLD,
+            error: None,
+        }
+    }
+
+    fn core_cfg(node: &StellarNode) -> String {
+        build_config_map_for_test(node)
+            .data
+            .unwrap()
+            .remove("stellar-core.cfg")
+            .unwrap()
+    }
+
+    fn primary_annotation(node: &StellarNode) -> Option<String> {
+        build_statefulset_for_test(node)
+            .spec
+            .unwrap()
+            .template
+            .metadata
+            .unwrap()
+            .annotations
+            .unwrap_or_default()
+            .get(PRIMARY_ARCHIVE_ANNOTATION)
+            .cloned()
+    }
+
+    #[test]
+    fn test_core_config_lists_archives_primary_first() {
+        let node = validator_with_archives(&["https://a", "https://b"]);
+        let cfg = core_cfg(&node);
+
+        assert!(cfg.contains("[HISTORY.archive1]\nget=\"curl -sf https://a/{0} -o {1}\""));
+        assert!(cfg.contains("[HISTORY.archive2]\nget=\"curl -sf https://b/{0} -o {1}\""));
+        assert_eq!(primary_annotation(&node).as_deref(), Some("https://a"));
+    }
+
+    #[test]
+    fn test_no_archive_config_when_history_disabled() {
+        let mut node = validator_with_archives(&["https://a"]);
+        node.spec
+            .validator_config
+            .as_mut()
+            .unwrap()
+            .enable_history_archive = false;
+
+        assert!(!core_cfg(&node).contains("[HISTORY."));
+        assert!(primary_annotation(&node).is_none());
+    }
+
+    #[test]
+    fn test_sustained_staleness_rotates_config_and_pod_template() {
+        let mut node = validator_with_archives(&["https://a", "https://b", "https://c"]);
+        let urls = node
+            .spec
+            .validator_config
+            .as_ref()
+            .unwrap()
+            .history_archive_urls
+            .clone();
+        let before = primary_annotation(&node);
+
+        let results = vec![
+            lag_result("https://a", 500),
+            lag_result("https://b", 3),
+            lag_result("https://c", 8),
+        ];
+        let mut strikes = Vec::new();
+        for _ in 0..3 {
+            strikes = update_lag_strikes(&strikes, &results);
+        }
+        let rotation = rotation_decision(&urls, &strikes, &results, 3)
+            .expect("primary lagging for 3 checks should rotate");
+        node.status = Some(StellarNodeStatus {
+            primary_history_archive: Some(rotation.to),
+            ..Default::default()
+        });
+
+        let cfg = core_cfg(&node);
+        assert!(cfg.contains("[HISTORY.archive1]\nget=\"curl -sf https://b/{0} -o {1}\""));
+        assert!(cfg.contains("[HISTORY.archive2]\nget=\"curl -sf https://a/{0} -o {1}\""));
+        assert!(cfg.contains("[HISTORY.archive3]\nget=\"curl -sf https://c/{0} -o {1}\""));
+        assert_eq!(
+            node.spec
+                .validator_config
+                .as_ref()
+                .unwrap()
+                .history_archive_urls,
+            urls,
+            "rotation must not touch the spec"
+        );
+
+        let after = primary_annotation(&node);
+        assert_eq!(after.as_deref(), Some("https://b"));
+        assert_ne!(before, after, "pod template must change to roll the pods");
+    }
+}
+
+#[cfg(test)]
+mod service_monitor_tests {
+    use crate::controller::operator_config::{MetricsEndpointConfig, MetricsTlsConfig};
+    use crate::controller::resources::build_service_monitor;
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn horizon() -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: NodeType::Horizon,
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("horizon", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    fn endpoint(tls: &MetricsTlsConfig) -> serde_json::Value {
+        let sm = build_service_monitor(&horizon(), &MetricsEndpointConfig::default(), tls);
+        sm.data["spec"]["endpoints"][0].clone()
+    }
+
+    #[test]
+    fn test_default_metrics_endpoint() {
+        let endpoint = endpoint(&MetricsTlsConfig::default());
+        assert_eq!(endpoint["path"], "/metrics");
+        assert_eq!(endpoint["targetPort"], 8000);
+        assert!(endpoint.get("port").is_none());
+    }
+
+    #[test]
+    fn test_custom_metrics_path_and_target_port() {
+        let metrics = MetricsEndpointConfig {
+            path: "/stats/prometheus".to_string(),
+            port: None,
+            target_port: 9100,
+        };
+        let sm = build_service_monitor(&horizon(), &metrics, &MetricsTlsConfig::default());
+        let endpoint = &sm.data["spec"]["endpoints"][0];
+        assert_eq!(endpoint["path"], "/stats/prometheus");
+        assert_eq!(endpoint["targetPort"], 9100);
+    }
+
+    #[test]
+    fn test_named_port_replaces_target_port() {
+        let metrics = MetricsEndpointConfig {
+            port: Some("exporter".to_string()),
+            ..Default::default()
+        };
+        let sm = build_service_monitor(&horizon(), &metrics, &MetricsTlsConfig::default());
+        let endpoint = &sm.data["spec"]["endpoints"][0];
+        assert_eq!(endpoint["port"], "exporter");
+        assert!(endpoint.get("targetPort").is_none());
+    }
+
+    #[test]
+    fn test_plain_http_without_metrics_tls() {
+        let endpoint = endpoint(&MetricsTlsConfig::default());
+        assert_eq!(endpoint["scheme"], "http");
+        assert!(endpoint.get("tlsConfig").is_none());
+    }
+
+    #[test]
+    fn test_https_with_tls_config_when_metrics_tls_enabled() {
+        let endpoint = endpoint(&MetricsTlsConfig {
+            enabled: true,
+            ca_secret: Some("metrics-ca".to_string()),
+            server_name: Some("horizon.stellar.svc".to_string()),
+            insecure_skip_verify: false,
+        });
+        assert_eq!(endpoint["scheme"], "https");
+        assert_eq!(endpoint["tlsConfig"]["ca"]["secret"]["name"], "metrics-ca");
+        assert_eq!(endpoint["tlsConfig"]["ca"]["secret"]["key"], "ca.crt");
+        assert_eq!(endpoint["tlsConfig"]["serverName"], "horizon.stellar.svc");
+        assert!(endpoint["tlsConfig"].get("insecureSkipVerify").is_none());
+    }
+
+    #[test]
+    fn test_insecure_skip_verify_without_ca() {
+        let endpoint = endpoint(&MetricsTlsConfig {
+            enabled: true,
+            insecure_skip_verify: true,
+            ..Default::default()
+        });
+        assert_eq!(endpoint["scheme"], "https");
+        assert_eq!(
+            endpoint["tlsConfig"],
+            serde_json::json!({ "insecureSkipVerify": true })
+        );
+    }
+
+    #[test]
+    fn test_tls_options_ignored_when_metrics_tls_disabled() {
+        let endpoint = endpoint(&MetricsTlsConfig {
+            enabled: false,
+            ca_secret: Some("metrics-ca".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(endpoint["scheme"], "http");
+        assert!(endpoint.get("tlsConfig").is_none());
+    }
+
+    #[test]
+    fn test_service_monitor_metadata() {
+        let sm = build_service_monitor(
+            &horizon(),
+            &MetricsEndpointConfig::default(),
+            &MetricsTlsConfig::default(),
+        );
+        assert_eq!(sm.metadata.namespace.as_deref(), Some("stellar"));
+        assert_eq!(sm.metadata.owner_references.unwrap().len(), 1);
+        assert_eq!(
+            sm.data["spec"]["selector"]["matchLabels"]["app.kubernetes.io/instance"],
+            "horizon"
+        );
+    }
+}
+
+#[cfg(test)]
+mod otel_collector_injection_tests {
+    use crate::controller::otel_collector::{LOCAL_OTLP_ENDPOINT, OTEL_CONFIG_VOLUME};
+    use crate::controller::resources::{build_deployment_for_test, build_statefulset_for_test};
+    use crate::crd::types::HorizonConfig;
+    use crate::crd::{
+        NodeType, OtelCollectorConfig, StellarNode, StellarNodeSpec, DEFAULT_OTEL_COLLECTOR_IMAGE,
+    };
+    use k8s_openapi::api::core::v1::PodSpec;
+
+    fn node(node_type: NodeType, enabled: bool) -> StellarNode {
+        let mut node = StellarNode::new(
+            "node-1",
+            StellarNodeSpec {
+                horizon_config: (node_type == NodeType::Horizon).then(|| HorizonConfig {
+                    database_secret_ref: "db".to_string(),
+                    ..Default::default()
+                }),
+                node_type,
+                otel_collector: Some(OtelCollectorConfig {
+                    enabled,
+                    endpoint: "otel-gateway:4317".to_string(),
+                    insecure: false,
+                    image: DEFAULT_OTEL_COLLECTOR_IMAGE.to_string(),
+                }),
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    fn assert_injected(pod: &PodSpec) {
+        assert!(pod.containers.iter().any(|c| c.name == "otel-collector"));
+        assert!(pod
+            .volumes
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|v| v.name == OTEL_CONFIG_VOLUME));
+        assert!(pod.containers[0].env.as_ref().unwrap().iter().any(|e| {
+            e.name == "OTEL_EXPORTER_OTLP_ENDPOINT"
+                && e.value.as_deref() == Some(LOCAL_OTLP_ENDPOINT)
+        }));
+    }
+
+    #[test]
+    fn test_sidecar_injected_into_both_workload_kinds() {
+        let statefulset = build_statefulset_for_test(&node(NodeType::Validator, true));
+        assert_injected(&statefulset.spec.unwrap().template.spec.unwrap());
+
+        let deployment = build_deployment_for_test(&node(NodeType::Horizon, true));
+        assert_injected(&deployment.spec.unwrap().template.spec.unwrap());
+    }
+
+    #[test]
+    fn test_disabled_collector_is_not_injected() {
+        let statefulset = build_statefulset_for_test(&node(NodeType::Validator, false));
+        let pod = statefulset.spec.unwrap().template.spec.unwrap();
+        assert!(!pod.containers.iter().any(|c| c.name == "otel-collector"));
+        assert!(!pod
+            .volumes
+            .unwrap_or_default()
+            .iter()
+            .any(|v| v.name == OTEL_CONFIG_VOLUME));
+    }
+}
+
+#[cfg(test)]
+mod pod_monitor_tests {
+    use crate::controller::operator_config::{
+        MetricsEndpointConfig, MetricsMonitorKind, MetricsTlsConfig,
+    };
+    use crate::controller::resources::{
+        build_pod_monitor, build_service_monitor, ensure_metrics_monitor,
+    };
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn node(node_type: NodeType) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type,
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("horizon", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    #[test]
+    fn test_pod_monitor_metadata_and_selector() {
+        let pm = build_pod_monitor(
+            &node(NodeType::Horizon),
+            &MetricsEndpointConfig::default(),
+            &MetricsTlsConfig::default(),
+        );
+        assert_eq!(pm.types.as_ref().unwrap().kind, "PodMonitor");
+        assert_eq!(pm.metadata.name.as_deref(), Some("horizon-pod-monitor"));
+        assert_eq!(pm.metadata.namespace.as_deref(), Some("stellar"));
+        assert_eq!(pm.metadata.owner_references.unwrap().len(), 1);
+        assert_eq!(
+            pm.data["spec"]["selector"]["matchLabels"]["app.kubernetes.io/instance"],
+            "horizon"
+        );
+        assert_eq!(
+            pm.data["spec"]["namespaceSelector"]["matchNames"][0],
+            "stellar"
+        );
+        assert!(pm.data["spec"].get("endpoints").is_none());
+    }
+
+    #[test]
+    fn test_pod_metrics_endpoint_mirrors_service_monitor() {
+        let metrics = MetricsEndpointConfig {
+            path: "/stats/prometheus".to_string(),
+            port: Some("exporter".to_string()),
+            ..Default::default()
+        };
+        let tls = MetricsTlsConfig {
+            enabled: true,
+            ca_secret: Some("metrics-ca".to_string()),
+            ..Default::default()
+        };
+        let horizon = node(NodeType::Horizon);
+        let pm = build_pod_monitor(&horizon, &metrics, &tls);
+        let sm = build_service_monitor(&horizon, &metrics, &tls);
+
+        let endpoint = &pm.data["spec"]["podMetricsEndpoints"][0];
+        assert_eq!(endpoint, &sm.data["spec"]["endpoints"][0]);
+        assert_eq!(endpoint["port"], "exporter");
+        assert_eq!(endpoint["path"], "/stats/prometheus");
+        assert_eq!(endpoint["scheme"], "https");
+        assert_eq!(endpoint["tlsConfig"]["ca"]["secret"]["name"], "metrics-ca");
+    }
+
+    #[tokio::test]
+    async fn test_pod_monitor_replaces_service_monitor() {
+        let (client, server) = fake_client();
+        let requests = server.serve();
+
+        ensure_metrics_monitor(
+            &client,
+            &node(NodeType::SorobanRpc),
+            MetricsMonitorKind::PodMonitor,
+            &MetricsEndpointConfig::default(),
+            &MetricsTlsConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let calls: Vec<_> = requests
+            .iter()
+            .map(|r| (r.method.as_str(), r.path.as_str()))
+            .collect();
+        assert_eq!(
+            calls,
+            [
+                (
+                    "PATCH",
+                    "/apis/monitoring.coreos.com/v1/namespaces/stellar/podmonitors/horizon-pod-monitor"
+                ),
+                (
+                    "DELETE",
+                    "/apis/monitoring.coreos.com/v1/namespaces/stellar/servicemonitors/horizon-service-monitor"
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validators_get_no_monitor() {
+        let (client, server) = fake_client();
+        let requests = server.serve();
+
+        ensure_metrics_monitor(
+            &client,
+            &node(NodeType::Validator),
+            MetricsMonitorKind::PodMonitor,
+            &MetricsEndpointConfig::default(),
+            &MetricsTlsConfig::default(),
+        )
+        .await
+        .unwrap();
+        assert!(requests.lock().unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod service_account_tests {
+    use std::collections::BTreeMap;
+
+    use k8s_openapi::api::rbac::v1::PolicyRule;
+
+    use crate::controller::resources::{
+        build_deployment_for_test, build_service_account, build_service_account_role,
+        build_statefulset_for_test, ensure_service_account,
+    };
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::types::{ServiceAccountConfig, ValidatorConfig};
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn node(node_type: NodeType, service_account: Option<ServiceAccountConfig>) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type,
+            validator_config: Some(ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                ..Default::default()
+            }),
+            service_account,
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("validator-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.uid = Some("uid-1".to_string());
+        node
+    }
+
+    fn irsa() -> ServiceAccountConfig {
+        ServiceAccountConfig {
+            annotations: BTreeMap::from([(
+                "eks.amazonaws.com/role-arn".to_string(),
+                "arn:aws:iam::123456789012:role/stellar".to_string(),
+            )]),
+            automount_token: Some(false),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_no_service_account_by_default() {
+        let node = node(NodeType::Validator, None);
+        assert!(build_service_account(&node).is_none());
+        let sts = build_statefulset_for_test(&node);
+        let pod_spec = sts.spec.unwrap().template.spec.unwrap();
+        assert_eq!(pod_spec.service_account_name, None);
+    }
+
+    #[test]
+    fn test_service_account_is_created_with_annotations() {
+        let node = node(NodeType::Validator, Some(irsa()));
+        let sa = build_service_account(&node).expect("ServiceAccount should be built");
+
+        assert_eq!(sa.metadata.name.as_deref(), Some("validator-1"));
+        assert_eq!(sa.metadata.namespace.as_deref(), Some("stellar"));
+        assert_eq!(
+            sa.metadata.annotations.unwrap()["eks.amazonaws.com/role-arn"],
+            "arn:aws:iam::123456789012:role/stellar"
+        );
+        assert_eq!(sa.automount_service_account_token, Some(false));
+        assert_eq!(sa.metadata.owner_references.unwrap()[0].uid, "uid-1");
+    }
+
+    #[test]
+    fn test_pod_spec_references_service_account() {
+        let node = node(NodeType::Validator, Some(irsa()));
+        let pod_spec = build_statefulset_for_test(&node)
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert_eq!(
+            pod_spec.service_account_name.as_deref(),
+            Some("validator-1")
+        );
+        assert_eq!(pod_spec.automount_service_account_token, Some(false));
+
+        let existing = ServiceAccountConfig {
+            create: false,
+            name: Some("shared-horizon".to_string()),
+            ..Default::default()
+        };
+        let node = node(NodeType::Horizon, Some(existing));
+        assert!(build_service_account(&node).is_none());
+        let pod_spec = build_deployment_for_test(&node)
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert_eq!(
+            pod_spec.service_account_name.as_deref(),
+            Some("shared-horizon")
+        );
+    }
+
+    #[test]
+    fn test_rules_are_bound_to_the_node_service_account_only() {
+        let node = node(NodeType::Validator, Some(irsa()));
+        assert!(build_service_account_role(&node).is_none());
+
+        let config = ServiceAccountConfig {
+            rules: vec![PolicyRule {
+                api_groups: Some(vec![String::new()]),
+                resources: Some(vec!["configmaps".to_string()]),
+                resource_names: Some(vec!["validator-1-config".to_string()]),
+                verbs: vec!["get".to_string()],
+                ..Default::default()
+            }],
+            ..irsa()
+        };
+        let node = node(NodeType::Validator, Some(config));
+        let (role, binding) = build_service_account_role(&node).expect("Role should be built");
+
+        assert_eq!(role.metadata.name.as_deref(), Some("validator-1-workload"));
+        assert_eq!(role.rules.unwrap()[0].verbs, vec!["get"]);
+        assert_eq!(binding.role_ref.kind, "Role");
+        assert_eq!(binding.role_ref.name, "validator-1-workload");
+        let subjects = binding.subjects.unwrap();
+        assert_eq!(subjects.len(), 1);
+        assert_eq!(subjects[0].kind, "ServiceAccount");
+        assert_eq!(subjects[0].name, "validator-1");
+        assert_eq!(subjects[0].namespace.as_deref(), Some("stellar"));
+    }
+
+    #[tokio::test]
+    async fn test_custom_named_service_account_is_removed_with_its_config() {
+        use k8s_openapi::api::core::v1::ServiceAccount;
+        use kube::api::{Api, PostParams};
+        use kube::ResourceExt;
+
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let accounts: Api<ServiceAccount> = Api::namespaced(client.clone(), "stellar");
+
+        // Left alone: not created by the operator for this node
+        let mut foreign = ServiceAccount::default();
+        foreign.metadata.name = Some("shared-horizon".to_string());
+        accounts
+            .create(&PostParams::default(), &foreign)
+            .await
+            .unwrap();
+
+        let named = |name: &str| ServiceAccountConfig {
+            name: Some(name.to_string()),
+            ..irsa()
+        };
+        for (config, expected) in [
+            (Some(named("core-a")), vec!["core-a", "shared-horizon"]),
+            (Some(named("core-b")), vec!["core-b", "shared-horizon"]),
+            (None, vec!["shared-horizon"]),
+        ] {
+            ensure_service_account(&client, &node(NodeType::Validator, config), false)
+                .await
+                .unwrap();
+            let names: Vec<_> = accounts
+                .list(&Default::default())
+                .await
+                .unwrap()
+                .items
+                .iter()
+                .map(|sa| sa.name_any())
+                .collect();
+            assert_eq!(names, expected);
+        }
+    }
+
+    #[test]
+    fn test_rules_outside_the_allowlist_are_not_granted() {
+        let config = ServiceAccountConfig {
+            rules: vec![PolicyRule {
+                api_groups: Some(vec![String::new()]),
+                resources: Some(vec!["secrets".to_string()]),
+                verbs: vec!["create".to_string()],
+                ..Default::default()
+            }],
+            ..irsa()
+        };
+        let node = node(NodeType::Validator, Some(config));
+        assert!(build_service_account_role(&node).is_none());
+    }
+}
+
+#[cfg(test)]
+mod dns_tests {
+    use k8s_openapi::api::core::v1::{PodDNSConfig, PodDNSConfigOption};
+
+    use crate::controller::resources::build_statefulset_for_test;
+    use crate::crd::types::ValidatorConfig;
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn validator(dns_policy: Option<&str>, dns_config: Option<PodDNSConfig>) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: NodeType::Validator,
+            validator_config: Some(ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                ..Default::default()
+            }),
+            dns_policy: dns_policy.map(str::to_string),
+            dns_config,
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("validator-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    #[test]
+    fn test_dns_defaults_left_to_kubernetes() {
+        let sts = build_statefulset_for_test(&validator(None, None));
+        let pod_spec = sts.spec.unwrap().template.spec.unwrap();
+        assert_eq!(pod_spec.dns_policy, None);
+        assert_eq!(pod_spec.dns_config, None);
+    }
+
+    #[test]
+    fn test_dns_policy_and_config_applied_to_pod_template() {
+        let dns_config = PodDNSConfig {
+            nameservers: Some(vec!["10.20.0.10".to_string()]),
+            searches: Some(vec!["stellar.svc.cluster-b.example.com".to_string()]),
+            options: Some(vec![PodDNSConfigOption {
+                name: Some("ndots".to_string()),
+                value: Some("2".to_string()),
+            }]),
+        };
+        let node = validator(Some("None"), Some(dns_config.clone()));
+        assert!(node.spec.validate().is_ok());
+
+        let sts = build_statefulset_for_test(&node);
+        let pod_spec = sts.spec.unwrap().template.spec.unwrap();
+        assert_eq!(pod_spec.dns_policy.as_deref(), Some("None"));
+        assert_eq!(pod_spec.dns_config, Some(dns_config));
+    }
+
+    #[test]
+    fn test_dns_policy_validation() {
+        let errors = validator(Some("ClusterLast"), None)
+            .spec
+            .validate()
+            .unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "spec.dnsPolicy"));
+
+        let errors = validator(Some("None"), None).spec.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "spec.dnsConfig"));
+
+        assert!(validator(Some("ClusterFirstWithHostNet"), None)
+            .spec
+            .validate()
+            .is_ok());
+    }
+}
+
+#[cfg(test)]
+mod host_network_tests {
+    use crate::controller::resources::build_statefulset_for_test;
+    use crate::crd::types::ValidatorConfig;
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn validator(host_network: bool) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: NodeType::Validator,
+            validator_config: Some(ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                ..Default::default()
+            }),
+            host_network,
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("validator-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    #[test]
+    fn test_host_network_off_by_default() {
+        let pod_spec = build_statefulset_for_test(&validator(false))
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert_eq!(pod_spec.host_network, None);
+        let main = &pod_spec.containers[0];
+        assert!(main.ports.iter().flatten().all(|p| p.host_port.is_none()));
+    }
+
+    #[test]
+    fn test_host_network_sets_pod_spec_and_host_ports() {
+        let node = validator(true);
+        assert!(node.spec.validate().is_ok());
+        assert!(node.spec.host_network_warning().is_some());
+
+        let pod_spec = build_statefulset_for_test(&node)
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert_eq!(pod_spec.host_network, Some(true));
+        assert_eq!(
+            pod_spec.dns_policy.as_deref(),
+            Some("ClusterFirstWithHostNet")
+        );
+        for container in &pod_spec.containers {
+            for port in container.ports.iter().flatten() {
+                assert_eq!(port.host_port, Some(port.container_port));
+            }
+        }
+        let main = pod_spec
+            .containers
+            .iter()
+            .find(|c| c.name == "stellar-node")
+            .unwrap();
+        assert!(main
+            .ports
+            .iter()
+            .flatten()
+            .any(|p| p.container_port == 11625 && p.host_port == Some(11625)));
+    }
+
+    #[test]
+    fn test_explicit_dns_policy_kept_with_host_network() {
+        let mut node = validator(true);
+        node.spec.dns_policy = Some("Default".to_string());
+        let pod_spec = build_statefulset_for_test(&node)
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        assert_eq!(pod_spec.dns_policy.as_deref(), Some("Default"));
+    }
+
+    #[test]
+    fn test_host_network_rejected_for_non_validators() {
+        for node_type in [NodeType::Horizon, NodeType::SorobanRpc] {
+            let spec = StellarNodeSpec {
+                node_type,
+                host_network: true,
+                ..validator(true).spec
+            };
+            let errors = spec.validate().unwrap_err();
+            assert!(
+                errors.iter().any(|e| e.field == "spec.hostNetwork"),
+                "{errors:?}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod ensure_tests {
+    use std::collections::BTreeMap;
+
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use crate::controller::resources::{ensure_config_map, ensure_service, ensure_statefulset};
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::types::ValidatorConfig;
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn validator() -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: NodeType::Validator,
+            validator_config: Some(ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("validator-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.uid = Some("uid-1".to_string());
+        node
+    }
+
+    #[tokio::test]
+    async fn test_ensure_service_applies_when_missing() {
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            let get = server.respond_not_found().await;
+            let patch = server.respond_echo().await;
+            (get, patch)
+        });
+
+        ensure_service(&client, &validator(), false, &BTreeMap::new(), false)
+            .await
+            .unwrap();
+        let (get, patch) = server.await.unwrap();
+
+        assert_eq!(get.method, Method::GET);
+        assert_eq!(get.path, "/api/v1/namespaces/stellar/services/validator-1");
+
+        assert_eq!(patch.method, Method::PATCH);
+        assert_eq!(
+            patch.path,
+            "/api/v1/namespaces/stellar/services/validator-1"
+        );
+        assert!(patch.has_query("fieldManager", "stellar-operator"));
+        assert!(patch.has_query("force", "true"));
+        assert!(!patch.query.contains("dryRun"));
+        assert_eq!(patch.body["kind"], "Service");
+        assert_eq!(patch.body["metadata"]["name"], "validator-1");
+        assert_eq!(patch.body["metadata"]["ownerReferences"][0]["uid"], "uid-1");
+    }
+
+    #[tokio::test]
+    async fn test_ensure_service_replaces_propagated_labels() {
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            server
+                .respond_with(&json!({
+                    "apiVersion": "v1",
+                    "kind": "Service",
+                    "metadata": {
+                        "name": "validator-1",
+                        "namespace": "stellar",
+                        "labels": { "team": "core", "cost-center": "42" }
+                    }
+                }))
+                .await;
+            server.respond_echo().await
+        });
+
+        let propagated = BTreeMap::from([("team".to_string(), "core".to_string())]);
+        ensure_service(&client, &validator(), false, &propagated, false)
+            .await
+            .unwrap();
+        let patch = server.await.unwrap();
+
+        let labels = &patch.body["metadata"]["labels"];
+        assert_eq!(labels["team"], "core");
+        assert!(
+            labels.get("cost-center").is_none(),
+            "labels no longer propagated should be dropped"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ensure_statefulset_dry_run() {
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            server.respond_not_found().await;
+            server.respond_echo().await
+        });
+
+        ensure_statefulset(&client, &validator(), false, None, &BTreeMap::new(), true)
+            .await
+            .unwrap();
+        let patch = server.await.unwrap();
+
+        assert_eq!(patch.method, Method::PATCH);
+        assert_eq!(
+            patch.path,
+            "/apis/apps/v1/namespaces/stellar/statefulsets/validator-1"
+        );
+        assert!(patch.has_query("dryRun", "All"));
+        assert_eq!(patch.body["kind"], "StatefulSet");
+        assert_eq!(
+            patch.body["spec"]["template"]["spec"]["containers"][0]["name"],
+            "stellar-node"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ensure_statefulset_surfaces_api_errors() {
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            server
+                .respond_status(StatusCode::FORBIDDEN, "Forbidden")
+                .await
+        });
+
+        let result =
+            ensure_statefulset(&client, &validator(), false, None, &BTreeMap::new(), false).await;
+        server.await.unwrap();
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_config_map_is_a_single_apply() {
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move { server.respond_echo().await });
+
+        ensure_config_map(&client, &validator(), None, false, false)
+            .await
+            .unwrap();
+        let patch = server.await.unwrap();
+
+        assert_eq!(patch.method, Method::PATCH);
+        assert_eq!(
+            patch.path,
+            "/api/v1/namespaces/stellar/configmaps/validator-1-config"
+        );
+        assert_eq!(patch.body["kind"], "ConfigMap");
+        assert!(patch.body["data"]["NETWORK_PASSPHRASE"].is_string());
+    }
+}
+
+#[cfg(test)]
+mod command_override_tests {
+    use crate::controller::resources::{build_deployment_for_test, build_statefulset_for_test};
+    use crate::crd::types::{HorizonConfig, ValidatorConfig};
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn node(node_type: NodeType, command: Option<&[&str]>, args: Option<&[&str]>) -> StellarNode {
+        let to_vec = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+        let spec = StellarNodeSpec {
+            node_type: node_type.clone(),
+            validator_config: (node_type == NodeType::Validator).then(|| ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                ..Default::default()
+            }),
+            horizon_config: (node_type == NodeType::Horizon).then(|| HorizonConfig {
+                database_secret_ref: "db".to_string(),
+                ..Default::default()
+            }),
+            command: command.map(to_vec),
+            args: args.map(to_vec),
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("node-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    #[test]
+    fn test_image_entrypoint_used_by_default() {
+        let sts = build_statefulset_for_test(&node(NodeType::Validator, None, None));
+        let main = &sts.spec.unwrap().template.spec.unwrap().containers[0];
+        assert_eq!(main.name, "stellar-node");
+        assert_eq!(main.command, None);
+        assert_eq!(main.args, None);
+    }
+
+    #[test]
+    fn test_validator_command_and_args_applied_to_main_container() {
+        let node = node(
+            NodeType::Validator,
+            Some(&["/usr/bin/stellar-core"]),
+            Some(&["run", "--conf", "/config/stellar-core.cfg"]),
+        );
+        assert!(node.spec.validate().is_ok());
+
+        let pod_spec = build_statefulset_for_test(&node)
+            .spec
+            .unwrap()
+            .template
+            .spec
+            .unwrap();
+        let main = pod_spec
+            .containers
+            .iter()
+            .find(|c| c.name == "stellar-node")
+            .unwrap();
+        assert_eq!(
+            main.command.as_deref(),
+            Some(&["/usr/bin/stellar-core".to_string()][..])
+        );
+        assert_eq!(main.args.as_ref().unwrap()[2], "/config/stellar-core.cfg");
+
+        // Sidecars keep their own entrypoints
+        for sidecar in pod_spec
+            .containers
+            .iter()
+            .filter(|c| c.name != "stellar-node")
+        {
+            assert_ne!(sidecar.command, main.command);
+        }
+    }
+
+    #[test]
+    fn test_horizon_args_only_override() {
+        let node = node(
+            NodeType::Horizon,
+            None,
+            Some(&["serve", "--apply-migrations"]),
+        );
+        let deployment = build_deployment_for_test(&node);
+        let main = &deployment.spec.unwrap().template.spec.unwrap().containers[0];
+        assert_eq!(main.command, None);
+        assert_eq!(
+            main.args,
+            Some(vec!["serve".to_string(), "--apply-migrations".to_string()])
+        );
+    }
+}
+
+#[cfg(test)]
+mod horizon_admin_tests {
+    use crate::controller::resources::{
+        build_admin_service, build_deployment_for_test, build_service_for_test,
+    };
+    use crate::crd::types::HorizonConfig;
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn horizon(admin_port: Option<u16>, per_hour_rate_limit: Option<u32>) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: NodeType::Horizon,
+            horizon_config: Some(HorizonConfig {
+                database_secret_ref: "db".to_string(),
+                admin_port,
+                per_hour_rate_limit,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("horizon-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    fn env_value(node: &StellarNode, name: &str) -> Option<String> {
+        let deployment = build_deployment_for_test(node);
+        deployment.spec.unwrap().template.spec.unwrap().containers[0]
+            .env
+            .as_ref()
+            .unwrap()
+            .iter()
+            .find(|e| e.name == name)
+            .and_then(|e| e.value.clone())
+    }
+
+    #[test]
+    fn test_admin_port_exposed_on_separate_cluster_ip_service() {
+        let node = horizon(Some(6060), None);
+        let ports = build_service_for_test(&node).spec.unwrap().ports.unwrap();
+        assert!(ports.iter().all(|p| p.name.as_deref() != Some("admin")));
+        assert!(ports.iter().any(|p| p.port == 8000));
+
+        let admin = build_admin_service(&node).expect("admin Service should be built");
+        assert_eq!(admin.metadata.name.as_deref(), Some("horizon-1-admin"));
+        let spec = admin.spec.unwrap();
+        assert_eq!(spec.type_.as_deref(), Some("ClusterIP"));
+        let ports = spec.ports.unwrap();
+        assert_eq!(ports.len(), 1);
+        assert_eq!(ports[0].name.as_deref(), Some("admin"));
+        assert_eq!(ports[0].port, 6060);
+    }
+
+    #[test]
+    fn test_admin_port_on_container_and_env() {
+        let node = horizon(Some(6060), None);
+        let deployment = build_deployment_for_test(&node);
+        let ports = deployment.spec.unwrap().template.spec.unwrap().containers[0]
+            .ports
+            .clone()
+            .unwrap();
+        assert!(ports
+            .iter()
+            .any(|p| p.name.as_deref() == Some("admin") && p.container_port == 6060));
+        assert_eq!(env_value(&node, "ADMIN_PORT").as_deref(), Some("6060"));
+    }
+
+    #[test]
+    fn test_no_admin_port_by_default() {
+        let node = horizon(None, None);
+        let service = build_service_for_test(&node);
+        assert_eq!(service.spec.unwrap().ports.unwrap().len(), 1);
+        assert!(build_admin_service(&node).is_none());
+        assert_eq!(env_value(&node, "ADMIN_PORT"), None);
+        assert_eq!(env_value(&node, "PER_HOUR_RATE_LIMIT"), None);
+    }
+
+    #[test]
+    fn test_rate_limit_rendered_into_env() {
+        let node = horizon(None, Some(7200));
+        assert_eq!(
+            env_value(&node, "PER_HOUR_RATE_LIMIT").as_deref(),
+            Some("7200")
+        );
+
+        let disabled = horizon(None, Some(0));
+        assert_eq!(
+            env_value(&disabled, "PER_HOUR_RATE_LIMIT").as_deref(),
+            Some("0")
+        );
+    }
+}
+
+#[cfg(test)]
+mod rolling_update_tests {
+    use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
+    use crate::controller::resources::build_deployment_for_test;
+    use crate::crd::types::{HorizonConfig, RolloutStrategy};
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn horizon(strategy: RolloutStrategy) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: NodeType::Horizon,
+            horizon_config: Some(HorizonConfig {
+                database_secret_ref: "db".to_string(),
+                ..Default::default()
+            }),
+            replicas: 10,
+            strategy,
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("horizon-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    #[test]
+    fn test_default_strategy_left_to_kubernetes() {
+        let deployment = build_deployment_for_test(&horizon(RolloutStrategy::default()));
+        assert_eq!(deployment.spec.unwrap().strategy, None);
+    }
+
+    #[test]
+    fn test_max_surge_and_max_unavailable_applied() {
+        let node = horizon(RolloutStrategy {
+            max_surge: Some(IntOrString::String("50%".to_string())),
+            max_unavailable: Some(IntOrString::Int(0)),
+            ..Default::default()
+        });
+        assert!(node.spec.validate().is_ok());
+
+        let strategy = build_deployment_for_test(&node)
+            .spec
+            .unwrap()
+            .strategy
+            .expect("strategy should be set");
+        assert_eq!(strategy.type_.as_deref(), Some("RollingUpdate"));
+        let rolling_update = strategy.rolling_update.unwrap();
+        assert_eq!(
+            rolling_update.max_surge,
+            Some(IntOrString::String("50%".to_string()))
+        );
+        assert_eq!(rolling_update.max_unavailable, Some(IntOrString::Int(0)));
+    }
+
+    #[test]
+    fn test_only_max_unavailable_set() {
+        let node = horizon(RolloutStrategy {
+            max_unavailable: Some(IntOrString::Int(2)),
+            ..Default::default()
+        });
+        let rolling_update = build_deployment_for_test(&node)
+            .spec
+            .unwrap()
+            .strategy
+            .unwrap()
+            .rolling_update
+            .unwrap();
+        assert_eq!(rolling_update.max_surge, None);
+        assert_eq!(rolling_update.max_unavailable, Some(IntOrString::Int(2)));
+    }
+
+    #[test]
+    fn test_min_ready_seconds_applied() {
+        let unset = build_deployment_for_test(&horizon(RolloutStrategy::default()));
+        assert_eq!(unset.spec.unwrap().min_ready_seconds, None);
+
+        let node = horizon(RolloutStrategy {
+            min_ready_seconds: Some(30),
+            ..Default::default()
+        });
+        assert!(node.spec.validate().is_ok());
+        let deployment = build_deployment_for_test(&node);
+        assert_eq!(deployment.spec.unwrap().min_ready_seconds, Some(30));
+    }
+
+    #[test]
+    fn test_progress_deadline_seconds_applied() {
+        let unset = build_deployment_for_test(&horizon(RolloutStrategy::default()));
+        assert_eq!(unset.spec.unwrap().progress_deadline_seconds, None);
+
+        let node = horizon(RolloutStrategy {
+            progress_deadline_seconds: Some(300),
+            ..Default::default()
+        });
+        assert!(node.spec.validate().is_ok());
+        let deployment = build_deployment_for_test(&node);
+        assert_eq!(
+            deployment.spec.unwrap().progress_deadline_seconds,
+            Some(300)
+        );
+    }
+}
+
+#[cfg(test)]
+mod bucket_list_db_config_tests {
+    use crate::controller::resources::build_config_map_for_test;
+    use crate::crd::types::{BucketListDbConfig, BucketListStorage, ValidatorConfig};
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn validator(bucket_list_db: Option<BucketListDbConfig>) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: NodeType::Validator,
+            validator_config: Some(ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                quorum_set: Some(
+                    "[QUORUM_SET]\nTHRESHOLD_PERCENT=67\nVALIDATORS=[\"GA\", \"GB\"]\n".to_string(),
+                ),
+                bucket_list_db,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("validator", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    fn core_cfg(node: &StellarNode) -> toml::Value {
+        let cfg = build_config_map_for_test(node)
+            .data
+            .unwrap()
+            .remove("stellar-core.cfg")
+            .unwrap();
+        cfg.parse().expect("stellar-core.cfg should be valid TOML")
+    }
+
+    #[test]
+    fn test_on_disk_settings_rendered_as_top_level_keys() {
+        let cfg = core_cfg(&validator(Some(BucketListDbConfig {
+            index_page_size_exponent: Some(16),
+            index_cutoff_mb: Some(100),
+            persist_index: Some(false),
+            memory_for_caching_mb: Some(2048),
+            ..Default::default()
+        })));
+
+        assert_eq!(
+            cfg["BUCKETLIST_DB_INDEX_PAGE_SIZE_EXPONENT"].as_integer(),
+            Some(16)
+        );
+        assert_eq!(cfg["BUCKETLIST_DB_INDEX_CUTOFF"].as_integer(), Some(100));
+        assert_eq!(cfg["BUCKETLIST_DB_PERSIST_INDEX"].as_bool(), Some(false));
+        assert_eq!(
+            cfg["BUCKETLIST_DB_MEMORY_FOR_CACHING"].as_integer(),
+            Some(2048)
+        );
+        // The quorum set table is left intact
+        assert!(cfg["QUORUM_SET"]
+            .get("BUCKETLIST_DB_INDEX_CUTOFF")
+            .is_none());
+    }
+
+    #[test]
+    fn test_in_memory_storage_uses_individual_indexes() {
+        let cfg = core_cfg(&validator(Some(BucketListDbConfig {
+            storage: BucketListStorage::InMemory,
+            ..Default::default()
+        })));
+        assert_eq!(
+            cfg["BUCKETLIST_DB_INDEX_PAGE_SIZE_EXPONENT"].as_integer(),
+            Some(0)
+        );
+        assert!(cfg.get("BUCKETLIST_DB_PERSIST_INDEX").is_none());
+    }
+
+    #[test]
+    fn test_unset_settings_keep_core_defaults() {
+        for db in [None, Some(BucketListDbConfig::default())] {
+            let cfg = core_cfg(&validator(db));
+            let keys: Vec<&String> = cfg.as_table().unwrap().keys().collect();
+            assert!(
+                !keys.iter().any(|key| key.starts_with("BUCKETLIST_DB")),
+                "{keys:?}"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod log_level_annotation_tests {
+    use std::collections::BTreeMap;
+
+    use crate::controller::resources::{
+        build_deployment_for_test, build_statefulset_for_test, log_level_env, requested_log_level,
+        LOG_LEVEL_ANNOTATION,
+    };
+    use crate::crd::types::{HorizonConfig, ValidatorConfig};
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn node(node_type: NodeType, log_level: Option<&str>) -> StellarNode {
+        let spec = StellarNodeSpec {
+            node_type: node_type.clone(),
+            validator_config: (node_type == NodeType::Validator).then(|| ValidatorConfig {
+                seed_secret_ref: "seed".to_string(),
+                ..Default::default()
+            }),
+            horizon_config: (node_type == NodeType::Horizon).then(|| HorizonConfig {
+                database_secret_ref: "db".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut node = StellarNode::new("node-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        if let Some(level) = log_level {
+            node.metadata.annotations = Some(BTreeMap::from([(
+                LOG_LEVEL_ANNOTATION.to_string(),
+                level.to_string(),
+            )]));
+        }
+        node
+    }
+
+    #[test]
+    fn test_annotation_maps_to_core_level() {
+        for (annotation, level) in [
+            ("debug", "debug"),
+            ("TRACE", "trace"),
+            (" info ", "info"),
+            ("warn", "warning"),
+            ("warning", "warning"),
+            ("error", "error"),
+            ("fatal", "fatal"),
+        ] {
+            assert_eq!(
+                requested_log_level(&node(NodeType::Validator, Some(annotation))),
+                Some(level),
+                "{annotation}"
+            );
+        }
+        // stellar-core ignores its environment; the level goes through `ll`
+        assert_eq!(
+            log_level_env(&node(NodeType::Validator, Some("debug"))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_annotation_maps_to_horizon_and_rpc_env() {
+        for node_type in [NodeType::Horizon, NodeType::SorobanRpc] {
+            assert_eq!(
+                log_level_env(&node(node_type.clone(), Some("debug"))),
+                Some(("LOG_LEVEL", "debug"))
+            );
+            assert_eq!(
+                log_level_env(&node(node_type, Some("warning"))),
+                Some(("LOG_LEVEL", "warn"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_or_unknown_level_sets_nothing() {
+        assert_eq!(requested_log_level(&node(NodeType::Validator, None)), None);
+        assert_eq!(
+            requested_log_level(&node(NodeType::Validator, Some("verbose"))),
+            None
+        );
+        assert_eq!(
+            requested_log_level(&node(NodeType::Validator, Some(""))),
+            None
+        );
+        assert_eq!(
+            log_level_env(&node(NodeType::Horizon, Some("verbose"))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_level_env_lands_on_main_container() {
+        let deployment = build_deployment_for_test(&node(NodeType::Horizon, Some("debug")));
+        let container = &deployment.spec.unwrap().template.spec.unwrap().containers[0];
+        assert!(container
+            .env
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|e| e.name == "LOG_LEVEL" && e.value.as_deref() == Some("debug")));
+
+        // A validator's level changes nothing in its pod template
+        let annotated = build_statefulset_for_test(&node(NodeType::Validator, Some("debug")));
+        let plain = build_statefulset_for_test(&node(NodeType::Validator, None));
+        assert_eq!(
+            annotated.spec.unwrap().template,
+            plain.spec.unwrap().template
+        );
+
+        let deployment = build_deployment_for_test(&node(NodeType::Horizon, None));
+        let container = &deployment.spec.unwrap().template.spec.unwrap().containers[0];
+        assert!(!container
+            .env
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|e| e.name == "LOG_LEVEL"));
+    }
+}
+
+#[cfg(test)]
+mod node_type_change_tests {
+    use std::collections::BTreeMap;
+
+    use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+    use kube::api::{Api, PostParams};
+
+    use crate::controller::resources::{
+        delete_stale_workload, ensure_deployment, ensure_statefulset, workload_kind,
+    };
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::types::HorizonConfig;
+    use crate::crd::{NodeType, StellarNode, StellarNodeSpec};
+
+    fn node(node_type: NodeType) -> StellarNode {
+        let mut node = StellarNode::new(
+            "node-1",
+            StellarNodeSpec {
+                horizon_config: (node_type == NodeType::Horizon).then(|| HorizonConfig {
+                    database_secret_ref: "db".to_string(),
+                    ..Default::default()
+                }),
+                node_type,
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.uid = Some("uid-1".to_string());
+        node
+    }
+
+    fn metadata(owner_uid: &str) -> serde_json::Value {
+        serde_json::json!({
+            "name": "node-1",
+            "ownerReferences": [{
+                "apiVersion": "stellar.org/v1alpha1",
+                "kind": "StellarNode",
+                "name": "node-1",
+                "uid": owner_uid
+            }]
+        })
+    }
+
+    fn deployment(owner_uid: &str) -> Deployment {
+        serde_json::from_value(serde_json::json!({
+            "metadata": metadata(owner_uid),
+            "spec": { "selector": {}, "template": {} }
+        }))
+        .unwrap()
+    }
+
+    fn statefulset(owner_uid: &str) -> StatefulSet {
+        serde_json::from_value(serde_json::json!({
+            "metadata": metadata(owner_uid),
+            "spec": { "selector": {}, "serviceName": "node-1", "template": {} }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_workload_kind_matches_node_type() {
+        assert_eq!(workload_kind(&NodeType::Validator), "StatefulSet");
+        assert_eq!(workload_kind(&NodeType::Horizon), "Deployment");
+        assert_eq!(workload_kind(&NodeType::SorobanRpc), "Deployment");
+    }
+
+    #[tokio::test]
+    async fn test_wrong_kind_is_never_created() {
+        let (client, server) = fake_client();
+        let requests = server.serve();
+        let labels = BTreeMap::new();
+
+        let err = ensure_deployment(&client, &node(NodeType::Validator), false, &labels, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("StatefulSet"), "{err}");
+        for node_type in [NodeType::Horizon, NodeType::SorobanRpc] {
+            assert!(
+                ensure_statefulset(&client, &node(node_type), false, None, &labels, false)
+                    .await
+                    .is_err()
+            );
+        }
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_node_type_change_deletes_owned_leftover() {
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let api: Api<Deployment> = Api::namespaced(client.clone(), "stellar");
+        api.create(&PostParams::default(), &deployment("uid-1"))
+            .await
+            .unwrap();
+
+        // Still Horizon: the Deployment is the right kind and stays
+        assert!(
+            !delete_stale_workload(&client, &node(NodeType::Horizon), false)
+                .await
+                .unwrap()
+        );
+        assert!(api.get_opt("node-1").await.unwrap().is_some());
+
+        // Switched to Validator: the Deployment is a leftover
+        assert!(
+            delete_stale_workload(&client, &node(NodeType::Validator), false)
+                .await
+                .unwrap()
+        );
+        assert!(api.get_opt("node-1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_leftover_not_owned_by_node_is_kept() {
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let api: Api<Deployment> = Api::namespaced(client.clone(), "stellar");
+        api.create(&PostParams::default(), &deployment("someone-else"))
+            .await
+            .unwrap();
+
+        assert!(
+            !delete_stale_workload(&client, &node(NodeType::Validator), false)
+                .await
+                .unwrap()
+        );
+        assert!(api.get_opt("node-1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_validator_to_horizon_migrates_statefulset_to_deployment() {
+        let (client, server) = fake_client();
+        let _requests = server.serve();
+        let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), "stellar");
+        let deployments: Api<Deployment> = Api::namespaced(client.clone(), "stellar");
+        statefulsets
+            .create(&PostParams::default(), &statefulset("uid-1"))
+            .await
+            .unwrap();
+
+        // The workload steps of a reconcile after nodeType became Horizon
+        let horizon = node(NodeType::Horizon);
+        assert!(delete_stale_workload(&client, &horizon, false)
+            .await
+            .unwrap());
+        ensure_deployment(&client, &horizon, false, &BTreeMap::new(), false)
+            .await
+            .unwrap();
+
+        assert!(statefulsets.get_opt("node-1").await.unwrap().is_none());
+        let deployment = deployments.get("node-1").await.unwrap();
+        assert_eq!(
+            deployment.metadata.labels.unwrap()["app.kubernetes.io/component"],
+            "horizon"
+        );
+
+        // Later reconciles find nothing left to migrate
+        assert!(!delete_stale_workload(&client, &horizon, false)
+            .await
+            .unwrap());
+    }
+}
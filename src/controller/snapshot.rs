@@ -3,9 +3,12 @@
 //! When a StellarNode (Validator) has `snapshotSchedule` configured, the operator
 //! creates Kubernetes VolumeSnapshot resources targeting the node's data PVC. Optionally
 //! flushes the Stellar database before the snapshot for consistency, then resumes normal operations.
+//!
+//! When such a node is deleted with `retentionPolicy: Delete`, the finalizer takes one last
+//! snapshot of the data PVC and waits for it to become ready before deleting the volume. The
+//! final snapshot has no owner reference so it outlives the StellarNode.
 
 use std::collections::BTreeMap;
-use std::str::FromStr;
 
 use chrono::Utc;
 use kube::api::{Api, DeleteParams, DynamicObject, ListParams, Patch, PatchParams, PostParams};
@@ -17,15 +20,35 @@ use crate::controller::resource_meta::merge_resource_meta;
 use crate::controller::resources::{
     owner_reference, resource_name, standard_labels as node_standard_labels,
 };
-use crate::crd::{SnapshotScheduleConfig, StellarNode};
+use crate::crd::{parse_unix_cron, SnapshotScheduleConfig, StellarNode};
 use crate::error::{Error, Result};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 
 const REQUEST_SNAPSHOT_ANNOTATION: &str = "stellar.org/request-snapshot";
 const LAST_SNAPSHOT_AT_ANNOTATION: &str = "stellar.org/last-snapshot-at";
 
+/// Label linking a final snapshot to the StellarNode it was taken from
+pub const FINAL_SNAPSHOT_OF_LABEL: &str = "stellar.org/final-snapshot-of";
+
+/// How often the finalizer checks whether the final snapshot is ready
+pub const FINAL_SNAPSHOT_POLL_SECS: u64 = 15;
+
+/// How long the finalizer waits for the final snapshot before deleting the PVC anyway
+pub const FINAL_SNAPSHOT_TIMEOUT_SECS: i64 = 1800;
+
+/// Progress of the snapshot taken before the data PVC is deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalSnapshotState {
+    /// The snapshot is `readyToUse`; the PVC can be deleted
+    Ready,
+    /// Still being cut; the finalizer should check again later
+    Pending,
+    /// Not ready within [`FINAL_SNAPSHOT_TIMEOUT_SECS`]; deletion proceeds without it
+    TimedOut,
+}
+
 /// VolumeSnapshot API resource for snapshot.storage.k8s.io/v1
-fn volume_snapshot_api_resource() -> ApiResource {
+pub(crate) fn volume_snapshot_api_resource() -> ApiResource {
     ApiResource {
         group: "snapshot.storage.k8s.io".to_string(),
         version: "v1".to_string(),
@@ -139,7 +162,7 @@ fn schedule_matches_now(config: &SnapshotScheduleConfig, node: &StellarNode) ->
         Some(s) if !s.is_empty() => s,
         _ => return false,
     };
-    let s = match cron::Schedule::from_str(schedule) {
+    let s = match parse_unix_cron(schedule) {
         Ok(s) => s,
         Err(_) => return false,
     };
@@ -168,27 +191,31 @@ fn request_db_flush(_client: &Client, _node: &StellarNode) -> Result<()> {
     Ok(())
 }
 
-/// Create a VolumeSnapshot targeting the node's data PVC.
-async fn create_volume_snapshot(
-    client: &Client,
+/// Build a VolumeSnapshot of `pvc_name`.
+///
+/// Scheduled snapshots are owned by the node. Final snapshots are not, so the
+/// garbage collector keeps them after the StellarNode is gone.
+fn build_volume_snapshot(
     node: &StellarNode,
     snapshot_name: &str,
     pvc_name: &str,
     config: &SnapshotScheduleConfig,
-) -> Result<()> {
-    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    final_snapshot: bool,
+) -> DynamicObject {
     let api_resource = volume_snapshot_api_resource();
-    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), &namespace, &api_resource);
+
+    let mut labels = node_standard_labels(node);
+    if final_snapshot {
+        labels.insert(FINAL_SNAPSHOT_OF_LABEL.to_string(), node.name_any());
+    } else {
+        labels.insert("stellar.org/snapshot-of".to_string(), node.name_any());
+    }
 
     let meta = ObjectMeta {
         name: Some(snapshot_name.to_string()),
-        namespace: Some(namespace.clone()),
-        labels: Some({
-            let mut l = node_standard_labels(node);
-            l.insert("stellar.org/snapshot-of".to_string(), node.name_any());
-            l
-        }),
-        owner_references: Some(vec![owner_reference(node)]),
+        namespace: node.namespace(),
+        labels: Some(labels),
+        owner_references: (!final_snapshot).then(|| vec![owner_reference(node)]),
         ..Default::default()
     };
 
@@ -204,7 +231,7 @@ async fn create_volume_snapshot(
         })
     });
 
-    let snapshot = DynamicObject {
+    DynamicObject {
         types: Some(kube::core::TypeMeta {
             api_version: api_resource.api_version.clone(),
             kind: api_resource.kind.clone(),
@@ -213,7 +240,22 @@ async fn create_volume_snapshot(
         data: serde_json::json!({
             "spec": spec
         }),
-    };
+    }
+}
+
+/// Create a VolumeSnapshot targeting the node's data PVC.
+async fn create_volume_snapshot(
+    client: &Client,
+    node: &StellarNode,
+    snapshot_name: &str,
+    pvc_name: &str,
+    config: &SnapshotScheduleConfig,
+) -> Result<()> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api_resource = volume_snapshot_api_resource();
+    let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), &namespace, &api_resource);
+
+    let snapshot = build_volume_snapshot(node, snapshot_name, pvc_name, config, false);
 
     match api.get(snapshot_name).await {
         Ok(_) => {
@@ -300,3 +342,228 @@ async fn update_snapshot_annotations(
 
     Ok(())
 }
+
+/// Name of the VolumeSnapshot taken right before the data PVC is deleted
+pub fn final_snapshot_name(node: &StellarNode) -> String {
+    resource_name(node, "data-final")
+}
+
+/// Classify a final snapshot by its `status.readyToUse` and age.
+fn final_snapshot_state(
+    snapshot: &DynamicObject,
+    now: chrono::DateTime<Utc>,
+) -> FinalSnapshotState {
+    let ready = snapshot
+        .data
+        .pointer("/status/readyToUse")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if ready {
+        return FinalSnapshotState::Ready;
+    }
+
+    let age_secs = snapshot
+        .metadata
+        .creation_timestamp
+        .as_ref()
+        .map(|t| (now - t.0).num_seconds())
+        .unwrap_or(0);
+    if age_secs >= FINAL_SNAPSHOT_TIMEOUT_SECS {
+        FinalSnapshotState::TimedOut
+    } else {
+        FinalSnapshotState::Pending
+    }
+}
+
+/// Whether the final snapshot of the node's data PVC has already been
+/// requested, i.e. the finalizer is polling for it rather than starting.
+pub async fn final_snapshot_requested(client: &Client, node: &StellarNode) -> Result<bool> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<DynamicObject> =
+        Api::namespaced_with(client.clone(), &namespace, &volume_snapshot_api_resource());
+    Ok(api.get_opt(&final_snapshot_name(node)).await?.is_some())
+}
+
+/// Take (or check on) the final snapshot of the node's data PVC.
+///
+/// Called from the finalizer before the PVC is deleted. The snapshot is
+/// created on the first call; later calls report whether it is ready yet.
+#[instrument(skip(client, node, config), fields(name = %node.name_any(), namespace = node.namespace()))]
+pub async fn ensure_final_snapshot(
+    client: &Client,
+    node: &StellarNode,
+    config: &SnapshotScheduleConfig,
+) -> Result<FinalSnapshotState> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let api: Api<DynamicObject> =
+        Api::namespaced_with(client.clone(), &namespace, &volume_snapshot_api_resource());
+    let snapshot_name = final_snapshot_name(node);
+
+    match api.get_opt(&snapshot_name).await? {
+        Some(existing) => Ok(final_snapshot_state(&existing, Utc::now())),
+        None => {
+            let pvc_name = resource_name(node, "data");
+            info!(
+                "Creating final VolumeSnapshot {} for PVC {} before deletion",
+                snapshot_name, pvc_name
+            );
+            let snapshot = build_volume_snapshot(node, &snapshot_name, &pvc_name, config, true);
+            api.create(&PostParams::default(), &snapshot).await?;
+            Ok(FinalSnapshotState::Pending)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::StellarNodeSpec;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+    fn node() -> StellarNode {
+        let mut node = StellarNode::new("validator-1", StellarNodeSpec::default());
+        node.metadata.namespace = Some("stellar".to_string());
+        node.metadata.uid = Some("uid-1".to_string());
+        node
+    }
+
+    fn snapshot(ready: Option<bool>, age_secs: i64) -> DynamicObject {
+        let mut snapshot = build_volume_snapshot(
+            &node(),
+            "validator-1-data-final",
+            "validator-1-data",
+            &SnapshotScheduleConfig::default(),
+            true,
+        );
+        snapshot.metadata.creation_timestamp =
+            Some(Time(Utc::now() - chrono::Duration::seconds(age_secs)));
+        if let Some(ready) = ready {
+            snapshot.data["status"] = serde_json::json!({ "readyToUse": ready });
+        }
+        snapshot
+    }
+
+    #[test]
+    fn test_final_snapshot_is_not_owned_by_node() {
+        let config = SnapshotScheduleConfig {
+            volume_snapshot_class_name: Some("csi-snapclass".to_string()),
+            ..Default::default()
+        };
+        let snapshot = build_volume_snapshot(
+            &node(),
+            &final_snapshot_name(&node()),
+            "validator-1-data",
+            &config,
+            true,
+        );
+
+        assert_eq!(snapshot.name_any(), "validator-1-data-final");
+        assert!(snapshot.metadata.owner_references.is_none());
+        assert_eq!(
+            snapshot.labels().get(FINAL_SNAPSHOT_OF_LABEL),
+            Some(&"validator-1".to_string())
+        );
+        assert_eq!(
+            snapshot.data["spec"]["source"]["persistentVolumeClaimName"],
+            "validator-1-data"
+        );
+        assert_eq!(
+            snapshot.data["spec"]["volumeSnapshotClassName"],
+            "csi-snapclass"
+        );
+    }
+
+    #[test]
+    fn test_scheduled_snapshot_is_owned_by_node() {
+        let snapshot = build_volume_snapshot(
+            &node(),
+            "validator-1-data-20240101-000000",
+            "validator-1-data",
+            &SnapshotScheduleConfig::default(),
+            false,
+        );
+        assert_eq!(snapshot.metadata.owner_references.unwrap().len(), 1);
+        assert!(!snapshot.labels().contains_key(FINAL_SNAPSHOT_OF_LABEL));
+    }
+
+    #[test]
+    fn test_final_snapshot_state_waits_until_ready() {
+        let now = Utc::now();
+        assert_eq!(
+            final_snapshot_state(&snapshot(None, 10), now),
+            FinalSnapshotState::Pending
+        );
+        assert_eq!(
+            final_snapshot_state(&snapshot(Some(false), 10), now),
+            FinalSnapshotState::Pending
+        );
+        assert_eq!(
+            final_snapshot_state(&snapshot(Some(true), 10), now),
+            FinalSnapshotState::Ready
+        );
+    }
+
+    #[test]
+    fn test_final_snapshot_state_times_out() {
+        assert_eq!(
+            final_snapshot_state(
+                &snapshot(Some(false), FINAL_SNAPSHOT_TIMEOUT_SECS + 1),
+                Utc::now()
+            ),
+            FinalSnapshotState::TimedOut
+        );
+    }
+
+    #[tokio::test]
+    async fn test_final_snapshot_is_created_then_waited_on_until_timeout() {
+        let (client, mut server) = fake_client();
+        let stale =
+            serde_json::to_value(snapshot(Some(false), FINAL_SNAPSHOT_TIMEOUT_SECS + 1)).unwrap();
+        let server = tokio::spawn(async move {
+            server.respond_not_found().await;
+            let create = server.respond_echo().await;
+            let poll = server.respond_with(&stale).await;
+            (create, poll)
+        });
+
+        let config = SnapshotScheduleConfig::default();
+        assert_eq!(
+            ensure_final_snapshot(&client, &node(), &config)
+                .await
+                .unwrap(),
+            FinalSnapshotState::Pending
+        );
+        assert_eq!(
+            ensure_final_snapshot(&client, &node(), &config)
+                .await
+                .unwrap(),
+            FinalSnapshotState::TimedOut
+        );
+
+        let (create, poll) = server.await.unwrap();
+        assert_eq!(create.method, http::Method::POST);
+        assert_eq!(create.body["metadata"]["name"], "validator-1-data-final");
+        assert_eq!(poll.method, http::Method::GET);
+        assert!(poll
+            .path
+            .ends_with("/volumesnapshots/validator-1-data-final"));
+    }
+
+    #[tokio::test]
+    async fn test_final_snapshot_api_failure_is_reported() {
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move {
+            server
+                .respond_status(http::StatusCode::INTERNAL_SERVER_ERROR, "InternalError")
+                .await
+        });
+
+        assert!(
+            ensure_final_snapshot(&client, &node(), &SnapshotScheduleConfig::default())
+                .await
+                .is_err()
+        );
+        server.await.unwrap();
+    }
+}
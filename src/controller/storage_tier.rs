@@ -0,0 +1,332 @@
+//! Storage tier selection for node data volumes
+//!
+//! `spec.storage.tier` records whether a node's data volume must be on SSD
+//! or HDD. StorageClasses are classified from the disk type their
+//! provisioner is given (`type: gp3`, `type: pd-ssd`, `skuName: Premium_LRS`,
+//! ...) and, failing that, from their name. With an empty `storageClass` the
+//! operator picks a class of the requested tier, preferring the cluster
+//! default; with a class set it refuses one of the other tier.
+//!
+//! A class is only selected when a volume is created: an existing data PVC
+//! keeps its `storageClassName`, which is then only checked against the
+//! tier, and the read pool's claim template keeps the class it was created
+//! with unless `storageClass` names another.
+//!
+//! Validators without a tier are expected on SSD: a class known to be HDD is
+//! only reported with a warning so existing nodes keep reconciling. Classes
+//! that cannot be classified are accepted, as are local volumes.
+
+use k8s_openapi::api::storage::v1::StorageClass;
+use kube::api::{Api, ListParams};
+use kube::{Client, ResourceExt};
+use tracing::{info, warn};
+
+use crate::crd::types::{StorageMode, StorageTier};
+use crate::crd::{NodeType, StellarNode};
+use crate::error::{Error, Result};
+
+/// Annotation marking the cluster's default StorageClass
+const DEFAULT_CLASS_ANNOTATION: &str = "storageclass.kubernetes.io/is-default-class";
+
+/// Provisioner parameters naming the disk type, across the major CSI drivers
+const DISK_TYPE_PARAMETERS: &[&str] = &["type", "skuName", "skuname", "storageaccounttype"];
+
+/// Tier of a provider disk type such as `gp3`, `pd-standard` or `Premium_LRS`
+pub fn disk_type_tier(disk_type: &str) -> Option<StorageTier> {
+    let disk_type = disk_type.to_ascii_lowercase();
+    match disk_type.as_str() {
+        // AWS EBS
+        "gp2" | "gp3" | "io1" | "io2" => Some(StorageTier::Ssd),
+        "st1" | "sc1" | "standard" => Some(StorageTier::Hdd),
+        // GCE PD
+        "pd-ssd" | "pd-balanced" | "pd-extreme" => Some(StorageTier::Ssd),
+        "pd-standard" => Some(StorageTier::Hdd),
+        // Azure Disk
+        "premium_lrs" | "premium_zrs" | "premiumv2_lrs" | "standardssd_lrs" | "standardssd_zrs"
+        | "ultrassd_lrs" => Some(StorageTier::Ssd),
+        "standard_lrs" => Some(StorageTier::Hdd),
+        _ if disk_type.starts_with("hyperdisk-") => Some(StorageTier::Ssd),
+        _ => None,
+    }
+}
+
+/// Tier suggested by a StorageClass name
+fn name_tier(name: &str) -> Option<StorageTier> {
+    let name = name.to_ascii_lowercase();
+    if ["ssd", "nvme", "premium", "fast"]
+        .iter()
+        .any(|hint| name.contains(hint))
+    {
+        Some(StorageTier::Ssd)
+    } else if ["hdd", "slow", "cold"]
+        .iter()
+        .any(|hint| name.contains(hint))
+    {
+        Some(StorageTier::Hdd)
+    } else {
+        None
+    }
+}
+
+/// Tier of the disks a StorageClass provisions, if it can be told
+pub fn storage_class_tier(class: &StorageClass) -> Option<StorageTier> {
+    class
+        .parameters
+        .iter()
+        .flatten()
+        .filter(|(key, _)| DISK_TYPE_PARAMETERS.contains(&key.as_str()))
+        .find_map(|(_, value)| disk_type_tier(value))
+        .or_else(|| name_tier(&class.name_any()))
+}
+
+fn is_default_class(class: &StorageClass) -> bool {
+    class
+        .annotations()
+        .get(DEFAULT_CLASS_ANNOTATION)
+        .is_some_and(|v| v == "true")
+}
+
+/// StorageClass of `tier` to use when none is named: the cluster default if
+/// it is of that tier, otherwise the first matching class by name
+pub fn select_storage_class(tier: &StorageTier, classes: &[StorageClass]) -> Option<String> {
+    let mut matching: Vec<&StorageClass> = classes
+        .iter()
+        .filter(|class| storage_class_tier(class).as_ref() == Some(tier))
+        .collect();
+    matching.sort_by_key(|class| (!is_default_class(class), class.name_any()));
+    matching.first().map(|class| class.name_any())
+}
+
+/// Tier the node's data volume is expected on and whether the spec asks for
+/// it explicitly
+pub fn expected_tier(node: &StellarNode) -> Option<(StorageTier, bool)> {
+    match (&node.spec.storage.tier, &node.spec.node_type) {
+        (Some(tier), _) => Some((tier.clone(), true)),
+        (None, NodeType::Validator) => Some((StorageTier::Ssd, false)),
+        (None, _) => None,
+    }
+}
+
+/// Check `class` provides the tier the node expects; an implicit
+/// expectation only warns.
+pub fn check_storage_class_tier(node: &StellarNode, class: &StorageClass) -> Result<()> {
+    let Some((expected, explicit)) = expected_tier(node) else {
+        return Ok(());
+    };
+    let Some(actual) = storage_class_tier(class) else {
+        return Ok(());
+    };
+    if actual == expected {
+        return Ok(());
+    }
+
+    let message = format!(
+        "StorageClass {} provisions {actual:?} volumes but {}/{} expects {expected:?}",
+        class.name_any(),
+        node.namespace().unwrap_or_default(),
+        node.name_any()
+    );
+    if !explicit {
+        warn!("{message}; set spec.storage.tier to make this an error");
+        return Ok(());
+    }
+    Err(Error::ValidationError(format!(
+        "{message}. Choose a StorageClass of the {expected:?} tier or leave \
+         spec.storage.storageClass empty to have one selected"
+    )))
+}
+
+/// StorageClass of the node's `spec.storage.tier`, preferring the cluster
+/// default; `None` when the spec sets no tier.
+pub async fn select_tiered_storage_class(
+    client: &Client,
+    node: &StellarNode,
+) -> Result<Option<String>> {
+    let Some(tier) = &node.spec.storage.tier else {
+        return Ok(None);
+    };
+    let api: Api<StorageClass> = Api::all(client.clone());
+    let classes = api.list(&ListParams::default()).await?.items;
+    match select_storage_class(tier, &classes) {
+        Some(class) => {
+            info!(
+                "Selected StorageClass {} for {:?} storage of {}/{}",
+                class,
+                tier,
+                node.namespace().unwrap_or_default(),
+                node.name_any()
+            );
+            Ok(Some(class))
+        }
+        None => Err(Error::ValidationError(format!(
+            "no StorageClass of the {tier:?} tier found; set spec.storage.storageClass"
+        ))),
+    }
+}
+
+/// Check the StorageClass named `class` against the tier the node expects.
+pub async fn check_named_storage_class(
+    client: &Client,
+    node: &StellarNode,
+    class: &str,
+) -> Result<()> {
+    if node.spec.storage.mode == StorageMode::Local || expected_tier(node).is_none() {
+        return Ok(());
+    }
+    let api: Api<StorageClass> = Api::all(client.clone());
+    match api.get_opt(class).await {
+        Ok(Some(class)) => check_storage_class_tier(node, &class)?,
+        Ok(None) => {}
+        // Without access to StorageClasses the tier cannot be checked
+        Err(e) => warn!("Could not read StorageClass {}: {}", class, e),
+    }
+    Ok(())
+}
+
+/// StorageClass a new PVC of the node should use: `resolved` unless a tier
+/// selects one, after checking the class against the tier the node expects.
+pub async fn resolve_tiered_storage_class(
+    client: &Client,
+    node: &StellarNode,
+    resolved: String,
+) -> Result<String> {
+    if node.spec.storage.mode == StorageMode::Local {
+        return Ok(resolved);
+    }
+    if resolved.is_empty() {
+        return Ok(select_tiered_storage_class(client, node)
+            .await?
+            .unwrap_or(resolved));
+    }
+    check_named_storage_class(client, node, &resolved).await?;
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::StellarNodeSpec;
+    use std::collections::BTreeMap;
+
+    fn class(name: &str, params: &[(&str, &str)], default: bool) -> StorageClass {
+        let mut class = StorageClass {
+            provisioner: "csi.example.com".to_string(),
+            parameters: (!params.is_empty()).then(|| {
+                params
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect()
+            }),
+            ..Default::default()
+        };
+        class.metadata.name = Some(name.to_string());
+        if default {
+            class.metadata.annotations = Some(BTreeMap::from([(
+                DEFAULT_CLASS_ANNOTATION.to_string(),
+                "true".to_string(),
+            )]));
+        }
+        class
+    }
+
+    fn node(node_type: NodeType, tier: Option<StorageTier>) -> StellarNode {
+        let mut spec = StellarNodeSpec {
+            node_type,
+            ..Default::default()
+        };
+        spec.storage.tier = tier;
+        let mut node = StellarNode::new("node-1", spec);
+        node.metadata.namespace = Some("stellar".to_string());
+        node
+    }
+
+    #[test]
+    fn test_disk_type_tier_mapping() {
+        for ssd in [
+            "gp3",
+            "io2",
+            "pd-ssd",
+            "pd-balanced",
+            "Premium_LRS",
+            "hyperdisk-balanced",
+        ] {
+            assert_eq!(disk_type_tier(ssd), Some(StorageTier::Ssd), "{ssd}");
+        }
+        for hdd in ["st1", "sc1", "pd-standard", "Standard_LRS"] {
+            assert_eq!(disk_type_tier(hdd), Some(StorageTier::Hdd), "{hdd}");
+        }
+        assert_eq!(disk_type_tier("ext4"), None);
+    }
+
+    #[test]
+    fn test_storage_class_tier_prefers_parameters_over_name() {
+        assert_eq!(
+            storage_class_tier(&class("fast", &[("type", "pd-standard")], false)),
+            Some(StorageTier::Hdd)
+        );
+        assert_eq!(
+            storage_class_tier(&class("managed", &[("skuName", "Premium_LRS")], false)),
+            Some(StorageTier::Ssd)
+        );
+        assert_eq!(
+            storage_class_tier(&class("local-nvme", &[], false)),
+            Some(StorageTier::Ssd)
+        );
+        assert_eq!(
+            storage_class_tier(&class("cold-archive", &[], false)),
+            Some(StorageTier::Hdd)
+        );
+        assert_eq!(storage_class_tier(&class("standard", &[], false)), None);
+    }
+
+    #[test]
+    fn test_select_storage_class_for_tier() {
+        let classes = vec![
+            class("standard", &[("type", "pd-standard")], true),
+            class("zz-ssd", &[("type", "pd-ssd")], false),
+            class("premium-rwo", &[("type", "pd-balanced")], false),
+        ];
+        assert_eq!(
+            select_storage_class(&StorageTier::Ssd, &classes).as_deref(),
+            Some("premium-rwo")
+        );
+        assert_eq!(
+            select_storage_class(&StorageTier::Hdd, &classes).as_deref(),
+            Some("standard")
+        );
+
+        // The cluster default wins when it matches the tier
+        let classes = vec![
+            class("a-ssd", &[("type", "gp3")], false),
+            class("gp3-default", &[("type", "gp3")], true),
+        ];
+        assert_eq!(
+            select_storage_class(&StorageTier::Ssd, &classes).as_deref(),
+            Some("gp3-default")
+        );
+        assert_eq!(select_storage_class(&StorageTier::Hdd, &classes), None);
+    }
+
+    #[test]
+    fn test_check_storage_class_tier() {
+        let hdd = class("standard", &[("type", "pd-standard")], false);
+        let ssd = class("premium-rwo", &[("type", "pd-ssd")], false);
+
+        let err =
+            check_storage_class_tier(&node(NodeType::Validator, Some(StorageTier::Ssd)), &hdd)
+                .unwrap_err();
+        assert!(err.to_string().contains("standard"), "{err}");
+        assert!(
+            check_storage_class_tier(&node(NodeType::Validator, Some(StorageTier::Ssd)), &ssd)
+                .is_ok()
+        );
+        // Implicit SSD expectation of validators only warns
+        assert!(check_storage_class_tier(&node(NodeType::Validator, None), &hdd).is_ok());
+        // Nodes without a tier are not checked
+        assert_eq!(expected_tier(&node(NodeType::Horizon, None)), None);
+        assert!(
+            check_storage_class_tier(&node(NodeType::Horizon, Some(StorageTier::Hdd)), &hdd)
+                .is_ok()
+        );
+    }
+}
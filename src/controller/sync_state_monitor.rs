@@ -6,6 +6,12 @@
 //! - `"Catching up"` — node is replaying historical ledgers (compute-intensive)
 //! - `"Booting"` / `"Joining SCP"` / other — transitional states treated as Unknown
 //!
+//! The same response also carries the node's consensus state (the ledger it
+//! tracks, how its quorum set voted and the result of the transitive quorum
+//! intersection check), surfaced as [`ConsensusHealth`] in the node status.
+//! Successive samples are compared by [`detect_desync`] to tell when a
+//! validator has fallen out of consensus.
+//!
 //! # Usage
 //!
 //! ```rust,ignore
@@ -14,14 +20,27 @@
 
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use k8s_openapi::api::core::v1::Pod;
 use kube::{api::Api, Client, ResourceExt};
 use serde::Deserialize;
 use tracing::{debug, warn};
 
-use crate::crd::{CoreSyncState, NodeType, StellarNode};
+use super::conditions;
+use crate::crd::{ConsensusHealth, CoreSyncState, NodeType, StellarNode};
 use crate::error::{Error, Result};
 
+/// How long a synced validator may track the same ledger before it counts as
+/// stalled; the network closes a ledger about every 5 seconds
+pub const LEDGER_STALL_SECS: i64 = 60;
+
+/// Degraded reason of a validator that fell out of `Synced!`
+pub const DESYNC_LOST_SYNC: &str = "ConsensusLostSync";
+/// Degraded reason of a synced validator whose tracked ledger stopped moving
+pub const DESYNC_LEDGER_STALLED: &str = "ConsensusLedgerStalled";
+/// Degraded reason of a validator whose quorum no longer intersects
+pub const DESYNC_QUORUM_INTERSECTION: &str = "QuorumIntersectionLost";
+
 /// Stellar Core `/info` response (only the fields we care about).
 #[derive(Debug, Deserialize)]
 struct CoreInfoResponse {
@@ -31,11 +50,90 @@ struct CoreInfoResponse {
 #[derive(Debug, Deserialize)]
 struct CoreInfo {
     state: String,
+    #[serde(default)]
+    ledger: Option<CoreLedger>,
+    #[serde(default)]
+    quorum: Option<CoreQuorum>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoreLedger {
+    num: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoreQuorum {
+    #[serde(default)]
+    qset: Option<CoreQuorumSet>,
+    #[serde(default)]
+    transitive: Option<CoreTransitiveQuorum>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoreQuorumSet {
+    #[serde(default)]
+    ledger: Option<u64>,
+    #[serde(default)]
+    agree: Option<u32>,
+    #[serde(default)]
+    disagree: Option<u32>,
+    #[serde(default)]
+    missing: Option<u32>,
+    #[serde(default)]
+    fail_at: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoreTransitiveQuorum {
+    #[serde(default)]
+    intersection: Option<bool>,
+}
+
+impl CoreInfo {
+    fn consensus_health(&self) -> ConsensusHealth {
+        let qset = self.quorum.as_ref().and_then(|q| q.qset.as_ref());
+        ConsensusHealth {
+            in_sync: parse_sync_state(&self.state) == CoreSyncState::Synced,
+            state: self.state.clone(),
+            tracking_ledger: qset
+                .and_then(|q| q.ledger)
+                .or(self.ledger.as_ref().map(|l| l.num)),
+            quorum_intersection_ok: self
+                .quorum
+                .as_ref()
+                .and_then(|q| q.transitive.as_ref())
+                .and_then(|t| t.intersection),
+            quorum_agree: qset.and_then(|q| q.agree),
+            quorum_disagree: qset.and_then(|q| q.disagree),
+            quorum_missing: qset.and_then(|q| q.missing),
+            quorum_fail_at: qset.and_then(|q| q.fail_at),
+        }
+    }
+}
+
+/// Parse a stellar-core `/info` response body into a [`ConsensusHealth`].
+pub fn parse_consensus_health(body: &str) -> Result<ConsensusHealth> {
+    let info: CoreInfoResponse = serde_json::from_str(body)
+        .map_err(|e| Error::ConfigError(format!("Failed to parse /info response: {e}")))?;
+    Ok(info.info.consensus_health())
 }
 
 /// Query the stellar-core `/info` endpoint at `pod_ip:11626` and return the
 /// parsed [`CoreSyncState`].
 pub async fn query_core_sync_state(pod_ip: &str) -> Result<CoreSyncState> {
+    let info = query_core_info(pod_ip).await?;
+    let state = parse_sync_state(&info.state);
+    debug!("stellar-core state='{}' → {:?}", info.state, state);
+    Ok(state)
+}
+
+/// Query the stellar-core `/info` endpoint at `pod_ip:11626` and return the
+/// node's [`ConsensusHealth`].
+pub async fn query_consensus_health(pod_ip: &str) -> Result<ConsensusHealth> {
+    Ok(query_core_info(pod_ip).await?.consensus_health())
+}
+
+async fn query_core_info(pod_ip: &str) -> Result<CoreInfo> {
     let url = format!("http://{pod_ip}:11626/info");
     debug!("Querying stellar-core info endpoint: {}", url);
 
@@ -61,10 +159,7 @@ pub async fn query_core_sync_state(pod_ip: &str) -> Result<CoreSyncState> {
         .json()
         .await
         .map_err(|e| Error::ConfigError(format!("Failed to parse /info response: {e}")))?;
-
-    let state = parse_sync_state(&info.info.state);
-    debug!("stellar-core state='{}' → {:?}", info.info.state, state);
-    Ok(state)
+    Ok(info.info)
 }
 
 /// Map the raw state string from stellar-core to a [`CoreSyncState`].
@@ -95,29 +190,7 @@ pub async fn resolve_node_sync_state(client: &Client, node: &StellarNode) -> Cor
     let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
     let name = node.name_any();
 
-    let pod_api: Api<Pod> = Api::namespaced(client.clone(), &namespace);
-    let label_selector =
-        format!("app.kubernetes.io/instance={name},app.kubernetes.io/name=stellar-node");
-
-    let pods = match pod_api
-        .list(&kube::api::ListParams::default().labels(&label_selector))
-        .await
-    {
-        Ok(p) => p,
-        Err(e) => {
-            warn!("Failed to list pods for {}/{}: {}", namespace, name, e);
-            return CoreSyncState::Unknown;
-        }
-    };
-
-    let pod_ip = pods
-        .items
-        .iter()
-        .find(|p| is_pod_ready(p))
-        .and_then(|p| p.status.as_ref())
-        .and_then(|s| s.pod_ip.clone());
-
-    match pod_ip {
+    match ready_pod_ip(client, node).await {
         Some(ip) => match query_core_sync_state(&ip).await {
             Ok(state) => state,
             Err(e) => {
@@ -138,6 +211,169 @@ pub async fn resolve_node_sync_state(client: &Client, node: &StellarNode) -> Cor
     }
 }
 
+/// Resolve the consensus state of a Validator from its first ready pod.
+///
+/// Returns `None` for other node types, when no pod is ready or when the
+/// endpoint cannot be queried.
+pub async fn resolve_node_consensus_health(
+    client: &Client,
+    node: &StellarNode,
+) -> Option<ConsensusHealth> {
+    if node.spec.node_type != NodeType::Validator {
+        return None;
+    }
+
+    let pod_ip = ready_pod_ip(client, node).await?;
+    match query_consensus_health(&pod_ip).await {
+        Ok(health) => Some(health),
+        Err(e) => {
+            warn!(
+                "Could not query consensus state for {}/{}: {}",
+                node.namespace().unwrap_or_else(|| "default".to_string()),
+                node.name_any(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// `current` with `tracking_ledger_since` carried over from `previous` while
+/// the tracked ledger has not moved, or set to `now` when it has
+pub fn track_ledger_progress(
+    previous: Option<&ConsensusHealth>,
+    mut current: ConsensusHealth,
+    now: DateTime<Utc>,
+) -> ConsensusHealth {
+    current.tracking_ledger_since = previous
+        .filter(|p| p.tracking_ledger.is_some() && p.tracking_ledger == current.tracking_ledger)
+        .and_then(|p| p.tracking_ledger_since.clone())
+        .or_else(|| current.tracking_ledger.map(|_| now.to_rfc3339()));
+    current
+}
+
+/// Merge patch replacing `status.consensus` with `consensus`.
+///
+/// Every field is written, with `null` for unset ones, so a value stellar-core
+/// no longer reports (e.g. the quorum section after leaving SCP) is removed
+/// instead of lingering from an earlier sample.
+pub fn consensus_status_patch(consensus: &ConsensusHealth) -> serde_json::Value {
+    serde_json::json!({
+        "status": {
+            "consensus": {
+                "inSync": consensus.in_sync,
+                "state": consensus.state,
+                "trackingLedger": consensus.tracking_ledger,
+                "trackingLedgerSince": consensus.tracking_ledger_since,
+                "quorumIntersectionOk": consensus.quorum_intersection_ok,
+                "quorumAgree": consensus.quorum_agree,
+                "quorumDisagree": consensus.quorum_disagree,
+                "quorumMissing": consensus.quorum_missing,
+                "quorumFailAt": consensus.quorum_fail_at,
+            }
+        }
+    })
+}
+
+/// A validator that has lost sync or quorum
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Desync {
+    /// Degraded condition reason
+    pub reason: &'static str,
+    /// Human-readable message
+    pub message: String,
+}
+
+/// Whether the node's Degraded condition is one [`detect_desync`] set
+pub fn was_desynced(node: &StellarNode) -> bool {
+    let conditions = node
+        .status
+        .as_ref()
+        .map(|s| s.conditions.as_slice())
+        .unwrap_or_default();
+    conditions::find_condition(conditions, conditions::CONDITION_TYPE_DEGRADED).is_some_and(|c| {
+        c.status == conditions::CONDITION_STATUS_TRUE
+            && [
+                DESYNC_LOST_SYNC,
+                DESYNC_LEDGER_STALLED,
+                DESYNC_QUORUM_INTERSECTION,
+            ]
+            .contains(&c.reason.as_str())
+    })
+}
+
+/// Compare a consensus sample with the previous one.
+///
+/// A validator is de-synchronized when stellar-core reports its quorum no
+/// longer intersects, when it leaves `Synced!` after having been synced (and
+/// stays flagged until it is synced again), or when it reports `Synced!` but
+/// has tracked the same ledger for [`LEDGER_STALL_SECS`]. A node that has
+/// never been synced is still catching up and is not flagged.
+pub fn detect_desync(
+    previous: Option<&ConsensusHealth>,
+    current: &ConsensusHealth,
+    was_desynced: bool,
+    now: DateTime<Utc>,
+) -> Option<Desync> {
+    if current.quorum_intersection_ok == Some(false) {
+        return Some(Desync {
+            reason: DESYNC_QUORUM_INTERSECTION,
+            message: "stellar-core reports the transitive quorum no longer intersects".to_string(),
+        });
+    }
+
+    if !current.in_sync {
+        let was_synced = previous.is_some_and(|p| p.in_sync);
+        return (was_synced || was_desynced).then(|| Desync {
+            reason: DESYNC_LOST_SYNC,
+            message: format!(
+                "Validator lost sync with the network (stellar-core state '{}')",
+                current.state
+            ),
+        });
+    }
+
+    let ledger = current.tracking_ledger?;
+    let since = current
+        .tracking_ledger_since
+        .as_deref()
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())?;
+    let stalled_for = (now - since.with_timezone(&Utc)).num_seconds();
+    (stalled_for >= LEDGER_STALL_SECS).then(|| Desync {
+        reason: DESYNC_LEDGER_STALLED,
+        message: format!(
+            "Validator has tracked ledger {ledger} for {stalled_for}s without progress"
+        ),
+    })
+}
+
+/// IP of the node's first ready pod
+async fn ready_pod_ip(client: &Client, node: &StellarNode) -> Option<String> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+
+    let pod_api: Api<Pod> = Api::namespaced(client.clone(), &namespace);
+    let label_selector =
+        format!("app.kubernetes.io/instance={name},app.kubernetes.io/name=stellar-node");
+
+    let pods = match pod_api
+        .list(&kube::api::ListParams::default().labels(&label_selector))
+        .await
+    {
+        Ok(p) => p,
+        Err(e) => {
+            warn!("Failed to list pods for {}/{}: {}", namespace, name, e);
+            return None;
+        }
+    };
+
+    pods.items
+        .iter()
+        .find(|p| is_pod_ready(p))
+        .and_then(|p| p.status.as_ref())
+        .and_then(|s| s.pod_ip.clone())
+}
+
 fn is_pod_ready(pod: &Pod) -> bool {
     pod.status
         .as_ref()
@@ -160,4 +396,177 @@ mod tests {
         assert_eq!(parse_sync_state("Joining SCP"), CoreSyncState::Unknown);
         assert_eq!(parse_sync_state(""), CoreSyncState::Unknown);
     }
+
+    const SAMPLE_INFO: &str = r#"{
+  "info": {
+    "build": "stellar-core 21.0.0 (c6f474133a2594c5a68c2b27a6cd4d1a9a2ab1b1)",
+    "ledger": {
+      "age": 3,
+      "baseFee": 100,
+      "baseReserve": 5000000,
+      "closeTime": 1718000000,
+      "hash": "4b8f1c0a6f0e1d2c3b4a59687766554433221100ffeeddccbbaa998877665544",
+      "maxTxSetSize": 1000,
+      "num": 52000010,
+      "version": 21
+    },
+    "network": "Public Global Stellar Network ; September 2015",
+    "peers": { "authenticated_count": 12, "pending_count": 0 },
+    "protocol_version": 21,
+    "quorum": {
+      "node": "GABCD",
+      "qset": {
+        "agree": 6,
+        "delayed": 0,
+        "disagree": 1,
+        "fail_at": 2,
+        "hash": "a1b2c3",
+        "lag_ms": 180,
+        "ledger": 52000011,
+        "missing": 0,
+        "phase": "EXTERNALIZE",
+        "validated": true
+      },
+      "transitive": {
+        "critical": null,
+        "intersection": true,
+        "last_check_ledger": 52000000,
+        "node_count": 31
+      }
+    },
+    "startedOn": "2024-06-10T08:00:00Z",
+    "state": "Synced!"
+  }
+}"#;
+
+    #[test]
+    fn test_parse_consensus_health() {
+        let health = parse_consensus_health(SAMPLE_INFO).unwrap();
+        assert_eq!(
+            health,
+            ConsensusHealth {
+                in_sync: true,
+                state: "Synced!".to_string(),
+                tracking_ledger: Some(52000011),
+                tracking_ledger_since: None,
+                quorum_intersection_ok: Some(true),
+                quorum_agree: Some(6),
+                quorum_disagree: Some(1),
+                quorum_missing: Some(0),
+                quorum_fail_at: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_consensus_health_while_catching_up() {
+        // Before joining SCP there is no quorum section; the tracked ledger
+        // falls back to the last closed one
+        let body = r#"{"info": {"state": "Catching up", "ledger": {"num": 1200}}}"#;
+        let health = parse_consensus_health(body).unwrap();
+        assert!(!health.in_sync);
+        assert_eq!(health.state, "Catching up");
+        assert_eq!(health.tracking_ledger, Some(1200));
+        assert_eq!(health.quorum_intersection_ok, None);
+        assert_eq!(health.quorum_fail_at, None);
+
+        let body =
+            r#"{"info": {"state": "Synced!", "quorum": {"transitive": {"intersection": false}}}}"#;
+        let health = parse_consensus_health(body).unwrap();
+        assert_eq!(health.quorum_intersection_ok, Some(false));
+
+        assert!(parse_consensus_health("not json").is_err());
+    }
+
+    #[test]
+    fn test_consensus_status_patch_nulls_unset_fields() {
+        let patch = consensus_status_patch(&sample("Catching up", 1200));
+        let consensus = &patch["status"]["consensus"];
+        assert_eq!(consensus["inSync"], false);
+        assert_eq!(consensus["state"], "Catching up");
+        assert_eq!(consensus["trackingLedger"], 1200);
+        for key in [
+            "trackingLedgerSince",
+            "quorumIntersectionOk",
+            "quorumAgree",
+            "quorumDisagree",
+            "quorumMissing",
+            "quorumFailAt",
+        ] {
+            assert!(consensus[key].is_null(), "{key} should be null");
+            assert!(consensus.as_object().unwrap().contains_key(key));
+        }
+    }
+
+    fn sample(state: &str, ledger: u64) -> ConsensusHealth {
+        ConsensusHealth {
+            in_sync: state == "Synced!",
+            state: state.to_string(),
+            tracking_ledger: Some(ledger),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_track_ledger_progress() {
+        let t0 = Utc::now();
+        let t1 = t0 + chrono::Duration::seconds(30);
+
+        let first = track_ledger_progress(None, sample("Synced!", 100), t0);
+        assert_eq!(first.tracking_ledger_since, Some(t0.to_rfc3339()));
+
+        // Same ledger: keep when it was first seen
+        let same = track_ledger_progress(Some(&first), sample("Synced!", 100), t1);
+        assert_eq!(same.tracking_ledger_since, Some(t0.to_rfc3339()));
+
+        // Ledger moved: restart the clock
+        let moved = track_ledger_progress(Some(&same), sample("Synced!", 106), t1);
+        assert_eq!(moved.tracking_ledger_since, Some(t1.to_rfc3339()));
+    }
+
+    #[test]
+    fn test_desync_detected_from_successive_samples() {
+        let t0 = Utc::now();
+        let synced = track_ledger_progress(None, sample("Synced!", 100), t0);
+
+        // Healthy progress
+        let t1 = t0 + chrono::Duration::seconds(30);
+        let next = track_ledger_progress(Some(&synced), sample("Synced!", 106), t1);
+        assert_eq!(detect_desync(Some(&synced), &next, false, t1), None);
+
+        // Falling out of Synced! after having been synced
+        let lost = sample("Catching up", 106);
+        let desync = detect_desync(Some(&next), &lost, false, t1).unwrap();
+        assert_eq!(desync.reason, DESYNC_LOST_SYNC);
+        assert!(desync.message.contains("Catching up"), "{}", desync.message);
+
+        // Still flagged on the next sample, cleared once synced again
+        assert!(detect_desync(Some(&lost), &lost, true, t1).is_some());
+        let recovered = track_ledger_progress(Some(&lost), sample("Synced!", 120), t1);
+        assert_eq!(detect_desync(Some(&lost), &recovered, true, t1), None);
+
+        // Synced! but stuck on the same ledger
+        let t2 = t0 + chrono::Duration::seconds(LEDGER_STALL_SECS);
+        let stuck = track_ledger_progress(Some(&synced), sample("Synced!", 100), t2);
+        let desync = detect_desync(Some(&synced), &stuck, false, t2).unwrap();
+        assert_eq!(desync.reason, DESYNC_LEDGER_STALLED);
+        let t_short = t0 + chrono::Duration::seconds(LEDGER_STALL_SECS - 1);
+        assert_eq!(detect_desync(Some(&synced), &stuck, false, t_short), None);
+    }
+
+    #[test]
+    fn test_desync_quorum_intersection_and_catchup() {
+        let now = Utc::now();
+        let mut broken = sample("Synced!", 100);
+        broken.quorum_intersection_ok = Some(false);
+        assert_eq!(
+            detect_desync(None, &broken, false, now).unwrap().reason,
+            DESYNC_QUORUM_INTERSECTION
+        );
+
+        // A node that was never synced is catching up, not de-synchronized
+        let booting = sample("Catching up", 10);
+        assert_eq!(detect_desync(None, &booting, false, now), None);
+        assert_eq!(detect_desync(Some(&booting), &booting, false, now), None);
+    }
 }
@@ -0,0 +1,434 @@
+//! Fake Kubernetes API server for testing `ensure_*` functions
+//!
+//! [`fake_client`] returns a [`kube::Client`] backed by an in-memory tower
+//! service together with a [`FakeApiServer`] that receives every request the
+//! client sends. Tests drive the server from a spawned task, answering each
+//! expected request in turn and asserting on the recorded method, path, query
+//! and body:
+//!
+//! ```ignore
+//! let (client, mut server) = fake_client();
+//! let server = tokio::spawn(async move {
+//!     server.respond_not_found().await;
+//!     server.respond_echo().await
+//! });
+//! ensure_service(&client, &node, false, &BTreeMap::new(), false).await?;
+//! let patch = server.await.unwrap();
+//! assert_eq!(patch.method, http::Method::PATCH);
+//! ```
+//!
+//! [`FakeApiServer::serve`] instead answers every request from an in-memory
+//! store, for tests that exercise a whole sequence of `ensure_*` calls.
+//!
+//! With the `in-memory-kube` feature the module is also public, so
+//! integration tests can run whole reconciles against [`in_memory_client`]
+//! and [`controller_state`] through `reconcile_for_fuzz`:
+//!
+//! ```ignore
+//! let (client, requests) = in_memory_client();
+//! let ctx = controller_state(client.clone());
+//! reconcile_for_fuzz(Arc::new(node), ctx).await?;
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use http::{Method, Request, Response, StatusCode};
+use kube::client::Body;
+use kube::Client;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+
+use super::ControllerState;
+
+/// A request together with the channel its response goes back on
+type Exchange = (Request<Body>, oneshot::Sender<Response<Body>>);
+
+/// Request received by the fake API server
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub path: String,
+    pub query: String,
+    /// `Content-Type` header, empty when the request has none
+    pub content_type: String,
+    pub body: Value,
+}
+
+impl RecordedRequest {
+    /// Whether the query string carries `key=value`
+    pub fn has_query(&self, key: &str, value: &str) -> bool {
+        self.query
+            .split('&')
+            .any(|pair| pair == format!("{key}={value}"))
+    }
+
+    /// Decoded value of the `key` query parameter
+    fn query_param(&self, key: &str) -> Option<String> {
+        self.query.split('&').find_map(|pair| {
+            let value = pair.strip_prefix(key)?.strip_prefix('=')?;
+            urlencoding::decode(value).ok().map(|v| v.into_owned())
+        })
+    }
+}
+
+/// Server side of a [`fake_client`]
+pub struct FakeApiServer {
+    requests: mpsc::UnboundedReceiver<Exchange>,
+}
+
+/// Client whose requests are answered by the returned [`FakeApiServer`]
+pub fn fake_client() -> (Client, FakeApiServer) {
+    let (tx, requests) = mpsc::unbounded_channel::<Exchange>();
+    let service = tower::service_fn(move |request: Request<Body>| {
+        let tx = tx.clone();
+        async move {
+            let (send, response) = oneshot::channel();
+            tx.send((request, send))
+                .map_err(|_| "fake API server was dropped")?;
+            response
+                .await
+                .map_err(|_| "fake API server did not respond")
+        }
+    });
+    (Client::new(service, "default"), FakeApiServer { requests })
+}
+
+/// Failed `Status` the API server returns with an error code
+fn status_body(status: StatusCode, reason: &str) -> Value {
+    json!({
+        "kind": "Status",
+        "apiVersion": "v1",
+        "metadata": {},
+        "status": "Failure",
+        "message": reason,
+        "reason": reason,
+        "code": status.as_u16(),
+    })
+}
+
+/// Client that stands in for a whole cluster: every request is answered by
+/// [`FakeApiServer::serve`], and the returned log records them in order.
+///
+/// Must be called from within a Tokio runtime.
+pub fn in_memory_client() -> (Client, Arc<Mutex<Vec<RecordedRequest>>>) {
+    let (client, server) = fake_client();
+    (client, server.serve())
+}
+
+/// Controller state for reconciling with `client`, with every optional
+/// integration off
+pub fn controller_state(client: Client) -> Arc<ControllerState> {
+    let (_layer, log_reload_handle) =
+        tracing_subscriber::reload::Layer::new(tracing_subscriber::EnvFilter::new("info"));
+    let audit_log = Arc::new(super::audit_log::AuditLog::new());
+    Arc::new(ControllerState {
+        client,
+        enable_mtls: false,
+        operator_namespace: "stellar-operator".to_string(),
+        watch_namespace: None,
+        mtls_config: None,
+        dry_run: false,
+        disable_finalizers: false,
+        retry_budget_retriable_secs: 15,
+        retry_budget_nonretriable_secs: 60,
+        retry_budget_max_attempts: 1,
+        is_leader: Arc::new(std::sync::atomic::AtomicBool::new(true)),
+        event_reporter: kube::runtime::events::Reporter {
+            controller: "stellar-operator".to_string(),
+            instance: None,
+        },
+        operator_config: Arc::new(Default::default()),
+        reconcile_id_counter: std::sync::atomic::AtomicU64::new(0),
+        last_reconcile_success: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+        log_reload_handle,
+        log_level_expires_at: Arc::new(tokio::sync::Mutex::new(None)),
+        last_event_received: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+        job_registry: Arc::new(super::background_jobs::JobRegistry::new()),
+        audit_recorder: Arc::new(super::AuditRecorder::new(audit_log.clone(), vec![], None)),
+        audit_log,
+        anomaly_detector: Arc::new(super::AnomalyDetector::new(Default::default())),
+        plugin_registry: Arc::new(crate::plugin_sdk::PluginRegistry::new()),
+        analytics_engine: Arc::new(crate::logging::analytics::AnalyticsEngine::new(
+            std::time::Duration::from_secs(3600),
+        )),
+        #[cfg(feature = "rest-api")]
+        oidc_config: None,
+        #[cfg(feature = "rest-api")]
+        metrics_store: Arc::new(crate::rest_api::metrics_store::StellarMetricsStore::new()),
+    })
+}
+
+/// Path segments after `/api/v1` or `/apis/<group>/<version>`
+fn resource_segments(path: &str) -> Vec<&str> {
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let skip = match segments.first() {
+        Some(&"api") => 2,
+        Some(&"apis") => 3,
+        _ => return Vec::new(),
+    };
+    segments.into_iter().skip(skip).collect()
+}
+
+/// Whether `path` addresses a collection rather than a single object
+fn is_collection(path: &str) -> bool {
+    matches!(
+        resource_segments(path).as_slice(),
+        ["namespaces", _, _] | [_]
+    )
+}
+
+/// Whether the object stored under `key` belongs to the collection at
+/// `path`; a cluster-wide path also lists namespaced objects
+fn in_collection(key: &str, path: &str) -> bool {
+    if let Some(name) = key
+        .strip_prefix(path)
+        .and_then(|rest| rest.strip_prefix('/'))
+    {
+        return !name.contains('/');
+    }
+    let Some((base, plural)) = path.rsplit_once('/') else {
+        return false;
+    };
+    let rest = key
+        .strip_prefix(base)
+        .and_then(|rest| rest.strip_prefix('/'));
+    matches!(
+        rest.map(|rest| rest.split('/').collect::<Vec<_>>()).as_deref(),
+        Some(["namespaces", _, resource, _]) if *resource == plural
+    )
+}
+
+/// Whether `object`'s labels satisfy an equality-based `selector`
+fn matches_labels(object: &Value, selector: &str) -> bool {
+    let labels = &object["metadata"]["labels"];
+    selector
+        .split(',')
+        .filter(|term| !term.is_empty())
+        .all(|term| {
+            if let Some((key, value)) = term.split_once("!=") {
+                labels[key].as_str() != Some(value)
+            } else if let Some((key, value)) = term.split_once('=') {
+                labels[key].as_str() == Some(value.trim_start_matches('='))
+            } else {
+                labels.get(term).is_some()
+            }
+        })
+}
+
+/// Whether a deleted object is only waiting for its finalizers to go
+fn awaiting_finalizers(object: &Value) -> bool {
+    object["metadata"]["finalizers"]
+        .as_array()
+        .is_some_and(|finalizers| !finalizers.is_empty())
+}
+
+/// Answer `request` from `objects`, keyed by object path
+fn answer_from_store(
+    objects: &mut BTreeMap<String, Value>,
+    request: &RecordedRequest,
+) -> (StatusCode, Value) {
+    // Status and scale writes land on the object itself
+    let path = ["/status", "/scale"]
+        .iter()
+        .find_map(|sub| request.path.strip_suffix(sub))
+        .unwrap_or(&request.path);
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            status_body(StatusCode::NOT_FOUND, "NotFound"),
+        )
+    };
+
+    if request.method == Method::GET && is_collection(path) {
+        let selector = request.query_param("labelSelector").unwrap_or_default();
+        let items: Vec<Value> = objects
+            .iter()
+            .filter(|(key, object)| in_collection(key, path) && matches_labels(object, &selector))
+            .map(|(_, object)| object.clone())
+            .collect();
+        let list = json!({
+            "apiVersion": "v1",
+            "kind": "List",
+            "metadata": { "resourceVersion": "" },
+            "items": items,
+        });
+        return (StatusCode::OK, list);
+    }
+
+    match request.method {
+        Method::GET => objects
+            .get(path)
+            .map(|object| (StatusCode::OK, object.clone()))
+            .unwrap_or_else(not_found),
+        Method::POST => {
+            let mut object = request.body.clone();
+            if object["metadata"]["name"].as_str().is_none() {
+                if let Some(prefix) = object["metadata"]["generateName"].as_str() {
+                    object["metadata"]["name"] = json!(format!("{prefix}{}", objects.len()));
+                }
+            }
+            let key = format!(
+                "{path}/{}",
+                object["metadata"]["name"].as_str().unwrap_or_default()
+            );
+            if objects.contains_key(&key) {
+                return (
+                    StatusCode::CONFLICT,
+                    status_body(StatusCode::CONFLICT, "AlreadyExists"),
+                );
+            }
+            objects.insert(key, object.clone());
+            (StatusCode::OK, object)
+        }
+        Method::DELETE => match objects.get_mut(path) {
+            // Held by finalizers: marked for deletion until they are removed
+            Some(object) if awaiting_finalizers(object) => {
+                if object["metadata"]["deletionTimestamp"].is_null() {
+                    object["metadata"]["deletionTimestamp"] = json!(
+                        chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+                    );
+                }
+                (StatusCode::OK, object.clone())
+            }
+            Some(_) => (StatusCode::OK, objects.remove(path).unwrap()),
+            None => not_found(),
+        },
+        Method::PATCH => {
+            let Some(object) = objects.get_mut(path) else {
+                // Lenient: patching a missing object creates it
+                objects.insert(path.to_string(), request.body.clone());
+                return (StatusCode::OK, request.body.clone());
+            };
+            if request.content_type == "application/json-patch+json" {
+                let applied = serde_json::from_value::<json_patch::Patch>(request.body.clone())
+                    .map_err(|e| e.to_string())
+                    .and_then(|patch| json_patch::patch(object, &patch).map_err(|e| e.to_string()));
+                if let Err(reason) = applied {
+                    let status = StatusCode::UNPROCESSABLE_ENTITY;
+                    return (status, status_body(status, &reason));
+                }
+            } else {
+                // Server-side applies are approximated by a merge as well
+                json_patch::merge(object, &request.body);
+            }
+            let object = object.clone();
+            if !object["metadata"]["deletionTimestamp"].is_null() && !awaiting_finalizers(&object) {
+                objects.remove(path);
+            }
+            (StatusCode::OK, object)
+        }
+        _ => {
+            objects.insert(path.to_string(), request.body.clone());
+            (StatusCode::OK, request.body.clone())
+        }
+    }
+}
+
+impl FakeApiServer {
+    /// Receive the next request and answer it with the status and body
+    /// `answer` picks for it; `None` once the client is gone
+    async fn reply(
+        &mut self,
+        answer: impl FnOnce(&RecordedRequest) -> (StatusCode, Value),
+    ) -> Option<RecordedRequest> {
+        let (request, send) = self.requests.recv().await?;
+
+        let method = request.method().clone();
+        let path = request.uri().path().to_string();
+        let query = request.uri().query().unwrap_or_default().to_string();
+        let content_type = request
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let bytes = request
+            .into_body()
+            .collect_bytes()
+            .await
+            .expect("request body should be readable");
+        let recorded = RecordedRequest {
+            method,
+            path,
+            query,
+            content_type,
+            body: if bytes.is_empty() {
+                Value::Null
+            } else {
+                serde_json::from_slice(&bytes).expect("request body should be JSON")
+            },
+        };
+
+        let (status, body) = answer(&recorded);
+        let response = Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        let _ = send.send(response);
+
+        Some(recorded)
+    }
+
+    /// Answer the next request with `status` and a JSON `body`
+    pub async fn respond(&mut self, status: StatusCode, body: &Value) -> RecordedRequest {
+        self.reply(|_| (status, body.clone()))
+            .await
+            .expect("client sent no request")
+    }
+
+    /// Answer the next request with `object`
+    pub async fn respond_with(&mut self, object: &Value) -> RecordedRequest {
+        self.respond(StatusCode::OK, object).await
+    }
+
+    /// Answer the next request with a 404, as for a missing object
+    pub async fn respond_not_found(&mut self) -> RecordedRequest {
+        self.respond_status(StatusCode::NOT_FOUND, "NotFound").await
+    }
+
+    /// Answer the next request with a failed `Status`
+    pub async fn respond_status(&mut self, status: StatusCode, reason: &str) -> RecordedRequest {
+        self.respond(status, &status_body(status, reason)).await
+    }
+
+    /// Answer the next request with its own body, as the API server does for
+    /// a server-side apply
+    pub async fn respond_echo(&mut self) -> RecordedRequest {
+        self.reply(|request| (StatusCode::OK, request.body.clone()))
+            .await
+            .expect("client sent no request")
+    }
+
+    /// Answer every request from an in-memory object store in the background,
+    /// appending each one to the returned log.
+    ///
+    /// Reads return the stored object or 404, and reads of a collection list
+    /// the stored objects in it, filtered by an equality `labelSelector`.
+    /// Creates store their body (409 if the object exists) and updates
+    /// replace it; applies and merge patches are merged into the stored
+    /// object and JSON patches applied to it, status patches included, while
+    /// patching a missing object stores the patch. Deletes remove the object, or only set
+    /// its `deletionTimestamp` while it still has finalizers, and the object
+    /// goes once a patch clears them. Enough for tests that run several
+    /// `ensure_*` calls, or whole reconciles, without scripting every request.
+    pub fn serve(mut self) -> Arc<Mutex<Vec<RecordedRequest>>> {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let requests = Arc::clone(&log);
+        tokio::spawn(async move {
+            let mut objects: BTreeMap<String, Value> = BTreeMap::new();
+            while self
+                .reply(|request| {
+                    // Logged before the response goes out, so the caller sees
+                    // it as soon as its request returns
+                    requests.lock().unwrap().push(request.clone());
+                    answer_from_store(&mut objects, request)
+                })
+                .await
+                .is_some()
+            {}
+        });
+        log
+    }
+}
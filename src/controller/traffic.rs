@@ -130,7 +130,7 @@ pub enum CircuitBreakerState {
 }
 
 #[derive(Debug, Clone)]
-struct CircuitBreaker {
+pub(crate) struct CircuitBreaker {
     config: CircuitBreakerConfig,
     state: CircuitBreakerState,
     consecutive_failures: u32,
@@ -139,7 +139,7 @@ struct CircuitBreaker {
 }
 
 impl CircuitBreaker {
-    fn new(config: CircuitBreakerConfig) -> Self {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
         Self {
             config,
             state: CircuitBreakerState::Closed,
@@ -149,7 +149,7 @@ impl CircuitBreaker {
         }
     }
 
-    fn allow(&mut self, now_ms: u64) -> bool {
+    pub(crate) fn allow(&mut self, now_ms: u64) -> bool {
         if self.state == CircuitBreakerState::Open {
             if let Some(opened_at) = self.opened_at_ms {
                 if now_ms.saturating_sub(opened_at) >= self.config.open_window_ms {
@@ -163,7 +163,7 @@ impl CircuitBreaker {
         true
     }
 
-    fn on_result(&mut self, success: bool, now_ms: u64) {
+    pub(crate) fn on_result(&mut self, success: bool, now_ms: u64) {
         match self.state {
             CircuitBreakerState::Closed => {
                 if success {
@@ -94,6 +94,7 @@ mod tests {
                     resources: ResourceRequirements::default(),
                     strategy: strategy.clone(),
                     archive_sharding: false,
+                    ..Default::default()
                 }),
                 db_maintenance_config: None,
                 oci_snapshot: None,
@@ -519,6 +520,7 @@ mod tests {
             resources: ResourceRequirements::default(),
             strategy: ReadReplicaStrategy::default(),
             archive_sharding: false,
+            ..Default::default()
         };
 
         assert_eq!(config.replicas, 1);
@@ -662,6 +664,7 @@ mod tests {
             resources: ResourceRequirements::default(),
             strategy: ReadReplicaStrategy::RoundRobin,
             archive_sharding: false,
+            ..Default::default()
         });
 
         let _node_http = StellarNode {
@@ -690,6 +693,7 @@ mod tests {
             resources: ResourceRequirements::default(),
             strategy: ReadReplicaStrategy::RoundRobin,
             archive_sharding: true,
+            ..Default::default()
         };
 
         assert!(config.archive_sharding);
@@ -703,6 +707,7 @@ mod tests {
             resources: ResourceRequirements::default(),
             strategy: ReadReplicaStrategy::FreshnessPreferred,
             archive_sharding: false,
+            ..Default::default()
         };
 
         assert!(!config.archive_sharding);
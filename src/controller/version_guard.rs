@@ -0,0 +1,258 @@
+//! Guard against downgrading a node's software version
+//!
+//! stellar-core and Horizon migrate their databases forward on upgrade; an
+//! older release started on the upgraded state can refuse to start or, worse,
+//! corrupt the ledger state of a validator. Before touching the workload the
+//! reconciler compares `spec.version` with the version currently deployed and
+//! refuses a lower one, unless the node carries
+//! `stellar.org/allow-downgrade: "true"` or the lower version is the node's
+//! `status.lastGoodVersion`, which is where a `stellar.org/rollback` request
+//! takes it.
+//!
+//! Only `[v]MAJOR.MINOR.PATCH` versions are compared; anything after the patch
+//! number (`-rc1`, `-2121.c6f474133.focal`, a digest) is ignored, and
+//! versions that do not parse, such as `latest` or a bare digest, are never
+//! treated as a downgrade.
+
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::core::v1::PodTemplateSpec;
+use kube::api::Api;
+use kube::{Client, ResourceExt};
+use tracing::warn;
+
+use crate::crd::{NodeType, StellarNode};
+use crate::error::{Error, Result};
+
+/// Annotation that lets a node move to a lower version
+pub const ALLOW_DOWNGRADE_ANNOTATION: &str = "stellar.org/allow-downgrade";
+
+/// `(major, minor, patch)` of a version tag, if it has one
+pub fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let version = version.split('@').next()?;
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let mut parts = version.splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?;
+    let end = patch
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(patch.len());
+    let patch = patch[..end].parse().ok()?;
+    Some((major, minor, patch))
+}
+
+/// Whether moving from `current` to `target` lowers the version
+pub fn is_downgrade(current: &str, target: &str) -> bool {
+    match (parse_version(current), parse_version(target)) {
+        (Some(current), Some(target)) => target < current,
+        _ => false,
+    }
+}
+
+/// Whether the node opted into downgrades
+pub fn downgrade_allowed(node: &StellarNode) -> bool {
+    node.annotations()
+        .get(ALLOW_DOWNGRADE_ANNOTATION)
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// Whether `spec.version` is the version the node last ran successfully
+fn is_last_good_version(node: &StellarNode) -> bool {
+    node.status
+        .as_ref()
+        .and_then(|s| s.last_good_version.as_deref())
+        .is_some_and(|version| version == node.spec.version)
+}
+
+/// Reject a move from the `deployed` version to a lower `spec.version`
+/// unless the node allows it or returns to its last good version.
+pub fn check_downgrade(node: &StellarNode, deployed: Option<&str>) -> Result<()> {
+    let Some(deployed) = deployed else {
+        return Ok(());
+    };
+    let target = &node.spec.version;
+    if !is_downgrade(deployed, target) {
+        return Ok(());
+    }
+
+    if downgrade_allowed(node) {
+        warn!(
+            "Downgrading {}/{} from {} to {} ({} is set)",
+            node.namespace().unwrap_or_default(),
+            node.name_any(),
+            deployed,
+            target,
+            ALLOW_DOWNGRADE_ANNOTATION
+        );
+        return Ok(());
+    }
+
+    if is_last_good_version(node) {
+        warn!(
+            "Downgrading {}/{} from {} to its last good version {}",
+            node.namespace().unwrap_or_default(),
+            node.name_any(),
+            deployed,
+            target
+        );
+        return Ok(());
+    }
+
+    Err(Error::ValidationError(format!(
+        "spec.version {target} is lower than the deployed version {deployed}; \
+         downgrades can corrupt ledger state. Set the {ALLOW_DOWNGRADE_ANNOTATION}: \"true\" \
+         annotation to proceed anyway"
+    )))
+}
+
+/// Version tag of a container image, ignoring any digest
+pub fn image_version(image: &str) -> Option<&str> {
+    let image = image.split('@').next()?;
+    let (repository, tag) = image.rsplit_once(':')?;
+    // A ':' before the last '/' belongs to a registry port, not a tag
+    (!tag.contains('/') && !repository.is_empty()).then_some(tag)
+}
+
+fn template_version(template: &PodTemplateSpec) -> Option<String> {
+    template
+        .spec
+        .as_ref()?
+        .containers
+        .first()?
+        .image
+        .as_deref()
+        .and_then(image_version)
+        .map(str::to_string)
+}
+
+/// Version of the node's workload as currently deployed
+pub async fn deployed_version(client: &Client, node: &StellarNode) -> Result<Option<String>> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+
+    let template = match node.spec.node_type {
+        NodeType::Validator => {
+            let api: Api<StatefulSet> = Api::namespaced(client.clone(), &namespace);
+            api.get_opt(&name)
+                .await?
+                .and_then(|s| s.spec)
+                .map(|s| s.template)
+        }
+        NodeType::Horizon | NodeType::SorobanRpc => {
+            let api: Api<Deployment> = Api::namespaced(client.clone(), &namespace);
+            api.get_opt(&name)
+                .await?
+                .and_then(|d| d.spec)
+                .map(|s| s.template)
+        }
+    };
+    Ok(template.as_ref().and_then(template_version))
+}
+
+/// Fail when the node would be downgraded without opting in.
+pub async fn check_version_downgrade(client: &Client, node: &StellarNode) -> Result<()> {
+    let deployed = deployed_version(client, node).await?;
+    check_downgrade(node, deployed.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::StellarNodeSpec;
+    use std::collections::BTreeMap;
+
+    fn node(version: &str, allow: Option<&str>) -> StellarNode {
+        let mut node = StellarNode::new(
+            "validator-1",
+            StellarNodeSpec {
+                version: version.to_string(),
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        if let Some(allow) = allow {
+            node.metadata.annotations = Some(BTreeMap::from([(
+                ALLOW_DOWNGRADE_ANNOTATION.to_string(),
+                allow.to_string(),
+            )]));
+        }
+        node
+    }
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(parse_version("v21.0.0"), Some((21, 0, 0)));
+        assert_eq!(parse_version("2.31.4"), Some((2, 31, 4)));
+        assert_eq!(parse_version("v22.1.0-rc2"), Some((22, 1, 0)));
+        assert_eq!(
+            parse_version("21.3.1-2007.4ede19620.focal"),
+            Some((21, 3, 1))
+        );
+        assert_eq!(parse_version("v21.0.0@sha256:abcd"), Some((21, 0, 0)));
+        assert_eq!(parse_version("latest"), None);
+        assert_eq!(parse_version("sha256:abcd"), None);
+        assert_eq!(parse_version("v21"), None);
+    }
+
+    #[test]
+    fn test_semver_comparison() {
+        assert!(is_downgrade("v21.0.0", "v20.4.1"));
+        assert!(is_downgrade("v21.1.0", "v21.0.9"));
+        assert!(is_downgrade("v2.10.0", "v2.9.0"));
+        assert!(!is_downgrade("v2.9.0", "v2.10.0"));
+        assert!(!is_downgrade("v21.0.0", "v21.0.0-rc1"));
+        assert!(!is_downgrade("v21.0.0", "v22.0.0"));
+        assert!(!is_downgrade("latest", "v20.0.0"));
+        assert!(!is_downgrade("v21.0.0", "latest"));
+    }
+
+    #[test]
+    fn test_downgrade_is_blocked() {
+        let err = check_downgrade(&node("v20.0.0", None), Some("v21.0.0")).unwrap_err();
+        assert!(
+            err.to_string().contains(ALLOW_DOWNGRADE_ANNOTATION),
+            "{err}"
+        );
+
+        assert!(check_downgrade(&node("v20.0.0", Some("false")), Some("v21.0.0")).is_err());
+        assert!(check_downgrade(&node("v22.0.0", None), Some("v21.0.0")).is_ok());
+        assert!(check_downgrade(&node("v20.0.0", None), None).is_ok());
+    }
+
+    #[test]
+    fn test_downgrade_to_last_good_version_is_allowed() {
+        let mut last_good = node("v20.0.0", None);
+        last_good.status = Some(crate::crd::StellarNodeStatus {
+            last_good_version: Some("v20.0.0".to_string()),
+            ..Default::default()
+        });
+        assert!(check_downgrade(&last_good, Some("v21.0.0")).is_ok());
+
+        last_good.spec.version = "v19.0.0".to_string();
+        assert!(check_downgrade(&last_good, Some("v21.0.0")).is_err());
+    }
+
+    #[test]
+    fn test_annotation_allows_downgrade() {
+        assert!(check_downgrade(&node("v20.0.0", Some("true")), Some("v21.0.0")).is_ok());
+        assert!(check_downgrade(&node("v20.0.0", Some("True")), Some("v21.0.0")).is_ok());
+    }
+
+    #[test]
+    fn test_image_version() {
+        assert_eq!(
+            image_version("stellar/stellar-core:v21.0.0"),
+            Some("v21.0.0")
+        );
+        assert_eq!(
+            image_version("stellar/stellar-core:v21.0.0@sha256:abcd"),
+            Some("v21.0.0")
+        );
+        assert_eq!(
+            image_version("registry.local:5000/stellar/horizon:v2.31.0"),
+            Some("v2.31.0")
+        );
+        assert_eq!(image_version("stellar/stellar-core@sha256:abcd"), None);
+        assert_eq!(image_version("registry.local:5000/stellar/horizon"), None);
+    }
+}
@@ -0,0 +1,517 @@
+//! Roll a node back to its last known good version
+//!
+//! Every time a node reaches `Ready` with its workload fully rolled out, the
+//! reconciler records the version of the deployed image in
+//! `status.lastGoodVersion`. Setting `stellar.org/rollback: "true"` on the
+//! node reverts `spec.version` to that version and clears the annotation in
+//! the same patch; the revert then rolls out like any other version change.
+//! The downgrade guard lets a node move to its `lastGoodVersion`, so a
+//! rollback to a lower version needs no `stellar.org/allow-downgrade`.
+//!
+//! Each new version recorded that way is also appended to
+//! `status.versionHistory`, which keeps the last [`MAX_VERSION_HISTORY`]
+//! entries.
+
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use k8s_openapi::api::core::v1::PodTemplateSpec;
+use kube::api::{Api, Patch, PatchParams};
+use kube::{Client, ResourceExt};
+use tracing::{info, warn};
+
+use super::version_guard::parse_version;
+use crate::crd::{NodeType, StellarNode, VersionHistoryEntry};
+use crate::error::Result;
+
+/// Annotation that asks the operator to revert to the last good version
+pub const ROLLBACK_ANNOTATION: &str = "stellar.org/rollback";
+
+/// Number of entries `status.versionHistory` keeps
+pub const MAX_VERSION_HISTORY: usize = 10;
+
+/// Whether the node asks to be rolled back
+pub fn rollback_requested(node: &StellarNode) -> bool {
+    node.annotations()
+        .get(ROLLBACK_ANNOTATION)
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+/// Version the node's Deployment or StatefulSet runs, and whether its rollout
+/// has finished
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkloadRollout {
+    /// Version taken from the main container's image, in `spec.version` form
+    pub version: String,
+    /// The controller observed the current generation and every replica was
+    /// updated to it
+    pub complete: bool,
+}
+
+impl WorkloadRollout {
+    pub fn from_deployment(deployment: &Deployment) -> Option<Self> {
+        let spec = deployment.spec.as_ref()?;
+        let image = main_image(&spec.template)?;
+        let status = deployment.status.as_ref();
+        Some(Self {
+            version: image_version(image)?.to_string(),
+            complete: rollout_complete(
+                deployment.metadata.generation,
+                status.and_then(|s| s.observed_generation),
+                spec.replicas.unwrap_or(1),
+                status.and_then(|s| s.updated_replicas),
+            ),
+        })
+    }
+
+    pub fn from_statefulset(statefulset: &StatefulSet) -> Option<Self> {
+        let spec = statefulset.spec.as_ref()?;
+        let image = main_image(&spec.template)?;
+        let status = statefulset.status.as_ref();
+        Some(Self {
+            version: image_version(image)?.to_string(),
+            complete: rollout_complete(
+                statefulset.metadata.generation,
+                status.and_then(|s| s.observed_generation),
+                spec.replicas.unwrap_or(1),
+                status.and_then(|s| s.updated_replicas),
+            ),
+        })
+    }
+}
+
+/// Image of the template's main (first) container
+fn main_image(template: &PodTemplateSpec) -> Option<&str> {
+    template.spec.as_ref()?.containers.first()?.image.as_deref()
+}
+
+/// Tag or digest of a container image reference, e.g. `v21.0.0` for
+/// `stellar/stellar-core:v21.0.0`
+pub fn image_version(image: &str) -> Option<&str> {
+    let name = image.rsplit('/').next()?;
+    match (name.find(':'), name.find('@')) {
+        (Some(colon), Some(at)) if at < colon => Some(&name[at + 1..]),
+        (Some(colon), _) => Some(&name[colon + 1..]),
+        (None, _) => None,
+    }
+}
+
+/// Whether a workload at `generation` finished rolling out `replicas`
+pub fn rollout_complete(
+    generation: Option<i64>,
+    observed_generation: Option<i64>,
+    replicas: i32,
+    updated_replicas: Option<i32>,
+) -> bool {
+    generation.is_some()
+        && observed_generation >= generation
+        && updated_replicas.unwrap_or(0) == replicas
+}
+
+/// Rollout state of the node's workload, `None` when it does not exist yet
+pub async fn fetch_workload_rollout(
+    client: &Client,
+    node: &StellarNode,
+) -> Result<Option<WorkloadRollout>> {
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+    let rollout = match node.spec.node_type {
+        NodeType::Validator => Api::<StatefulSet>::namespaced(client.clone(), &namespace)
+            .get_opt(&name)
+            .await?
+            .as_ref()
+            .and_then(WorkloadRollout::from_statefulset),
+        NodeType::Horizon | NodeType::SorobanRpc => {
+            Api::<Deployment>::namespaced(client.clone(), &namespace)
+                .get_opt(&name)
+                .await?
+                .as_ref()
+                .and_then(WorkloadRollout::from_deployment)
+        }
+    };
+    Ok(rollout)
+}
+
+/// Version to record as last known good for a node reported in `phase`: the
+/// version its workload runs, once that finished rolling out
+pub fn last_good_version<'a>(rollout: Option<&'a WorkloadRollout>, phase: &str) -> Option<&'a str> {
+    rollout
+        .filter(|rollout| phase == "Ready" && rollout.complete)
+        .map(|rollout| rollout.version.as_str())
+}
+
+/// Why the node moved from `previous` to `version`
+pub fn version_change_reason(previous: Option<&str>, version: &str) -> &'static str {
+    let Some(previous) = previous else {
+        return "Initial";
+    };
+    match (parse_version(previous), parse_version(version)) {
+        (Some(previous), Some(version)) if version > previous => "Upgrade",
+        (Some(previous), Some(version)) if version < previous => "Downgrade",
+        _ => "Change",
+    }
+}
+
+/// Version history with the deployed version appended, when the node reports
+/// `phase` with its workload fully rolled out on a version other than the
+/// latest recorded one; `None` when the history is unchanged.
+pub fn next_version_history(
+    node: &StellarNode,
+    rollout: Option<&WorkloadRollout>,
+    phase: &str,
+    now: &str,
+) -> Option<Vec<VersionHistoryEntry>> {
+    let version = last_good_version(rollout, phase)?;
+    let mut history = node
+        .status
+        .as_ref()
+        .map(|status| status.version_history.clone())
+        .unwrap_or_default();
+    let previous = history.last().map(|entry| entry.version.as_str());
+    if previous == Some(version) {
+        return None;
+    }
+
+    history.push(VersionHistoryEntry {
+        version: version.to_string(),
+        timestamp: now.to_string(),
+        reason: version_change_reason(previous, version).to_string(),
+    });
+    let excess = history.len().saturating_sub(MAX_VERSION_HISTORY);
+    history.drain(..excess);
+    Some(history)
+}
+
+/// Version a rollback would move the node to, if one is recorded and differs
+/// from `spec.version`
+pub fn rollback_target(node: &StellarNode) -> Option<&str> {
+    node.status
+        .as_ref()?
+        .last_good_version
+        .as_deref()
+        .filter(|version| *version != node.spec.version)
+}
+
+/// Patch reverting the node to `target` and clearing the rollback request;
+/// with no target only the request is cleared
+pub fn rollback_patch(target: Option<&str>) -> serde_json::Value {
+    let mut patch = serde_json::json!({
+        "metadata": {
+            "annotations": { ROLLBACK_ANNOTATION: null }
+        }
+    });
+    if let Some(target) = target {
+        patch["spec"] = serde_json::json!({ "version": target });
+    }
+    patch
+}
+
+/// Serve a pending rollback request; returns the version the node was
+/// reverted to, or `None` when there was no request or nothing to revert to.
+pub async fn apply_rollback(client: &Client, node: &StellarNode) -> Result<Option<String>> {
+    if !rollback_requested(node) {
+        return Ok(None);
+    }
+
+    let namespace = node.namespace().unwrap_or_else(|| "default".to_string());
+    let name = node.name_any();
+    let target = rollback_target(node);
+    match target {
+        Some(target) => info!(
+            "Rolling {}/{} back from {} to last good version {}",
+            namespace, name, node.spec.version, target
+        ),
+        None => warn!(
+            "Rollback requested for {}/{} but no other last good version is recorded; \
+             clearing {}",
+            namespace, name, ROLLBACK_ANNOTATION
+        ),
+    }
+
+    let api: Api<StellarNode> = Api::namespaced(client.clone(), &namespace);
+    api.patch(
+        &name,
+        &PatchParams::default(),
+        &Patch::Merge(rollback_patch(target)),
+    )
+    .await?;
+
+    Ok(target.map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::controller::test_harness::fake_client;
+    use crate::crd::{StellarNodeSpec, StellarNodeStatus, VersionHistoryEntry};
+    use std::collections::BTreeMap;
+
+    fn node(version: &str, last_good: Option<&str>, rollback: Option<&str>) -> StellarNode {
+        let mut node = StellarNode::new(
+            "horizon-1",
+            StellarNodeSpec {
+                version: version.to_string(),
+                ..Default::default()
+            },
+        );
+        node.metadata.namespace = Some("stellar".to_string());
+        node.status = Some(StellarNodeStatus {
+            last_good_version: last_good.map(str::to_string),
+            ..Default::default()
+        });
+        if let Some(rollback) = rollback {
+            node.metadata.annotations = Some(BTreeMap::from([(
+                ROLLBACK_ANNOTATION.to_string(),
+                rollback.to_string(),
+            )]));
+        }
+        node
+    }
+
+    fn rollout(version: &str, complete: bool) -> WorkloadRollout {
+        WorkloadRollout {
+            version: version.to_string(),
+            complete,
+        }
+    }
+
+    #[test]
+    fn test_last_good_version_recorded_when_ready() {
+        let deployed = rollout("v2.31.0", true);
+        assert_eq!(last_good_version(Some(&deployed), "Ready"), Some("v2.31.0"));
+        assert_eq!(last_good_version(Some(&deployed), "Creating"), None);
+        assert_eq!(last_good_version(Some(&deployed), "Degraded"), None);
+        assert_eq!(last_good_version(Some(&deployed), "Failed"), None);
+    }
+
+    #[test]
+    fn test_last_good_version_waits_for_the_rollout() {
+        // spec.version may already name a newer version than the pods run
+        let rolling = rollout("v2.31.0", false);
+        assert_eq!(last_good_version(Some(&rolling), "Ready"), None);
+        assert_eq!(last_good_version(None, "Ready"), None);
+    }
+
+    #[test]
+    fn test_image_version() {
+        assert_eq!(
+            image_version("stellar/stellar-core:v21.0.0"),
+            Some("v21.0.0")
+        );
+        assert_eq!(
+            image_version("registry.local:5000/stellar/horizon:2.31.0"),
+            Some("2.31.0")
+        );
+        assert_eq!(
+            image_version("stellar/stellar-core@sha256:abc"),
+            Some("sha256:abc")
+        );
+        assert_eq!(
+            image_version("stellar/stellar-core:v21.0.0@sha256:abc"),
+            Some("v21.0.0@sha256:abc")
+        );
+        assert_eq!(image_version("stellar/stellar-core"), None);
+    }
+
+    #[test]
+    fn test_rollout_complete_needs_current_generation_and_updated_replicas() {
+        assert!(rollout_complete(Some(3), Some(3), 2, Some(2)));
+        // Controller has not seen the latest template yet
+        assert!(!rollout_complete(Some(3), Some(2), 2, Some(2)));
+        assert!(!rollout_complete(Some(3), None, 2, Some(2)));
+        // Old replicas still running
+        assert!(!rollout_complete(Some(3), Some(3), 2, Some(1)));
+        assert!(!rollout_complete(Some(3), Some(3), 2, None));
+    }
+
+    #[test]
+    fn test_workload_rollout_from_statefulset() {
+        let statefulset: StatefulSet = serde_json::from_value(serde_json::json!({
+            "metadata": { "name": "validator-1", "generation": 2 },
+            "spec": {
+                "replicas": 1,
+                "selector": {},
+                "serviceName": "validator-1",
+                "template": {
+                    "spec": {
+                        "containers": [{
+                            "name": "stellar-core",
+                            "image": "stellar/stellar-core:v21.0.0"
+                        }]
+                    }
+                }
+            },
+            "status": { "replicas": 1, "observedGeneration": 2, "updatedReplicas": 1 }
+        }))
+        .unwrap();
+        assert_eq!(
+            WorkloadRollout::from_statefulset(&statefulset),
+            Some(WorkloadRollout {
+                version: "v21.0.0".to_string(),
+                complete: true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_rollback_target() {
+        assert_eq!(
+            rollback_target(&node("v2.32.0", Some("v2.31.0"), None)),
+            Some("v2.31.0")
+        );
+        assert_eq!(
+            rollback_target(&node("v2.31.0", Some("v2.31.0"), None)),
+            None
+        );
+        assert_eq!(rollback_target(&node("v2.32.0", None, None)), None);
+    }
+
+    #[test]
+    fn test_rollback_passes_the_downgrade_guard() {
+        use crate::controller::version_guard::check_downgrade;
+
+        let node = node("v22.0.0", Some("v21.0.0"), Some("true"));
+        let mut patched = serde_json::to_value(&node).unwrap();
+        json_patch::merge(&mut patched, &rollback_patch(rollback_target(&node)));
+        let patched: StellarNode = serde_json::from_value(patched).unwrap();
+
+        assert_eq!(patched.spec.version, "v21.0.0");
+        assert!(!rollback_requested(&patched));
+        assert!(check_downgrade(&patched, Some("v22.0.0")).is_ok());
+    }
+
+    #[test]
+    fn test_rollback_requested() {
+        assert!(rollback_requested(&node("v2.32.0", None, Some("true"))));
+        assert!(rollback_requested(&node("v2.32.0", None, Some("True"))));
+        assert!(!rollback_requested(&node("v2.32.0", None, Some("false"))));
+        assert!(!rollback_requested(&node("v2.32.0", None, None)));
+    }
+
+    #[tokio::test]
+    async fn test_rollback_reverts_to_last_good_version() {
+        let node = node("v2.32.0", Some("v2.31.0"), Some("true"));
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move { server.respond_echo().await });
+
+        let reverted = apply_rollback(&client, &node).await.unwrap();
+        let patch = server.await.unwrap();
+
+        assert_eq!(reverted.as_deref(), Some("v2.31.0"));
+        assert_eq!(patch.method, http::Method::PATCH);
+        assert_eq!(
+            patch.path,
+            "/apis/stellar.org/v1alpha1/namespaces/stellar/stellarnodes/horizon-1"
+        );
+        assert_eq!(patch.body["spec"]["version"], "v2.31.0");
+        assert_eq!(
+            patch.body["metadata"]["annotations"].get(ROLLBACK_ANNOTATION),
+            Some(&serde_json::Value::Null)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_without_last_good_only_clears_request() {
+        let node = node("v2.32.0", None, Some("true"));
+        let (client, mut server) = fake_client();
+        let server = tokio::spawn(async move { server.respond_echo().await });
+
+        assert_eq!(apply_rollback(&client, &node).await.unwrap(), None);
+        let patch = server.await.unwrap();
+        assert!(patch.body.get("spec").is_none());
+        assert_eq!(
+            patch.body["metadata"]["annotations"].get(ROLLBACK_ANNOTATION),
+            Some(&serde_json::Value::Null)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_request_sends_nothing() {
+        let node = node("v2.32.0", Some("v2.31.0"), None);
+        let (client, server) = fake_client();
+        let requests = server.serve();
+
+        assert_eq!(apply_rollback(&client, &node).await.unwrap(), None);
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    fn entry(version: &str) -> VersionHistoryEntry {
+        VersionHistoryEntry {
+            version: version.to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            reason: "Upgrade".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_version_history_appends_on_change() {
+        let now = "2026-02-01T00:00:00Z";
+        let mut node = node("v2.31.0", None, None);
+
+        let deployed = rollout("v2.31.0", true);
+        let history = next_version_history(&node, Some(&deployed), "Ready", now).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].version, "v2.31.0");
+        assert_eq!(history[0].timestamp, now);
+        assert_eq!(history[0].reason, "Initial");
+
+        // Same version again, or not Ready: nothing to record
+        node.status.as_mut().unwrap().version_history = history;
+        assert_eq!(
+            next_version_history(&node, Some(&deployed), "Ready", now),
+            None
+        );
+        let deployed = rollout("v2.32.0", true);
+        assert_eq!(
+            next_version_history(&node, Some(&deployed), "Creating", now),
+            None
+        );
+
+        let history = next_version_history(&node, Some(&deployed), "Ready", now).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].version, "v2.32.0");
+        assert_eq!(history[1].reason, "Upgrade");
+
+        node.status.as_mut().unwrap().version_history = history;
+        let deployed = rollout("v2.31.0", true);
+        let history = next_version_history(&node, Some(&deployed), "Ready", now).unwrap();
+        assert_eq!(history[2].reason, "Downgrade");
+    }
+
+    #[test]
+    fn test_version_history_waits_for_the_rollout() {
+        let now = "2026-02-01T00:00:00Z";
+        // spec.version already moved on, the pods still run the old version
+        let node = node("v2.32.0", None, None);
+        let rolling = rollout("v2.32.0", false);
+        assert_eq!(
+            next_version_history(&node, Some(&rolling), "Ready", now),
+            None
+        );
+        assert_eq!(next_version_history(&node, None, "Ready", now), None);
+    }
+
+    #[test]
+    fn test_version_history_is_bounded() {
+        let mut node = node("v2.40.0", None, None);
+        node.status.as_mut().unwrap().version_history = (0..MAX_VERSION_HISTORY)
+            .map(|minor| entry(&format!("v2.{}.0", 30 + minor)))
+            .collect();
+
+        let deployed = rollout("v2.40.0", true);
+        let history =
+            next_version_history(&node, Some(&deployed), "Ready", "2026-02-01T00:00:00Z").unwrap();
+        assert_eq!(history.len(), MAX_VERSION_HISTORY);
+        assert_eq!(history[0].version, "v2.31.0");
+        assert_eq!(history.last().unwrap().version, "v2.40.0");
+    }
+
+    #[test]
+    fn test_version_change_reason() {
+        assert_eq!(version_change_reason(None, "v2.31.0"), "Initial");
+        assert_eq!(version_change_reason(Some("v2.31.0"), "v2.32.0"), "Upgrade");
+        assert_eq!(
+            version_change_reason(Some("v2.32.0"), "v2.31.0"),
+            "Downgrade"
+        );
+        assert_eq!(version_change_reason(Some("latest"), "v2.31.0"), "Change");
+    }
+}
@@ -9,6 +9,7 @@ use std::collections::BTreeMap;
     group = "postgresql.cnpg.io",
     version = "v1",
     kind = "Cluster",
+    status = "ClusterStatus",
     namespaced
 )]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +27,16 @@ pub struct ClusterSpec {
     pub replica: Option<ReplicaConfiguration>,
 }
 
+/// Subset of the CNPG Cluster status read by the operator
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ready_instances: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct PostgresConfiguration {
     pub parameters: BTreeMap<String, String>,
@@ -48,6 +59,9 @@ pub struct BackupConfiguration {
 #[serde(rename_all = "camelCase")]
 pub struct BarmanObjectStore {
     pub destination_path: String,
+    /// Folder under `destination_path` (default: the cluster name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
     pub endpoint_u_r_l: Option<String>,
     pub s3_credentials: Option<S3Credentials>,
     pub azure_credentials: Option<AzureCredentials>,
@@ -104,6 +118,16 @@ pub struct InitDbConfiguration {
 #[serde(rename_all = "camelCase")]
 pub struct RecoveryConfiguration {
     pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovery_target: Option<RecoveryTarget>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryTarget {
+    /// RFC3339 timestamp to replay WAL up to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_time: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
@@ -120,8 +144,13 @@ pub struct MonitoringConfiguration {
 #[serde(rename_all = "camelCase")]
 pub struct ExternalCluster {
     pub name: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub connection_parameters: BTreeMap<String, String>,
-    pub password: SecretKeySelector,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<SecretKeySelector>,
+    /// Object store holding the base backups and WAL archive of the source
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub barman_object_store: Option<BarmanObjectStore>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
@@ -59,8 +59,10 @@ pub mod service_mesh;
 pub mod stellar_autoscaler;
 pub mod stellar_benchmark;
 pub mod stellar_federation;
+pub mod stellar_network_config;
 pub mod stellar_network_policy;
 mod stellar_node;
+pub mod stellar_node_template;
 pub mod stellar_observability;
 pub mod stellar_performance;
 pub mod stellar_topology;
@@ -93,7 +95,7 @@ pub use multi_region::{
     ClusterConfig, ClusterHealthStatus, FailoverPolicy, MultiRegionConfig, MultiRegionHealthCheck,
     MultiRegionSpec, MultiRegionStatus, SecretSyncConfig,
 };
-pub use read_replica::{ReadReplicaConfig, ReadReplicaStrategy};
+pub use read_replica::{ReadReplicaConfig, ReadReplicaStrategy, ReadReplicaVolume};
 pub use secret_policy::{
     AwsKmsConfig, AzureKeyVaultConfig, GcpKmsConfig, KmsProvider, RotationPolicy,
     SecretAuditConfig, SecretPolicy, SecretPolicyCondition, SecretPolicyPhase, SecretPolicySpec,
@@ -118,6 +120,7 @@ pub use stellar_federation::{
     FederationCluster, ReplicationConfig, ReplicationMode, RoutingStrategy, StellarFederation,
     StellarFederationSpec, StellarFederationStatus, TrafficRoutingPolicy,
 };
+pub use stellar_network_config::{StellarNetworkConfig, StellarNetworkConfigSpec};
 pub use stellar_network_policy::{
     AllowedDestination, Condition as NetworkPolicyCondition, DNSRule, EgressRule, GRPCRule,
     HTTPRule, HeaderMatch, IPBlock, IngressRule, L7Rule, LabelSelector, LabelSelectorRequirement,
@@ -130,6 +133,7 @@ pub use stellar_node::{
     BGPStatus, SnapshotBootstrapStatus, SpecValidationError, StellarNode, StellarNodeSpec,
     StellarNodeStatus,
 };
+pub use stellar_node_template::{StellarNodeTemplate, StellarNodeTemplateSpec};
 pub use stellar_observability::{
     AlertRule, AlertingConfig, AnomalyDetectionConfig, AnomalyModel, AnomalySensitivity,
     LoggingBackend, LoggingConfig, StellarObservability, StellarObservabilitySpec,
@@ -24,6 +24,28 @@ pub struct ReadReplicaConfig {
     /// When true, replicas serve different archives to balance bandwidth
     #[serde(default)]
     pub archive_sharding: bool,
+
+    /// Volume holding each replica's buckets and ledger database
+    #[serde(default)]
+    pub data_volume: ReadReplicaVolume,
+
+    /// Size of each replica's data volume: the PVC request for `Persistent`
+    /// (defaults to `spec.storage.size`), the size limit for `EmptyDir`
+    /// (unlimited by default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_volume_size: Option<String>,
+}
+
+/// Volume backing a read replica's data
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
+pub enum ReadReplicaVolume {
+    /// An emptyDir: pods schedule without waiting for a volume, but a
+    /// restarted replica catches up from the archives again
+    #[default]
+    EmptyDir,
+    /// A PVC per replica, from `spec.storage.storageClass`; the replica keeps
+    /// its state across restarts
+    Persistent,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
@@ -0,0 +1,103 @@
+//! StellarNetworkConfig CRD for network settings shared across namespaces
+
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::types::StellarNetwork;
+
+/// Cluster-wide settings of one Stellar network, referenced by StellarNodes
+/// through `spec.networkConfigRef`
+///
+/// Centralizes what every node on a network would otherwise repeat: the
+/// passphrase of a custom network, its history archives, the quorum set its
+/// validators share and the Horizon used as the reference for ingestion lag.
+///
+/// ```yaml
+/// apiVersion: stellar.org/v1alpha1
+/// kind: StellarNetworkConfig
+/// metadata:
+///   name: private-net
+/// spec:
+///   network:
+///     custom: private-net
+///   passphrase: "Private Network ; January 2026"
+///   historyArchiveUrls:
+///     - https://archive.private-net.example.com
+///   horizonUrl: https://horizon.private-net.example.com
+///   quorumSet: |
+///     [QUORUM_SET]
+///     THRESHOLD_PERCENT=67
+///     VALIDATORS=["GA... validator-1", "GB... validator-2", "GC... validator-3"]
+/// ```
+#[derive(CustomResource, Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+#[kube(
+    group = "stellar.org",
+    version = "v1alpha1",
+    kind = "StellarNetworkConfig",
+    shortname = "snc",
+    printcolumn = r#"{"name":"Network","type":"string","jsonPath":".spec.network"}"#,
+    printcolumn = r#"{"name":"Age","type":"date","jsonPath":".metadata.creationTimestamp"}"#
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StellarNetworkConfigSpec {
+    /// The network these settings describe
+    pub network: StellarNetwork,
+
+    /// Network passphrase; required for a custom network, and must match the
+    /// well-known passphrase when set for a public one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+
+    /// History archives of the network, used by nodes that list none
+    #[serde(default)]
+    pub history_archive_urls: Vec<String>,
+
+    /// Horizon queried for the latest network ledger when computing
+    /// ingestion lag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub horizon_url: Option<String>,
+
+    /// Quorum set shared by the network's validators, as a stellar-core
+    /// `[QUORUM_SET]` TOML fragment. Published in a `<name>-quorum-set`
+    /// ConfigMap in each namespace running a validator that sets neither
+    /// `quorumSet` nor `vlSource`; such validators render from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quorum_set: Option<String>,
+}
+
+impl StellarNetworkConfigSpec {
+    /// Passphrase of the network
+    pub fn network_passphrase(&self) -> &str {
+        self.network.passphrase(&self.passphrase)
+    }
+
+    /// Check the settings are complete and consistent.
+    pub fn validate(&self) -> Result<(), String> {
+        self.network.validate_custom_name()?;
+        if let Some(quorum_set) = &self.quorum_set {
+            let parsed = quorum_set
+                .parse::<toml::Value>()
+                .map_err(|e| format!("quorumSet is not valid TOML: {e}"))?;
+            if !parsed.get("QUORUM_SET").is_some_and(toml::Value::is_table) {
+                return Err("quorumSet must contain a [QUORUM_SET] table".to_string());
+            }
+        }
+        match (&self.network, self.passphrase.as_deref()) {
+            (StellarNetwork::Custom(_), None | Some("")) => {
+                Err("passphrase is required for a custom network".to_string())
+            }
+            (StellarNetwork::Custom(_), Some(_)) | (_, None) => Ok(()),
+            (network, Some(passphrase)) => {
+                let expected = network.passphrase(&None);
+                if passphrase == expected {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "passphrase '{passphrase}' does not match the {network:?} passphrase '{expected}'"
+                    ))
+                }
+            }
+        }
+    }
+}
@@ -7,20 +7,23 @@ use k8s_openapi::api::core::v1::{Volume, VolumeMount};
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
 use kube::CustomResource;
+use regex::Regex;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet};
+use std::sync::LazyLock;
 
 use super::types::{
     AuditConfig, AutoscalingConfig, CertManagerConfig, Condition, CoreSyncState,
     CrossClusterConfig, DisasterRecoveryConfig, DisasterRecoveryStatus, ExternalDatabaseConfig,
     ForensicSnapshotConfig, GasAutoscalingConfig, GlobalDiscoveryConfig, HistoryMode,
     HorizonConfig, IngressConfig, LabelPropagationConfig, LoadBalancerConfig, LogShipperConfig,
-    ManagedDatabaseConfig, NetworkPolicyConfig, NodeType, OciSnapshotConfig, PlacementConfig,
-    PodAntiAffinityStrength, PolicyConfig, ProbeConfig, RbacConfig, ResourceRequirements,
-    RestoreFromSnapshotConfig, RetentionPolicy, RolloutStrategy, SnapshotScheduleConfig,
-    SorobanConfig, StellarNetwork, StorageConfig, SyncStateScalingConfig, ValidatorConfig,
-    VpaConfig,
+    ManagedDatabaseConfig, NetworkPolicyConfig, NodeType, OciSnapshotConfig, OtelCollectorConfig,
+    PlacementConfig, PodAntiAffinityStrength, PolicyConfig, ProbeConfig, RbacConfig,
+    ResourceRequirements, RestoreFromSnapshotConfig, RetentionPolicy, RolloutStrategy,
+    ServiceAccountConfig, SnapshotScheduleConfig, SorobanConfig, StellarNetwork, StorageConfig,
+    StorageTier, SyncStateScalingConfig, ValidatorConfig, VpaConfig,
+    SERVICE_ACCOUNT_RULE_RESOURCES, SERVICE_ACCOUNT_RULE_VERBS,
 };
 
 /// Structured validation error for `StellarNodeSpec`
@@ -145,6 +148,11 @@ pub struct StellarNodeSpec {
     /// into every managed pod to stream compressed logs directly to S3.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub log_shipper: Option<LogShipperConfig>,
+    /// OpenTelemetry Collector sidecar forwarding the node's traces.
+    /// When set and `enabled: true`, an `otel-collector` sidecar is injected
+    /// into every managed pod.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otel_collector: Option<OtelCollectorConfig>,
     /// Dynamic resource scaling based on Stellar Core sync state.
     ///
     /// When enabled, the operator boosts CPU/memory while the node is catching up
@@ -169,14 +177,32 @@ pub struct StellarNodeSpec {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub service_annotations: Option<BTreeMap<String, String>>,
 
-    /// Custom pod volumes available to the main Stellar container and init containers.
+    /// Extra pod volumes, e.g. ConfigMaps with CA bundles or scripts, available to
+    /// the main Stellar container and init containers.
+    ///
+    /// Names must not collide with the operator-managed volumes
+    /// ([`MANAGED_VOLUME_NAMES`]).
+    ///
+    /// # Example
+    /// ```yaml
+    /// volumes:
+    ///   - name: ca-bundle
+    ///     configMap:
+    ///       name: corporate-ca
+    /// volumeMounts:
+    ///   - name: ca-bundle
+    ///     mountPath: /etc/ssl/custom
+    ///     readOnly: true
+    /// ```
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[schemars(skip)]
+    #[schemars(schema_with = "super::schema_utils::array_of_objects_schema")]
     pub volumes: Option<Vec<Volume>>,
 
-    /// Custom volume mounts for the main Stellar container.
+    /// Extra volume mounts for the main Stellar container, each referencing a
+    /// volume from `volumes`. Mount paths must not collide with the
+    /// operator-managed mounts ([`MANAGED_MOUNT_PATHS`]).
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[schemars(skip)]
+    #[schemars(schema_with = "super::schema_utils::array_of_objects_schema")]
     pub volume_mounts: Option<Vec<VolumeMount>>,
 
     /// Global discovery configuration for cross-cluster discovery
@@ -306,6 +332,33 @@ pub struct StellarNodeSpec {
     #[schemars(skip)]
     pub resource_meta: Option<ObjectMeta>,
 
+    /// Extra labels and annotations applied only to the node's Service.
+    ///
+    /// Use this for Service-specific settings such as cloud load balancer
+    /// annotations that must not end up on pods or volumes.
+    ///
+    /// # Example
+    /// ```yaml
+    /// serviceMeta:
+    ///   annotations:
+    ///     service.beta.kubernetes.io/aws-load-balancer-type: nlb
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "super::schema_utils::object_schema")]
+    pub service_meta: Option<ObjectMeta>,
+
+    /// Extra labels and annotations applied only to the node's data PVC.
+    ///
+    /// # Example
+    /// ```yaml
+    /// pvcMeta:
+    ///   labels:
+    ///     backup-tier: gold
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "super::schema_utils::object_schema")]
+    pub pvc_meta: Option<ObjectMeta>,
+
     /// Optional sidecar containers to run alongside the main Stellar container.
     ///
     /// These containers share the pod's network namespace and can mount the same
@@ -500,6 +553,87 @@ pub struct StellarNodeSpec {
     /// ```
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority_class_name: Option<String>,
+
+    /// Dedicated ServiceAccount for the node's pods, optionally with scoped
+    /// permissions. Pods use the namespace's `default` ServiceAccount when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account: Option<ServiceAccountConfig>,
+
+    /// DNS policy of the node's pods: `ClusterFirst` (Kubernetes default),
+    /// `ClusterFirstWithHostNet`, `Default` or `None`. `None` requires `dnsConfig`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_policy: Option<String>,
+
+    /// Extra resolvers, search domains and options for the node's pods, e.g. to
+    /// resolve peers in other clusters.
+    ///
+    /// # Example
+    /// ```yaml
+    /// dnsPolicy: ClusterFirst
+    /// dnsConfig:
+    ///   searches:
+    ///     - stellar.svc.cluster-b.example.com
+    ///   nameservers:
+    ///     - 10.20.0.10
+    ///   options:
+    ///     - name: ndots
+    ///       value: "2"
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "super::schema_utils::object_schema")]
+    pub dns_config: Option<k8s_openapi::api::core::v1::PodDNSConfig>,
+
+    /// Run the validator pod in the host's network namespace, for bare-metal
+    /// validators whose peers must reach them on the host address.
+    ///
+    /// Validators only. Every container port is bound on the host, the pod
+    /// bypasses NetworkPolicies, and it can see all host interfaces; prefer a
+    /// LoadBalancer or NodePort Service where possible.
+    #[serde(default)]
+    pub host_network: bool,
+
+    /// Entrypoint of the main container, replacing the image's `ENTRYPOINT`.
+    /// Defaults to the image's entrypoint.
+    ///
+    /// For validators, a `--conf` argument must point to the operator-managed
+    /// config at `/config/stellar-core.cfg`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<Vec<String>>,
+
+    /// Arguments of the main container, replacing the image's `CMD`.
+    /// Defaults to the image's arguments.
+    ///
+    /// ```yaml
+    /// args: ["run", "--conf", "/config/stellar-core.cfg", "--wait-for-consensus"]
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<String>>,
+
+    /// Name of a `StellarNodeTemplate` in the same namespace to inherit from.
+    ///
+    /// The template's spec is used as the base and every field set on this
+    /// StellarNode overrides it. Fields the schema requires or defaults
+    /// (`nodeType`, `network`, `version`, `replicas`, ...) are always taken
+    /// from the node itself and cannot come from the template.
+    ///
+    /// # Example
+    /// ```yaml
+    /// templateRef: mainnet-validator
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_ref: Option<String>,
+
+    /// Name of a cluster-scoped `StellarNetworkConfig` supplying the
+    /// network passphrase, history archives and reference Horizon.
+    ///
+    /// `network` must still be set and must match the config's network.
+    ///
+    /// # Example
+    /// ```yaml
+    /// networkConfigRef: private-net
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network_config_ref: Option<String>,
 }
 
 fn default_network_policy() -> Option<NetworkPolicyConfig> {
@@ -562,6 +696,8 @@ impl Default for StellarNodeSpec {
             nat_traversal: None,
             label_propagation: None,
             resource_meta: None,
+            service_meta: None,
+            pvc_meta: None,
             custom_network_passphrase: None,
             passphrase_secret_ref: None,
             sidecars: None,
@@ -577,13 +713,22 @@ impl Default for StellarNodeSpec {
             secret_rotation: None,
             backup_verification: None,
             log_shipper: None,
+            otel_collector: None,
             sync_state_scaling: None,
             pruning_policy: None,
             rbac: None,
             audit: None,
             policy: None,
             priority_class_name: None,
+            service_account: None,
+            dns_policy: None,
+            dns_config: None,
+            host_network: false,
+            command: None,
+            args: None,
             security_context: None,
+            template_ref: None,
+            network_config_ref: None,
         }
     }
 }
@@ -633,6 +778,15 @@ impl StellarNodeSpec {
             ));
         }
 
+        // 0a. Image version format
+        if let Err(msg) = validate_version(&self.version) {
+            errors.push(SpecValidationError::new(
+                "spec.version",
+                msg,
+                "Use a release tag such as \"v21.0.0\" or \"21.3.1-2007.4ede19620.focal\", optionally pinned as \"v21.0.0@sha256:<digest>\", or a bare \"sha256:<digest>\".",
+            ));
+        }
+
         // 1. Database Mutual Exclusion
         if self.database.is_some() && self.managed_database.is_some() {
             errors.push(SpecValidationError::new(
@@ -642,6 +796,35 @@ impl StellarNodeSpec {
             ));
         }
 
+        // 1a. Managed database backup endpoint
+        if let Some(endpoint) = self
+            .managed_database
+            .as_ref()
+            .and_then(|db| db.backup.as_ref())
+            .and_then(|backup| backup.endpoint_url.as_deref())
+        {
+            if let Err(msg) = validate_endpoint_url(endpoint) {
+                errors.push(SpecValidationError::new(
+                    "spec.managedDatabase.backup.endpointUrl",
+                    msg,
+                    "Provide a full http(s) URL including the host. Example: \"https://minio.storage.svc:9000\".",
+                ));
+            }
+        }
+
+        // 1b. OpenTelemetry Collector forwarding endpoint
+        if self
+            .otel_collector
+            .as_ref()
+            .is_some_and(|otel| otel.enabled && otel.endpoint.trim().is_empty())
+        {
+            errors.push(SpecValidationError::new(
+                "spec.otelCollector.endpoint",
+                "otelCollector is enabled but has no endpoint to forward to",
+                "Set the OTLP gRPC endpoint as host:port. Example: \"otel-gateway.observability.svc:4317\".",
+            ));
+        }
+
         // 2. PDB Conflict Check
         if self.min_available.is_some() && self.max_unavailable.is_some() {
             errors.push(SpecValidationError::new(
@@ -663,6 +846,17 @@ impl StellarNodeSpec {
             }
         }
 
+        // 2a'. Access modes: a validator's ledger state must have one writer
+        if self.node_type == NodeType::Validator
+            && self.storage.access_modes.iter().any(|m| !m.is_exclusive())
+        {
+            errors.push(SpecValidationError::new(
+                "spec.storage.accessModes",
+                "Validator nodes must use a ReadWriteOnce (or ReadWriteOncePod) volume",
+                "Remove spec.storage.accessModes or set it to [ReadWriteOnce] for Validator nodes; shared access modes are for Horizon and SorobanRpc pools.",
+            ));
+        }
+
         // 2b. snapshotRef validation (applies to all node types)
         if let Some(ref snap_ref) = self.storage.snapshot_ref {
             let has_csi = snap_ref.volume_snapshot_name.is_some();
@@ -715,17 +909,7 @@ impl StellarNodeSpec {
                     ));
                 }
 
-                if [
-                    "data",
-                    "config",
-                    "tls",
-                    "keys",
-                    "cloudhsm-socket",
-                    "dedicatedhsm-socket",
-                    "soroban-cache",
-                ]
-                .contains(&volume.name.as_str())
-                {
+                if MANAGED_VOLUME_NAMES.contains(&volume.name.as_str()) {
                     errors.push(SpecValidationError::new(
                         "spec.volumes[].name",
                         format!("Volume name '{}' conflicts with an operator-managed pod volume", volume.name),
@@ -743,7 +927,29 @@ impl StellarNodeSpec {
 
         if let Some(ref volume_mounts) = self.volume_mounts {
             let mut seen = BTreeSet::new();
+            let mut seen_paths = BTreeSet::new();
             for mount in volume_mounts {
+                let mount_path = mount.mount_path.trim_end_matches('/');
+                if MANAGED_MOUNT_PATHS.contains(&mount_path) {
+                    errors.push(SpecValidationError::new(
+                        "spec.volumeMounts[].mountPath",
+                        format!(
+                            "Mount path '{}' conflicts with an operator-managed volume mount",
+                            mount.mount_path
+                        ),
+                        "Mount the custom volume at a different path, e.g. under /etc or /opt/custom.",
+                    ));
+                } else if !mount_path.is_empty() && !seen_paths.insert(mount_path.to_string()) {
+                    errors.push(SpecValidationError::new(
+                        "spec.volumeMounts[].mountPath",
+                        format!(
+                            "Duplicate mountPath '{}' in spec.volumeMounts",
+                            mount.mount_path
+                        ),
+                        "Give each custom volume mount a distinct mountPath.",
+                    ));
+                }
+
                 if mount.name.is_empty() {
                     errors.push(SpecValidationError::new(
                         "spec.volumeMounts[].name",
@@ -810,6 +1016,20 @@ impl StellarNodeSpec {
                             "Provide at least one valid history archive URL in spec.validatorConfig.historyArchiveUrls when enableHistoryArchive is true.",
                         ));
                     }
+                    if self.storage.tier == Some(StorageTier::Hdd) {
+                        errors.push(SpecValidationError::new(
+                            "spec.storage.tier",
+                            "Validator nodes cannot use HDD storage",
+                            "Set spec.storage.tier to SSD (or leave it unset) for Validator nodes; stellar-core falls out of sync on slow disks.",
+                        ));
+                    }
+                    if let Some(Err(e)) = vc.bucket_list_db.as_ref().map(|db| db.validate()) {
+                        errors.push(SpecValidationError::new(
+                            "spec.validatorConfig.bucketListDb",
+                            e,
+                            "Leave indexPageSizeExponent unset for InMemory storage, or keep it between 4 and 31 for OnDisk.",
+                        ));
+                    }
                 }
 
                 // Exactly 1 replica required
@@ -895,6 +1115,20 @@ impl StellarNodeSpec {
                         }
                     }
                 }
+                if let Some(schedule) = self
+                    .snapshot_schedule
+                    .as_ref()
+                    .and_then(|s| s.schedule.as_deref())
+                    .filter(|s| !s.trim().is_empty())
+                {
+                    if let Err(msg) = crate::crd::types::parse_unix_cron(schedule) {
+                        errors.push(SpecValidationError::new(
+                            "spec.snapshotSchedule.schedule",
+                            msg,
+                            "Use a five-field Unix cron expression without seconds. Example: \"0 2 * * *\" for daily at 02:00 UTC.",
+                        ));
+                    }
+                }
                 // Snapshot schedule and restore only apply to Validators (ledger data)
                 if (self.snapshot_schedule.is_some() || self.restore_from_snapshot.is_some())
                     && self
@@ -926,6 +1160,15 @@ impl StellarNodeSpec {
                         "Add a spec.horizonConfig section with the required Horizon settings when nodeType is Horizon.",
                     ));
                 }
+                if let Some(admin_port) = self.horizon_config.as_ref().and_then(|c| c.admin_port) {
+                    if admin_port == 0 || admin_port == 8000 {
+                        errors.push(SpecValidationError::new(
+                            "spec.horizonConfig.adminPort",
+                            "adminPort must be non-zero and differ from the HTTP port 8000",
+                            "Set spec.horizonConfig.adminPort to a free port such as 6060.",
+                        ));
+                    }
+                }
                 if let Some(ref autoscaling) = self.autoscaling {
                     if autoscaling.min_replicas < 1 {
                         errors.push(SpecValidationError::new(
@@ -1041,6 +1284,154 @@ impl StellarNodeSpec {
             }
         }
 
+        // 5a. ServiceAccount validation
+        if let Some(ref sa) = self.service_account {
+            if !sa.create && sa.name.as_deref().unwrap_or_default().is_empty() {
+                errors.push(SpecValidationError::new(
+                    "spec.serviceAccount.name",
+                    "serviceAccount.name is required when serviceAccount.create is false",
+                    "Set spec.serviceAccount.name to an existing ServiceAccount, or set create: true to let the operator create one.",
+                ));
+            }
+            if !sa.create && !sa.rules.is_empty() {
+                errors.push(SpecValidationError::new(
+                    "spec.serviceAccount.rules",
+                    "serviceAccount.rules require a ServiceAccount created by the operator",
+                    "Set spec.serviceAccount.create to true, or grant the permissions to the existing ServiceAccount yourself.",
+                ));
+            }
+            for (i, rule) in sa.rules.iter().enumerate() {
+                if !ServiceAccountConfig::rule_is_allowed(rule) {
+                    errors.push(SpecValidationError::new(
+                        format!("spec.serviceAccount.rules[{i}]"),
+                        format!(
+                            "serviceAccount.rules may only grant {} on core resources {}",
+                            SERVICE_ACCOUNT_RULE_VERBS.join(", "),
+                            SERVICE_ACCOUNT_RULE_RESOURCES.join(", ")
+                        ),
+                        "Grant broader permissions to an existing ServiceAccount yourself and reference it with create: false.",
+                    ));
+                }
+            }
+        }
+
+        // 5b. DNS policy validation
+        if let Some(ref policy) = self.dns_policy {
+            if !DNS_POLICIES.contains(&policy.as_str()) {
+                errors.push(SpecValidationError::new(
+                    "spec.dnsPolicy",
+                    format!("dnsPolicy '{policy}' is not supported"),
+                    format!("Use one of: {}.", DNS_POLICIES.join(", ")),
+                ));
+            } else if policy == "None" && self.dns_config.is_none() {
+                errors.push(SpecValidationError::new(
+                    "spec.dnsConfig",
+                    "dnsPolicy None requires dnsConfig",
+                    "Set spec.dnsConfig with at least one nameserver, or use another dnsPolicy.",
+                ));
+            }
+        }
+
+        // 5c. hostNetwork is only for validators
+        if self.host_network && self.node_type != NodeType::Validator {
+            errors.push(SpecValidationError::new(
+                "spec.hostNetwork",
+                "hostNetwork is only supported for Validator nodes",
+                "Remove spec.hostNetwork, or expose the node through a Service (e.g. spec.loadBalancer) instead.",
+            ));
+        }
+
+        // 5d. command/args overrides must keep the managed config
+        for (field, values) in [("spec.command", &self.command), ("spec.args", &self.args)] {
+            let Some(values) = values else {
+                continue;
+            };
+            if (field == "spec.command" && values.is_empty())
+                || values.iter().any(|v| v.trim().is_empty())
+            {
+                errors.push(SpecValidationError::new(
+                    field,
+                    format!("{field} must not be empty or contain empty entries"),
+                    format!("Remove {field} to use the image default, or list non-empty entries."),
+                ));
+            }
+        }
+        if self.node_type == NodeType::Validator {
+            if let Some(conf) = self.conf_override().filter(|c| *c != CORE_CONFIG_PATH) {
+                errors.push(SpecValidationError::new(
+                    "spec.args",
+                    format!("--conf {conf} bypasses the operator-managed config"),
+                    format!("Point --conf at {CORE_CONFIG_PATH}, which holds the quorum set, peers and network settings the operator manages."),
+                ));
+            }
+        }
+
+        // 5e. Rolling update parameters of Deployment-based nodes
+        let strategy = &self.strategy;
+        if strategy.max_surge.is_some() || strategy.max_unavailable.is_some() {
+            let is_zero = |value: &Option<IntOrString>| match value {
+                Some(IntOrString::Int(n)) => *n == 0,
+                Some(IntOrString::String(s)) => s.trim() == "0%" || s.trim() == "0",
+                None => false,
+            };
+            if self.node_type == NodeType::Validator {
+                errors.push(SpecValidationError::new(
+                    "spec.strategy",
+                    "maxSurge/maxUnavailable only apply to Horizon and SorobanRpc Deployments",
+                    "Remove spec.strategy.maxSurge and spec.strategy.maxUnavailable for Validator nodes.",
+                ));
+            } else if is_zero(&strategy.max_surge) && is_zero(&strategy.max_unavailable) {
+                errors.push(SpecValidationError::new(
+                    "spec.strategy",
+                    "maxSurge and maxUnavailable cannot both be zero",
+                    "Allow at least one extra pod (maxSurge) or one unavailable pod (maxUnavailable) so the rollout can progress.",
+                ));
+            }
+        }
+        if let Some(seconds) = strategy.min_ready_seconds {
+            if self.node_type == NodeType::Validator {
+                errors.push(SpecValidationError::new(
+                    "spec.strategy.minReadySeconds",
+                    "minReadySeconds only applies to Horizon and SorobanRpc Deployments",
+                    "Remove spec.strategy.minReadySeconds for Validator nodes.",
+                ));
+            } else if seconds < 0 {
+                errors.push(SpecValidationError::new(
+                    "spec.strategy.minReadySeconds",
+                    "minReadySeconds must not be negative",
+                    "Set spec.strategy.minReadySeconds to 0 or more seconds.",
+                ));
+            }
+        }
+        if let Some(deadline) = strategy.progress_deadline_seconds {
+            if self.node_type == NodeType::Validator {
+                errors.push(SpecValidationError::new(
+                    "spec.strategy.progressDeadlineSeconds",
+                    "progressDeadlineSeconds only applies to Horizon and SorobanRpc Deployments",
+                    "Remove spec.strategy.progressDeadlineSeconds for Validator nodes.",
+                ));
+            } else if deadline <= strategy.min_ready_seconds.unwrap_or(0) {
+                errors.push(SpecValidationError::new(
+                    "spec.strategy.progressDeadlineSeconds",
+                    "progressDeadlineSeconds must be positive and greater than minReadySeconds",
+                    "Set spec.strategy.progressDeadlineSeconds above spec.strategy.minReadySeconds.",
+                ));
+            }
+        }
+
+        // 5f. CVE canary analysis needs a Prometheus to query
+        if let Some(cve) = &self.cve_handling {
+            if !cve.canary_analysis.is_empty()
+                && cve.prometheus_url.as_deref().unwrap_or_default().is_empty()
+            {
+                errors.push(SpecValidationError::new(
+                    "spec.cveHandling.prometheusUrl",
+                    "prometheusUrl is required when canaryAnalysis is set",
+                    "Set spec.cveHandling.prometheusUrl to the Prometheus the canary checks query, or remove spec.cveHandling.canaryAnalysis.",
+                ));
+            }
+        }
+
         // 6. PriorityClass name validation
         if let Some(ref pcn) = self.priority_class_name {
             if pcn.is_empty() {
@@ -1075,6 +1466,128 @@ impl StellarNodeSpec {
     pub fn should_delete_pvc(&self) -> bool {
         self.storage.retention_policy == RetentionPolicy::Delete
     }
+
+    /// Config path passed with `--conf` in the command/args overrides, if any
+    pub fn conf_override(&self) -> Option<&str> {
+        let mut words = self
+            .command
+            .iter()
+            .chain(self.args.iter())
+            .flatten()
+            .map(String::as_str);
+        while let Some(word) = words.next() {
+            if word == "--conf" {
+                return Some(words.next().unwrap_or_default());
+            }
+            if let Some(conf) = word.strip_prefix("--conf=") {
+                return Some(conf);
+            }
+        }
+        None
+    }
+
+    /// Warn about running in the host network namespace
+    ///
+    /// Returns `None` unless `hostNetwork` is set.
+    pub fn host_network_warning(&self) -> Option<String> {
+        self.host_network.then(|| {
+            "hostNetwork is enabled: the validator binds its ports directly on the host, \
+             bypasses NetworkPolicies and shares the host's network stack. Only use it on \
+             dedicated bare-metal nodes."
+                .to_string()
+        })
+    }
+
+    /// Warn when the data PVC is deleted with the node although snapshots are scheduled
+    ///
+    /// With `retentionPolicy: Delete` the PVC goes away together with the
+    /// StellarNode, so any ledger data written after the last scheduled snapshot
+    /// is lost. Returns `None` when the combination is safe.
+    pub fn retention_backup_conflict(&self) -> Option<String> {
+        if !self.should_delete_pvc() || self.snapshot_schedule.is_none() {
+            return None;
+        }
+        Some(
+            "storage.retentionPolicy is Delete while snapshotSchedule is set: the data PVC \
+             is deleted together with the StellarNode and data written since the last \
+             snapshot is lost. Set storage.retentionPolicy to Retain to keep the volume."
+                .to_string(),
+        )
+    }
+}
+
+/// Path of the operator-managed stellar-core config in the validator container
+pub const CORE_CONFIG_PATH: &str = "/config/stellar-core.cfg";
+
+/// Pod DNS policies accepted by Kubernetes
+const DNS_POLICIES: &[&str] = &["ClusterFirst", "ClusterFirstWithHostNet", "Default", "None"];
+
+/// Pod volumes created by the operator, which `spec.volumes` must not reuse
+pub const MANAGED_VOLUME_NAMES: &[&str] = &[
+    "data",
+    "config",
+    "tls",
+    "keys",
+    "cloudhsm-socket",
+    "dedicatedhsm-socket",
+    "soroban-cache",
+    "handoff-socket",
+    "stellar-logs",
+    "stellar-seed-csi",
+    "sys-kernel-debug",
+    "lib-modules",
+    "otel-collector-config",
+    "history-archive",
+];
+
+/// Paths the operator mounts into the main container, which
+/// `spec.volumeMounts` must not reuse
+pub const MANAGED_MOUNT_PATHS: &[&str] = &[
+    "/opt/stellar/data",
+    "/data",
+    "/config",
+    "/keys",
+    "/etc/stellar/tls",
+    "/var/run/cloudhsm",
+    "/var/run/dedicatedhsm",
+    "/var/log/stellar",
+    "/history-archive",
+];
+
+/// `[v]MAJOR.MINOR.PATCH[-suffix]`, optionally pinned with `@sha256:<digest>`,
+/// or a bare `sha256:<digest>`
+static VERSION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(v?\d+\.\d+\.\d+(-[0-9A-Za-z][0-9A-Za-z.-]*)?(@sha256:[0-9a-f]{64})?|sha256:[0-9a-f]{64})$",
+    )
+    .expect("version regex is valid")
+});
+
+/// Check that `version` forms a pullable image reference in
+/// [`StellarNodeSpec::container_image`].
+fn validate_version(version: &str) -> Result<(), String> {
+    if VERSION_PATTERN.is_match(version) {
+        Ok(())
+    } else {
+        Err(format!(
+            "version '{version}' is not a MAJOR.MINOR.PATCH release tag or a sha256 digest"
+        ))
+    }
+}
+
+/// Check that an object store endpoint is an http(s) URL with a host.
+fn validate_endpoint_url(endpoint: &str) -> Result<(), String> {
+    let url = url::Url::parse(endpoint.trim())
+        .map_err(|e| format!("endpointUrl '{endpoint}' is not a valid URL: {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!(
+            "endpointUrl '{endpoint}' must use the http or https scheme"
+        ));
+    }
+    if url.host_str().unwrap_or_default().is_empty() {
+        return Err(format!("endpointUrl '{endpoint}' must include a host"));
+    }
+    Ok(())
 }
 
 fn validate_gas_autoscaling(gas: &GasAutoscalingConfig, errors: &mut Vec<SpecValidationError>) {
@@ -1316,13 +1829,25 @@ fn validate_cross_cluster(cc: &CrossClusterConfig, errors: &mut Vec<SpecValidati
     }
 
     // Validate peer clusters
+    let mut seen_cluster_ids: BTreeMap<&str, usize> = BTreeMap::new();
     for (i, peer) in cc.peer_clusters.iter().enumerate() {
-        if peer.cluster_id.trim().is_empty() {
+        let cluster_id = peer.cluster_id.trim();
+        if cluster_id.is_empty() {
             errors.push(SpecValidationError::new(
                 format!("spec.crossCluster.peerClusters[{i}].clusterId"),
                 "crossCluster.peerClusters[].clusterId must not be empty",
                 "Set a non-empty identifier for each entry in spec.crossCluster.peerClusters[].clusterId.",
             ));
+        } else if let Some(first) = seen_cluster_ids.get(cluster_id) {
+            errors.push(SpecValidationError::new(
+                format!("spec.crossCluster.peerClusters[{i}].clusterId"),
+                format!(
+                    "crossCluster.peerClusters[].clusterId '{cluster_id}' duplicates peerClusters[{first}]"
+                ),
+                "Give every entry in spec.crossCluster.peerClusters a unique clusterId; duplicates produce colliding ExternalName services.",
+            ));
+        } else {
+            seen_cluster_ids.insert(cluster_id, i);
         }
         if peer.endpoint.trim().is_empty() {
             errors.push(SpecValidationError::new(
@@ -1425,6 +1950,20 @@ fn validate_cross_cluster(cc: &CrossClusterConfig, errors: &mut Vec<SpecValidati
                             "Set percentile to a value between 1 and 100 in spec.crossCluster.healthCheck.latencyMeasurement.",
                         ));
                     }
+                    if lm.timeout_ms == 0 {
+                        errors.push(SpecValidationError::new(
+                            "spec.crossCluster.healthCheck.latencyMeasurement.timeoutMs",
+                            "crossCluster.healthCheck.latencyMeasurement.timeoutMs must be greater than 0",
+                            "Set timeoutMs to a value greater than 0 (default 5000) in spec.crossCluster.healthCheck.latencyMeasurement.",
+                        ));
+                    }
+                    if lm.mad_threshold.is_nan() || lm.mad_threshold <= 0.0 {
+                        errors.push(SpecValidationError::new(
+                            "spec.crossCluster.healthCheck.latencyMeasurement.madThreshold",
+                            "crossCluster.healthCheck.latencyMeasurement.madThreshold must be greater than 0",
+                            "Set madThreshold to a positive number of median absolute deviations (default 3) in spec.crossCluster.healthCheck.latencyMeasurement.",
+                        ));
+                    }
                 }
             }
         }
@@ -1517,7 +2056,7 @@ fn validate_service_mesh(
 #[serde(rename_all = "camelCase")]
 pub struct StellarNodeStatus {
     /// Current phase of the node lifecycle
-    /// (Pending, Creating, Running, Syncing, Ready, Failed, Degraded, Remediating, Terminating)
+    /// (Pending, Creating, Running, Syncing, Ready, Failed, Degraded, Remediating, Suspended, Terminating)
     ///
     /// DEPRECATED: Use the conditions array instead. This field is maintained for backward compatibility
     /// and will be removed in a future version. The phase is now derived from the conditions.
@@ -1545,6 +2084,7 @@ pub struct StellarNodeStatus {
     /// - Ready: True when all sub-resources are healthy and the node is operational
     /// - Progressing: True when the node is being created, updated, or syncing
     /// - Degraded: True when the node is operational but experiencing issues
+    /// - Suspended: True while `spec.suspended` holds the workload at 0 replicas
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditions: Vec<Condition>,
 
@@ -1639,6 +2179,10 @@ pub struct StellarNodeStatus {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sync_state: Option<CoreSyncState>,
 
+    /// Consensus state reported by stellar-core (Validator nodes only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub consensus: Option<super::types::ConsensusHealth>,
+
     /// Whether sync-state-driven resource scaling is currently active and which
     /// profile is applied (`CatchingUp` or `Synced`).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1648,6 +2192,20 @@ pub struct StellarNodeStatus {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pruning_status: Option<super::types::PruningStatus>,
 
+    /// Per-archive count of consecutive lagging integrity checks, used to decide
+    /// when the primary history archive is rotated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archive_lag_strikes: Vec<super::types::ArchiveLagStrike>,
+
+    /// History archive promoted to primary after the first configured one kept
+    /// lagging. Ignored once it is no longer listed in `historyArchiveUrls`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_history_archive: Option<String>,
+
+    /// DNS resolutions of cross-cluster peer endpoints backing ExternalName services.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub peer_endpoints: Vec<super::types::PeerEndpointResolution>,
+
     /// Observed resource version of the passphrase secret (for rotation detection).
     /// When this differs from the current secret's resourceVersion, the operator
     /// triggers a graceful rolling restart.
@@ -1663,6 +2221,24 @@ pub struct StellarNodeStatus {
     /// Timestamp of the last secret rotation (RFC3339).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_secret_rotation_time: Option<String>,
+
+    /// Time the most recent backup (OCI snapshot push Job) finished (RFC3339).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_backup_time: Option<String>,
+
+    /// Outcome of the most recent backup: `Succeeded` or `Failed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_backup_status: Option<String>,
+
+    /// Version the node's workload last finished rolling out and reached
+    /// `Ready` on; the version the `stellar.org/rollback` annotation reverts to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_good_version: Option<String>,
+
+    /// Versions the node's workload finished rolling out and reached `Ready`
+    /// on, oldest first, bounded to the most recent few.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub version_history: Vec<super::types::VersionHistoryEntry>,
 }
 
 /// BGP advertisement status information
@@ -1813,6 +2389,13 @@ impl StellarNodeStatus {
         has_ready_condition && self.ready_replicas >= self.replicas
     }
 
+    /// Check if the node is held at 0 replicas by `spec.suspended`
+    pub fn is_suspended(&self) -> bool {
+        self.conditions
+            .iter()
+            .any(|c| c.type_ == "Suspended" && c.status == "True")
+    }
+
     /// Check if the node is degraded
     pub fn is_degraded(&self) -> bool {
         self.conditions
@@ -1837,7 +2420,9 @@ impl StellarNodeStatus {
     /// This allows existing code to continue using phase while we transition
     /// to conditions-based status reporting
     pub fn derive_phase_from_conditions(&self) -> String {
-        if self.is_ready() {
+        if self.is_suspended() {
+            "Suspended".to_string()
+        } else if self.is_ready() {
             "Ready".to_string()
         } else if self.is_degraded() {
             "Degraded".to_string()
@@ -1850,6 +2435,7 @@ impl StellarNodeStatus {
                     match ready_cond.reason.as_str() {
                         "PodsPending" => "Pending".to_string(),
                         "Creating" => "Creating".to_string(),
+                        "Suspended" | "NodeSuspended" => "Suspended".to_string(),
                         _ => "NotReady".to_string(),
                     }
                 } else {
@@ -1884,6 +2470,7 @@ mod tests {
                     check_interval_seconds: 300,
                     ..Default::default()
                 }),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1911,6 +2498,7 @@ mod tests {
                     check_interval_seconds: 300,
                     ..Default::default()
                 }),
+                ..Default::default()
             },
             ..Default::default()
         };
@@ -1952,4 +2540,51 @@ mod tests {
         spec.version = "v2.10.0".to_string();
         assert_eq!(spec.container_image(), "stellar/horizon:v2.10.0");
     }
+
+    fn condition(type_: &str, status: &str, reason: &str) -> Condition {
+        Condition {
+            type_: type_.to_string(),
+            status: status.to_string(),
+            last_transition_time: "2026-01-01T00:00:00Z".to_string(),
+            reason: reason.to_string(),
+            message: String::new(),
+            observed_generation: None,
+        }
+    }
+
+    fn status_with(conditions: Vec<Condition>) -> StellarNodeStatus {
+        StellarNodeStatus {
+            conditions,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_suspended_condition_maps_to_suspended_phase() {
+        let status = status_with(vec![
+            condition("Ready", "False", "NodeNotHealthy"),
+            condition("Degraded", "True", "HealthCheckFailed"),
+            condition("Suspended", "True", "SuspendedBySpec"),
+        ]);
+        assert!(status.is_suspended());
+        assert_eq!(status.derive_phase_from_conditions(), "Suspended");
+    }
+
+    #[test]
+    fn test_suspended_ready_reason_maps_to_suspended_phase() {
+        for reason in ["Suspended", "NodeSuspended"] {
+            let status = status_with(vec![condition("Ready", "False", reason)]);
+            assert_eq!(status.derive_phase_from_conditions(), "Suspended");
+        }
+    }
+
+    #[test]
+    fn test_not_ready_without_suspension() {
+        let status = status_with(vec![
+            condition("Ready", "False", "NodeNotHealthy"),
+            condition("Suspended", "False", "SuspendedBySpec"),
+        ]);
+        assert!(!status.is_suspended());
+        assert_eq!(status.derive_phase_from_conditions(), "NotReady");
+    }
 }
@@ -0,0 +1,43 @@
+//! StellarNodeTemplate CRD for sharing a base spec between StellarNodes
+
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::schema_utils::object_schema;
+
+/// Reusable base spec that StellarNodes inherit through `spec.templateRef`
+///
+/// The template holds a partial `StellarNode` spec. Fields set on the node
+/// override the template; nested objects are merged key by key and lists are
+/// replaced as a whole. Fields every node carries because the schema requires
+/// or defaults them (`nodeType`, `network`, `version`, `replicas`,
+/// `resources`, `storage`, ...) cannot be set in a template.
+///
+/// ```yaml
+/// apiVersion: stellar.org/v1alpha1
+/// kind: StellarNodeTemplate
+/// metadata:
+///   name: mainnet-validator
+/// spec:
+///   template:
+///     priorityClassName: stellar-validator-critical
+///     validatorConfig:
+///       enableHistoryArchive: true
+///       historyArchiveUrls:
+///         - https://history.stellar.org/prd/core-live/core_live_001
+/// ```
+#[derive(CustomResource, Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[kube(
+    group = "stellar.org",
+    version = "v1alpha1",
+    kind = "StellarNodeTemplate",
+    namespaced,
+    shortname = "snt"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StellarNodeTemplateSpec {
+    /// Partial StellarNode spec used as the base for referencing nodes
+    #[schemars(schema_with = "object_schema")]
+    pub template: serde_json::Value,
+}
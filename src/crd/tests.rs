@@ -5,10 +5,13 @@
 
 #[cfg(test)]
 mod stellar_node_spec_validation {
+    use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+
     use crate::crd::{
-        AutoscalingConfig, HorizonConfig, IngressConfig, IngressHost, IngressPath, NodeType,
-        ResourceRequirements, ResourceSpec, SorobanConfig, SpecValidationError, StellarNetwork,
-        StellarNodeSpec, StorageConfig, ValidatorConfig,
+        AutoscalingConfig, BucketListDbConfig, BucketListStorage, HorizonConfig, IngressConfig,
+        IngressHost, IngressPath, ManagedDatabaseBackupConfig, ManagedDatabaseConfig, NodeType,
+        OtelCollectorConfig, PvcAccessMode, ResourceRequirements, ResourceSpec, SorobanConfig,
+        SpecValidationError, StellarNetwork, StellarNodeSpec, StorageConfig, ValidatorConfig,
     };
 
     /// Helper to create a minimal valid StellarNodeSpec for a Validator
@@ -209,6 +212,8 @@ mod stellar_node_spec_validation {
     fn default_storage() -> StorageConfig {
         StorageConfig {
             storage_class: "standard".to_string(),
+            tier: None,
+            access_modes: vec![],
             size: "100Gi".to_string(),
             retention_policy: Default::default(),
             annotations: None,
@@ -365,6 +370,60 @@ mod stellar_node_spec_validation {
         assert!(spec.validate().is_ok());
     }
 
+    #[test]
+    fn test_validator_access_modes_must_be_exclusive() {
+        let mut spec = valid_validator_spec();
+        spec.storage.access_modes = vec![PvcAccessMode::ReadWriteOncePod];
+        assert!(spec.validate().is_ok());
+
+        spec.storage.access_modes =
+            vec![PvcAccessMode::ReadWriteOnce, PvcAccessMode::ReadWriteMany];
+        let errors = spec.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "spec.storage.accessModes"));
+
+        // Shared pools may use RWX
+        let mut spec = valid_horizon_spec();
+        spec.storage.access_modes = vec![PvcAccessMode::ReadWriteMany];
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validator_bucket_list_db_options_validated() {
+        let mut spec = valid_validator_spec();
+        let vc = spec.validator_config.as_mut().unwrap();
+        vc.bucket_list_db = Some(BucketListDbConfig {
+            index_page_size_exponent: Some(14),
+            index_cutoff_mb: Some(20),
+            ..Default::default()
+        });
+        assert!(spec.validate().is_ok());
+
+        for db in [
+            BucketListDbConfig {
+                index_page_size_exponent: Some(2),
+                ..Default::default()
+            },
+            BucketListDbConfig {
+                index_page_size_exponent: Some(40),
+                ..Default::default()
+            },
+            BucketListDbConfig {
+                storage: BucketListStorage::InMemory,
+                index_page_size_exponent: Some(14),
+                ..Default::default()
+            },
+        ] {
+            spec.validator_config.as_mut().unwrap().bucket_list_db = Some(db.clone());
+            let errors = spec.validate().unwrap_err();
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.field == "spec.validatorConfig.bucketListDb"),
+                "{db:?}: {errors:?}"
+            );
+        }
+    }
+
     // =========================================================================
     // Horizon Node Tests
     // =========================================================================
@@ -399,6 +458,98 @@ mod stellar_node_spec_validation {
         assert!(spec.validate().is_ok());
     }
 
+    #[test]
+    fn test_horizon_admin_port_must_not_clash_with_http_port() {
+        let mut spec = valid_horizon_spec();
+        spec.horizon_config.as_mut().unwrap().admin_port = Some(6060);
+        assert!(spec.validate().is_ok());
+
+        for port in [0, 8000] {
+            spec.horizon_config.as_mut().unwrap().admin_port = Some(port);
+            let errors = spec.validate().unwrap_err();
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.field == "spec.horizonConfig.adminPort"),
+                "port {port}: {errors:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_enabled_otel_collector_needs_endpoint() {
+        let mut spec = valid_horizon_spec();
+        spec.otel_collector = Some(OtelCollectorConfig {
+            enabled: true,
+            endpoint: " ".to_string(),
+            insecure: false,
+            image: "otel/opentelemetry-collector-contrib:0.111.0".to_string(),
+        });
+        let errors = spec.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "spec.otelCollector.endpoint"));
+
+        spec.otel_collector.as_mut().unwrap().enabled = false;
+        assert!(spec.validate().is_ok());
+        spec.otel_collector.as_mut().unwrap().enabled = true;
+        spec.otel_collector.as_mut().unwrap().endpoint = "otel-gateway:4317".to_string();
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_rolling_update_params_cannot_both_be_zero() {
+        let mut spec = valid_horizon_spec();
+        spec.strategy.max_surge = Some(IntOrString::Int(0));
+        spec.strategy.max_unavailable = Some(IntOrString::String("0%".to_string()));
+        let errors = spec.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "spec.strategy" && e.message.contains("both be zero")));
+
+        spec.strategy.max_surge = Some(IntOrString::Int(1));
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_rolling_update_params_rejected_for_validator() {
+        let mut spec = valid_validator_spec();
+        spec.strategy.max_surge = Some(IntOrString::Int(1));
+        let errors = spec.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "spec.strategy"));
+    }
+
+    #[test]
+    fn test_min_ready_seconds_validation() {
+        let mut spec = valid_horizon_spec();
+        spec.strategy.min_ready_seconds = Some(30);
+        assert!(spec.validate().is_ok());
+
+        spec.strategy.min_ready_seconds = Some(-1);
+        let errors = spec.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "spec.strategy.minReadySeconds"));
+
+        let mut validator = valid_validator_spec();
+        validator.strategy.min_ready_seconds = Some(30);
+        assert!(validator.validate().is_err());
+    }
+
+    #[test]
+    fn test_progress_deadline_must_exceed_min_ready_seconds() {
+        let mut spec = valid_horizon_spec();
+        spec.strategy.min_ready_seconds = Some(30);
+        spec.strategy.progress_deadline_seconds = Some(300);
+        assert!(spec.validate().is_ok());
+
+        spec.strategy.progress_deadline_seconds = Some(30);
+        let errors = spec.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "spec.strategy.progressDeadlineSeconds"));
+    }
+
     #[test]
     fn test_horizon_valid_autoscaling_passes() {
         let mut spec = valid_horizon_spec();
@@ -893,6 +1044,285 @@ mod stellar_node_spec_validation {
             .any(|e| e.field == "spec.network.customName"));
     }
 
+    fn custom_volume(name: &str) -> k8s_openapi::api::core::v1::Volume {
+        k8s_openapi::api::core::v1::Volume {
+            name: name.to_string(),
+            config_map: Some(k8s_openapi::api::core::v1::ConfigMapVolumeSource {
+                name: Some("corporate-ca".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn custom_mount(name: &str, path: &str) -> k8s_openapi::api::core::v1::VolumeMount {
+        k8s_openapi::api::core::v1::VolumeMount {
+            name: name.to_string(),
+            mount_path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_extra_volumes_pass() {
+        let mut spec = valid_validator_spec();
+        spec.volumes = Some(vec![custom_volume("ca-bundle"), custom_volume("scripts")]);
+        spec.volume_mounts = Some(vec![
+            custom_mount("ca-bundle", "/etc/ssl/custom"),
+            custom_mount("scripts", "/opt/scripts"),
+        ]);
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_extra_volume_name_collision_fails() {
+        for name in [
+            "data",
+            "tls",
+            "stellar-logs",
+            "handoff-socket",
+            "otel-collector-config",
+            "history-archive",
+        ] {
+            let mut spec = valid_validator_spec();
+            spec.volumes = Some(vec![custom_volume(name)]);
+            let errors = spec.validate().unwrap_err();
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.field == "spec.volumes[].name"
+                        && e.message.contains("operator-managed")),
+                "Expected collision error for volume '{name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn test_extra_volume_mount_path_collision_fails() {
+        for path in [
+            "/config",
+            "/opt/stellar/data/",
+            "/etc/stellar/tls",
+            "/history-archive",
+        ] {
+            let mut spec = valid_validator_spec();
+            spec.volumes = Some(vec![custom_volume("ca-bundle")]);
+            spec.volume_mounts = Some(vec![custom_mount("ca-bundle", path)]);
+            let errors = spec.validate().unwrap_err();
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.field == "spec.volumeMounts[].mountPath"),
+                "Expected mountPath collision error for '{path}'"
+            );
+        }
+
+        let mut spec = valid_validator_spec();
+        spec.volumes = Some(vec![custom_volume("a"), custom_volume("b")]);
+        spec.volume_mounts = Some(vec![
+            custom_mount("a", "/etc/custom"),
+            custom_mount("b", "/etc/custom/"),
+        ]);
+        let errors = spec.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.message.contains("Duplicate mountPath")));
+    }
+
+    #[test]
+    fn test_extra_volumes_are_in_crd_schema() {
+        use kube::CustomResourceExt;
+        let crd = serde_json::to_value(crate::crd::StellarNode::crd()).unwrap();
+        let spec = &crd["spec"]["versions"][0]["schema"]["openAPIV3Schema"]["properties"]["spec"]
+            ["properties"];
+        for field in ["volumes", "volumeMounts"] {
+            assert_eq!(
+                spec[field]["items"]["x-kubernetes-preserve-unknown-fields"], true,
+                "{field} must be kept by the API server"
+            );
+        }
+    }
+
+    #[test]
+    fn test_existing_service_account_requires_name() {
+        let mut spec = valid_validator_spec();
+        spec.service_account = Some(crate::crd::ServiceAccountConfig {
+            create: false,
+            ..Default::default()
+        });
+        let errors = spec.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "spec.serviceAccount.name"));
+
+        spec.service_account = Some(crate::crd::ServiceAccountConfig {
+            create: false,
+            name: Some("shared".to_string()),
+            ..Default::default()
+        });
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_service_account_rules_are_limited_to_read_access() {
+        use k8s_openapi::api::rbac::v1::PolicyRule;
+
+        let rule = |group: &str, resource: &str, verb: &str| PolicyRule {
+            api_groups: Some(vec![group.to_string()]),
+            resources: Some(vec![resource.to_string()]),
+            verbs: vec![verb.to_string()],
+            ..Default::default()
+        };
+        let mut spec = valid_validator_spec();
+        spec.service_account = Some(crate::crd::ServiceAccountConfig {
+            rules: vec![rule("", "configmaps", "get")],
+            ..Default::default()
+        });
+        assert!(spec.validate().is_ok());
+
+        for denied in [
+            rule("", "configmaps", "update"),
+            rule("", "secrets", "get"),
+            rule("", "*", "get"),
+            rule("", "pods/exec", "get"),
+            rule("rbac.authorization.k8s.io", "roles", "get"),
+        ] {
+            spec.service_account = Some(crate::crd::ServiceAccountConfig {
+                rules: vec![rule("", "configmaps", "get"), denied],
+                ..Default::default()
+            });
+            let errors = spec.validate().unwrap_err();
+            assert!(errors
+                .iter()
+                .any(|e| e.field == "spec.serviceAccount.rules[1]"));
+        }
+    }
+
+    #[test]
+    fn test_canary_analysis_requires_prometheus_url() {
+        let check = crate::crd::CanaryMetricCheck {
+            name: "error-rate".to_string(),
+            query: "sum(rate(errors[5m]))".to_string(),
+            max: Some(0.01),
+            min: None,
+        };
+        let mut spec = valid_validator_spec();
+        spec.cve_handling = Some(crate::crd::CVEHandlingConfig {
+            canary_analysis: vec![check],
+            ..Default::default()
+        });
+        let errors = spec.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "spec.cveHandling.prometheusUrl"));
+
+        spec.cve_handling.as_mut().unwrap().prometheus_url =
+            Some("http://prometheus:9090".to_string());
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_valid_versions_pass() {
+        let digest = "a".repeat(64);
+        for version in [
+            "v21.0.0".to_string(),
+            "2.31.0".to_string(),
+            "v22.0.0-rc2".to_string(),
+            "21.3.1-2007.4ede19620.focal".to_string(),
+            format!("v21.0.0@sha256:{digest}"),
+            format!("sha256:{digest}"),
+        ] {
+            let mut spec = valid_validator_spec();
+            spec.version = version.clone();
+            assert!(spec.validate().is_ok(), "Expected '{version}' to be valid");
+        }
+    }
+
+    #[test]
+    fn test_command_and_args_overrides_are_valid() {
+        let mut spec = valid_validator_spec();
+        spec.command = Some(vec!["/usr/bin/stellar-core".to_string()]);
+        spec.args = Some(
+            [
+                "run",
+                "--conf",
+                "/config/stellar-core.cfg",
+                "--wait-for-consensus",
+            ]
+            .map(String::from)
+            .to_vec(),
+        );
+        assert!(spec.validate().is_ok());
+
+        spec.args = Some(vec!["--conf=/config/stellar-core.cfg".to_string()]);
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_empty_command_or_args_fail() {
+        let mut spec = valid_validator_spec();
+        spec.command = Some(vec![]);
+        let errors = spec.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "spec.command"));
+
+        let mut spec = valid_horizon_spec();
+        spec.args = Some(vec!["serve".to_string(), " ".to_string()]);
+        let errors = spec.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "spec.args"));
+
+        // An empty args list clears the image's CMD, which is allowed
+        let mut spec = valid_horizon_spec();
+        spec.args = Some(vec![]);
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validator_conf_must_be_managed_config() {
+        for args in [
+            vec!["run", "--conf", "/etc/stellar/custom.cfg"],
+            vec!["run", "--conf=/tmp/stellar-core.cfg"],
+            vec!["run", "--conf"],
+        ] {
+            let mut spec = valid_validator_spec();
+            spec.args = Some(args.iter().map(|a| a.to_string()).collect());
+            let errors = spec.validate().unwrap_err();
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.field == "spec.args" && e.message.contains("--conf")),
+                "Expected --conf error for {args:?}"
+            );
+        }
+
+        // Horizon has no stellar-core config to bypass
+        let mut spec = valid_horizon_spec();
+        spec.args = Some(vec!["--conf".to_string(), "/etc/other.cfg".to_string()]);
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_malformed_versions_fail() {
+        for version in [
+            "",
+            "latest",
+            "v21",
+            "v21.0",
+            "21.0.0 ",
+            "v21.0.0:extra",
+            "stellar/stellar-core:v21.0.0",
+            "sha256:abc123",
+            "v21.0.0@sha256:ABC",
+        ] {
+            let mut spec = valid_validator_spec();
+            spec.version = version.to_string();
+            let result = spec.validate();
+            assert!(
+                result
+                    .as_ref()
+                    .is_err_and(|errors| errors.iter().any(|e| e.field == "spec.version")),
+                "Expected spec.version error for '{version}'"
+            );
+        }
+    }
+
     #[test]
     fn test_non_custom_networks_skip_name_validation() {
         for network in [
@@ -1392,4 +1822,237 @@ mod stellar_node_spec_validation {
 
         assert!(deserialized_yaml.captive_core_structured_config.is_some());
     }
+
+    fn cross_cluster_spec(peers: &[(&str, &str)]) -> StellarNodeSpec {
+        use crate::crd::{CrossClusterConfig, CrossClusterMode, PeerClusterConfig};
+
+        let mut spec = valid_validator_spec();
+        spec.cross_cluster = Some(CrossClusterConfig {
+            enabled: true,
+            mode: CrossClusterMode::DirectIP,
+            peer_clusters: peers
+                .iter()
+                .map(|(cluster_id, endpoint)| PeerClusterConfig {
+                    cluster_id: cluster_id.to_string(),
+                    endpoint: endpoint.to_string(),
+                    latency_threshold_ms: None,
+                    region: None,
+                    priority: 100,
+                    port: None,
+                    enabled: true,
+                    kubeconfig_secret_ref: None,
+                    kubeconfig_secret_key: "kubeconfig".to_string(),
+                    target_namespace: None,
+                })
+                .collect(),
+            ..Default::default()
+        });
+        spec
+    }
+
+    #[test]
+    fn test_cross_cluster_unique_peer_cluster_ids_pass() {
+        let spec = cross_cluster_spec(&[("us-east", "10.0.0.1"), ("eu-west", "10.0.0.2")]);
+        assert!(spec.validate().is_ok());
+    }
+
+    #[test]
+    fn test_cross_cluster_duplicate_peer_cluster_id_fails() {
+        let spec = cross_cluster_spec(&[
+            ("us-east", "10.0.0.1"),
+            ("eu-west", "10.0.0.2"),
+            ("us-east", "10.0.0.3"),
+        ]);
+
+        let errors = spec.validate().unwrap_err();
+        let duplicates: Vec<_> = errors
+            .iter()
+            .filter(|e| e.message.contains("duplicates"))
+            .collect();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(
+            duplicates[0].field,
+            "spec.crossCluster.peerClusters[2].clusterId"
+        );
+        assert_eq!(
+            duplicates[0].message,
+            "crossCluster.peerClusters[].clusterId 'us-east' duplicates peerClusters[0]"
+        );
+    }
+
+    #[test]
+    fn test_cross_cluster_duplicate_detection_ignores_whitespace() {
+        let spec = cross_cluster_spec(&[("us-east", "10.0.0.1"), (" us-east ", "10.0.0.2")]);
+        let errors = spec.validate().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.field == "spec.crossCluster.peerClusters[1].clusterId"));
+    }
+
+    #[test]
+    fn test_cross_cluster_empty_endpoint_fails() {
+        let spec = cross_cluster_spec(&[("us-east", "  ")]);
+        let errors = spec.validate().unwrap_err();
+        assert!(errors.iter().any(|e| {
+            e == &SpecValidationError::new(
+                "spec.crossCluster.peerClusters[0].endpoint",
+                "crossCluster.peerClusters[].endpoint must not be empty",
+                "Set a non-empty endpoint URL for each entry in spec.crossCluster.peerClusters[].endpoint.",
+            )
+        }));
+    }
+
+    #[test]
+    fn test_cross_cluster_mad_threshold_must_be_positive() {
+        for (threshold, valid) in [(3.0, true), (0.5, true), (0.0, false), (-1.0, false)] {
+            let mut spec = cross_cluster_spec(&[("us-east", "10.0.0.1")]);
+            spec.cross_cluster.as_mut().unwrap().health_check = Some(
+                serde_json::from_value(serde_json::json!({
+                    "latencyMeasurement": { "outlierRejection": "mad", "madThreshold": threshold }
+                }))
+                .unwrap(),
+            );
+
+            let field = "spec.crossCluster.healthCheck.latencyMeasurement.madThreshold";
+            let rejected = spec
+                .validate()
+                .err()
+                .unwrap_or_default()
+                .iter()
+                .any(|e| e.field == field);
+            assert_eq!(rejected, !valid, "madThreshold {threshold}");
+        }
+    }
+
+    #[test]
+    fn test_cross_cluster_latency_timeout_must_be_positive() {
+        for (timeout_ms, valid) in [(5000, true), (1, true), (0, false)] {
+            let mut spec = cross_cluster_spec(&[("us-east", "10.0.0.1")]);
+            spec.cross_cluster.as_mut().unwrap().health_check = Some(
+                serde_json::from_value(serde_json::json!({
+                    "latencyMeasurement": { "timeoutMs": timeout_ms }
+                }))
+                .unwrap(),
+            );
+
+            let field = "spec.crossCluster.healthCheck.latencyMeasurement.timeoutMs";
+            let rejected = spec
+                .validate()
+                .err()
+                .unwrap_or_default()
+                .iter()
+                .any(|e| e.field == field);
+            assert_eq!(rejected, !valid, "timeoutMs {timeout_ms}");
+        }
+    }
+
+    #[test]
+    fn test_retention_delete_with_snapshot_schedule_warns() {
+        let mut spec = valid_validator_spec();
+        spec.storage.retention_policy = crate::crd::types::RetentionPolicy::Delete;
+        spec.snapshot_schedule = Some(Default::default());
+
+        let warning = spec.retention_backup_conflict().expect("warning expected");
+        assert!(warning.contains("retentionPolicy"));
+        assert!(
+            spec.validate().is_ok(),
+            "conflict is a warning, not an error"
+        );
+    }
+
+    #[test]
+    fn test_retention_retain_or_no_snapshots_does_not_warn() {
+        let mut spec = valid_validator_spec();
+        spec.storage.retention_policy = crate::crd::types::RetentionPolicy::Delete;
+        assert!(spec.retention_backup_conflict().is_none());
+
+        spec.snapshot_schedule = Some(Default::default());
+        spec.storage.retention_policy = crate::crd::types::RetentionPolicy::Retain;
+        assert!(spec.retention_backup_conflict().is_none());
+    }
+
+    fn backup_endpoint_spec(endpoint: &str) -> StellarNodeSpec {
+        let mut spec = valid_horizon_spec();
+        spec.managed_database = Some(ManagedDatabaseConfig {
+            instances: 3,
+            storage: Default::default(),
+            backup: Some(ManagedDatabaseBackupConfig {
+                enabled: true,
+                destination_path: "s3://stellar-wal/horizon".to_string(),
+                credentials_secret_ref: "wal-creds".to_string(),
+                retention_policy: "30d".to_string(),
+                endpoint_url: Some(endpoint.to_string()),
+            }),
+            pooling: None,
+            postgres_version: "16".to_string(),
+            database_name: None,
+            username: None,
+        });
+        spec
+    }
+
+    #[test]
+    fn test_backup_endpoint_url_valid_passes() {
+        for endpoint in ["https://minio.storage.svc:9000", "http://10.0.0.5:9000/"] {
+            assert!(
+                backup_endpoint_spec(endpoint).validate().is_ok(),
+                "{endpoint} should be accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn test_backup_endpoint_url_invalid_fails() {
+        for endpoint in [
+            "minio.storage.svc:9000",
+            "s3.amazonaws.com",
+            "ftp://minio",
+            "https://",
+        ] {
+            let errors = backup_endpoint_spec(endpoint).validate().unwrap_err();
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.field == "spec.managedDatabase.backup.endpointUrl"),
+                "{endpoint} should be rejected"
+            );
+        }
+    }
+
+    fn snapshot_schedule_spec(schedule: &str) -> StellarNodeSpec {
+        let mut spec = valid_validator_spec();
+        spec.snapshot_schedule = Some(crate::crd::SnapshotScheduleConfig {
+            schedule: Some(schedule.to_string()),
+            ..Default::default()
+        });
+        spec
+    }
+
+    #[test]
+    fn test_snapshot_schedule_five_field_cron_passes() {
+        assert!(snapshot_schedule_spec("0 2 * * *").validate().is_ok());
+        assert!(snapshot_schedule_spec("*/15 * * * 1-5").validate().is_ok());
+    }
+
+    #[test]
+    fn test_snapshot_schedule_wrong_format_fails() {
+        for schedule in ["0 0 2 * * *", "0 2 * *", "0 25 * * *"] {
+            let errors = snapshot_schedule_spec(schedule).validate().unwrap_err();
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| e.field == "spec.snapshotSchedule.schedule"),
+                "{schedule} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_unix_cron_runs_at_second_zero() {
+        use chrono::Timelike;
+
+        let schedule = crate::crd::parse_unix_cron("30 2 * * *").unwrap();
+        let next = schedule.upcoming(chrono::Utc).next().unwrap();
+        assert_eq!((next.hour(), next.minute(), next.second()), (2, 30, 0));
+    }
 }
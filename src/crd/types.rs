@@ -207,6 +207,44 @@ pub enum StorageMode {
     Local,
 }
 
+/// Class of disk a node's data volume should live on
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum StorageTier {
+    /// SSD-backed volumes (gp3, pd-ssd, Premium_LRS, ...)
+    Ssd,
+    /// Spinning-disk volumes (st1, pd-standard, Standard_LRS, ...)
+    Hdd,
+}
+
+/// Access mode of a node's data PVC
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub enum PvcAccessMode {
+    ReadWriteOnce,
+    ReadWriteOncePod,
+    ReadOnlyMany,
+    ReadWriteMany,
+}
+
+impl PvcAccessMode {
+    /// Whether the volume can only be mounted by one node (or pod)
+    pub fn is_exclusive(&self) -> bool {
+        matches!(self, Self::ReadWriteOnce | Self::ReadWriteOncePod)
+    }
+}
+
+impl std::fmt::Display for PvcAccessMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self {
+            Self::ReadWriteOnce => "ReadWriteOnce",
+            Self::ReadWriteOncePod => "ReadWriteOncePod",
+            Self::ReadOnlyMany => "ReadOnlyMany",
+            Self::ReadWriteMany => "ReadWriteMany",
+        };
+        f.write_str(mode)
+    }
+}
+
 /// Reference to a pre-computed snapshot used to bootstrap a new node.
 ///
 /// Supports two bootstrap mechanisms:
@@ -263,6 +301,16 @@ pub struct StorageConfig {
     #[serde(default)]
     pub mode: StorageMode,
     pub storage_class: String,
+    /// Disk class the data volume must be on. With an empty `storageClass`
+    /// the operator picks a StorageClass of this tier; otherwise it checks
+    /// the given class matches. Validators are expected on `SSD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<StorageTier>,
+    /// Access modes of the data PVC (default `[ReadWriteOnce]`). Pools of
+    /// Horizon or Soroban RPC replicas sharing a read-only dataset can use
+    /// `ReadWriteMany` or `ReadOnlyMany`; validators must stay exclusive.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub access_modes: Vec<PvcAccessMode>,
     pub size: String,
     #[serde(default)]
     pub retention_policy: RetentionPolicy,
@@ -285,11 +333,23 @@ pub struct StorageConfig {
     pub snapshot_ref: Option<SnapshotRef>,
 }
 
+impl StorageConfig {
+    /// Access modes to request on the data PVC
+    pub fn pvc_access_modes(&self) -> Vec<String> {
+        if self.access_modes.is_empty() {
+            return vec![PvcAccessMode::ReadWriteOnce.to_string()];
+        }
+        self.access_modes.iter().map(ToString::to_string).collect()
+    }
+}
+
 impl Default for StorageConfig {
     fn default() -> Self {
         Self {
             mode: StorageMode::default(),
             storage_class: "standard".to_string(),
+            tier: None,
+            access_modes: Vec::new(),
             size: "100Gi".to_string(),
             retention_policy: RetentionPolicy::default(),
             annotations: None,
@@ -477,7 +537,8 @@ fn default_issuer_group() -> String {
 #[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct SnapshotScheduleConfig {
-    /// Cron expression for scheduled snapshots (e.g. "0 2 * * *" for daily at 2 AM).
+    /// Five-field Unix cron expression (minute hour day-of-month month day-of-week)
+    /// for scheduled snapshots, e.g. "0 2 * * *" for daily at 2 AM UTC.
     /// If unset, snapshots are only taken when triggered via annotation `stellar.org/request-snapshot: "true"`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schedule: Option<String>,
@@ -496,6 +557,21 @@ pub struct SnapshotScheduleConfig {
     pub encryption_key_ref: Option<String>,
 }
 
+/// Parse a five-field Unix cron expression (as used by Kubernetes CronJobs).
+///
+/// The `cron` crate expects a leading seconds field, so the expression is
+/// run at second 0 of each matching minute.
+pub fn parse_unix_cron(expr: &str) -> Result<cron::Schedule, String> {
+    let fields = expr.split_whitespace().count();
+    if fields != 5 {
+        return Err(format!(
+            "schedule '{expr}' has {fields} field(s); expected 5 (minute hour day-of-month month day-of-week)"
+        ));
+    }
+    <cron::Schedule as std::str::FromStr>::from_str(&format!("0 {}", expr.trim()))
+        .map_err(|e| format!("schedule '{expr}' is not a valid cron expression: {e}"))
+}
+
 /// Configuration to bootstrap a new node from an existing CSI VolumeSnapshot
 ///
 /// When set, the node's PVC is created from the specified VolumeSnapshot instead of
@@ -584,7 +660,8 @@ pub struct StellarSecurityContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub run_as_group: Option<i64>,
 
-    /// fsGroup for the pod's volume ownership. Defaults to `10000`.
+    /// fsGroup for the pod's volume ownership, so the non-root process can
+    /// write its data volume. Defaults to `10000`, the stellar user's GID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fs_group: Option<i64>,
 
@@ -677,6 +754,46 @@ fn default_batch_size_lines() -> u32 {
 fn default_flush_interval_secs() -> u64 {
     60
 }
+
+/// Image of the OpenTelemetry Collector sidecar
+pub const DEFAULT_OTEL_COLLECTOR_IMAGE: &str = "otel/opentelemetry-collector-contrib:0.111.0";
+
+/// OpenTelemetry Collector sidecar forwarding the node's traces over OTLP.
+///
+/// The collector receives OTLP on `localhost:4317` (gRPC) and `:4318`
+/// (HTTP), which the main container finds in `OTEL_EXPORTER_OTLP_ENDPOINT`,
+/// and redacts the same span attributes as the operator's own exporter
+/// before forwarding.
+///
+/// # Example
+/// ```yaml
+/// otelCollector:
+///   enabled: true
+///   endpoint: "otel-gateway.observability.svc:4317"
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OtelCollectorConfig {
+    /// Inject the collector sidecar.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// OTLP gRPC endpoint the collector forwards to (`host:port`).
+    pub endpoint: String,
+
+    /// Forward without TLS, e.g. to an in-cluster gateway.
+    #[serde(default)]
+    pub insecure: bool,
+
+    /// Image of the collector; must include the `attributes` and `resource`
+    /// processors, as the contrib distribution does.
+    #[serde(default = "default_otel_collector_image")]
+    pub image: String,
+}
+
+fn default_otel_collector_image() -> String {
+    DEFAULT_OTEL_COLLECTOR_IMAGE.to_string()
+}
 /// Observed sync state of a Stellar Core node, derived from the `/info` HTTP endpoint.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum CoreSyncState {
@@ -699,6 +816,38 @@ impl std::fmt::Display for CoreSyncState {
     }
 }
 
+/// Consensus state of a validator, from the stellar-core `/info` endpoint
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsensusHealth {
+    /// Whether stellar-core reports `Synced!`
+    pub in_sync: bool,
+    /// Raw stellar-core state, e.g. `Synced!`, `Catching up`, `Joining SCP`
+    pub state: String,
+    /// Ledger the node is tracking consensus on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracking_ledger: Option<u64>,
+    /// When `trackingLedger` was first observed at its current value (RFC3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracking_ledger_since: Option<String>,
+    /// Result of stellar-core's last transitive quorum intersection check;
+    /// unset until it has run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quorum_intersection_ok: Option<bool>,
+    /// Quorum set members that agreed on the last ledger
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quorum_agree: Option<u32>,
+    /// Quorum set members that disagreed on the last ledger
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quorum_disagree: Option<u32>,
+    /// Quorum set members that were not heard from on the last ledger
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quorum_missing: Option<u32>,
+    /// How many more quorum set members can fail before the node loses quorum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quorum_fail_at: Option<u32>,
+}
+
 /// Resource profile applied during a specific sync phase.
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -814,6 +963,21 @@ pub struct ValidatorConfig {
     /// History archive URLs to fetch from
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub history_archive_urls: Vec<String>,
+    /// Maximum number of ledgers a history archive may lag behind this node
+    /// before it is reported as degraded (defaults to 20).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_lag_threshold: Option<u64>,
+    /// Automatic rotation of the primary history archive when it stays behind
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_rotation: Option<ArchiveRotationConfig>,
+    /// BucketListDB tuning (index layout and caching), which drives most of
+    /// stellar-core's memory and disk use
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket_list_db: Option<BucketListDbConfig>,
+    /// Publish history to a shared volume served over HTTP by an nginx
+    /// sidecar, so other nodes can fetch from it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_server: Option<ArchiveServerConfig>,
     /// Node is in catchup mode (syncing historical data)
     #[serde(default)]
     pub catchup_complete: bool,
@@ -837,6 +1001,173 @@ pub struct ValidatorConfig {
     pub external_dns: Option<ExternalDNSConfig>,
 }
 
+/// Automatic history archive rotation configuration
+///
+/// The first entry of `historyArchiveUrls` is the primary archive. When it fails
+/// `consecutiveChecks` integrity checks in a row, the operator moves the healthiest
+/// configured archive to the front of the list, which regenerates the config and
+/// rolls the validator pods.
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveRotationConfig {
+    /// Enable automatic rotation of the primary archive
+    #[serde(default)]
+    pub enabled: bool,
+    /// Consecutive lagging integrity checks before the primary is rotated (default: 3)
+    #[serde(default = "default_archive_rotation_checks")]
+    pub consecutive_checks: u32,
+}
+
+/// nginx image serving a validator's history archive
+pub const DEFAULT_ARCHIVE_SERVER_IMAGE: &str = "nginxinc/nginx-unprivileged:1.27-alpine";
+
+/// History archive volume published by a validator and served over HTTP
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveServerConfig {
+    /// Size of the archive PVC (default: 100Gi)
+    #[serde(default = "default_archive_server_size")]
+    pub size: String,
+    /// StorageClass of the archive PVC (cluster default when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_class: Option<String>,
+    /// Image of the nginx sidecar; must run as non-root and listen on 8080
+    #[serde(default = "default_archive_server_image")]
+    pub image: String,
+}
+
+fn default_archive_server_size() -> String {
+    "100Gi".to_string()
+}
+
+fn default_archive_server_image() -> String {
+    DEFAULT_ARCHIVE_SERVER_IMAGE.to_string()
+}
+
+impl Default for ArchiveServerConfig {
+    fn default() -> Self {
+        Self {
+            size: default_archive_server_size(),
+            storage_class: None,
+            image: default_archive_server_image(),
+        }
+    }
+}
+
+/// Where BucketListDB keeps the index of the bucket list
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub enum BucketListStorage {
+    /// Page-based indexes on disk; entries are read from the bucket files
+    #[default]
+    OnDisk,
+    /// An index entry per ledger entry, all held in memory; faster lookups
+    /// at the cost of several GB of RAM on pubnet
+    InMemory,
+}
+
+/// BucketListDB settings rendered into stellar-core.cfg
+///
+/// Unset fields keep stellar-core's defaults.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketListDbConfig {
+    /// Index layout: `OnDisk` (default) or `InMemory`
+    #[serde(default)]
+    pub storage: BucketListStorage,
+    /// Index pages are 2^N bytes (`BUCKETLIST_DB_INDEX_PAGE_SIZE_EXPONENT`);
+    /// larger pages use less memory but read more from disk per lookup.
+    /// Only valid with `OnDisk` storage, between 4 and 31.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_page_size_exponent: Option<u32>,
+    /// Buckets smaller than this many MB are fully indexed in memory
+    /// (`BUCKETLIST_DB_INDEX_CUTOFF`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_cutoff_mb: Option<u32>,
+    /// Persist indexes to disk so restarts don't rebuild them
+    /// (`BUCKETLIST_DB_PERSIST_INDEX`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persist_index: Option<bool>,
+    /// Memory in MB for caching ledger entries
+    /// (`BUCKETLIST_DB_MEMORY_FOR_CACHING`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_for_caching_mb: Option<u32>,
+}
+
+impl BucketListDbConfig {
+    /// Check the settings are consistent.
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.storage, self.index_page_size_exponent) {
+            (BucketListStorage::InMemory, Some(_)) => Err(
+                "indexPageSizeExponent cannot be set with InMemory storage, which indexes \
+                 every entry"
+                    .to_string(),
+            ),
+            (BucketListStorage::OnDisk, Some(exp)) if !(4..=31).contains(&exp) => Err(format!(
+                "indexPageSizeExponent must be between 4 and 31, got {exp}"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// stellar-core.cfg keys for these settings, one per line
+    pub fn to_stellar_core_toml(&self) -> String {
+        let mut toml = String::new();
+        let page_size_exponent = match self.storage {
+            // An exponent of 0 asks for an individual index per entry
+            BucketListStorage::InMemory => Some(0),
+            BucketListStorage::OnDisk => self.index_page_size_exponent,
+        };
+        if let Some(exp) = page_size_exponent {
+            toml.push_str(&format!("BUCKETLIST_DB_INDEX_PAGE_SIZE_EXPONENT={exp}\n"));
+        }
+        if let Some(cutoff) = self.index_cutoff_mb {
+            toml.push_str(&format!("BUCKETLIST_DB_INDEX_CUTOFF={cutoff}\n"));
+        }
+        if let Some(persist) = self.persist_index {
+            toml.push_str(&format!("BUCKETLIST_DB_PERSIST_INDEX={persist}\n"));
+        }
+        if let Some(memory) = self.memory_for_caching_mb {
+            toml.push_str(&format!("BUCKETLIST_DB_MEMORY_FOR_CACHING={memory}\n"));
+        }
+        toml
+    }
+}
+
+fn default_archive_rotation_checks() -> u32 {
+    3
+}
+
+impl Default for ArchiveRotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            consecutive_checks: default_archive_rotation_checks(),
+        }
+    }
+}
+
+/// Consecutive lagging integrity checks observed for a history archive URL
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveLagStrike {
+    /// History archive URL
+    pub url: String,
+    /// Number of integrity checks in a row that found this archive lagging or unreachable
+    pub consecutive_lagging_checks: u32,
+}
+
+/// A version the node reached `Ready` on
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionHistoryEntry {
+    /// `spec.version` the node became Ready on
+    pub version: String,
+    /// When the version was first seen Ready (RFC3339)
+    pub timestamp: String,
+    /// How the node got there: `Initial`, `Upgrade`, `Downgrade` or `Change`
+    pub reason: String,
+}
+
 /// Quorum set optimization configuration
 #[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -957,6 +1288,23 @@ pub struct HorizonConfig {
     pub enable_experimental_ingestion: bool,
     #[serde(default = "default_true")]
     pub auto_migration: bool,
+    /// Maximum number of attempts the migration init container makes before
+    /// giving up (defaults to 5).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub migration_max_attempts: Option<u32>,
+    /// Initial delay in seconds between migration attempts; doubled after every
+    /// failure and capped at 300 seconds (defaults to 5).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub migration_backoff_seconds: Option<u32>,
+    /// Port of Horizon's admin server (pprof and Prometheus metrics). When
+    /// set, Horizon listens on it and it is exposed on the container and a
+    /// separate ClusterIP Service `<name>-admin`, never on the public Service.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_port: Option<u16>,
+    /// Requests allowed per client IP per hour; `0` disables rate limiting.
+    /// Horizon's own default (3600) applies when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_hour_rate_limit: Option<u32>,
 }
 
 fn default_true() -> bool {
@@ -1311,6 +1659,27 @@ pub struct RolloutStrategy {
     pub strategy_type: RolloutStrategyType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canary: Option<CanaryConfig>,
+    /// Pods a rolling update of a Horizon or Soroban RPC Deployment may create
+    /// above the desired replicas, as a number or a percentage (Kubernetes
+    /// defaults to 25%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "super::schema_utils::int_or_string_schema")]
+    pub max_surge: Option<k8s_openapi::apimachinery::pkg::util::intstr::IntOrString>,
+    /// Pods of a Horizon or Soroban RPC Deployment that may be unavailable
+    /// during a rolling update, as a number or a percentage (Kubernetes
+    /// defaults to 25%)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "super::schema_utils::int_or_string_schema")]
+    pub max_unavailable: Option<k8s_openapi::apimachinery::pkg::util::intstr::IntOrString>,
+    /// Seconds a new Horizon or Soroban RPC pod must stay Ready before it
+    /// counts as available and an old pod is removed; damps flapping rollouts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_ready_seconds: Option<i32>,
+    /// Seconds a Horizon or Soroban RPC rollout may go without progress
+    /// before the Deployment reports it stuck and the node turns Degraded
+    /// (Kubernetes defaults to 600)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress_deadline_seconds: Option<i32>,
 }
 
 impl RolloutStrategy {
@@ -1988,6 +2357,11 @@ pub struct CrossClusterConfig {
     pub latency_threshold_ms: u32,
     #[serde(default)]
     pub auto_discovery: bool,
+    /// ID of this cluster in the clusterset (Submariner's cluster ID).
+    /// Required by `autoDiscovery` to leave the node's own cluster out of the
+    /// discovered peers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub health_check: Option<CrossClusterHealthCheck>,
 }
@@ -2007,6 +2381,7 @@ impl Default for CrossClusterConfig {
             peer_clusters: Vec::new(),
             latency_threshold_ms: default_latency_threshold(),
             auto_discovery: false,
+            cluster_id: None,
             health_check: None,
         }
     }
@@ -2097,6 +2472,10 @@ pub struct ExternalNameConfig {
     pub ttl: u32,
     #[serde(default = "default_true")]
     pub create_external_name_services: bool,
+    /// Point peer services at the first resolved IPs instead of the DNS name.
+    /// Pinned endpoints are not re-resolved until the peer endpoint changes.
+    #[serde(default)]
+    pub pin_to_ip: bool,
 }
 
 /// Peer cluster configuration
@@ -2126,6 +2505,27 @@ pub struct PeerClusterConfig {
     pub target_namespace: Option<String>,
 }
 
+/// Last DNS resolution of a peer cluster endpoint
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerEndpointResolution {
+    pub cluster_id: String,
+    /// Endpoint as configured in `peerClusters[].endpoint`
+    pub endpoint: String,
+    /// Addresses the endpoint resolved to, sorted
+    #[serde(default)]
+    pub resolved_ips: Vec<String>,
+    /// RFC3339 timestamp of the last successful resolution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<String>,
+    /// Whether the peer service is pinned to `resolvedIps`
+    #[serde(default)]
+    pub pinned: bool,
+    /// Error from the most recent resolution attempt, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
 fn default_kubeconfig_key() -> String {
     "kubeconfig".to_string()
 }
@@ -2180,6 +2580,21 @@ pub struct LatencyMeasurementConfig {
     pub sample_count: u32,
     #[serde(default = "default_latency_percentile")]
     pub percentile: u8,
+    /// Timeout for a single latency probe in milliseconds
+    #[serde(default = "default_latency_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Retries per sample after a retriable network failure
+    #[serde(default = "default_latency_max_retries")]
+    pub max_retries: u32,
+    /// Outlier rejection applied to samples before the percentile is taken
+    #[serde(default)]
+    pub outlier_rejection: LatencyOutlierRejection,
+    /// Samples further than this many MADs from the median are dropped (`mad` only)
+    #[serde(default = "default_latency_mad_threshold")]
+    pub mad_threshold: f64,
+    /// Percentage of samples trimmed from each end (`trim` only)
+    #[serde(default = "default_latency_trim_percent")]
+    pub trim_percent: u8,
 }
 
 fn default_latency_samples() -> u32 {
@@ -2190,6 +2605,35 @@ fn default_latency_percentile() -> u8 {
     95
 }
 
+fn default_latency_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_latency_max_retries() -> u32 {
+    2
+}
+
+fn default_latency_mad_threshold() -> f64 {
+    3.0
+}
+
+fn default_latency_trim_percent() -> u8 {
+    10
+}
+
+/// Outlier rejection for latency samples
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LatencyOutlierRejection {
+    /// Use every sample
+    #[default]
+    None,
+    /// Drop samples far from the median, measured in median absolute deviations
+    Mad,
+    /// Drop a fixed percentage of the lowest and highest samples
+    Trim,
+}
+
 /// Method for measuring cross-cluster latency
 #[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -2221,6 +2665,44 @@ pub struct CVEHandlingConfig {
     pub enable_auto_rollback: bool,
     #[serde(default = "default_health_threshold")]
     pub consensus_health_threshold: f64,
+    /// Prometheus queried for the `canaryAnalysis` checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prometheus_url: Option<String>,
+    /// Metric checks the canary must pass, on top of its smoke tests, before
+    /// the patched version is rolled out
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub canary_analysis: Vec<CanaryMetricCheck>,
+    /// Seconds the canary is kept after the rollout completes or is rolled
+    /// back, for inspection, before it is deleted
+    #[serde(default = "default_canary_cleanup_grace")]
+    pub canary_cleanup_grace_secs: u64,
+}
+
+/// A metric the CVE canary must keep within bounds before promotion
+///
+/// ```yaml
+/// canaryAnalysis:
+///   - name: error-rate
+///     query: sum(rate(http_requests_total{pod=~"{canary}-.*",code=~"5.."}[5m])) / sum(rate(http_requests_total{pod=~"{canary}-.*"}[5m]))
+///     max: 0.01
+///   - name: p99-latency
+///     query: histogram_quantile(0.99, sum by (le) (rate(http_request_duration_seconds_bucket{namespace="{namespace}",pod=~"{canary}-.*"}[5m])))
+///     max: 0.5
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CanaryMetricCheck {
+    /// Name reported when the check fails
+    pub name: String,
+    /// PromQL instant query; `{canary}` and `{namespace}` are replaced with
+    /// the canary Deployment's name and namespace
+    pub query: String,
+    /// Highest acceptable value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    /// Lowest acceptable value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
 }
 
 fn default_cve_enabled() -> bool {
@@ -2239,6 +2721,10 @@ fn default_canary_pass_rate() -> f64 {
     100.0
 }
 
+fn default_canary_cleanup_grace() -> u64 {
+    300
+}
+
 fn default_enable_rollback() -> bool {
     true
 }
@@ -2257,6 +2743,9 @@ impl Default for CVEHandlingConfig {
             canary_pass_rate_threshold: 100.0,
             enable_auto_rollback: true,
             consensus_health_threshold: 0.95,
+            prometheus_url: None,
+            canary_analysis: Vec::new(),
+            canary_cleanup_grace_secs: 300,
         }
     }
 }
@@ -2302,6 +2791,10 @@ pub struct ManagedDatabaseBackupConfig {
     pub credentials_secret_ref: String,
     #[serde(default = "default_retention")]
     pub retention_policy: String,
+    /// Endpoint of an S3-compatible object store (e.g. MinIO), such as
+    /// `https://minio.storage.svc:9000`. Unset means AWS S3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint_url: Option<String>,
 }
 
 fn default_retention() -> String {
@@ -2552,6 +3045,46 @@ pub struct OciSnapshotConfig {
     /// from `registry`, `image`, and `tag_strategy`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pull_image_ref: Option<String>,
+
+    /// Number of succeeded push Jobs to keep; older ones are deleted (default: 3)
+    #[serde(default = "default_successful_jobs_history_limit")]
+    pub successful_jobs_history_limit: u32,
+
+    /// Number of failed push Jobs to keep for debugging (default: 1)
+    #[serde(default = "default_failed_jobs_history_limit")]
+    pub failed_jobs_history_limit: u32,
+
+    /// Maximum run time of a push or pull Job in seconds before Kubernetes
+    /// terminates it. Unset means no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_deadline_seconds: Option<i64>,
+
+    /// Resource requests/limits for the push and pull Job containers.
+    /// Defaults to 250m/512Mi requested and 1 CPU/2Gi limit, enough to tar and
+    /// stream a ledger archive without being OOMKilled.
+    #[serde(default = "default_snapshot_job_resources")]
+    pub resources: ResourceRequirements,
+}
+
+fn default_snapshot_job_resources() -> ResourceRequirements {
+    ResourceRequirements {
+        requests: ResourceSpec {
+            cpu: "250m".to_string(),
+            memory: "512Mi".to_string(),
+        },
+        limits: ResourceSpec {
+            cpu: "1".to_string(),
+            memory: "2Gi".to_string(),
+        },
+    }
+}
+
+fn default_successful_jobs_history_limit() -> u32 {
+    3
+}
+
+fn default_failed_jobs_history_limit() -> u32 {
+    1
 }
 
 // ============================================================================
@@ -3063,6 +3596,95 @@ pub enum OperatorRole {
     Viewer,
 }
 
+/// Dedicated ServiceAccount for the pods of a StellarNode
+///
+/// Gives each node its own identity, e.g. for IRSA or GKE Workload Identity,
+/// instead of the namespace's `default` ServiceAccount.
+///
+/// ```yaml
+/// serviceAccount:
+///   annotations:
+///     eks.amazonaws.com/role-arn: arn:aws:iam::123456789012:role/stellar-validator
+///   rules:
+///     - apiGroups: [""]
+///       resources: ["configmaps"]
+///       resourceNames: ["validator-1-config"]
+///       verbs: ["get"]
+/// ```
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccountConfig {
+    /// Create the ServiceAccount; when false, `name` must refer to an
+    /// existing one
+    #[serde(default = "default_true")]
+    pub create: bool,
+
+    /// ServiceAccount name; defaults to the StellarNode name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Annotations on the created ServiceAccount, e.g. cloud workload identity bindings
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+
+    /// Mount the ServiceAccount token into the pods; the cluster default
+    /// applies when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub automount_token: Option<bool>,
+
+    /// Namespaced permissions granted to the ServiceAccount only, through a
+    /// Role and RoleBinding owned by the node. Limited to read access
+    /// ([`SERVICE_ACCOUNT_RULE_VERBS`]) on a few core resources
+    /// ([`SERVICE_ACCOUNT_RULE_RESOURCES`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(schema_with = "super::schema_utils::array_of_objects_schema")]
+    pub rules: Vec<k8s_openapi::api::rbac::v1::PolicyRule>,
+}
+
+/// Verbs `serviceAccount.rules` may grant
+pub const SERVICE_ACCOUNT_RULE_VERBS: &[&str] = &["get", "list", "watch"];
+
+/// Core API group resources `serviceAccount.rules` may grant access to
+pub const SERVICE_ACCOUNT_RULE_RESOURCES: &[&str] =
+    &["configmaps", "endpoints", "pods", "services"];
+
+impl ServiceAccountConfig {
+    /// Whether `rule` stays within the read-only allowlist, so a StellarNode
+    /// cannot use the operator's RBAC permissions to grant itself write access
+    pub fn rule_is_allowed(rule: &k8s_openapi::api::rbac::v1::PolicyRule) -> bool {
+        let api_groups = rule.api_groups.as_deref().unwrap_or_default();
+        let resources = rule.resources.as_deref().unwrap_or_default();
+        !api_groups.is_empty()
+            && api_groups.iter().all(|group| group.is_empty())
+            && !resources.is_empty()
+            && resources
+                .iter()
+                .all(|r| SERVICE_ACCOUNT_RULE_RESOURCES.contains(&r.as_str()))
+            && !rule.verbs.is_empty()
+            && rule
+                .verbs
+                .iter()
+                .all(|v| SERVICE_ACCOUNT_RULE_VERBS.contains(&v.as_str()))
+            && rule
+                .non_resource_urls
+                .as_deref()
+                .unwrap_or_default()
+                .is_empty()
+    }
+}
+
+impl Default for ServiceAccountConfig {
+    fn default() -> Self {
+        Self {
+            create: true,
+            name: None,
+            annotations: BTreeMap::new(),
+            automount_token: None,
+            rules: Vec::new(),
+        }
+    }
+}
+
 /// RBAC configuration for operator-level permissions
 #[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -140,7 +140,10 @@ impl Error {
     pub fn is_retriable(&self) -> bool {
         matches!(
             self,
-            Error::KubeError(_) | Error::FinalizerError(_) | Error::RemediationError(_)
+            Error::KubeError(_)
+                | Error::FinalizerError(_)
+                | Error::RemediationError(_)
+                | Error::NetworkError(_)
         )
     }
 
@@ -244,6 +247,12 @@ mod tests {
         assert!(our_err.is_retriable());
     }
 
+    #[test]
+    fn test_network_error_is_retriable() {
+        assert!(Error::NetworkError("TCP connect timeout".to_string()).is_retriable());
+        assert!(!Error::ConfigError("bad".to_string()).is_retriable());
+    }
+
     #[test]
     fn test_kube_error_status_message() {
         // Test that KubeError status_message includes error code and description
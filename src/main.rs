@@ -108,7 +108,11 @@ async fn main() -> Result<(), Error> {
                 eprintln!("error: {e}");
                 process::exit(2);
             }
-            return run_operator(run_args).await;
+            if let Err(e) = run_operator(run_args).await {
+                eprintln!("error: {e}");
+                process::exit(1);
+            }
+            return Ok(());
         }
         Commands::Webhook(webhook_args) => return run_webhook(webhook_args).await,
         Commands::Doctor(doctor_args) => return run_doctor(doctor_args).await,
@@ -138,6 +138,7 @@ impl From<&Condition> for ConditionDisplay {
             "Synced" if c.status == "True" => ConditionSeverity::Success,
             "Synced" if c.status == "False" => ConditionSeverity::Warning,
             "ArchiveIntegrityDegraded" if c.status == "True" => ConditionSeverity::Warning,
+            "DataRetentionRisk" if c.status == "True" => ConditionSeverity::Warning,
             _ => ConditionSeverity::Info,
         };
 
@@ -95,3 +95,15 @@ pub struct LogLevelResponse {
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
     pub message: String,
 }
+
+/// Response for a rolling restart request
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeRestartResponse {
+    pub name: String,
+    pub namespace: String,
+    /// Kind of the workload that was restarted (`StatefulSet` or `Deployment`)
+    pub workload_kind: String,
+    /// RFC3339 timestamp written to the `stellar.org/restarted-at` annotation
+    pub restarted_at: String,
+}
@@ -8,18 +8,26 @@ use axum::{
     Json,
 };
 use chrono::{Duration, Utc};
-use kube::{api::Api, ResourceExt};
-use tracing::{error, instrument};
+use k8s_openapi::api::apps::v1::{Deployment, StatefulSet};
+use kube::{
+    api::{Api, Patch, PatchParams},
+    ResourceExt,
+};
+use tracing::{error, info, instrument};
 
+use crate::controller::pod_restart::restart_patch;
+use crate::controller::resources::workload_kind;
 use crate::controller::{AdminAction, AuditEntry, ControllerState};
-use crate::crd::StellarNode;
+use crate::crd::{NodeType, StellarNode};
 use crate::rest_api::auth::RequestIdentity;
 
 use super::dto::{
     ErrorResponse, HealthResponse, LeaderResponse, LogLevelRequest, LogLevelResponse,
-    NodeDetailResponse, NodeListResponse, NodeSummary, ProbeResponse,
+    NodeDetailResponse, NodeListResponse, NodeRestartResponse, NodeSummary, ProbeResponse,
 };
 
+pub use crate::controller::pod_restart::RESTARTED_AT_ANNOTATION;
+
 /// Get the documentation search index
 #[instrument]
 pub async fn get_search_index() -> axum::response::Response {
@@ -136,6 +144,117 @@ pub async fn get_node(
     }
 }
 
+/// Trigger a rolling restart of a StellarNode's StatefulSet or Deployment
+#[instrument(skip(state, identity), fields(node_name = %name, namespace = %namespace, reconcile_id = "-"))]
+pub async fn restart_node(
+    State(state): State<Arc<ControllerState>>,
+    Path((namespace, name)): Path<(String, String)>,
+    Extension(identity): Extension<RequestIdentity>,
+) -> Result<Json<NodeRestartResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let api: Api<StellarNode> = Api::namespaced(state.client.clone(), &namespace);
+
+    let node = match api.get(&name).await {
+        Ok(node) => node,
+        Err(kube::Error::Api(e)) if e.code == 404 => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::new(
+                    "not_found",
+                    &format!("Node {namespace}/{name} not found"),
+                )),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to get node {}/{}: {:?}", namespace, name, e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new("get_failed", &e.to_string())),
+            ));
+        }
+    };
+
+    if node.spec.suspended {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ErrorResponse::new(
+                "node_suspended",
+                &format!("Node {namespace}/{name} is suspended and has no pods to restart"),
+            )),
+        ));
+    }
+
+    let restarted_at = Utc::now().to_rfc3339();
+    let patch = restart_patch(&restarted_at);
+    let params = PatchParams::default();
+    let kind = workload_kind(&node.spec.node_type);
+
+    let result = match node.spec.node_type {
+        NodeType::Validator => {
+            let workloads: Api<StatefulSet> = Api::namespaced(state.client.clone(), &namespace);
+            workloads
+                .patch(&name, &params, &Patch::Merge(&patch))
+                .await
+                .map(|_| ())
+        }
+        NodeType::Horizon | NodeType::SorobanRpc => {
+            let workloads: Api<Deployment> = Api::namespaced(state.client.clone(), &namespace);
+            workloads
+                .patch(&name, &params, &Patch::Merge(&patch))
+                .await
+                .map(|_| ())
+        }
+    };
+
+    match result {
+        Ok(()) => {}
+        Err(kube::Error::Api(e)) if e.code == 404 => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse::new(
+                    "workload_not_found",
+                    &format!("{kind} {namespace}/{name} not found"),
+                )),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to restart {} {}/{}: {:?}", kind, namespace, name, e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse::new("restart_failed", &e.to_string())),
+            ));
+        }
+    }
+
+    info!(
+        "Triggered rolling restart of {} {}/{}",
+        kind, namespace, name
+    );
+
+    state
+        .audit_recorder
+        .record(
+            AuditEntry::new(
+                AdminAction::Other("node_restart".to_string()),
+                identity.subject.clone(),
+                &name,
+                namespace.clone(),
+                Some(&format!("{{\"restartedAt\":\"{restarted_at}\"}}")),
+            )
+            .with_metadata(serde_json::json!({
+                "authType": identity.auth_type,
+                "groups": identity.groups,
+            })),
+        )
+        .await;
+
+    Ok(Json(NodeRestartResponse {
+        name,
+        namespace,
+        workload_kind: kind.to_string(),
+        restarted_at,
+    }))
+}
+
 /// Set the operator log level dynamically
 #[instrument(skip(state), fields(node_name = "-", namespace = %state.operator_namespace, reconcile_id = "-"))]
 pub async fn set_log_level(
@@ -394,3 +513,34 @@ pub async fn compliance_report(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_patch_bumps_pod_template_annotation() {
+        let patch = restart_patch("2026-01-01T00:00:00+00:00");
+        assert_eq!(
+            patch["spec"]["template"]["metadata"]["annotations"][RESTARTED_AT_ANNOTATION],
+            "2026-01-01T00:00:00+00:00"
+        );
+        assert!(
+            patch["metadata"].is_null(),
+            "only the pod template is patched"
+        );
+    }
+
+    #[test]
+    fn test_restart_response_serialization() {
+        let resp = NodeRestartResponse {
+            name: "validator-1".to_string(),
+            namespace: "stellar".to_string(),
+            workload_kind: "StatefulSet".to_string(),
+            restarted_at: "2026-01-01T00:00:00+00:00".to_string(),
+        };
+        let json = serde_json::to_value(&resp).unwrap();
+        assert_eq!(json["workloadKind"], "StatefulSet");
+        assert_eq!(json["restartedAt"], "2026-01-01T00:00:00+00:00");
+    }
+}
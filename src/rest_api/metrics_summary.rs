@@ -0,0 +1,289 @@
+//! JSON summary of operator metrics
+//!
+//! Custom dashboards that do not scrape Prometheus can poll this endpoint for
+//! a compact view of the operator's own metrics registry.
+//!
+//! Endpoints:
+//! - GET /status/metrics - Reconcile counts, error counts by kind and per-node ledger/lag
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::Json;
+use serde::Serialize;
+use tracing::{instrument, warn};
+
+const RECONCILE_DURATION_METRIC: &str = "stellar_reconcile_duration_seconds";
+const RECONCILE_ERRORS_METRIC: &str = "stellar_reconcile_errors";
+const LEDGER_SEQUENCE_METRIC: &str = "stellar_node_ledger_sequence";
+const INGESTION_LAG_METRIC: &str = "stellar_node_ingestion_lag";
+
+/// Reconcile totals for a single controller
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileSummary {
+    pub controller: String,
+    pub count: u64,
+    pub duration_seconds_sum: f64,
+}
+
+/// Reconcile error count for a controller and error kind
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCount {
+    pub controller: String,
+    pub kind: String,
+    pub count: u64,
+}
+
+/// Latest ledger and lag reported for a single node
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeMetricsSummary {
+    pub namespace: String,
+    pub name: String,
+    pub node_type: String,
+    pub network: String,
+    pub ledger_sequence: Option<i64>,
+    pub ingestion_lag: Option<i64>,
+}
+
+/// Response for `GET /status/metrics`
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSummary {
+    pub timestamp: i64,
+    pub total_reconciles: u64,
+    pub total_errors: u64,
+    pub reconciles: Vec<ReconcileSummary>,
+    pub errors: Vec<ErrorCount>,
+    pub nodes: Vec<NodeMetricsSummary>,
+}
+
+/// A single sample line from the Prometheus text exposition format
+#[derive(Debug, Clone, PartialEq)]
+struct Sample {
+    name: String,
+    labels: HashMap<String, String>,
+    value: f64,
+}
+
+impl Sample {
+    fn label(&self, key: &str) -> String {
+        self.labels.get(key).cloned().unwrap_or_default()
+    }
+}
+
+/// Parse a `key="value",...` label block, honouring escaped characters.
+fn parse_labels(block: &str) -> Option<HashMap<String, String>> {
+    let mut labels = HashMap::new();
+    let mut chars = block.chars().peekable();
+
+    loop {
+        while matches!(chars.peek(), Some(',') | Some(' ')) {
+            chars.next();
+        }
+        if chars.peek().is_none() {
+            return Some(labels);
+        }
+
+        let key: String = chars.by_ref().take_while(|c| *c != '=').collect();
+        if chars.next() != Some('"') {
+            return None;
+        }
+
+        let mut value = String::new();
+        loop {
+            match chars.next()? {
+                '\\' => match chars.next()? {
+                    'n' => value.push('\n'),
+                    other => value.push(other),
+                },
+                '"' => break,
+                c => value.push(c),
+            }
+        }
+        labels.insert(key.trim().to_string(), value);
+    }
+}
+
+/// Parse one non-comment line of the text exposition format.
+fn parse_sample(line: &str) -> Option<Sample> {
+    let (name, labels, rest) = match line.find('{') {
+        Some(open) => {
+            let close = line.rfind('}')?;
+            (
+                &line[..open],
+                parse_labels(&line[open + 1..close])?,
+                &line[close + 1..],
+            )
+        }
+        None => {
+            let (name, rest) = line.split_once(' ')?;
+            (name, HashMap::new(), rest)
+        }
+    };
+
+    let value = rest.split_whitespace().next()?.parse().ok()?;
+    Some(Sample {
+        name: name.to_string(),
+        labels,
+        value,
+    })
+}
+
+/// Build a [`MetricsSummary`] from Prometheus text exposition output.
+pub fn summarize(text: &str, timestamp: i64) -> MetricsSummary {
+    let mut reconciles: BTreeMap<String, ReconcileSummary> = BTreeMap::new();
+    let mut errors: BTreeMap<(String, String), u64> = BTreeMap::new();
+    let mut nodes: BTreeMap<(String, String), NodeMetricsSummary> = BTreeMap::new();
+
+    let samples = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(parse_sample);
+
+    for sample in samples {
+        if let Some(suffix) = sample.name.strip_prefix(RECONCILE_DURATION_METRIC) {
+            let controller = sample.label("controller");
+            let entry = reconciles
+                .entry(controller.clone())
+                .or_insert_with(|| ReconcileSummary {
+                    controller,
+                    count: 0,
+                    duration_seconds_sum: 0.0,
+                });
+            match suffix {
+                "_count" => entry.count = sample.value as u64,
+                "_sum" => entry.duration_seconds_sum = sample.value,
+                _ => {}
+            }
+        } else if sample.name.trim_end_matches("_total") == RECONCILE_ERRORS_METRIC {
+            *errors
+                .entry((sample.label("controller"), sample.label("kind")))
+                .or_default() += sample.value as u64;
+        } else if sample.name == LEDGER_SEQUENCE_METRIC || sample.name == INGESTION_LAG_METRIC {
+            let key = (sample.label("namespace"), sample.label("name"));
+            let entry = nodes
+                .entry(key.clone())
+                .or_insert_with(|| NodeMetricsSummary {
+                    namespace: key.0,
+                    name: key.1,
+                    node_type: sample.label("node_type"),
+                    network: sample.label("network"),
+                    ..Default::default()
+                });
+            if sample.name == LEDGER_SEQUENCE_METRIC {
+                entry.ledger_sequence = Some(sample.value as i64);
+            } else {
+                entry.ingestion_lag = Some(sample.value as i64);
+            }
+        }
+    }
+
+    let reconciles: Vec<ReconcileSummary> = reconciles.into_values().collect();
+    let errors: Vec<ErrorCount> = errors
+        .into_iter()
+        .map(|((controller, kind), count)| ErrorCount {
+            controller,
+            kind,
+            count,
+        })
+        .collect();
+
+    MetricsSummary {
+        timestamp,
+        total_reconciles: reconciles.iter().map(|r| r.count).sum(),
+        total_errors: errors.iter().map(|e| e.count).sum(),
+        reconciles,
+        errors,
+        nodes: nodes.into_values().collect(),
+    }
+}
+
+/// Get a JSON summary of the operator's Prometheus metrics
+#[instrument(fields(node_name = "-", namespace = "-", reconcile_id = "-"))]
+pub async fn get_metrics_summary() -> Json<MetricsSummary> {
+    use prometheus_client::encoding::text::encode;
+
+    let mut buffer = String::new();
+    if let Err(e) = encode(&mut buffer, &crate::controller::metrics::REGISTRY) {
+        warn!("Failed to encode metrics registry: {}", e);
+    }
+    Json(summarize(&buffer, chrono::Utc::now().timestamp()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPOSITION: &str = r#"# HELP stellar_reconcile_duration_seconds Duration of reconcile loops in seconds.
+# TYPE stellar_reconcile_duration_seconds histogram
+stellar_reconcile_duration_seconds_sum{controller="stellarnode"} 4.5
+stellar_reconcile_duration_seconds_count{controller="stellarnode"} 12
+stellar_reconcile_duration_seconds_bucket{le="0.001",controller="stellarnode"} 0
+# TYPE stellar_reconcile_errors_total counter
+stellar_reconcile_errors_total_total{controller="stellarnode",kind="kube"} 3
+stellar_reconcile_errors_total_total{controller="stellarnode",kind="validation"} 1
+# TYPE stellar_node_ledger_sequence gauge
+stellar_node_ledger_sequence{namespace="stellar",name="validator-1",node_type="validator",network="testnet",hardware_generation="unknown"} 49500000
+stellar_node_ingestion_lag{namespace="stellar",name="validator-1",node_type="validator",network="testnet",hardware_generation="unknown"} 2
+# EOF
+"#;
+
+    #[test]
+    fn test_parse_sample_with_escaped_label() {
+        let sample = parse_sample(r#"metric{a="x\"y",b="z"} 7"#).unwrap();
+        assert_eq!(sample.name, "metric");
+        assert_eq!(sample.label("a"), "x\"y");
+        assert_eq!(sample.label("b"), "z");
+        assert_eq!(sample.value, 7.0);
+    }
+
+    #[test]
+    fn test_parse_sample_without_labels() {
+        let sample = parse_sample("up 1").unwrap();
+        assert_eq!(sample.name, "up");
+        assert!(sample.labels.is_empty());
+        assert_eq!(sample.value, 1.0);
+    }
+
+    #[test]
+    fn test_summarize_collects_reconciles_errors_and_nodes() {
+        let summary = summarize(EXPOSITION, 1_700_000_000);
+
+        assert_eq!(summary.total_reconciles, 12);
+        assert_eq!(summary.total_errors, 4);
+        assert_eq!(summary.reconciles[0].duration_seconds_sum, 4.5);
+        assert_eq!(summary.errors.len(), 2);
+        assert_eq!(summary.errors[0].kind, "kube");
+        assert_eq!(summary.nodes.len(), 1);
+        assert_eq!(summary.nodes[0].ledger_sequence, Some(49_500_000));
+        assert_eq!(summary.nodes[0].ingestion_lag, Some(2));
+    }
+
+    #[test]
+    fn test_summary_json_shape() {
+        let json = serde_json::to_value(summarize(EXPOSITION, 1_700_000_000)).unwrap();
+
+        assert_eq!(json["timestamp"], 1_700_000_000);
+        assert_eq!(json["totalReconciles"], 12);
+        assert_eq!(json["totalErrors"], 4);
+        assert_eq!(json["reconciles"][0]["controller"], "stellarnode");
+        assert_eq!(json["reconciles"][0]["durationSecondsSum"], 4.5);
+        assert_eq!(json["errors"][1]["kind"], "validation");
+        assert_eq!(json["errors"][1]["count"], 1);
+        assert_eq!(json["nodes"][0]["nodeType"], "validator");
+        assert_eq!(json["nodes"][0]["ledgerSequence"], 49_500_000);
+        assert_eq!(json["nodes"][0]["ingestionLag"], 2);
+    }
+
+    #[test]
+    fn test_summarize_empty_registry() {
+        let summary = summarize("# EOF\n", 0);
+        assert_eq!(summary.total_reconciles, 0);
+        assert!(summary.reconciles.is_empty());
+        assert!(summary.errors.is_empty());
+        assert!(summary.nodes.is_empty());
+    }
+}
@@ -29,7 +29,10 @@
 //! - `GET /leader` - Leader election status
 //! - `GET /api/v1/nodes` - List all StellarNodes
 //! - `GET /api/v1/nodes/:namespace/:name` - Get specific StellarNode
+//! - `GET /api/v1/nodes/events` - Server-Sent Events stream of node status transitions
+//! - `POST /api/v1/nodes/:namespace/:name/restart` - Trigger a rolling restart of a node
 //! - `GET /metrics` - Prometheus metrics
+//! - `GET /status/metrics` - JSON summary of reconcile/error counts and per-node ledger/lag
 //! - `GET /` - Interactive dashboard
 //! - `POST /config/log-level` - Adjust log level dynamically
 //!
@@ -57,6 +60,9 @@ mod health_summary;
 mod horizon_cache_handlers;
 mod job_handlers;
 pub mod metrics_store;
+#[cfg(feature = "metrics")]
+mod metrics_summary;
+mod node_events;
 mod oidc;
 mod resource_optimization_handlers;
 mod scp_topology;
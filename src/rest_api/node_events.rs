@@ -0,0 +1,241 @@
+//! Live StellarNode status event stream
+//!
+//! Provides a Server-Sent Events endpoint for live dashboards:
+//!
+//! - `GET /api/v1/nodes/events` — streams status/phase transitions as they happen
+//!
+//! Each connection opens its own kube watch on StellarNode resources and emits an
+//! `event: status` frame whenever a node's derived phase or status message changes.
+//! The first observation of every node is also emitted so clients start from a
+//! complete picture, and deletions are reported with the `Deleted` phase.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use chrono::Utc;
+use futures::{future, Stream, StreamExt};
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::{Api, ResourceExt};
+use serde::Serialize;
+use tracing::{debug, instrument, warn};
+
+use crate::controller::ControllerState;
+use crate::crd::StellarNode;
+
+/// SSE event name used for node status frames
+pub const STATUS_EVENT: &str = "status";
+
+/// Phase reported when a StellarNode is removed from the cluster
+pub const DELETED_PHASE: &str = "Deleted";
+
+/// A single node status transition
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStatusEvent {
+    pub name: String,
+    pub namespace: String,
+    pub phase: String,
+    /// Phase last seen on this connection, `None` for the first observation
+    pub previous_phase: Option<String>,
+    pub message: Option<String>,
+    pub ready_replicas: i32,
+    pub ledger_sequence: Option<u64>,
+    /// RFC3339 timestamp of when the transition was observed
+    pub timestamp: String,
+}
+
+impl NodeStatusEvent {
+    /// Render the event as an SSE frame
+    pub fn to_sse_event(&self) -> Event {
+        Event::default()
+            .event(STATUS_EVENT)
+            .data(serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+/// Remembers the last reported status per node so only transitions are emitted
+#[derive(Debug, Default)]
+pub struct NodeStatusTracker {
+    last: HashMap<(String, String), (String, Option<String>)>,
+}
+
+impl NodeStatusTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the node's current status, returning an event if it changed.
+    pub fn observe(&mut self, node: &StellarNode) -> Option<NodeStatusEvent> {
+        let key = (node.namespace().unwrap_or_default(), node.name_any());
+        let status = node.status.clone().unwrap_or_default();
+        let phase = status.derive_phase_from_conditions();
+        let message = status.message.clone();
+
+        let previous = self.last.get(&key);
+        if previous == Some(&(phase.clone(), message.clone())) {
+            return None;
+        }
+        let previous_phase = previous.map(|(p, _)| p.clone());
+        self.last
+            .insert(key.clone(), (phase.clone(), message.clone()));
+
+        Some(NodeStatusEvent {
+            namespace: key.0,
+            name: key.1,
+            phase,
+            previous_phase,
+            message,
+            ready_replicas: status.ready_replicas,
+            ledger_sequence: status.ledger_sequence,
+            timestamp: Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Forget a deleted node, returning a `Deleted` event.
+    pub fn remove(&mut self, node: &StellarNode) -> NodeStatusEvent {
+        let key = (node.namespace().unwrap_or_default(), node.name_any());
+        let previous_phase = self.last.remove(&key).map(|(p, _)| p);
+
+        NodeStatusEvent {
+            namespace: key.0,
+            name: key.1,
+            phase: DELETED_PHASE.to_string(),
+            previous_phase,
+            message: None,
+            ready_replicas: 0,
+            ledger_sequence: None,
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Turn a StellarNode watch stream into SSE status frames.
+pub fn status_events<S>(watch: S) -> impl Stream<Item = Result<Event, Infallible>>
+where
+    S: Stream<Item = Result<watcher::Event<StellarNode>, watcher::Error>>,
+{
+    let mut tracker = NodeStatusTracker::new();
+
+    watch.filter_map(move |event| {
+        let status = match event {
+            Ok(watcher::Event::Apply(node)) | Ok(watcher::Event::InitApply(node)) => {
+                tracker.observe(&node)
+            }
+            Ok(watcher::Event::Delete(node)) => Some(tracker.remove(&node)),
+            Ok(watcher::Event::Init) | Ok(watcher::Event::InitDone) => None,
+            Err(e) => {
+                warn!("StellarNode status watch error: {}", e);
+                None
+            }
+        };
+        future::ready(status.map(|s| Ok(s.to_sse_event())))
+    })
+}
+
+/// `GET /api/v1/nodes/events`
+#[instrument(
+    skip(state),
+    fields(node_name = "-", namespace = "-", reconcile_id = "-")
+)]
+pub async fn node_events(
+    State(state): State<Arc<ControllerState>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    debug!("Opening StellarNode status event stream");
+    let api: Api<StellarNode> = Api::all(state.client.clone());
+    let watch = watcher(api, watcher::Config::default()).default_backoff();
+
+    // Keep the open-watch gauge raised for as long as the client stays connected
+    #[cfg(feature = "metrics")]
+    let watch = {
+        let guard = crate::controller::metrics::track_watch_streams(1);
+        watch.map(move |event| {
+            let _held = &guard;
+            event
+        })
+    };
+
+    Sse::new(status_events(watch)).keep_alive(KeepAlive::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::{Condition, StellarNodeSpec, StellarNodeStatus};
+    use axum::response::IntoResponse;
+
+    fn node_with_status(ready: bool) -> StellarNode {
+        let mut node = StellarNode::new("validator-1", StellarNodeSpec::default());
+        node.metadata.namespace = Some("stellar".to_string());
+        node.status = Some(StellarNodeStatus {
+            conditions: vec![Condition::ready(ready, "PodsPending", "waiting")],
+            ready_replicas: i32::from(ready),
+            replicas: 1,
+            ..Default::default()
+        });
+        node
+    }
+
+    async fn body_of(events: Vec<Result<watcher::Event<StellarNode>, watcher::Error>>) -> String {
+        let response = Sse::new(status_events(futures::stream::iter(events))).into_response();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_tracker_only_emits_on_change() {
+        let mut tracker = NodeStatusTracker::new();
+        let pending = node_with_status(false);
+
+        let first = tracker.observe(&pending).unwrap();
+        assert_eq!(first.phase, "Pending");
+        assert_eq!(first.previous_phase, None);
+        assert!(tracker.observe(&pending).is_none());
+
+        let ready = tracker.observe(&node_with_status(true)).unwrap();
+        assert_eq!(ready.phase, "Ready");
+        assert_eq!(ready.previous_phase.as_deref(), Some("Pending"));
+    }
+
+    #[test]
+    fn test_tracker_reports_deletion() {
+        let mut tracker = NodeStatusTracker::new();
+        let node = node_with_status(true);
+        tracker.observe(&node);
+
+        let deleted = tracker.remove(&node);
+        assert_eq!(deleted.phase, DELETED_PHASE);
+        assert_eq!(deleted.previous_phase.as_deref(), Some("Ready"));
+    }
+
+    #[tokio::test]
+    async fn test_status_change_produces_sse_frame() {
+        let body = body_of(vec![
+            Ok(watcher::Event::InitApply(node_with_status(false))),
+            Ok(watcher::Event::InitDone),
+            Ok(watcher::Event::Apply(node_with_status(true))),
+        ])
+        .await;
+
+        let frames: Vec<&str> = body.split("\n\n").filter(|f| !f.is_empty()).collect();
+        assert_eq!(frames.len(), 2);
+        assert!(frames[1].contains("event: status"));
+        assert!(frames[1].contains(r#""phase":"Ready""#));
+        assert!(frames[1].contains(r#""previousPhase":"Pending""#));
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_status_produces_no_frame() {
+        let body = body_of(vec![
+            Ok(watcher::Event::Apply(node_with_status(true))),
+            Ok(watcher::Event::Apply(node_with_status(true))),
+        ])
+        .await;
+
+        assert_eq!(body.matches("event: status").count(), 1);
+    }
+}
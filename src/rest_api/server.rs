@@ -29,6 +29,9 @@ use super::handlers;
 use super::health_summary;
 use super::horizon_cache_handlers;
 use super::job_handlers;
+#[cfg(feature = "metrics")]
+use super::metrics_summary;
+use super::node_events;
 use super::resource_optimization_handlers;
 use super::scp_topology;
 use super::stellar_metrics_server;
@@ -106,7 +109,13 @@ pub async fn run_server(
     let protected = Router::new()
         .route("/leader", get(handlers::leader_status))
         .route("/api/v1/nodes", get(handlers::list_nodes))
+        .route("/api/v1/nodes/events", get(node_events::node_events))
         .route("/api/v1/nodes/:namespace/:name", get(handlers::get_node))
+        .route(
+            "/api/v1/nodes/:namespace/:name/restart",
+            axum::routing::post(handlers::restart_node)
+                .route_layer(middleware::from_fn(auth::api_admin)),
+        )
         // Health summary API (Issue #552)
         .route("/v1/health/summary", get(health_summary::get_health_summary))
         .route("/v1/health/nodes", get(health_summary::get_node_health_status))
@@ -224,6 +233,7 @@ pub async fn run_server(
     #[cfg(feature = "metrics")]
     {
         app = app.route("/metrics", get(metrics_handler));
+        app = app.route("/status/metrics", get(metrics_summary::get_metrics_summary));
     }
 
     // Default to 9090 to match Prometheus scrape conventions and project docs.
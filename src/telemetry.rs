@@ -17,11 +17,21 @@ use opentelemetry_sdk::export::trace::SpanData;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use opentelemetry_sdk::resource::Resource;
 use opentelemetry_sdk::runtime;
-use opentelemetry_sdk::trace::{Config, Sampler, SpanProcessor};
+use opentelemetry_sdk::trace::{Config, SpanProcessor};
 use std::env;
 use tracing_opentelemetry::OtelData;
 use tracing_subscriber::{registry::LookupSpan, Layer};
 
+/// Span attributes replaced with `[REDACTED]` before export, by the operator
+/// and by the OpenTelemetry Collector sidecars it injects into node pods
+pub const SCRUBBED_SPAN_ATTRIBUTES: &[&str] = &[
+    "net.peer.ip",
+    "net.host.ip",
+    "http.client_ip",
+    "k8s.cluster.name",
+    "host.name",
+];
+
 /// A span processor that scrubs sensitive information from span attributes
 #[derive(Debug)]
 struct ScrubbingProcessor {
@@ -37,13 +47,7 @@ impl ScrubbingProcessor {
 
     fn scrub_attributes(&self, attributes: &mut [KeyValue]) {
         for kv in attributes.iter_mut() {
-            let key = kv.key.as_str();
-            if key == "net.peer.ip"
-                || key == "net.host.ip"
-                || key == "http.client_ip"
-                || key == "k8s.cluster.name"
-                || key == "host.name"
-            {
+            if SCRUBBED_SPAN_ATTRIBUTES.contains(&kv.key.as_str()) {
                 kv.value = opentelemetry::Value::String("[REDACTED]".into());
             }
         }
@@ -135,6 +139,21 @@ where
     OtelTraceIdLayer
 }
 
+// ---------------------------------------------------------------------------
+// Sampling and batching
+// ---------------------------------------------------------------------------
+
+/// Tracer settings: the operator's resource, with the sampler the SDK reads
+/// from `OTEL_TRACES_SAMPLER` and `OTEL_TRACES_SAMPLER_ARG` (parent-based,
+/// sampling every trace, when unset).
+///
+/// Span batching is left to the SDK as well: `BatchSpanProcessor::builder`
+/// reads `OTEL_BSP_MAX_QUEUE_SIZE`, `OTEL_BSP_MAX_EXPORT_BATCH_SIZE`,
+/// `OTEL_BSP_SCHEDULE_DELAY` and `OTEL_BSP_EXPORT_TIMEOUT`.
+fn trace_config(resource: Resource) -> Config {
+    Config::default().with_resource(resource)
+}
+
 /// Initialize OpenTelemetry tracer and tracing subscriber
 pub fn init_telemetry<S>(_subscriber: &S) -> Box<dyn Layer<S> + Send + Sync>
 where
@@ -170,11 +189,7 @@ where
     let scrubbing_processor = ScrubbingProcessor::new(Box::new(batch_processor));
 
     let provider = opentelemetry_sdk::trace::TracerProvider::builder()
-        .with_config(
-            Config::default()
-                .with_resource(resource)
-                .with_sampler(Sampler::AlwaysOn),
-        )
+        .with_config(trace_config(resource))
         .with_span_processor(scrubbing_processor)
         .build();
 
@@ -265,4 +280,35 @@ mod tests {
             opentelemetry::Value::String("[REDACTED]".into())
         );
     }
+
+    #[test]
+    fn test_sampling_and_batching_follow_otel_env() {
+        use opentelemetry_sdk::trace::BatchConfig;
+
+        // Unset, every trace is sampled
+        let config = trace_config(Resource::empty());
+        assert_eq!(format!("{:?}", config.sampler), "ParentBased(AlwaysOn)");
+
+        let vars = [
+            ("OTEL_TRACES_SAMPLER", "parentbased_traceidratio"),
+            ("OTEL_TRACES_SAMPLER_ARG", "0.25"),
+            ("OTEL_BSP_MAX_QUEUE_SIZE", "8192"),
+            ("OTEL_BSP_SCHEDULE_DELAY", "1000"),
+        ];
+        for (key, value) in vars {
+            env::set_var(key, value);
+        }
+        let config = trace_config(Resource::empty());
+        let batch = format!("{:?}", BatchConfig::default());
+        for (key, _) in vars {
+            env::remove_var(key);
+        }
+
+        assert_eq!(
+            format!("{:?}", config.sampler),
+            "ParentBased(TraceIdRatioBased(0.25))"
+        );
+        assert!(batch.contains("max_queue_size: 8192"), "{batch}");
+        assert!(batch.contains("scheduled_delay: 1s"), "{batch}");
+    }
 }
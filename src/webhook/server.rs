@@ -313,6 +313,8 @@ impl WebhookServer {
                 // Collect image pinning warnings
                 if let Ok(node) = serde_json::from_value::<StellarNode>(object.clone()) {
                     warnings.extend(check_image_pinning(&node.spec));
+                    warnings.extend(node.spec.retention_backup_conflict());
+                    warnings.extend(node.spec.host_network_warning());
                 }
 
                 if let Some(mut builtin) = validate_spec_builtin(object) {
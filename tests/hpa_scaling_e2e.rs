@@ -37,6 +37,7 @@ async fn mock_controller_state() -> Option<Arc<ControllerState>> {
         watch_namespace: None,
         mtls_config: None,
         dry_run: false,
+        disable_finalizers: false,
         retry_budget_retriable_secs: 10,
         retry_budget_nonretriable_secs: 60,
         retry_budget_max_attempts: 3,
@@ -0,0 +1,221 @@
+//! Full reconcile flows against the in-memory Kubernetes API.
+//!
+//! Every kube API call goes to `controller::test_harness::in_memory_client`,
+//! so these tests drive `reconcile_for_fuzz` through create, update and
+//! delete without a cluster.
+//!
+//! Run with: `cargo test -p stellar-k8s --features in-memory-kube --test in_memory_reconcile`
+
+#![cfg(feature = "in-memory-kube")]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use k8s_openapi::api::apps::v1::StatefulSet;
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::api::{Api, DeleteParams, Patch, PatchParams, PostParams};
+use kube::{Client, ResourceExt};
+
+use stellar_k8s::controller::test_harness::{controller_state, in_memory_client, RecordedRequest};
+use stellar_k8s::controller::{reconcile_for_fuzz, ControllerState, STELLAR_NODE_FINALIZER};
+use stellar_k8s::crd::{NodeType, StellarNode, StellarNodeSpec, ValidatorConfig};
+
+const NAMESPACE: &str = "stellar";
+const NAME: &str = "validator-1";
+
+fn quorum_set(validators: &[&str]) -> String {
+    let validators: Vec<String> = validators.iter().map(|v| format!("\"{v}\"")).collect();
+    format!(
+        "[QUORUM_SET]\nTHRESHOLD_PERCENT=67\nVALIDATORS=[{}]\n",
+        validators.join(", ")
+    )
+}
+
+fn validator() -> StellarNode {
+    let mut node = StellarNode::new(
+        NAME,
+        StellarNodeSpec {
+            node_type: NodeType::Validator,
+            validator_config: Some(ValidatorConfig {
+                seed_secret_ref: "validator-seed".to_string(),
+                quorum_set: Some(quorum_set(&["GA", "GB", "GC"])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    );
+    node.metadata.namespace = Some(NAMESPACE.to_string());
+    node.metadata.uid = Some("in-memory-uid-1".to_string());
+    node
+}
+
+/// Reconcile the node as currently stored, as the controller would on a watch event
+async fn reconcile_stored(client: &Client, ctx: &Arc<ControllerState>) -> String {
+    let api: Api<StellarNode> = Api::namespaced(client.clone(), NAMESPACE);
+    let node = api.get(NAME).await.expect("node should be stored");
+    format!(
+        "{:?}",
+        reconcile_for_fuzz(Arc::new(node), ctx.clone()).await
+    )
+}
+
+async fn stored_core_config(client: &Client) -> Option<String> {
+    let api: Api<ConfigMap> = Api::namespaced(client.clone(), NAMESPACE);
+    api.get_opt(&format!("{NAME}-config"))
+        .await
+        .unwrap()
+        .and_then(|cm| cm.data)
+        .and_then(|mut data| data.remove("stellar-core.cfg"))
+}
+
+#[tokio::test]
+async fn validator_create_update_delete() {
+    let (client, requests) = in_memory_client();
+    let ctx = controller_state(client.clone());
+    let nodes: Api<StellarNode> = Api::namespaced(client.clone(), NAMESPACE);
+    let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), NAMESPACE);
+    nodes
+        .create(&PostParams::default(), &validator())
+        .await
+        .unwrap();
+
+    // Create: workload and config are applied and the finalizer is added
+    let result = reconcile_stored(&client, &ctx).await;
+    assert!(
+        statefulsets.get_opt(NAME).await.unwrap().is_some(),
+        "no StatefulSet after first reconcile: {result}"
+    );
+    let config = stored_core_config(&client).await.unwrap();
+    assert!(config.contains("\"GC\""), "{config}");
+    let node = nodes.get(NAME).await.unwrap();
+    assert!(node
+        .finalizers()
+        .iter()
+        .any(|f| f == STELLAR_NODE_FINALIZER));
+
+    // Update: a safe quorum set change reaches the ConfigMap
+    nodes
+        .patch(
+            NAME,
+            &PatchParams::default(),
+            &Patch::Merge(serde_json::json!({
+                "spec": { "validatorConfig": { "quorumSet": quorum_set(&["GA", "GB", "GC", "GD"]) } }
+            })),
+        )
+        .await
+        .unwrap();
+    let result = reconcile_stored(&client, &ctx).await;
+    let config = stored_core_config(&client).await.unwrap();
+    assert!(config.contains("\"GD\""), "{result}\n{config}");
+
+    // Delete: held by the finalizer until cleanup removes the owned resources
+    nodes.delete(NAME, &DeleteParams::default()).await.unwrap();
+    assert!(nodes
+        .get(NAME)
+        .await
+        .unwrap()
+        .metadata
+        .deletion_timestamp
+        .is_some());
+    let result = reconcile_stored(&client, &ctx).await;
+    assert!(
+        nodes.get_opt(NAME).await.unwrap().is_none(),
+        "node still stored after cleanup: {result}"
+    );
+    assert!(statefulsets.get_opt(NAME).await.unwrap().is_none());
+    assert_eq!(stored_core_config(&client).await, None);
+
+    let requests = requests.lock().unwrap();
+    assert!(
+        requests
+            .iter()
+            .any(|r| r.method == http::Method::DELETE
+                && r.path.ends_with("/statefulsets/validator-1"))
+    );
+}
+
+/// Object a mutating request wrote, or `None` for reads and Events
+fn written_object(request: &RecordedRequest) -> Option<String> {
+    if request.method == http::Method::GET || request.path.contains("/events") {
+        return None;
+    }
+    let path = ["/status", "/scale"]
+        .iter()
+        .find_map(|sub| request.path.strip_suffix(sub))
+        .unwrap_or(&request.path);
+    if request.method == http::Method::POST {
+        let name = request.body["metadata"]["name"].as_str()?;
+        return Some(format!("{path}/{name}"));
+    }
+    Some(path.to_string())
+}
+
+/// Stored objects at `paths`, without the `status` each reconcile reports into
+async fn snapshot(
+    client: &Client,
+    paths: &BTreeSet<String>,
+) -> BTreeMap<String, Option<serde_json::Value>> {
+    let mut objects = BTreeMap::new();
+    for path in paths {
+        let request = http::Request::get(path.as_str()).body(Vec::new()).unwrap();
+        let object = client
+            .request::<serde_json::Value>(request)
+            .await
+            .ok()
+            .map(|mut object| {
+                if let Some(fields) = object.as_object_mut() {
+                    fields.remove("status");
+                }
+                object
+            });
+        objects.insert(path.clone(), object);
+    }
+    objects
+}
+
+#[tokio::test]
+async fn second_reconcile_of_unchanged_validator_writes_nothing() {
+    let (client, requests) = in_memory_client();
+    let ctx = controller_state(client.clone());
+    let nodes: Api<StellarNode> = Api::namespaced(client.clone(), NAMESPACE);
+    let statefulsets: Api<StatefulSet> = Api::namespaced(client.clone(), NAMESPACE);
+    nodes
+        .create(&PostParams::default(), &validator())
+        .await
+        .unwrap();
+
+    let result = reconcile_stored(&client, &ctx).await;
+    assert!(
+        statefulsets.get_opt(NAME).await.unwrap().is_some(),
+        "no StatefulSet after first reconcile: {result}"
+    );
+    let first: BTreeSet<String> = std::mem::take(&mut *requests.lock().unwrap())
+        .iter()
+        .filter_map(written_object)
+        .collect();
+    let before = snapshot(&client, &first).await;
+    requests.lock().unwrap().clear();
+
+    let result = reconcile_stored(&client, &ctx).await;
+    let second = std::mem::take(&mut *requests.lock().unwrap());
+    for request in &second {
+        let Some(object) = written_object(request) else {
+            continue;
+        };
+        assert!(
+            request.method == http::Method::PATCH,
+            "second reconcile sent {} {}: {result}",
+            request.method,
+            request.path
+        );
+        assert!(
+            first.contains(&object),
+            "second reconcile wrote {object}, which the first did not: {result}"
+        );
+    }
+    assert_eq!(
+        snapshot(&client, &first).await,
+        before,
+        "second reconcile changed stored objects: {result}"
+    );
+}
@@ -60,6 +60,8 @@ fn default_storage() -> StorageConfig {
     StorageConfig {
         mode: Default::default(),
         storage_class: "standard".to_string(),
+        tier: None,
+        access_modes: vec![],
         size: "100Gi".to_string(),
         retention_policy: Default::default(),
         annotations: None,
@@ -83,6 +85,10 @@ fn base_validator_spec() -> StellarNodeSpec {
             quorum_set: None,
             enable_history_archive: false,
             history_archive_urls: vec![],
+            archive_lag_threshold: None,
+            archive_rotation: None,
+            bucket_list_db: None,
+            archive_server: None,
             catchup_complete: false,
             key_source: Default::default(),
             kms_config: None,
@@ -155,6 +161,10 @@ fn base_horizon_spec() -> StellarNodeSpec {
             ingest_workers: 1,
             enable_experimental_ingestion: false,
             auto_migration: false,
+            migration_max_attempts: None,
+            migration_backoff_seconds: None,
+            admin_port: None,
+            per_hour_rate_limit: None,
         }),
         soroban_config: None,
         replicas: 2,
@@ -328,8 +338,8 @@ proptest! {
 
 /// Reconcile with a failing client must not panic and must converge to Err or Ok(Action).
 /// Ignored by default: creating a kube Client from a fake URL triggers TLS/crypto setup that
-/// may require process-level crypto provider. Run with `--ignored` against a real cluster or
-/// use a mock client (e.g. tower-test) for full reconcile fuzzing.
+/// may require process-level crypto provider. Run with `--ignored` against a real cluster, or
+/// enable `in-memory-kube` for the cluster-free variant below.
 #[tokio::test]
 #[ignore = "requires real cluster or mock client; run with --ignored when testing reconcile convergence"]
 async fn reconcile_with_failing_client_never_panics_and_converges() {
@@ -343,6 +353,7 @@ async fn reconcile_with_failing_client_never_panics_and_converges() {
         operator_namespace: "default".to_string(),
         mtls_config: None,
         dry_run: false,
+        disable_finalizers: false,
         is_leader: Arc::new(AtomicBool::new(true)),
         watch_namespace: None,
         event_reporter: kube::runtime::events::Reporter {
@@ -385,3 +396,22 @@ async fn reconcile_with_failing_client_never_panics_and_converges() {
     let result = reconcile_for_fuzz(Arc::new(node), ctx).await;
     assert!(result.is_ok() || result.is_err());
 }
+
+/// Same convergence check as above, with every kube API call answered by the
+/// in-memory store instead of a cluster.
+#[cfg(feature = "in-memory-kube")]
+#[tokio::test]
+async fn reconcile_against_in_memory_api_never_panics_and_converges() {
+    use stellar_k8s::controller::test_harness::{controller_state, in_memory_client};
+
+    for spec in [
+        base_validator_spec(),
+        base_horizon_spec(),
+        base_soroban_spec(),
+    ] {
+        let (client, _requests) = in_memory_client();
+        let node = make_node(spec, "fuzz-node".to_string(), "default".to_string());
+        let result = reconcile_for_fuzz(Arc::new(node), controller_state(client)).await;
+        assert!(result.is_ok() || result.is_err());
+    }
+}